@@ -0,0 +1,33 @@
+//! Allocation counting shared by the benches that compare allocations of fused and
+//! unfused ops.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts the number of heap allocations made, and how many bytes they take.
+struct CountingAlloc;
+
+static NUM_ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static NUM_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCS.fetch_add(1, Ordering::Relaxed);
+        NUM_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// The number of heap allocations made so far, and how many bytes they took.
+pub fn counters() -> (usize, usize) {
+    (
+        NUM_ALLOCS.load(Ordering::Relaxed),
+        NUM_BYTES.load(Ordering::Relaxed),
+    )
+}
@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use dfdx::{losses, prelude::*};
+
+mod common;
+use common::counters;
+
+#[cfg(feature = "cuda")]
+type Dev = Cuda;
+
+#[cfg(not(feature = "cuda"))]
+type Dev = Cpu;
+
+type Dtype = f32;
+type InputShape = Rank2<1024, 1000>;
+
+fn main() {
+    println!("Benchmarking `softmax_cross_entropy` vs `cross_entropy_with_logits_loss`");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Input shape {}", std::any::type_name::<InputShape>());
+    println!();
+
+    let dev: Dev = Default::default();
+
+    loop {
+        let logits: Tensor<InputShape, Dtype, _> = dev.sample_normal();
+        let targets: Tensor<InputShape, Dtype, _> = dev.sample_normal().softmax::<Axis<1>>();
+
+        let (allocs, bytes) = counters();
+        let start = Instant::now();
+        let loss = losses::cross_entropy_with_logits_loss(logits.leaky_trace(), targets.clone());
+        let _ = loss.backward();
+        dev.synchronize();
+        let chain_dur = start.elapsed();
+        let (chain_allocs, chain_bytes) = (counters().0 - allocs, counters().1 - bytes);
+
+        let (allocs, bytes) = counters();
+        let start = Instant::now();
+        let loss = losses::softmax_cross_entropy(logits.leaky_trace(), targets.clone());
+        let _ = loss.backward();
+        dev.synchronize();
+        let fused_dur = start.elapsed();
+        let (fused_allocs, fused_bytes) = (counters().0 - allocs, counters().1 - bytes);
+
+        println!(
+            "chain={:?} ({} allocs, {} KiB) fused={:?} ({} allocs, {} KiB)",
+            chain_dur,
+            chain_allocs,
+            chain_bytes / 1024,
+            fused_dur,
+            fused_allocs,
+            fused_bytes / 1024
+        );
+    }
+}
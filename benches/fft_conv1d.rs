@@ -0,0 +1,61 @@
+use std::time::Instant;
+
+use dfdx::{prelude::*, tensor_ops::fft_conv1d};
+
+#[cfg(feature = "cuda")]
+type Dev = Cuda;
+
+#[cfg(not(feature = "cuda"))]
+type Dev = Cpu;
+
+type Dtype = f32;
+type SignalShape = Rank1<16384>;
+type KernelShape = Rank1<2048>;
+
+/// `out[i] = sum_k signal[i + k] * kernel[k]`, which takes `O(L * K)`
+fn direct_conv1d(signal: &[Dtype], kernel: &[Dtype]) -> Vec<Dtype> {
+    (0..signal.len() - kernel.len() + 1)
+        .map(|i| {
+            kernel
+                .iter()
+                .enumerate()
+                .map(|(k, w)| signal[i + k] * w)
+                .sum()
+        })
+        .collect()
+}
+
+fn main() {
+    println!("Benchmarking `fft_conv1d` against a direct convolution");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Signal shape {}", std::any::type_name::<SignalShape>());
+    println!("Kernel shape {}", std::any::type_name::<KernelShape>());
+    println!();
+
+    let dev: Dev = Default::default();
+
+    loop {
+        let signal: Tensor<SignalShape, Dtype, _> = dev.sample_normal();
+        let kernel: Tensor<KernelShape, Dtype, _> = dev.sample_normal();
+
+        let start = Instant::now();
+        let out = fft_conv1d(signal.leaky_trace(), kernel.clone());
+        let loss = out.square().mean();
+        dev.synchronize();
+        let fwd_dur = start.elapsed();
+
+        let start = Instant::now();
+        let _ = loss.backward();
+        dev.synchronize();
+        let bwd_dur = start.elapsed();
+
+        let start = Instant::now();
+        let _ = direct_conv1d(&signal.as_vec(), &kernel.as_vec());
+        let direct_dur = start.elapsed();
+        println!(
+            "fft fwd={:?} bwd={:?} | direct fwd={:?}",
+            fwd_dur, bwd_dur, direct_dur
+        );
+    }
+}
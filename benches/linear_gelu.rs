@@ -0,0 +1,51 @@
+use std::time::Instant;
+
+use dfdx::{prelude::*, tensor_ops::linear_gelu};
+
+mod common;
+use common::counters;
+
+#[cfg(feature = "cuda")]
+type Dev = Cuda;
+
+#[cfg(not(feature = "cuda"))]
+type Dev = Cpu;
+
+type Dtype = f32;
+type InputShape = Rank2<64, 512>;
+type Model = Linear<512, 2048>;
+
+fn main() {
+    println!("Benchmarking `linear_gelu` vs `Linear` then `gelu()`");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Input shape {}", std::any::type_name::<InputShape>());
+    println!("Model {}", std::any::type_name::<Model>());
+    println!();
+
+    let dev: Dev = Default::default();
+    let m = dev.build_module::<Model, Dtype>();
+
+    loop {
+        let x: Tensor<InputShape, Dtype, _> = dev.sample_normal();
+
+        let (allocs, bytes) = counters();
+        let start = Instant::now();
+        let y = m.forward(x.leaky_trace()).gelu();
+        let _ = y.sum().backward();
+        let unfused_dur = start.elapsed();
+        let (unfused_allocs, unfused_bytes) = (counters().0 - allocs, counters().1 - bytes);
+
+        let (allocs, bytes) = counters();
+        let start = Instant::now();
+        let y = linear_gelu(x.leaky_trace(), &m.weight, &m.bias);
+        let _ = y.sum().backward();
+        let fused_dur = start.elapsed();
+        let (fused_allocs, fused_bytes) = (counters().0 - allocs, counters().1 - bytes);
+
+        println!(
+            "unfused={:?} ({} allocs, {} bytes) fused={:?} ({} allocs, {} bytes)",
+            unfused_dur, unfused_allocs, unfused_bytes, fused_dur, fused_allocs, fused_bytes
+        );
+    }
+}
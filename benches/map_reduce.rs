@@ -0,0 +1,45 @@
+use std::time::Instant;
+
+use dfdx::prelude::*;
+
+mod common;
+use common::counters;
+
+// `map_reduce_last_dim` is only implemented for `Cpu`
+type Dev = Cpu;
+
+type Dtype = f32;
+type InputShape = Rank2<1024, 1000>;
+
+fn main() {
+    println!("Benchmarking `map_reduce_last_dim` vs `square().sum()`");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Input shape {}", std::any::type_name::<InputShape>());
+    println!();
+
+    let dev: Dev = Default::default();
+
+    loop {
+        let t: Tensor<InputShape, Dtype, _> = dev.sample_normal();
+
+        let (allocs, bytes) = counters();
+        let start = Instant::now();
+        let r = t.leaky_trace().square().sum::<Rank1<1024>, _>();
+        let _ = r.sum().backward();
+        let unfused_dur = start.elapsed();
+        let (unfused_allocs, unfused_bytes) = (counters().0 - allocs, counters().1 - bytes);
+
+        let (allocs, bytes) = counters();
+        let start = Instant::now();
+        let r = map_reduce_last_dim(t.leaky_trace(), |x| x * x, |x| 2.0 * x, Reduction::Sum);
+        let _ = r.sum().backward();
+        let fused_dur = start.elapsed();
+        let (fused_allocs, fused_bytes) = (counters().0 - allocs, counters().1 - bytes);
+
+        println!(
+            "unfused={:?} ({} allocs, {} bytes) fused={:?} ({} allocs, {} bytes)",
+            unfused_dur, unfused_allocs, unfused_bytes, fused_dur, fused_allocs, fused_bytes
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Instant;
+
+use dfdx::{data::OneHotEncode, losses, prelude::*};
+
+mod common;
+use common::counters;
+
+#[cfg(feature = "cuda")]
+type Dev = Cuda;
+
+#[cfg(not(feature = "cuda"))]
+type Dev = Cpu;
+
+type Dtype = f32;
+type InputShape = Rank2<1024, 1000>;
+
+fn main() {
+    println!("Benchmarking `sparse_cross_entropy` vs `softmax_cross_entropy` with one hot targets");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Input shape {}", std::any::type_name::<InputShape>());
+    println!();
+
+    let dev: Dev = Default::default();
+
+    loop {
+        let logits: Tensor<InputShape, Dtype, _> = dev.sample_normal();
+        let labels: [usize; 1024] = std::array::from_fn(|i| (i * 7) % 1000);
+
+        let (allocs, bytes) = counters();
+        let start = Instant::now();
+        let one_hot: Tensor<InputShape, Dtype, _> = dev.one_hot_encode(Const, labels);
+        let loss = losses::softmax_cross_entropy(logits.leaky_trace(), one_hot);
+        let _ = loss.backward();
+        dev.synchronize();
+        let dense_dur = start.elapsed();
+        let (dense_allocs, dense_bytes) = (counters().0 - allocs, counters().1 - bytes);
+
+        let (allocs, bytes) = counters();
+        let start = Instant::now();
+        let loss = losses::sparse_cross_entropy(logits.leaky_trace(), &labels);
+        let _ = loss.backward();
+        dev.synchronize();
+        let sparse_dur = start.elapsed();
+        let (sparse_allocs, sparse_bytes) = (counters().0 - allocs, counters().1 - bytes);
+
+        println!(
+            "dense={:?} ({} allocs, {} bytes) sparse={:?} ({} allocs, {} bytes)",
+            dense_dur, dense_allocs, dense_bytes, sparse_dur, sparse_allocs, sparse_bytes
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+use dfdx::prelude::*;
+
+#[cfg(feature = "cuda")]
+type Dev = Cuda;
+
+#[cfg(not(feature = "cuda"))]
+type Dev = Cpu;
+
+type Dtype = f32;
+
+/// Axis 2 has size 1, so reducing `Axes2<2, 3>` visits the exact same elements as reducing
+/// `Axis<3>`, but goes through the generic strided kernel.
+type InputShape = Rank4<16, 512, 1, 1024>;
+
+type OutputShape = Rank3<16, 512, 1>;
+
+fn main() {
+    println!("Benchmarking `sum` and `max` over the last dim, strided vs contiguous kernel");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Input shape {}", std::any::type_name::<InputShape>());
+    println!("cpu-par-reduce {}", cfg!(feature = "cpu-par-reduce"));
+    println!();
+
+    let dev: Dev = Default::default();
+
+    loop {
+        let t: Tensor<InputShape, Dtype, _> = dev.sample_normal();
+
+        let (strided_fwd, strided_bwd, strided) = time(&dev, || {
+            t.leaky_trace()
+                .sum::<Rank2<16, 512>, Axes2<2, 3>>()
+                .reshape::<OutputShape>()
+        });
+        let (contiguous_fwd, contiguous_bwd, contiguous) =
+            time(&dev, || t.leaky_trace().sum::<OutputShape, Axis<3>>());
+        // the contiguous kernel adds in a different order, so the results aren't bit identical
+        assert_close(&strided, &contiguous, 1e-3);
+        println!(
+            "sum strided fwd={:?} bwd={:?} | contiguous fwd={:?} bwd={:?}",
+            strided_fwd, strided_bwd, contiguous_fwd, contiguous_bwd
+        );
+
+        let (strided_fwd, strided_bwd, strided) = time(&dev, || {
+            t.leaky_trace()
+                .max::<Rank2<16, 512>, Axes2<2, 3>>()
+                .reshape::<OutputShape>()
+        });
+        let (contiguous_fwd, contiguous_bwd, contiguous) =
+            time(&dev, || t.leaky_trace().max::<OutputShape, Axis<3>>());
+        assert_eq!(strided, contiguous);
+        println!(
+            "max strided fwd={:?} bwd={:?} | contiguous fwd={:?} bwd={:?}",
+            strided_fwd, strided_bwd, contiguous_fwd, contiguous_bwd
+        );
+    }
+}
+
+fn time<F>(dev: &Dev, f: F) -> (Duration, Duration, Vec<Dtype>)
+where
+    F: FnOnce() -> Tensor<OutputShape, Dtype, Dev, OwnedTape<Dtype, Dev>>,
+{
+    let start = Instant::now();
+    let y = f();
+    dev.synchronize();
+    let fwd_dur = start.elapsed();
+    let values = y.as_vec();
+
+    let start = Instant::now();
+    let _ = y.sum().backward();
+    dev.synchronize();
+    let bwd_dur = start.elapsed();
+    (fwd_dur, bwd_dur, values)
+}
+
+fn assert_close(a: &[Dtype], b: &[Dtype], tolerance: Dtype) {
+    for (a, b) in a.iter().zip(b.iter()) {
+        assert!((a - b).abs() <= tolerance * a.abs().max(1.0), "{a} != {b}");
+    }
+}
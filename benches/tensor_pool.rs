@@ -0,0 +1,51 @@
+use std::time::{Duration, Instant};
+
+use dfdx::prelude::*;
+
+// the pool is only implemented for `Cpu`
+type Dev = Cpu;
+
+type Dtype = f32;
+
+/// Small enough that the allocator, not the op, dominates.
+type InputShape = Rank2<8, 16>;
+
+const ITERS: usize = 100_000;
+
+fn main() {
+    println!("Benchmarking allocations with the `TensorPool` disabled vs enabled");
+    println!("Device {}", std::any::type_name::<Dev>());
+    println!("Dtype {}", std::any::type_name::<Dtype>());
+    println!("Input shape {}", std::any::type_name::<InputShape>());
+    println!("Iterations {ITERS}");
+    println!();
+
+    let dev: Dev = Default::default();
+    let a: Tensor<InputShape, Dtype, _> = dev.sample_normal();
+
+    // the same work in both cases: while the pool is disabled, `recycle` just drops
+    let step = || {
+        let b = a.clone() * 2.0;
+        let c = b.clone() + a.clone();
+        dev.pool().recycle(b);
+        dev.pool().recycle(c);
+    };
+
+    loop {
+        dev.pool().disable();
+        let disabled = time(step);
+
+        dev.pool().enable();
+        let enabled = time(step);
+
+        println!("disabled={:?} enabled={:?}", disabled, enabled);
+    }
+}
+
+fn time<F: FnMut()>(mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        f();
+    }
+    start.elapsed()
+}
@@ -0,0 +1,118 @@
+use arrow_array::{types::ArrowPrimitiveType, Array, ArrowNativeTypeOp, PrimitiveArray};
+
+use crate::{
+    shapes::*,
+    tensor::{DeviceStorage, Tensor, TensorFromVec},
+};
+
+/// Copies [Apache Arrow](https://docs.rs/arrow) arrays into tensors.
+///
+/// Requires the `"arrow"` feature.
+pub trait FromArrow<E: Dtype + ArrowNativeTypeOp>: DeviceStorage + TensorFromVec<E> {
+    /// Copies the values of `array` into a tensor of shape `shape`, in row major order.
+    /// Returns an error if the length of `array` isn't the number of elements of `shape`,
+    /// or if `array` has any nulls.
+    ///
+    /// ```rust
+    /// # use dfdx::{prelude::*, data::FromArrow};
+    /// # use arrow_array::Float32Array;
+    /// # let dev: Cpu = Default::default();
+    /// let array = Float32Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    /// let t = dev.from_arrow(&array, (Const::<2>, Const::<3>)).unwrap();
+    /// assert_eq!(t.array(), [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_arrow<S: Shape, A: ArrowPrimitiveType<Native = E>>(
+        &self,
+        array: &PrimitiveArray<A>,
+        shape: S,
+    ) -> Result<Tensor<S, E, Self>, FromArrowError<Self::Err>> {
+        let num_elements = shape.num_elements();
+        if array.len() != num_elements {
+            return Err(FromArrowError::WrongNumElements {
+                expected: num_elements,
+                found: array.len(),
+            });
+        }
+        if array.null_count() > 0 {
+            return Err(FromArrowError::Nulls(array.null_count()));
+        }
+        self.try_tensor_from_vec(array.values().iter().copied().collect(), shape)
+            .map_err(FromArrowError::Device)
+    }
+}
+impl<E: Dtype + ArrowNativeTypeOp, D: DeviceStorage + TensorFromVec<E>> FromArrow<E> for D {}
+
+/// An error from [FromArrow::from_arrow].
+#[derive(Debug)]
+pub enum FromArrowError<Err> {
+    /// The array doesn't have one value per element of the shape.
+    WrongNumElements { expected: usize, found: usize },
+    /// The array has this many null values, which tensors can't represent.
+    Nulls(usize),
+    /// The device failed to create the tensor.
+    Device(Err),
+}
+
+impl<Err: std::fmt::Display> std::fmt::Display for FromArrowError<Err> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongNumElements { expected, found } => {
+                write!(fmt, "expected {expected} values, found {found}")
+            }
+            Self::Nulls(n) => write!(fmt, "array has {n} null values"),
+            Self::Device(err) => write!(fmt, "{err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Err: std::fmt::Debug + std::fmt::Display> std::error::Error for FromArrowError<Err> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tests::*};
+    use arrow_array::{Float32Array, Float64Array};
+
+    #[test]
+    fn test_from_arrow_2d() {
+        let dev: Cpu = Default::default();
+        let array = Float32Array::from((0..12).map(|x| x as f32).collect::<std::vec::Vec<_>>());
+        let t: Tensor2D<3, 4> = dev.from_arrow(&array, Default::default()).unwrap();
+        assert_eq!(
+            t.array(),
+            [
+                [0.0, 1.0, 2.0, 3.0],
+                [4.0, 5.0, 6.0, 7.0],
+                [8.0, 9.0, 10.0, 11.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_arrow_sliced_f64() {
+        let dev: TestDevice = Default::default();
+        let array = Float64Array::from(vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        let t = dev.from_arrow(&array.slice(1, 3), (3,)).unwrap();
+        assert_eq!(t.as_vec(), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_from_arrow_errors() {
+        let dev: TestDevice = Default::default();
+        let array = Float32Array::from(vec![1.0; 11]);
+        match dev.from_arrow(&array, (Const::<3>, Const::<4>)) {
+            Err(FromArrowError::WrongNumElements { expected, found }) => {
+                assert_eq!((expected, found), (12, 11))
+            }
+            r => panic!("{r:?}"),
+        }
+
+        let array = Float32Array::from(vec![Some(1.0), None, Some(3.0)]);
+        match dev.from_arrow(&array, (Const::<3>,)) {
+            Err(FromArrowError::Nulls(n)) => assert_eq!(n, 1),
+            r => panic!("{r:?}"),
+        }
+    }
+}
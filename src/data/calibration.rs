@@ -0,0 +1,76 @@
+use std::vec::Vec;
+
+/// The expected calibration error (ECE) from
+/// [On Calibration of Modern Neural Networks](https://arxiv.org/abs/1706.04599).
+///
+/// The predictions are split into `bins` equal width bins by their confidence in `[0, 1]`,
+/// and the ECE is the mean over all predictions of `|accuracy - mean confidence|` of their bin.
+/// A perfectly calibrated classifier has an ECE of `0`.
+///
+/// **Panics** if `confidences` and `correct` have different lengths, if they are empty,
+/// or if `bins` is 0.
+///
+/// ```rust
+/// # use dfdx::data::expected_calibration_error;
+/// // 90% confident, but only right half of the time
+/// let ece = expected_calibration_error(&[0.9, 0.9], &[true, false], 10);
+/// assert!((ece - 0.4).abs() < 1e-6);
+/// ```
+pub fn expected_calibration_error(confidences: &[f32], correct: &[bool], bins: usize) -> f32 {
+    assert_eq!(
+        confidences.len(),
+        correct.len(),
+        "expected one confidence per prediction"
+    );
+    assert!(!confidences.is_empty(), "expected at least one prediction");
+    assert!(bins > 0, "expected at least one bin");
+
+    let mut count: Vec<usize> = vec![0; bins];
+    let mut conf_sum: Vec<f64> = vec![0.0; bins];
+    let mut num_correct: Vec<usize> = vec![0; bins];
+    for (&c, &ok) in confidences.iter().zip(correct.iter()) {
+        let b = ((c * bins as f32) as usize).min(bins - 1);
+        count[b] += 1;
+        conf_sum[b] += c as f64;
+        num_correct[b] += ok as usize;
+    }
+
+    let total = confidences.len() as f64;
+    let ece: f64 = (0..bins)
+        .filter(|&b| count[b] > 0)
+        .map(|b| {
+            let n = count[b] as f64;
+            (n / total) * (num_correct[b] as f64 / n - conf_sum[b] / n).abs()
+        })
+        .sum();
+    ece as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ece_perfectly_calibrated() {
+        // in each bin, the fraction that is correct is the confidence of the bin
+        let mut confidences = Vec::new();
+        let mut correct = Vec::new();
+        for b in 0..10 {
+            let conf = (b as f32 + 0.5) / 10.0;
+            for i in 0..100 {
+                confidences.push(conf);
+                correct.push(i < b * 10 + 5);
+            }
+        }
+        let ece = expected_calibration_error(&confidences, &correct, 10);
+        assert!(ece.abs() < 1e-6, "{ece}");
+    }
+
+    #[test]
+    fn test_ece_miscalibrated() {
+        // bin [0.0, 0.5): confidence 0.2, accuracy 1.0. bin [0.5, 1.0]: confidence 1.0, accuracy 0.5
+        let ece = expected_calibration_error(&[0.2, 1.0, 1.0], &[true, true, false], 2);
+        let expected = (1.0 / 3.0) * 0.8 + (2.0 / 3.0) * 0.5;
+        assert!((ece - expected).abs() < 1e-6, "{ece}");
+    }
+}
@@ -0,0 +1,137 @@
+use std::vec::Vec;
+
+/// The precision, recall, and F1 score of one class, or their averages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassMetrics {
+    /// The fraction of predictions of the class that were correct.
+    pub precision: f32,
+    /// The fraction of samples of the class that were predicted correctly.
+    pub recall: f32,
+    /// The harmonic mean of [Self::precision] and [Self::recall].
+    pub f1: f32,
+    /// The number of samples of the class.
+    pub support: usize,
+}
+
+/// The result of [classification_report()].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationReport {
+    /// The metrics of each class.
+    pub classes: Vec<ClassMetrics>,
+    /// The unweighted mean of the metrics of each class, with the total support.
+    pub macro_avg: ClassMetrics,
+}
+
+/// Computes the precision, recall, and F1 score of each class, and their macro averages.
+/// Like [super::ConfusionMatrix], `preds[k]` is the predicted class of a sample of class
+/// `targets[k]`.
+///
+/// A metric with a denominator of zero, e.g. the precision of a class that is never
+/// predicted, is `0`.
+///
+/// **Panics** if `preds` and `targets` have different lengths, or a class is out of bounds.
+///
+/// ```rust
+/// # use dfdx::data::classification_report;
+/// let report = classification_report(&[0, 1, 1], &[0, 1, 0], 2);
+/// assert_eq!(report.classes[0].precision, 1.0);
+/// assert_eq!(report.classes[0].recall, 0.5);
+/// assert_eq!(report.classes[1].precision, 0.5);
+/// assert_eq!(report.macro_avg.recall, 0.75);
+/// ```
+pub fn classification_report(
+    preds: &[usize],
+    targets: &[usize],
+    num_classes: usize,
+) -> ClassificationReport {
+    assert_eq!(
+        preds.len(),
+        targets.len(),
+        "expected one prediction per target"
+    );
+    let mut true_pos = vec![0; num_classes];
+    let mut predicted = vec![0; num_classes];
+    let mut support = vec![0; num_classes];
+    for (&p, &t) in preds.iter().zip(targets.iter()) {
+        assert!(
+            p < num_classes && t < num_classes,
+            "classes must be less than {num_classes}"
+        );
+        predicted[p] += 1;
+        support[t] += 1;
+        if p == t {
+            true_pos[t] += 1;
+        }
+    }
+
+    let ratio = |a: f32, b: f32| if b == 0.0 { 0.0 } else { a / b };
+    let classes: Vec<ClassMetrics> = (0..num_classes)
+        .map(|i| {
+            let precision = ratio(true_pos[i] as f32, predicted[i] as f32);
+            let recall = ratio(true_pos[i] as f32, support[i] as f32);
+            ClassMetrics {
+                precision,
+                recall,
+                f1: ratio(2.0 * precision * recall, precision + recall),
+                support: support[i],
+            }
+        })
+        .collect();
+
+    let n = num_classes as f32;
+    let macro_avg = ClassMetrics {
+        precision: ratio(classes.iter().map(|c| c.precision).sum(), n),
+        recall: ratio(classes.iter().map(|c| c.recall).sum(), n),
+        f1: ratio(classes.iter().map(|c| c.f1).sum(), n),
+        support: targets.len(),
+    };
+    ClassificationReport { classes, macro_avg }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_classification_report() {
+        let preds = [0, 0, 1, 2, 2, 2, 1, 0];
+        let targets = [0, 0, 1, 2, 1, 0, 2, 1];
+        let report = classification_report(&preds, &targets, 4);
+        let c = &report.classes;
+
+        // class 0: 2 of 3 predictions right, 2 of 3 samples found
+        assert_close(&c[0].precision, &(2.0 / 3.0));
+        assert_close(&c[0].recall, &(2.0 / 3.0));
+        assert_close(&c[0].f1, &(2.0 / 3.0));
+        assert_eq!(c[0].support, 3);
+
+        // class 1: 1 of 2 predictions right, 1 of 3 samples found
+        assert_close(&c[1].precision, &0.5);
+        assert_close(&c[1].recall, &(1.0 / 3.0));
+        assert_close(&c[1].f1, &0.4);
+        assert_eq!(c[1].support, 3);
+
+        // class 2: 1 of 3 predictions right, 1 of 2 samples found
+        assert_close(&c[2].precision, &(1.0 / 3.0));
+        assert_close(&c[2].recall, &0.5);
+        assert_close(&c[2].f1, &0.4);
+
+        // class 3 is never predicted and has no samples
+        assert_eq!(
+            c[3],
+            ClassMetrics {
+                precision: 0.0,
+                recall: 0.0,
+                f1: 0.0,
+                support: 0
+            }
+        );
+
+        let m = report.macro_avg;
+        assert_close(&m.precision, &((2.0 / 3.0 + 0.5 + 1.0 / 3.0) / 4.0));
+        assert_close(&m.recall, &((2.0 / 3.0 + 1.0 / 3.0 + 0.5) / 4.0));
+        assert_close(&m.f1, &((2.0 / 3.0 + 0.4 + 0.4) / 4.0));
+        assert_eq!(m.support, 8);
+    }
+}
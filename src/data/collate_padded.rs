@@ -0,0 +1,72 @@
+use std::vec::Vec;
+
+use crate::{
+    shapes::*,
+    tensor::{DeviceStorage, Tensor, TensorFromVec},
+};
+
+/// Pads variable length `sequences` with `pad_value` into a `(batch, max length)` tensor,
+/// and returns it with the length of each sequence, e.g. for masking the padding.
+///
+/// **Panics** if `sequences` is empty.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, data::collate_padded};
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<(usize,), f32, _> = dev.tensor_from_vec(vec![1.0, 2.0], (2,));
+/// let b: Tensor<(usize,), f32, _> = dev.tensor_from_vec(vec![3.0], (1,));
+/// let (batch, lengths) = collate_padded(vec![a, b], 0.0);
+/// assert_eq!(batch.shape(), &(2, 2));
+/// assert_eq!(batch.as_vec(), [1.0, 2.0, 3.0, 0.0]);
+/// assert_eq!(lengths, [2, 1]);
+/// ```
+pub fn collate_padded<L: Dim, E: Unit, D: DeviceStorage + TensorFromVec<E>, T>(
+    sequences: Vec<Tensor<(L,), E, D, T>>,
+    pad_value: E,
+) -> (Tensor<(usize, usize), E, D>, Vec<usize>) {
+    try_collate_padded(sequences, pad_value).unwrap()
+}
+
+/// See [collate_padded()]
+#[allow(clippy::type_complexity)]
+pub fn try_collate_padded<L: Dim, E: Unit, D: DeviceStorage + TensorFromVec<E>, T>(
+    sequences: Vec<Tensor<(L,), E, D, T>>,
+    pad_value: E,
+) -> Result<(Tensor<(usize, usize), E, D>, Vec<usize>), D::Err> {
+    assert!(!sequences.is_empty(), "expected at least one sequence");
+    let lengths: Vec<usize> = sequences.iter().map(|s| s.shape().0.size()).collect();
+    let max_len = lengths.iter().cloned().max().unwrap();
+
+    let mut data = Vec::with_capacity(sequences.len() * max_len);
+    for (seq, &len) in sequences.iter().zip(lengths.iter()) {
+        data.extend(seq.as_vec());
+        data.resize(data.len() + max_len - len, pad_value);
+    }
+    let batch = sequences[0]
+        .device
+        .try_tensor_from_vec(data, (sequences.len(), max_len))?;
+    Ok((batch, lengths))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_collate_padded() {
+        let dev: TestDevice = Default::default();
+        let seqs: Vec<Tensor<(usize,), TestDtype, _>> = vec![
+            dev.tensor_from_vec(vec![1.0, 2.0], (2,)),
+            dev.tensor_from_vec(vec![3.0, 4.0, 5.0], (3,)),
+            dev.tensor_from_vec(vec![6.0], (1,)),
+        ];
+        let (batch, lengths) = collate_padded(seqs, -1.0);
+        assert_eq!(lengths, [2, 3, 1]);
+        assert_eq!(batch.shape(), &(3, 3));
+        assert_eq!(
+            batch.realize::<Rank2<3, 3>>().unwrap().array(),
+            [[1.0, 2.0, -1.0], [3.0, 4.0, 5.0], [6.0, -1.0, -1.0]]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::vec::Vec;
+
+use crate::{
+    shapes::*,
+    tensor::{DeviceStorage, Tensor, TensorFromVec},
+};
+
+/// Counts the predictions of a classifier for each class, to see which classes are
+/// confused with each other.
+pub trait ConfusionMatrix<E: Dtype>: DeviceStorage + TensorFromVec<E> {
+    /// Returns a `(num_classes, num_classes)` tensor, where row `i` column `j` is the number
+    /// of samples of class `targets[k] == i` that were predicted as `preds[k] == j`. The
+    /// diagonal counts the correct predictions.
+    ///
+    /// `num_classes` can be `Const` or `usize`.
+    ///
+    /// **Panics** if `preds` and `targets` have different lengths, or a class is out of bounds.
+    ///
+    /// ```rust
+    /// # use dfdx::{prelude::*, data::ConfusionMatrix};
+    /// # let dev: Cpu = Default::default();
+    /// let preds = [0, 1, 1, 2];
+    /// let targets = [0, 1, 2, 2];
+    /// let m: Tensor<Rank2<3, 3>, f32, _> = dev.confusion_matrix(&preds, &targets, Const::<3>);
+    /// assert_eq!(m.array(), [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 1.0, 1.0]]);
+    /// ```
+    fn confusion_matrix<N: Dim>(
+        &self,
+        preds: &[usize],
+        targets: &[usize],
+        num_classes: N,
+    ) -> Tensor<(N, N), E, Self> {
+        self.try_confusion_matrix(preds, targets, num_classes)
+            .unwrap()
+    }
+
+    /// See [ConfusionMatrix::confusion_matrix]
+    fn try_confusion_matrix<N: Dim>(
+        &self,
+        preds: &[usize],
+        targets: &[usize],
+        num_classes: N,
+    ) -> Result<Tensor<(N, N), E, Self>, Self::Err> {
+        assert_eq!(
+            preds.len(),
+            targets.len(),
+            "expected one prediction per target"
+        );
+        let n = num_classes.size();
+        let mut counts: Vec<usize> = vec![0; n * n];
+        for (&p, &t) in preds.iter().zip(targets.iter()) {
+            assert!(p < n && t < n, "classes must be less than {n}");
+            counts[t * n + p] += 1;
+        }
+        let data = counts
+            .into_iter()
+            .map(|c| E::from_usize(c).unwrap())
+            .collect();
+        self.try_tensor_from_vec(data, (num_classes, num_classes))
+    }
+}
+impl<E: Dtype, D: DeviceStorage + TensorFromVec<E>> ConfusionMatrix<E> for D {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tests::*};
+
+    #[test]
+    fn test_confusion_matrix() {
+        let dev: TestDevice = Default::default();
+        let preds = [0, 0, 1, 2, 2, 2, 1, 0];
+        let targets = [0, 0, 1, 2, 1, 0, 2, 1];
+        let m: Tensor<Rank2<3, 3>, TestDtype, _> =
+            dev.confusion_matrix(&preds, &targets, Const::<3>);
+        assert_eq!(
+            m.array(),
+            [
+                // two 0s right, one 0 predicted as 2
+                [2.0, 0.0, 1.0],
+                // one 1 right, one predicted as 2, one predicted as 0
+                [1.0, 1.0, 1.0],
+                // one 2 right, one 2 predicted as 1
+                [0.0, 1.0, 1.0],
+            ]
+        );
+
+        // runtime number of classes
+        let m2: Tensor<(usize, usize), TestDtype, _> = dev.confusion_matrix(&preds, &targets, 3);
+        assert_eq!(m2.shape(), &(3, 3));
+        assert_eq!(m2.as_vec(), m.as_vec());
+    }
+}
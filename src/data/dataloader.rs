@@ -0,0 +1,279 @@
+use rand::prelude::{Rng, SliceRandom};
+use std::{
+    sync::{
+        mpsc::{sync_channel, Receiver},
+        Arc,
+    },
+    thread::JoinHandle,
+    vec::Vec,
+};
+
+use super::ExactSizeDataset;
+
+/// Loads batches of an [ExactSizeDataset] on worker threads, in a deterministic order.
+///
+/// Each batch is made by calling `collate` on its items. With `num_workers == 0` the
+/// batches are loaded lazily on the calling thread. Otherwise batch `i` is always loaded
+/// by worker `i % num_workers`, which prefetches up to `prefetch` batches ahead, and the
+/// batches are returned in order. So the batches are the same for any number of workers,
+/// and the same as [ExactSizeDataset::iter()] or [ExactSizeDataset::shuffled()] batched
+/// with [super::IteratorBatchExt::batch_with_last()].
+///
+/// Requires the `"std"` feature.
+///
+/// ```rust
+/// # use dfdx::data::{DataLoader, ExactSizeDataset};
+/// # use rand::prelude::*;
+/// struct Squares;
+/// impl ExactSizeDataset for Squares {
+///     type Item<'a> = usize;
+///     fn get(&self, index: usize) -> usize { index * index }
+///     fn len(&self) -> usize { 10 }
+/// }
+///
+/// let mut loader = DataLoader::new(Squares, 4, |items: Vec<usize>| items);
+/// loader.num_workers = 2;
+/// let mut rng = StdRng::seed_from_u64(0);
+/// for batch in loader.shuffled(&mut rng) {
+///     assert!(batch.len() == 4 || batch.len() == 2);
+/// }
+/// ```
+pub struct DataLoader<D, F> {
+    dataset: Arc<D>,
+    collate: Arc<F>,
+
+    /// The number of items in each batch. The last batch may be smaller.
+    pub batch_size: usize,
+
+    /// The number of threads to load batches on. Defaults to `0`, which loads them on the
+    /// calling thread.
+    pub num_workers: usize,
+
+    /// The number of batches each worker loads ahead. Defaults to `2`.
+    pub prefetch: usize,
+
+    /// Whether to skip the last batch if it is smaller than `batch_size`. Defaults to `false`.
+    pub drop_last: bool,
+}
+
+impl<D, F> DataLoader<D, F> {
+    /// Constructs without workers. **Panics** if `batch_size` is 0.
+    pub fn new(dataset: D, batch_size: usize, collate: F) -> Self {
+        assert!(batch_size > 0, "batch_size must be at least 1");
+        Self {
+            dataset: Arc::new(dataset),
+            collate: Arc::new(collate),
+            batch_size,
+            num_workers: 0,
+            prefetch: 2,
+            drop_last: false,
+        }
+    }
+
+    /// The dataset the batches are loaded from.
+    pub fn dataset(&self) -> &D {
+        &self.dataset
+    }
+}
+
+impl<D: ExactSizeDataset, F> DataLoader<D, F> {
+    /// Loads the batches in order.
+    pub fn iter<B>(&self) -> DataLoaderIter<D, F, B>
+    where
+        D: Send + Sync + 'static,
+        F: for<'a> Fn(Vec<D::Item<'a>>) -> B + Send + Sync + 'static,
+        B: Send + 'static,
+    {
+        let indices: Vec<usize> = (0..self.dataset.len()).collect();
+        self.load(indices)
+    }
+
+    /// Loads the batches in an order shuffled with `rng`.
+    pub fn shuffled<B, R: Rng>(&self, rng: &mut R) -> DataLoaderIter<D, F, B>
+    where
+        D: Send + Sync + 'static,
+        F: for<'a> Fn(Vec<D::Item<'a>>) -> B + Send + Sync + 'static,
+        B: Send + 'static,
+    {
+        let mut indices: Vec<usize> = (0..self.dataset.len()).collect();
+        indices.shuffle(rng);
+        self.load(indices)
+    }
+
+    fn load<B>(&self, mut indices: Vec<usize>) -> DataLoaderIter<D, F, B>
+    where
+        D: Send + Sync + 'static,
+        F: for<'a> Fn(Vec<D::Item<'a>>) -> B + Send + Sync + 'static,
+        B: Send + 'static,
+    {
+        // the same order as `ExactSizeDataset::shuffled()`, which pops from the back
+        indices.reverse();
+        let mut batches: Vec<Vec<usize>> = indices
+            .chunks(self.batch_size)
+            .map(|c| c.to_vec())
+            .collect();
+        if self.drop_last && batches.last().map(Vec::len) != Some(self.batch_size) {
+            batches.pop();
+        }
+        let num_batches = batches.len();
+
+        let loading = if self.num_workers == 0 {
+            Loading::Local(batches.into_iter())
+        } else {
+            let mut receivers = Vec::with_capacity(self.num_workers);
+            let mut workers = Vec::with_capacity(self.num_workers);
+            for w in 0..self.num_workers {
+                let assigned: Vec<Vec<usize>> = batches
+                    .iter()
+                    .skip(w)
+                    .step_by(self.num_workers)
+                    .cloned()
+                    .collect();
+                let (sender, receiver) = sync_channel(self.prefetch);
+                let dataset = self.dataset.clone();
+                let collate = self.collate.clone();
+                workers.push(std::thread::spawn(move || {
+                    for batch in assigned {
+                        let items = batch.into_iter().map(|i| dataset.get(i)).collect();
+                        if sender.send(collate(items)).is_err() {
+                            // the iterator was dropped
+                            return;
+                        }
+                    }
+                }));
+                receivers.push(receiver);
+            }
+            Loading::Workers { receivers, workers }
+        };
+
+        DataLoaderIter {
+            dataset: self.dataset.clone(),
+            collate: self.collate.clone(),
+            loading,
+            next: 0,
+            num_batches,
+        }
+    }
+}
+
+enum Loading<B> {
+    Local(std::vec::IntoIter<Vec<usize>>),
+    Workers {
+        receivers: Vec<Receiver<B>>,
+        workers: Vec<JoinHandle<()>>,
+    },
+}
+
+/// The batches of a [DataLoader], in order.
+pub struct DataLoaderIter<D, F, B> {
+    dataset: Arc<D>,
+    collate: Arc<F>,
+    loading: Loading<B>,
+    next: usize,
+    num_batches: usize,
+}
+
+impl<D: ExactSizeDataset, F, B> Iterator for DataLoaderIter<D, F, B>
+where
+    F: for<'a> Fn(Vec<D::Item<'a>>) -> B,
+{
+    type Item = B;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.num_batches {
+            return None;
+        }
+        let batch = match &mut self.loading {
+            Loading::Local(batches) => {
+                let items = batches
+                    .next()?
+                    .into_iter()
+                    .map(|i| self.dataset.get(i))
+                    .collect();
+                (self.collate)(items)
+            }
+            Loading::Workers { receivers, .. } => receivers[self.next % receivers.len()]
+                .recv()
+                .expect("a data loader worker panicked"),
+        };
+        self.next += 1;
+        Some(batch)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.num_batches - self.next;
+        (len, Some(len))
+    }
+}
+
+impl<D: ExactSizeDataset, F, B> ExactSizeIterator for DataLoaderIter<D, F, B> where
+    F: for<'a> Fn(Vec<D::Item<'a>>) -> B
+{
+}
+
+impl<D, F, B> Drop for DataLoaderIter<D, F, B> {
+    fn drop(&mut self) {
+        if let Loading::Workers { receivers, workers } = &mut self.loading {
+            // workers waiting to send error out once their receiver is gone
+            receivers.clear();
+            for worker in workers.drain(..) {
+                let _ = worker.join();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::IteratorBatchExt;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    struct Numbers(usize);
+    impl ExactSizeDataset for Numbers {
+        type Item<'a> = usize;
+        fn get(&self, index: usize) -> Self::Item<'_> {
+            index * 10
+        }
+        fn len(&self) -> usize {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_data_loader_workers_deterministic() {
+        let mut loader = DataLoader::new(Numbers(103), 8, |items: Vec<usize>| items);
+
+        let single: Vec<Vec<usize>> = loader.shuffled(&mut StdRng::seed_from_u64(1)).collect();
+        assert_eq!(single.len(), 13);
+        assert_eq!(single[12].len(), 7);
+
+        loader.num_workers = 4;
+        for _ in 0..3 {
+            let mut rng = StdRng::seed_from_u64(1);
+            assert_eq!(loader.shuffled(&mut rng).collect::<Vec<_>>(), single);
+        }
+
+        // the same as batching the shuffled dataset
+        let expected: Vec<Vec<usize>> = Numbers(103)
+            .shuffled(&mut StdRng::seed_from_u64(1))
+            .batch_with_last(8)
+            .collect();
+        assert_eq!(single, expected);
+
+        // a different seed gives a different order
+        let other: Vec<Vec<usize>> = loader.shuffled(&mut StdRng::seed_from_u64(2)).collect();
+        assert_ne!(other, single);
+    }
+
+    #[test]
+    fn test_data_loader_drop_last() {
+        let mut loader = DataLoader::new(Numbers(10), 4, |items: Vec<usize>| items.len());
+        loader.num_workers = 3;
+        assert_eq!(loader.iter().collect::<Vec<_>>(), [4, 4, 2]);
+        loader.drop_last = true;
+        let mut iter = loader.iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some(4));
+        // dropping early stops the workers
+    }
+}
@@ -0,0 +1,75 @@
+use std::vec::Vec;
+
+/// Generalized advantage estimation from
+/// [High-Dimensional Continuous Control Using Generalized Advantage Estimation](https://arxiv.org/abs/1506.02438),
+/// for actor-critic methods like PPO.
+///
+/// `rewards[t]` and `dones[t]` are the reward of step `t` and whether the episode ended with
+/// it. `values` has one more element than `rewards`: `values[t]` is the critic's value of the
+/// state step `t` started from, and the last element is the value of the state after the last
+/// step, used to bootstrap a trajectory that was cut off mid episode.
+///
+/// Returns the advantage of each step, computed backwards with
+/// `delta[t] = rewards[t] + gamma * values[t + 1] * (1 - dones[t]) - values[t]` and
+/// `gae[t] = delta[t] + gamma * lambda * (1 - dones[t]) * gae[t + 1]`, so advantages don't
+/// leak across episode boundaries. Add `values[..n]` to get the value targets.
+///
+/// **Panics** if `dones` isn't the length of `rewards`, or `values` isn't one longer.
+///
+/// ```rust
+/// # use dfdx::data::compute_gae;
+/// let gae = compute_gae(&[1.0, 1.0], &[0.0, 0.0, 0.0], &[false, true], 0.5, 1.0);
+/// assert_eq!(gae, [1.5, 1.0]);
+/// ```
+pub fn compute_gae(
+    rewards: &[f32],
+    values: &[f32],
+    dones: &[bool],
+    gamma: f32,
+    lambda: f32,
+) -> Vec<f32> {
+    assert_eq!(rewards.len(), dones.len(), "expected one done per reward");
+    assert_eq!(
+        values.len(),
+        rewards.len() + 1,
+        "expected one value per reward, plus the bootstrap value"
+    );
+
+    let mut advantages = Vec::with_capacity(rewards.len());
+    advantages.resize(rewards.len(), 0.0);
+    let mut gae = 0.0;
+    for t in (0..rewards.len()).rev() {
+        let not_done = if dones[t] { 0.0 } else { 1.0 };
+        let delta = rewards[t] + gamma * values[t + 1] * not_done - values[t];
+        gae = delta + gamma * lambda * not_done * gae;
+        advantages[t] = gae;
+    }
+    advantages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_close;
+
+    #[test]
+    fn test_gae_with_episode_boundary() {
+        let rewards = [1.0, 0.0, 2.0, 1.0];
+        let values = [0.5, 1.0, 1.5, 0.5, 2.0];
+        let dones = [false, true, false, false];
+        let (gamma, lambda) = (0.9, 0.8);
+        let gae = compute_gae(&rewards, &values, &dones, gamma, lambda);
+
+        // the second episode, bootstrapped from values[4]
+        let delta3 = 1.0 + 0.9 * 2.0 - 0.5;
+        let delta2 = 2.0 + 0.9 * 0.5 - 1.5;
+        let gae3 = delta3;
+        let gae2 = delta2 + 0.9 * 0.8 * gae3;
+        // the first episode ends at step 1, so nothing flows back from step 2
+        let delta1 = 0.0 - 1.0;
+        let delta0 = 1.0 + 0.9 * 1.0 - 0.5;
+        let gae1 = delta1;
+        let gae0 = delta0 + 0.9 * 0.8 * gae1;
+        assert_close(&[gae[0], gae[1], gae[2], gae[3]], &[gae0, gae1, gae2, gae3]);
+    }
+}
@@ -1,15 +1,51 @@
 //! A collection of useful data utilities such as [ExactSizeDataset], [OneHotEncode], [Arange],
 //! and iterator extension traits!
 mod arange;
+#[cfg(feature = "arrow")]
+mod arrow;
 mod batch;
+mod calibration;
+mod classification_report;
 mod collate;
+mod collate_padded;
+mod confusion_matrix;
+#[cfg(feature = "std")]
+mod dataloader;
 mod dataset;
+mod gae;
 mod one_hot_encode;
+mod perlin;
+#[cfg(feature = "std")]
+mod prefetch;
+mod progressive_resize;
+mod replay_buffer;
+mod roc_auc;
+mod running_stats;
 mod stack;
+#[cfg(feature = "tokenizers")]
+mod tokenizers;
 
+#[cfg(feature = "tokenizers")]
+pub use self::tokenizers::FromEncoding;
 pub use arange::Arange;
+#[cfg(feature = "arrow")]
+pub use arrow::{FromArrow, FromArrowError};
 pub use batch::IteratorBatchExt;
+pub use calibration::expected_calibration_error;
+pub use classification_report::{classification_report, ClassMetrics, ClassificationReport};
 pub use collate::{Collate, IteratorCollateExt};
+pub use collate_padded::{collate_padded, try_collate_padded};
+pub use confusion_matrix::ConfusionMatrix;
+#[cfg(feature = "std")]
+pub use dataloader::{DataLoader, DataLoaderIter};
 pub use dataset::ExactSizeDataset;
+pub use gae::compute_gae;
 pub use one_hot_encode::OneHotEncode;
+pub use perlin::PerlinNoise;
+#[cfg(feature = "std")]
+pub use prefetch::Prefetcher;
+pub use progressive_resize::ProgressiveResize;
+pub use replay_buffer::{BatchedTransitions, ReplayBuffer};
+pub use roc_auc::roc_auc;
+pub use running_stats::RunningStats;
 pub use stack::IteratorStackExt;
@@ -0,0 +1,92 @@
+use crate::{
+    shapes::*,
+    tensor::{DeviceStorage, Tensor, TensorFromVec},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::vec::Vec;
+
+/// Generates smooth procedural noise tensors, e.g. for synthetic data.
+pub trait PerlinNoise<E: Dtype>: DeviceStorage + TensorFromVec<E> {
+    /// 2d [Perlin noise](https://en.wikipedia.org/wiki/Perlin_noise) of size `H`x`W`, with
+    /// values in `[-1, 1]`. The same `seed` always gives the same noise.
+    ///
+    /// `scale` is the size in elements of each lattice cell, where a random gradient is
+    /// picked for each corner. Larger values give smoother noise, and elements on the lattice
+    /// are always 0.
+    ///
+    /// ```rust
+    /// # use dfdx::{prelude::*, data::PerlinNoise};
+    /// # let dev: Cpu = Default::default();
+    /// let noise: Tensor<Rank2<32, 32>, f32, _> = dev.perlin_noise_2d(0, 8.0);
+    /// assert!(noise.as_vec().iter().all(|x| (-1.0..=1.0).contains(x)));
+    /// ```
+    fn perlin_noise_2d<const H: usize, const W: usize>(
+        &self,
+        seed: u64,
+        scale: f32,
+    ) -> Tensor<Rank2<H, W>, E, Self> {
+        assert!(scale > 0.0, "scale must be positive");
+        let mut perm: Vec<usize> = (0..256).collect();
+        perm.shuffle(&mut StdRng::seed_from_u64(seed));
+        let gradient = |x: i64, y: i64| {
+            let hash = perm[(perm[(x & 255) as usize] + (y & 255) as usize) & 255];
+            let angle = hash as f32 * (core::f32::consts::TAU / 256.0);
+            (angle.cos(), angle.sin())
+        };
+        let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+        let lerp = |a: f32, b: f32, t: f32| a + t * (b - a);
+
+        let mut data = Vec::with_capacity(H * W);
+        for i in 0..H {
+            for j in 0..W {
+                let (x, y) = (j as f32 / scale, i as f32 / scale);
+                let (x0, y0) = (x.floor(), y.floor());
+                let (fx, fy) = (x - x0, y - y0);
+                let (x0, y0) = (x0 as i64, y0 as i64);
+                // dot product of the gradient at each corner with the offset from it
+                let corner = |dx: i64, dy: i64| {
+                    let (gx, gy) = gradient(x0 + dx, y0 + dy);
+                    gx * (fx - dx as f32) + gy * (fy - dy as f32)
+                };
+                let (u, v) = (fade(fx), fade(fy));
+                let top = lerp(corner(0, 0), corner(1, 0), u);
+                let bottom = lerp(corner(0, 1), corner(1, 1), u);
+                // with unit gradients the noise is within +-sqrt(1/2), so rescale to +-1
+                let value = lerp(top, bottom, v) * core::f32::consts::SQRT_2;
+                data.push(E::from_f32(value.clamp(-1.0, 1.0)).unwrap());
+            }
+        }
+        self.tensor_from_vec(data, (Const, Const))
+    }
+}
+impl<E: Dtype, D: DeviceStorage + TensorFromVec<E>> PerlinNoise<E> for D {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tests::*};
+
+    #[test]
+    fn test_perlin_noise_seeded_and_bounded() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<24, 32>, TestDtype, _> = dev.perlin_noise_2d(3, 6.0);
+        let b: Tensor<Rank2<24, 32>, TestDtype, _> = dev.perlin_noise_2d(3, 6.0);
+        let c: Tensor<Rank2<24, 32>, TestDtype, _> = dev.perlin_noise_2d(4, 6.0);
+        assert_eq!(a.array(), b.array());
+        assert_ne!(a.array(), c.array());
+
+        let a = a.array();
+        let flat = a.iter().flatten();
+        assert!(flat.clone().all(|x| (-1.0..=1.0).contains(x)));
+        // it isn't flat, and the lattice points are 0
+        assert!(flat.clone().any(|x| x.abs() > 0.2));
+        assert_eq!(a[6][12], 0.0);
+        // neighbors are close to each other
+        for row in a.iter() {
+            for j in 1..32 {
+                assert!((row[j] - row[j - 1]).abs() < 0.5);
+            }
+        }
+    }
+}
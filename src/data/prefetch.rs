@@ -0,0 +1,120 @@
+use std::{
+    sync::mpsc::{sync_channel, Receiver},
+    thread::JoinHandle,
+};
+
+/// Produces batches on a background thread while the calling thread uses
+/// the current one, e.g. to prepare data during training.
+///
+/// `next_batch` is called on the background thread until it returns `None`, with at most
+/// `capacity` batches waiting to be taken. The batches are returned in the order they were
+/// produced. The background thread stops once `next_batch` returns `None`, or when the
+/// [Prefetcher] is dropped.
+///
+/// Requires the `"std"` feature.
+///
+/// ```rust
+/// # use dfdx::data::Prefetcher;
+/// let mut batches = (0..10).map(|i| [i; 4]);
+/// let prefetcher = Prefetcher::new(2, move || batches.next());
+/// for (i, batch) in prefetcher.enumerate() {
+///     // -- snip training on `batch` while the next is prepared --
+///     assert_eq!(batch, [i; 4]);
+/// }
+/// ```
+pub struct Prefetcher<B> {
+    receiver: Option<Receiver<B>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<B: Send + 'static> Prefetcher<B> {
+    /// Starts calling `next_batch` on a background thread. **Panics** if `capacity` is 0.
+    pub fn new<F: FnMut() -> Option<B> + Send + 'static>(
+        capacity: usize,
+        mut next_batch: F,
+    ) -> Self {
+        assert!(capacity > 0, "capacity must be at least 1");
+        let (sender, receiver) = sync_channel(capacity);
+        let worker = std::thread::spawn(move || {
+            while let Some(batch) = next_batch() {
+                if sender.send(batch).is_err() {
+                    // the prefetcher was dropped
+                    return;
+                }
+            }
+        });
+        Self {
+            receiver: Some(receiver),
+            worker: Some(worker),
+        }
+    }
+}
+
+impl<B> Prefetcher<B> {
+    /// Stops the background thread, and re-raises its panic if it had one.
+    fn shutdown(&mut self) {
+        self.receiver = None;
+        if let Some(worker) = self.worker.take() {
+            if let Err(e) = worker.join() {
+                std::panic::resume_unwind(e);
+            }
+        }
+    }
+}
+
+impl<B> Iterator for Prefetcher<B> {
+    type Item = B;
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = self.receiver.as_ref()?.recv().ok();
+        if batch.is_none() {
+            // `next_batch` returned `None` or panicked
+            self.shutdown();
+        }
+        batch
+    }
+}
+
+impl<B> Drop for Prefetcher<B> {
+    fn drop(&mut self) {
+        // don't panic again while unwinding
+        if !std::thread::panicking() {
+            self.shutdown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_prefetcher_in_order_and_shuts_down() {
+        let mut i = 0;
+        let mut prefetcher = Prefetcher::new(2, move || {
+            i += 1;
+            (i <= 20).then_some([i; 3])
+        });
+        let batches: Vec<[i32; 3]> = prefetcher.by_ref().collect();
+        assert_eq!(batches, (1..=20).map(|i| [i; 3]).collect::<Vec<_>>());
+
+        assert!(prefetcher.worker.is_none());
+        assert!(prefetcher.receiver.is_none());
+        assert_eq!(prefetcher.next(), None);
+    }
+
+    #[test]
+    fn test_prefetcher_dropped_early() {
+        // the worker is blocked on the full channel, and stops when the prefetcher is dropped
+        let mut prefetcher = Prefetcher::new(1, || Some(0));
+        assert_eq!(prefetcher.next(), Some(0));
+        drop(prefetcher);
+    }
+
+    #[test]
+    #[should_panic = "bad batch"]
+    fn test_prefetcher_worker_panic() {
+        let mut prefetcher = Prefetcher::new(1, || -> Option<i32> { panic!("bad batch") });
+        prefetcher.next();
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    shapes::*,
+    tensor::{Tape, Tensor, ZerosTensor},
+    tensor_ops::{Bilinear, Upscale2DKernel},
+};
+
+/// Progressive resizing: the size of the training images changes as training goes on,
+/// usually starting small for fast early epochs and growing to the full size.
+///
+/// The schedule is a list of `(epoch, (height, width))`, sorted by epoch, where each size
+/// is used from its epoch until the next one starts. It can't be changed after
+/// [ProgressiveResize::new()] checks it, see [ProgressiveResize::schedule()].
+///
+/// ```rust
+/// # use dfdx::{prelude::*, data::ProgressiveResize};
+/// # let dev: Cpu = Default::default();
+/// let resize = ProgressiveResize::new(vec![(0, (8, 8)), (5, (16, 16))]);
+/// assert_eq!(resize.size(3), (8, 8));
+/// assert_eq!(resize.size(7), (16, 16));
+/// let batch: Tensor<Rank4<2, 3, 32, 32>, f32, _> = dev.zeros();
+/// let batch = resize.resize(3, batch);
+/// assert_eq!(batch.shape(), &(Const::<2>, Const::<3>, 8, 8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProgressiveResize {
+    schedule: std::vec::Vec<(usize, (usize, usize))>,
+}
+
+impl ProgressiveResize {
+    /// **Panics** if `schedule` is empty, or is not sorted by epoch.
+    pub fn new(schedule: std::vec::Vec<(usize, (usize, usize))>) -> Self {
+        assert!(!schedule.is_empty(), "the schedule needs at least one size");
+        assert!(
+            schedule.windows(2).all(|w| w[0].0 < w[1].0),
+            "the schedule must be sorted by epoch"
+        );
+        Self { schedule }
+    }
+
+    /// The `(epoch, (height, width))` entries of the schedule, sorted by epoch.
+    pub fn schedule(&self) -> &[(usize, (usize, usize))] {
+        &self.schedule
+    }
+
+    /// The `(height, width)` to use for `epoch`. Epochs before the first entry of the
+    /// schedule use its size.
+    pub fn size(&self, epoch: usize) -> (usize, usize) {
+        self.schedule
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= epoch)
+            .unwrap_or(&self.schedule[0])
+            .1
+    }
+
+    /// Resizes `batch` to the size for `epoch`, with [Tensor::resize_bilinear_to()].
+    pub fn resize<B: Dim, C: Dim, H: Dim, W: Dim, E: Dtype, D, T: 'static + Tape<E, D>>(
+        &self,
+        epoch: usize,
+        batch: Tensor<(B, C, H, W), E, D, T>,
+    ) -> Tensor<(B, C, usize, usize), E, D, T>
+    where
+        D: Upscale2DKernel<E, Bilinear> + ZerosTensor<E>,
+    {
+        self.try_resize(epoch, batch).unwrap()
+    }
+
+    /// See [ProgressiveResize::resize()]
+    #[allow(clippy::type_complexity)]
+    pub fn try_resize<B: Dim, C: Dim, H: Dim, W: Dim, E: Dtype, D, T: 'static + Tape<E, D>>(
+        &self,
+        epoch: usize,
+        batch: Tensor<(B, C, H, W), E, D, T>,
+    ) -> Result<Tensor<(B, C, usize, usize), E, D, T>, D::Err>
+    where
+        D: Upscale2DKernel<E, Bilinear> + ZerosTensor<E>,
+    {
+        let (h, w) = self.size(epoch);
+        batch.try_resize_bilinear_to(h, w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_progressive_resize_schedule() {
+        let dev: TestDevice = Default::default();
+        let resize = ProgressiveResize::new(std::vec![(1, (2, 2)), (3, (3, 3)), (5, (4, 4))]);
+        assert_eq!(resize.schedule(), [(1, (2, 2)), (3, (3, 3)), (5, (4, 4))]);
+        let sizes: std::vec::Vec<_> = (0..7).map(|e| resize.size(e)).collect();
+        assert_eq!(
+            sizes,
+            [(2, 2), (2, 2), (2, 2), (3, 3), (3, 3), (4, 4), (4, 4)]
+        );
+
+        let x: Tensor<Rank3<1, 4, 4>, TestDtype, _> = dev.tensor([[
+            [0.0, 1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0, 7.0],
+            [8.0, 9.0, 10.0, 11.0],
+            [12.0, 13.0, 14.0, 15.0],
+        ]]);
+        let batch = x.clone().broadcast::<Rank4<2, 1, 4, 4>, _>();
+        let batch = dev.tensor(batch.array());
+
+        // the same as resizing each image to the size for the epoch
+        let r = resize.resize(3, batch.clone());
+        assert_eq!(r.shape(), &(Const::<2>, Const::<1>, 3, 3));
+        let expected = x.resize_bilinear::<3, 3>().array();
+        assert_eq!(
+            r.realize::<Rank4<2, 1, 3, 3>>().unwrap().array(),
+            [expected; 2]
+        );
+
+        let r = resize.resize(6, batch.clone());
+        assert_eq!(
+            r.realize::<Rank4<2, 1, 4, 4>>().unwrap().array(),
+            batch.array()
+        );
+    }
+}
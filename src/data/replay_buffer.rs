@@ -0,0 +1,213 @@
+use rand::Rng;
+use std::vec::Vec;
+
+use crate::{
+    shapes::*,
+    tensor::Tensor,
+    tensor_ops::{AddDim, Device, TryStack},
+};
+
+/// A fixed capacity buffer of `(state, action, reward, next_state, done)` transitions for
+/// off-policy reinforcement learning, e.g. DQN. Once it is full, each new transition replaces
+/// the oldest one.
+///
+/// States have shape `S` and actions have shape `A`. Discrete actions can be stored as
+/// [Rank0] tensors, and converted with [crate::tensor_ops::to_dtype()] to index with.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, data::ReplayBuffer};
+/// # use rand::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let mut buffer: ReplayBuffer<Rank1<4>, Rank0, f32, Cpu> = ReplayBuffer::new(1000);
+/// for _ in 0..10 {
+///     // -- snip stepping the environment --
+///     buffer.push(dev.sample_normal(), dev.tensor(1.0), 0.5, dev.sample_normal(), false);
+/// }
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let batch = buffer.sample(8, &mut rng);
+/// assert_eq!(batch.states.shape(), &(8, Const::<4>));
+/// assert_eq!(batch.rewards.shape(), &(8,));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplayBuffer<S: Shape, A: Shape, E: Dtype, D: Device<E>> {
+    capacity: usize,
+    transitions: Vec<Transition<S, A, E, D>>,
+    /// The index the next transition is written to once the buffer is full.
+    next: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Transition<S: Shape, A: Shape, E: Dtype, D: Device<E>> {
+    state: Tensor<S, E, D>,
+    action: Tensor<A, E, D>,
+    reward: E,
+    next_state: Tensor<S, E, D>,
+    done: bool,
+}
+
+/// A batch of transitions from [ReplayBuffer::sample()], stacked along a new first dimension.
+#[derive(Debug, Clone)]
+pub struct BatchedTransitions<S: AddDim<usize>, A: AddDim<usize>, E: Dtype, D: Device<E>> {
+    pub states: Tensor<S::Larger, E, D>,
+    pub actions: Tensor<A::Larger, E, D>,
+    pub rewards: Tensor<(usize,), E, D>,
+    pub next_states: Tensor<S::Larger, E, D>,
+    /// `1` where the episode ended with the transition, and `0` otherwise.
+    pub dones: Tensor<(usize,), E, D>,
+}
+
+impl<S: Shape, A: Shape, E: Dtype, D: Device<E>> ReplayBuffer<S, A, E, D> {
+    /// An empty buffer that holds up to `capacity` transitions. **Panics** if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be at least 1");
+        Self {
+            capacity,
+            transitions: Vec::with_capacity(capacity),
+            next: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of transitions currently stored.
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Adds a transition, replacing the oldest one if the buffer is full.
+    pub fn push(
+        &mut self,
+        state: Tensor<S, E, D>,
+        action: Tensor<A, E, D>,
+        reward: E,
+        next_state: Tensor<S, E, D>,
+        done: bool,
+    ) {
+        let transition = Transition {
+            state,
+            action,
+            reward,
+            next_state,
+            done,
+        };
+        if self.transitions.len() < self.capacity {
+            self.transitions.push(transition);
+        } else {
+            self.transitions[self.next] = transition;
+            self.next = (self.next + 1) % self.capacity;
+        }
+    }
+
+    /// Samples `batch_size` transitions uniformly at random with replacement.
+    ///
+    /// **Panics** if the buffer is empty.
+    pub fn sample<R: Rng>(&self, batch_size: usize, rng: &mut R) -> BatchedTransitions<S, A, E, D>
+    where
+        S: AddDim<usize>,
+        A: AddDim<usize>,
+    {
+        self.try_sample(batch_size, rng).unwrap()
+    }
+
+    /// See [ReplayBuffer::sample()]
+    pub fn try_sample<R: Rng>(
+        &self,
+        batch_size: usize,
+        rng: &mut R,
+    ) -> Result<BatchedTransitions<S, A, E, D>, D::Err>
+    where
+        S: AddDim<usize>,
+        A: AddDim<usize>,
+    {
+        assert!(!self.is_empty(), "can't sample from an empty buffer");
+        let batch: Vec<&Transition<S, A, E, D>> = (0..batch_size)
+            .map(|_| &self.transitions[rng.gen_range(0..self.transitions.len())])
+            .collect();
+
+        let device = &batch[0].state.device;
+        let rewards = batch.iter().map(|t| t.reward).collect();
+        let dones = batch
+            .iter()
+            .map(|t| if t.done { E::ONE } else { E::default() })
+            .collect();
+        Ok(BatchedTransitions {
+            states: batch
+                .iter()
+                .map(|t| t.state.clone())
+                .collect::<Vec<_>>()
+                .try_stack()?,
+            actions: batch
+                .iter()
+                .map(|t| t.action.clone())
+                .collect::<Vec<_>>()
+                .try_stack()?,
+            rewards: device.try_tensor_from_vec(rewards, (batch_size,))?,
+            next_states: batch
+                .iter()
+                .map(|t| t.next_state.clone())
+                .collect::<Vec<_>>()
+                .try_stack()?,
+            dones: device.try_tensor_from_vec(dones, (batch_size,))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tests::*};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_replay_buffer_overwrites_oldest() {
+        let dev: TestDevice = Default::default();
+        let mut buffer: ReplayBuffer<Rank1<2>, Rank1<3>, TestDtype, _> = ReplayBuffer::new(3);
+        for i in 0..5 {
+            let i = i as TestDtype;
+            let done = i == 4.0;
+            buffer.push(
+                dev.tensor([i; 2]),
+                dev.tensor([-i; 3]),
+                i,
+                dev.tensor([i + 1.0; 2]),
+                done,
+            );
+        }
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.capacity(), 3);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let batch = buffer.sample(16, &mut rng);
+        assert_eq!(batch.states.shape(), &(16, Const::<2>));
+        assert_eq!(batch.actions.shape(), &(16, Const::<3>));
+        assert_eq!(batch.rewards.shape(), &(16,));
+        assert_eq!(batch.next_states.shape(), &(16, Const::<2>));
+        assert_eq!(batch.dones.shape(), &(16,));
+
+        // transitions 0 and 1 were overwritten, and every field comes from the same transition
+        let (states, actions, next_states) = (
+            batch.states.as_vec(),
+            batch.actions.as_vec(),
+            batch.next_states.as_vec(),
+        );
+        for (b, (&r, &d)) in batch
+            .rewards
+            .as_vec()
+            .iter()
+            .zip(batch.dones.as_vec().iter())
+            .enumerate()
+        {
+            assert!([2.0, 3.0, 4.0].contains(&r), "{r}");
+            assert_eq!(states[2 * b], r);
+            assert_eq!(actions[3 * b], -r);
+            assert_eq!(next_states[2 * b], r + 1.0);
+            assert_eq!(d, if r == 4.0 { 1.0 } else { 0.0 });
+        }
+    }
+}
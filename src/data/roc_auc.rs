@@ -0,0 +1,88 @@
+use std::vec::Vec;
+
+/// The area under the ROC curve of a binary classifier, from the `scores` it gives each
+/// sample and whether each sample is positive in `labels`.
+///
+/// This is the probability that a random positive is scored higher than a random negative,
+/// computed from the ranks of the scores with the
+/// [Mann–Whitney U statistic](https://en.wikipedia.org/wiki/Mann%E2%80%93Whitney_U_test).
+/// Tied scores get the average of their ranks, so a tie counts as half.
+///
+/// **Panics** if `scores` and `labels` have different lengths, if there aren't both positive
+/// and negative labels, or if a score is NaN.
+///
+/// ```rust
+/// # use dfdx::data::roc_auc;
+/// assert_eq!(roc_auc(&[0.1, 0.4, 0.35, 0.8], &[false, false, true, true]), 0.75);
+/// ```
+pub fn roc_auc(scores: &[f32], labels: &[bool]) -> f32 {
+    assert_eq!(scores.len(), labels.len(), "expected one label per score");
+    let num_pos = labels.iter().filter(|&&l| l).count();
+    let num_neg = labels.len() - num_pos;
+    assert!(
+        num_pos > 0 && num_neg > 0,
+        "roc_auc needs both positive and negative labels"
+    );
+
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| {
+        scores[a]
+            .partial_cmp(&scores[b])
+            .expect("scores can't be NaN")
+    });
+
+    // the sum of the 1-based ranks of the positives, with ties getting their average rank
+    let mut pos_rank_sum = 0.0f64;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && scores[order[end]] == scores[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        let tied_pos = order[start..end].iter().filter(|&&i| labels[i]).count();
+        pos_rank_sum += rank * tied_pos as f64;
+        start = end;
+    }
+
+    let (num_pos, num_neg) = (num_pos as f64, num_neg as f64);
+    let u = pos_rank_sum - num_pos * (num_pos + 1.0) / 2.0;
+    (u / (num_pos * num_neg)) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_roc_auc_separable() {
+        let scores = [0.1, 0.2, 0.3, 0.6, 0.7, 0.9];
+        let labels = [false, false, false, true, true, true];
+        assert_eq!(roc_auc(&scores, &labels), 1.0);
+
+        // flipping the labels gives the worst possible classifier
+        let flipped = labels.map(|l| !l);
+        assert_eq!(roc_auc(&scores, &flipped), 0.0);
+    }
+
+    #[test]
+    fn test_roc_auc_ties() {
+        // every positive is tied with a negative, so each pair counts as half
+        let scores = [0.5, 0.5, 0.5, 0.5];
+        let labels = [true, false, true, false];
+        assert_eq!(roc_auc(&scores, &labels), 0.5);
+
+        // (pos 0.5) beats (neg 0.2) and ties (neg 0.5): (1 + 0.5) / 2
+        assert_eq!(roc_auc(&[0.2, 0.5, 0.5], &[false, false, true]), 0.75);
+    }
+
+    #[test]
+    fn test_roc_auc_random() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let scores: Vec<f32> = (0..10000).map(|_| rng.gen()).collect();
+        let labels: Vec<bool> = (0..10000).map(|_| rng.gen()).collect();
+        let auc = roc_auc(&scores, &labels);
+        assert!((auc - 0.5).abs() < 0.02, "{auc}");
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    shapes::*,
+    tensor::{DeviceStorage, Tensor},
+    tensor_ops::*,
+};
+
+/// Per feature mean & variance of a stream of batches, so statistics can be computed
+/// over datasets that don't fit in memory.
+///
+/// Each [RunningStats::update()] merges the batch's statistics into the running ones
+/// with the parallel form of [Welford's algorithm](https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm),
+/// which stays numerically stable over many batches.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, data::RunningStats};
+/// # let dev: Cpu = Default::default();
+/// let mut stats = RunningStats::new(&dev, Const::<2>);
+/// stats.update(&dev.tensor([[1.0f32, 10.0], [3.0, 10.0]]));
+/// stats.update(&dev.tensor([[5.0f32, 40.0]]));
+/// let (mean, var) = stats.finalize();
+/// assert_eq!(mean.array(), [3.0, 20.0]);
+/// assert_eq!(var.array(), [8.0 / 3.0, 200.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RunningStats<N: Dim, E: Dtype, D: DeviceStorage> {
+    count: usize,
+    mean: Tensor<(N,), E, D>,
+    m2: Tensor<(N,), E, D>,
+}
+
+impl<N: Dim, E: Dtype, D: Device<E>> RunningStats<N, E, D> {
+    /// Empty statistics for `n` features.
+    pub fn new(dev: &D, n: N) -> Self {
+        Self::try_new(dev, n).unwrap()
+    }
+
+    /// See [RunningStats::new]
+    pub fn try_new(dev: &D, n: N) -> Result<Self, D::Err> {
+        Ok(Self {
+            count: 0,
+            mean: dev.try_zeros_like(&(n,))?,
+            m2: dev.try_zeros_like(&(n,))?,
+        })
+    }
+
+    /// The number of samples seen so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Adds every row of `batch` to the statistics.
+    pub fn update<B: Dim>(&mut self, batch: &Tensor<(B, N), E, D>) {
+        self.try_update(batch).unwrap()
+    }
+
+    /// See [RunningStats::update]
+    pub fn try_update<B: Dim>(&mut self, batch: &Tensor<(B, N), E, D>) -> Result<(), D::Err> {
+        let batch_count = batch.shape.0.size();
+        if batch_count == 0 {
+            return Ok(());
+        }
+        let batch_mean = batch.clone().try_mean::<(N,), Axis<0>>()?;
+        let batch_m2 = batch
+            .clone()
+            .try_sub(batch_mean.clone().try_broadcast_like(&batch.shape)?)?
+            .try_square()?
+            .try_sum::<(N,), Axis<0>>()?;
+
+        let total = self.count + batch_count;
+        let delta = batch_mean.try_sub(self.mean.clone())?;
+        let weight = E::from_usize(batch_count).unwrap() / E::from_usize(total).unwrap();
+        let cross = E::from_usize(self.count).unwrap() * weight;
+        self.mean = self.mean.clone().try_add(delta.clone().try_mul(weight)?)?;
+        self.m2 = self
+            .m2
+            .clone()
+            .try_add(batch_m2)?
+            .try_add(delta.try_square()?.try_mul(cross)?)?;
+        self.count = total;
+        Ok(())
+    }
+
+    /// The mean and (biased) variance of each feature over all the samples seen so far.
+    ///
+    /// **Panics** if no samples have been seen.
+    #[allow(clippy::type_complexity)]
+    pub fn finalize(&self) -> (Tensor<(N,), E, D>, Tensor<(N,), E, D>) {
+        self.try_finalize().unwrap()
+    }
+
+    /// See [RunningStats::finalize]
+    #[allow(clippy::type_complexity)]
+    pub fn try_finalize(&self) -> Result<(Tensor<(N,), E, D>, Tensor<(N,), E, D>), D::Err> {
+        assert!(self.count > 0, "RunningStats::finalize() with no samples");
+        let var = self
+            .m2
+            .clone()
+            .try_div(E::from_usize(self.count).unwrap())?;
+        Ok((self.mean.clone(), var))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tests::*};
+
+    #[test]
+    fn test_running_stats_matches_full_data() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank2<1, 3>, TestDtype, _> = dev.sample_normal();
+        let c: Tensor<Rank2<7, 3>, TestDtype, _> = dev.sample_normal();
+
+        let mut stats = RunningStats::new(&dev, Const::<3>);
+        stats.update(&a);
+        stats.update(&b);
+        stats.update(&c);
+        assert_eq!(stats.count(), 12);
+
+        let mut all = a.as_vec();
+        all.extend(b.as_vec());
+        all.extend(c.as_vec());
+        let all: Tensor<Rank2<12, 3>, TestDtype, _> = dev.tensor(all);
+
+        let (mean, var) = stats.finalize();
+        assert_close(&mean.array(), &all.clone().mean::<Rank1<3>, _>().array());
+        assert_close(&var.array(), &all.var::<Rank1<3>, _>().array());
+    }
+
+    #[test]
+    fn test_running_stats_empty_batch() {
+        let dev: TestDevice = Default::default();
+        let mut stats = RunningStats::new(&dev, 2);
+        let empty: Tensor<(usize, usize), TestDtype, _> = dev.zeros_like(&(0, 2));
+        stats.update(&empty);
+        assert_eq!(stats.count(), 0);
+        stats.update(&dev.tensor_from_vec(vec![1.0, 2.0, 3.0, 6.0], (2, 2)));
+        let (mean, var) = stats.finalize();
+        assert_eq!(mean.as_vec(), [2.0, 4.0]);
+        assert_eq!(var.as_vec(), [1.0, 4.0]);
+    }
+
+    #[test]
+    #[should_panic = "no samples"]
+    fn test_running_stats_finalize_without_samples() {
+        let dev: TestDevice = Default::default();
+        let stats: RunningStats<_, TestDtype, _> = RunningStats::new(&dev, Const::<2>);
+        let _ = stats.finalize();
+    }
+}
@@ -0,0 +1,94 @@
+use std::vec::Vec;
+use tokenizers::Encoding;
+
+use crate::{
+    shapes::*,
+    tensor::{DeviceStorage, Tensor, TensorFromVec},
+};
+
+/// Turns an [Encoding] from the [tokenizers](https://docs.rs/tokenizers) crate into tensors
+/// for a text model.
+///
+/// Requires the `"tokenizers"` feature.
+pub trait FromEncoding<E: Dtype>: DeviceStorage + TensorFromVec<usize> + TensorFromVec<E> {
+    /// Returns the token ids of `encoding`, e.g. for [crate::nn::modules::Embedding], and its
+    /// attention mask, which is `1` for real tokens and `0` for padding. Both have one
+    /// element per token of `encoding`.
+    ///
+    /// ```rust
+    /// # use dfdx::{prelude::*, data::FromEncoding};
+    /// # use tokenizers::{Encoding, PaddingDirection};
+    /// # let dev: Cpu = Default::default();
+    /// # let mut encoding = Encoding::new(
+    /// #     vec![5, 6], vec![0; 2], vec!["a".into(), "b".into()], vec![None; 2],
+    /// #     vec![(0, 0); 2], vec![0; 2], vec![1; 2], vec![], Default::default(),
+    /// # );
+    /// // `encoding` is from e.g. `tokenizer.encode("a b", false)`
+    /// encoding.pad(4, 0, 0, "[PAD]", PaddingDirection::Right);
+    /// let (ids, mask): (_, Tensor<_, f32, _>) = dev.from_encoding(&encoding);
+    /// assert_eq!(ids.as_vec(), [5, 6, 0, 0]);
+    /// assert_eq!(mask.as_vec(), [1.0, 1.0, 0.0, 0.0]);
+    /// ```
+    #[allow(clippy::type_complexity, clippy::wrong_self_convention)]
+    fn from_encoding(
+        &self,
+        encoding: &Encoding,
+    ) -> (Tensor<(usize,), usize, Self>, Tensor<(usize,), E, Self>) {
+        self.try_from_encoding(encoding).unwrap()
+    }
+
+    /// See [FromEncoding::from_encoding]
+    #[allow(clippy::type_complexity, clippy::wrong_self_convention)]
+    fn try_from_encoding(
+        &self,
+        encoding: &Encoding,
+    ) -> Result<(Tensor<(usize,), usize, Self>, Tensor<(usize,), E, Self>), Self::Err> {
+        let ids: Vec<usize> = encoding.get_ids().iter().map(|&i| i as usize).collect();
+        let mask: Vec<E> = encoding
+            .get_attention_mask()
+            .iter()
+            .map(|&m| if m == 0 { E::default() } else { E::ONE })
+            .collect();
+        assert_eq!(ids.len(), mask.len(), "expected one mask value per token");
+        let len = ids.len();
+        let ids = self.try_tensor_from_vec(ids, (len,))?;
+        let mask = self.try_tensor_from_vec(mask, (len,))?;
+        Ok((ids, mask))
+    }
+}
+impl<E: Dtype, D: DeviceStorage + TensorFromVec<usize> + TensorFromVec<E>> FromEncoding<E> for D {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use tokenizers::PaddingDirection;
+
+    fn encoding(ids: Vec<u32>) -> Encoding {
+        let n = ids.len();
+        let tokens = ids.iter().map(|i| i.to_string()).collect();
+        Encoding::new(
+            ids,
+            vec![0; n],
+            tokens,
+            vec![None; n],
+            vec![(0, 0); n],
+            vec![0; n],
+            vec![1; n],
+            vec![],
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn test_from_encoding_marks_padding() {
+        let dev: TestDevice = Default::default();
+        let mut enc = encoding(vec![7, 3, 9]);
+        enc.pad(5, 1, 0, "[PAD]", PaddingDirection::Left);
+
+        let (ids, mask): (_, Tensor<_, TestDtype, _>) = dev.from_encoding(&enc);
+        assert_eq!(ids.shape(), mask.shape());
+        assert_eq!(ids.as_vec(), [1, 1, 7, 3, 9]);
+        assert_eq!(mask.as_vec(), [0.0, 0.0, 1.0, 1.0, 1.0]);
+    }
+}
@@ -68,6 +68,12 @@
 //! Used to enable the threading feature of `matrixmultiply`. This makes matmuls
 //! substantially faster!
 //!
+//! # "cpu-par-reduce"
+//!
+//! Splits large Cpu reductions over the last dimension across threads, and sums each
+//! row with several independent accumulators. This is faster, but sums are no longer
+//! bit for bit identical to the default build.
+//!
 //! # "cpu-mkl-matmul"
 //!
 //! Enables using the `Intel MKL` libraries (assuming you installed it already) for matrix multiplication.
@@ -101,6 +107,26 @@
 //! dfdx = { version = "...", features = ["numpy"] }
 //! ```
 //!
+//! # "ndarray"
+//!
+//! Enables converting tensors to and from [ndarray](https://docs.rs/ndarray) arrays, see
+//! [crate::tensor::FromNdarray].
+//!
+//! Example:
+//! ```toml
+//! dfdx = { version = "...", features = ["ndarray"] }
+//! ```
+//!
+//! # "arrow"
+//!
+//! Enables copying [Apache Arrow](https://docs.rs/arrow) arrays into tensors, see
+//! [crate::data::FromArrow].
+//!
+//! Example:
+//! ```toml
+//! dfdx = { version = "...", features = ["arrow"] }
+//! ```
+//!
 //! # "safetensors"
 //!
 //! Enables saving and loading tensors/nn to .safetensors files.
@@ -110,6 +136,25 @@
 //! dfdx = { version = "...", features = ["safetensors"] }
 //! ```
 //!
+//! # "profile"
+//!
+//! Enables [crate::profile], which records how long each tensor op takes to run.
+//!
+//! Example:
+//! ```toml
+//! dfdx = { version = "...", features = ["profile"] }
+//! ```
+//!
+//! # "graphviz"
+//!
+//! Records a description of every backward operation on the tape, so it can be exported with
+//! [crate::tensor::OwnedTape::to_dot]. Off by default, since it costs an allocation per op.
+//!
+//! Example:
+//! ```toml
+//! dfdx = { version = "...", features = ["graphviz"] }
+//! ```
+//!
 //! # "nightly"
 //!
 //! Enables using all features that currently require the nightly rust compiler.
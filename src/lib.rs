@@ -187,6 +187,8 @@ pub mod feature_flags;
 pub mod losses;
 pub mod nn;
 pub mod optim;
+#[cfg(feature = "profile")]
+pub mod profile;
 pub mod shapes;
 pub mod tensor;
 pub mod tensor_ops;
@@ -324,4 +326,32 @@ pub(crate) mod tests {
     ) {
         a.assert_close(b, tolerance);
     }
+
+    /// Counts the heap allocations of each thread, so that tests running in parallel don't
+    /// count each other's allocations.
+    struct CountingAlloc;
+
+    std::thread_local! {
+        static NUM_ALLOCS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            let _ = NUM_ALLOCS.try_with(|n| n.set(n.get() + 1));
+            std::alloc::System.alloc(layout)
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc;
+
+    /// Runs `f`, and returns its output and the number of heap allocations it made.
+    pub fn count_allocs<R>(f: impl FnOnce() -> R) -> (R, usize) {
+        let before = NUM_ALLOCS.with(|n| n.get());
+        let out = f();
+        (out, NUM_ALLOCS.with(|n| n.get()) - before)
+    }
 }
@@ -2,10 +2,15 @@
 
 use crate::{
     shapes::*,
-    tensor::{Tape, Tensor},
-    tensor_ops::*,
+    tensor::{Merge, PutTape, SplitTape, Tape, Tensor},
+    tensor_ops::{
+        ops::{try_host_binary_op, try_host_unary_op},
+        *,
+    },
 };
 
+use num_traits::Float;
+
 /// [Mean Squared Error](https://en.wikipedia.org/wiki/Mean_squared_error).
 /// This computes `(pred - targ).square().mean()`.
 ///
@@ -86,6 +91,141 @@ pub fn cross_entropy_with_logits_loss<S: Shape, E: Dtype, D: Device<E>, T: Tape<
     (probs * target_probs).mean().negate() * last_axis_numel
 }
 
+/// Computes the same value as [cross_entropy_with_logits_loss()] with a fused kernel that
+/// records a single op on the tape.
+///
+/// Each row is handled in one pass: its `logsumexp` and the dot product with `target_probs`
+/// give the loss directly, so no `log_softmax`, product or mean intermediates are
+/// allocated. Only the `logsumexp` of each row is kept around for backward, which computes
+/// `(softmax(logits) * sum(target_probs) - target_probs) / B` (where `B` is the number of
+/// rows). With probability vectors as targets that is `(softmax(logits) - target_probs) / B`.
+///
+/// If `logits` or `target_probs` is not contiguous (e.g. it was broadcasted or permuted),
+/// or is empty, this falls back to [cross_entropy_with_logits_loss()].
+///
+/// # Arguments
+///
+/// - `logits`: The un-normalized output from a model. Softmax is applied **in** this function
+/// - `target_probs`: Target containing probability vectors **NOT** class indices.
+pub fn softmax_cross_entropy<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    logits: Tensor<S, E, D, T>,
+    target_probs: Tensor<S, E, D>,
+) -> Tensor<Rank0, E, D, T> {
+    try_softmax_cross_entropy(logits, target_probs).unwrap()
+}
+
+/// Fallible version of [softmax_cross_entropy()]
+pub fn try_softmax_cross_entropy<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    logits: Tensor<S, E, D, T>,
+    target_probs: Tensor<S, E, D>,
+) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+    let last_axis_numel = <S as HasAxes<S::LastAxis>>::size(logits.shape());
+    let num_elements = logits.shape.num_elements();
+    let strides = logits.shape.strides();
+    if num_elements == 0 || logits.strides != strides || target_probs.strides != strides {
+        let probs = logits.try_log_softmax::<S::LastAxis>()?;
+        return probs
+            .try_mul(target_probs)?
+            .try_mean()?
+            .try_negate()?
+            .try_mul(E::from_usize(last_axis_numel).unwrap());
+    }
+    let num_rows = num_elements / last_axis_numel;
+    let inv_num_rows = E::ONE / E::from_usize(num_rows).unwrap();
+    let (logits, mut tape) = logits.split_tape();
+    let (losses, logsumexp) = ops::profiled(|| {
+        SoftmaxCrossEntropyKernel::forward(&logits.device, num_rows, &logits, &target_probs)
+    })?;
+    let loss = losses.try_sum::<Rank0, _>()?.try_mul(inv_num_rows)?;
+    let phantom_out = loss.clone();
+    tape.try_alloc_grad(&logits)?;
+    tape.try_alloc_grad(&loss)?;
+    tape.add_backward_op(move |grads| {
+        let (grad_logits, grad_out) = grads.mut_and_ref(&logits, &phantom_out);
+        SoftmaxCrossEntropyKernel::backward(
+            &logits.device,
+            num_rows,
+            &logits,
+            &target_probs,
+            &logsumexp,
+            inv_num_rows,
+            grad_logits,
+            grad_out,
+        )
+    });
+    Ok(loss.put_tape(tape))
+}
+
+/// Same as [softmax_cross_entropy()], but with class indices as targets, so no one hot
+/// vectors are ever created. Row `b` of `logits` has the target class `targets[b]`.
+///
+/// This computes `-log_softmax(logits)[b][targets[b]]` averaged over the rows, and the
+/// gradient is directly computed as `(softmax(logits) - one_hot(targets)) / B`.
+///
+/// **Panics** if `targets` doesn't have one entry per row, or if any target is not `< C`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::sparse_cross_entropy};
+/// # let dev: Cpu = Default::default();
+/// let logits: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[0.0, 0.0], [1.0, 1.0]]);
+/// let loss = sparse_cross_entropy(logits, &[0, 1]);
+/// assert!((loss.array() - 2.0f32.ln()).abs() < 1e-6);
+/// ```
+pub fn sparse_cross_entropy<B: Dim, C: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    logits: Tensor<(B, C), E, D, T>,
+    targets: &[usize],
+) -> Tensor<Rank0, E, D, T> {
+    try_sparse_cross_entropy(logits, targets).unwrap()
+}
+
+/// Fallible version of [sparse_cross_entropy()]
+pub fn try_sparse_cross_entropy<B: Dim, C: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    logits: Tensor<(B, C), E, D, T>,
+    targets: &[usize],
+) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+    let (b, c) = logits.shape;
+    let (b, c) = (b.size(), c.size());
+    assert_eq!(targets.len(), b, "expected one target per row");
+    for &t in targets.iter() {
+        assert!(t < c, "target {t} is not < {c}");
+    }
+    let targets = targets.to_vec();
+    let grad_targets = targets.clone();
+    let inv_b = E::one() / E::from(b).unwrap();
+    // log(sum(exp(row))), computed stably
+    let logsumexp = |row: &[E]| {
+        let max = row.iter().fold(E::neg_infinity(), |m, &x| m.max(x));
+        max + row.iter().fold(E::zero(), |s, &x| s + (x - max).exp()).ln()
+    };
+    try_host_unary_op(
+        logits,
+        (),
+        move |l| {
+            let loss = l
+                .chunks_exact(c)
+                .zip(targets)
+                .fold(E::zero(), |loss, (row, t)| loss + logsumexp(row) - row[t]);
+            vec![loss * inv_b]
+        },
+        move |l, _, grad| {
+            let scale = grad[0] * inv_b;
+            let mut grad_l = vec![E::zero(); l.len()];
+            for ((g, row), t) in grad_l
+                .chunks_exact_mut(c)
+                .zip(l.chunks_exact(c))
+                .zip(grad_targets)
+            {
+                let lse = logsumexp(row);
+                for (g, &x) in g.iter_mut().zip(row) {
+                    *g = (x - lse).exp() * scale;
+                }
+                g[t] -= scale;
+            }
+            grad_l
+        },
+    )
+}
+
 /// [KL Divergence loss](https://en.wikipedia.org/wiki/Kullback%E2%80%93Leibler_divergence).
 /// This computes `(target_probs * (target_probs.log() - logits.log_softmax())).sum(-1).mean()`
 ///
@@ -123,6 +263,924 @@ pub fn binary_cross_entropy_with_logits_loss<S: Shape, E: Dtype, D: Device<E>, T
     logits.bce_with_logits(target_probs).mean()
 }
 
+/// [Total variation](https://en.wikipedia.org/wiki/Total_variation_denoising) of a
+/// `(C, H, W)` image: the sum of absolute differences between vertically and
+/// horizontally adjacent pixels. Used as a regularizer that favors smooth images.
+///
+/// The gradient of a pair of equal pixels is 0, and the loss of an empty image is 0.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::total_variation_loss};
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank3<1, 2, 2>, f32, _> = dev.tensor([[[0.0, 1.0], [2.0, 4.0]]]);
+/// assert_eq!(total_variation_loss(t).array(), 8.0);
+/// ```
+pub fn total_variation_loss<C: Dim, H: Dim, W: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(C, H, W), E, D, T>,
+) -> Tensor<Rank0, E, D, T> {
+    let (_, h, w) = t.shape;
+    if h.size() == 0 || w.size() == 0 {
+        // an empty image has no adjacent pixels
+        let (t, tape) = t.split_tape();
+        return t.device.zeros().put_tape(tape);
+    }
+    let up = t.retaped::<T>().slice((.., ..h.size() - 1, ..));
+    let down = t.retaped::<T>().slice((.., 1.., ..));
+    let left = t.retaped::<T>().slice((.., .., ..w.size() - 1));
+    let right = t.slice((.., .., 1..));
+    (down - up).abs().sum() + (right - left).abs().sum()
+}
+
+/// [Cosine embedding loss](https://pytorch.org/docs/stable/generated/torch.nn.CosineEmbeddingLoss.html)
+/// between two embeddings, for learning whether they are similar (`label = 1`) or
+/// dissimilar (`label = -1`).
+///
+/// With `cos` the cosine similarity of `a` and `b`, computes:
+/// 1. if `label == 1`: `1 - cos`
+/// 2. if `label == -1`: `max(0, cos - margin)`
+///
+/// If either embedding is all zeros, `cos` is treated as 0 with a gradient of 0.
+///
+/// **Panics** if `label` is not `1` or `-1`.
+///
+/// **Pytorch equivalent**: `F.cosine_embedding_loss(a, b, label, margin)`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::cosine_embedding_loss};
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 0.0]);
+/// let b: Tensor<Rank1<2>, f32, _> = dev.tensor([0.0, 2.0]);
+/// assert_eq!(cosine_embedding_loss(a.clone(), b.clone(), 1, 0.0).array(), 1.0);
+/// assert_eq!(cosine_embedding_loss(a, b, -1, 0.0).array(), 0.0);
+/// ```
+pub fn cosine_embedding_loss<S: Shape, E, D, T, R>(
+    a: Tensor<S, E, D, T>,
+    b: Tensor<S, E, D, R>,
+    label: i8,
+    margin: E,
+) -> Tensor<Rank0, E, D, T>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    assert!(
+        label == 1 || label == -1,
+        "label must be 1 or -1, got {label}"
+    );
+    // returns `(cos, |a| * |b|)`
+    let cos = |a: &[E], b: &[E]| {
+        let dot = a.iter().zip(b).fold(E::zero(), |s, (&a, &b)| s + a * b);
+        let norm_a = a.iter().fold(E::zero(), |s, &a| s + a * a).sqrt();
+        let norm_b = b.iter().fold(E::zero(), |s, &b| s + b * b).sqrt();
+        let norms = norm_a * norm_b;
+        if norms == E::zero() {
+            (E::zero(), norms)
+        } else {
+            (dot / norms, norms)
+        }
+    };
+    try_host_binary_op(
+        a,
+        b,
+        (),
+        move |a, b| {
+            let (cos, _) = cos(a, b);
+            let loss = if label == 1 {
+                E::one() - cos
+            } else {
+                (cos - margin).max(E::zero())
+            };
+            vec![loss]
+        },
+        move |a, b, _, grad| {
+            let (cos, norms) = cos(a, b);
+            let grad_cos = match label {
+                1 => -grad[0],
+                _ if cos > margin => grad[0],
+                _ => E::zero(),
+            };
+            if norms == E::zero() || grad_cos == E::zero() {
+                return (vec![E::zero(); a.len()], vec![E::zero(); b.len()]);
+            }
+            // d cos / da = b / (|a| |b|) - cos * a / |a|^2, and the same for b
+            let sq_a = a.iter().fold(E::zero(), |s, &a| s + a * a);
+            let sq_b = b.iter().fold(E::zero(), |s, &b| s + b * b);
+            let grad_a = a
+                .iter()
+                .zip(b)
+                .map(|(&a, &b)| grad_cos * (b / norms - cos * a / sq_a))
+                .collect();
+            let grad_b = a
+                .iter()
+                .zip(b)
+                .map(|(&a, &b)| grad_cos * (a / norms - cos * b / sq_b))
+                .collect();
+            (grad_a, grad_b)
+        },
+    )
+    .unwrap()
+}
+
+/// [Margin ranking loss](https://pytorch.org/docs/stable/generated/torch.nn.MarginRankingLoss.html),
+/// for learning that `x1` should be ranked higher than `x2` where `y == 1`, and lower
+/// where `y == -1`.
+///
+/// This computes `(-y * (x1 - x2) + margin).relu().mean()`, so pairs that are ordered
+/// correctly by at least `margin` have a loss & gradient of 0.
+///
+/// **Pytorch equivalent**: `F.margin_ranking_loss(x1, x2, y, margin)`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::margin_ranking_loss};
+/// # let dev: Cpu = Default::default();
+/// let x1: Tensor<Rank1<2>, f32, _> = dev.tensor([2.0, 1.0]);
+/// let x2: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 1.0]);
+/// let y = dev.tensor([1.0, 1.0]);
+/// assert_eq!(margin_ranking_loss(x1, x2, y, 1.0).array(), 0.5);
+/// ```
+pub fn margin_ranking_loss<S: Shape, E: Dtype, D: Device<E>, T, R>(
+    x1: Tensor<S, E, D, T>,
+    x2: Tensor<S, E, D, R>,
+    y: Tensor<S, E, D>,
+    margin: E,
+) -> Tensor<Rank0, E, D, T>
+where
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    (((x1 - x2) * y).negate() + margin).relu().mean()
+}
+
+/// [Hinge loss](https://en.wikipedia.org/wiki/Hinge_loss) for max-margin classifiers,
+/// where `targets` holds a label of `1` or `-1` for each element of `scores`.
+///
+/// This computes `(margin - y * scores).relu().mean()`, so scores on the correct side
+/// by at least `margin` have a loss & gradient of 0.
+///
+/// **Panics** if `targets` doesn't have one label per score, or if any label is not
+/// `1` or `-1`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::hinge_loss};
+/// # let dev: Cpu = Default::default();
+/// let scores: Tensor<Rank1<2>, f32, _> = dev.tensor([2.0, 0.5]);
+/// assert_eq!(hinge_loss(scores, &[1, -1], 1.0).array(), 0.75);
+/// ```
+pub fn hinge_loss<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    scores: Tensor<S, E, D, T>,
+    targets: &[i8],
+    margin: E,
+) -> Tensor<Rank0, E, D, T> {
+    assert_eq!(
+        targets.len(),
+        scores.shape.num_elements(),
+        "expected one target per score"
+    );
+    let y = targets
+        .iter()
+        .map(|&y| match y {
+            1 => E::ONE,
+            -1 => E::from_i8(-1).unwrap(),
+            _ => panic!("target must be 1 or -1, got {y}"),
+        })
+        .collect();
+    let y = scores.device.tensor_from_vec(y, scores.shape);
+    ((scores * y).negate() + margin).relu().mean()
+}
+
+/// The clipped surrogate objective of
+/// [Proximal Policy Optimization](https://arxiv.org/abs/1707.06347), negated to minimize.
+///
+/// With the probability ratio `r = exp(log_probs - old_log_probs)`, this computes
+/// `-min(r * advantages, clamp(r, 1 - clip_eps, 1 + clip_eps) * advantages).mean()`. Where the
+/// clipped term is the smaller one, the ratio has moved too far in the direction the advantage
+/// rewards, and the gradient is 0.
+///
+/// `old_log_probs` are the log probabilities of the actions under the policy that collected
+/// them, and `advantages` are e.g. from [crate::data::compute_gae()].
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::ppo_clip_loss};
+/// # let dev: Cpu = Default::default();
+/// let log_probs: Tensor<Rank1<2>, f32, _> = dev.tensor([0.0, 1.0]);
+/// let old_log_probs = dev.zeros();
+/// let advantages = dev.tensor([1.0, 1.0]);
+/// let loss = ppo_clip_loss(log_probs, old_log_probs, advantages, 0.2);
+/// assert_eq!(loss.array(), -1.1);
+/// ```
+pub fn ppo_clip_loss<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    log_probs: Tensor<S, E, D, T>,
+    old_log_probs: Tensor<S, E, D>,
+    advantages: Tensor<S, E, D>,
+    clip_eps: E,
+) -> Tensor<Rank0, E, D, T> {
+    let ratio = (log_probs - old_log_probs).exp();
+    let clipped = ratio
+        .retaped::<T>()
+        .clamp(E::ONE - clip_eps, E::ONE + clip_eps)
+        * advantages.clone();
+    (ratio * advantages).minimum(clipped).mean().negate()
+}
+
+/// [Dice loss](https://en.wikipedia.org/wiki/S%C3%B8rensen%E2%80%93Dice_coefficient) for
+/// segmentation of `(C, H, W)` masks. This computes
+/// `1 - (2 * sum(pred * target) + smooth) / (sum(pred) + sum(target) + smooth)` for each
+/// channel, and averages it over the channels.
+///
+/// `smooth` keeps the loss defined for empty masks.
+///
+/// # Arguments
+///
+/// - `pred`: Predicted probabilities (e.g. from [sigmoid()]) of each pixel being in the mask.
+/// - `target`: The true masks, usually made of 0s and 1s.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::dice_loss};
+/// # let dev: Cpu = Default::default();
+/// let pred: Tensor<Rank3<1, 1, 2>, f32, _> = dev.tensor([[[1.0, 0.0]]]);
+/// let target = dev.tensor([[[1.0, 1.0]]]);
+/// assert_eq!(dice_loss(pred, target, 0.0).array(), 1.0 - 2.0 / 3.0);
+/// ```
+pub fn dice_loss<C: Dim, H: Dim, W: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    pred: Tensor<(C, H, W), E, D, T>,
+    target: Tensor<(C, H, W), E, D>,
+    smooth: E,
+) -> Tensor<Rank0, E, D, T> {
+    let intersection = (pred.retaped::<T>() * target.clone()).sum::<(C,), Axes2<1, 2>>();
+    let total = pred.sum::<(C,), Axes2<1, 2>>() + target.sum();
+    let two = E::from_f32(2.0).unwrap();
+    let dice = (intersection * two + smooth) / (total + smooth);
+    (dice.negate() + E::ONE).mean()
+}
+
+/// [Generalized IoU loss](https://giou.stanford.edu/) for regressing boxes in
+/// `(x1, y1, x2, y2)` format. This computes `1 - GIoU` for each pair of boxes, and
+/// averages it over the `N` pairs, where
+/// `GIoU = IoU - (area(C) - area(union)) / area(C)` and `C` is the smallest box enclosing
+/// both boxes.
+///
+/// Unlike `1 - IoU`, the loss still has a gradient when the boxes don't overlap, which
+/// moves `pred_boxes` towards `target_boxes`. Boxes must have a positive area.
+///
+/// **Pytorch equivalent**: `torchvision.ops.generalized_box_iou_loss(pred_boxes, target_boxes, reduction="mean")`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::giou_loss};
+/// # let dev: Cpu = Default::default();
+/// let pred: Tensor<Rank2<1, 4>, f32, _> = dev.tensor([[0.0, 0.0, 1.0, 1.0]]);
+/// let target = dev.tensor([[1.0, 0.0, 2.0, 1.0]]);
+/// // IoU is 0, and the union covers the whole enclosing box
+/// assert_eq!(giou_loss(pred, target).array(), 1.0);
+/// ```
+pub fn giou_loss<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    pred_boxes: Tensor<(N, Const<4>), E, D, T>,
+    target_boxes: Tensor<(N, Const<4>), E, D>,
+) -> Tensor<Rank0, E, D, T> {
+    let targets = target_boxes.as_vec();
+    let grad_targets = targets.clone();
+    let inv_n = E::one() / E::from(pred_boxes.shape.0.size()).unwrap();
+    try_host_unary_op(
+        pred_boxes,
+        (),
+        move |pred| {
+            let loss = pred
+                .chunks_exact(4)
+                .zip(targets.chunks_exact(4))
+                .fold(E::zero(), |loss, (p, t)| loss + Giou::new(p, t).loss());
+            vec![loss * inv_n]
+        },
+        move |pred, _, grad| {
+            let mut grad_pred = std::vec::Vec::with_capacity(pred.len());
+            for (p, t) in pred.chunks_exact(4).zip(grad_targets.chunks_exact(4)) {
+                let g = Giou::new(p, t).grad();
+                grad_pred.extend(g.map(|g| g * grad[0] * inv_n));
+            }
+            grad_pred
+        },
+    )
+    .unwrap()
+}
+
+/// The terms of the GIoU of a predicted box `p` & target box `t`.
+struct Giou<'a, E> {
+    p: &'a [E],
+    t: &'a [E],
+    /// Width & height of the intersection
+    inter: [E; 2],
+    /// Width & height of the enclosing box
+    enclosing: [E; 2],
+}
+
+impl<'a, E: Float> Giou<'a, E> {
+    fn new(p: &'a [E], t: &'a [E]) -> Self {
+        let overlap = |i: usize| (p[i + 2].min(t[i + 2]) - p[i].max(t[i])).max(E::zero());
+        let enclosing = |i: usize| p[i + 2].max(t[i + 2]) - p[i].min(t[i]);
+        Self {
+            p,
+            t,
+            inter: [overlap(0), overlap(1)],
+            enclosing: [enclosing(0), enclosing(1)],
+        }
+    }
+
+    /// Returns `(area(intersection), area(union), area(enclosing))`
+    fn areas(&self) -> (E, E, E) {
+        let area = |b: &[E]| (b[2] - b[0]) * (b[3] - b[1]);
+        let inter = self.inter[0] * self.inter[1];
+        let union = area(self.p) + area(self.t) - inter;
+        (inter, union, self.enclosing[0] * self.enclosing[1])
+    }
+
+    /// `1 - GIoU = 2 - I / U - U / C`
+    fn loss(&self) -> E {
+        let (i, u, c) = self.areas();
+        E::one() + E::one() - i / u - u / c
+    }
+
+    /// Gradient of [Giou::loss] w.r.t. `p`. Where `p` and `t` share an edge, the edge of
+    /// `p` is treated as the one that bounds the intersection & enclosing box.
+    fn grad(&self) -> [E; 4] {
+        let (p, t) = (self.p, self.t);
+        let (i, u, c) = self.areas();
+        let grad_u = i / (u * u) - E::one() / c;
+        // the intersection is also subtracted from the union
+        let grad_i = -E::one() / u - grad_u;
+        let grad_c = u / (c * c);
+
+        let mut grad = [E::zero(); 4];
+        for axis in 0..2 {
+            let (lo, hi) = (axis, axis + 2);
+            let other = 1 - axis;
+            // the area of `p` and the intersection/enclosing box scale
+            // with their extent along the other axis
+            let extent = p[other + 2] - p[other];
+            let mut d_lo = -grad_u * extent;
+            let mut d_hi = grad_u * extent;
+            if self.inter[axis] > E::zero() {
+                if p[lo] >= t[lo] {
+                    d_lo = d_lo - grad_i * self.inter[other];
+                }
+                if p[hi] <= t[hi] {
+                    d_hi = d_hi + grad_i * self.inter[other];
+                }
+            }
+            if p[lo] <= t[lo] {
+                d_lo = d_lo - grad_c * self.enclosing[other];
+            }
+            if p[hi] >= t[hi] {
+                d_hi = d_hi + grad_c * self.enclosing[other];
+            }
+            grad[lo] = d_lo;
+            grad[hi] = d_hi;
+        }
+        grad
+    }
+}
+
+/// A weighted sum of losses that all take the same prediction & target. Each loss
+/// gets its own copy of the prediction, and the gradients of all of them are
+/// accumulated on the prediction's tape.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::*};
+/// # let dev: Cpu = Default::default();
+/// // logits of a segmentation mask
+/// let loss = CompoundLoss::new()
+///     .add(0.5, |p, t| dice_loss(p.sigmoid(), t, 1.0))
+///     .add(0.5, binary_cross_entropy_with_logits_loss);
+/// let logits: Tensor<Rank3<1, 2, 2>, f32, _> = dev.zeros();
+/// let target = dev.ones();
+/// let r = loss.forward(logits.leaky_trace(), target);
+/// let g = r.backward();
+/// ```
+pub struct CompoundLoss<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> {
+    #[allow(clippy::type_complexity)]
+    losses: std::vec::Vec<(
+        E,
+        std::boxed::Box<dyn Fn(Tensor<S, E, D, T>, Tensor<S, E, D>) -> Tensor<Rank0, E, D, T>>,
+    )>,
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Default for CompoundLoss<S, E, D, T> {
+    fn default() -> Self {
+        Self {
+            losses: Default::default(),
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> CompoundLoss<S, E, D, T> {
+    /// A compound loss without any losses, which are added with [CompoundLoss::add()].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds `loss` to the sum, scaled by `weight`.
+    pub fn add<F>(mut self, weight: E, loss: F) -> Self
+    where
+        F: 'static + Fn(Tensor<S, E, D, T>, Tensor<S, E, D>) -> Tensor<Rank0, E, D, T>,
+    {
+        self.losses.push((weight, std::boxed::Box::new(loss)));
+        self
+    }
+
+    /// The weighted sum of all the losses of `pred` & `target`.
+    ///
+    /// **Panics** if no losses were added.
+    pub fn forward(
+        &self,
+        pred: Tensor<S, E, D, T>,
+        target: Tensor<S, E, D>,
+    ) -> Tensor<Rank0, E, D, T> {
+        assert!(!self.losses.is_empty(), "CompoundLoss has no losses");
+        let (pred, tape) = pred.split_tape();
+        let total = self
+            .losses
+            .iter()
+            .map(|(weight, loss)| loss(pred.retaped::<T>(), target.clone()) * *weight)
+            .reduce(|total, loss| total + loss)
+            .unwrap();
+        let (total, total_tape) = total.split_tape();
+        total.put_tape(tape.merge(total_tape))
+    }
+}
+
+/// [Quantile loss](https://en.wikipedia.org/wiki/Quantile_regression) (also called
+/// pinball loss) for predicting the `q`th quantile of the target. With
+/// `err = targ - pred`, this computes `max(q * err, (q - 1) * err).mean()`.
+///
+/// Predictions below the target are penalized `q / (1 - q)` times as much as
+/// predictions above it, so `q = 0.5` is half of [mae_loss()].
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::quantile_loss};
+/// # let dev: Cpu = Default::default();
+/// let pred: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 3.0]);
+/// let targ = dev.tensor([2.0, 2.0]);
+/// assert_eq!(quantile_loss(pred, targ, 0.75).array(), (0.75 + 0.25) / 2.0);
+/// ```
+pub fn quantile_loss<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    pred: Tensor<S, E, D, T>,
+    targ: Tensor<S, E, D>,
+    q: E,
+) -> Tensor<Rank0, E, D, T> {
+    let over = pred - targ;
+    let under = over.retaped::<T>().negate().relu() * q;
+    (over.relu() * (E::ONE - q) + under).mean()
+}
+
+/// [Poisson](https://en.wikipedia.org/wiki/Poisson_distribution) negative log likelihood
+/// of count targets, given the log of the predicted rate. This computes
+/// `(log_rate.exp() - targ * log_rate).mean()`, which has the gradient
+/// `log_rate.exp() - targ`.
+///
+/// If `full` is true, this also adds the [Stirling approximation](https://en.wikipedia.org/wiki/Stirling%27s_approximation)
+/// of `ln(targ!)`, which is `targ * ln(targ) - targ + 0.5 * ln(2 * pi * targ)` for targets
+/// greater than 1, and 0 otherwise. It doesn't depend on `log_rate`, so only the value of
+/// the loss changes.
+///
+/// **Pytorch equivalent**: `F.poisson_nll_loss(log_rate, targ, log_input=True, full=full)`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::poisson_nll_loss};
+/// # let dev: Cpu = Default::default();
+/// let log_rate: Tensor<Rank1<2>, f32, _> = dev.tensor([0.0, 0.0]);
+/// let targ = dev.tensor([0.0, 1.0]);
+/// assert_eq!(poisson_nll_loss(log_rate, targ, false).array(), 1.0);
+/// ```
+pub fn poisson_nll_loss<S: Shape, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    log_rate: Tensor<S, E, D, T>,
+    targ: Tensor<S, E, D>,
+    full: bool,
+) -> Tensor<Rank0, E, D, T> {
+    let stirling = if full {
+        let targ = targ.as_vec();
+        let two_pi = E::from(2.0 * std::f64::consts::PI).unwrap();
+        let sum = targ
+            .iter()
+            .filter(|&&t| t > E::one())
+            .fold(E::zero(), |sum, &t| {
+                sum + t * t.ln() - t + (two_pi * t).ln() * E::from(0.5).unwrap()
+            });
+        sum / E::from(targ.len()).unwrap()
+    } else {
+        E::zero()
+    };
+    let nll = log_rate.retaped::<T>().exp() - log_rate * targ;
+    nll.mean() + stirling
+}
+
+/// [Gaussian](https://en.wikipedia.org/wiki/Normal_distribution) negative log likelihood
+/// of `targ`, given a predicted `mean` and variance `var` for each element. This computes
+/// `(0.5 * (var.ln() + (targ - mean)^2 / var)).mean()`, leaving out the constant term.
+///
+/// `var` is clamped to be at least `eps` for stability. Like pytorch, the clamp itself isn't
+/// differentiated: clamped elements get the gradient of `var = eps`, instead of none.
+///
+/// **Pytorch equivalent**: `F.gaussian_nll_loss(mean, targ, var, eps=eps)`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::gaussian_nll_loss};
+/// # let dev: Cpu = Default::default();
+/// let mean: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, -1.0]);
+/// let var = dev.tensor([1.0, 1.0]);
+/// let targ = dev.tensor([2.0, 0.0]);
+/// assert_eq!(gaussian_nll_loss(mean, var, targ, 1e-6).array(), 0.5);
+/// ```
+pub fn gaussian_nll_loss<S: Shape, E, D, T, R>(
+    mean: Tensor<S, E, D, T>,
+    var: Tensor<S, E, D, R>,
+    targ: Tensor<S, E, D>,
+    eps: E,
+) -> Tensor<Rank0, E, D, T>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    try_gaussian_nll_loss(mean, var, targ, eps).unwrap()
+}
+
+/// Fallible version of [gaussian_nll_loss()]
+pub fn try_gaussian_nll_loss<S: Shape, E, D, T, R>(
+    mean: Tensor<S, E, D, T>,
+    var: Tensor<S, E, D, R>,
+    targ: Tensor<S, E, D>,
+    eps: E,
+) -> Result<Tensor<Rank0, E, D, T>, D::Err>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    // clamps the value, but passes the gradient through unchanged like pytorch's `no_grad` clamp
+    let (var, tape) = var.split_tape();
+    let offset = var
+        .clone()
+        .try_clamp(eps, E::infinity())?
+        .try_sub(var.clone())?;
+    let var = var.put_tape(tape).try_add(offset)?;
+    let sq_err = mean
+        .try_sub(targ)?
+        .try_square()?
+        .try_div(var.retaped::<R>())?;
+    sq_err
+        .try_add(var.try_ln()?)?
+        .try_mul(E::from(0.5).unwrap())?
+        .try_mean()
+}
+
+/// The reconstruction term of [vae_loss()].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reconstruction {
+    /// [mse_loss()], for real valued data.
+    Mse,
+    /// [binary_cross_entropy_with_logits_loss()], for data in `[0, 1]` like binarized
+    /// images. The reconstruction must be **logits**, not the output of a sigmoid.
+    BceWithLogits,
+}
+
+/// The negative evidence lower bound of a
+/// [variational autoencoder](https://arxiv.org/abs/1312.6114), which is the
+/// `reconstruction` loss of `recon` against `target`, plus the KL divergence between the
+/// encoder's `N(mean, exp(logvar))` and the prior `N(0, 1)`.
+///
+/// The KL divergence is computed analytically as
+/// `(0.5 * (mean^2 + exp(logvar) - logvar - 1)).mean()`. Both terms are averaged over their
+/// elements, like the other losses.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::{vae_loss, Reconstruction}};
+/// # let dev: Cpu = Default::default();
+/// let recon: Tensor<Rank2<2, 4>, f32, _> = dev.zeros();
+/// let target = dev.zeros();
+/// let mean: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+/// let logvar = dev.zeros();
+/// let loss = vae_loss(recon, target, mean, logvar, Reconstruction::Mse);
+/// assert_eq!(loss.array(), 0.0);
+/// ```
+pub fn vae_loss<S: Shape, L: Shape, E, D, T>(
+    recon: Tensor<S, E, D, T>,
+    target: Tensor<S, E, D>,
+    mean: Tensor<L, E, D, T>,
+    logvar: Tensor<L, E, D, T>,
+    reconstruction: Reconstruction,
+) -> Tensor<Rank0, E, D, T>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    T: Tape<E, D> + Merge<T>,
+{
+    let recon_loss = match reconstruction {
+        Reconstruction::Mse => mse_loss(recon, target),
+        Reconstruction::BceWithLogits => binary_cross_entropy_with_logits_loss(recon, target),
+    };
+    let kl =
+        (mean.square() + logvar.retaped::<T>().exp() - logvar - E::one()) * E::from(0.5).unwrap();
+    recon_loss + kl.mean()
+}
+
+/// [Connectionist temporal classification](https://distill.pub/2017/ctc/) loss, for
+/// training on sequences that aren't aligned with their targets.
+///
+/// Computes the negative log likelihood of each target under all of its alignments
+/// to the input, divided by the target length, and averages that over the batch.
+///
+/// # Arguments
+/// - `log_probs`: `(L, B, C)` log probabilities (e.g. from [log_softmax()]) of each of
+///   `C` classes at each of the `L` timesteps.
+/// - `targets`: The target sequences of the whole batch concatenated together. Must
+///   not contain `blank`.
+/// - `input_lengths`: The number of timesteps of each sequence in the batch. The
+///   remaining timesteps have a gradient of 0.
+/// - `target_lengths`: The length of each target sequence in `targets`.
+/// - `blank`: The class that separates repeated labels and fills the gaps between
+///   labels.
+/// - `zero_infinity`: Whether the loss of a sequence that can't be aligned with its
+///   target is 0 instead of infinite.
+///
+/// A target is too long to be aligned with its input if it needs more than one timestep
+/// per label plus one per pair of repeated consecutive labels. The gradient of such a
+/// sequence is 0 either way.
+///
+/// **Pytorch equivalent**: `F.ctc_loss(log_probs, targets, input_lengths, target_lengths, blank, zero_infinity=zero_infinity)`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, losses::ctc_loss};
+/// # let dev: Cpu = Default::default();
+/// let probs: Tensor<Rank3<2, 1, 2>, f32, _> = dev.tensor([[[0.5, 0.5]], [[0.5, 0.5]]]);
+/// // "1" is produced by the alignments "11", "01" and "10", which have probability 1/4 each
+/// let loss = ctc_loss(probs.ln(), &[1], &[2], &[1], 0, false);
+/// assert!((loss.array() - (4.0f32 / 3.0).ln()).abs() < 1e-6);
+/// ```
+pub fn ctc_loss<L: Dim, B: Dim, C: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    log_probs: Tensor<(L, B, C), E, D, T>,
+    targets: &[usize],
+    input_lengths: &[usize],
+    target_lengths: &[usize],
+    blank: usize,
+    zero_infinity: bool,
+) -> Tensor<Rank0, E, D, T> {
+    try_ctc_loss(
+        log_probs,
+        targets,
+        input_lengths,
+        target_lengths,
+        blank,
+        zero_infinity,
+    )
+    .unwrap()
+}
+
+/// Fallible version of [ctc_loss()]. Returns a [CtcLossError] if the lengths, targets
+/// or blank don't fit `log_probs`.
+pub fn try_ctc_loss<L: Dim, B: Dim, C: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    log_probs: Tensor<(L, B, C), E, D, T>,
+    targets: &[usize],
+    input_lengths: &[usize],
+    target_lengths: &[usize],
+    blank: usize,
+    zero_infinity: bool,
+) -> Result<Tensor<Rank0, E, D, T>, CtcLossError<D::Err>> {
+    let (l, b, c) = log_probs.shape;
+    let ctc = Ctc {
+        shape: (l.size(), b.size(), c.size()),
+        targets: targets.to_vec(),
+        input_lengths: input_lengths.to_vec(),
+        target_lengths: target_lengths.to_vec(),
+        blank,
+        zero_infinity,
+    };
+    ctc.validate()?;
+    let loss = ctc.clone();
+    let loss = try_host_unary_op(
+        log_probs,
+        (),
+        move |lp| vec![loss.loss(lp)],
+        move |lp, _, grad| ctc.grad(lp, grad[0]),
+    )?;
+    Ok(loss)
+}
+
+/// An error from [try_ctc_loss()].
+#[derive(Debug)]
+pub enum CtcLossError<Err> {
+    /// `input_lengths` doesn't have one length per batch item.
+    WrongNumInputLengths,
+    /// `target_lengths` doesn't have one length per batch item.
+    WrongNumTargetLengths,
+    /// `targets` doesn't have the total length of `target_lengths`.
+    WrongNumTargets,
+    /// `blank` isn't one of the classes.
+    InvalidBlank(usize),
+    /// This input length is 0 or longer than the number of timesteps.
+    InvalidInputLength(usize),
+    /// This target is `blank` or isn't one of the classes.
+    InvalidTarget(usize),
+    /// The device failed to compute the loss.
+    Device(Err),
+}
+
+impl<Err> From<Err> for CtcLossError<Err> {
+    fn from(e: Err) -> Self {
+        Self::Device(e)
+    }
+}
+
+impl<Err: std::fmt::Display> std::fmt::Display for CtcLossError<Err> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongNumInputLengths => write!(fmt, "expected one input length per batch item"),
+            Self::WrongNumTargetLengths => {
+                write!(fmt, "expected one target length per batch item")
+            }
+            Self::WrongNumTargets => write!(
+                fmt,
+                "targets must have the total length of all target_lengths"
+            ),
+            Self::InvalidBlank(blank) => write!(fmt, "blank {blank} is not one of the classes"),
+            Self::InvalidInputLength(len) => write!(fmt, "invalid input length {len}"),
+            Self::InvalidTarget(t) => write!(fmt, "invalid target {t}"),
+            Self::Device(err) => write!(fmt, "{err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Err: std::fmt::Debug + std::fmt::Display> std::error::Error for CtcLossError<Err> {}
+
+#[derive(Clone)]
+struct Ctc {
+    shape: (usize, usize, usize),
+    targets: std::vec::Vec<usize>,
+    input_lengths: std::vec::Vec<usize>,
+    target_lengths: std::vec::Vec<usize>,
+    blank: usize,
+    zero_infinity: bool,
+}
+
+/// Log space forward (`alpha`) & backward (`beta`) variables of a single sequence,
+/// each of shape `(input_length, labels.len())`.
+struct CtcLattice<E> {
+    labels: std::vec::Vec<usize>,
+    alpha: std::vec::Vec<E>,
+    beta: std::vec::Vec<E>,
+    log_likelihood: E,
+}
+
+fn log_add<E: Float>(a: E, b: E) -> E {
+    let m = a.max(b);
+    if m == E::neg_infinity() {
+        m
+    } else {
+        m + ((a - m).exp() + (b - m).exp()).ln()
+    }
+}
+
+impl Ctc {
+    fn validate<Err>(&self) -> Result<(), CtcLossError<Err>> {
+        let (l, b, c) = self.shape;
+        if self.input_lengths.len() != b {
+            return Err(CtcLossError::WrongNumInputLengths);
+        }
+        if self.target_lengths.len() != b {
+            return Err(CtcLossError::WrongNumTargetLengths);
+        }
+        if self.targets.len() != self.target_lengths.iter().sum::<usize>() {
+            return Err(CtcLossError::WrongNumTargets);
+        }
+        if self.blank >= c {
+            return Err(CtcLossError::InvalidBlank(self.blank));
+        }
+        if let Some(&len) = self.input_lengths.iter().find(|&&len| len == 0 || len > l) {
+            return Err(CtcLossError::InvalidInputLength(len));
+        }
+        if let Some(&t) = self.targets.iter().find(|&&t| t >= c || t == self.blank) {
+            return Err(CtcLossError::InvalidTarget(t));
+        }
+        Ok(())
+    }
+
+    /// Index of `log_probs[t][i][k]`
+    fn idx(&self, t: usize, i: usize, k: usize) -> usize {
+        let (_, b, c) = self.shape;
+        (t * b + i) * c + k
+    }
+
+    fn lattice<E: Float>(&self, lp: &[E], i: usize) -> CtcLattice<E> {
+        let start: usize = self.target_lengths[..i].iter().sum();
+        let target = &self.targets[start..start + self.target_lengths[i]];
+
+        // blanks are inserted before, between and after every label
+        let mut labels = vec![self.blank; 2 * target.len() + 1];
+        for (k, &t) in target.iter().enumerate() {
+            labels[2 * k + 1] = t;
+        }
+        let n = labels.len();
+        let steps = self.input_lengths[i];
+        // a label can be skipped over from two steps back, unless it is a blank or a repeat
+        let can_skip = |s: usize, prev: usize| labels[s] != self.blank && labels[s] != labels[prev];
+
+        let mut alpha = vec![E::neg_infinity(); steps * n];
+        alpha[0] = lp[self.idx(0, i, labels[0])];
+        if n > 1 {
+            alpha[1] = lp[self.idx(0, i, labels[1])];
+        }
+        for t in 1..steps {
+            for s in 0..n {
+                let mut a = alpha[(t - 1) * n + s];
+                if s > 0 {
+                    a = log_add(a, alpha[(t - 1) * n + s - 1]);
+                }
+                if s > 1 && can_skip(s, s - 2) {
+                    a = log_add(a, alpha[(t - 1) * n + s - 2]);
+                }
+                alpha[t * n + s] = a + lp[self.idx(t, i, labels[s])];
+            }
+        }
+
+        let mut beta = vec![E::neg_infinity(); steps * n];
+        let last = steps - 1;
+        beta[last * n + n - 1] = lp[self.idx(last, i, labels[n - 1])];
+        if n > 1 {
+            beta[last * n + n - 2] = lp[self.idx(last, i, labels[n - 2])];
+        }
+        for t in (0..last).rev() {
+            for s in 0..n {
+                let mut b = beta[(t + 1) * n + s];
+                if s + 1 < n {
+                    b = log_add(b, beta[(t + 1) * n + s + 1]);
+                }
+                if s + 2 < n && can_skip(s, s + 2) {
+                    b = log_add(b, beta[(t + 1) * n + s + 2]);
+                }
+                beta[t * n + s] = b + lp[self.idx(t, i, labels[s])];
+            }
+        }
+
+        let mut log_likelihood = alpha[last * n + n - 1];
+        if n > 1 {
+            log_likelihood = log_add(log_likelihood, alpha[last * n + n - 2]);
+        }
+        CtcLattice {
+            labels,
+            alpha,
+            beta,
+            log_likelihood,
+        }
+    }
+
+    /// `1 / (batch_size * target_length)`, the weight of each sequence in the mean
+    fn weight<E: Float>(&self, i: usize) -> E {
+        let (_, b, _) = self.shape;
+        E::one() / E::from(b * self.target_lengths[i].max(1)).unwrap()
+    }
+
+    fn loss<E: Float>(&self, lp: &[E]) -> E {
+        (0..self.shape.1).fold(E::zero(), |loss, i| {
+            let log_likelihood = self.lattice(lp, i).log_likelihood;
+            if self.zero_infinity && log_likelihood == E::neg_infinity() {
+                loss
+            } else {
+                loss - log_likelihood * self.weight(i)
+            }
+        })
+    }
+
+    fn grad<E: Float>(&self, lp: &[E], grad_loss: E) -> std::vec::Vec<E> {
+        let (_, b, c) = self.shape;
+        let mut grad = vec![E::zero(); lp.len()];
+        for i in 0..b {
+            let lattice = self.lattice(lp, i);
+            if lattice.log_likelihood == E::neg_infinity() {
+                // no alignment is possible, so there is nothing to increase the likelihood of
+                continue;
+            }
+            let n = lattice.labels.len();
+            let scale = grad_loss * self.weight(i);
+            for t in 0..self.input_lengths[i] {
+                // log of the total probability of the alignments that emit `k` at `t`
+                let mut emitted = vec![E::neg_infinity(); c];
+                for (s, &k) in lattice.labels.iter().enumerate() {
+                    let ab = lattice.alpha[t * n + s] + lattice.beta[t * n + s];
+                    emitted[k] = log_add(emitted[k], ab);
+                }
+                for (k, e) in emitted.into_iter().enumerate() {
+                    let j = self.idx(t, i, k);
+                    // alpha & beta both include lp[j], so subtract it once
+                    let p = (e - lp[j] - lattice.log_likelihood).exp();
+                    grad[j] = -p * scale;
+                }
+            }
+        }
+        grad
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +1245,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_softmax_cross_entropy() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<4, 6>, TestDtype, _> = dev.sample_normal();
+        let y: Tensor<Rank2<4, 6>, TestDtype, _> =
+            dev.sample_normal::<Rank2<4, 6>>().softmax::<Axis<1>>();
+
+        let chain = cross_entropy_with_logits_loss(x.leaky_trace(), y.clone());
+        let single = softmax_cross_entropy(x.leaky_trace(), y.clone());
+        assert_close(&single.array(), &chain.array());
+
+        let g_chain = chain.backward();
+        let g_single = single.backward();
+        assert_close(&g_single.get(&x).array(), &g_chain.get(&x).array());
+
+        // (softmax - onehot) / B
+        let probs = x.clone().softmax::<Axis<1>>().array();
+        let targs = y.array();
+        let mut expected = [[0.0; 6]; 4];
+        for i in 0..4 {
+            for j in 0..6 {
+                expected[i][j] = (probs[i][j] - targs[i][j]) / 4.0;
+            }
+        }
+        assert_close(&g_single.get(&x).array(), &expected);
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_chained() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 3, 5>, TestDtype, _> = dev.sample_normal();
+        let y: Tensor<Rank3<2, 3, 5>, TestDtype, _> =
+            dev.sample_normal::<Rank3<2, 3, 5>>().softmax::<Axis<2>>();
+
+        let chain = cross_entropy_with_logits_loss(x.leaky_trace() * 2.0, y.clone()).sqrt();
+        let single = softmax_cross_entropy(x.leaky_trace() * 2.0, y.clone()).sqrt();
+        assert_close(&single.array(), &chain.array());
+        assert_close(
+            &single.backward().get(&x).array(),
+            &chain.backward().get(&x).array(),
+        );
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_records_single_op() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<3, 7>, TestDtype, _> = dev.sample_normal();
+        // targets that don't sum to 1 still match the unfused version
+        let y: Tensor<Rank2<3, 7>, TestDtype, _> = dev.sample_uniform();
+
+        let chain = cross_entropy_with_logits_loss(x.leaky_trace(), y.clone());
+        let single = softmax_cross_entropy(x.leaky_trace(), y);
+        assert_eq!(single.tape.operations.len(), 1);
+        assert!(chain.tape.operations.len() > 1);
+        assert_close(&single.array(), &chain.array());
+        assert_close(
+            &single.backward().get(&x).array(),
+            &chain.backward().get(&x).array(),
+        );
+    }
+
+    #[test]
+    fn test_softmax_cross_entropy_non_contiguous() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<5, 3>, TestDtype, _> = dev.sample_normal();
+        let y: Tensor<Rank2<3, 5>, TestDtype, _> =
+            dev.sample_normal::<Rank2<3, 5>>().softmax::<Axis<1>>();
+        let chain = cross_entropy_with_logits_loss(x.leaky_trace().permute(), y.clone());
+        let single = softmax_cross_entropy(x.leaky_trace().permute(), y);
+        assert_close(&single.array(), &chain.array());
+        assert_close(
+            &single.backward().get(&x).array(),
+            &chain.backward().get(&x).array(),
+        );
+    }
+
     #[test]
     fn test_hard_crossentropy() {
         let dev: TestDevice = Default::default();
@@ -373,4 +1507,551 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_total_variation_constant_image() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.ones();
+        let loss = total_variation_loss(x.leaky_trace() * 2.5);
+        assert_eq!(loss.array(), 0.0);
+        let g = loss.backward();
+        assert_eq!(g.get(&x).array(), [[[0.0; 4]; 3]; 2]);
+    }
+
+    #[test]
+    fn test_total_variation_empty_image() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<(Const<2>, usize, Const<3>), TestDtype, _> =
+            dev.zeros_like(&(Const, 0, Const));
+        assert_eq!(total_variation_loss(x.leaky_trace()).array(), 0.0);
+        let y: Tensor<(Const<2>, Const<3>, usize), TestDtype, _> =
+            dev.zeros_like(&(Const, Const, 0));
+        assert_eq!(total_variation_loss(y.leaky_trace()).array(), 0.0);
+    }
+
+    #[test]
+    fn test_total_variation_gradient_image() {
+        let dev: TestDevice = Default::default();
+        // increases by 1 to the right and by 2 downwards
+        let x: Tensor<Rank3<1, 3, 3>, TestDtype, _> =
+            dev.tensor([[[0.0, 1.0, 2.0], [2.0, 3.0, 4.0], [4.0, 5.0, 6.0]]]);
+        let loss = total_variation_loss(x.leaky_trace());
+        // 6 vertical pairs with difference 2, and 6 horizontal pairs with difference 1
+        assert_close(&loss.array(), &18.0);
+
+        // each pixel gets +1 per neighbour below or to its right that is larger,
+        // and -1 per neighbour above or to its left that is smaller
+        let g = loss.backward();
+        assert_close(
+            &g.get(&x).array(),
+            &[[[-2.0, -1.0, 0.0], [-1.0, 0.0, 1.0], [0.0, 1.0, 2.0]]],
+        );
+    }
+
+    #[test]
+    fn test_ctc_single_label() {
+        let dev: TestDevice = Default::default();
+        let probs: Tensor<Rank3<2, 1, 3>, TestDtype, _> =
+            dev.tensor([[[0.5, 0.3, 0.2]], [[0.4, 0.4, 0.2]]]);
+        let x = probs.ln();
+        let loss = ctc_loss(x.leaky_trace(), &[1], &[2], &[1], 0, false);
+        // the alignments of "1" are "11", "01" and "10":
+        // 0.3 * 0.4 + 0.5 * 0.4 + 0.3 * 0.4 = 0.44
+        assert_close(&loss.array(), &-TestDtype::ln(0.44));
+
+        // each gradient is minus the fraction of the probability that goes through it
+        let g = loss.backward();
+        assert_close(
+            &g.get(&x).array(),
+            &[
+                [[-0.2 / 0.44, -0.24 / 0.44, 0.0]],
+                [[-0.12 / 0.44, -0.32 / 0.44, 0.0]],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_ctc_repeated_labels_and_lengths() {
+        let dev: TestDevice = Default::default();
+        let probs: Tensor<Rank3<3, 2, 3>, TestDtype, _> = dev.tensor([
+            [[0.2, 0.7, 0.1], [0.1, 0.1, 0.8]],
+            [[0.6, 0.3, 0.1], [0.3, 0.3, 0.4]],
+            [[0.1, 0.5, 0.4], [0.3, 0.3, 0.4]],
+        ]);
+        let x = probs.ln();
+        // "11" needs a blank between the labels, so with 3 steps it can only be "101".
+        // the second sequence only has 1 step, so "2" can only be "2".
+        let loss = ctc_loss(x.leaky_trace(), &[1, 1, 2], &[3, 1], &[2, 1], 0, false);
+        let nll_0 = -TestDtype::ln(0.7 * 0.6 * 0.5);
+        let nll_1 = -TestDtype::ln(0.8);
+        assert_close(&loss.array(), &((nll_0 / 2.0 + nll_1) / 2.0));
+
+        let g = loss.backward();
+        assert_close(
+            &g.get(&x).array(),
+            &[
+                [[0.0, -0.25, 0.0], [0.0, 0.0, -0.5]],
+                [[-0.25, 0.0, 0.0], [0.0; 3]],
+                [[0.0, -0.25, 0.0], [0.0; 3]],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_ctc_impossible_alignment() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<1, 2, 2>, TestDtype, _> = dev.tensor([[[0.5, 0.5], [0.25, 0.75]]]).ln();
+        // "11" needs 3 steps, but the first sequence only has 1
+        let loss = ctc_loss(x.leaky_trace(), &[1, 1, 1], &[1, 1], &[2, 1], 0, false);
+        assert_eq!(loss.array(), TestDtype::INFINITY);
+        let g = loss.backward();
+        assert!(g.get(&x).as_vec().iter().all(|g| g.is_finite()));
+
+        let loss = ctc_loss(x.leaky_trace(), &[1, 1, 1], &[1, 1], &[2, 1], 0, true);
+        assert_close(&loss.array(), &(-TestDtype::ln(0.75) / 2.0));
+        let g = loss.backward();
+        assert_close(&g.get(&x).array(), &[[[0.0, 0.0], [0.0, -0.5]]]);
+    }
+
+    #[test]
+    fn test_ctc_invalid_inputs() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 1, 3>, TestDtype, _> = dev.zeros();
+        let check = |targets: &[usize], input_lengths: &[usize], target_lengths: &[usize]| {
+            try_ctc_loss(x.clone(), targets, input_lengths, target_lengths, 0, false)
+        };
+        assert!(matches!(
+            check(&[1], &[2, 2], &[1]),
+            Err(CtcLossError::WrongNumInputLengths)
+        ));
+        assert!(matches!(
+            check(&[1], &[2], &[1, 1]),
+            Err(CtcLossError::WrongNumTargetLengths)
+        ));
+        assert!(matches!(
+            check(&[1, 2], &[2], &[1]),
+            Err(CtcLossError::WrongNumTargets)
+        ));
+        assert!(matches!(
+            check(&[1], &[3], &[1]),
+            Err(CtcLossError::InvalidInputLength(3))
+        ));
+        assert!(matches!(
+            check(&[0], &[2], &[1]),
+            Err(CtcLossError::InvalidTarget(0))
+        ));
+        assert!(matches!(
+            check(&[3], &[2], &[1]),
+            Err(CtcLossError::InvalidTarget(3))
+        ));
+        assert!(matches!(
+            try_ctc_loss(x.clone(), &[1], &[2], &[1], 3, false),
+            Err(CtcLossError::InvalidBlank(3))
+        ));
+    }
+
+    #[test]
+    fn test_sparse_cross_entropy_matches_one_hot() {
+        let dev: TestDevice = Default::default();
+        let logits: Tensor<Rank2<3, 4>, TestDtype, _> = dev.tensor([
+            [0.5, -1.0, 2.0, 0.1],
+            [-0.3, 0.0, 1.5, -2.0],
+            [100.0, 101.0, 99.0, 100.0],
+        ]);
+        let targets = [2, 0, 1];
+        let one_hot: Tensor<Rank2<3, 4>, TestDtype, _> = dev.tensor([
+            [0.0, 0.0, 1.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+        ]);
+
+        let sparse = sparse_cross_entropy(logits.leaky_trace(), &targets);
+        let dense = softmax_cross_entropy(logits.leaky_trace(), one_hot);
+        assert_close(&sparse.array(), &dense.array());
+        let fallible = try_sparse_cross_entropy(logits.clone(), &targets).unwrap();
+        assert_eq!(fallible.array(), sparse.array());
+
+        let g_sparse = sparse.backward();
+        let g_dense = dense.backward();
+        assert_close(
+            &g_sparse.get(&logits).array(),
+            &g_dense.get(&logits).array(),
+        );
+    }
+
+    #[test]
+    #[should_panic = "target 4 is not < 4"]
+    fn test_sparse_cross_entropy_invalid_target() {
+        let dev: TestDevice = Default::default();
+        let logits: Tensor<Rank2<2, 4>, TestDtype, _> = dev.zeros();
+        sparse_cross_entropy(logits, &[0, 4]);
+    }
+
+    #[test]
+    fn test_cosine_embedding_positive_pair() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, -2.0, 0.5]);
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, -2.0, 0.5]);
+        let loss = cosine_embedding_loss(a.leaky_trace(), b.leaky_trace(), 1, 0.0);
+        assert_close(&loss.array(), &0.0);
+
+        // identical vectors are already as similar as possible
+        let g = loss.backward();
+        assert_close(&g.get(&a).array(), &[0.0; 3]);
+        assert_close(&g.get(&b).array(), &[0.0; 3]);
+    }
+
+    #[test]
+    fn test_cosine_embedding_negative_pair() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 0.0]);
+        let b: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 1.0]);
+        let loss = cosine_embedding_loss(a.leaky_trace(), b.leaky_trace(), -1, 0.5);
+        let cos = TestDtype::sqrt(0.5);
+        assert_close(&loss.array(), &(cos - 0.5));
+
+        let g = loss.backward();
+        assert_close(&g.get(&a).array(), &[0.0, cos]);
+        assert_close(&g.get(&b).array(), &[0.5 * cos, -0.5 * cos]);
+
+        // within the margin the pair is dissimilar enough
+        let loss = cosine_embedding_loss(a.leaky_trace(), b, -1, 0.75);
+        assert_eq!(loss.array(), 0.0);
+        assert_eq!(loss.backward().get(&a).array(), [0.0; 2]);
+    }
+
+    #[test]
+    fn test_margin_ranking_correct_order() {
+        let dev: TestDevice = Default::default();
+        let x1: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([2.0, 3.0, -1.0]);
+        let x2: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 1.0, 1.0]);
+        let y = dev.tensor([1.0, 1.0, -1.0]);
+        let loss = margin_ranking_loss(x1.leaky_trace(), x2.leaky_trace(), y, 0.5);
+        assert_eq!(loss.array(), 0.0);
+
+        let g = loss.backward();
+        assert_eq!(g.get(&x1).array(), [0.0; 3]);
+        assert_eq!(g.get(&x2).array(), [0.0; 3]);
+    }
+
+    #[test]
+    fn test_margin_ranking_violates_margin() {
+        let dev: TestDevice = Default::default();
+        let x1: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 0.0]);
+        let x2: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.8, 1.0]);
+        let y = dev.tensor([1.0, -1.0]);
+        // only the first pair is within the margin: -(1.0 - 0.8) + 0.5 = 0.3
+        let loss = margin_ranking_loss(x1.leaky_trace(), x2.leaky_trace(), y, 0.5);
+        assert_close(&loss.array(), &0.15);
+
+        let g = loss.backward();
+        assert_close(&g.get(&x1).array(), &[-0.5, 0.0]);
+        assert_close(&g.get(&x2).array(), &[0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_dice_loss_perfect_and_disjoint() {
+        let dev: TestDevice = Default::default();
+        let mask: Tensor<Rank3<2, 2, 2>, TestDtype, _> =
+            dev.tensor([[[1.0, 0.0], [1.0, 1.0]], [[0.0, 0.0], [0.0, 1.0]]]);
+        let inverted = mask.clone().negate() + 1.0;
+
+        let loss = dice_loss(mask.clone(), mask.clone(), 1e-6);
+        assert_close(&loss.array(), &0.0);
+
+        let loss = dice_loss(inverted, mask, 1e-6);
+        assert_close(&loss.array(), &1.0);
+    }
+
+    #[test]
+    fn test_dice_loss_grad() {
+        let dev: TestDevice = Default::default();
+        let pred: Tensor<Rank3<1, 1, 2>, TestDtype, _> = dev.tensor([[[0.5, 0.5]]]);
+        let target = dev.tensor([[[1.0, 0.0]]]);
+        // 1 - 2a / (a + b + 1), where a & b are the predictions
+        let loss = dice_loss(pred.leaky_trace(), target, 0.0);
+        assert_close(&loss.array(), &0.5);
+
+        let g = loss.backward();
+        assert_close(&g.get(&pred).array(), &[[[-0.75, 0.25]]]);
+    }
+
+    #[test]
+    fn test_giou_loss_identical_boxes() {
+        let dev: TestDevice = Default::default();
+        let boxes: Tensor<Rank2<2, 4>, TestDtype, _> =
+            dev.tensor([[0.0, 0.0, 1.0, 1.0], [-1.0, 2.0, 3.0, 2.5]]);
+        let loss = giou_loss(boxes.clone(), boxes);
+        assert_close(&loss.array(), &0.0);
+    }
+
+    #[test]
+    fn test_giou_loss_disjoint_boxes() {
+        let dev: TestDevice = Default::default();
+        let pred: Tensor<Rank2<1, 4>, TestDtype, _> = dev.tensor([[0.0, 0.0, 1.0, 1.0]]);
+        let target = dev.tensor([[2.0, 0.5, 3.0, 1.5]]);
+        // I = 0, U = 2, C = 3 * 1.5, so the loss is 1 + (C - U) / C
+        let loss = giou_loss(pred.leaky_trace(), target);
+        assert_close(&loss.array(), &(14.0 / 9.0));
+
+        // growing towards the target reduces the loss
+        let g = loss.backward();
+        assert_close(
+            &g.get(&pred).array(),
+            &[[2.0 / 27.0, -2.0 / 27.0, -2.0 / 9.0, -2.0 / 9.0]],
+        );
+    }
+
+    #[test]
+    fn test_giou_loss_grad_finite_differences() {
+        let dev: TestDevice = Default::default();
+        let pred = [[0.0, 0.0, 2.0, 1.5], [1.0, -1.0, 2.5, 0.5]];
+        let target = [[0.5, 0.25, 3.0, 1.0], [0.0, 0.0, 2.0, 1.0]];
+        let f = |p: &[[f64; 4]; 2]| {
+            let sum: f64 = (0..2).map(|i| Giou::new(&p[i], &target[i]).loss()).sum();
+            sum / 2.0
+        };
+
+        let x = dev.tensor(pred).to_dtype::<TestDtype>();
+        let t = dev.tensor(target).to_dtype::<TestDtype>();
+        let loss = giou_loss(x.leaky_trace(), t);
+        assert_close(&loss.array(), &(f(&pred) as TestDtype));
+
+        let eps = 1e-6;
+        let mut expected = [[0.0; 4]; 2];
+        for i in 0..2 {
+            for j in 0..4 {
+                let (mut hi, mut lo) = (pred, pred);
+                hi[i][j] += eps;
+                lo[i][j] -= eps;
+                expected[i][j] = ((f(&hi) - f(&lo)) / (2.0 * eps)) as TestDtype;
+            }
+        }
+        let g = loss.backward();
+        assert_close_with_tolerance(&g.get(&x).array(), &expected, 1e-4);
+    }
+
+    #[test]
+    fn test_compound_loss_weighted_sum() {
+        let dev: TestDevice = Default::default();
+        let pred: Tensor<Rank3<1, 2, 2>, TestDtype, _> = dev.tensor([[[0.9, 0.2], [0.4, 0.7]]]);
+        let target: Tensor<Rank3<1, 2, 2>, TestDtype, _> = dev.tensor([[[1.0, 0.0], [1.0, 0.0]]]);
+        let losses = CompoundLoss::new()
+            .add(0.25, |p, t| dice_loss(p, t, 1.0))
+            .add(2.0, mse_loss);
+
+        let r = losses.forward(pred.leaky_trace(), target.clone());
+        let dice = dice_loss(pred.leaky_trace(), target.clone(), 1.0);
+        let mse = mse_loss(pred.leaky_trace(), target);
+        assert_close(&r.array(), &(0.25 * dice.array() + 2.0 * mse.array()));
+
+        // the gradients of both losses end up on the same tape
+        let g = r.backward();
+        let g_dice = dice.backward().get(&pred).array()[0];
+        let g_mse = mse.backward().get(&pred).array()[0];
+        let mut expected = [[0.0; 2]; 2];
+        for i in 0..2 {
+            for j in 0..2 {
+                expected[i][j] = 0.25 * g_dice[i][j] + 2.0 * g_mse[i][j];
+            }
+        }
+        assert_close(&g.get(&pred).array(), &[expected]);
+    }
+
+    #[test]
+    fn test_quantile_loss_median() {
+        let dev: TestDevice = Default::default();
+        let pred: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([1.0, -2.0, 0.5, 3.0]);
+        let targ: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([0.0, 1.0, 0.5, 5.0]);
+        let loss = quantile_loss(pred.clone(), targ.clone(), 0.5);
+        assert_close(&loss.array(), &(mae_loss(pred, targ).array() / 2.0));
+    }
+
+    #[test]
+    fn test_quantile_loss_asymmetric_grad() {
+        let dev: TestDevice = Default::default();
+        let pred: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([2.0, -1.0]);
+        let targ = dev.tensor([1.0, 1.0]);
+        let loss = quantile_loss(pred.leaky_trace(), targ, 0.9);
+        assert_close(&loss.array(), &((0.1 * 1.0 + 0.9 * 2.0) / 2.0));
+
+        // under predicting is pushed up 9 times harder than over predicting is pushed down
+        let g = loss.backward();
+        assert_close(&g.get(&pred).array(), &[0.05, -0.45]);
+    }
+
+    #[test]
+    fn test_poisson_nll_loss() {
+        let dev: TestDevice = Default::default();
+        let log_rate: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([TestDtype::ln(2.0), 0.0]);
+        let targ: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([3.0, 0.0]);
+        let loss = poisson_nll_loss(log_rate.leaky_trace(), targ.clone(), false);
+        let nll = 2.0 - 3.0 * TestDtype::ln(2.0) + 1.0;
+        assert_close(&loss.array(), &(nll / 2.0));
+
+        // the gradient is the predicted rate minus the target
+        let g = loss.backward();
+        assert_close(&g.get(&log_rate).array(), &[-0.5, 0.5]);
+
+        let loss = poisson_nll_loss(log_rate.leaky_trace(), targ, true);
+        let stirling = 3.0 * TestDtype::ln(3.0) - 3.0
+            + 0.5 * TestDtype::ln(6.0 * std::f64::consts::PI as TestDtype);
+        assert_close(&loss.array(), &((nll + stirling) / 2.0));
+        let g = loss.backward();
+        assert_close(&g.get(&log_rate).array(), &[-0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_gaussian_nll_loss() {
+        let dev: TestDevice = Default::default();
+        let mean: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 0.0]);
+        let var: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([2.0, 1.0]);
+        let targ: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([3.0, 0.0]);
+        let loss = gaussian_nll_loss(mean.leaky_trace(), var.leaky_trace(), targ.clone(), 1e-6);
+        assert_close(&loss.array(), &((0.5 * TestDtype::ln(2.0) + 1.0) / 2.0));
+
+        // d/dmean is `(mean - targ) / var`, d/dvar is `0.5 * (1 / var - (targ - mean)^2 / var^2)`
+        let g = loss.backward();
+        assert_close(&g.get(&mean).array(), &[-0.5, 0.0]);
+        assert_close(&g.get(&var).array(), &[-0.125, 0.25]);
+
+        // variances below eps are clamped, but still get the gradient at eps like pytorch
+        let var: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([2.0, 0.0]);
+        let loss = try_gaussian_nll_loss(mean.leaky_trace(), var.leaky_trace(), targ, 1.0).unwrap();
+        assert_close(&loss.array(), &((0.5 * TestDtype::ln(2.0) + 1.0) / 2.0));
+        let g = loss.backward();
+        assert_close(&g.get(&var).array(), &[-0.125, 0.25]);
+    }
+
+    #[test]
+    fn test_vae_loss_kl_at_prior() {
+        let dev: TestDevice = Default::default();
+        let recon: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.5, -1.0]);
+        let target: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 0.0]);
+        let mean: Tensor<Rank1<3>, TestDtype, _> = dev.zeros();
+        let logvar: Tensor<Rank1<3>, TestDtype, _> = dev.zeros();
+        for reconstruction in [Reconstruction::Mse, Reconstruction::BceWithLogits] {
+            let loss = vae_loss(
+                recon.leaky_trace(),
+                target.clone(),
+                mean.leaky_trace(),
+                logvar.leaky_trace(),
+                reconstruction,
+            );
+            let expected = match reconstruction {
+                Reconstruction::Mse => mse_loss(recon.clone(), target.clone()),
+                Reconstruction::BceWithLogits => {
+                    binary_cross_entropy_with_logits_loss(recon.clone(), target.clone())
+                }
+            };
+            assert_close(&loss.array(), &expected.array());
+
+            let g = loss.backward();
+            assert_eq!(g.get(&mean).array(), [0.0; 3]);
+            assert_eq!(g.get(&logvar).array(), [0.0; 3]);
+            assert!(g.get(&recon).array().iter().all(|g| *g != 0.0));
+        }
+    }
+
+    #[test]
+    fn test_vae_loss_kl_pushes_toward_prior() {
+        let dev: TestDevice = Default::default();
+        let recon: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 0.0]);
+        let target = dev.tensor([1.0, 0.0]);
+        let mean: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, -2.0]);
+        let logvar: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.5, -1.0]);
+        let loss = vae_loss(
+            recon.leaky_trace(),
+            target,
+            mean.leaky_trace(),
+            logvar.leaky_trace(),
+            Reconstruction::Mse,
+        );
+        let (m, lv) = (mean.array(), logvar.array());
+        let kl = [0, 1].map(|i| 0.5 * (m[i] * m[i] + lv[i].exp() - lv[i] - 1.0));
+        assert_close(&loss.array(), &((kl[0] + kl[1]) / 2.0));
+
+        // gradient descent moves the mean & logvar toward 0
+        let g = loss.backward();
+        assert_close(&g.get(&mean).array(), &[0.5, -1.0]);
+        assert_close(
+            &g.get(&logvar).array(),
+            &lv.map(|lv| 0.25 * (lv.exp() - 1.0)),
+        );
+        assert_eq!(g.get(&recon).array(), [0.0; 2]);
+    }
+
+    #[test]
+    fn test_hinge_loss_correct_with_margin() {
+        let dev: TestDevice = Default::default();
+        let scores: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([2.0, -1.5, 1.0]);
+        let loss = hinge_loss(scores.leaky_trace(), &[1, -1, 1], 1.0);
+        assert_eq!(loss.array(), 0.0);
+
+        let g = loss.backward();
+        assert_eq!(g.get(&scores).array(), [0.0; 3]);
+    }
+
+    #[test]
+    fn test_hinge_loss_violates_margin() {
+        let dev: TestDevice = Default::default();
+        let scores: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[0.5, -2.0], [0.5, 3.0]]);
+        // only 0.5 with a label of 1 and 0.5 with a label of -1 are within the margin
+        let loss = hinge_loss(scores.leaky_trace(), &[1, -1, -1, 1], 1.0);
+        assert_close(&loss.array(), &0.5);
+
+        let g = loss.backward();
+        assert_close(&g.get(&scores).array(), &[[-0.25, 0.0], [0.25, 0.0]]);
+    }
+
+    #[test]
+    #[should_panic = "target must be 1 or -1, got 0"]
+    fn test_hinge_loss_invalid_target() {
+        let dev: TestDevice = Default::default();
+        let scores: Tensor<Rank1<2>, TestDtype, _> = dev.zeros();
+        hinge_loss(scores, &[1, 0], 1.0);
+    }
+
+    #[test]
+    fn test_ppo_clip_loss_inside_clip_range() {
+        let dev: TestDevice = Default::default();
+        let log_probs: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.1, -0.1, 0.05]);
+        let old_log_probs: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.0, 0.0, 0.1]);
+        let advantages: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, -2.0, 0.5]);
+
+        let loss = ppo_clip_loss(
+            log_probs.leaky_trace(),
+            old_log_probs.clone(),
+            advantages.clone(),
+            0.2,
+        );
+        let unclipped = ((log_probs.leaky_trace() - old_log_probs).exp() * advantages)
+            .mean()
+            .negate();
+        assert_close(&loss.array(), &unclipped.array());
+
+        let g = loss.backward();
+        let g_unclipped = unclipped.backward();
+        assert_close(
+            &g.get(&log_probs).array(),
+            &g_unclipped.get(&log_probs).array(),
+        );
+    }
+
+    #[test]
+    fn test_ppo_clip_loss_outside_clip_range() {
+        let dev: TestDevice = Default::default();
+        let ratios: [TestDtype; 4] = [1.5, 0.5, 1.5, 0.5];
+        let log_probs: Tensor<Rank1<4>, TestDtype, _> = dev.tensor(ratios.map(|r| r.ln()));
+        let advantages: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([1.0, -1.0, -1.0, 1.0]);
+
+        let loss = ppo_clip_loss(log_probs.leaky_trace(), dev.zeros(), advantages, 0.2);
+        // the first two are clipped to 1.2 * 1 and 0.8 * -1, but clipping doesn't help the
+        // last two, which are worse off than the clipped objective
+        assert_close(&loss.array(), &(-(1.2 - 0.8 - 1.5 + 0.5) / 4.0));
+
+        let g = loss.backward();
+        // the gradient of -r * A / 4 wrt log r is -r * A / 4
+        assert_close(&g.get(&log_probs).array(), &[0.0, 0.0, 0.375, -0.125]);
+    }
 }
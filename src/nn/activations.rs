@@ -1,4 +1,4 @@
-use crate::{shapes::*, tensor::*, tensor_ops::*};
+use crate::{shapes::*, tensor::*, tensor_ops::ops::UnaryInPlaceKernel, tensor_ops::*};
 
 use super::module::{Module, NonMutableModule, ZeroSizedModule};
 
@@ -26,6 +26,7 @@ macro_rules! activation_impls {
 
 activation_impls!(ReLU, try_relu, #[doc="Calls [relu()]."]);
 activation_impls!(GeLU, try_gelu, #[doc="Calls [gelu()]."]);
+activation_impls!(ExactGeLU, try_exact_gelu, #[doc="Calls [exact_gelu()]."]);
 activation_impls!(Sin, try_sin, #[doc="Calls [sin()]."]);
 activation_impls!(Cos, try_cos, #[doc="Calls [cos()]."]);
 activation_impls!(Ln, try_ln, #[doc="Calls [ln()]."]);
@@ -36,6 +37,42 @@ activation_impls!(Square, try_square, #[doc="Calls [square()]."]);
 activation_impls!(Sqrt, try_sqrt, #[doc="Calls [sqrt()]."]);
 activation_impls!(Abs, try_abs, #[doc="Calls [abs()]."]);
 
+/// A pointwise activation that can also be applied in place to a tensor that isn't
+/// tracking gradients, which reuses the tensor's buffer instead of allocating a new
+/// one (unless the buffer is shared with another tensor, e.g. after a clone).
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let mut t: Tensor<Rank1<3>, f32, _> = dev.tensor([-1.0, 0.0, 2.0]);
+/// ReLU.forward_inplace(&mut t);
+/// assert_eq!(t.array(), [0.0, 0.0, 2.0]);
+/// ```
+pub trait ActivationInPlace<E: Dtype, D: DeviceStorage> {
+    /// Applies the activation to `t` in place.
+    fn forward_inplace<S: Shape>(&self, t: &mut Tensor<S, E, D>) {
+        self.try_forward_inplace(t).unwrap()
+    }
+
+    /// Fallible version of [ActivationInPlace::forward_inplace]
+    fn try_forward_inplace<S: Shape>(&self, t: &mut Tensor<S, E, D>) -> Result<(), D::Err>;
+}
+
+macro_rules! activation_inplace_impls {
+    ($struct_name:ident, $op:ident) => {
+        impl<E: Dtype, D: UnaryInPlaceKernel<$op, E>> ActivationInPlace<E, D> for $struct_name {
+            fn try_forward_inplace<S: Shape>(&self, t: &mut Tensor<S, E, D>) -> Result<(), D::Err> {
+                t.device.clone().forward_inplace($op, t)
+            }
+        }
+    };
+}
+
+activation_inplace_impls!(ReLU, ReLUKernelOp);
+activation_inplace_impls!(GeLU, GeLUKernelOp);
+activation_inplace_impls!(Sigmoid, SigmoidKernelOp);
+activation_inplace_impls!(Tanh, TanhKernelOp);
+
 /// Calls [softmax()].
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Softmax;
@@ -79,6 +116,15 @@ mod tests {
         assert_eq!(r1.array(), r2.array());
     }
 
+    #[test]
+    fn test_nn_activations_exact_gelu() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([-2.0, -1.0, 0.0, 1.0, 2.0]);
+        let r1 = ExactGeLU.forward_mut(t.clone());
+        let r2 = exact_gelu(t);
+        assert_eq!(r1.array(), r2.array());
+    }
+
     #[test]
     fn test_nn_activations_sin() {
         let dev: TestDevice = Default::default();
@@ -170,4 +216,35 @@ mod tests {
         let r2 = t.softmax::<crate::shapes::Axis<1>>();
         assert_eq!(r1.array(), r2.array());
     }
+
+    #[test]
+    fn test_nn_activations_relu_inplace() {
+        let dev: TestDevice = Default::default();
+        let mut t = dev.tensor([-2.0, -0.5, 0.5, 2.0]);
+        let buf = t.data.as_ref() as *const _;
+        ReLU.forward_inplace(&mut t);
+        assert_eq!(t.array(), [0.0, 0.0, 0.5, 2.0]);
+        // the same buffer is used for the output
+        assert_eq!(t.data.as_ref() as *const _, buf);
+    }
+
+    #[test]
+    fn test_nn_activations_inplace_matches_forward() {
+        let dev: TestDevice = Default::default();
+        let t = dev.tensor([-2.0, -1.0, 0.0, 1.0, 2.0]);
+
+        let mut r = t.clone();
+        GeLU.forward_inplace(&mut r);
+        assert_eq!(r.array(), gelu(t.clone()).array());
+
+        let mut r = t.clone();
+        Sigmoid.forward_inplace(&mut r);
+        assert_eq!(r.array(), sigmoid(t.clone()).array());
+
+        // `r` shared its buffer with `t`, so `t` is unchanged
+        let mut r = t.clone();
+        Tanh.forward_inplace(&mut r);
+        assert_eq!(r.array(), tanh(t.clone()).array());
+        assert_eq!(t.array(), [-2.0, -1.0, 0.0, 1.0, 2.0]);
+    }
 }
@@ -23,11 +23,16 @@ use super::*;
 #[derive(Debug, Default, Clone)]
 pub struct AddInto<T>(pub T);
 
-impl<T: BuildOnDevice<D, E>, D: Device<E>, E: Dtype> BuildOnDevice<D, E> for AddInto<T> {
+impl<T: BuildOnDevice<D, E>, D: Device<E>, E: Dtype> BuildOnDevice<D, E> for AddInto<T>
+where
+    T::Built: 'static,
+{
     type Built = AddInto<T::Built>;
 }
 
-impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D>> TensorCollection<E, D> for AddInto<T> {
+impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D> + 'static> TensorCollection<E, D>
+    for AddInto<T>
+{
     type To<E2: Dtype, D2: Device<E2>> = AddInto<T::To<E2, D2>>;
 
     fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
@@ -0,0 +1,108 @@
+use crate::{
+    shapes::*,
+    tensor::{NoneTape, Tensor},
+    tensor_ops::{Device, ToDtypeKernel},
+};
+
+use super::{to_dtype::ToDtype, *};
+
+/// Runs `M` with a lower precision dtype `C`, while keeping the parameters of `M` in the
+/// dtype of the model, e.g. `f32`.
+///
+/// The input is converted to `C`, the parameters are copied to `C` with [ToDtype], `M` is
+/// called with them, and the output is converted back. `module` is the master copy of the
+/// parameters and is never modified. The parameters are copied on every forward.
+///
+/// Since [crate::tensor_ops::to_dtype()] does not track gradients, only [Module] with
+/// a [NoneTape] input is implemented, e.g. for inference or evaluation.
+///
+/// # Generics
+/// - `M`: The module to run.
+/// - `C`: The dtype to run `M` with.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = AutoCast<Linear<4, 4>, f32>;
+/// let model = dev.build_module::<Model, f64>();
+/// let x: Tensor<Rank1<4>, f64, _> = dev.sample_normal();
+/// let y: Tensor<Rank1<4>, f64, _> = model.forward(x);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AutoCast<M, C> {
+    pub module: M,
+    compute: core::marker::PhantomData<C>,
+}
+
+impl<M, C> AutoCast<M, C> {
+    pub fn new(module: M) -> Self {
+        Self {
+            module,
+            compute: Default::default(),
+        }
+    }
+}
+
+impl<D: Device<E>, E: Dtype, M: BuildOnDevice<D, E>, C> BuildOnDevice<D, E> for AutoCast<M, C>
+where
+    M::Built: 'static,
+{
+    type Built = AutoCast<M::Built, C>;
+}
+
+impl<E: Dtype, D: Device<E>, M: TensorCollection<E, D> + 'static, C> TensorCollection<E, D>
+    for AutoCast<M, C>
+{
+    type To<E2: Dtype, D2: Device<E2>> = AutoCast<M::To<E2, D2>, C>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            Self::module("module", |s| &s.module, |s| &mut s.module),
+            AutoCast::new,
+        )
+    }
+}
+
+impl<S: Shape, S2: Shape, E: Dtype, C: Dtype, D, M> Module<Tensor<S, E, D, NoneTape>>
+    for AutoCast<M, C>
+where
+    D: Device<E> + Device<C> + ToDtypeKernel<E, C> + ToDtypeKernel<C, E>,
+    M: TensorCollection<E, D>,
+    M::To<C, D>: Module<Tensor<S, C, D>, Output = Tensor<S2, C, D>, Error = D::Err>,
+{
+    type Output = Tensor<S2, E, D>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<S, E, D, NoneTape>) -> Result<Self::Output, D::Err> {
+        let module: M::To<C, D> = self.module.try_to_dtype()?;
+        module.try_forward(x.try_to_dtype()?)?.try_to_dtype()
+    }
+}
+
+impl<M, C> NonMutableModule for AutoCast<M, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::Linear, tensor::*, tests::*};
+
+    #[test]
+    fn test_autocast_linear() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<AutoCast<Linear<4, 4>, f32>, f64>();
+        let weight: [[f64; 4]; 4] = model.module.weight.array();
+
+        let x: Tensor<Rank2<3, 4>, f64, _> = dev.sample_normal();
+        let y: Tensor<Rank2<3, 4>, f64, _> = model.forward(x.clone());
+        let expected = model.module.forward(x).array();
+        for (a, b) in y.array().iter().flatten().zip(expected.iter().flatten()) {
+            assert!((a - b).abs() < 1e-5, "{a} != {b}");
+        }
+
+        // the master parameters keep their dtype and values
+        assert_eq!(model.module.weight.array(), weight);
+    }
+}
@@ -71,11 +71,28 @@ pub struct BatchNorm1D<const C: usize, E: Dtype, D: DeviceStorage> {
     ///
     /// `running_stat * (1.0 - momentum) + stat * momentum`.
     pub momentum: E,
+    /// Whether [ModuleMut] normalizes with batch statistics and updates the running
+    /// statistics. If `false`, it behaves like [Module] but still records gradients.
+    /// Defaults to `true`. See [SetTraining].
+    pub training: bool,
 }
 
 impl<const C: usize, E: Dtype, D: Device<E>> BatchNorm1D<C, E, D> {
+    /// Freezes (or unfreezes) [Self::running_mean] and [Self::running_var], e.g. for fine-tuning.
+    /// While frozen, [ModuleMut] normalizes with the running statistics without updating them,
+    /// but [Self::scale] and [Self::bias] still get gradients.
+    ///
+    /// This sets [Self::training] to `!frozen`, i.e. it is [SetTraining::set_training()] for
+    /// just this module.
+    pub fn freeze_running_stats(&mut self, frozen: bool) {
+        self.training = !frozen;
+    }
+
     /// generic forward for inference
-    fn infer_fwd<S: Shape, Ax: Axes>(&self, x: Tensor<S, E, D>) -> Result<Tensor<S, E, D>, D::Err>
+    fn infer_fwd<S: Shape, T: Tape<E, D>, Ax: Axes>(
+        &self,
+        x: Tensor<S, E, D, T>,
+    ) -> Result<Tensor<S, E, D, T>, D::Err>
     where
         Rank1<C>: BroadcastShapeTo<S, Ax>,
     {
@@ -108,6 +125,94 @@ impl<const C: usize, E: Dtype, D: Device<E>> BatchNorm1D<C, E, D> {
     }
 }
 
+impl<B: Dim, const C: usize, L: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Tensor<(B, Const<C>, L), E, D, T>
+{
+    /// [BatchNorm1D] for padded sequences, where `mask` is `1` for the valid timesteps of
+    /// each sequence and `0` for padding.
+    ///
+    /// If [BatchNorm1D::training] is set, the batch statistics are only computed over the
+    /// valid timesteps, and the running statistics are updated with them. Otherwise the
+    /// running statistics are used like [Module] does.
+    ///
+    /// Padding timesteps are 0 in the output, and get a gradient of 0.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let mut bn = dev.build_module::<BatchNorm1D<3>, f32>();
+    /// let x: Tensor<Rank3<2, 3, 4>, f32, _> = dev.sample_normal();
+    /// let mask: Tensor<Rank2<2, 4>, f32, _> = dev.tensor([[1.0; 4], [1.0, 1.0, 0.0, 0.0]]);
+    /// let y = x.leaky_trace().masked_batchnorm(&mask, &mut bn);
+    /// ```
+    pub fn masked_batchnorm(
+        self,
+        mask: &Tensor<(B, L), E, D>,
+        bn: &mut BatchNorm1D<C, E, D>,
+    ) -> Self {
+        self.try_masked_batchnorm(mask, bn).unwrap()
+    }
+
+    /// See [Tensor::masked_batchnorm]
+    pub fn try_masked_batchnorm(
+        self,
+        mask: &Tensor<(B, L), E, D>,
+        bn: &mut BatchNorm1D<C, E, D>,
+    ) -> Result<Self, D::Err> {
+        let shape = *self.shape();
+        // the number of valid timesteps, summed on device so only the sum is copied back
+        let n = mask.clone().try_sum::<Rank0, _>()?.as_vec()[0];
+        // with no valid timesteps, the statistics are all 0 and the running ones are kept
+        let any_valid = n > E::default();
+        let count = if any_valid { n } else { E::ONE };
+        let mask = mask.clone().try_broadcast_like::<_, Axis<1>>(&shape)?;
+        if !bn.training {
+            return bn.infer_fwd(self)?.try_mul(mask);
+        }
+
+        // statistics of the valid timesteps - on tape
+        let mean_chan = self
+            .retaped::<T>()
+            .try_mul(mask.clone())?
+            .try_sum::<Rank1<C>, Axes2<0, 2>>()?
+            .try_div(count)?;
+        if any_valid {
+            bn.running_mean
+                .try_axpy(E::ONE - bn.momentum, &mean_chan, bn.momentum)?;
+        }
+
+        // padding is zeroed out, so it doesn't count towards the variance
+        let centered = self
+            .try_sub(mean_chan.try_broadcast_like(&shape)?)?
+            .try_mul(mask.clone())?;
+        let var_chan = centered
+            .retaped::<T>()
+            .try_square()?
+            .try_sum::<Rank1<C>, Axes2<0, 2>>()?
+            .try_div(count)?;
+
+        // NOTE: uses unbiased variance in running estimate, which is undefined for 1 timestep
+        if any_valid {
+            let unbias = if n > E::ONE { n / (n - E::ONE) } else { E::ONE };
+            bn.running_var
+                .try_axpy(E::ONE - bn.momentum, &var_chan, bn.momentum * unbias)?;
+        }
+
+        let std = var_chan.try_add(bn.epsilon)?.try_sqrt()?;
+        let scale = bn
+            .scale
+            .retaped::<T>()
+            .try_div(std)?
+            .try_broadcast_like(&shape)?;
+        let bias = bn
+            .bias
+            .retaped::<T>()
+            .try_broadcast_like(&shape)?
+            .try_mul(mask)?;
+        centered.try_mul(scale)?.try_add(bias)
+    }
+}
+
 impl<B: Dim, const C: usize, E: Dtype, D: Device<E>> Module<Tensor<(B, Const<C>), E, D, NoneTape>>
     for BatchNorm1D<C, E, D>
 {
@@ -144,12 +249,16 @@ impl<B: Dim, const C: usize, L: Dim, E: Dtype, D: Device<E>>
     type Output = Tensor<(B, Const<C>, L), E, D, OwnedTape<E, D>>;
     type Error = D::Err;
 
-    /// Training 1d forward - updates [Self::running_mean] and [Self::running_var]
+    /// Training 1d forward - updates [Self::running_mean] and [Self::running_var] if [Self::training]
     fn try_forward_mut(
         &mut self,
         x: Tensor<(B, Const<C>, L), E, D, OwnedTape<E, D>>,
     ) -> Result<Self::Output, D::Err> {
-        self.train_fwd(x)
+        if self.training {
+            self.train_fwd(x)
+        } else {
+            self.infer_fwd(x)
+        }
     }
 }
 
@@ -159,12 +268,16 @@ impl<B: Dim, const C: usize, E: Dtype, D: Device<E>>
     type Output = Tensor<(B, Const<C>), E, D, OwnedTape<E, D>>;
     type Error = D::Err;
 
-    /// Training 2d forward - updates [Self::running_mean] and [Self::running_var]
+    /// Training 2d forward - updates [Self::running_mean] and [Self::running_var] if [Self::training]
     fn try_forward_mut(
         &mut self,
         x: Tensor<(B, Const<C>), E, D, OwnedTape<E, D>>,
     ) -> Result<Self::Output, D::Err> {
-        self.train_fwd(x)
+        if self.training {
+            self.train_fwd(x)
+        } else {
+            self.infer_fwd(x)
+        }
     }
 }
 
@@ -200,17 +313,23 @@ impl<const C: usize, E: Dtype, D: Device<E>> TensorCollection<E, D> for BatchNor
                     |s| &mut s.running_var,
                     TensorOptions::detached(|t| t.try_fill_with_ones()),
                 ),
+                Self::scalar("training", |s| &s.training, |s| &mut s.training, true),
             ),
-            |(scale, bias, running_mean, running_var)| BatchNorm1D {
+            |(scale, bias, running_mean, running_var, training)| BatchNorm1D {
                 scale,
                 bias,
                 running_mean,
                 running_var,
                 epsilon: V::E2::from_f32(1e-5).unwrap(),
                 momentum: V::E2::from_f32(0.1).unwrap(),
+                training,
             },
         )
     }
+
+    fn set_training_mode(&mut self, training: bool) {
+        self.training = training;
+    }
 }
 
 #[cfg(test)]
@@ -300,4 +419,90 @@ mod tests {
         let mut opt = Sgd::new(&bn, Default::default());
         opt.update(&mut bn, &g).expect("");
     }
+
+    #[test]
+    fn test_batchnorm1d_frozen_running_stats() {
+        let dev: TestDevice = Default::default();
+
+        let mut bn = dev.build_module::<BatchNorm1D<3>, TestDtype>();
+        bn.running_mean = dev.tensor([0.5, -1.0, 0.0]);
+        bn.running_var = dev.tensor([2.0, 1.0, 0.5]);
+        bn.freeze_running_stats(true);
+        assert!(!bn.training);
+
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let y = bn.forward_mut(x.leaky_trace());
+        assert_close(&y.array(), &bn.forward(x).array());
+        let g = y.exp().mean().backward();
+        assert_ne!(g.get(&bn.scale).array(), [0.0; 3]);
+        assert_eq!(bn.running_mean.array(), [0.5, -1.0, 0.0]);
+        assert_eq!(bn.running_var.array(), [2.0, 1.0, 0.5]);
+
+        bn.freeze_running_stats(false);
+        let _ = bn.forward_mut(dev.sample_normal::<Rank2<4, 3>>().leaky_trace());
+        assert_ne!(bn.running_mean.array(), [0.5, -1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_masked_batchnorm_ignores_padding() {
+        let dev: TestDevice = Default::default();
+        // (batch, channel, time), where the last 2 timesteps of the 2nd sequence are padding
+        let x: Tensor<Rank3<2, 2, 3>, TestDtype, _> = dev.tensor([
+            [[1.0, -2.0, 0.5], [3.0, 0.0, -1.0]],
+            [[2.0, 100.0, -100.0], [-4.0, 50.0, 50.0]],
+        ]);
+        let mask: Tensor<Rank2<2, 3>, TestDtype, _> =
+            dev.tensor([[1.0, 1.0, 1.0], [1.0, 0.0, 0.0]]);
+        let mut bn = BatchNorm1D::<2>::build_on_device(&dev);
+        let y = x.leaky_trace().masked_batchnorm(&mask, &mut bn);
+
+        // the same as batchnorm over only the valid timesteps
+        let valid: Tensor<Rank2<4, 2>, TestDtype, _> =
+            dev.tensor([[1.0, 3.0], [-2.0, 0.0], [0.5, -1.0], [2.0, -4.0]]);
+        let mut expected_bn = BatchNorm1D::<2>::build_on_device(&dev);
+        let expected = expected_bn.forward_mut(valid.leaky_trace()).array();
+        assert_close(&bn.running_mean.array(), &expected_bn.running_mean.array());
+        assert_close(&bn.running_var.array(), &expected_bn.running_var.array());
+
+        let y_arr = y.array();
+        for (row, (b, t)) in [(0, 0), (0, 1), (0, 2), (1, 0)].into_iter().enumerate() {
+            for c in 0..2 {
+                assert_close(&y_arr[b][c][t], &expected[row][c]);
+            }
+        }
+        assert_eq!(
+            y_arr[1],
+            [[y_arr[1][0][0], 0.0, 0.0], [y_arr[1][1][0], 0.0, 0.0]]
+        );
+
+        let g = y.exp().sum().backward();
+        let g_x = g.get(&x).array();
+        assert_eq!(
+            [g_x[1][0][1], g_x[1][0][2], g_x[1][1][1], g_x[1][1][2]],
+            [0.0; 4]
+        );
+        assert_ne!(g_x[0][0][0], 0.0);
+    }
+
+    #[test]
+    fn test_masked_batchnorm_with_at_most_one_valid_timestep() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 2, 3>, TestDtype, _> = dev.sample_normal();
+        let mut bn = BatchNorm1D::<2>::build_on_device(&dev);
+
+        // all padding: the output is 0, and the running statistics are unchanged
+        let mask: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let y = x.leaky_trace().masked_batchnorm(&mask, &mut bn);
+        assert_eq!(y.array(), [[[0.0; 3]; 2]; 2]);
+        assert_eq!(bn.running_mean.array(), [0.0; 2]);
+        assert_eq!(bn.running_var.array(), [1.0; 2]);
+        let g = y.sum().backward();
+        assert_eq!(g.get(&x).array(), [[[0.0; 3]; 2]; 2]);
+
+        // a single timestep has a variance of 0, without an unbiased estimate
+        let mask = dev.tensor([[0.0, 1.0, 0.0], [0.0; 3]]);
+        let y = x.leaky_trace().masked_batchnorm(&mask, &mut bn);
+        assert!(y.array().iter().flatten().flatten().all(|v| v.is_finite()));
+        assert_close(&bn.running_var.array(), &[0.9; 2]);
+    }
 }
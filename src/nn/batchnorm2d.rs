@@ -62,14 +62,14 @@ where
 }
 
 /// generic batchnorm forward for inference
-pub fn infer_fwd<const C: usize, S: Shape, E: Dtype, D: Device<E>, Ax: Axes>(
-    x: Tensor<S, E, D>,
+pub fn infer_fwd<const C: usize, S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>, Ax: Axes>(
+    x: Tensor<S, E, D, T>,
     var: &Tensor<Rank1<C>, E, D>,
     mean: &Tensor<Rank1<C>, E, D>,
     scale: &Tensor<Rank1<C>, E, D>,
     bias: &Tensor<Rank1<C>, E, D>,
     epsilon: E,
-) -> Result<Tensor<S, E, D>, D::Err>
+) -> Result<Tensor<S, E, D, T>, D::Err>
 where
     Rank1<C>: BroadcastShapeTo<S, Ax>,
 {
@@ -82,8 +82,8 @@ where
     // normalize & affine
     let x = x.try_sub(mean.try_broadcast_like(&shape)?)?;
     let x = x.try_div(std.try_broadcast_like(&shape)?)?;
-    let x = x.try_mul(scale.clone().try_broadcast_like(&shape)?)?;
-    x.try_add(bias.clone().try_broadcast_like(&shape)?)
+    let x = x.try_mul(scale.retaped::<T>().try_broadcast_like(&shape)?)?;
+    x.try_add(bias.retaped::<T>().try_broadcast_like(&shape)?)
 }
 
 /// Batch normalization for images as described in
@@ -136,10 +136,27 @@ pub struct BatchNorm2D<const C: usize, E: Dtype, D: DeviceStorage> {
     ///
     /// `running_stat * (1.0 - momentum) + stat * momentum`.
     pub momentum: E,
+    /// Whether [ModuleMut] normalizes with batch statistics and updates the running
+    /// statistics. If `false`, it behaves like [Module] but still records gradients.
+    /// Defaults to `true`. See [SetTraining].
+    pub training: bool,
 }
 
 impl<const C: usize, E: Dtype, D: Device<E>> BatchNorm2D<C, E, D> {
-    fn infer_fwd<S: Shape, Ax: Axes>(&self, x: Tensor<S, E, D>) -> Result<Tensor<S, E, D>, D::Err>
+    /// Freezes (or unfreezes) [Self::running_mean] and [Self::running_var], e.g. for fine-tuning.
+    /// While frozen, [ModuleMut] normalizes with the running statistics without updating them,
+    /// but [Self::scale] and [Self::bias] still get gradients.
+    ///
+    /// This sets [Self::training] to `!frozen`, i.e. it is [SetTraining::set_training()] for
+    /// just this module.
+    pub fn freeze_running_stats(&mut self, frozen: bool) {
+        self.training = !frozen;
+    }
+
+    fn infer_fwd<S: Shape, T: Tape<E, D>, Ax: Axes>(
+        &self,
+        x: Tensor<S, E, D, T>,
+    ) -> Result<Tensor<S, E, D, T>, D::Err>
     where
         Rank1<C>: BroadcastShapeTo<S, Ax>,
     {
@@ -208,12 +225,16 @@ impl<const C: usize, H: Dim, W: Dim, E: Dtype, D: Device<E>>
     type Output = Tensor<(Const<C>, H, W), E, D, OwnedTape<E, D>>;
     type Error = D::Err;
 
-    /// Training 3d forward - updates [Self::running_mean] and [Self::running_var]
+    /// Training 3d forward - updates [Self::running_mean] and [Self::running_var] if [Self::training]
     fn try_forward_mut(
         &mut self,
         x: Tensor<(Const<C>, H, W), E, D, OwnedTape<E, D>>,
     ) -> Result<Self::Output, D::Err> {
-        self.train_fwd(x)
+        if self.training {
+            self.train_fwd(x)
+        } else {
+            self.infer_fwd(x)
+        }
     }
 }
 
@@ -223,12 +244,16 @@ impl<B: Dim, const C: usize, H: Dim, W: Dim, E: Dtype, D: Device<E>>
     type Output = Tensor<(B, Const<C>, H, W), E, D, OwnedTape<E, D>>;
     type Error = D::Err;
 
-    /// Training 4d forward - updates [Self::running_mean] and [Self::running_var]
+    /// Training 4d forward - updates [Self::running_mean] and [Self::running_var] if [Self::training]
     fn try_forward_mut(
         &mut self,
         x: Tensor<(B, Const<C>, H, W), E, D, OwnedTape<E, D>>,
     ) -> Result<Self::Output, D::Err> {
-        self.train_fwd(x)
+        if self.training {
+            self.train_fwd(x)
+        } else {
+            self.infer_fwd(x)
+        }
     }
 }
 
@@ -264,17 +289,23 @@ impl<const C: usize, E: Dtype, D: Device<E>> TensorCollection<E, D> for BatchNor
                     |s| &mut s.running_var,
                     TensorOptions::detached(|t| t.try_fill_with_ones()),
                 ),
+                Self::scalar("training", |s| &s.training, |s| &mut s.training, true),
             ),
-            |(scale, bias, running_mean, running_var)| BatchNorm2D {
+            |(scale, bias, running_mean, running_var, training)| BatchNorm2D {
                 scale,
                 bias,
                 running_mean,
                 running_var,
                 epsilon: V::E2::from_f32(1e-5).unwrap(),
                 momentum: V::E2::from_f32(0.1).unwrap(),
+                training,
             },
         )
     }
+
+    fn set_training_mode(&mut self, training: bool) {
+        self.training = training;
+    }
 }
 
 #[cfg(test)]
@@ -414,4 +445,64 @@ mod tests {
         let mut opt = Sgd::new(&bn, Default::default());
         opt.update(&mut bn, &g).expect("");
     }
+
+    #[test]
+    fn test_batchnorm2d_frozen_running_stats() {
+        let dev: TestDevice = Default::default();
+
+        let mut bn = dev.build_module::<BatchNorm2D<3>, TestDtype>();
+        bn.running_mean = dev.tensor([0.5, -1.0, 0.0]);
+        bn.running_var = dev.tensor([2.0, 1.0, 0.5]);
+        bn.freeze_running_stats(true);
+        let mut opt = Sgd::new(&bn, Default::default());
+
+        for _ in 0..3 {
+            let x: Tensor<Rank4<2, 3, 2, 2>, TestDtype, _> = dev.sample_normal();
+            let y = bn.forward_mut(x.leaky_trace());
+            assert_close(&y.array(), &bn.forward(x).array());
+            let g = y.exp().mean().backward();
+            assert_ne!(g.get(&bn.scale).array(), [0.0; 3]);
+            assert_ne!(g.get(&bn.bias).array(), [0.0; 3]);
+            opt.update(&mut bn, &g).expect("");
+        }
+        assert_eq!(bn.running_mean.array(), [0.5, -1.0, 0.0]);
+        assert_eq!(bn.running_var.array(), [2.0, 1.0, 0.5]);
+        assert_ne!(bn.scale.array(), [1.0; 3]);
+
+        bn.freeze_running_stats(false);
+        let _ = bn.forward_mut(dev.sample_normal::<Rank3<3, 2, 2>>().leaky_trace());
+        assert_ne!(bn.running_mean.array(), [0.5, -1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_batchnorm2d_eval_forward_keeps_running_stats() {
+        let dev: TestDevice = Default::default();
+
+        let mut bn = dev.build_module::<BatchNorm2D<3>, TestDtype>();
+        bn.running_mean = dev.tensor([0.5, -1.0, 0.25]);
+        bn.running_var = dev.tensor([2.0, 0.75, 0.5]);
+        let bits = |t: &Tensor<Rank1<3>, TestDtype, TestDevice>| {
+            t.as_vec()
+                .iter()
+                .map(|v| v.to_bits())
+                .collect::<std::vec::Vec<_>>()
+        };
+        let (mean, var) = (bits(&bn.running_mean), bits(&bn.running_var));
+
+        for _ in 0..100 {
+            let x: Tensor<Rank4<2, 3, 2, 2>, TestDtype, _> = dev.sample_normal();
+            let _ = bn.forward(x);
+        }
+        assert_eq!(bits(&bn.running_mean), mean);
+        assert_eq!(bits(&bn.running_var), var);
+
+        // forward_mut in evaluation mode doesn't update them either
+        bn.set_training(false);
+        for _ in 0..100 {
+            let x: Tensor<Rank3<3, 2, 2>, TestDtype, _> = dev.sample_normal();
+            let _ = bn.forward_mut(x.leaky_trace());
+        }
+        assert_eq!(bits(&bn.running_mean), mean);
+        assert_eq!(bits(&bn.running_var), var);
+    }
 }
@@ -0,0 +1,220 @@
+use std::{string::String, vec::Vec};
+
+use crate::{
+    shapes::*,
+    tensor::*,
+    tensor_ops::{checkpoint::try_recompute_backward, Device},
+};
+
+use super::*;
+
+/// Same as [super::modules::Repeated], but with activation checkpointing for each block when
+/// gradients are being tracked.
+///
+/// Each block runs its forward without recording any operations, so none of its intermediate
+/// activations are kept in memory. Instead only the input to each block is stored, and the block's
+/// forward is run a second time (this time with a tape) during backward to compute gradients.
+/// This trades extra compute for less memory usage on deep stacks.
+///
+/// Since the block is re-run during the backward pass, `T` must be deterministic (e.g. no
+/// dropout), and only [Module::forward] is supported.
+///
+/// # Generics
+/// - `T` the [Module] to repeat
+/// - `N` the number of times to repeat `T`.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = CheckpointedRepeated<(Linear<10, 10>, ReLU), 5>;
+/// let model = dev.build_module::<Model, f32>();
+/// let x: Tensor<Rank1<10>, f32, _> = dev.sample_normal();
+/// let grads = model.alloc_grads();
+/// let out = model.forward(x.trace(grads));
+/// let grads = out.sum().backward();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CheckpointedRepeated<T, const N: usize> {
+    pub modules: Vec<T>,
+}
+
+impl<D: Device<E>, E: Dtype, T: BuildOnDevice<D, E>, const N: usize> BuildOnDevice<D, E>
+    for CheckpointedRepeated<T, N>
+where
+    T::Built: 'static,
+{
+    type Built = CheckpointedRepeated<T::Built, N>;
+}
+
+impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D> + 'static, const N: usize>
+    TensorCollection<E, D> for CheckpointedRepeated<T, N>
+{
+    type To<E2: Dtype, D2: Device<E2>> = CheckpointedRepeated<T::To<E2, D2>, N>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        let names: Vec<String> = (0..N).map(|i| format!("{i}")).collect();
+
+        visitor.visit_fields(
+            (0..N)
+                .zip(names.iter())
+                .map(|(i, name)| {
+                    Self::module(name, move |s| &s.modules[i], move |s| &mut s.modules[i])
+                })
+                .collect::<Vec<_>>(),
+            |modules| CheckpointedRepeated { modules },
+        )
+    }
+}
+
+impl<T, const N: usize> std::ops::Index<usize> for CheckpointedRepeated<T, N> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.modules[index]
+    }
+}
+
+impl<T, const N: usize> NonMutableModule for CheckpointedRepeated<T, N> {}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T, const N: usize> Module<Tensor<S, E, D>>
+    for CheckpointedRepeated<T, N>
+where
+    T: Module<Tensor<S, E, D>, Output = Tensor<S, E, D>, Error = D::Err>,
+{
+    type Output = Tensor<S, E, D>;
+    type Error = D::Err;
+
+    fn try_forward(&self, mut x: Tensor<S, E, D>) -> Result<Self::Output, D::Err> {
+        for i in 0..N {
+            x = self.modules[i].try_forward(x)?;
+        }
+        Ok(x)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T, const N: usize> Module<Tensor<S, E, D, OwnedTape<E, D>>>
+    for CheckpointedRepeated<T, N>
+where
+    T: 'static
+        + Clone
+        + Module<Tensor<S, E, D>, Output = Tensor<S, E, D>, Error = D::Err>
+        + Module<
+            Tensor<S, E, D, OwnedTape<E, D>>,
+            Output = Tensor<S, E, D, OwnedTape<E, D>>,
+            Error = D::Err,
+        >,
+{
+    type Output = Tensor<S, E, D, OwnedTape<E, D>>;
+    type Error = D::Err;
+
+    fn try_forward(&self, mut x: Tensor<S, E, D, OwnedTape<E, D>>) -> Result<Self::Output, D::Err> {
+        for i in 0..N {
+            x = try_checkpoint(&self.modules[i], x)?;
+        }
+        Ok(x)
+    }
+}
+
+/// Runs `module` without a tape, and records a single backward operation that re-runs
+/// `module` with a tape to compute the gradients.
+#[allow(clippy::type_complexity)]
+fn try_checkpoint<S: Shape, E: Dtype, D: Device<E>, M>(
+    module: &M,
+    x: Tensor<S, E, D, OwnedTape<E, D>>,
+) -> Result<Tensor<S, E, D, OwnedTape<E, D>>, D::Err>
+where
+    M: 'static
+        + Clone
+        + Module<Tensor<S, E, D>, Output = Tensor<S, E, D>, Error = D::Err>
+        + Module<
+            Tensor<S, E, D, OwnedTape<E, D>>,
+            Output = Tensor<S, E, D, OwnedTape<E, D>>,
+            Error = D::Err,
+        >,
+{
+    let (x, mut tape) = x.split_tape();
+    let y = module.try_forward(x.clone())?;
+    let phantom_y = y.clone();
+    let module = module.clone();
+    tape.try_alloc_grad(&x)?;
+    tape.try_alloc_grad(&y)?;
+    tape.add_backward_op(move |grads| {
+        try_recompute_backward(grads, x.clone(), &phantom_y, |x| module.try_forward(x))
+    });
+    Ok(y.put_tape(tape))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, tests::*};
+
+    #[test]
+    fn test_checkpointed_forward_matches_repeated() {
+        let dev: TestDevice = Default::default();
+        type Model = Repeated<(Linear<3, 3>, Tanh), 3>;
+        let m = dev.build_module::<Model, TestDtype>();
+        let c = CheckpointedRepeated::<_, 3> {
+            modules: m.modules.clone(),
+        };
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        assert_close(&c.forward(x.clone()).array(), &m.forward(x.clone()).array());
+        assert_close(
+            &c.forward(x.leaky_trace()).array(),
+            &m.forward(x.leaky_trace()).array(),
+        );
+    }
+
+    #[test]
+    fn test_checkpointed_gradients_match_repeated() {
+        let dev: TestDevice = Default::default();
+        type Model = Repeated<(Linear<3, 3>, Tanh), 3>;
+        let m = dev.build_module::<Model, TestDtype>();
+        let c = CheckpointedRepeated::<_, 3> {
+            modules: m.modules.clone(),
+        };
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+
+        let g1 = m
+            .forward(x.trace(m.alloc_grads()))
+            .square()
+            .mean()
+            .backward();
+        let g2 = c
+            .forward(x.trace(c.alloc_grads()))
+            .square()
+            .mean()
+            .backward();
+        for i in 0..3 {
+            assert_close(
+                &g2.get(&c[i].0.weight).array(),
+                &g1.get(&m[i].0.weight).array(),
+            );
+            assert_close(&g2.get(&c[i].0.bias).array(), &g1.get(&m[i].0.bias).array());
+        }
+
+        // also works without pre-allocated gradients
+        let g1 = m.forward(x.leaky_trace()).square().mean().backward();
+        let g2 = c.forward(x.leaky_trace()).square().mean().backward();
+        assert_close(&g2.get(&x).array(), &g1.get(&x).array());
+        assert_close(
+            &g2.get(&c[0].0.weight).array(),
+            &g1.get(&m[0].0.weight).array(),
+        );
+    }
+
+    #[test]
+    fn test_checkpointed_fewer_recorded_ops() {
+        let dev: TestDevice = Default::default();
+        type Model = Repeated<(Linear<3, 3>, Tanh), 3>;
+        let m = dev.build_module::<Model, TestDtype>();
+        let c = CheckpointedRepeated::<_, 3> {
+            modules: m.modules.clone(),
+        };
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let (_, tape) = c.forward(x.leaky_trace()).split_tape();
+        assert_eq!(tape.operations.len(), 3);
+    }
+}
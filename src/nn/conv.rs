@@ -108,6 +108,68 @@ where
 {
 }
 
+/// **Requires Nightly** Folds an inference mode [crate::nn::modules::BatchNorm2D] that comes
+/// after `conv` into it, so that `(conv, bn)` can be replaced with `(conv, bias)` for deployment.
+///
+/// With `s = bn.scale / sqrt(bn.running_var + bn.epsilon)`, this scales each output channel
+/// of `conv.weight` by `s`, and returns a [crate::nn::modules::Bias2D] holding
+/// `bn.bias - bn.running_mean * s`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, nn::modules};
+/// # let dev: Cpu = Default::default();
+/// let mut conv = dev.build_module::<Conv2D<3, 4, 3>, f32>();
+/// let bn = dev.build_module::<BatchNorm2D<4>, f32>();
+/// let bias: modules::Bias2D<4, f32, Cpu> = fold_batchnorm(&mut conv, &bn);
+/// ```
+#[cfg(feature = "nightly")]
+pub fn fold_batchnorm<
+    const I: usize,
+    const O: usize,
+    const K: usize,
+    const S: usize,
+    const P: usize,
+    E,
+    D,
+>(
+    conv: &mut Conv2D<I, O, K, S, P, E, D>,
+    bn: &modules::BatchNorm2D<O, E, D>,
+) -> modules::Bias2D<O, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+{
+    try_fold_batchnorm(conv, bn).unwrap()
+}
+
+/// See [fold_batchnorm]
+#[cfg(feature = "nightly")]
+pub fn try_fold_batchnorm<
+    const I: usize,
+    const O: usize,
+    const K: usize,
+    const S: usize,
+    const P: usize,
+    E,
+    D,
+>(
+    conv: &mut Conv2D<I, O, K, S, P, E, D>,
+    bn: &modules::BatchNorm2D<O, E, D>,
+) -> Result<modules::Bias2D<O, E, D>, D::Err>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+{
+    let std = bn.running_var.clone().try_add(bn.epsilon)?.try_sqrt()?;
+    let s = bn.scale.clone().try_div(std)?;
+    let bias = bn
+        .bias
+        .clone()
+        .try_sub(bn.running_mean.clone().try_mul(s.clone())?)?;
+    conv.weight = conv.weight.clone().try_mul(s.try_broadcast()?)?;
+    Ok(modules::Bias2D { bias })
+}
+
 #[cfg(feature = "nightly")]
 #[cfg(test)]
 mod tests {
@@ -191,4 +253,21 @@ mod tests {
 
         assert_ne!(weight_init.array(), m.weight.array());
     }
+
+    #[test]
+    fn test_fold_batchnorm() {
+        let dev: TestDevice = Default::default();
+        let mut conv = dev.build_module::<Conv2D<2, 3, 3, 1, 1>, TestDtype>();
+        let mut bn = dev.build_module::<crate::nn::builders::BatchNorm2D<3>, TestDtype>();
+        bn.scale = dev.tensor([0.5, -1.0, 2.0]);
+        bn.bias = dev.tensor([0.1, 0.2, -0.3]);
+        bn.running_mean = dev.tensor([1.0, -0.5, 0.25]);
+        bn.running_var = dev.tensor([4.0, 0.5, 1.5]);
+
+        let x = dev.sample_normal::<Rank4<2, 2, 5, 5>>();
+        let expected = bn.forward(conv.forward(x.clone()));
+        let bias = fold_batchnorm(&mut conv, &bn);
+        let folded = bias.forward(conv.forward(x));
+        assert_close_with_tolerance(&folded.array(), &expected.array(), 1e-5);
+    }
 }
@@ -0,0 +1,180 @@
+use num_traits::Float;
+use rand_distr::Uniform;
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::*;
+
+pub mod builder {
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct CrossLayer<const M: usize>;
+}
+
+impl<const M: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E> for builder::CrossLayer<M>
+where
+    CrossLayer<M, E, D>: BuildModule<D, E>,
+{
+    type Built = CrossLayer<M, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// A layer of a [deep & cross network](https://arxiv.org/abs/1708.05123), which computes
+/// the explicit feature interactions `x0 * (weight . xl) + bias + xl` of the input `x0` of
+/// the network, and the output `xl` of the previous cross layer.
+///
+/// The input is the tuple `(x0, xl)`, so the first layer is called with `(x0.retaped(), x0)`
+/// when `x0` is traced.
+///
+/// Initializes [Self::weight] from a Uniform distribution between [-1 / sqrt(M), 1 / sqrt(M)],
+/// and [Self::bias] with zeros.
+///
+/// # Generics
+/// - `M` The size of the input & output vectors.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = CrossLayer<5>;
+/// let model = dev.build_module::<Model, f32>();
+/// // single item forward
+/// let x0: Tensor<Rank1<5>, f32, _> = dev.sample_normal();
+/// let _: Tensor<Rank1<5>, f32, _> = model.forward((x0.clone(), x0));
+/// // batched forward
+/// let x0: Tensor<Rank2<10, 5>, f32, _> = dev.sample_normal();
+/// let _: Tensor<Rank2<10, 5>, f32, _> = model.forward((x0.clone(), x0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CrossLayer<const M: usize, E: Dtype, D: DeviceStorage> {
+    /// Weight vector, shape (M, )
+    pub weight: Tensor<Rank1<M>, E, D>,
+
+    /// Bias vector, shape (M, )
+    pub bias: Tensor<Rank1<M>, E, D>,
+}
+
+impl<const M: usize, E: Dtype, D: DeviceStorage> NonMutableModule for CrossLayer<M, E, D> {}
+
+impl<const M: usize, E: Dtype + Float, D: Device<E>> TensorCollection<E, D>
+    for CrossLayer<M, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = CrossLayer<M, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::tensor(
+                    "weight",
+                    |s| &s.weight,
+                    |s| &mut s.weight,
+                    TensorOptions::reset_with(|t| {
+                        let b: E = E::ONE / E::from_usize(M).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::tensor(
+                    "bias",
+                    |s| &s.bias,
+                    |s| &mut s.bias,
+                    TensorOptions::reset_to_zeros(),
+                ),
+            ),
+            |(weight, bias)| CrossLayer { weight, bias },
+        )
+    }
+}
+
+impl<const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D> + Merge<T>>
+    Module<(Tensor<Rank1<M>, E, D, T>, Tensor<Rank1<M>, E, D, T>)> for CrossLayer<M, E, D>
+{
+    type Output = Tensor<Rank1<M>, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(
+        &self,
+        (x0, xl): (Tensor<Rank1<M>, E, D, T>, Tensor<Rank1<M>, E, D, T>),
+    ) -> Result<Self::Output, D::Err> {
+        let dot = xl
+            .retaped::<T>()
+            .try_mul(self.weight.clone())?
+            .try_sum::<Rank0, _>()?;
+        x0.try_mul(dot.try_broadcast()?)?
+            .try_add(self.bias.clone())?
+            .try_add(xl)
+    }
+}
+
+impl<B: Dim, const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D> + Merge<T>>
+    Module<(
+        Tensor<(B, Const<M>), E, D, T>,
+        Tensor<(B, Const<M>), E, D, T>,
+    )> for CrossLayer<M, E, D>
+{
+    type Output = Tensor<(B, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(
+        &self,
+        (x0, xl): (
+            Tensor<(B, Const<M>), E, D, T>,
+            Tensor<(B, Const<M>), E, D, T>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        let shape = *xl.shape();
+        let dot = xl
+            .retaped::<T>()
+            .try_mul(self.weight.clone().try_broadcast_like(&shape)?)?
+            .try_sum::<(B,), _>()?;
+        x0.try_mul(dot.try_broadcast_like(&shape)?)?
+            .try_add(self.bias.clone().try_broadcast_like(&shape)?)?
+            .try_add(xl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_cross_layer_1d() {
+        let dev: TestDevice = Default::default();
+        let model = CrossLayer {
+            weight: dev.tensor([0.5, -1.0, 2.0]),
+            bias: dev.tensor([0.1, 0.2, 0.3]),
+        };
+        let x0: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, -1.0]);
+        let xl: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([2.0, 0.0, 1.0]);
+        let y = model.forward((x0.leaky_trace(), xl.leaky_trace()));
+        // weight . xl = 1 + 0 + 2 = 3
+        assert_close(&y.array(), &[3.0 + 0.1 + 2.0, 6.0 + 0.2, -3.0 + 0.3 + 1.0]);
+
+        let g = y.sum().backward();
+        // d/dx0 = weight . xl, d/dxl = sum(x0) * weight + 1, d/dweight = sum(x0) * xl
+        assert_close(&g.get(&x0).array(), &[3.0; 3]);
+        assert_close(
+            &g.get(&xl).array(),
+            &[2.0 * 0.5 + 1.0, -2.0 + 1.0, 2.0 * 2.0 + 1.0],
+        );
+        assert_close(&g.get(&model.weight).array(), &[4.0, 0.0, 2.0]);
+        assert_close(&g.get(&model.bias).array(), &[1.0; 3]);
+    }
+
+    #[test]
+    fn test_cross_layer_batched() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builder::CrossLayer<4>, TestDtype>();
+        assert_eq!(model.bias.array(), [0.0; 4]);
+        let x0: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let xl: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let y = model.forward((x0.clone(), xl.clone())).array();
+        for (i, y) in y.iter().enumerate() {
+            let r = model.forward((dev.tensor(x0.array()[i]), dev.tensor(xl.array()[i])));
+            assert_close(y, &r.array());
+        }
+    }
+}
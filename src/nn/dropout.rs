@@ -129,7 +129,27 @@ impl Default for Dropout {
     }
 }
 
-impl ZeroSizedModule for Dropout {}
+impl<E: Dtype, D: Device<E>> BuildOnDevice<D, E> for Dropout {
+    type Built = Self;
+}
+
+impl<E: Dtype, D: Device<E>> TensorCollection<E, D> for Dropout {
+    type To<E2: Dtype, D2: Device<E2>> = Self;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            <Self as TensorCollection<E, D>>::scalar(
+                "p",
+                |s| &s.p,
+                |s| &mut s.p,
+                Dropout::default().p,
+            ),
+            |p| Dropout { p },
+        )
+    }
+}
 
 impl<S: Shape, E: Dtype, D: Device<E>> Module<Tensor<S, E, D, NoneTape>> for Dropout {
     type Output = Tensor<S, E, D, NoneTape>;
@@ -194,4 +214,14 @@ mod tests {
         let r = dropout.forward_mut(t.leaky_trace());
         assert_ne!(t.array(), r.array());
     }
+
+    #[test]
+    fn test_dropout_keeps_p_on_to_device() {
+        let dev: TestDevice = Default::default();
+        let mut model =
+            dev.build_module::<(crate::nn::builders::Linear<2, 2>, Dropout), TestDtype>();
+        model.1.p = 0.25;
+        let model = model.to_device(&dev);
+        assert_eq!(model.1.p, 0.25);
+    }
 }
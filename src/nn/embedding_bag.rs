@@ -0,0 +1,238 @@
+use num_traits::Float;
+use rand_distr::{uniform::SampleUniform, Uniform};
+
+use crate::{
+    shapes::*,
+    tensor::*,
+    tensor_ops::{ops::try_host_unary_op, *},
+};
+
+use super::*;
+
+pub mod builder {
+    #[derive(Debug)]
+    pub struct EmbeddingBag<const VOCAB: usize, const DIM: usize>;
+}
+
+impl<const V: usize, const M: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::EmbeddingBag<V, M>
+where
+    EmbeddingBag<V, M, E, D>: BuildModule<D, E>,
+{
+    type Built = EmbeddingBag<V, M, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, D::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// How [EmbeddingBag] reduces the embeddings of each bag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BagMode {
+    Sum,
+    /// The mean of an empty bag is 0.
+    Mean,
+}
+
+/// An [Embedding] that reduces the embeddings of each bag of indices into one vector, without
+/// materializing the embeddings of every index, e.g. for bag of words or hashed features.
+///
+/// The input is a tuple of the `indices` of all the bags, and the `offsets` where each bag
+/// starts in `indices`, like pytorch's `nn.EmbeddingBag`. So bag `i` is
+/// `indices[offsets[i]..offsets[i + 1]]`, and the last bag ends at the end of `indices`.
+///
+/// [Self::mode] is [BagMode::Sum] by default. Initializes [Self::weight] like [Embedding].
+///
+/// # Generics
+/// - `VOCAB` The size of the vocabulary, indices must be between 0 and VOCAB.
+/// - `DIM` The size of each embedding.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = EmbeddingBag<7, 2>;
+/// let model = dev.build_module::<Model, f32>();
+/// // the bags [1, 2, 4] and [3]
+/// let indices: Tensor<Rank1<4>, usize, _> = dev.tensor([1, 2, 4, 3]);
+/// let offsets: Tensor<Rank1<2>, usize, _> = dev.tensor([0, 3]);
+/// let _: Tensor<Rank2<2, 2>, f32, _> = model.forward((indices, offsets));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EmbeddingBag<const VOCAB: usize, const DIM: usize, E: Dtype, D: DeviceStorage> {
+    pub weight: Tensor<Rank2<VOCAB, DIM>, E, D>,
+    pub mode: BagMode,
+}
+
+impl<const V: usize, const M: usize, E: Dtype, D: DeviceStorage> NonMutableModule
+    for EmbeddingBag<V, M, E, D>
+{
+}
+
+impl<const C: usize, const M: usize, E: Dtype + Float + SampleUniform, D: Device<E>>
+    TensorCollection<E, D> for EmbeddingBag<C, M, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = EmbeddingBag<C, M, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::tensor(
+                    "weight",
+                    |s| &s.weight,
+                    |s| &mut s.weight,
+                    TensorOptions::reset_with(|t| {
+                        let b: E = E::ONE / E::from_usize(C).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::scalar("mode", |s| &s.mode, |s| &mut s.mode, BagMode::Sum),
+            ),
+            |(weight, mode)| EmbeddingBag { weight, mode },
+        )
+    }
+}
+
+impl<const V: usize, const M: usize, L: Dim, B: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<(Tensor<(L,), usize, D, T>, Tensor<(B,), usize, D>)> for EmbeddingBag<V, M, E, D>
+{
+    type Output = Tensor<(B, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    /// **Panics** if `offsets` doesn't start at 0, decreases, or goes past the end of
+    /// `indices`, or if an index is out of bounds.
+    fn try_forward(
+        &self,
+        (indices, offsets): (Tensor<(L,), usize, D, T>, Tensor<(B,), usize, D>),
+    ) -> Result<Self::Output, D::Err> {
+        let (indices, tape) = indices.split_tape();
+        let (indices, starts) = (indices.as_vec(), offsets.as_vec());
+        let batch = offsets.shape.0;
+        assert!(
+            starts.is_empty() || starts[0] == 0,
+            "offsets must start at 0"
+        );
+        assert!(indices.iter().all(|&i| i < V), "index out of bounds");
+        // the `[start, end)` of each bag
+        let mut bags = std::vec::Vec::with_capacity(starts.len());
+        for (b, &start) in starts.iter().enumerate() {
+            let end = starts.get(b + 1).copied().unwrap_or(indices.len());
+            assert!(start <= end && end <= indices.len(), "invalid offsets");
+            bags.push((start, end));
+        }
+        let scale = |(start, end): (usize, usize)| match self.mode {
+            BagMode::Sum => E::ONE,
+            BagMode::Mean => E::ONE / E::from_usize((end - start).max(1)).unwrap(),
+        };
+        let scales: std::vec::Vec<E> = bags.iter().map(|&bag| scale(bag)).collect();
+        let fwd_bags = bags.clone();
+        let fwd_indices = indices.clone();
+        let fwd_scales = scales.clone();
+        try_host_unary_op(
+            self.weight.clone().put_tape(tape),
+            (batch, Const),
+            |weight| {
+                let mut out = vec![E::default(); fwd_bags.len() * M];
+                for (b, &(start, end)) in fwd_bags.iter().enumerate() {
+                    for &i in &fwd_indices[start..end] {
+                        for j in 0..M {
+                            out[b * M + j] += fwd_scales[b] * weight[i * M + j];
+                        }
+                    }
+                }
+                out
+            },
+            move |weight, _, grad_out| {
+                // scatter adds the gradient of each bag to every row looked up in it
+                let mut grad = vec![E::default(); weight.len()];
+                for (b, &(start, end)) in bags.iter().enumerate() {
+                    for &i in &indices[start..end] {
+                        for j in 0..M {
+                            grad[i * M + j] += scales[b] * grad_out[b * M + j];
+                        }
+                    }
+                }
+                grad
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    const W: [[TestDtype; 3]; 4] = [
+        [1.0, 2.0, 3.0],
+        [-1.0, 0.5, 0.0],
+        [0.25, -2.0, 1.0],
+        [4.0, 0.0, -1.0],
+    ];
+
+    #[test]
+    fn test_embedding_bag_sum() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builder::EmbeddingBag<4, 3>, TestDtype>();
+        assert_eq!(model.mode, BagMode::Sum);
+        model.weight = dev.tensor(W);
+
+        // the bags [0, 2, 2] and [3]
+        let indices: Tensor<Rank1<4>, usize, _> = dev.tensor([0, 2, 2, 3]);
+        let offsets: Tensor<Rank1<2>, usize, _> = dev.tensor([0, 3]);
+        let y = model.forward((indices.leaky_trace(), offsets));
+        assert_close(&y.array(), &[[1.5, -2.0, 5.0], [4.0, 0.0, -1.0]]);
+
+        // row 2 is looked up twice, and row 1 isn't looked up
+        let g = (y * dev.tensor([[1.0, 2.0, 3.0], [-1.0, -1.0, 1.0]]))
+            .sum::<Rank0, _>()
+            .backward();
+        assert_close(
+            &g.get(&model.weight).array(),
+            &[
+                [1.0, 2.0, 3.0],
+                [0.0, 0.0, 0.0],
+                [2.0, 4.0, 6.0],
+                [-1.0, -1.0, 1.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_embedding_bag_mean() {
+        let dev: TestDevice = Default::default();
+        let model = EmbeddingBag {
+            weight: dev.tensor(W),
+            mode: BagMode::Mean,
+        };
+
+        // the bags [1, 3], [] and [0]
+        let indices: Tensor<(usize,), usize, _> = dev.tensor_from_vec(std::vec![1, 3, 0], (3,));
+        let offsets: Tensor<Rank1<3>, usize, _> = dev.tensor([0, 2, 2]);
+        let y = model.forward((indices.leaky_trace(), offsets));
+        assert_close(
+            &y.array(),
+            &[[1.5, 0.25, -0.5], [0.0, 0.0, 0.0], [1.0, 2.0, 3.0]],
+        );
+
+        let g = y.sum::<Rank0, _>().backward();
+        assert_close(
+            &g.get(&model.weight).array(),
+            &[[1.0; 3], [0.5; 3], [0.0; 3], [0.5; 3]],
+        );
+    }
+
+    #[test]
+    fn test_embedding_bag_keeps_mode() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builder::EmbeddingBag<4, 3>, TestDtype>();
+        model.mode = BagMode::Mean;
+
+        let model = model.to_device(&dev);
+        assert_eq!(model.mode, BagMode::Mean);
+
+        let model: EmbeddingBag<4, 3, f64, TestDevice> = model.to_dtype();
+        assert_eq!(model.mode, BagMode::Mean);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{Module, NonMutableModule, ZeroSizedModule};
+
+/// The pairwise interaction term of a [factorization machine](https://www.csie.ntu.edu.tw/~b97053/paper/Rendle2010FM.pdf),
+/// `sum_{i < j} <v_i, v_j>` over the `F` embeddings `v_i` of size `K` of each example, e.g.
+/// from an [super::modules::Embedding] per feature.
+///
+/// This is computed in `O(F * K)` as `0.5 * sum_k((sum_i v_ik)^2 - sum_i v_ik^2)`:
+/// - Reduces 2d (F, K) to 0d ()
+/// - Reduces 3d (B, F, K) to 1d (B, )
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let fm: FMInteraction = Default::default();
+/// let v: Tensor<Rank2<3, 2>, f32, _> = dev.tensor([[1.0, 0.0], [2.0, 1.0], [0.0, 3.0]]);
+/// // <v0, v1> + <v0, v2> + <v1, v2> = 2 + 0 + 3
+/// assert_eq!(fm.forward(v).array(), 5.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FMInteraction;
+
+impl ZeroSizedModule for FMInteraction {}
+impl NonMutableModule for FMInteraction {}
+
+impl<F: Dim, K: Dim, E: Dtype, D: Device<E>, T: Tape<E, D> + Merge<T>>
+    Module<Tensor<(F, K), E, D, T>> for FMInteraction
+{
+    type Output = Tensor<(), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, input: Tensor<(F, K), E, D, T>) -> Result<Self::Output, D::Err> {
+        let square_of_sum = input.retaped::<T>().try_sum::<_, Axis<0>>()?.try_square()?;
+        let sum_of_squares = input.try_square()?.try_sum::<_, Axis<0>>()?;
+        square_of_sum
+            .try_sub(sum_of_squares)?
+            .try_sum::<_, Axis<0>>()?
+            .try_mul(E::from_f32(0.5).unwrap())
+    }
+}
+
+impl<B: Dim, F: Dim, K: Dim, E: Dtype, D: Device<E>, T: Tape<E, D> + Merge<T>>
+    Module<Tensor<(B, F, K), E, D, T>> for FMInteraction
+{
+    type Output = Tensor<(B,), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, input: Tensor<(B, F, K), E, D, T>) -> Result<Self::Output, D::Err> {
+        let square_of_sum = input.retaped::<T>().try_sum::<_, Axis<1>>()?.try_square()?;
+        let sum_of_squares = input.try_square()?.try_sum::<_, Axis<1>>()?;
+        square_of_sum
+            .try_sub(sum_of_squares)?
+            .try_sum::<_, Axis<1>>()?
+            .try_mul(E::from_f32(0.5).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_fm_interaction_matches_pairwise_sum() {
+        let dev: TestDevice = Default::default();
+        let v: Tensor<Rank3<2, 4, 3>, TestDtype, _> = dev.sample_normal();
+        let r = FMInteraction.forward(v.leaky_trace());
+
+        let v_arr = v.array();
+        let mut expected = [0.0; 2];
+        let mut grad = [[[0.0; 3]; 4]; 2];
+        for b in 0..2 {
+            for i in 0..4 {
+                for j in 0..4 {
+                    for k in 0..3 {
+                        if i < j {
+                            expected[b] += v_arr[b][i][k] * v_arr[b][j][k];
+                        }
+                        // d/dv_ik = sum_{j != i} v_jk
+                        if i != j {
+                            grad[b][i][k] += v_arr[b][j][k];
+                        }
+                    }
+                }
+            }
+        }
+        assert_close(&r.array(), &expected);
+
+        let g = r.sum().backward();
+        assert_close(&g.get(&v).array(), &grad);
+    }
+
+    #[test]
+    fn test_fm_interaction_unbatched() {
+        let dev: TestDevice = Default::default();
+        let v: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let batched = FMInteraction.forward(v.clone().broadcast::<Rank3<1, 4, 3>, _>());
+        assert_close(&FMInteraction.forward(v).array(), &batched.array()[0]);
+    }
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+/// Calls `hook` with the output of `module` every time it is run, e.g. to log statistics
+/// about activations while debugging. The output is passed on unchanged.
+///
+/// Hooks can be put on any module, including each of the layers of a sequential model:
+/// ```rust
+/// # use dfdx::{prelude::*, nn::ForwardHook};
+/// # let dev: Cpu = Default::default();
+/// let l1 = dev.build_module::<Linear<2, 3>, f32>();
+/// let l2 = dev.build_module::<Linear<3, 1>, f32>();
+/// let model = (
+///     ForwardHook::new(l1, |y: &Tensor<Rank1<3>, f32, Cpu>| println!("l1 {:?}", y.array())),
+///     ForwardHook::new(l2, |y: &Tensor<Rank1<1>, f32, Cpu>| println!("l2 {:?}", y.array())),
+/// );
+/// let _ = model.forward(dev.zeros::<Rank1<2>>());
+/// ```
+#[derive(Clone)]
+pub struct ForwardHook<M, F> {
+    pub module: M,
+    pub hook: F,
+}
+
+impl<M, F> ForwardHook<M, F> {
+    /// Calls `hook` with every output of `module`.
+    pub fn new(module: M, hook: F) -> Self {
+        Self { module, hook }
+    }
+}
+
+impl<M: std::fmt::Debug, F> std::fmt::Debug for ForwardHook<M, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForwardHook")
+            .field("module", &self.module)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Input, M: Module<Input>, F: Fn(&M::Output)> Module<Input> for ForwardHook<M, F> {
+    type Output = M::Output;
+    type Error = M::Error;
+
+    fn try_forward(&self, x: Input) -> Result<Self::Output, M::Error> {
+        let y = self.module.try_forward(x)?;
+        (self.hook)(&y);
+        Ok(y)
+    }
+}
+
+impl<Input, M: ModuleMut<Input>, F: Fn(&M::Output)> ModuleMut<Input> for ForwardHook<M, F> {
+    type Output = M::Output;
+    type Error = M::Error;
+
+    fn try_forward_mut(&mut self, x: Input) -> Result<Self::Output, M::Error> {
+        let y = self.module.try_forward_mut(x)?;
+        (self.hook)(&y);
+        Ok(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, tests::*};
+    use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+    #[test]
+    fn test_forward_hook_records_output_shapes() {
+        let dev: TestDevice = Default::default();
+        let l1 = dev.build_module::<Linear<2, 3>, TestDtype>();
+        let l2 = dev.build_module::<Linear<3, 5>, TestDtype>();
+
+        type Recorded = Rc<RefCell<Vec<(&'static str, [usize; 2])>>>;
+        let shapes: Recorded = Default::default();
+        let (s1, s2) = (shapes.clone(), shapes.clone());
+        type Out<const N: usize> =
+            Tensor<Rank2<4, N>, TestDtype, TestDevice, OwnedTape<TestDtype, TestDevice>>;
+        let mut model = (
+            ForwardHook::new(l1, move |y: &Out<3>| {
+                s1.borrow_mut().push(("l1", y.shape().concrete()))
+            }),
+            ForwardHook::new(l2, move |y: &Out<5>| {
+                s2.borrow_mut().push(("l2", y.shape().concrete()))
+            }),
+        );
+
+        let x: Tensor<Rank2<4, 2>, TestDtype, _> = dev.sample_normal();
+        let y = model.forward_mut(x.leaky_trace());
+        assert_eq!(*shapes.borrow(), [("l1", [4, 3]), ("l2", [4, 5])]);
+
+        // the output is unchanged
+        let expected = model.1.module.forward(model.0.module.forward(x));
+        assert_eq!(y.array(), expected.array());
+    }
+}
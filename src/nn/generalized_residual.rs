@@ -31,12 +31,19 @@ pub struct GeneralizedResidual<F, R> {
 
 impl<D: Device<E>, E: Dtype, F: BuildOnDevice<D, E>, R: BuildOnDevice<D, E>> BuildOnDevice<D, E>
     for GeneralizedResidual<F, R>
+where
+    F::Built: 'static,
+    R::Built: 'static,
 {
     type Built = GeneralizedResidual<F::Built, R::Built>;
 }
 
-impl<E: Dtype, D: Device<E>, F: TensorCollection<E, D>, R: TensorCollection<E, D>>
-    TensorCollection<E, D> for GeneralizedResidual<F, R>
+impl<
+        E: Dtype,
+        D: Device<E>,
+        F: TensorCollection<E, D> + 'static,
+        R: TensorCollection<E, D> + 'static,
+    > TensorCollection<E, D> for GeneralizedResidual<F, R>
 {
     type To<E2: Dtype, D2: Device<E2>> = GeneralizedResidual<F::To<E2, D2>, R::To<E2, D2>>;
 
@@ -0,0 +1,177 @@
+use num_traits::Float;
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+use super::tensor_collection::*;
+
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{DeviceStorage, Gradients, Tensor},
+    tensor_ops::{Device, SumTo},
+};
+
+/// Records the L2 norm of the gradient of each named parameter of a model at every step,
+/// e.g. to plot them and spot exploding or vanishing gradients.
+///
+/// Parameters are named by their path in the model, like `"0.weight"`. Only trainable
+/// parameters are recorded, and a parameter with no gradient in a step is recorded as `0`,
+/// so every history has one entry per step.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, nn::GradientMonitor};
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<2, 5>, f32>();
+/// let mut monitor = GradientMonitor::default();
+/// for _ in 0..3 {
+///     let x: Tensor<Rank1<2>, f32, _> = dev.sample_normal();
+///     let grads = model.forward(x.traced(model.alloc_grads())).square().mean().backward();
+///     monitor.record(&model, &grads);
+/// }
+/// assert_eq!(monitor.num_steps(), 3);
+/// assert_eq!(monitor.history("weight").unwrap().len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GradientMonitor<E> {
+    history: BTreeMap<String, Vec<E>>,
+    steps: usize,
+}
+
+impl<E> Default for GradientMonitor<E> {
+    fn default() -> Self {
+        Self {
+            history: BTreeMap::new(),
+            steps: 0,
+        }
+    }
+}
+
+impl<E> GradientMonitor<E> {
+    /// The number of steps that have been recorded.
+    pub fn num_steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The gradient norms of the parameter `name` at each step, or `None` if
+    /// no parameter has that name.
+    pub fn history(&self, name: &str) -> Option<&[E]> {
+        self.history.get(name).map(|h| h.as_slice())
+    }
+
+    /// The names of the recorded parameters, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.history.keys().map(|k| k.as_str())
+    }
+
+    /// Iterates over the name & history of each recorded parameter, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[E])> {
+        self.history.iter().map(|(k, h)| (k.as_str(), h.as_slice()))
+    }
+
+    /// Forgets all the recorded steps.
+    pub fn clear(&mut self) {
+        self.history.clear();
+        self.steps = 0;
+    }
+}
+
+impl<E: Dtype + Float> GradientMonitor<E> {
+    /// Records the gradient norm of each parameter of `model` in `gradients` as a new step.
+    pub fn record<D: Device<E>, M: TensorCollection<E, D>>(
+        &mut self,
+        model: &M,
+        gradients: &Gradients<E, D>,
+    ) {
+        self.try_record(model, gradients).unwrap()
+    }
+
+    /// See [GradientMonitor::record()]
+    pub fn try_record<D: Device<E>, M: TensorCollection<E, D>>(
+        &mut self,
+        model: &M,
+        gradients: &Gradients<E, D>,
+    ) -> Result<(), D::Err> {
+        let mut norms = GradientNorms {
+            gradients,
+            norms: Vec::new(),
+        };
+        M::iter_tensors(&mut RecursiveWalker {
+            m: (model, String::new()),
+            f: &mut norms,
+        })?;
+        for (name, norm) in norms.norms {
+            self.history.entry(name).or_default().push(norm);
+        }
+        self.steps += 1;
+        Ok(())
+    }
+}
+
+struct GradientNorms<'a, E: Dtype, D: DeviceStorage> {
+    gradients: &'a Gradients<E, D>,
+    norms: Vec<(String, E)>,
+}
+
+impl<E: Dtype + Float, D: Device<E>> TensorVisitor<E, D> for GradientNorms<'_, E, D> {
+    type Viewer = (ViewTensorRef, ViewTensorName);
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        (p, name): (&Tensor<S, E, D>, String),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if opts.do_gradient_update {
+            let norm = match self.gradients.get_ref_checked(p) {
+                Some(_) => {
+                    let g = self.gradients.get(p);
+                    g.try_square()?.try_sum::<(), _>()?.as_vec()[0].sqrt()
+                }
+                None => E::zero(),
+            };
+            self.norms.push((name, norm));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders::*, DeviceBuildExt, Module, ZeroGrads},
+        shapes::*,
+        tensor::*,
+        tensor_ops::*,
+        tests::*,
+    };
+
+    #[test]
+    fn test_gradient_monitor_two_steps() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<3, 2>, TestDtype>();
+        let mut monitor = GradientMonitor::default();
+
+        let mut expected = Vec::new();
+        for _ in 0..2 {
+            let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+            let grads = model
+                .forward(x.traced(model.alloc_grads()))
+                .square()
+                .mean()
+                .backward();
+            let g = grads.get(&model.weight).as_vec();
+            expected.push(g.iter().map(|v| v * v).sum::<TestDtype>().sqrt());
+            monitor.record(&model, &grads);
+        }
+
+        assert_eq!(monitor.num_steps(), 2);
+        assert_eq!(monitor.names().collect::<Vec<_>>(), ["bias", "weight"]);
+        assert_eq!(monitor.history("bias").unwrap().len(), 2);
+        let weight = monitor.history("weight").unwrap();
+        assert_eq!(weight.len(), 2);
+        assert_close(&weight[0], &expected[0]);
+        assert_close(&weight[1], &expected[1]);
+        assert!(monitor.history("missing").is_none());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    shapes::*,
+    tensor::{Tape, Tensor},
+    tensor_ops::*,
+};
+
+use super::*;
+
+/// Calls [gradient_reversal()] with [Self::lambda], for adversarial domain adaptation.
+/// The forward is the identity, and the gradient is multiplied by `-lambda`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let grl = GradientReversal { lambda: 2.0 };
+/// let x: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, -1.0]);
+/// let r = grl.forward(x.leaky_trace());
+/// assert_eq!(r.array(), [1.0, -1.0]);
+/// let g = r.sum().backward();
+/// assert_eq!(g.get(&x).array(), [-2.0, -2.0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GradientReversal {
+    /// How much to scale the reversed gradient by. Defaults to `1.0`.
+    pub lambda: f32,
+}
+
+impl Default for GradientReversal {
+    /// Sets `self.lambda` to `1.0`
+    fn default() -> Self {
+        Self { lambda: 1.0 }
+    }
+}
+
+impl<E: Dtype, D: Device<E>> BuildOnDevice<D, E> for GradientReversal {
+    type Built = Self;
+}
+
+impl<E: Dtype, D: Device<E>> TensorCollection<E, D> for GradientReversal {
+    type To<E2: Dtype, D2: Device<E2>> = Self;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            <Self as TensorCollection<E, D>>::scalar(
+                "lambda",
+                |s| &s.lambda,
+                |s| &mut s.lambda,
+                GradientReversal::default().lambda,
+            ),
+            |lambda| GradientReversal { lambda },
+        )
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Module<Tensor<S, E, D, T>>
+    for GradientReversal
+{
+    type Output = Tensor<S, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, input: Tensor<S, E, D, T>) -> Result<Self::Output, D::Err> {
+        input.try_gradient_reversal(E::from_f32(self.lambda).unwrap())
+    }
+}
+
+impl NonMutableModule for GradientReversal {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tests::*};
+
+    #[test]
+    fn test_gradient_reversal_module() {
+        let dev: TestDevice = Default::default();
+        let grl = dev.build_module::<GradientReversal, TestDtype>();
+        assert_eq!(grl.lambda, 1.0);
+
+        let grl = GradientReversal { lambda: 0.5 };
+        let x: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let r = grl.forward(x.leaky_trace());
+        assert_eq!(r.array(), x.array());
+
+        // the upstream gradient of `(r * w).sum()` is `w`
+        let g = (r * w.clone()).sum().backward();
+        assert_close(&g.get(&x).array(), &(w * -0.5).array());
+    }
+
+    #[test]
+    fn test_gradient_reversal_keeps_lambda() {
+        let dev: TestDevice = Default::default();
+        let mut model =
+            dev.build_module::<(crate::nn::builders::Linear<2, 2>, GradientReversal), TestDtype>();
+        model.1.lambda = 0.25;
+
+        let model = model.to_device(&dev);
+        assert_eq!(model.1.lambda, 0.25);
+
+        let model: (
+            crate::nn::modules::Linear<2, 2, f64, TestDevice>,
+            GradientReversal,
+        ) = model.to_dtype();
+        assert_eq!(model.1.lambda, 0.25);
+    }
+}
@@ -4,7 +4,7 @@ use super::*;
 
 macro_rules! tuple_impls {
     ([$($name:ident),+] [$($idx:tt),+], $last:ident, [$($rev_tail:ident),+]) => {
-        impl<E: Dtype, D: Device<E>, $($name: TensorCollection<E, D>),+> TensorCollection<E, D> for ($($name,)+) {
+        impl<E: Dtype, D: Device<E>, $($name: TensorCollection<E, D> + 'static),+> TensorCollection<E, D> for ($($name,)+) {
             type To<E2: Dtype, D2: Device<E2>> = ($($name::To<E2, D2>,)+);
 
             #[allow(non_snake_case)]
@@ -18,7 +18,10 @@ macro_rules! tuple_impls {
             }
         }
 
-        impl<D: Device<E>, E: Dtype, $($name: BuildOnDevice<D, E>),+> BuildOnDevice<D, E> for ($($name,)+) {
+        impl<D: Device<E>, E: Dtype, $($name: BuildOnDevice<D, E>),+> BuildOnDevice<D, E> for ($($name,)+)
+        where
+            $($name::Built: 'static),+
+        {
             type Built = ($($name::Built, )+);
         }
 
@@ -0,0 +1,203 @@
+use std::{string::String, vec::Vec};
+
+use crate::{shapes::*, tensor::*, tensor_ops::Device};
+
+use super::*;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Repeats `T` `N` times like [super::modules::Repeated], but randomly skips whole layers
+/// during training, as introduced in
+/// [Reducing Transformer Depth on Demand with Structured Dropout](https://arxiv.org/abs/1909.11556).
+///
+/// - [ModuleMut] (training): each layer is skipped with probability `p`, and passes its input
+///   through unchanged.
+/// - [Module] (inference): always runs every layer.
+///
+/// Unlike [super::modules::StochasticDepth], the outputs of the kept layers are not scaled,
+/// because a skipped layer returns its input rather than dropping a residual branch. Layers
+/// like [super::modules::TransformerEncoderBlock] contain their own residual connections.
+///
+/// # Generics
+/// - `T` the [Module] to repeat. Its input must be the same as its output.
+/// - `N` the number of times to repeat `T`.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = LayerDrop<TransformerEncoderBlock<16, 4, 32>, 6>;
+/// let mut model = dev.build_module::<Model, f32>();
+/// model.p = 0.5;
+/// let x: Tensor<Rank2<3, 16>, f32, _> = dev.sample_normal();
+/// let y = model.forward_mut(x.leaky_trace());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayerDrop<T, const N: usize> {
+    pub modules: Vec<T>,
+    /// Probability of skipping each layer during training. Defaults to `0.2`.
+    pub p: f32,
+    /// Whether [ModuleMut] randomly skips layers. If `false`, it always runs every
+    /// layer like [Module] does. Defaults to `true`. See [SetTraining].
+    pub training: bool,
+}
+
+impl<D: Device<E>, E: Dtype, T: BuildOnDevice<D, E>, const N: usize> BuildOnDevice<D, E>
+    for LayerDrop<T, N>
+where
+    T::Built: 'static,
+{
+    type Built = LayerDrop<T::Built, N>;
+}
+
+impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D> + 'static, const N: usize>
+    TensorCollection<E, D> for LayerDrop<T, N>
+{
+    type To<E2: Dtype, D2: Device<E2>> = LayerDrop<T::To<E2, D2>, N>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        let names: Vec<String> = (0..N).map(|i| format!("{i}")).collect();
+
+        visitor.visit_fields(
+            (
+                (0..N)
+                    .zip(names.iter())
+                    .map(|(i, name)| {
+                        Self::module(name, move |s| &s.modules[i], move |s| &mut s.modules[i])
+                    })
+                    .collect::<Vec<_>>(),
+                Self::scalar("p", |s| &s.p, |s| &mut s.p, 0.2),
+                Self::scalar("training", |s| &s.training, |s| &mut s.training, true),
+            ),
+            |(modules, p, training)| LayerDrop {
+                modules,
+                p,
+                training,
+            },
+        )
+    }
+
+    fn set_training_mode(&mut self, training: bool) {
+        self.training = training;
+    }
+}
+
+impl<T, const N: usize> std::ops::Index<usize> for LayerDrop<T, N> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.modules[index]
+    }
+}
+
+impl<Input, T: Module<Input, Output = Input>, const N: usize> Module<Input> for LayerDrop<T, N> {
+    type Output = T::Output;
+    type Error = T::Error;
+
+    /// Runs every layer.
+    fn try_forward(&self, mut x: Input) -> Result<Self::Output, T::Error> {
+        for i in 0..N {
+            x = self.modules[i].try_forward(x)?;
+        }
+        Ok(x)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T, const N: usize>
+    ModuleMut<Tensor<S, E, D, OwnedTape<E, D>>> for LayerDrop<T, N>
+where
+    T: ModuleMut<
+        Tensor<S, E, D, OwnedTape<E, D>>,
+        Output = Tensor<S, E, D, OwnedTape<E, D>>,
+        Error = D::Err,
+    >,
+{
+    type Output = Tensor<S, E, D, OwnedTape<E, D>>;
+    type Error = D::Err;
+
+    /// Skips each layer with probability `p`, using the device's rng.
+    /// Runs every layer if `self.training` is false.
+    fn try_forward_mut(
+        &mut self,
+        mut x: Tensor<S, E, D, OwnedTape<E, D>>,
+    ) -> Result<Self::Output, D::Err> {
+        let mut rng = StdRng::seed_from_u64(x.device.random_u64());
+        for i in 0..N {
+            if self.training && rng.gen::<f32>() < self.p {
+                continue;
+            }
+            x = self.modules[i].try_forward_mut(x)?;
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use crate::{nn::builders::Linear, tensor_ops::*};
+
+    #[test]
+    fn test_layer_drop_inference_runs_all_layers() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<LayerDrop<Linear<2, 2>, 3>, TestDtype>();
+        model.p = 1.0;
+        let x: Tensor<Rank2<4, 2>, TestDtype, _> = dev.sample_normal();
+        let expected = model[2].forward(model[1].forward(model[0].forward(x.clone())));
+        assert_close(&model.forward(x.clone()).array(), &expected.array());
+
+        model.set_training(false);
+        let y = model.forward_mut(x.leaky_trace());
+        assert_close(&y.array(), &expected.array());
+    }
+
+    #[test]
+    fn test_layer_drop_skips_layers() {
+        let dev = TestDevice::seed_from_u64(0);
+        let mut model = dev.build_module::<LayerDrop<Linear<2, 2>, 4>, TestDtype>();
+        model.p = 0.5;
+        let x: Tensor<Rank2<4, 2>, TestDtype, _> = dev.sample_normal();
+
+        let mut num_skipped = 0;
+        for _ in 0..25 {
+            let y = model.forward_mut(x.trace(model.alloc_grads()));
+            let y_array = y.array();
+            let g = y.sum().backward();
+
+            // only the kept layers get gradients, and they are all that ran
+            let mut expected = x.clone();
+            for i in 0..4 {
+                let kept = g.get(&model[i].weight).array() != [[0.0; 2]; 2];
+                if kept {
+                    expected = model[i].forward(expected);
+                } else {
+                    assert_eq!(g.get(&model[i].bias).array(), [0.0; 2]);
+                    num_skipped += 1;
+                }
+            }
+            assert_close(&y_array, &expected.array());
+        }
+        // 100 layers skipped with probability 0.5
+        assert!(
+            (30..70).contains(&num_skipped),
+            "expected about 50 skipped layers, got {num_skipped}"
+        );
+    }
+
+    #[test]
+    fn test_layer_drop_keeps_p_and_training() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<LayerDrop<Linear<2, 2>, 3>, TestDtype>();
+        model.p = 0.5;
+        model.set_training(false);
+
+        let model = model.to_device(&dev);
+        assert_eq!((model.p, model.training), (0.5, false));
+
+        let model: LayerDrop<crate::nn::modules::Linear<2, 2, f64, TestDevice>, 3> =
+            model.to_dtype();
+        assert_eq!((model.p, model.training), (0.5, false));
+    }
+}
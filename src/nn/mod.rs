@@ -23,6 +23,13 @@
 //! - [modules::BatchNorm2D]
 //! - [modules::DropoutOneIn]
 //! - [modules::Dropout]
+//! - [modules::LayerDrop]
+//! - [modules::SpectralNorm]
+//! - [modules::StochasticDepth]
+//!
+//! Use [SetTraining::set_training()] to make [ModuleMut::forward_mut()] of these (except the
+//! dropouts) behave like evaluation while still tracking gradients, e.g. to fine-tune with
+//! frozen batch norm statistics.
 //!
 //! # Fallible forwards
 //!
@@ -76,6 +83,27 @@
 //! model.zero_grads(&mut grads);
 //! ```
 //!
+//! Passing the same gradients into multiple `.trace()` calls accumulates into them,
+//! which is how you can average gradients over several batches before calling
+//! `optimizer.update()`:
+//!
+//! ```rust
+//! # use dfdx::{prelude::*, optim::*};
+//! # let dev: Cpu = Default::default();
+//! # type Model = Linear<5, 2>;
+//! let mut model = dev.build_module::<Model, f32>();
+//! let mut opt = Sgd::new(&model, Default::default());
+//! let accumulation_steps = 4;
+//! let mut grads = model.alloc_grads();
+//! for _ in 0..accumulation_steps {
+//!     let x: Tensor<Rank2<8, 5>, f32, _> = dev.sample_normal();
+//!     let loss = model.forward(x.trace(grads)).square().mean() / accumulation_steps as f32;
+//!     grads = loss.backward();
+//! }
+//! opt.update(&mut model, &grads).unwrap();
+//! model.zero_grads(&mut grads);
+//! ```
+//!
 //! # Exponential Moving Average (EMA)
 //!
 //! All models implement [ModelEMA::ema()] to keep track of an exponential moving average
@@ -149,6 +177,11 @@
 //! mlp.load_state_dict(state_dict)
 //! ```
 //!
+//! Going the other way, `np.savez("model.npz", **{k: v.numpy() for k, v in mlp.state_dict().items()})`
+//! can be loaded with [LoadFromNpz::load()], as long as the keys match the field names of the
+//! dfdx model (e.g. `0.weight`, `2.bias`). Use [SaveToNpz::write()] and
+//! [LoadFromNpz::read()] with a prefix to store several modules in one archive.
+//!
 //! # safetensors
 //!
 //! Enable with the `"safetensors"` feature.
@@ -176,40 +209,68 @@
 mod build_module;
 mod num_params;
 mod reset_params;
+mod set_training;
 pub mod tensor_collection;
 mod to_device;
 mod to_dtype;
+mod visit_modules;
 mod zero_grads;
 
 mod module;
 
 mod activations;
 mod add_into;
+mod autocast;
 mod batchnorm1d;
 mod batchnorm2d;
 mod bias2d;
+mod checkpointed_repeated;
 mod conv;
 mod convtrans;
+mod cross_layer;
 mod dropout;
 mod ema;
 mod embedding;
+mod embedding_bag;
 mod flatten;
+mod fm_interaction;
+mod forward_hook;
 mod generalized_residual;
+mod gradient_monitor;
+mod gradient_reversal;
 mod impl_module_for_tuples;
+mod layer_drop;
 mod layer_norm;
 mod linear;
+mod moe;
 #[cfg(feature = "numpy")]
 mod npz;
+mod pipeline;
+mod polyak;
 mod pool2d;
+mod pool_adaptive;
 mod pool_global;
 mod repeated;
 mod residual;
+mod residual_relu;
+mod rms_norm;
 #[cfg(feature = "safetensors")]
 mod safetensors;
+mod se_block;
+mod shard;
+mod snapshot_ensemble;
+mod spectral_norm;
 mod split_into;
+mod stochastic_depth;
+mod swa;
+mod swiglu;
+mod tabular_encoder;
+mod tcn;
 mod transformer;
 mod unbiased_linear;
 mod upscale;
+mod weight_histograms;
+mod weight_norm;
 
 pub use module::{
     BuildModule, BuildOnDevice, DeviceBuildExt, Module, ModuleMut, NonMutableModule,
@@ -220,13 +281,26 @@ pub use tensor_collection::*;
 
 #[cfg(feature = "safetensors")]
 pub use self::safetensors::{LoadFromSafetensors, SaveToSafetensors};
+#[cfg(feature = "nightly")]
+pub use conv::{fold_batchnorm, try_fold_batchnorm};
 pub use ema::ModelEMA;
+pub use forward_hook::ForwardHook;
+pub use gradient_monitor::GradientMonitor;
+pub use moe::{combine, dispatch, try_combine, try_dispatch};
 #[cfg(feature = "numpy")]
 pub use npz::{LoadFromNpz, SaveToNpz};
 pub use num_params::NumParams;
+pub use pipeline::{partition, PipelineStage};
+pub use polyak::PolyakAverager;
 pub use reset_params::ResetParams;
+pub use set_training::SetTraining;
+pub use shard::{all_gather, shard_output, try_all_gather, try_shard_output};
+pub use snapshot_ensemble::SnapshotEnsemble;
+pub use swa::{try_update_bn_stats, update_bn_stats, Swa};
 pub use to_device::ToDevice;
 pub use to_dtype::ToDtype;
+pub use visit_modules::{ModuleDescriptor, VisitModules};
+pub use weight_histograms::weight_histograms;
 pub use zero_grads::ZeroGrads;
 
 pub mod modules {
@@ -235,32 +309,52 @@ pub mod modules {
     //! in a device/dtype agnostic way.
     pub use super::activations::*;
     pub use super::add_into::AddInto;
+    pub use super::autocast::AutoCast;
     pub use super::batchnorm1d::BatchNorm1D;
     pub use super::batchnorm2d::BatchNorm2D;
     pub use super::bias2d::Bias2D;
+    pub use super::checkpointed_repeated::CheckpointedRepeated;
     #[cfg(feature = "nightly")]
     pub use super::conv::Conv2D;
     #[cfg(feature = "nightly")]
     pub use super::convtrans::ConvTrans2D;
+    pub use super::cross_layer::CrossLayer;
     pub use super::dropout::{Dropout, DropoutOneIn};
     pub use super::embedding::Embedding;
+    pub use super::embedding_bag::{BagMode, EmbeddingBag};
     #[cfg(feature = "nightly")]
     pub use super::flatten::Flatten2D;
+    pub use super::fm_interaction::FMInteraction;
     pub use super::generalized_residual::GeneralizedResidual;
+    pub use super::gradient_reversal::GradientReversal;
+    pub use super::layer_drop::LayerDrop;
     pub use super::layer_norm::LayerNorm1D;
     pub use super::linear::Linear;
+    pub use super::moe::Router;
     #[cfg(feature = "nightly")]
     pub use super::pool2d::{AvgPool2D, MaxPool2D, MinPool2D};
+    pub use super::pool_adaptive::AdaptiveAvgPool2D;
     pub use super::pool_global::{AvgPoolGlobal, MaxPoolGlobal, MinPoolGlobal};
     pub use super::repeated::Repeated;
     pub use super::residual::Residual;
+    pub use super::residual_relu::ResidualReLU;
+    pub use super::rms_norm::RMSNorm;
+    pub use super::se_block::SEBlock;
+    pub use super::spectral_norm::SpectralNorm;
     pub use super::split_into::SplitInto;
+    pub use super::stochastic_depth::StochasticDepth;
+    pub use super::swiglu::SwiGLU;
+    pub use super::tabular_encoder::TabularEncoder;
+    pub use super::tcn::TCNBlock;
     pub use super::transformer::{
-        MultiHeadAttention, Transformer, TransformerDecoder, TransformerDecoderBlock,
-        TransformerEncoder, TransformerEncoderBlock,
+        KVCache, MultiHeadAttention, MultiHeadAttentionDropout, Transformer, TransformerDecoder,
+        TransformerDecoderBlock, TransformerEncoder, TransformerEncoderBlock,
     };
     pub use super::unbiased_linear::UnbiasedLinear;
     pub use super::upscale::Upscale2D;
+    #[cfg(feature = "nightly")]
+    pub use super::upscale::Upscale2DBy;
+    pub use super::weight_norm::WeightNorm;
     pub use super::*;
 }
 
@@ -269,31 +363,51 @@ pub mod builders {
     //! worrying about device or dtype.
     pub use super::activations::*;
     pub use super::add_into::AddInto;
+    pub use super::autocast::AutoCast;
     pub use super::batchnorm1d::builder::BatchNorm1D;
     pub use super::batchnorm2d::builder::BatchNorm2D;
     pub use super::bias2d::builder::Bias2D;
+    pub use super::checkpointed_repeated::CheckpointedRepeated;
     #[cfg(feature = "nightly")]
     pub use super::conv::builder::Conv2D;
     #[cfg(feature = "nightly")]
     pub use super::convtrans::builder::ConvTrans2D;
+    pub use super::cross_layer::builder::CrossLayer;
     pub use super::dropout::{Dropout, DropoutOneIn};
     pub use super::embedding::builder::Embedding;
+    pub use super::embedding_bag::{builder::EmbeddingBag, BagMode};
     #[cfg(feature = "nightly")]
     pub use super::flatten::Flatten2D;
+    pub use super::fm_interaction::FMInteraction;
     pub use super::generalized_residual::GeneralizedResidual;
+    pub use super::gradient_reversal::GradientReversal;
+    pub use super::layer_drop::LayerDrop;
     pub use super::layer_norm::builder::LayerNorm1D;
     pub use super::linear::builder::Linear;
+    pub use super::moe::builder::Router;
     #[cfg(feature = "nightly")]
     pub use super::pool2d::{AvgPool2D, MaxPool2D, MinPool2D};
+    pub use super::pool_adaptive::AdaptiveAvgPool2D;
     pub use super::pool_global::{AvgPoolGlobal, MaxPoolGlobal, MinPoolGlobal};
     pub use super::repeated::Repeated;
     pub use super::residual::Residual;
+    pub use super::residual_relu::ResidualReLU;
+    pub use super::rms_norm::builder::RMSNorm;
+    pub use super::se_block::builder::SEBlock;
+    pub use super::spectral_norm::builder::SpectralNorm;
     pub use super::split_into::SplitInto;
+    pub use super::stochastic_depth::StochasticDepth;
+    pub use super::swiglu::builder::SwiGLU;
+    pub use super::tabular_encoder::builder::TabularEncoder;
+    pub use super::tcn::builder::TCNBlock;
     pub use super::transformer::builder::{
-        MultiHeadAttention, Transformer, TransformerDecoder, TransformerDecoderBlock,
-        TransformerEncoder, TransformerEncoderBlock,
+        MultiHeadAttention, MultiHeadAttentionDropout, Transformer, TransformerDecoder,
+        TransformerDecoderBlock, TransformerEncoder, TransformerEncoderBlock,
     };
     pub use super::unbiased_linear::builder::UnbiasedLinear;
     pub use super::upscale::Upscale2D;
+    #[cfg(feature = "nightly")]
+    pub use super::upscale::Upscale2DBy;
+    pub use super::weight_norm::builder::WeightNorm;
     pub use super::*;
 }
@@ -0,0 +1,309 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{modules::Linear, *};
+
+use num_traits::Float;
+use rand_distr::uniform::SampleUniform;
+
+pub mod builder {
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Router<const M: usize, const N: usize>;
+}
+
+impl<const M: usize, const N: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::Router<M, N>
+where
+    Router<M, N, E, D>: BuildModule<D, E>,
+{
+    type Built = Router<M, N, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// Top-1 mixture of experts routing, as in
+/// [Switch Transformers](https://arxiv.org/abs/2101.03961).
+///
+/// [Router::route()] sends each token to the expert with the largest logit from [Self::gate],
+/// and weights the output of that expert by its softmax probability, so the gate is trained
+/// through the outputs of the experts. Use [dispatch()] to split the tokens up between the
+/// experts, and [combine()] to put the outputs of the experts back together.
+///
+/// # Generics
+/// - `M` The size of each token.
+/// - `N` The number of experts.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::{prelude::*, nn::{combine, dispatch}};
+/// # let dev: Cpu = Default::default();
+/// let router = dev.build_module::<Router<4, 2>, f32>();
+/// let experts = [
+///     dev.build_module::<Linear<4, 3>, f32>(),
+///     dev.build_module::<Linear<4, 3>, f32>(),
+/// ];
+/// let x: Tensor<Rank2<5, 4>, f32, _> = dev.sample_normal();
+/// let (assignments, gate) = router.route(x.clone());
+/// let outputs = dispatch(x, &assignments, 2)
+///     .into_iter()
+///     .map(|(e, tokens)| (e, experts[e].forward(tokens)))
+///     .collect();
+/// let y = combine(outputs, &assignments, gate);
+/// assert_eq!(y.shape(), &(Const::<5>, Const::<3>));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Router<const M: usize, const N: usize, E: Dtype, D: DeviceStorage> {
+    /// Computes one logit per expert for each token.
+    pub gate: Linear<M, N, E, D>,
+}
+
+impl<const M: usize, const N: usize, E: Dtype, D: DeviceStorage> NonMutableModule
+    for Router<M, N, E, D>
+{
+}
+
+impl<const M: usize, const N: usize, E: Dtype + Float + SampleUniform, D: Device<E>>
+    TensorCollection<E, D> for Router<M, N, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = Router<M, N, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(Self::module("gate", |s| &s.gate, |s| &mut s.gate), |gate| {
+            Router { gate }
+        })
+    }
+}
+
+impl<const M: usize, const N: usize, E: Dtype, D: Device<E>> Router<M, N, E, D> {
+    /// Returns the expert of each token, and the weight of its output.
+    /// See [Tensor::top1_gate()].
+    pub fn route<B: Dim, T: Tape<E, D>>(
+        &self,
+        x: Tensor<(B, Const<M>), E, D, T>,
+    ) -> (std::vec::Vec<usize>, Tensor<(B,), E, D, T>) {
+        self.try_route(x).unwrap()
+    }
+
+    /// See [Router::route()]
+    #[allow(clippy::type_complexity)]
+    pub fn try_route<B: Dim, T: Tape<E, D>>(
+        &self,
+        x: Tensor<(B, Const<M>), E, D, T>,
+    ) -> Result<(std::vec::Vec<usize>, Tensor<(B,), E, D, T>), D::Err> {
+        self.gate.try_forward(x)?.try_top1_gate()
+    }
+}
+
+impl<B: Dim, N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(B, N), E, D, T> {
+    /// Top-1 gating over the logits of `N` experts for each of `B` tokens. Returns the
+    /// argmax expert of each token, along with its softmax probability.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let logits: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[0.0, 1.0], [2.0, 2.0]]);
+    /// let (experts, gate) = logits.top1_gate();
+    /// assert_eq!(experts, [1, 0]);
+    /// assert_eq!(gate.array(), [1.0 / (1.0 + (-1.0f32).exp()), 0.5]);
+    /// ```
+    pub fn top1_gate(self) -> (std::vec::Vec<usize>, Tensor<(B,), E, D, T>) {
+        self.try_top1_gate().unwrap()
+    }
+
+    /// See [Tensor::top1_gate()]
+    #[allow(clippy::type_complexity)]
+    pub fn try_top1_gate(self) -> Result<(std::vec::Vec<usize>, Tensor<(B,), E, D, T>), D::Err> {
+        let (b, n) = self.shape;
+        let logits = self.as_vec();
+        let experts: std::vec::Vec<usize> = (0..b.size())
+            .map(|i| {
+                let row = &logits[i * n.size()..(i + 1) * n.size()];
+                let mut best = 0;
+                for (j, v) in row.iter().enumerate() {
+                    if *v > row[best] {
+                        best = j;
+                    }
+                }
+                best
+            })
+            .collect();
+        let idx = self.device.try_tensor_from_vec(experts.clone(), (b,))?;
+        let gate = self.try_softmax::<Axis<1>>()?.try_select(idx)?;
+        Ok((experts, gate))
+    }
+}
+
+/// Splits the tokens `x` between `num_experts` experts, where token `i` goes to expert
+/// `experts[i]`. Returns each expert that was given any tokens, along with its tokens in
+/// order. The tape of `x` goes with the tokens of the first of these experts.
+///
+/// **Panics** if `experts` doesn't have one entry per token, or an expert is out of bounds.
+#[allow(clippy::type_complexity)]
+pub fn dispatch<B: Dim, M: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    x: Tensor<(B, M), E, D, T>,
+    experts: &[usize],
+    num_experts: usize,
+) -> std::vec::Vec<(usize, Tensor<(usize, M), E, D, T>)> {
+    try_dispatch(x, experts, num_experts).unwrap()
+}
+
+/// See [dispatch()]
+#[allow(clippy::type_complexity)]
+pub fn try_dispatch<B: Dim, M: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    x: Tensor<(B, M), E, D, T>,
+    experts: &[usize],
+    num_experts: usize,
+) -> Result<std::vec::Vec<(usize, Tensor<(usize, M), E, D, T>)>, D::Err> {
+    assert_eq!(
+        experts.len(),
+        x.shape.0.size(),
+        "expected one expert per token"
+    );
+    assert!(
+        experts.iter().all(|&e| e < num_experts),
+        "experts must be less than {num_experts}"
+    );
+    let (x, tape) = x.split_tape();
+    let mut tape = Some(tape);
+    let mut dispatched = std::vec::Vec::with_capacity(num_experts);
+    for e in 0..num_experts {
+        let rows: std::vec::Vec<usize> = (0..experts.len()).filter(|&i| experts[i] == e).collect();
+        if rows.is_empty() {
+            continue;
+        }
+        let n = rows.len();
+        let idx = x.device.try_tensor_from_vec(rows, (n,))?;
+        let tokens = match tape.take() {
+            Some(tape) => x.clone().put_tape(tape),
+            None => x.retaped::<T>(),
+        };
+        dispatched.push((e, tokens.try_gather(idx)?));
+    }
+    Ok(dispatched)
+}
+
+/// Puts the outputs of the experts back in the order of the tokens, and multiplies
+/// each by its weight in `gate`. This is the inverse of [dispatch()], where `outputs`
+/// are the outputs of each expert on the tokens it was given.
+///
+/// **Panics** if the outputs don't have one row for each token given to their expert.
+#[allow(clippy::type_complexity)]
+pub fn combine<B: Dim, O: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    outputs: std::vec::Vec<(usize, Tensor<(usize, O), E, D, T>)>,
+    experts: &[usize],
+    gate: Tensor<(B,), E, D, T>,
+) -> Tensor<(B, O), E, D, T> {
+    try_combine(outputs, experts, gate).unwrap()
+}
+
+/// See [combine()]
+#[allow(clippy::type_complexity)]
+pub fn try_combine<B: Dim, O: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    outputs: std::vec::Vec<(usize, Tensor<(usize, O), E, D, T>)>,
+    experts: &[usize],
+    gate: Tensor<(B,), E, D, T>,
+) -> Result<Tensor<(B, O), E, D, T>, D::Err> {
+    let b = gate.shape.0;
+    assert_eq!(experts.len(), b.size(), "expected one expert per token");
+
+    // the row of each token after concatenating the outputs of all the experts
+    let mut offsets = std::collections::BTreeMap::new();
+    let mut total = 0;
+    for (e, out) in outputs.iter() {
+        let n = experts.iter().filter(|&x| x == e).count();
+        assert_eq!(out.shape.0, n, "expert {e} was given {n} tokens");
+        offsets.insert(*e, total);
+        total += n;
+    }
+    assert_eq!(total, b.size(), "missing the outputs of some experts");
+    let rows = experts
+        .iter()
+        .map(|e| {
+            let offset = offsets.get_mut(e).unwrap();
+            *offset += 1;
+            *offset - 1
+        })
+        .collect();
+    let idx = gate.device.try_tensor_from_vec(rows, (b,))?;
+
+    let mut outputs = outputs.into_iter().map(|(_, out)| out);
+    let mut catted = outputs.next().expect("combine needs at least one token");
+    for out in outputs {
+        catted = catted.try_concat(out)?;
+    }
+    let y = catted.try_gather(idx)?;
+    let shape = *y.shape();
+    y.try_mul(gate.try_broadcast_like(&shape)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_router_top1_dispatch_combine() {
+        let dev: TestDevice = Default::default();
+        let mut router = dev.build_module::<builder::Router<2, 2>, TestDtype>();
+        router.gate.weight = dev.tensor([[1.0, 0.0], [0.0, 1.0]]);
+        router.gate.bias = dev.zeros();
+        let experts = [
+            dev.build_module::<builders::Linear<2, 3>, TestDtype>(),
+            dev.build_module::<builders::Linear<2, 3>, TestDtype>(),
+        ];
+
+        let x: Tensor<Rank2<4, 2>, TestDtype, _> =
+            dev.tensor([[1.0, 0.0], [0.0, 2.0], [-1.0, 1.0], [3.0, 1.0]]);
+        let (assignments, gate) = router.route(x.leaky_trace());
+        assert_eq!(assignments, [0, 1, 1, 0]);
+        let e: [TestDtype; 4] = [1.0, 2.0, 2.0, 2.0];
+        let p = e.map(|d| 1.0 / (1.0 + (-d).exp()));
+        assert_close(&gate.array(), &p);
+
+        let tokens = dispatch(x.retaped::<OwnedTape<_, _>>(), &assignments, 2);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].1.shape().0, 2);
+        assert_eq!(tokens[1].1.shape().0, 2);
+        let outputs = tokens
+            .into_iter()
+            .map(|(e, t)| (e, experts[e].forward(t)))
+            .collect();
+        let y = combine(outputs, &assignments, gate);
+
+        let y0 = experts[0].forward(x.clone()).array();
+        let y1 = experts[1].forward(x.clone()).array();
+        let mut expected = [[0.0; 3]; 4];
+        for i in 0..4 {
+            let row = if assignments[i] == 0 { y0[i] } else { y1[i] };
+            expected[i] = row.map(|v| v * p[i]);
+        }
+        assert_close(&y.array(), &expected);
+
+        // the gate is trained through the outputs of the experts
+        let g = y.sum().backward();
+        assert!(g
+            .get(&router.gate.weight)
+            .array()
+            .iter()
+            .flatten()
+            .any(|v| *v != 0.0));
+        assert!(g.get(&x).array().iter().flatten().any(|v| *v != 0.0));
+    }
+
+    #[test]
+    fn test_dispatch_skips_experts_without_tokens() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<3, 2>, TestDtype, _> = dev.sample_normal();
+        let tokens = dispatch(x.clone(), &[2, 0, 2], 3);
+        assert_eq!(
+            tokens.iter().map(|(e, _)| *e).collect::<std::vec::Vec<_>>(),
+            [0, 2]
+        );
+
+        let gate = dev.ones::<Rank1<3>>();
+        let y = combine(tokens, &[2, 0, 2], gate);
+        assert_eq!(y.array(), x.array());
+    }
+}
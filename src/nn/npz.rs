@@ -35,12 +35,13 @@ pub trait SaveToNpz<E: Dtype + NumpyDtype, D: Device<E>>: TensorCollection<E, D>
         let f = std::fs::File::create(path)?;
         let f = BufWriter::new(f);
         let mut zip = ZipWriter::new(f);
-        self.write(&mut zip)?;
+        self.write("", &mut zip)?;
         zip.finish()?;
         Ok(())
     }
 
     /// Write this object into [ZipWriter] `w` with a base filename of `filename_prefix`.
+    /// This lets multiple objects be saved into a single archive.
     ///
     /// Example:
     /// ```ignore
@@ -51,20 +52,29 @@ pub trait SaveToNpz<E: Dtype + NumpyDtype, D: Device<E>>: TensorCollection<E, D>
     /// model.write("1", &mut zip)?;
     /// ```
     /// Will save a zip file with the following files in it:
-    /// - `0.weight.npy`
-    /// - `0.bias.npy`
-    /// - `1.weight.npy`
-    /// - `1.bias.npy`
-    fn write<W>(&self, w: &mut ZipWriter<W>) -> ZipResult<()>
+    /// - `0.weight`
+    /// - `0.bias`
+    /// - `1.weight`
+    /// - `1.bias`
+    fn write<W>(&self, filename_prefix: &str, w: &mut ZipWriter<W>) -> ZipResult<()>
     where
         W: Write + Seek,
     {
         Self::iter_tensors(&mut RecursiveWalker {
-            m: (self, String::new()),
+            m: (self, String::from(filename_prefix)),
             f: w,
         })?;
         Ok(())
     }
+
+    /// Same as [SaveToNpz::write()] with an empty prefix, so each tensor is named by
+    /// its path in the module.
+    fn write_unprefixed<W>(&self, w: &mut ZipWriter<W>) -> ZipResult<()>
+    where
+        W: Write + Seek,
+    {
+        self.write("", w)
+    }
 }
 impl<E: Dtype + NumpyDtype, D: Device<E>, T: TensorCollection<E, D>> SaveToNpz<E, D> for T {}
 
@@ -84,11 +94,11 @@ pub trait LoadFromNpz<E: Dtype + NumpyDtype, D: Device<E>>: TensorCollection<E,
         let f = std::fs::File::open(path)?;
         let f = BufReader::new(f);
         let mut zip = ZipArchive::new(f)?;
-        self.read(&mut zip)?;
+        self.read("", &mut zip)?;
         Ok(())
     }
 
-    /// Reads this object from a [ZipArchive]. `r` with a base filename of `filename_prefix`.
+    /// Reads this object from a [ZipArchive] `r` with a base filename of `filename_prefix`.
     ///
     /// Example:
     /// ```ignore
@@ -97,19 +107,31 @@ pub trait LoadFromNpz<E: Dtype + NumpyDtype, D: Device<E>>: TensorCollection<E,
     /// let mut zip = ZipArchive::new(...);
     /// model.read("0", &mut zip)?;
     /// ```
-    /// Will try to read data from the following files:
-    /// - `0.weight.npy`
-    /// - `0.bias.npy`
-    fn read<R>(&mut self, r: &mut ZipArchive<R>) -> Result<(), NpzError>
+    /// Will try to read data from the following files (with or without a `.npy` extension):
+    /// - `0.weight`
+    /// - `0.bias`
+    ///
+    /// Returns [NpzError::MissingKey] if one of the files isn't in the archive, and a
+    /// [NpzError::Npy] if one has a different shape or dtype.
+    fn read<R>(&mut self, filename_prefix: &str, r: &mut ZipArchive<R>) -> Result<(), NpzError>
     where
         R: Read + Seek,
     {
         Self::iter_tensors(&mut RecursiveWalker {
-            m: (self, String::new()),
+            m: (self, String::from(filename_prefix)),
             f: r,
         })?;
         Ok(())
     }
+
+    /// Same as [LoadFromNpz::read()] with an empty prefix, so each tensor is named by
+    /// its path in the module.
+    fn read_unprefixed<R>(&mut self, r: &mut ZipArchive<R>) -> Result<(), NpzError>
+    where
+        R: Read + Seek,
+    {
+        self.read("", r)
+    }
 }
 impl<E: Dtype + NumpyDtype, D: Device<E>, T: TensorCollection<E, D>> LoadFromNpz<E, D> for T {}
 
@@ -154,12 +176,17 @@ mod tests {
     use crate::{
         nn::builders::*,
         shapes::*,
-        tensor::{numpy::NumpyDtype, AsArray, SampleTensor, Tensor},
+        tensor::{
+            numpy::{NpzError, NumpyDtype},
+            AsArray, SampleTensor, Tensor,
+        },
         tensor_ops::Device,
         tests::{TestDevice, TestDtype},
     };
     use rand_distr::{Distribution, Standard, StandardNormal};
+    use std::io::Cursor;
     use tempfile::NamedTempFile;
+    use zip::{ZipArchive, ZipWriter};
 
     fn test_save_load<S: ConstShape, E: Dtype + NumpyDtype, D: Device<E>, M: BuildOnDevice<D, E>>(
         dev: &D,
@@ -334,4 +361,133 @@ mod tests {
         let y2 = loaded.forward_mut((src.clone(), tgt.clone()));
         assert_eq!(y1.array(), y2.array());
     }
+
+    #[test]
+    fn test_save_load_with_prefix() {
+        let dev: TestDevice = Default::default();
+        type T = Linear<3, 3>;
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+
+        let encoder = T::build_on_device(&dev);
+        let decoder = T::build_on_device(&dev);
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        encoder.write("encoder", &mut zip).unwrap();
+        decoder.write("decoder", &mut zip).unwrap();
+        let mut zip = ZipArchive::new(zip.finish().unwrap()).unwrap();
+
+        let mut names: Vec<&str> = zip.file_names().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "decoder.bias",
+                "decoder.weight",
+                "encoder.bias",
+                "encoder.weight"
+            ]
+        );
+
+        let mut loaded = T::build_on_device(&dev);
+        loaded.read("decoder", &mut zip).unwrap();
+        assert_eq!(
+            loaded.forward(x.clone()).array(),
+            decoder.forward(x.clone()).array()
+        );
+        loaded.read("encoder", &mut zip).unwrap();
+        assert_eq!(
+            loaded.forward(x.clone()).array(),
+            encoder.forward(x).array()
+        );
+    }
+
+    #[test]
+    fn test_load_numpy_keys() {
+        let dev: TestDevice = Default::default();
+        type T = (Linear<2, 3>, ReLU, Linear<3, 1>);
+        let x: Tensor<Rank1<2>, TestDtype, _> = dev.sample_normal();
+        let saved = T::build_on_device(&dev);
+
+        // `numpy.savez(path, **state_dict)` stores each array as `{key}.npy`
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        saved
+            .0
+            .weight
+            .write_to_npz(&mut zip, "0.weight.npy".into())
+            .unwrap();
+        saved
+            .0
+            .bias
+            .write_to_npz(&mut zip, "0.bias.npy".into())
+            .unwrap();
+        saved
+            .2
+            .weight
+            .write_to_npz(&mut zip, "2.weight.npy".into())
+            .unwrap();
+        saved
+            .2
+            .bias
+            .write_to_npz(&mut zip, "2.bias.npy".into())
+            .unwrap();
+        let mut zip = ZipArchive::new(zip.finish().unwrap()).unwrap();
+
+        let mut loaded = T::build_on_device(&dev);
+        assert_ne!(
+            loaded.forward(x.clone()).array(),
+            saved.forward(x.clone()).array()
+        );
+        loaded.read_unprefixed(&mut zip).unwrap();
+        assert_eq!(loaded.forward(x.clone()).array(), saved.forward(x).array());
+    }
+
+    #[test]
+    fn test_load_missing_key() {
+        let dev: TestDevice = Default::default();
+        let saved = dev.build_module::<Linear<3, 3>, TestDtype>();
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        saved
+            .weight
+            .write_to_npz(&mut zip, "weight".into())
+            .unwrap();
+        let mut zip = ZipArchive::new(zip.finish().unwrap()).unwrap();
+
+        let mut loaded = dev.build_module::<Linear<3, 3>, TestDtype>();
+        match loaded.read_unprefixed(&mut zip) {
+            Err(NpzError::MissingKey(key)) => assert_eq!(key, "bias"),
+            r => panic!("expected a missing key, found {r:?}"),
+        }
+    }
+
+    #[test]
+    fn test_load_shape_mismatch() {
+        let dev: TestDevice = Default::default();
+        let file = NamedTempFile::new().expect("failed to create tempfile");
+        dev.build_module::<Linear<3, 5>, TestDtype>()
+            .save(file.path())
+            .unwrap();
+
+        let mut loaded = dev.build_module::<Linear<5, 3>, TestDtype>();
+        assert!(matches!(loaded.load(file.path()), Err(NpzError::Npy(_))));
+    }
+
+    #[test]
+    fn test_save_load_zero_sized() {
+        let dev: TestDevice = Default::default();
+        type T = (Linear<3, 3>, Upscale2D<4>, Linear<3, 3>);
+        let saved = dev.build_module::<T, TestDtype>();
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        saved.write_unprefixed(&mut zip).unwrap();
+        let mut zip = ZipArchive::new(zip.finish().unwrap()).unwrap();
+
+        let mut names: Vec<&str> = zip.file_names().collect();
+        names.sort();
+        assert_eq!(names, ["0.bias", "0.weight", "2.bias", "2.weight"]);
+
+        let mut loaded = dev.build_module::<T, TestDtype>();
+        loaded.read_unprefixed(&mut zip).unwrap();
+        assert_eq!(loaded.0.weight.array(), saved.0.weight.array());
+        assert_eq!(loaded.2.bias.array(), saved.2.bias.array());
+    }
 }
@@ -0,0 +1,120 @@
+use std::vec::Vec;
+
+use super::*;
+
+/// A contiguous group of blocks from a sequential model, made by [partition()].
+/// Each stage can be run on its own, with its output passed as the input of the next stage.
+///
+/// Like [super::modules::Repeated], this requires that `T`'s input is the same as its output.
+#[derive(Debug, Clone)]
+pub struct PipelineStage<T> {
+    pub modules: Vec<T>,
+}
+
+/// Splits `modules` into `num_stages` contiguous [PipelineStage]s, e.g. for pipeline parallelism.
+/// Running the stages in order is the same as running all of `modules` in order.
+///
+/// The stages are as even as possible, with earlier stages getting one extra module
+/// when `modules` doesn't divide evenly.
+///
+/// **Panics** if `num_stages` is 0, or there are fewer modules than stages.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, nn::partition};
+/// # let dev: Cpu = Default::default();
+/// type Model = Repeated<(Linear<3, 3>, ReLU), 5>;
+/// let model = dev.build_module::<Model, f32>();
+/// let stages = partition(model.modules, 2);
+/// assert_eq!(stages[0].modules.len(), 3);
+/// assert_eq!(stages[1].modules.len(), 2);
+///
+/// let x: Tensor<Rank1<3>, f32, _> = dev.sample_normal();
+/// let y = stages[1].forward(stages[0].forward(x));
+/// ```
+pub fn partition<T>(modules: Vec<T>, num_stages: usize) -> Vec<PipelineStage<T>> {
+    assert!(num_stages > 0, "Can't partition into 0 stages");
+    assert!(
+        modules.len() >= num_stages,
+        "Can't partition {} modules into {num_stages} stages",
+        modules.len()
+    );
+    let base = modules.len() / num_stages;
+    let extra = modules.len() % num_stages;
+    let mut modules = modules.into_iter();
+    (0..num_stages)
+        .map(|i| {
+            let size = base + usize::from(i < extra);
+            PipelineStage {
+                modules: modules.by_ref().take(size).collect(),
+            }
+        })
+        .collect()
+}
+
+impl<Input, T: Module<Input, Output = Input>> Module<Input> for PipelineStage<T> {
+    type Output = T::Output;
+    type Error = T::Error;
+
+    fn try_forward(&self, mut x: Input) -> Result<Self::Output, T::Error> {
+        for m in self.modules.iter() {
+            x = m.try_forward(x)?;
+        }
+        Ok(x)
+    }
+}
+
+impl<Input, T: ModuleMut<Input, Output = Input>> ModuleMut<Input> for PipelineStage<T> {
+    type Output = T::Output;
+    type Error = T::Error;
+
+    fn try_forward_mut(&mut self, mut x: Input) -> Result<Self::Output, T::Error> {
+        for m in self.modules.iter_mut() {
+            x = m.try_forward_mut(x)?;
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, tests::*};
+
+    #[test]
+    fn test_partition_matches_monolithic() {
+        let dev: TestDevice = Default::default();
+
+        type Model = Repeated<(Linear<4, 4>, Tanh), 4>;
+        let model = dev.build_module::<Model, TestDtype>();
+        let stages = partition(model.modules.clone(), 2);
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[0].modules.len(), 2);
+        assert_eq!(stages[1].modules.len(), 2);
+
+        let x: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let h = stages[0].forward(x.clone());
+        let y = stages[1].forward(h);
+        assert_close(&y.array(), &model.forward(x.clone()).array());
+
+        // gradients flow back through the activation passed between stages
+        let h = stages[0].forward(x.leaky_trace());
+        let g = stages[1].forward(h).sum().backward();
+        let expected = model.forward(x.leaky_trace()).sum().backward();
+        assert_close(&g.get(&x).array(), &expected.get(&x).array());
+    }
+
+    #[test]
+    fn test_partition_uneven() {
+        let sizes: Vec<usize> = partition(vec![0; 7], 3)
+            .iter()
+            .map(|s| s.modules.len())
+            .collect();
+        assert_eq!(sizes, [3, 2, 2]);
+    }
+
+    #[test]
+    #[should_panic = "Can't partition 2 modules into 3 stages"]
+    fn test_partition_too_many_stages() {
+        let _ = partition(vec![0; 2], 3);
+    }
+}
@@ -0,0 +1,125 @@
+use super::{ema::ModelEMA, tensor_collection::*};
+
+use crate::{shapes::*, tensor_ops::Device};
+
+/// Polyak (iterate) averaging: the element-wise mean of snapshots of a model, e.g. the
+/// checkpoints from the last few epochs of training.
+///
+/// Each call to [PolyakAverager::add()] updates the running average with [ModelEMA], so
+/// only the snapshots' trainable parameters are averaged. Everything else, like the
+/// running statistics of batch normalization, is kept from the first snapshot.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, nn::PolyakAverager};
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<2, 5>, f32>();
+/// let mut averager = PolyakAverager::default();
+/// for _ in 0..3 {
+///     // ... train `model` ...
+///     averager.add(&model);
+/// }
+/// assert_eq!(averager.len(), 3);
+/// let averaged = averager.into_average().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolyakAverager<M> {
+    average: Option<M>,
+    count: usize,
+}
+
+impl<M> Default for PolyakAverager<M> {
+    fn default() -> Self {
+        Self {
+            average: None,
+            count: 0,
+        }
+    }
+}
+
+impl<M> PolyakAverager<M> {
+    /// The number of snapshots that have been added.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Whether no snapshots have been added.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The average of the snapshots so far, or `None` if none have been added.
+    pub fn average(&self) -> Option<&M> {
+        self.average.as_ref()
+    }
+
+    /// Returns the average of all the snapshots, or `None` if none have been added.
+    pub fn into_average(self) -> Option<M> {
+        self.average
+    }
+}
+
+impl<M: Clone> PolyakAverager<M> {
+    /// Adds a snapshot of `model` to the average.
+    pub fn add<E: Dtype, D: Device<E>>(&mut self, model: &M)
+    where
+        M: TensorCollection<E, D>,
+    {
+        self.try_add(model).unwrap()
+    }
+
+    /// See [PolyakAverager::add()]
+    pub fn try_add<E: Dtype, D: Device<E>>(&mut self, model: &M) -> Result<(), D::Err>
+    where
+        M: TensorCollection<E, D>,
+    {
+        self.count += 1;
+        match self.average.as_mut() {
+            None => self.average = Some(model.clone()),
+            // avg_n = avg_{n-1} * (n - 1) / n + model / n
+            Some(average) => {
+                let n = E::from_usize(self.count).unwrap();
+                let decay = E::from_usize(self.count - 1).unwrap() / n;
+                average.try_ema(model, decay)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders::*, DeviceBuildExt},
+        tensor::*,
+        tests::*,
+    };
+
+    #[test]
+    fn test_polyak_average_of_three_linears() {
+        let dev: TestDevice = Default::default();
+        let snapshots = [
+            dev.build_module::<Linear<3, 2>, TestDtype>(),
+            dev.build_module::<Linear<3, 2>, TestDtype>(),
+            dev.build_module::<Linear<3, 2>, TestDtype>(),
+        ];
+
+        let mut averager = PolyakAverager::default();
+        assert!(averager.is_empty());
+        for s in snapshots.iter() {
+            averager.add(s);
+        }
+        assert_eq!(averager.len(), 3);
+        let average = averager.into_average().unwrap();
+
+        let weight = (snapshots[0].weight.clone()
+            + snapshots[1].weight.clone()
+            + snapshots[2].weight.clone())
+            / 3.0;
+        let bias =
+            (snapshots[0].bias.clone() + snapshots[1].bias.clone() + snapshots[2].bias.clone())
+                / 3.0;
+        assert_close(&average.weight.array(), &weight.array());
+        assert_close(&average.bias.array(), &bias.array());
+    }
+}
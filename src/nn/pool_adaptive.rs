@@ -0,0 +1,155 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{Module, NonMutableModule, ZeroSizedModule};
+
+/// Applies average pooling over windows chosen so that the output image is always
+/// `(OH, OW)`, regardless of the input height & width:
+/// - Reduces 3d (C, H, W) to 3d (C, OH, OW)
+/// - Reduces 4d (B, C, H, W) to 4d (B, C, OH, OW)
+///
+/// Output cell `i` along an axis of size `L` averages input cells `floor(i * L / O)`
+/// through `ceil((i + 1) * L / O)`, so windows may overlap when `L` is not a multiple of `O`.
+///
+/// **Pytorch equivalent**: `torch.nn.AdaptiveAvgPool2d((OH, OW))`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let m: AdaptiveAvgPool2D<2, 3> = Default::default();
+/// let _: Tensor<Rank3<5, 2, 3>, f32, _> = m.forward(dev.zeros::<Rank3<5, 16, 8>>());
+/// let _: Tensor<Rank4<10, 5, 2, 3>, f32, _> = m.forward(dev.zeros::<Rank4<10, 5, 7, 7>>());
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct AdaptiveAvgPool2D<const OH: usize, const OW: usize = OH>;
+
+impl<const OH: usize, const OW: usize> ZeroSizedModule for AdaptiveAvgPool2D<OH, OW> {}
+impl<const OH: usize, const OW: usize> NonMutableModule for AdaptiveAvgPool2D<OH, OW> {}
+
+/// Builds the `(inp, out)` matrix that averages each adaptive window of an axis.
+fn window_weights<E: Dtype>(inp: usize, out: usize) -> std::vec::Vec<E> {
+    let mut weights = vec![E::default(); inp * out];
+    for o in 0..out {
+        let start = (o * inp) / out;
+        let end = ((o + 1) * inp).div_ceil(out);
+        let w = E::ONE / E::from_usize(end - start).unwrap();
+        for i in start..end {
+            weights[i * out + o] = w;
+        }
+    }
+    weights
+}
+
+/// Pools the last two axes of `(N, H, W)` to `(N, OH, OW)` by multiplying with
+/// the window weights along each axis, so the gradient of every output cell is
+/// spread evenly over the input cells of its window.
+#[allow(clippy::type_complexity)]
+fn try_adaptive_avg_pool<
+    const OH: usize,
+    const OW: usize,
+    N: Dim,
+    H: Dim,
+    W: Dim,
+    E: Dtype,
+    D: Device<E>,
+    T: Tape<E, D>,
+>(
+    x: Tensor<(N, H, W), E, D, T>,
+) -> Result<Tensor<(N, Const<OH>, Const<OW>), E, D, T>, D::Err> {
+    let &(_, h, w) = x.shape();
+    let pool_w = x
+        .device
+        .try_tensor_from_vec(window_weights(w.size(), OW), (w, Const::<OW>))?;
+    let pool_h = x
+        .device
+        .try_tensor_from_vec(window_weights(h.size(), OH), (h, Const::<OH>))?;
+    let x = x.try_matmul(pool_w)?;
+    let x = x.try_permute::<_, Axes3<0, 2, 1>>()?;
+    let x = x.try_matmul(pool_h)?;
+    x.try_permute::<_, Axes3<0, 2, 1>>()
+}
+
+impl<
+        const OH: usize,
+        const OW: usize,
+        C: Dim,
+        H: Dim,
+        W: Dim,
+        E: Dtype,
+        D: Device<E>,
+        T: Tape<E, D>,
+    > Module<Tensor<(C, H, W), E, D, T>> for AdaptiveAvgPool2D<OH, OW>
+{
+    type Output = Tensor<(C, Const<OH>, Const<OW>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, input: Tensor<(C, H, W), E, D, T>) -> Result<Self::Output, D::Err> {
+        try_adaptive_avg_pool(input)
+    }
+}
+
+impl<
+        const OH: usize,
+        const OW: usize,
+        B: Dim,
+        C: Dim,
+        H: Dim,
+        W: Dim,
+        E: Dtype,
+        D: Device<E>,
+        T: Tape<E, D>,
+    > Module<Tensor<(B, C, H, W), E, D, T>> for AdaptiveAvgPool2D<OH, OW>
+{
+    type Output = Tensor<(B, C, Const<OH>, Const<OW>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, input: Tensor<(B, C, H, W), E, D, T>) -> Result<Self::Output, D::Err> {
+        let &(b, c, h, w) = input.shape();
+        let x = input
+            .try_reshape_like(&(b.size() * c.size(), h, w))
+            .unwrap()?;
+        let x = try_adaptive_avg_pool::<OH, OW, _, _, _, _, _, _>(x)?;
+        x.try_reshape_like(&(b, c, Const, Const)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_adaptive_avg_pool_quadrants() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<1, 1, 6, 6>, TestDtype, _> = dev.tensor([[[
+            [1.0, 1.0, 1.0, 2.0, 2.0, 2.0],
+            [1.0, 1.0, 1.0, 2.0, 2.0, 2.0],
+            [1.0, 1.0, 4.0, 2.0, 2.0, 2.0],
+            [3.0, 3.0, 3.0, 6.0, 4.0, 4.0],
+            [3.0, 3.0, 3.0, 4.0, 4.0, 4.0],
+            [3.0, 3.0, 3.0, 4.0, 4.0, 4.0],
+        ]]]);
+        let m: AdaptiveAvgPool2D<2> = Default::default();
+        let y = m.forward(x.leaky_trace());
+        assert_close(&y.array(), &[[[[4.0 / 3.0, 2.0], [3.0, 38.0 / 9.0]]]]);
+
+        let g = y.sum().backward();
+        assert_close(&g.get(&x).array(), &[[[[1.0 / 9.0; 6]; 6]]]);
+    }
+
+    #[test]
+    fn test_adaptive_avg_pool_overlapping() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<1, 1, 5>, TestDtype, _> = dev.tensor([[[1.0, 2.0, 3.0, 4.0, 5.0]]]);
+        let m: AdaptiveAvgPool2D<1, 3> = Default::default();
+        let y = m.forward(x.leaky_trace());
+        // windows are [0, 2), [1, 4), [3, 5)
+        assert_close(&y.array(), &[[[1.5, 3.0, 4.5]]]);
+
+        let g = y.sum().backward();
+        assert_close(
+            &g.get(&x).array(),
+            &[[[0.5, 0.5 + 1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0 + 0.5, 0.5]]],
+        );
+    }
+}
@@ -25,12 +25,14 @@ pub struct Repeated<T, const N: usize> {
 
 impl<D: Device<E>, E: Dtype, T: BuildOnDevice<D, E>, const N: usize> BuildOnDevice<D, E>
     for Repeated<T, N>
+where
+    T::Built: 'static,
 {
     type Built = Repeated<T::Built, N>;
 }
 
-impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D>, const N: usize> TensorCollection<E, D>
-    for Repeated<T, N>
+impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D> + 'static, const N: usize>
+    TensorCollection<E, D> for Repeated<T, N>
 {
     type To<E2: Dtype, D2: Device<E2>> = Repeated<T::To<E2, D2>, N>;
 
@@ -25,11 +25,16 @@ use super::*;
 #[derive(Debug, Clone, Default)]
 pub struct Residual<F>(pub F);
 
-impl<D: Device<E>, E: Dtype, F: BuildOnDevice<D, E>> BuildOnDevice<D, E> for Residual<F> {
+impl<D: Device<E>, E: Dtype, F: BuildOnDevice<D, E>> BuildOnDevice<D, E> for Residual<F>
+where
+    F::Built: 'static,
+{
     type Built = Residual<F::Built>;
 }
 
-impl<E: Dtype, D: Device<E>, F: TensorCollection<E, D>> TensorCollection<E, D> for Residual<F> {
+impl<E: Dtype, D: Device<E>, F: TensorCollection<E, D> + 'static> TensorCollection<E, D>
+    for Residual<F>
+{
     type To<E2: Dtype, D2: Device<E2>> = Residual<F::To<E2, D2>>;
 
     fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
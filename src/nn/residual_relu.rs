@@ -0,0 +1,103 @@
+use crate::{shapes::*, tensor::*, tensor_ops::Device};
+
+use super::*;
+
+/// A residual connection around `F` followed by a [crate::nn::modules::ReLU]: `relu(F(x) + x)`.
+///
+/// This computes the same thing as `(Residual<F>, ReLU)`, but uses the fused
+/// [crate::tensor_ops::add_relu()], so the sum `F(x) + x` is never allocated.
+///
+/// # Generics
+/// - `F`: The underlying module to do a skip connection around.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = ResidualReLU<ReLU>;
+/// let model = dev.build_module::<Model, f32>();
+/// let x = dev.tensor([-2.0, -1.0, 0.0, 1.0, 2.0]);
+/// let y = model.forward(x);
+/// assert_eq!(y.array(), [0.0, 0.0, 0.0, 2.0, 4.0]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ResidualReLU<F>(pub F);
+
+impl<D: Device<E>, E: Dtype, F: BuildOnDevice<D, E>> BuildOnDevice<D, E> for ResidualReLU<F>
+where
+    F::Built: 'static,
+{
+    type Built = ResidualReLU<F::Built>;
+}
+
+impl<E: Dtype, D: Device<E>, F: TensorCollection<E, D> + 'static> TensorCollection<E, D>
+    for ResidualReLU<F>
+{
+    type To<E2: Dtype, D2: Device<E2>> = ResidualReLU<F::To<E2, D2>>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(Self::module("0", |s| &s.0, |s| &mut s.0), ResidualReLU)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>, F> Module<Tensor<S, E, D, T>>
+    for ResidualReLU<F>
+where
+    F: Module<Tensor<S, E, D, T>, Output = Tensor<S, E, D, T>, Error = D::Err>,
+{
+    type Output = Tensor<S, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<S, E, D, T>) -> Result<Self::Output, D::Err> {
+        self.0.try_forward(x.with_empty_tape())?.try_add_relu(x)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>, F> ModuleMut<Tensor<S, E, D, T>>
+    for ResidualReLU<F>
+where
+    F: ModuleMut<Tensor<S, E, D, T>, Output = Tensor<S, E, D, T>, Error = D::Err>,
+{
+    type Output = Tensor<S, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward_mut(&mut self, x: Tensor<S, E, D, T>) -> Result<Self::Output, D::Err> {
+        self.0.try_forward_mut(x.with_empty_tape())?.try_add_relu(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use crate::{nn::builders::*, tensor_ops::*};
+
+    #[test]
+    fn test_residual_relu_matches_residual_then_relu() {
+        let dev: TestDevice = Default::default();
+        let fused = dev.build_module::<ResidualReLU<Linear<2, 2>>, TestDtype>();
+        let unfused = (Residual(fused.0.clone()), modules::ReLU);
+
+        let x: Tensor<Rank2<4, 2>, TestDtype, _> = dev.sample_normal();
+        let y1 = fused.forward(x.leaky_trace());
+        let y2 = unfused.forward(x.leaky_trace());
+        assert_close(&y1.array(), &y2.array());
+
+        // only the add_relu is recorded on top of the linear layer
+        assert_eq!(y1.tape.operations.len() + 1, y2.tape.operations.len());
+
+        let g1 = y1.exp().mean().backward();
+        let g2 = y2.exp().mean().backward();
+        assert_close(&g1.get(&x).array(), &g2.get(&x).array());
+        assert_close(
+            &g1.get(&fused.0.weight).array(),
+            &g2.get(&unfused.0 .0.weight).array(),
+        );
+        assert_close(
+            &g1.get(&fused.0.bias).array(),
+            &g2.get(&unfused.0 .0.bias).array(),
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+use num_traits::FromPrimitive;
+
+use super::*;
+
+pub mod builder {
+    #[derive(Debug)]
+    pub struct RMSNorm<const M: usize>;
+}
+impl<const M: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E> for builder::RMSNorm<M>
+where
+    RMSNorm<M, E, D>: BuildModule<D, E>,
+{
+    type Built = RMSNorm<M, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, D::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// Implements root mean square layer normalization as described in
+/// [Root Mean Square Layer Normalization](https://arxiv.org/abs/1910.07467), as used in LLaMA.
+///
+/// This calls [rmsnorm()] on the last axis of the input, and then multiplies element-wise by the
+/// learnable [Self::scale]. Unlike [LayerNorm1D] the mean isn't subtracted, and there is no bias.
+///
+/// [Self::epsilon] is passed to [rmsnorm()] and added to the mean square. It defaults to `1e-6`.
+///
+/// # Generics
+/// - `M` The size of the scale tensor.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = RMSNorm<5>;
+/// let model = dev.build_module::<Model, f32>();
+/// let _: Tensor<Rank1<5>, f32, _> = model.forward(dev.zeros::<Rank1<5>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RMSNorm<const M: usize, E: Dtype, D: DeviceStorage> {
+    pub scale: Tensor<Rank1<M>, E, D>,
+    pub epsilon: E,
+}
+
+impl<const M: usize, E: Dtype, D: DeviceStorage> NonMutableModule for RMSNorm<M, E, D> {}
+
+impl<const M: usize, E: Dtype, D: Device<E>> TensorCollection<E, D> for RMSNorm<M, E, D> {
+    type To<E2: Dtype, D2: Device<E2>> = RMSNorm<M, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            Self::tensor(
+                "scale",
+                |s| &s.scale,
+                |s| &mut s.scale,
+                TensorOptions::reset_to_ones(),
+            ),
+            |scale| RMSNorm {
+                scale,
+                epsilon: V::E2::from_f32(1e-6).unwrap(),
+            },
+        )
+    }
+}
+
+impl<const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D>> Module<Tensor<Rank1<M>, E, D, T>>
+    for RMSNorm<M, E, D>
+{
+    type Output = Tensor<Rank1<M>, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<Rank1<M>, E, D, T>) -> Result<Self::Output, D::Err> {
+        x.try_rmsnorm(self.epsilon)?.try_mul(self.scale.clone())
+    }
+}
+
+impl<B: Dim, const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<(B, Const<M>), E, D, T>> for RMSNorm<M, E, D>
+{
+    type Output = Tensor<(B, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(B, Const<M>), E, D, T>) -> Result<Self::Output, D::Err> {
+        let shape = *x.shape();
+        x.try_rmsnorm::<Axis<1>>(self.epsilon)?
+            .try_mul(self.scale.retaped::<T>().try_broadcast_like(&shape)?)
+    }
+}
+
+impl<B: Dim, S: Dim, const M: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<(B, S, Const<M>), E, D, T>> for RMSNorm<M, E, D>
+{
+    type Output = Tensor<(B, S, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(B, S, Const<M>), E, D, T>) -> Result<Self::Output, D::Err> {
+        let shape = *x.shape();
+        x.try_rmsnorm::<Axis<2>>(self.epsilon)?
+            .try_mul(self.scale.retaped::<T>().try_broadcast_like(&shape)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_rms_norm_2d_forward() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::RMSNorm<3>, TestDtype>();
+        assert_eq!(m.scale.array(), [1.0; 3]);
+        m.scale = dev.tensor([1.0, 2.0, -0.5]);
+
+        let x: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor([[1.0, 2.0, 2.0], [-3.0, 0.0, 3.0]]);
+        let r = m.forward(x.leaky_trace());
+        // rms of each row is sqrt(3) and sqrt(6)
+        let rms: [TestDtype; 2] = [
+            (3.0f64 + 1e-6).sqrt() as TestDtype,
+            (6.0f64 + 1e-6).sqrt() as TestDtype,
+        ];
+        assert_close(
+            &r.array(),
+            &[
+                [1.0 / rms[0], 4.0 / rms[0], -1.0 / rms[0]],
+                [-3.0 / rms[1], 0.0, -1.5 / rms[1]],
+            ],
+        );
+
+        // the gradient of the scale is the normalized input, summed over the batch
+        let g = r.sum().backward();
+        assert_close(
+            &g.get(&m.scale).array(),
+            &[
+                1.0 / rms[0] - 3.0 / rms[1],
+                2.0 / rms[0],
+                2.0 / rms[0] + 3.0 / rms[1],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_rms_norm_3d_matches_2d() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::RMSNorm<4>, TestDtype>();
+        m.scale = dev.sample_normal();
+        let x: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let r = m.forward(x.clone());
+        let r2 = m.forward(x.reshape::<Rank2<6, 4>>());
+        assert_close(&r.reshape::<Rank2<6, 4>>().array(), &r2.array());
+    }
+}
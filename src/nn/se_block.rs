@@ -0,0 +1,159 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{linear::Linear, *};
+
+use num_traits::Float;
+use rand_distr::uniform::SampleUniform;
+
+pub mod builder {
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct SEBlock<const C: usize, const HIDDEN: usize>;
+}
+
+impl<const C: usize, const HIDDEN: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::SEBlock<C, HIDDEN>
+where
+    SEBlock<C, HIDDEN, E, D>: BuildModule<D, E>,
+{
+    type Built = SEBlock<C, HIDDEN, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// A squeeze-and-excitation block from [Squeeze-and-Excitation Networks](https://arxiv.org/abs/1709.01507),
+/// which rescales each channel of an image by a learned gate.
+///
+/// Computes `x * sigmoid(fc2(relu(fc1(mean(x)))))`, where the mean is over the height & width
+/// of each channel, and the gate is broadcast back over them.
+///
+/// # Generics
+/// - `C` The number of channels of the input & output.
+/// - `HIDDEN` The size of the bottleneck, usually `C / 16`.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = SEBlock<8, 2>;
+/// let model = dev.build_module::<Model, f32>();
+/// let _: Tensor<Rank3<8, 5, 5>, f32, _> = model.forward(dev.zeros::<Rank3<8, 5, 5>>());
+/// let _: Tensor<Rank4<3, 8, 5, 5>, f32, _> = model.forward(dev.zeros::<Rank4<3, 8, 5, 5>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SEBlock<const C: usize, const HIDDEN: usize, E: Dtype, D: DeviceStorage> {
+    /// The squeeze into the bottleneck.
+    pub fc1: Linear<C, HIDDEN, E, D>,
+    /// The excitation back to a gate per channel.
+    pub fc2: Linear<HIDDEN, C, E, D>,
+}
+
+impl<const C: usize, const HIDDEN: usize, E: Dtype, D: DeviceStorage> NonMutableModule
+    for SEBlock<C, HIDDEN, E, D>
+{
+}
+
+impl<const C: usize, const HIDDEN: usize, E: Dtype + Float + SampleUniform, D: Device<E>>
+    TensorCollection<E, D> for SEBlock<C, HIDDEN, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = SEBlock<C, HIDDEN, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::module("fc1", |s| &s.fc1, |s| &mut s.fc1),
+                Self::module("fc2", |s| &s.fc2, |s| &mut s.fc2),
+            ),
+            |(fc1, fc2)| SEBlock { fc1, fc2 },
+        )
+    }
+}
+
+impl<
+        B: Dim,
+        const C: usize,
+        const HIDDEN: usize,
+        H: Dim,
+        W: Dim,
+        E: Dtype,
+        D: Device<E>,
+        T: Tape<E, D> + Merge<T>,
+    > Module<Tensor<(B, Const<C>, H, W), E, D, T>> for SEBlock<C, HIDDEN, E, D>
+{
+    type Output = Tensor<(B, Const<C>, H, W), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(B, Const<C>, H, W), E, D, T>) -> Result<Self::Output, D::Err> {
+        let shape = *x.shape();
+        let squeezed = x.retaped::<T>().try_mean::<(B, Const<C>), _>()?;
+        let hidden = self.fc1.try_forward(squeezed)?.try_relu()?;
+        let gate = self.fc2.try_forward(hidden)?.try_sigmoid()?;
+        x.try_mul(gate.try_broadcast_like(&shape)?)
+    }
+}
+
+impl<
+        const C: usize,
+        const HIDDEN: usize,
+        H: Dim,
+        W: Dim,
+        E: Dtype,
+        D: Device<E>,
+        T: Tape<E, D> + Merge<T>,
+    > Module<Tensor<(Const<C>, H, W), E, D, T>> for SEBlock<C, HIDDEN, E, D>
+{
+    type Output = Tensor<(Const<C>, H, W), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(Const<C>, H, W), E, D, T>) -> Result<Self::Output, D::Err> {
+        let shape = *x.shape();
+        let squeezed = x.retaped::<T>().try_mean::<Rank1<C>, _>()?;
+        let hidden = self.fc1.try_forward(squeezed)?.try_relu()?;
+        let gate = self.fc2.try_forward(hidden)?.try_sigmoid()?;
+        x.try_mul(gate.try_broadcast_like(&shape)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_se_block_scales_channels_by_gate() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builder::SEBlock<3, 2>, TestDtype>();
+        let x: Tensor<Rank4<2, 3, 2, 4>, TestDtype, _> = dev.sample_normal();
+        let y = model.forward(x.leaky_trace());
+
+        let x_arr = x.array();
+        let y_arr = y.array();
+        for b in 0..2 {
+            let squeezed = x_arr[b].map(|c| c.iter().flatten().sum::<TestDtype>() / 8.0);
+            let hidden = model.fc1.forward(dev.tensor(squeezed)).relu();
+            let gate = model.fc2.forward(hidden).sigmoid().array();
+            for c in 0..3 {
+                let expected = x_arr[b][c].map(|row| row.map(|v| v * gate[c]));
+                assert_close(&y_arr[b][c], &expected);
+            }
+        }
+
+        let g = y.square().mean().backward();
+        let any_nonzero = |v: std::vec::Vec<TestDtype>| v.iter().any(|v| *v != 0.0);
+        assert!(any_nonzero(g.get(&model.fc1.weight).as_vec()));
+        assert!(any_nonzero(g.get(&model.fc2.weight).as_vec()));
+        assert!(any_nonzero(g.get(&model.fc2.bias).as_vec()));
+        assert!(any_nonzero(g.get(&x).as_vec()));
+    }
+
+    #[test]
+    fn test_se_block_unbatched() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builder::SEBlock<4, 2>, TestDtype>();
+        let x: Tensor<Rank3<4, 3, 3>, TestDtype, _> = dev.sample_normal();
+        let batched = model.forward(x.clone().broadcast::<Rank4<1, 4, 3, 3>, _>());
+        assert_close(&model.forward(x).array(), &batched.array()[0]);
+    }
+}
@@ -0,0 +1,141 @@
+use super::tensor_collection::*;
+
+use crate::{shapes::*, tensor::*, tensor_ops::Device};
+
+struct TrainingWalker<'a, M> {
+    m: &'a mut M,
+    training: bool,
+}
+
+impl<'a, T, E, D> ModuleVisitor<T, E, D> for TrainingWalker<'a, T>
+where
+    T: TensorCollection<E, D>,
+    E: Dtype,
+    D: Device<E>,
+{
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit_module<Field, GetRef, GetMut>(
+        &mut self,
+        _name: &str,
+        _get_refs: GetRef,
+        mut get_muts: GetMut,
+    ) -> Result<Option<Field::To<E, D>>, Self::Err>
+    where
+        GetRef: FnMut(&T) -> &Field,
+        GetMut: FnMut(&mut T) -> &mut Field,
+        Field: TensorCollection<E, D> + 'static,
+    {
+        let m = get_muts(self.m);
+        TensorCollection::<E, D>::set_training_mode(m, self.training);
+        Field::iter_tensors(&mut TrainingWalker {
+            m,
+            training: self.training,
+        })?;
+        Ok(None)
+    }
+
+    fn visit_tensor<S: Shape, GetRef, GetMut>(
+        &mut self,
+        _name: &str,
+        _get_refs: GetRef,
+        _get_muts: GetMut,
+        _opts: TensorOptions<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err>
+    where
+        GetRef: FnMut(&T) -> &Tensor<S, E, D>,
+        GetMut: FnMut(&mut T) -> &mut Tensor<S, E, D>,
+    {
+        Ok(None)
+    }
+
+    fn visit_fields<M: ModuleFields<T, E, D>>(
+        &mut self,
+        fields: M,
+        _builder: impl FnOnce(M::Output<E, D>) -> T::To<E, D>,
+    ) -> Result<Option<T::To<E, D>>, Self::Err> {
+        fields.visit_fields(self)?;
+        Ok(None)
+    }
+}
+
+/// Recursively switches a model and all of its sub-modules between training and
+/// evaluation mode. Models are in training mode when they are built.
+///
+/// Training mode only affects [super::ModuleMut::forward_mut]; [super::Module::forward]
+/// always runs in evaluation mode. In evaluation mode, `forward_mut` still records
+/// gradients, but:
+/// - [super::modules::BatchNorm1D] and [super::modules::BatchNorm2D] normalize with
+///   the running statistics, and do not update them
+/// - [super::modules::StochasticDepth] always applies its module
+/// - [super::modules::SpectralNorm] does not run power iteration
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// use dfdx::nn::SetTraining;
+/// type Model = (Linear<2, 5>, BatchNorm1D<5>);
+/// let mut model = dev.build_module::<Model, f32>();
+/// model.set_training(false);
+/// assert!(!model.1.training);
+/// ```
+pub trait SetTraining<E: Dtype, D: Device<E>>: TensorCollection<E, D> {
+    /// Sets training mode of `self` and every sub-module to `training`.
+    fn set_training(&mut self, training: bool) {
+        self.try_set_training(training).unwrap()
+    }
+
+    /// Fallible version of [SetTraining::set_training].
+    fn try_set_training(&mut self, training: bool) -> Result<(), D::Err> {
+        self.set_training_mode(training);
+        Self::iter_tensors(&mut TrainingWalker { m: self, training })?;
+        Ok(())
+    }
+}
+impl<E: Dtype, D: Device<E>, M: TensorCollection<E, D>> SetTraining<E, D> for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_set_training_nested() {
+        let dev: TestDevice = Default::default();
+        type Model = (Linear<3, 3>, Residual<BatchNorm1D<3>>);
+        let mut model = dev.build_module::<Model, TestDtype>();
+        assert!(model.1 .0.training);
+
+        model.set_training(false);
+        assert!(!model.1 .0.training);
+
+        // the mode is kept when moving the model
+        let mut model = model.to_device(&dev);
+        assert!(!model.1 .0.training);
+
+        model.set_training(true);
+        assert!(model.1 .0.training);
+    }
+
+    #[test]
+    fn test_set_training_batchnorm_eval_uses_running_stats() {
+        let dev: TestDevice = Default::default();
+        type Model = (Linear<2, 2>, BatchNorm1D<2>);
+        let mut model = dev.build_module::<Model, TestDtype>();
+        model.set_training(false);
+
+        let x: Tensor<Rank2<3, 2>, TestDtype, _> =
+            dev.tensor([[1.0, 2.0], [3.0, -4.0], [5.0, 0.0]]);
+        let y = model.1.forward_mut(x.leaky_trace());
+        // running mean is 0 and running var is 1
+        let std = (1.0 + 1e-5 as TestDtype).sqrt();
+        assert_close(&y.array(), &x.array().map(|r| r.map(|v| v / std)));
+        assert_eq!(model.1.running_mean.array(), [0.0; 2]);
+        assert_eq!(model.1.running_var.array(), [1.0; 2]);
+
+        let g = y.sum().backward();
+        assert_close(&g.get(&model.1.bias).array(), &[3.0; 2]);
+    }
+}
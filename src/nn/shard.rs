@@ -0,0 +1,125 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::modules::Linear;
+
+/// Slices out the `shard_index`th of `num_shards` equal parts of the outputs of `linear`,
+/// e.g. for tensor (model) parallelism. The shard computes outputs
+/// `shard_index * M..(shard_index + 1) * M` of the full layer, and [all_gather()] puts the
+/// outputs of all the shards back together.
+///
+/// **Panics** if `M * num_shards != O`, or if `shard_index >= num_shards`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, nn::{all_gather, modules, shard_output}};
+/// # let dev: Cpu = Default::default();
+/// let linear = Linear::<4, 6>::build_on_device(&dev);
+/// let a: modules::Linear<4, 3, f32, Cpu> = shard_output(&linear, 2, 0);
+/// let b: modules::Linear<4, 3, f32, Cpu> = shard_output(&linear, 2, 1);
+///
+/// let x: Tensor<Rank2<5, 4>, f32, _> = dev.sample_normal();
+/// let y = all_gather(vec![a.forward(x.clone()), b.forward(x)]);
+/// assert_eq!(y.shape(), &(Const::<5>, 6));
+/// ```
+pub fn shard_output<const I: usize, const O: usize, const M: usize, E: Dtype, D: Device<E>>(
+    linear: &Linear<I, O, E, D>,
+    num_shards: usize,
+    shard_index: usize,
+) -> Linear<I, M, E, D> {
+    try_shard_output(linear, num_shards, shard_index).unwrap()
+}
+
+/// See [shard_output()]
+pub fn try_shard_output<const I: usize, const O: usize, const M: usize, E: Dtype, D: Device<E>>(
+    linear: &Linear<I, O, E, D>,
+    num_shards: usize,
+    shard_index: usize,
+) -> Result<Linear<I, M, E, D>, D::Err> {
+    assert_eq!(
+        M * num_shards,
+        O,
+        "{O} outputs can't be split into {num_shards} shards of size {M}"
+    );
+    assert!(shard_index < num_shards);
+    let rows = shard_index * M..(shard_index + 1) * M;
+    let weight = linear.weight.clone().try_slice((rows.clone(), ..))?;
+    let bias = linear.bias.clone().try_slice((rows,))?;
+    Ok(Linear {
+        weight: weight.realize().unwrap(),
+        bias: bias.realize().unwrap(),
+    })
+}
+
+/// Concatenates the outputs of each shard along the last dimension, in order. This is the
+/// inverse of splitting a layer's outputs with [shard_output()].
+///
+/// **Panics** if `shards` is empty, or if the shards have different batch sizes.
+pub fn all_gather<B: Dim, M: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    shards: std::vec::Vec<Tensor<(B, M), E, D, T>>,
+) -> Tensor<(B, usize), E, D, T> {
+    try_all_gather(shards).unwrap()
+}
+
+/// See [all_gather()]
+#[allow(clippy::type_complexity)]
+pub fn try_all_gather<B: Dim, M: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    shards: std::vec::Vec<Tensor<(B, M), E, D, T>>,
+) -> Result<Tensor<(B, usize), E, D, T>, D::Err> {
+    assert!(!shards.is_empty(), "all_gather needs at least one shard");
+    let shape = shards[0].shape;
+    let width = shards.len() * shape.1.size();
+    // stacking needs every shard to have the same strides
+    let shards = shards
+        .into_iter()
+        .map(|s| s.try_reshape_like(&shape).unwrap())
+        .collect::<Result<std::vec::Vec<_>, _>>()?;
+    shards
+        .try_stack()?
+        .try_permute::<_, Axes3<1, 0, 2>>()?
+        .try_reshape_like(&(shape.0, width))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::Linear, nn::*, tests::*};
+
+    #[test]
+    fn test_shard_linear_matches_full() {
+        let dev: TestDevice = Default::default();
+        let linear = dev.build_module::<Linear<4, 6>, TestDtype>();
+        let a: modules::Linear<4, 3, TestDtype, _> = shard_output(&linear, 2, 0);
+        let b: modules::Linear<4, 3, TestDtype, _> = shard_output(&linear, 2, 1);
+
+        let x: Tensor<Rank2<5, 4>, TestDtype, _> = dev.sample_normal();
+        let y = all_gather(vec![a.forward(x.clone()), b.forward(x.clone())]);
+        let expected = linear.forward(x);
+        assert_close(
+            &y.realize::<Rank2<5, 6>>().unwrap().array(),
+            &expected.array(),
+        );
+    }
+
+    #[test]
+    fn test_all_gather_gradients() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+        let b: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([5.0, 6.0]);
+        let b: Tensor<Rank2<2, 2>, TestDtype, _> = b.broadcast::<_, Axis<1>>();
+        let y = all_gather(vec![a.leaky_trace(), b.leaky_trace()]);
+        assert_eq!(y.as_vec(), [1.0, 2.0, 5.0, 5.0, 3.0, 4.0, 6.0, 6.0]);
+
+        let w: Tensor<(Const<2>, usize), TestDtype, _> =
+            dev.tensor_from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], (Const, 4));
+        let g = (y * w).sum().backward();
+        assert_eq!(g.get(&a).array(), [[1.0, 2.0], [5.0, 6.0]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shard_output_uneven() {
+        let dev: TestDevice = Default::default();
+        let linear = dev.build_module::<Linear<4, 6>, TestDtype>();
+        let _: modules::Linear<4, 2, TestDtype, _> = shard_output(&linear, 2, 0);
+    }
+}
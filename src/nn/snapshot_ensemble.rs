@@ -0,0 +1,113 @@
+use super::Module;
+
+use crate::{
+    shapes::*,
+    tensor::{Tape, Tensor},
+    tensor_ops::*,
+};
+
+/// An ensemble of snapshots of the same model, e.g. taken at the end of each cycle of a
+/// cyclic learning rate schedule, as in [Snapshot Ensembles](https://arxiv.org/abs/1704.00109).
+///
+/// [SnapshotEnsemble::predict_mean()] averages the outputs of all the snapshots.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, nn::SnapshotEnsemble};
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<Linear<2, 5>, f32>();
+/// let mut ensemble = SnapshotEnsemble::default();
+/// for _ in 0..3 {
+///     // ... train `model` for a cycle ...
+///     ensemble.push(&model);
+/// }
+/// let x: Tensor<Rank1<2>, f32, _> = dev.sample_normal();
+/// let y = ensemble.predict_mean(x);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnapshotEnsemble<M> {
+    pub snapshots: std::vec::Vec<M>,
+}
+
+impl<M> Default for SnapshotEnsemble<M> {
+    fn default() -> Self {
+        Self {
+            snapshots: std::vec::Vec::new(),
+        }
+    }
+}
+
+impl<M> SnapshotEnsemble<M> {
+    /// The number of snapshots in the ensemble.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Whether the ensemble has no snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Adds a clone of the current state of `model` to the ensemble.
+    pub fn push(&mut self, model: &M)
+    where
+        M: Clone,
+    {
+        self.snapshots.push(model.clone());
+    }
+
+    /// Forwards `input` through every snapshot, and returns the mean of their outputs.
+    ///
+    /// **Panics** if there are no snapshots.
+    pub fn predict_mean<Input: Clone, S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+        &self,
+        input: Input,
+    ) -> Tensor<S, E, D, T>
+    where
+        M: Module<Input, Output = Tensor<S, E, D, T>, Error = D::Err>,
+    {
+        self.try_predict_mean(input).unwrap()
+    }
+
+    /// See [SnapshotEnsemble::predict_mean()]
+    pub fn try_predict_mean<Input: Clone, S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+        &self,
+        input: Input,
+    ) -> Result<Tensor<S, E, D, T>, D::Err>
+    where
+        M: Module<Input, Output = Tensor<S, E, D, T>, Error = D::Err>,
+    {
+        assert!(!self.is_empty(), "predict_mean needs at least one snapshot");
+        let mut snapshots = self.snapshots.iter();
+        let mut sum = snapshots.next().unwrap().try_forward(input.clone())?;
+        for m in snapshots {
+            sum = sum.try_add(m.try_forward(input.clone())?)?;
+        }
+        sum.try_div(E::from_usize(self.len()).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders::*, DeviceBuildExt},
+        tensor::*,
+        tests::*,
+    };
+
+    #[test]
+    fn test_snapshot_ensemble_mean_of_forwards() {
+        let dev: TestDevice = Default::default();
+        let a = dev.build_module::<Linear<3, 2>, TestDtype>();
+        let b = dev.build_module::<Linear<3, 2>, TestDtype>();
+        let mut ensemble = SnapshotEnsemble::default();
+        ensemble.push(&a);
+        ensemble.push(&b);
+        assert_eq!(ensemble.len(), 2);
+
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let y = ensemble.predict_mean(x.clone());
+        let expected = (a.forward(x.clone()) + b.forward(x)) / 2.0;
+        assert_close(&y.array(), &expected.array());
+    }
+}
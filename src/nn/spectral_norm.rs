@@ -0,0 +1,336 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{linear::Linear, *};
+
+use num_traits::{Float, FromPrimitive};
+use rand_distr::Uniform;
+
+pub mod builder {
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct SpectralNorm<const I: usize, const O: usize>;
+}
+
+impl<const I: usize, const O: usize, E: Dtype + Float, D: Device<E>> BuildOnDevice<D, E>
+    for builder::SpectralNorm<I, O>
+where
+    SpectralNorm<I, O, E, D>: BuildModule<D, E>,
+{
+    type Built = SpectralNorm<I, O, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// A [super::modules::Linear] whose weight is divided by its spectral norm (largest singular value),
+/// as introduced in [Spectral Normalization for Generative Adversarial Networks](https://arxiv.org/abs/1802.05957).
+///
+/// The spectral norm is estimated as `u^T * weight * v`, where [Self::u] and [Self::v] are estimates of the
+/// first left and right singular vectors of [Self::weight].
+///
+/// # Training vs Inference
+///
+/// - [ModuleMut]: runs one step of power iteration to update [Self::u] and [Self::v] if
+///   [Self::training] is true, then runs the linear layer with the normalized weight.
+///   Gradients flow through the normalization.
+/// - [Module]: uses the current [Self::u] and [Self::v] without updating them.
+///
+/// # Generics
+/// - `I` The "input" size of vectors & matrices.
+/// - `O` The "output" size of vectors & matrices.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = SpectralNorm<5, 2>;
+/// let mut model = dev.build_module::<Model, f32>();
+/// let _: Tensor<Rank1<2>, f32, _> = model.forward(dev.zeros::<Rank1<5>>());
+/// let _: Tensor<Rank2<10, 2>, f32, _, _> = model.forward_mut(dev.zeros::<Rank2<10, 5>>().leaky_trace());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpectralNorm<const I: usize, const O: usize, E: Dtype, D: DeviceStorage> {
+    /// Un-normalized weight matrix, shape (O, I)
+    pub weight: Tensor<Rank2<O, I>, E, D>,
+
+    /// Bias vector, shape (O, )
+    pub bias: Tensor<Rank1<O>, E, D>,
+
+    /// Estimate of the first left singular vector of [Self::weight], shape (O, )
+    pub u: Tensor<Rank1<O>, E, D>,
+
+    /// Estimate of the first right singular vector of [Self::weight], shape (I, )
+    pub v: Tensor<Rank1<I>, E, D>,
+
+    /// Added to the norm when normalizing [Self::u] and [Self::v]. Defaults to `1e-12`
+    pub epsilon: E,
+
+    /// Whether [ModuleMut] runs power iteration. Defaults to `true`. See [SetTraining].
+    pub training: bool,
+}
+
+impl<const I: usize, const O: usize, E: Dtype + Float, D: Device<E>> SpectralNorm<I, O, E, D> {
+    /// Runs a single step of power iteration, updating [Self::u] and [Self::v].
+    pub fn power_iteration(&mut self) {
+        self.try_power_iteration().unwrap()
+    }
+
+    /// Fallible version of [SpectralNorm::power_iteration]
+    pub fn try_power_iteration(&mut self) -> Result<(), D::Err> {
+        // NOTE: updating in place keeps the ids of u & v the same
+        let v = self.u.clone().try_matmul(self.weight.clone())?;
+        let v = self.try_l2_normalize(v)?;
+        self.v.try_axpy(E::zero(), &v, E::ONE)?;
+        let u = self
+            .v
+            .clone()
+            .try_matmul(self.weight.clone().try_permute()?)?;
+        let u = self.try_l2_normalize(u)?;
+        self.u.try_axpy(E::zero(), &u, E::ONE)?;
+        Ok(())
+    }
+
+    /// The current estimate of the spectral norm of [Self::weight]: `u^T * weight * v`.
+    pub fn sigma(&self) -> Tensor<Rank0, E, D> {
+        self.try_sigma(NoneTape).unwrap()
+    }
+
+    /// [Self::weight] divided by [SpectralNorm::sigma].
+    pub fn normalized_weight(&self) -> Tensor<Rank2<O, I>, E, D> {
+        self.try_normalized_weight(NoneTape).unwrap()
+    }
+
+    fn try_l2_normalize<const N: usize>(
+        &self,
+        t: Tensor<Rank1<N>, E, D>,
+    ) -> Result<Tensor<Rank1<N>, E, D>, D::Err> {
+        let norm = t.clone().try_frobenius_norm()?.try_add(self.epsilon)?;
+        t.try_div(norm.try_broadcast()?)
+    }
+
+    fn try_sigma<T: Tape<E, D>>(&self, tape: T) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+        let u = self.u.clone().try_broadcast::<Rank2<O, I>, _>()?;
+        let v = self.v.clone().try_broadcast::<Rank2<O, I>, _>()?;
+        self.weight
+            .clone()
+            .put_tape(tape)
+            .try_mul(u.try_mul(v)?)?
+            .try_sum()
+    }
+
+    fn try_normalized_weight<T: Tape<E, D>>(
+        &self,
+        tape: T,
+    ) -> Result<Tensor<Rank2<O, I>, E, D, T>, D::Err> {
+        let sigma = self.try_sigma(tape)?;
+        let (sigma, tape) = sigma.split_tape();
+        self.weight
+            .clone()
+            .put_tape(tape)
+            .try_div(sigma.try_broadcast()?)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn try_fwd<S: Shape, T: Tape<E, D>>(
+        &self,
+        x: Tensor<S, E, D, T>,
+    ) -> Result<<Linear<I, O, E, D> as Module<Tensor<S, E, D, T>>>::Output, D::Err>
+    where
+        Linear<I, O, E, D>: Module<Tensor<S, E, D, T>, Error = D::Err>,
+    {
+        let (x, tape) = x.split_tape();
+        let (weight, tape) = self.try_normalized_weight(tape)?.split_tape();
+        let linear = Linear {
+            weight,
+            bias: self.bias.clone(),
+        };
+        linear.try_forward(x.put_tape(tape))
+    }
+}
+
+impl<const I: usize, const O: usize, E: Dtype + Float, D: Device<E>> TensorCollection<E, D>
+    for SpectralNorm<I, O, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = SpectralNorm<I, O, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::tensor(
+                    "weight",
+                    |s| &s.weight,
+                    |s| &mut s.weight,
+                    TensorOptions::reset_with(|t| {
+                        let b: E = E::ONE / E::from_usize(I).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::tensor(
+                    "bias",
+                    |s| &s.bias,
+                    |s| &mut s.bias,
+                    TensorOptions::reset_with(|t| {
+                        let b: E = E::ONE / E::from_usize(I).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::tensor(
+                    "u",
+                    |s| &s.u,
+                    |s| &mut s.u,
+                    TensorOptions::detached(|t| {
+                        t.try_fill_with_distr(Uniform::new(-E::ONE, E::ONE))?;
+                        let norm = t.clone().try_frobenius_norm()?;
+                        *t = t.clone().try_div(norm.try_broadcast()?)?;
+                        Ok(())
+                    }),
+                ),
+                Self::tensor(
+                    "v",
+                    |s| &s.v,
+                    |s| &mut s.v,
+                    TensorOptions::detached(|t| {
+                        t.try_fill_with_distr(Uniform::new(-E::ONE, E::ONE))?;
+                        let norm = t.clone().try_frobenius_norm()?;
+                        *t = t.clone().try_div(norm.try_broadcast()?)?;
+                        Ok(())
+                    }),
+                ),
+                Self::scalar("training", |s| &s.training, |s| &mut s.training, true),
+            ),
+            |(weight, bias, u, v, training)| SpectralNorm {
+                weight,
+                bias,
+                u,
+                v,
+                epsilon: V::E2::from_f32(1e-12).unwrap(),
+                training,
+            },
+        )
+    }
+
+    fn set_training_mode(&mut self, training: bool) {
+        self.training = training;
+    }
+}
+
+impl<const I: usize, const O: usize, S: Shape, E: Dtype + Float, D: Device<E>>
+    Module<Tensor<S, E, D, NoneTape>> for SpectralNorm<I, O, E, D>
+where
+    Linear<I, O, E, D>: Module<Tensor<S, E, D, NoneTape>, Error = D::Err>,
+{
+    type Output = <Linear<I, O, E, D> as Module<Tensor<S, E, D, NoneTape>>>::Output;
+    type Error = D::Err;
+
+    /// Inference forward - does **not** update [Self::u] and [Self::v]
+    fn try_forward(&self, x: Tensor<S, E, D, NoneTape>) -> Result<Self::Output, D::Err> {
+        self.try_fwd(x)
+    }
+}
+
+impl<const I: usize, const O: usize, S: Shape, E: Dtype + Float, D: Device<E>>
+    ModuleMut<Tensor<S, E, D, OwnedTape<E, D>>> for SpectralNorm<I, O, E, D>
+where
+    Linear<I, O, E, D>: Module<Tensor<S, E, D, OwnedTape<E, D>>, Error = D::Err>,
+{
+    type Output = <Linear<I, O, E, D> as Module<Tensor<S, E, D, OwnedTape<E, D>>>>::Output;
+    type Error = D::Err;
+
+    /// Training forward - updates [Self::u] and [Self::v] with one step of power iteration
+    fn try_forward_mut(
+        &mut self,
+        x: Tensor<S, E, D, OwnedTape<E, D>>,
+    ) -> Result<Self::Output, D::Err> {
+        if self.training {
+            self.try_power_iteration()?;
+        }
+        self.try_fwd(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    /// Largest singular value, computed independently with many steps of power iteration.
+    fn spectral_norm<const O: usize, const I: usize>(w: [[TestDtype; I]; O]) -> TestDtype {
+        let mut v = [1.0; I];
+        for _ in 0..500 {
+            let mut u = [0.0; O];
+            for o in 0..O {
+                u[o] = (0..I).map(|i| w[o][i] * v[i]).sum();
+            }
+            for i in 0..I {
+                v[i] = (0..O).map(|o| w[o][i] * u[o]).sum();
+            }
+            let n: TestDtype = v.iter().map(|x| x * x).sum::<TestDtype>().sqrt();
+            v = v.map(|x| x / n);
+        }
+        let wv: TestDtype = (0..O)
+            .map(|o| (0..I).map(|i| w[o][i] * v[i]).sum::<TestDtype>().powi(2))
+            .sum();
+        wv.sqrt()
+    }
+
+    #[test]
+    fn test_spectral_norm_known_weight() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::SpectralNorm<2, 3>, TestDtype>();
+        m.weight = dev.tensor([[3.0, 0.0], [0.0, 1.0], [0.0, 0.0]]);
+        for _ in 0..10 {
+            m.power_iteration();
+        }
+        assert_close(&m.sigma().array(), &3.0);
+        assert_close(
+            &m.normalized_weight().array(),
+            &[[1.0, 0.0], [0.0, 1.0 / 3.0], [0.0, 0.0]],
+        );
+    }
+
+    #[test]
+    fn test_spectral_norm_close_to_one() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::SpectralNorm<5, 4>, TestDtype>();
+        m.weight = dev.sample_normal();
+        assert!(spectral_norm(m.weight.array()) > 1.1);
+        let x: Tensor<Rank2<8, 5>, TestDtype, _> = dev.sample_normal();
+        for _ in 0..30 {
+            let _ = m.forward_mut(x.leaky_trace());
+        }
+        assert_close_with_tolerance(&spectral_norm(m.normalized_weight().array()), &1.0, 1e-3);
+    }
+
+    #[test]
+    fn test_spectral_norm_forward_and_grads() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::SpectralNorm<3, 2>, TestDtype>();
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+
+        let y = m.forward(x.clone());
+        let linear = Linear {
+            weight: m.normalized_weight(),
+            bias: m.bias.clone(),
+        };
+        assert_close(&y.array(), &linear.forward(x.clone()).array());
+
+        let u = m.u.clone();
+        let y = m.forward_mut(x.trace(m.alloc_grads()));
+        assert_ne!(m.u.array(), u.array());
+        assert_eq!(m.u.id, u.id);
+        let g = y.sum().backward();
+        assert_eq!(g.get(&m.bias).array(), [4.0; 2]);
+        assert_ne!(g.get(&m.weight).array(), [[0.0; 3]; 2]);
+
+        // the gradient of the normalized weight is orthogonal to the current u v^T direction,
+        // since scaling weight doesn't change weight / sigma.
+        let w = m.weight.array();
+        let gw = g.get(&m.weight).array();
+        let dot: TestDtype = (0..2)
+            .flat_map(|o| (0..3).map(move |i| (o, i)))
+            .map(|(o, i)| w[o][i] * gw[o][i])
+            .sum();
+        assert!(dot.abs() < 1e-4, "{dot}");
+    }
+}
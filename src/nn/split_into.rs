@@ -22,11 +22,16 @@ use super::*;
 #[derive(Debug, Default, Clone)]
 pub struct SplitInto<T>(pub T);
 
-impl<T: BuildOnDevice<D, E>, D: Device<E>, E: Dtype> BuildOnDevice<D, E> for SplitInto<T> {
+impl<T: BuildOnDevice<D, E>, D: Device<E>, E: Dtype> BuildOnDevice<D, E> for SplitInto<T>
+where
+    T::Built: 'static,
+{
     type Built = SplitInto<T::Built>;
 }
 
-impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D>> TensorCollection<E, D> for SplitInto<T> {
+impl<E: Dtype, D: Device<E>, T: TensorCollection<E, D> + 'static> TensorCollection<E, D>
+    for SplitInto<T>
+{
     type To<E2: Dtype, D2: Device<E2>> = SplitInto<T::To<E2, D2>>;
 
     fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
@@ -0,0 +1,207 @@
+use crate::{
+    shapes::*,
+    tensor::*,
+    tensor_ops::{Device, TryAdd, TryMul},
+};
+
+use super::*;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// A [super::modules::Residual] connection around `F` whose branch is randomly skipped during training,
+/// as introduced in [Deep Networks with Stochastic Depth](https://arxiv.org/abs/1603.09382).
+///
+/// - [ModuleMut] (training): with probability `1 - survival_prob` returns `x` without
+///   running `F`. Otherwise returns `x + F(x) / survival_prob`.
+/// - [Module] (inference): always returns `x + F(x)`.
+///
+/// The scaling by `1 / survival_prob` keeps the expected output during training the same
+/// as the output during inference, similar to [super::modules::Dropout].
+///
+/// # Generics
+/// - `F`: The residual branch that may be skipped.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = StochasticDepth<ReLU>;
+/// let mut model = dev.build_module::<Model, f32>();
+/// model.survival_prob = 0.5;
+/// let x = dev.tensor([-2.0, -1.0, 0.0, 1.0, 2.0]);
+/// let y = model.forward(x.clone());
+/// assert_eq!(y.array(), [-2.0, -1.0, 0.0, 2.0, 4.0]);
+/// let y = model.forward_mut(x.leaky_trace());
+/// assert!(y.array() == [-2.0, -1.0, 0.0, 1.0, 2.0] || y.array() == [-2.0, -1.0, 0.0, 3.0, 6.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StochasticDepth<F> {
+    pub module: F,
+    /// Probability of running `F` during training. Defaults to `0.8`.
+    pub survival_prob: f32,
+    /// Whether [ModuleMut] randomly skips `F`. If `false`, it always runs `F`
+    /// like [Module] does. Defaults to `true`. See [SetTraining].
+    pub training: bool,
+}
+
+impl<F: Default> Default for StochasticDepth<F> {
+    /// Sets `self.survival_prob` to `0.8`, and `self.training` to `true`
+    fn default() -> Self {
+        Self {
+            module: Default::default(),
+            survival_prob: 0.8,
+            training: true,
+        }
+    }
+}
+
+impl<D: Device<E>, E: Dtype, F: BuildOnDevice<D, E>> BuildOnDevice<D, E> for StochasticDepth<F>
+where
+    F::Built: 'static,
+{
+    type Built = StochasticDepth<F::Built>;
+}
+
+impl<E: Dtype, D: Device<E>, F: TensorCollection<E, D> + 'static> TensorCollection<E, D>
+    for StochasticDepth<F>
+{
+    type To<E2: Dtype, D2: Device<E2>> = StochasticDepth<F::To<E2, D2>>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::module("module", |s| &s.module, |s| &mut s.module),
+                Self::scalar(
+                    "survival_prob",
+                    |s| &s.survival_prob,
+                    |s| &mut s.survival_prob,
+                    0.8,
+                ),
+                Self::scalar("training", |s| &s.training, |s| &mut s.training, true),
+            ),
+            |(module, survival_prob, training)| StochasticDepth {
+                module,
+                survival_prob,
+                training,
+            },
+        )
+    }
+
+    fn set_training_mode(&mut self, training: bool) {
+        self.training = training;
+    }
+}
+
+impl<T: WithEmptyTape + TryAdd<T>, F: Module<T, Output = T, Error = T::Err>> Module<T>
+    for StochasticDepth<F>
+{
+    type Output = T;
+    type Error = F::Error;
+
+    /// Always runs `F`: `x + F(x)`
+    fn try_forward(&self, x: T) -> Result<Self::Output, F::Error> {
+        self.module.try_forward(x.with_empty_tape())?.try_add(x)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, F> ModuleMut<Tensor<S, E, D, OwnedTape<E, D>>>
+    for StochasticDepth<F>
+where
+    F: ModuleMut<
+        Tensor<S, E, D, OwnedTape<E, D>>,
+        Output = Tensor<S, E, D, OwnedTape<E, D>>,
+        Error = D::Err,
+    >,
+{
+    type Output = Tensor<S, E, D, OwnedTape<E, D>>;
+    type Error = D::Err;
+
+    /// Skips `F` with probability `1 - survival_prob`, using the device's rng.
+    /// Always runs `F` if `self.training` is false.
+    fn try_forward_mut(
+        &mut self,
+        x: Tensor<S, E, D, OwnedTape<E, D>>,
+    ) -> Result<Self::Output, D::Err> {
+        if !self.training {
+            return self.module.try_forward_mut(x.with_empty_tape())?.try_add(x);
+        }
+        let mut rng = StdRng::seed_from_u64(x.device.random_u64());
+        if rng.gen::<f32>() >= self.survival_prob {
+            return Ok(x);
+        }
+        let scale = E::from_f32(1.0 / self.survival_prob).unwrap();
+        self.module
+            .try_forward_mut(x.with_empty_tape())?
+            .try_mul(scale)?
+            .try_add(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use crate::{nn::builders::Linear, tensor_ops::*};
+
+    #[test]
+    fn test_stochastic_depth_inference_runs_module() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<StochasticDepth<Linear<2, 2>>, TestDtype>();
+        model.survival_prob = 0.0;
+        let x: Tensor<Rank2<4, 2>, TestDtype, _> = dev.sample_normal();
+        let y = model.forward(x.clone());
+        let expected = model.module.forward(x.clone()) + x;
+        assert_close(&y.array(), &expected.array());
+    }
+
+    #[test]
+    fn test_stochastic_depth_skips() {
+        let dev = TestDevice::seed_from_u64(0);
+        let mut model = dev.build_module::<StochasticDepth<Linear<2, 2>>, TestDtype>();
+        model.survival_prob = 0.5;
+        let x: Tensor<Rank2<4, 2>, TestDtype, _> = dev.sample_normal();
+        let branch = model.module.forward(x.clone()) * 2.0 + x.clone();
+
+        let mut num_skipped = 0;
+        for _ in 0..20 {
+            let y = model.forward_mut(x.trace(model.alloc_grads()));
+            let skipped = y.array() == x.array();
+            if !skipped {
+                assert_close(&y.array(), &branch.array());
+            }
+            let g = y.sum().backward();
+            let g_weight = g.get(&model.module.weight).array();
+            assert_eq!(skipped, g_weight == [[0.0; 2]; 2]);
+            num_skipped += skipped as usize;
+        }
+        assert!(
+            (1..20).contains(&num_skipped),
+            "expected some but not all skipped, got {num_skipped}"
+        );
+    }
+
+    #[test]
+    fn test_stochastic_depth_always_skips() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<StochasticDepth<Linear<2, 2>>, TestDtype>();
+        model.survival_prob = 0.0;
+        let x: Tensor<Rank1<2>, TestDtype, _> = dev.sample_normal();
+        for _ in 0..5 {
+            let y = model.forward_mut(x.trace(model.alloc_grads()));
+            assert_eq!(y.array(), x.array());
+            let g = y.sum().backward();
+            assert_eq!(g.get(&model.module.bias).array(), [0.0; 2]);
+        }
+    }
+
+    #[test]
+    fn test_stochastic_depth_keeps_survival_prob() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<StochasticDepth<Linear<2, 2>>, TestDtype>();
+        model.survival_prob = 0.3;
+        let model = model.to_device(&dev);
+        assert_eq!(model.survival_prob, 0.3);
+    }
+}
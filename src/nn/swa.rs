@@ -0,0 +1,230 @@
+use super::{
+    ema::ModelEMA, polyak::PolyakAverager, set_training::SetTraining, tensor_collection::*,
+};
+
+use crate::{shapes::*, tensor::*, tensor_ops::Device};
+
+use std::string::String;
+
+/// Stochastic Weight Averaging from
+/// [Averaging Weights Leads to Wider Optima and Better Generalization](https://arxiv.org/abs/1803.05407).
+///
+/// Training continues with a constant (or cyclic) learning rate, and [Swa::step()] is called
+/// at the end of each epoch. From `start_epoch` on, the trainable parameters of the model are
+/// added to a running average with [PolyakAverager]. At the end of training,
+/// [Swa::finalize()] writes the average into the model, and [update_bn_stats()] recomputes
+/// the running statistics of its batch normalization layers for the averaged weights.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, nn::{Swa, update_bn_stats}};
+/// # let dev: Cpu = Default::default();
+/// type Model = (Linear<2, 5>, BatchNorm1D<5>);
+/// let mut model = dev.build_module::<Model, f32>();
+/// let mut swa = Swa::new(3);
+/// for epoch in 0..5 {
+///     // -- snip training --
+///     swa.step(epoch, &model);
+/// }
+/// assert_eq!(swa.num_averaged(), 2);
+/// swa.finalize(&mut model);
+/// let batches = (0..10).map(|_| dev.sample_normal::<Rank2<8, 2>>());
+/// update_bn_stats(&mut model, batches, |m, x| {
+///     m.forward_mut(x.leaky_traced());
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Swa<M> {
+    /// The first epoch to add to the average.
+    pub start_epoch: usize,
+    averager: PolyakAverager<M>,
+}
+
+impl<M> Swa<M> {
+    pub fn new(start_epoch: usize) -> Self {
+        Self {
+            start_epoch,
+            averager: Default::default(),
+        }
+    }
+
+    /// The number of epochs that have been averaged.
+    pub fn num_averaged(&self) -> usize {
+        self.averager.len()
+    }
+
+    /// The average so far, or `None` if no epochs have been averaged.
+    pub fn average(&self) -> Option<&M> {
+        self.averager.average()
+    }
+}
+
+impl<M: Clone> Swa<M> {
+    /// Adds `model` to the average if `epoch` is at least `start_epoch`, and returns
+    /// whether it was added.
+    pub fn step<E: Dtype, D: Device<E>>(&mut self, epoch: usize, model: &M) -> bool
+    where
+        M: TensorCollection<E, D>,
+    {
+        self.try_step(epoch, model).unwrap()
+    }
+
+    /// See [Swa::step()]
+    pub fn try_step<E: Dtype, D: Device<E>>(
+        &mut self,
+        epoch: usize,
+        model: &M,
+    ) -> Result<bool, D::Err>
+    where
+        M: TensorCollection<E, D>,
+    {
+        if epoch < self.start_epoch {
+            return Ok(false);
+        }
+        self.averager.try_add(model)?;
+        Ok(true)
+    }
+
+    /// Replaces the trainable parameters of `model` with their average, and returns whether
+    /// there was an average. Everything else, like running statistics, is left as it was.
+    pub fn finalize<E: Dtype, D: Device<E>>(&self, model: &mut M) -> bool
+    where
+        M: TensorCollection<E, D>,
+    {
+        self.try_finalize(model).unwrap()
+    }
+
+    /// See [Swa::finalize()]
+    pub fn try_finalize<E: Dtype, D: Device<E>>(&self, model: &mut M) -> Result<bool, D::Err>
+    where
+        M: TensorCollection<E, D>,
+    {
+        match self.averager.average() {
+            Some(average) => {
+                model.try_ema(average, E::default())?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+/// Recomputes the running statistics of the batch normalization layers in `model`, e.g.
+/// after [Swa::finalize()] changes its weights.
+///
+/// The running statistics are reset, and then `forward` is called with `model` in
+/// training mode on each of `batches`, which should call [super::ModuleMut::forward_mut].
+/// The new running statistics are an exponential moving average over the batches with the
+/// momentum of each layer, so there should be at least `1 / momentum` batches.
+///
+/// The model is left in training mode.
+pub fn update_bn_stats<M, E, D, I, F>(model: &mut M, batches: I, forward: F)
+where
+    M: TensorCollection<E, D>,
+    E: Dtype,
+    D: Device<E>,
+    I: IntoIterator,
+    F: FnMut(&mut M, I::Item),
+{
+    try_update_bn_stats(model, batches, forward).unwrap()
+}
+
+/// See [update_bn_stats()]
+pub fn try_update_bn_stats<M, E, D, I, F>(
+    model: &mut M,
+    batches: I,
+    mut forward: F,
+) -> Result<(), D::Err>
+where
+    M: TensorCollection<E, D>,
+    E: Dtype,
+    D: Device<E>,
+    I: IntoIterator,
+    F: FnMut(&mut M, I::Item),
+{
+    M::iter_tensors(&mut RecursiveWalker {
+        m: (&mut *model, String::new()),
+        f: &mut ResetRunningStats,
+    })?;
+    model.try_set_training(true)?;
+    for batch in batches {
+        forward(model, batch);
+    }
+    Ok(())
+}
+
+struct ResetRunningStats;
+
+impl<E: Dtype, D: Device<E>> TensorVisitor<E, D> for ResetRunningStats {
+    type Viewer = (ViewTensorMut, ViewTensorName);
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        (t, name): (&mut Tensor<S, E, D>, String),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if name.ends_with("running_mean") || name.ends_with("running_var") {
+            (opts.reset)(t)?;
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders::*, DeviceBuildExt, ModuleMut},
+        tests::*,
+    };
+
+    #[test]
+    fn test_swa_averages_snapshots_after_start() {
+        let dev: TestDevice = Default::default();
+        let snapshots = [
+            dev.build_module::<Linear<3, 2>, TestDtype>(),
+            dev.build_module::<Linear<3, 2>, TestDtype>(),
+            dev.build_module::<Linear<3, 2>, TestDtype>(),
+            dev.build_module::<Linear<3, 2>, TestDtype>(),
+        ];
+
+        let mut swa = Swa::new(1);
+        let added: std::vec::Vec<bool> = snapshots
+            .iter()
+            .enumerate()
+            .map(|(epoch, s)| swa.step(epoch, s))
+            .collect();
+        assert_eq!(added, [false, true, true, true]);
+        assert_eq!(swa.num_averaged(), 3);
+
+        let mut model = snapshots[0].clone();
+        assert!(swa.finalize(&mut model));
+        let s = &snapshots;
+        let weight = (s[1].weight.clone() + s[2].weight.clone() + s[3].weight.clone()) / 3.0;
+        let bias = (s[1].bias.clone() + s[2].bias.clone() + s[3].bias.clone()) / 3.0;
+        assert_close(&model.weight.array(), &weight.array());
+        assert_close(&model.bias.array(), &bias.array());
+    }
+
+    #[test]
+    fn test_update_bn_stats() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<BatchNorm1D<2>, TestDtype>();
+        model.running_mean = dev.tensor([10.0, -10.0]);
+        model.running_var = dev.tensor([5.0, 5.0]);
+        model.momentum = 0.5;
+        model.set_training(false);
+
+        let x: Tensor<Rank2<4, 2>, TestDtype, _> =
+            dev.tensor([[1.0, 2.0], [3.0, 2.0], [1.0, 2.0], [3.0, 2.0]]);
+        update_bn_stats(&mut model, [x], |m, x| {
+            m.forward_mut(x.leaky_traced());
+        });
+        assert!(model.training);
+        // one update with a momentum of 0.5 from the reset statistics of mean 0 and var 1
+        assert_close(&model.running_mean.array(), &[1.0, 1.0]);
+        assert_close(&model.running_var.array(), &[0.5 + 0.5 * 4.0 / 3.0, 0.5]);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{unbiased_linear::UnbiasedLinear, *};
+
+use num_traits::Float;
+use rand_distr::uniform::SampleUniform;
+
+pub mod builder {
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct SwiGLU<const I: usize, const H: usize>;
+}
+
+impl<const I: usize, const H: usize, E: Dtype, D: Device<E>> BuildOnDevice<D, E>
+    for builder::SwiGLU<I, H>
+where
+    SwiGLU<I, H, E, D>: BuildModule<D, E>,
+{
+    type Built = SwiGLU<I, H, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// The gated feed forward block from
+/// [GLU Variants Improve Transformer](https://arxiv.org/abs/2002.05202), as used in LLaMA.
+///
+/// Computes `w2(silu(w1(x)) * w3(x))`, where `silu(x) = x * sigmoid(x)`, and none of the
+/// linear layers have a bias.
+///
+/// # Generics
+/// - `I` The size of the input & output.
+/// - `H` The hidden size of the gate.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = SwiGLU<5, 8>;
+/// let model = dev.build_module::<Model, f32>();
+/// let _: Tensor<Rank1<5>, f32, _> = model.forward(dev.zeros::<Rank1<5>>());
+/// let _: Tensor<Rank3<2, 3, 5>, f32, _> = model.forward(dev.zeros::<Rank3<2, 3, 5>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SwiGLU<const I: usize, const H: usize, E: Dtype, D: DeviceStorage> {
+    /// The projection that goes through silu, the gate.
+    pub w1: UnbiasedLinear<I, H, E, D>,
+    /// The projection back to the input size.
+    pub w2: UnbiasedLinear<H, I, E, D>,
+    /// The projection that is multiplied by the gate.
+    pub w3: UnbiasedLinear<I, H, E, D>,
+}
+
+impl<const I: usize, const H: usize, E: Dtype, D: DeviceStorage> NonMutableModule
+    for SwiGLU<I, H, E, D>
+{
+}
+
+impl<const I: usize, const H: usize, E: Dtype + Float + SampleUniform, D: Device<E>>
+    TensorCollection<E, D> for SwiGLU<I, H, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = SwiGLU<I, H, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::module("w1", |s| &s.w1, |s| &mut s.w1),
+                Self::module("w2", |s| &s.w2, |s| &mut s.w2),
+                Self::module("w3", |s| &s.w3, |s| &mut s.w3),
+            ),
+            |(w1, w2, w3)| SwiGLU { w1, w2, w3 },
+        )
+    }
+}
+
+impl<const I: usize, const H: usize, E: Dtype, D: Device<E>> SwiGLU<I, H, E, D> {
+    fn try_swiglu<S: Shape, Hidden: Shape, T: Tape<E, D>>(
+        &self,
+        x: Tensor<S, E, D, T>,
+    ) -> Result<Tensor<S, E, D, T>, D::Err>
+    where
+        UnbiasedLinear<I, H, E, D>:
+            Module<Tensor<S, E, D, T>, Output = Tensor<Hidden, E, D, T>, Error = D::Err>,
+        UnbiasedLinear<H, I, E, D>:
+            Module<Tensor<Hidden, E, D, T>, Output = Tensor<S, E, D, T>, Error = D::Err>,
+    {
+        let up = self.w3.try_forward(x.retaped::<T>())?;
+        let gate = self.w1.try_forward(x)?;
+        let gate = gate.retaped::<T>().try_sigmoid()?.try_mul(gate)?;
+        self.w2.try_forward(gate.try_mul(up)?)
+    }
+}
+
+impl<const I: usize, const H: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<Rank1<I>, E, D, T>> for SwiGLU<I, H, E, D>
+{
+    type Output = Tensor<Rank1<I>, E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<Rank1<I>, E, D, T>) -> Result<Self::Output, D::Err> {
+        self.try_swiglu::<_, Rank1<H>, _>(x)
+    }
+}
+
+impl<B: Dim, const I: usize, const H: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<(B, Const<I>), E, D, T>> for SwiGLU<I, H, E, D>
+{
+    type Output = Tensor<(B, Const<I>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(B, Const<I>), E, D, T>) -> Result<Self::Output, D::Err> {
+        self.try_swiglu::<_, (B, Const<H>), _>(x)
+    }
+}
+
+impl<B: Dim, S: Dim, const I: usize, const H: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<(B, S, Const<I>), E, D, T>> for SwiGLU<I, H, E, D>
+{
+    type Output = Tensor<(B, S, Const<I>), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(B, S, Const<I>), E, D, T>) -> Result<Self::Output, D::Err> {
+        self.try_swiglu::<_, (B, S, Const<H>), _>(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_swiglu_forward_and_gradients() {
+        let dev: TestDevice = Default::default();
+        let m = dev.build_module::<builder::SwiGLU<3, 4>, TestDtype>();
+        let x: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let y: Tensor<Rank2<2, 3>, TestDtype, _, _> = m.forward(x.leaky_trace());
+
+        let h1 = x.clone().matmul(m.w1.weight.clone().permute());
+        let h3 = x.clone().matmul(m.w3.weight.clone().permute());
+        let expected = (h1.clone() * h1.sigmoid() * h3).matmul(m.w2.weight.clone().permute());
+        assert_close(&y.array(), &expected.array());
+
+        let g = y.square().mean().backward();
+        for w in [&m.w1.weight, &m.w3.weight] {
+            assert!(g.get(w).array().iter().flatten().any(|v| *v != 0.0));
+        }
+        assert!(g
+            .get(&m.w2.weight)
+            .array()
+            .iter()
+            .flatten()
+            .any(|v| *v != 0.0));
+        assert!(g.get(&x).array().iter().flatten().any(|v| *v != 0.0));
+    }
+}
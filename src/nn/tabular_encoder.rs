@@ -0,0 +1,246 @@
+use num_traits::Float;
+use rand_distr::uniform::SampleUniform;
+use std::{string::String, vec::Vec};
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{modules::Embedding, *};
+
+pub mod builder {
+    #[derive(Debug)]
+    pub struct TabularEncoder<
+        const CATEGORICAL: usize,
+        const VOCAB: usize,
+        const DIM: usize,
+        const NUMERIC: usize,
+    >;
+}
+
+impl<const C: usize, const V: usize, const M: usize, const N: usize, E: Dtype, D: Device<E>>
+    BuildOnDevice<D, E> for builder::TabularEncoder<C, V, M, N>
+where
+    TabularEncoder<C, V, M, N, E, D>: BuildModule<D, E>,
+{
+    type Built = TabularEncoder<C, V, M, N, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, D::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// Encodes rows of mixed tabular data into one feature vector each, by concatenating an
+/// [Embedding] of each categorical column with the normalized numeric columns.
+///
+/// The input is a tuple of the `(B, CATEGORICAL)` categories and the `(B, NUMERIC)` numbers
+/// of each row. The output is `(B, CATEGORICAL * DIM + NUMERIC)`, with the embeddings of the
+/// categorical columns in order, and then `(numeric - numeric_mean) / numeric_std`. The tapes
+/// of both inputs are merged, so either can be traced for the embeddings to get gradients.
+///
+/// [Self::numeric_mean] and [Self::numeric_std] are 0 and 1 by default, and aren't updated
+/// by gradients, so they should be set to the statistics of the training data.
+///
+/// # Generics
+/// - `CATEGORICAL` The number of categorical columns, each with its own embedding table.
+/// - `VOCAB` The number of categories of each categorical column.
+/// - `DIM` The size of each embedding.
+/// - `NUMERIC` The number of numeric columns.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = TabularEncoder<2, 10, 4, 3>;
+/// let model = dev.build_module::<Model, f32>();
+/// let categories: Tensor<Rank2<5, 2>, usize, _> = dev.zeros();
+/// let numbers: Tensor<Rank2<5, 3>, f32, _> = dev.sample_normal();
+/// let features = model.forward((categories, numbers));
+/// assert_eq!(features.shape(), &(Const::<5>, 2 * 4 + 3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TabularEncoder<
+    const CATEGORICAL: usize,
+    const VOCAB: usize,
+    const DIM: usize,
+    const NUMERIC: usize,
+    E: Dtype,
+    D: DeviceStorage,
+> {
+    pub embeddings: Vec<Embedding<VOCAB, DIM, E, D>>,
+    pub numeric_mean: Tensor<Rank1<NUMERIC>, E, D>,
+    /// Must be positive.
+    pub numeric_std: Tensor<Rank1<NUMERIC>, E, D>,
+}
+
+impl<
+        const C: usize,
+        const V: usize,
+        const M: usize,
+        const N: usize,
+        E: Dtype,
+        D: DeviceStorage,
+    > NonMutableModule for TabularEncoder<C, V, M, N, E, D>
+{
+}
+
+impl<
+        const C: usize,
+        const V: usize,
+        const M: usize,
+        const N: usize,
+        E: Dtype + Float + SampleUniform,
+        D: Device<E>,
+    > TensorCollection<E, D> for TabularEncoder<C, V, M, N, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = TabularEncoder<C, V, M, N, E2, D2>;
+
+    fn iter_tensors<Vis: ModuleVisitor<Self, E, D>>(
+        visitor: &mut Vis,
+    ) -> Result<Option<Self::To<Vis::E2, Vis::D2>>, Vis::Err> {
+        let names: Vec<String> = (0..C).map(|i| format!("embeddings.{i}")).collect();
+        visitor.visit_fields(
+            (
+                (0..C)
+                    .zip(names.iter())
+                    .map(|(i, name)| {
+                        Self::module(
+                            name,
+                            move |s| &s.embeddings[i],
+                            move |s| &mut s.embeddings[i],
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+                Self::tensor(
+                    "numeric_mean",
+                    |s| &s.numeric_mean,
+                    |s| &mut s.numeric_mean,
+                    TensorOptions::detached(|t| t.try_fill_with_zeros()),
+                ),
+                Self::tensor(
+                    "numeric_std",
+                    |s| &s.numeric_std,
+                    |s| &mut s.numeric_std,
+                    TensorOptions::detached(|t| t.try_fill_with_ones()),
+                ),
+            ),
+            |(embeddings, numeric_mean, numeric_std)| TabularEncoder {
+                embeddings,
+                numeric_mean,
+                numeric_std,
+            },
+        )
+    }
+}
+
+impl<
+        B: Dim,
+        const C: usize,
+        const V: usize,
+        const M: usize,
+        const N: usize,
+        E: Dtype,
+        D: Device<E>,
+        T: Tape<E, D> + Merge<R>,
+        R: Tape<E, D>,
+    >
+    Module<(
+        Tensor<(B, Const<C>), usize, D, T>,
+        Tensor<(B, Const<N>), E, D, R>,
+    )> for TabularEncoder<C, V, M, N, E, D>
+{
+    type Output = Tensor<(B, usize), E, D, T>;
+    type Error = D::Err;
+
+    fn try_forward(
+        &self,
+        (categories, numbers): (
+            Tensor<(B, Const<C>), usize, D, T>,
+            Tensor<(B, Const<N>), E, D, R>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        let batch = categories.shape.0;
+        let (categories, tape) = categories.split_tape();
+        let categories = categories.as_vec();
+        let (numbers, numbers_tape) = numbers.split_tape();
+        let numbers = numbers.put_tape(tape.merge(numbers_tape));
+
+        // each part is `(width, B)` so they can be concatenated along the first dimension
+        let shape = *numbers.shape();
+        let numbers = numbers
+            .try_sub(self.numeric_mean.clone().try_broadcast_like(&shape)?)?
+            .try_div(self.numeric_std.clone().try_broadcast_like(&shape)?)?;
+        let mut out: Tensor<(usize, B), E, D, T> = numbers
+            .try_permute::<_, Axes2<1, 0>>()?
+            .try_reshape_like(&(N, batch))
+            .unwrap()?;
+        for (c, embedding) in self.embeddings.iter().enumerate().rev() {
+            let column = categories.iter().skip(c).step_by(C).copied().collect();
+            let column = embedding
+                .weight
+                .device
+                .try_tensor_from_vec(column, (batch,))?;
+            let embedded: Tensor<(usize, B), E, D, T> = embedding
+                .try_forward(column.put_tape(T::default()))?
+                .try_permute::<_, Axes2<1, 0>>()?
+                .try_reshape_like(&(M, batch))
+                .unwrap()?;
+            out = embedded.try_concat(out)?;
+        }
+        out.try_permute::<_, Axes2<1, 0>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_tabular_encoder() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builder::TabularEncoder<1, 3, 2, 2>, TestDtype>();
+        assert_eq!(model.embeddings.len(), 1);
+        model.embeddings[0].weight = dev.tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+        model.numeric_mean = dev.tensor([1.0, -1.0]);
+        model.numeric_std = dev.tensor([2.0, 0.5]);
+
+        let categories: Tensor<Rank2<3, 1>, usize, _> = dev.tensor([[2], [0], [2]]);
+        let numbers: Tensor<Rank2<3, 2>, TestDtype, _> =
+            dev.tensor([[1.0, -1.0], [3.0, 0.0], [-1.0, -0.5]]);
+        let y = model.forward((categories.leaky_trace(), numbers));
+        assert_eq!(y.shape(), &(Const::<3>, 4));
+        assert_eq!(
+            y.as_vec(),
+            [5.0, 6.0, 0.0, 0.0, 1.0, 2.0, 1.0, 2.0, 5.0, 6.0, -1.0, 1.0]
+        );
+
+        // category 2 is looked up twice, and category 1 isn't looked up
+        let w = dev.tensor_from_vec(
+            std::vec![1.0, 2.0, 9.0, 9.0, 3.0, 4.0, 9.0, 9.0, 5.0, 6.0, 9.0, 9.0],
+            (Const::<3>, 4),
+        );
+        let g = (y * w).sum().backward();
+        assert_eq!(
+            g.get(&model.embeddings[0].weight).array(),
+            [[3.0, 4.0], [0.0, 0.0], [6.0, 8.0]]
+        );
+    }
+
+    #[test]
+    fn test_tabular_encoder_column_order() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builder::TabularEncoder<2, 4, 3, 1>, TestDtype>();
+        let categories: Tensor<Rank2<2, 2>, usize, _> = dev.tensor([[0, 3], [1, 2]]);
+        let numbers: Tensor<Rank2<2, 1>, TestDtype, _> = dev.tensor([[7.0], [8.0]]);
+        let y = model.forward((categories, numbers)).as_vec();
+        let (a, b) = (
+            model.embeddings[0].weight.array(),
+            model.embeddings[1].weight.array(),
+        );
+        let mut expected = std::vec::Vec::new();
+        for (row, (i, j)) in [(0, 3), (1, 2)].into_iter().enumerate() {
+            expected.extend(a[i]);
+            expected.extend(b[j]);
+            expected.push(7.0 + row as TestDtype);
+        }
+        assert_eq!(y, expected);
+    }
+}
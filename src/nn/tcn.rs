@@ -0,0 +1,364 @@
+use num_traits::Float;
+use rand_distr::{uniform::SampleUniform, Uniform};
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{modules::Dropout, *};
+
+pub mod builder {
+    #[derive(Debug)]
+    pub struct TCNBlock<const I: usize, const O: usize, const K: usize, const DILATION: usize>;
+}
+
+impl<
+        const I: usize,
+        const O: usize,
+        const K: usize,
+        const DILATION: usize,
+        E: Dtype,
+        D: Device<E>,
+    > BuildOnDevice<D, E> for builder::TCNBlock<I, O, K, DILATION>
+where
+    TCNBlock<I, O, K, DILATION, E, D>: BuildModule<D, E>,
+{
+    type Built = TCNBlock<I, O, K, DILATION, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, D::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// A residual block of a [temporal convolutional network](https://arxiv.org/abs/1803.01271),
+/// for modeling sequences without recurrence.
+///
+/// The input of shape `(I, L)` or `(B, I, L)` goes through a dilated causal convolution, a
+/// [ReLU], and [Self::dropout], and then a 1x1 convolution of the input, which matches the
+/// number of channels, is added as the residual. The convolution is causal because the input
+/// is padded with `(K - 1) * DILATION` zeros at the start, so the output at time `t` only
+/// depends on the inputs up to `t`.
+///
+/// Like [Dropout], the dropout is only applied by [ModuleMut] with an [OwnedTape].
+///
+/// Initializes [Self::weight], [Self::bias], [Self::residual_weight] & [Self::residual_bias]
+/// from a Uniform distribution between [-1 / sqrt(I * K), 1 / sqrt(I * K)], where the residual
+/// has `K = 1`.
+///
+/// # Generics
+/// - `I` The number of input channels.
+/// - `O` The number of output channels.
+/// - `K` The size of the kernel.
+/// - `DILATION` The spacing between the kernel elements.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = TCNBlock<3, 8, 2, 4>;
+/// let model = dev.build_module::<Model, f32>();
+/// let x: Tensor<Rank3<10, 3, 16>, f32, _> = dev.sample_normal();
+/// let _: Tensor<Rank3<10, 8, 16>, f32, _> = model.forward(x);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TCNBlock<
+    const I: usize,
+    const O: usize,
+    const K: usize,
+    const DILATION: usize,
+    E: Dtype,
+    D: DeviceStorage,
+> {
+    pub weight: Tensor<Rank3<O, I, K>, E, D>,
+    pub bias: Tensor<Rank1<O>, E, D>,
+    pub residual_weight: Tensor<Rank2<O, I>, E, D>,
+    pub residual_bias: Tensor<Rank1<O>, E, D>,
+    pub dropout: Dropout,
+}
+
+impl<
+        const I: usize,
+        const O: usize,
+        const K: usize,
+        const DILATION: usize,
+        E: Dtype + Float + SampleUniform,
+        D: Device<E>,
+    > TensorCollection<E, D> for TCNBlock<I, O, K, DILATION, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = TCNBlock<I, O, K, DILATION, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::tensor(
+                    "weight",
+                    |s| &s.weight,
+                    |s| &mut s.weight,
+                    TensorOptions::reset_with(|t| {
+                        let b = E::ONE / E::from_usize(I * K).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::tensor(
+                    "bias",
+                    |s| &s.bias,
+                    |s| &mut s.bias,
+                    TensorOptions::reset_with(|t| {
+                        let b = E::ONE / E::from_usize(I * K).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::tensor(
+                    "residual_weight",
+                    |s| &s.residual_weight,
+                    |s| &mut s.residual_weight,
+                    TensorOptions::reset_with(|t| {
+                        let b = E::ONE / E::from_usize(I).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::tensor(
+                    "residual_bias",
+                    |s| &s.residual_bias,
+                    |s| &mut s.residual_bias,
+                    TensorOptions::reset_with(|t| {
+                        let b = E::ONE / E::from_usize(I).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::module("dropout", |s| &s.dropout, |s| &mut s.dropout),
+            ),
+            |(weight, bias, residual_weight, residual_bias, dropout)| TCNBlock {
+                weight,
+                bias,
+                residual_weight,
+                residual_bias,
+                dropout,
+            },
+        )
+    }
+}
+
+impl<
+        const I: usize,
+        const O: usize,
+        const K: usize,
+        const DILATION: usize,
+        E: Dtype,
+        D: Device<E>,
+    > TCNBlock<I, O, K, DILATION, E, D>
+{
+    /// The activated causal convolution, and the residual, of `x`.
+    #[allow(clippy::type_complexity)]
+    fn try_branches<B: Dim, L: Dim, T: Tape<E, D>>(
+        &self,
+        x: Tensor<(B, Const<I>, L), E, D, T>,
+    ) -> Result<
+        (
+            Tensor<(B, Const<O>, L), E, D, T>,
+            Tensor<(B, Const<O>, L), E, D, T>,
+        ),
+        D::Err,
+    > {
+        assert!(K > 0, "the kernel can't be empty");
+        let (batch, _, len) = x.shape;
+        let shape = (batch, Const::<O>, len);
+        let residual = self
+            .residual_weight
+            .retaped::<T>()
+            .try_matmul(x.retaped::<T>())?
+            .try_add(
+                self.residual_bias
+                    .retaped::<T>()
+                    .try_broadcast_like(&shape)?,
+            )?;
+
+        let (padded, tape) = x
+            .try_pad1d((K - 1) * DILATION, 0, PadMode::Zeros)?
+            .split_tape();
+        let mut tape = Some(tape);
+        let mut conv: Option<Tensor<(B, Const<O>, usize), E, D, T>> = None;
+        for k in 0..K {
+            let start = k * DILATION;
+            let taps = padded
+                .clone()
+                .put_tape(tape.take().unwrap_or_default())
+                .try_slice((.., .., start..start + len.size()))?;
+            let weight = self
+                .weight
+                .retaped::<T>()
+                .try_slice((.., .., k..k + 1))?
+                .try_reshape_like(&(Const::<O>, Const::<I>))
+                .unwrap()?;
+            let tap = weight.try_matmul(taps)?;
+            conv = Some(match conv {
+                Some(conv) => conv.try_add(tap)?,
+                None => tap,
+            });
+        }
+        let conv = conv
+            .unwrap()
+            .try_reshape_like(&shape)
+            .unwrap()?
+            .try_add(self.bias.retaped::<T>().try_broadcast_like(&shape)?)?
+            .try_relu()?;
+        Ok((conv, residual))
+    }
+}
+
+impl<
+        const I: usize,
+        const O: usize,
+        const K: usize,
+        const DILATION: usize,
+        B: Dim,
+        L: Dim,
+        E: Dtype,
+        D: Device<E>,
+    > Module<Tensor<(B, Const<I>, L), E, D>> for TCNBlock<I, O, K, DILATION, E, D>
+{
+    type Output = Tensor<(B, Const<O>, L), E, D>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(B, Const<I>, L), E, D>) -> Result<Self::Output, D::Err> {
+        let (conv, residual) = self.try_branches(x)?;
+        conv.try_add(residual)
+    }
+}
+
+impl<
+        const I: usize,
+        const O: usize,
+        const K: usize,
+        const DILATION: usize,
+        L: Dim,
+        E: Dtype,
+        D: Device<E>,
+    > Module<Tensor<(Const<I>, L), E, D>> for TCNBlock<I, O, K, DILATION, E, D>
+{
+    type Output = Tensor<(Const<O>, L), E, D>;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<(Const<I>, L), E, D>) -> Result<Self::Output, D::Err> {
+        let len = x.shape.1;
+        let x = x.try_reshape_like(&(Const::<1>, Const, len)).unwrap()?;
+        self.try_forward(x)?
+            .try_reshape_like(&(Const, len))
+            .unwrap()
+    }
+}
+
+impl<
+        const I: usize,
+        const O: usize,
+        const K: usize,
+        const DILATION: usize,
+        B: Dim,
+        L: Dim,
+        E: Dtype,
+        D: Device<E>,
+    > ModuleMut<Tensor<(B, Const<I>, L), E, D, OwnedTape<E, D>>>
+    for TCNBlock<I, O, K, DILATION, E, D>
+{
+    type Output = Tensor<(B, Const<O>, L), E, D, OwnedTape<E, D>>;
+    type Error = D::Err;
+
+    fn try_forward_mut(
+        &mut self,
+        x: Tensor<(B, Const<I>, L), E, D, OwnedTape<E, D>>,
+    ) -> Result<Self::Output, D::Err> {
+        let (conv, residual) = self.try_branches(x)?;
+        self.dropout.try_forward_mut(conv)?.try_add(residual)
+    }
+}
+
+impl<
+        const I: usize,
+        const O: usize,
+        const K: usize,
+        const DILATION: usize,
+        L: Dim,
+        E: Dtype,
+        D: Device<E>,
+    > ModuleMut<Tensor<(Const<I>, L), E, D, OwnedTape<E, D>>>
+    for TCNBlock<I, O, K, DILATION, E, D>
+{
+    type Output = Tensor<(Const<O>, L), E, D, OwnedTape<E, D>>;
+    type Error = D::Err;
+
+    fn try_forward_mut(
+        &mut self,
+        x: Tensor<(Const<I>, L), E, D, OwnedTape<E, D>>,
+    ) -> Result<Self::Output, D::Err> {
+        let len = x.shape.1;
+        let x = x.try_reshape_like(&(Const::<1>, Const, len)).unwrap()?;
+        self.try_forward_mut(x)?
+            .try_reshape_like(&(Const, len))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_tcn_block_is_causal() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<builder::TCNBlock<2, 3, 3, 2>, TestDtype>();
+        let x: Tensor<Rank2<2, 12>, TestDtype, _> = dev.sample_normal();
+        let y = model.forward(x.clone()).array();
+
+        // changing the input at time 7 only changes the outputs from time 7 onwards
+        let mut changed = x.array();
+        changed[0][7] += 1.0;
+        changed[1][7] -= 2.0;
+        let y_changed = model.forward(dev.tensor(changed)).array();
+        for c in 0..3 {
+            assert_eq!(y[c][..7], y_changed[c][..7]);
+            assert_ne!(y[c][7], y_changed[c][7]);
+        }
+
+        // the output at time t only depends on the inputs at t, t - 2 & t - 4
+        let mut changed = x.array();
+        changed[0][3] += 1.0;
+        let y_changed = model.forward(dev.tensor(changed)).array();
+        for c in 0..3 {
+            for t in (0..3).chain([4, 6]).chain(8..12) {
+                assert_eq!(y[c][t], y_changed[c][t]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tcn_block_residual_grads() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<builder::TCNBlock<2, 3, 2, 1>, TestDtype>();
+        model.dropout.p = 0.0;
+        // without the convolution, only the residual is left
+        model.weight = dev.zeros();
+        model.bias = dev.zeros();
+        model.residual_weight = dev.tensor([[1.0, 2.0], [0.0, -1.0], [0.5, 0.5]]);
+        model.residual_bias = dev.tensor([0.0, 1.0, 2.0]);
+
+        let x: Tensor<Rank3<1, 2, 4>, TestDtype, _> = dev.sample_normal();
+        let y = model.forward_mut(x.leaky_trace());
+        let x_arr = x.array()[0];
+        let mut expected = [[0.0; 4]; 3];
+        for (o, row) in expected.iter_mut().enumerate() {
+            for (t, y) in row.iter_mut().enumerate() {
+                let w = model.residual_weight.array()[o];
+                *y = w[0] * x_arr[0][t] + w[1] * x_arr[1][t] + model.residual_bias.array()[o];
+            }
+        }
+        assert_close(&y.array(), &[expected]);
+
+        let g = y.sum().backward();
+        assert_close(&g.get(&x).array(), &[[[1.5; 4], [1.5; 4]]]);
+        assert_close(&g.get(&model.residual_bias).array(), &[4.0; 3]);
+        let sums = [x_arr[0].iter().sum(), x_arr[1].iter().sum()];
+        assert_close(&g.get(&model.residual_weight).array(), &[sums; 3]);
+        // the relu of a zero convolution has no gradient
+        assert_eq!(g.get(&model.weight).array(), [[[0.0; 2]; 2]; 3]);
+    }
+}
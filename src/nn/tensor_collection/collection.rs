@@ -6,7 +6,7 @@ use crate::{
     tensor_ops::Device,
 };
 
-use super::{ModuleField, ModuleFields, TensorField};
+use super::{ModuleField, ModuleFields, ScalarField, TensorField};
 
 /// A collection of named tensors. Implementing this trait will enable anything
 /// that operates on tensors, including resetting, counting number of params, updating gradients,
@@ -61,6 +61,11 @@ pub trait TensorCollection<E: Dtype, D: Device<E>>: Sized {
         visitor: &mut V,
     ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err>;
 
+    /// Switches this module, but not its sub-modules, between training and evaluation mode.
+    /// Does nothing by default. Do not use this; use [crate::nn::SetTraining] instead, which
+    /// calls this for every sub-module.
+    fn set_training_mode(&mut self, _training: bool) {}
+
     /// Creates a [ModuleFields] that represents a field that may contain one or more tensors.
     ///
     /// See also: [ModuleField], [TensorCollection].
@@ -72,7 +77,7 @@ pub trait TensorCollection<E: Dtype, D: Device<E>>: Sized {
     where
         F1: FnMut(&Self) -> &Field,
         F2: FnMut(&mut Self) -> &mut Field,
-        Field: TensorCollection<E, D>,
+        Field: TensorCollection<E, D> + 'static,
     {
         ModuleField {
             name,
@@ -105,6 +110,30 @@ pub trait TensorCollection<E: Dtype, D: Device<E>>: Sized {
             m: Default::default(),
         }
     }
+
+    /// Creates a [ModuleFields] that represents a field without tensors, like a hyperparameter.
+    /// `default` is used when building a new module.
+    ///
+    /// See also: [ScalarField], [TensorCollection].
+    fn scalar<F1, F2, N>(
+        name: &str,
+        get_ref: F1,
+        get_mut: F2,
+        default: N,
+    ) -> ScalarField<'_, F1, F2, Self, N>
+    where
+        F1: FnMut(&Self) -> &N,
+        F2: FnMut(&mut Self) -> &mut N,
+        N: Clone,
+    {
+        ScalarField {
+            name,
+            get_ref,
+            get_mut,
+            default,
+            m: Default::default(),
+        }
+    }
 }
 
 /// An object that can visit [TensorCollection]s and [Tensor]s recursively.
@@ -114,6 +143,9 @@ pub trait ModuleVisitor<T: TensorCollection<E, D>, E: Dtype, D: Device<E>>: Size
     type D2: Device<Self::E2>;
 
     /// Visit a [TensorCollection]. Do not use this; use visit_fields instead.
+    ///
+    /// `Field` is `'static` so that [crate::nn::VisitModules] can hand it out as a
+    /// [std::any::Any].
     fn visit_module<Field, GetRef, GetMut>(
         &mut self,
         name: &str,
@@ -123,7 +155,7 @@ pub trait ModuleVisitor<T: TensorCollection<E, D>, E: Dtype, D: Device<E>>: Size
     where
         GetRef: FnMut(&T) -> &Field,
         GetMut: FnMut(&mut T) -> &mut Field,
-        Field: TensorCollection<E, D>;
+        Field: TensorCollection<E, D> + 'static;
 
     /// Visits an actual named [Tensor]. Do not use this; use visit_fields instead.
     fn visit_tensor<S: Shape, GetRef, GetMut>(
@@ -137,6 +169,22 @@ pub trait ModuleVisitor<T: TensorCollection<E, D>, E: Dtype, D: Device<E>>: Size
         GetRef: FnMut(&T) -> &Tensor<S, E, D>,
         GetMut: FnMut(&mut T) -> &mut Tensor<S, E, D>;
 
+    /// Visits a named field without tensors. Returns `default` unless overridden, which
+    /// is what a newly built module should use. Do not use this; use visit_fields instead.
+    fn visit_scalar<N: Clone, GetRef, GetMut>(
+        &mut self,
+        _name: &str,
+        _get_refs: GetRef,
+        _get_muts: GetMut,
+        default: N,
+    ) -> Result<Option<N>, Self::Err>
+    where
+        GetRef: FnMut(&T) -> &N,
+        GetMut: FnMut(&mut T) -> &mut N,
+    {
+        Ok(Some(default))
+    }
+
     /// Takes something that implements [ModuleFields] and function that takes
     /// [ModuleFields::Output] and returns an instance of T.
     fn visit_fields<M: ModuleFields<T, E, D>>(
@@ -96,6 +96,18 @@ pub trait TensorViewer: 'static {
     where
         GetRef: FnMut(&Mod) -> &Field,
         GetMut: FnMut(&mut Mod) -> &mut Field;
+
+    /// Given a view of a module, returns a reference to one of that module's fields if this
+    /// view has access to the module. Returns `None` by default.
+    fn view_ref<'a, Mod, Field, GetRef>(
+        _module: &'a Self::View<'_, Mod>,
+        _get_ref: &mut GetRef,
+    ) -> Option<&'a Field>
+    where
+        GetRef: FnMut(&Mod) -> &Field,
+    {
+        None
+    }
 }
 
 /// A list of a Module's fields. Used in [ModuleVisitor::visit_fields].
@@ -145,6 +157,21 @@ where
     pub(super) m: std::marker::PhantomData<Mod>,
 }
 
+/// A [ModuleFields] that represents a field that does not contain any Tensors, like a
+/// hyperparameter or a flag. Its value is carried over when converting a module to a
+/// different device or dtype, and set to `default` when building a new module.
+pub struct ScalarField<'a, F1, F2, Mod, N>
+where
+    F1: FnMut(&Mod) -> &N,
+    F2: FnMut(&mut Mod) -> &mut N,
+{
+    pub(super) name: &'a str,
+    pub(super) get_ref: F1,
+    pub(super) get_mut: F2,
+    pub(super) default: N,
+    pub(super) m: std::marker::PhantomData<Mod>,
+}
+
 /// A [TensorViewer] that represents a `&Tensor`
 #[derive(Debug)]
 pub enum ViewTensorRef {}
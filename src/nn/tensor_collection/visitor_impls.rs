@@ -27,7 +27,7 @@ impl<'a, T: TensorCollection<E, D>, E: Dtype, D: Device<E>, F: TensorVisitor<E,
     where
         GetRef: FnMut(&T) -> &Field,
         GetMut: FnMut(&mut T) -> &mut Field,
-        Field: TensorCollection<E, D>,
+        Field: TensorCollection<E, D> + 'static,
     {
         let mut walker = RecursiveWalker {
             m: F::Viewer::view_field(&mut self.m, name, &mut get_refs, &mut get_muts),
@@ -53,6 +53,24 @@ impl<'a, T: TensorCollection<E, D>, E: Dtype, D: Device<E>, F: TensorVisitor<E,
         )
     }
 
+    fn visit_scalar<N: Clone, GetRef, GetMut>(
+        &mut self,
+        _name: &str,
+        mut get_refs: GetRef,
+        _get_muts: GetMut,
+        default: N,
+    ) -> Result<Option<N>, Self::Err>
+    where
+        GetRef: FnMut(&T) -> &N,
+        GetMut: FnMut(&mut T) -> &mut N,
+    {
+        Ok(Some(
+            F::Viewer::view_ref(&self.m, &mut get_refs)
+                .cloned()
+                .unwrap_or(default),
+        ))
+    }
+
     fn visit_fields<M: ModuleFields<T, E, D>>(
         &mut self,
         fields: M,
@@ -93,6 +111,13 @@ impl TensorViewer for ViewTensorRef {
     {
         get_ref(module)
     }
+
+    fn view_ref<'a, Mod, Field, GetRef>(module: &'a &Mod, get_ref: &mut GetRef) -> Option<&'a Field>
+    where
+        GetRef: FnMut(&Mod) -> &Field,
+    {
+        Some(get_ref(module))
+    }
 }
 
 impl TensorViewer for ViewTensorMut {
@@ -110,6 +135,16 @@ impl TensorViewer for ViewTensorMut {
     {
         get_mut(module)
     }
+
+    fn view_ref<'a, Mod, Field, GetRef>(
+        module: &'a &mut Mod,
+        get_ref: &mut GetRef,
+    ) -> Option<&'a Field>
+    where
+        GetRef: FnMut(&Mod) -> &Field,
+    {
+        Some(get_ref(module))
+    }
 }
 
 impl TensorViewer for ViewTensorName {
@@ -151,6 +186,16 @@ impl<T: TensorViewer> TensorViewer for Vec<T> {
             .map(|x| T::view_field(x, name, get_ref, get_mut))
             .collect()
     }
+
+    fn view_ref<'a, Mod, Field, GetRef>(
+        module: &'a Self::View<'_, Mod>,
+        get_ref: &mut GetRef,
+    ) -> Option<&'a Field>
+    where
+        GetRef: FnMut(&Mod) -> &Field,
+    {
+        module.first().and_then(|x| T::view_ref(x, get_ref))
+    }
 }
 
 impl<T: TensorViewer> TensorViewer for Option<T> {
@@ -170,6 +215,16 @@ impl<T: TensorViewer> TensorViewer for Option<T> {
             .as_mut()
             .map(|x| T::view_field(x, name, get_ref, get_mut))
     }
+
+    fn view_ref<'a, Mod, Field, GetRef>(
+        module: &'a Self::View<'_, Mod>,
+        get_ref: &mut GetRef,
+    ) -> Option<&'a Field>
+    where
+        GetRef: FnMut(&Mod) -> &Field,
+    {
+        module.as_ref().and_then(|x| T::view_ref(x, get_ref))
+    }
 }
 
 impl<'a, F1, F2, E: Dtype, D: Device<E>, Mod: TensorCollection<E, D>, Field> ModuleFields<Mod, E, D>
@@ -177,7 +232,7 @@ impl<'a, F1, F2, E: Dtype, D: Device<E>, Mod: TensorCollection<E, D>, Field> Mod
 where
     F1: FnMut(&Mod) -> &Field,
     F2: FnMut(&mut Mod) -> &mut Field,
-    Field: TensorCollection<E, D>,
+    Field: TensorCollection<E, D> + 'static,
 {
     type Options<E2: Dtype, D2: Device<E2>> = Option<Field::To<E2, D2>>;
     type Output<E2: Dtype, D2: Device<E2>> = Field::To<E2, D2>;
@@ -219,6 +274,29 @@ where
     }
 }
 
+impl<'a, F1, F2, N: Clone, E: Dtype, D: Device<E>, Mod: TensorCollection<E, D>>
+    ModuleFields<Mod, E, D> for ScalarField<'a, F1, F2, Mod, N>
+where
+    F1: FnMut(&Mod) -> &N,
+    F2: FnMut(&mut Mod) -> &mut N,
+{
+    type Options<E2: Dtype, D2: Device<E2>> = Option<N>;
+    type Output<E2: Dtype, D2: Device<E2>> = N;
+
+    fn visit_fields<V: ModuleVisitor<Mod, E, D>>(
+        self,
+        visitor: &mut V,
+    ) -> Result<Self::Options<V::E2, V::D2>, V::Err> {
+        visitor.visit_scalar(self.name, self.get_ref, self.get_mut, self.default)
+    }
+
+    fn handle_options<E2: Dtype, D2: Device<E2>>(
+        options: Self::Options<E2, D2>,
+    ) -> Option<Self::Output<E2, D2>> {
+        options
+    }
+}
+
 impl<T: ModuleFields<Mod, E, D>, Mod: TensorCollection<E, D>, E: Dtype, D: Device<E>>
     ModuleFields<Mod, E, D> for Vec<T>
 {
@@ -281,6 +359,16 @@ macro_rules! tuple_impls {
             {
                 ($($name::view_field(&mut module.$idx, name, get_ref, get_mut),)+)
             }
+
+            fn view_ref<'a, Mod, Field, GetRef>(
+                module: &'a Self::View<'_, Mod>,
+                get_ref: &mut GetRef,
+            ) -> Option<&'a Field>
+            where
+                GetRef: FnMut(&Mod) -> &Field,
+            {
+                None$(.or_else(|| $name::view_ref(&module.$idx, get_ref)))+
+            }
         }
 
         impl<$($name: ModuleFields<Mod, E, D>),+, Mod: TensorCollection<E, D>, E: Dtype, D: Device<E>>
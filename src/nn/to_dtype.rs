@@ -25,11 +25,21 @@ impl<E1: Dtype, E2: Dtype, D: Device<E1> + Device<E2> + ToDtypeKernel<E1, E2>> T
     }
 }
 
-/// Something that can be copied to have a different dtype
+/// Something that can be copied to have a different dtype. Every parameter
+/// of the module is converted with [crate::tensor_ops::to_dtype()].
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// use dfdx::nn::ToDtype;
+/// type Model = (Linear<3, 5>, ReLU, Linear<5, 2>);
+/// let model = dev.build_module::<Model, f32>();
+/// let model_f64: <Model as BuildOnDevice<Cpu, f64>>::Built = model.to_dtype();
+/// ```
 pub trait ToDtype<E1: Dtype, E2: Dtype, D: Device<E1> + Device<E2> + ToDtypeKernel<E1, E2>>:
     TensorCollection<E1, D>
 {
-    /// Fallible version of [ToDevice::to_dtype]
+    /// Fallible version of [ToDtype::to_dtype]
     fn try_to_dtype(&self) -> Result<Self::To<E2, D>, D::Err> {
         let out = Self::iter_tensors(&mut RecursiveWalker {
             m: self,
@@ -52,3 +62,47 @@ where
     T: TensorCollection<E1, D>,
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, shapes::*, tensor::*, tests::*};
+
+    #[test]
+    fn test_linear_f32_to_f64() {
+        let dev: TestDevice = Default::default();
+        let model = dev.build_module::<Linear<3, 2>, f32>();
+        let model_f64: crate::nn::modules::Linear<3, 2, f64, _> = model.to_dtype();
+        let weight = model.weight.array();
+        let weight_f64 = model_f64.weight.array();
+        for i in 0..2 {
+            for j in 0..3 {
+                assert_eq!(weight_f64[i][j], weight[i][j] as f64);
+            }
+        }
+        let bias = model.bias.array();
+        let bias_f64 = model_f64.bias.array();
+        for i in 0..2 {
+            assert_eq!(bias_f64[i], bias[i] as f64);
+        }
+
+        let x: Tensor<Rank1<3>, f32, _> = dev.sample_normal();
+        let y = model.forward(x.clone()).array();
+        let y_f64 = model_f64.forward(x.to_dtype::<f64>()).array();
+        for i in 0..2 {
+            assert!((y_f64[i] - y[i] as f64).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_to_dtype_roundtrip() {
+        let dev: TestDevice = Default::default();
+        type Model = (Linear<3, 5>, ReLU, LayerNorm1D<5>);
+        let model = dev.build_module::<Model, f32>();
+        let model_f64 = ToDtype::<f32, f64, _>::to_dtype(&model);
+        let model_f32 = ToDtype::<f64, f32, _>::to_dtype(&model_f64);
+        assert_eq!(model.0.weight.array(), model_f32.0.weight.array());
+        assert_eq!(model.2.gamma.array(), model_f32.2.gamma.array());
+        assert_eq!(model.2.beta.array(), model_f32.2.beta.array());
+    }
+}
@@ -0,0 +1,178 @@
+use num_traits::Float;
+
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{Module, MultiHeadAttention};
+
+/// The keys & values of all the tokens seen so far by a [MultiHeadAttention], for
+/// autoregressive inference with [MultiHeadAttention::forward_cached()].
+///
+/// The keys & values are stored after they are projected by [MultiHeadAttention::w_k]
+/// and [MultiHeadAttention::w_v], so each token is only projected once.
+#[derive(Debug, Clone)]
+pub struct KVCache<const K: usize, const V: usize, E: Dtype, D: DeviceStorage> {
+    pub keys: Option<Tensor<(usize, Const<K>), E, D>>,
+    pub values: Option<Tensor<(usize, Const<V>), E, D>>,
+}
+
+impl<const K: usize, const V: usize, E: Dtype, D: DeviceStorage> Default for KVCache<K, V, E, D> {
+    fn default() -> Self {
+        Self {
+            keys: None,
+            values: None,
+        }
+    }
+}
+
+impl<const K: usize, const V: usize, E: Dtype, D: Device<E>> KVCache<K, V, E, D> {
+    /// The number of tokens in the cache.
+    pub fn len(&self) -> usize {
+        self.keys.as_ref().map_or(0, |k| k.shape.0)
+    }
+
+    /// Whether there are no tokens in the cache.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all the tokens, e.g. to start a new sequence.
+    pub fn clear(&mut self) {
+        self.keys = None;
+        self.values = None;
+    }
+
+    /// Appends the keys & values of new tokens, returning all the cached keys & values.
+    #[allow(clippy::type_complexity)]
+    fn try_append<S: Dim>(
+        &mut self,
+        k: Tensor<(S, Const<K>), E, D>,
+        v: Tensor<(S, Const<V>), E, D>,
+    ) -> Result<
+        (
+            Tensor<(usize, Const<K>), E, D>,
+            Tensor<(usize, Const<V>), E, D>,
+        ),
+        D::Err,
+    > {
+        let s = k.shape.0.size();
+        let k = k.try_reshape_like(&(s, Const)).unwrap()?;
+        let v = v.try_reshape_like(&(s, Const)).unwrap()?;
+        let (k, v) = match (self.keys.take(), self.values.take()) {
+            (Some(keys), Some(values)) => (keys.try_concat(k)?, values.try_concat(v)?),
+            _ => (k, v),
+        };
+        self.keys = Some(k.clone());
+        self.values = Some(v.clone());
+        Ok((k, v))
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D>
+    MultiHeadAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+{
+    /// Self attention for autoregressive inference. The keys & values of `x` are appended
+    /// to `cache`, and then `x` attends over every token in the cache, without recomputing
+    /// the keys & values of the previous tokens.
+    ///
+    /// Like the rest of [MultiHeadAttention], no causal mask is applied within `x`, so
+    /// usually `x` is a single new token.
+    ///
+    /// ```rust
+    /// # use dfdx::{prelude::*, nn::modules::KVCache};
+    /// # let dev: Cpu = Default::default();
+    /// let mha = dev.build_module::<MultiHeadAttention<8, 2>, f32>();
+    /// let mut cache = KVCache::default();
+    /// for _ in 0..3 {
+    ///     let token: Tensor<Rank2<1, 8>, f32, _> = dev.sample_normal();
+    ///     let _ = mha.forward_cached(token, &mut cache);
+    /// }
+    /// assert_eq!(cache.len(), 3);
+    /// ```
+    pub fn forward_cached<S1: Dim>(
+        &self,
+        x: Tensor<(S1, Const<M>), E, D>,
+        cache: &mut KVCache<K, V, E, D>,
+    ) -> Tensor<(S1, Const<M>), E, D> {
+        self.try_forward_cached(x, cache).unwrap()
+    }
+
+    /// See [MultiHeadAttention::forward_cached]
+    pub fn try_forward_cached<S1: Dim>(
+        &self,
+        x: Tensor<(S1, Const<M>), E, D>,
+        cache: &mut KVCache<K, V, E, D>,
+    ) -> Result<Tensor<(S1, Const<M>), E, D>, D::Err> {
+        let s1 = x.shape.0;
+        let k = self.w_k.try_forward(x.clone())?;
+        let v = self.w_v.try_forward(x.clone())?;
+        let (k, v) = cache.try_append(k, v)?;
+        let s2 = cache.len();
+
+        let v = v.try_reshape_like(&(s2, H, V / H)).unwrap()?;
+        let v = v.try_permute::<_, Axes3<1, 0, 2>>()?;
+
+        let k = k.try_reshape_like(&(s2, H, K / H)).unwrap()?;
+        let k = k.try_permute::<_, Axes3<1, 2, 0>>()?;
+
+        let q = self.w_q.try_forward(x)?;
+        let q = q.try_reshape_like(&(s1, H, K / H)).unwrap()?;
+        let q = q.try_permute::<_, Axes3<1, 0, 2>>()?;
+
+        // Get weights
+        let scalar: E = E::ONE / E::from_usize(K / H).unwrap().sqrt();
+        let weights = q.try_matmul(k)?.try_mul(scalar)?;
+        let weights = weights.try_softmax::<Axis<2>>()?;
+
+        // Get new tokens
+        let tokens = weights.try_matmul(v)?;
+        let tokens = tokens.try_permute::<_, Axes3<1, 0, 2>>()?;
+        let tokens = tokens.try_reshape_like(&(s1, Const::<V>)).unwrap()?;
+
+        self.w_o.try_forward(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders, DeviceBuildExt},
+        tests::*,
+    };
+
+    #[test]
+    fn test_kv_cache_matches_full_forward() {
+        let dev: TestDevice = Default::default();
+        let mha = dev.build_module::<builders::MultiHeadAttention<8, 2>, TestDtype>();
+        let x: Tensor<Rank2<4, 8>, TestDtype, _> = dev.sample_normal();
+        let x = x.realize::<(usize, Const<8>)>().unwrap();
+
+        let mut cache = KVCache::default();
+        let mut last = None;
+        for t in 0..4 {
+            let token = x.clone().slice((t..t + 1, ..));
+            let y = mha.forward_cached(token.clone(), &mut cache);
+            assert_eq!(cache.len(), t + 1);
+            let y = y.realize::<Rank2<1, 8>>().unwrap();
+
+            // the same as attending over all of the tokens so far
+            let prefix = x.clone().slice((..t + 1, ..));
+            let expected = mha.forward((token, prefix.clone(), prefix));
+            assert_close(
+                &y.array(),
+                &expected.realize::<Rank2<1, 8>>().unwrap().array(),
+            );
+            last = Some(y);
+        }
+
+        // the last token attends over the whole sequence, like the full forward
+        let full = mha.forward(x).slice((3..4, ..));
+        assert_close(
+            &last.unwrap().array(),
+            &full.realize::<Rank2<1, 8>>().unwrap().array(),
+        );
+    }
+}
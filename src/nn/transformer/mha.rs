@@ -19,6 +19,15 @@ pub mod builder {
             "NUM_HEADS must divide K_DIM & V_DIM evenly! If you haven't specified K_DIM & V_DIM, they default to EMBED_DIM, which means NUM_HEADS must divide EMBED_DIM evenly."
         );
     }
+
+    #[derive(Debug, Clone)]
+    pub struct MultiHeadAttentionDropout<
+        const EMBED_DIM: usize,
+        const NUM_HEADS: usize,
+        const DROPOUT_ONE_IN: usize,
+        const K_DIM: usize = EMBED_DIM,
+        const V_DIM: usize = EMBED_DIM,
+    >;
 }
 
 impl<const M: usize, const H: usize, const K: usize, const V: usize, E: Dtype, D: Device<E>>
@@ -34,6 +43,26 @@ where
     }
 }
 
+impl<
+        const M: usize,
+        const H: usize,
+        const N: usize,
+        const K: usize,
+        const V: usize,
+        E: Dtype,
+        D: Device<E>,
+    > BuildOnDevice<D, E> for builder::MultiHeadAttentionDropout<M, H, N, K, V>
+where
+    MultiHeadAttentionDropout<M, H, N, K, V, E, D>: BuildModule<D, E>,
+{
+    type Built = MultiHeadAttentionDropout<M, H, N, K, V, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        #[allow(clippy::let_unit_value)]
+        let _ = builder::MultiHeadAttention::<M, H, K, V>::TYPE_CHECK;
+        Self::Built::try_build(device)
+    }
+}
+
 /// A multi-head attention layer.
 ///
 /// Generics:
@@ -48,6 +77,8 @@ where
 /// - `MultiHeadAttention<8, 2>` is an attention layer with 2 heads and 8 token, key and value dims.
 /// - `MultiHeadAttention<8, 2, 6, 4>` is an attention layer with the key and value dimension different
 ///   than the embed dimension
+///
+/// See [MultiHeadAttentionDropout] for a version that applies dropout to the attention weights.
 #[derive(Debug, Clone)]
 pub struct MultiHeadAttention<
     const EMBED_DIM: usize,
@@ -85,31 +116,22 @@ where
     }
 }
 
-impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, S1, S2, T>
-    Module<(
-        Tensor<(S1, Const<M>), E, D, T>,
-        Tensor<(S2, Const<M>), E, D>,
-        Tensor<(S2, Const<M>), E, D>,
-    )> for MultiHeadAttention<M, H, K, V, E, D>
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D>
+    MultiHeadAttention<M, H, K, V, E, D>
 where
     E: Dtype + Float,
     D: Device<E>,
-    S1: Dim,
-    S2: Dim,
-    T: Tape<E, D>,
 {
-    type Output = Tensor<(S1, Const<M>), E, D, T>;
-    type Error = D::Err;
-
-    /// Encoder-Decoder style self attention where one set of tensors is used for values and keys, and another is used for queries
-    fn try_forward(
+    /// Unbatched attention. If `attn_dropout` is `Some(p)`, the attention weights
+    /// are passed through [dropout()] with probability `p` before multiplying the values.
+    #[allow(clippy::type_complexity)]
+    fn try_attend<S1: Dim, S2: Dim, T: Tape<E, D>>(
         &self,
-        (q, k, v): (
-            Tensor<(S1, Const<M>), E, D, T>,
-            Tensor<(S2, Const<M>), E, D>,
-            Tensor<(S2, Const<M>), E, D>,
-        ),
-    ) -> Result<Self::Output, D::Err> {
+        q: Tensor<(S1, Const<M>), E, D, T>,
+        k: Tensor<(S2, Const<M>), E, D>,
+        v: Tensor<(S2, Const<M>), E, D>,
+        attn_dropout: Option<E>,
+    ) -> Result<Tensor<(S1, Const<M>), E, D, T>, D::Err> {
         assert_eq!(k.shape.0, v.shape.0);
         let s1 = q.shape.0;
         let s2 = k.shape.0;
@@ -129,6 +151,10 @@ where
         let scalar: E = E::ONE / E::from_usize(K / H).unwrap().sqrt();
         let weights = q.try_matmul(k)?.try_mul(scalar)?;
         let weights = weights.try_softmax::<Axis<2>>()?;
+        let weights = match attn_dropout {
+            Some(p) => weights.try_dropout(p)?,
+            None => weights,
+        };
 
         // Get new tokens
         let tokens = weights.try_matmul(v)?;
@@ -137,34 +163,16 @@ where
 
         self.w_o.try_forward(tokens)
     }
-}
 
-impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, B, S1, S2, T>
-    Module<(
-        Tensor<(B, S1, Const<M>), E, D, T>,
-        Tensor<(B, S2, Const<M>), E, D>,
-        Tensor<(B, S2, Const<M>), E, D>,
-    )> for MultiHeadAttention<M, H, K, V, E, D>
-where
-    E: Dtype + Float,
-    D: Device<E>,
-    B: Dim,
-    S1: Dim,
-    S2: Dim,
-    T: Tape<E, D>,
-{
-    type Output = Tensor<(B, S1, Const<M>), E, D, T>;
-    type Error = D::Err;
-
-    /// Batched Encoder-Decoder style self attention where one set of tensors is used for values and keys, and another is used for queries
-    fn try_forward(
+    /// Batched version of [Self::try_attend].
+    #[allow(clippy::type_complexity)]
+    fn try_attend_batched<B: Dim, S1: Dim, S2: Dim, T: Tape<E, D>>(
         &self,
-        (q, k, v): (
-            Tensor<(B, S1, Const<M>), E, D, T>,
-            Tensor<(B, S2, Const<M>), E, D>,
-            Tensor<(B, S2, Const<M>), E, D>,
-        ),
-    ) -> Result<Self::Output, D::Err> {
+        q: Tensor<(B, S1, Const<M>), E, D, T>,
+        k: Tensor<(B, S2, Const<M>), E, D>,
+        v: Tensor<(B, S2, Const<M>), E, D>,
+        attn_dropout: Option<E>,
+    ) -> Result<Tensor<(B, S1, Const<M>), E, D, T>, D::Err> {
         assert_eq!(q.shape.0, k.shape.0);
         assert_eq!(q.shape.0, v.shape.0);
         assert_eq!(k.shape.1, v.shape.1);
@@ -189,6 +197,10 @@ where
         let scalar: E = E::ONE / E::from_usize(K / H).unwrap().sqrt();
         let weights = q.try_matmul(k)?.try_mul(scalar)?;
         let weights = weights.try_softmax::<Axis<3>>()?;
+        let weights = match attn_dropout {
+            Some(p) => weights.try_dropout(p)?,
+            None => weights,
+        };
 
         // Get new tokens
         let tokens = weights.try_matmul(v)?;
@@ -199,6 +211,65 @@ where
     }
 }
 
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, S1, S2, T>
+    Module<(
+        Tensor<(S1, Const<M>), E, D, T>,
+        Tensor<(S2, Const<M>), E, D>,
+        Tensor<(S2, Const<M>), E, D>,
+    )> for MultiHeadAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    S1: Dim,
+    S2: Dim,
+    T: Tape<E, D>,
+{
+    type Output = Tensor<(S1, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    /// Encoder-Decoder style self attention where one set of tensors is used for values and keys, and another is used for queries
+    fn try_forward(
+        &self,
+        (q, k, v): (
+            Tensor<(S1, Const<M>), E, D, T>,
+            Tensor<(S2, Const<M>), E, D>,
+            Tensor<(S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        self.try_attend(q, k, v, None)
+    }
+}
+
+impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, B, S1, S2, T>
+    Module<(
+        Tensor<(B, S1, Const<M>), E, D, T>,
+        Tensor<(B, S2, Const<M>), E, D>,
+        Tensor<(B, S2, Const<M>), E, D>,
+    )> for MultiHeadAttention<M, H, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    B: Dim,
+    S1: Dim,
+    S2: Dim,
+    T: Tape<E, D>,
+{
+    type Output = Tensor<(B, S1, Const<M>), E, D, T>;
+    type Error = D::Err;
+
+    /// Batched Encoder-Decoder style self attention where one set of tensors is used for values and keys, and another is used for queries
+    fn try_forward(
+        &self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, T>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        self.try_attend_batched(q, k, v, None)
+    }
+}
+
 impl<const M: usize, const H: usize, const K: usize, const V: usize, E, D, Src> Module<Src>
     for MultiHeadAttention<M, H, K, V, E, D>
 where
@@ -221,6 +292,170 @@ impl<const M: usize, const H: usize, const K: usize, const V: usize, E: Dtype, D
 {
 }
 
+/// A [MultiHeadAttention] layer that applies [dropout()] with probability `1.0 / DROPOUT_ONE_IN`
+/// to the attention weights, after the softmax and before they are multiplied with the values.
+///
+/// Like [DropoutOneIn], dropout only happens in [ModuleMut], which requires the queries to have
+/// an [OwnedTape]. [Module] is plain attention. The kept weights are scaled by `1 / (1 - p)` and
+/// not renormalized, and the mask comes from the device's rng, so it is reproducible with a
+/// seeded device.
+///
+/// Generics:
+/// - `EMBED_DIM`, `NUM_HEADS`, `K_DIM` and `V_DIM`: see [MultiHeadAttention].
+/// - `DROPOUT_ONE_IN`: p is set as `1.0 / DROPOUT_ONE_IN`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = MultiHeadAttentionDropout<8, 2, 10>;
+/// let mut mha = dev.build_module::<Model, f32>();
+/// let x: Tensor<Rank2<3, 8>, f32, _> = dev.sample_normal();
+/// let y = mha.forward_mut(x.leaky_trace());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiHeadAttentionDropout<
+    const EMBED_DIM: usize,
+    const NUM_HEADS: usize,
+    const DROPOUT_ONE_IN: usize,
+    const K_DIM: usize,
+    const V_DIM: usize,
+    E: Dtype,
+    D: DeviceStorage,
+> {
+    pub mha: MultiHeadAttention<EMBED_DIM, NUM_HEADS, K_DIM, V_DIM, E, D>,
+}
+
+impl<const M: usize, const H: usize, const N: usize, const K: usize, const V: usize, E, D>
+    TensorCollection<E, D> for MultiHeadAttentionDropout<M, H, N, K, V, E, D>
+where
+    E: Dtype + Float + SampleUniform,
+    D: Device<E>,
+{
+    type To<E2: Dtype, D2: Device<E2>> = MultiHeadAttentionDropout<M, H, N, K, V, E2, D2>;
+
+    fn iter_tensors<Vi: ModuleVisitor<Self, E, D>>(
+        visitor: &mut Vi,
+    ) -> Result<Option<Self::To<Vi::E2, Vi::D2>>, Vi::Err> {
+        visitor.visit_fields(Self::module("mha", |s| &s.mha, |s| &mut s.mha), |mha| {
+            MultiHeadAttentionDropout { mha }
+        })
+    }
+}
+
+impl<const M: usize, const H: usize, const N: usize, const K: usize, const V: usize, E, D, X>
+    Module<X> for MultiHeadAttentionDropout<M, H, N, K, V, E, D>
+where
+    E: Dtype,
+    D: Device<E>,
+    MultiHeadAttention<M, H, K, V, E, D>: Module<X>,
+{
+    type Output = <MultiHeadAttention<M, H, K, V, E, D> as Module<X>>::Output;
+    type Error = <MultiHeadAttention<M, H, K, V, E, D> as Module<X>>::Error;
+
+    /// Attention without dropout.
+    fn try_forward(&self, x: X) -> Result<Self::Output, Self::Error> {
+        self.mha.try_forward(x)
+    }
+}
+
+impl<
+        const M: usize,
+        const H: usize,
+        const N: usize,
+        const K: usize,
+        const V: usize,
+        E,
+        D,
+        S1,
+        S2,
+    >
+    ModuleMut<(
+        Tensor<(S1, Const<M>), E, D, OwnedTape<E, D>>,
+        Tensor<(S2, Const<M>), E, D>,
+        Tensor<(S2, Const<M>), E, D>,
+    )> for MultiHeadAttentionDropout<M, H, N, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    S1: Dim,
+    S2: Dim,
+{
+    type Output = Tensor<(S1, Const<M>), E, D, OwnedTape<E, D>>;
+    type Error = D::Err;
+
+    /// Attention with dropout `p=1/N` on the attention weights.
+    fn try_forward_mut(
+        &mut self,
+        (q, k, v): (
+            Tensor<(S1, Const<M>), E, D, OwnedTape<E, D>>,
+            Tensor<(S2, Const<M>), E, D>,
+            Tensor<(S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        let p = E::ONE / E::from_usize(N).unwrap();
+        self.mha.try_attend(q, k, v, Some(p))
+    }
+}
+
+impl<
+        const M: usize,
+        const H: usize,
+        const N: usize,
+        const K: usize,
+        const V: usize,
+        E,
+        D,
+        B,
+        S1,
+        S2,
+    >
+    ModuleMut<(
+        Tensor<(B, S1, Const<M>), E, D, OwnedTape<E, D>>,
+        Tensor<(B, S2, Const<M>), E, D>,
+        Tensor<(B, S2, Const<M>), E, D>,
+    )> for MultiHeadAttentionDropout<M, H, N, K, V, E, D>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    B: Dim,
+    S1: Dim,
+    S2: Dim,
+{
+    type Output = Tensor<(B, S1, Const<M>), E, D, OwnedTape<E, D>>;
+    type Error = D::Err;
+
+    /// Batched attention with dropout `p=1/N` on the attention weights.
+    fn try_forward_mut(
+        &mut self,
+        (q, k, v): (
+            Tensor<(B, S1, Const<M>), E, D, OwnedTape<E, D>>,
+            Tensor<(B, S2, Const<M>), E, D>,
+            Tensor<(B, S2, Const<M>), E, D>,
+        ),
+    ) -> Result<Self::Output, D::Err> {
+        let p = E::ONE / E::from_usize(N).unwrap();
+        self.mha.try_attend_batched(q, k, v, Some(p))
+    }
+}
+
+impl<const M: usize, const H: usize, const N: usize, const K: usize, const V: usize, E, D, Src>
+    ModuleMut<Src> for MultiHeadAttentionDropout<M, H, N, K, V, E, D>
+where
+    E: Dtype,
+    D: Device<E>,
+    Src: SplitTape,
+    Self: ModuleMut<(Src, Src::NoTape, Src::NoTape), Output = Src, Error = D::Err>,
+{
+    type Output = Src;
+    type Error = D::Err;
+
+    fn try_forward_mut(&mut self, src: Src) -> Result<Self::Output, D::Err> {
+        let (src, tape) = src.split_tape();
+        self.try_forward_mut((src.clone().put_tape(tape), src.clone(), src))
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::excessive_precision)]
 mod tests {
@@ -335,4 +570,66 @@ mod tests {
         let mut opt = Sgd::new(&mha, Default::default());
         opt.update(&mut mha, &g).expect("");
     }
+
+    #[test]
+    fn test_mha_attn_dropout() {
+        const M: usize = 8;
+        const H: usize = 2;
+
+        // two devices with the same seed produce the same parameters, inputs and dropout mask
+        let dev = TestDevice::seed_from_u64(3);
+        let dev2 = TestDevice::seed_from_u64(3);
+        type Model = builder::MultiHeadAttentionDropout<M, H, 2>;
+        let mut mha = dev.build_module::<Model, TestDtype>();
+        let mha2 = dev2.build_module::<Model, TestDtype>().mha;
+        let q: Tensor<Rank2<3, M>, TestDtype, _> = dev.sample_normal();
+        let kv: Tensor<Rank2<4, M>, TestDtype, _> = dev.sample_normal();
+        let q2: Tensor<Rank2<3, M>, TestDtype, _> = dev2.sample_normal();
+        let kv2: Tensor<Rank2<4, M>, TestDtype, _> = dev2.sample_normal();
+
+        // no dropout in Module::forward, even with a tape
+        let expected = mha.mha.forward((q.clone(), kv.clone(), kv.clone())).array();
+        let y = mha.forward((q.leaky_trace(), kv.clone(), kv.clone()));
+        assert_eq!(y.array(), expected);
+
+        let y = mha.forward_mut((q.leaky_trace(), kv.clone(), kv.clone()));
+
+        // p = 0 leaves attention unchanged
+        let y0 = mha
+            .mha
+            .try_attend(q.leaky_trace(), kv.clone(), kv, Some(0.0));
+        assert_eq!(y0.unwrap().array(), expected);
+
+        // the same computation by hand, with the same mask
+        let v = mha2.w_v.forward(kv2.clone()).reshape::<Rank3<4, H, 4>>();
+        let v = v.permute::<_, Axes3<1, 0, 2>>();
+        let k = mha2.w_k.forward(kv2).reshape::<Rank3<4, H, 4>>();
+        let k = k.permute::<_, Axes3<1, 2, 0>>();
+        let q = mha2.w_q.forward(q2).reshape::<Rank3<3, H, 4>>();
+        let q = q.permute::<_, Axes3<1, 0, 2>>();
+        let weights = (q.matmul(k) / 2.0).softmax::<Axis<2>>();
+        let dropped = weights.clone().leaky_trace().dropout(0.5);
+        let tokens = dropped.retaped::<NoneTape>().matmul(v);
+        let tokens = tokens
+            .permute::<_, Axes3<1, 0, 2>>()
+            .reshape::<Rank2<3, M>>();
+        assert_close(&y.array(), &mha2.w_o.forward(tokens).array());
+
+        // dropped weights are zeroed, and the rest are scaled by 1 / (1 - p) without renormalizing
+        let (weights, dropped) = (weights.array(), dropped.array());
+        let mut num_dropped = 0;
+        for (w, d) in weights
+            .iter()
+            .flatten()
+            .flatten()
+            .zip(dropped.iter().flatten().flatten())
+        {
+            if *d == 0.0 {
+                num_dropped += 1;
+            } else {
+                assert!((d - 2.0 * w).abs() < 1e-6);
+            }
+        }
+        assert!(num_dropped > 0);
+    }
 }
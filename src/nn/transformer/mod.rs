@@ -1,9 +1,11 @@
 mod decoder;
 mod encoder;
+mod kv_cache;
 mod mha;
 
 pub use decoder::*;
 pub use encoder::*;
+pub use kv_cache::*;
 pub use mha::*;
 
 use num_traits::Float;
@@ -25,7 +27,7 @@ pub mod builder {
 
     pub use super::decoder::builder::{TransformerDecoder, TransformerDecoderBlock};
     pub use super::encoder::builder::{TransformerEncoder, TransformerEncoderBlock};
-    pub use super::mha::builder::MultiHeadAttention;
+    pub use super::mha::builder::{MultiHeadAttention, MultiHeadAttentionDropout};
 }
 
 impl<const M: usize, const H: usize, const A: usize, const B: usize, const F: usize, E, D>
@@ -87,7 +87,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{shapes::*, tensor::*, tests::*};
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
 
     #[test]
     fn test_upscale2d() {
@@ -110,4 +110,29 @@ mod tests {
         let _: Tensor<Rank3<3, 12, 12>, _, _> =
             Upscale2DBy::<3, 3, Bilinear>::default().forward(x.clone());
     }
+
+    #[test]
+    fn test_upscale2d_bicubic() {
+        use crate::prelude::Bicubic;
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<3, 4, 4>, TestDtype, _> = dev.sample_normal();
+        let y: Tensor<Rank3<3, 9, 9>, _, _> =
+            Upscale2D::<9, 9, Bicubic>::default().forward(x.clone());
+        let batched: Tensor<Rank4<2, 3, 9, 9>, _, _> =
+            Upscale2D::<9, 9, Bicubic>::default().forward(x.broadcast::<Rank4<2, 3, 4, 4>, _>());
+        assert_close(&batched.array()[1], &y.array());
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn test_upscale2dby_bicubic() {
+        use crate::prelude::Bicubic;
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<3, 4, 4>, TestDtype, _> = dev.sample_normal();
+        let y: Tensor<Rank3<3, 8, 8>, _, _> =
+            Upscale2DBy::<2, 2, Bicubic>::default().forward(x.leaky_trace());
+        assert_close(&y.array(), &x.clone().upscale_2d::<8, 8, Bicubic>().array());
+        let _: Tensor<Rank4<2, 3, 8, 8>, _, _> =
+            Upscale2DBy::<2, 2, Bicubic>::default().forward(x.broadcast::<Rank4<2, 3, 4, 4>, _>());
+    }
 }
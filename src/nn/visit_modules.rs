@@ -0,0 +1,234 @@
+use super::tensor_collection::*;
+
+use crate::{shapes::*, tensor::*, tensor_ops::Device};
+
+use std::string::String;
+
+/// Describes a sub-module encountered by [VisitModules::visit_modules_mut].
+pub trait ModuleDescriptor {
+    /// The name of the module relative to the root module, e.g. `"1.0"`.
+    fn name(&self) -> &str;
+    /// The rust type name of the module, e.g. `"dfdx::nn::modules::ReLU"`.
+    fn type_name(&self) -> &'static str;
+    /// The module itself, which can be changed after downcasting it to its type
+    /// with [std::any::Any::downcast_mut].
+    fn module_mut(&mut self) -> &mut dyn std::any::Any;
+}
+
+struct Descriptor<'a, M> {
+    name: &'a str,
+    module: &'a mut M,
+}
+
+impl<'a, M: 'static> ModuleDescriptor for Descriptor<'a, M> {
+    fn name(&self) -> &str {
+        self.name
+    }
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<M>()
+    }
+    fn module_mut(&mut self) -> &mut dyn std::any::Any {
+        self.module
+    }
+}
+
+struct ModuleWalker<'a, M, F> {
+    m: &'a mut M,
+    name: String,
+    f: &'a mut F,
+}
+
+impl<'a, T, E, D, F> ModuleVisitor<T, E, D> for ModuleWalker<'a, T, F>
+where
+    T: TensorCollection<E, D>,
+    E: Dtype,
+    D: Device<E>,
+    F: FnMut(&mut dyn ModuleDescriptor),
+{
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit_module<Field, GetRef, GetMut>(
+        &mut self,
+        name: &str,
+        _get_refs: GetRef,
+        mut get_muts: GetMut,
+    ) -> Result<Option<Field::To<E, D>>, Self::Err>
+    where
+        GetRef: FnMut(&T) -> &Field,
+        GetMut: FnMut(&mut T) -> &mut Field,
+        Field: TensorCollection<E, D> + 'static,
+    {
+        let name = if self.name.is_empty() {
+            name.into()
+        } else {
+            format!("{}.{name}", self.name)
+        };
+        (self.f)(&mut Descriptor {
+            name: &name,
+            module: get_muts(self.m),
+        });
+        Field::iter_tensors(&mut ModuleWalker {
+            m: get_muts(self.m),
+            name,
+            f: &mut *self.f,
+        })?;
+        Ok(None)
+    }
+
+    fn visit_tensor<S: Shape, GetRef, GetMut>(
+        &mut self,
+        _name: &str,
+        _get_refs: GetRef,
+        _get_muts: GetMut,
+        _opts: TensorOptions<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err>
+    where
+        GetRef: FnMut(&T) -> &Tensor<S, E, D>,
+        GetMut: FnMut(&mut T) -> &mut Tensor<S, E, D>,
+    {
+        Ok(None)
+    }
+
+    fn visit_fields<M: ModuleFields<T, E, D>>(
+        &mut self,
+        fields: M,
+        _builder: impl FnOnce(M::Output<E, D>) -> T::To<E, D>,
+    ) -> Result<Option<T::To<E, D>>, Self::Err> {
+        fields.visit_fields(self)?;
+        Ok(None)
+    }
+}
+
+/// Recursively visits every sub-module of a model, e.g. every element of a tuple,
+/// every block of a [super::modules::Repeated], or the inner module of a [super::modules::Residual].
+///
+/// The root module itself is not visited. Modules are visited in the order
+/// they are declared, with parents visited before their children.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// use dfdx::nn::VisitModules;
+/// type Model = (Linear<2, 5>, Residual<ReLU>);
+/// let mut model = dev.build_module::<Model, f32>();
+/// let mut names = Vec::new();
+/// model.visit_modules_mut(&mut |m| names.push(m.name().to_string()));
+/// assert_eq!(names, ["0", "1", "1.0"]);
+/// ```
+///
+/// Modules can be changed by downcasting them to their type:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// use dfdx::nn::VisitModules;
+/// let mut model = dev.build_module::<(Linear<2, 5>, Dropout), f32>();
+/// model.visit_modules_mut(&mut |m| {
+///     if let Some(dropout) = m.module_mut().downcast_mut::<Dropout>() {
+///         dropout.p = 0.0;
+///     }
+/// });
+/// assert_eq!(model.1.p, 0.0);
+/// ```
+pub trait VisitModules<E: Dtype, D: Device<E>>: TensorCollection<E, D> {
+    /// Calls `f` on each sub-module of `self`.
+    fn visit_modules_mut<F: FnMut(&mut dyn ModuleDescriptor)>(&mut self, f: &mut F) {
+        self.try_visit_modules_mut(f).unwrap()
+    }
+
+    /// Fallible version of [VisitModules::visit_modules_mut].
+    fn try_visit_modules_mut<F: FnMut(&mut dyn ModuleDescriptor)>(
+        &mut self,
+        f: &mut F,
+    ) -> Result<(), D::Err> {
+        Self::iter_tensors(&mut ModuleWalker {
+            m: self,
+            name: String::new(),
+            f,
+        })?;
+        Ok(())
+    }
+}
+impl<E: Dtype, D: Device<E>, M: TensorCollection<E, D>> VisitModules<E, D> for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, tests::*};
+
+    #[test]
+    fn test_count_tuple_modules() {
+        let dev: TestDevice = Default::default();
+        type Model = (Linear<2, 5>, ReLU, Linear<5, 3>);
+        let mut model = dev.build_module::<Model, TestDtype>();
+        let mut count = 0;
+        model.visit_modules_mut(&mut |_| count += 1);
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_visit_nested_modules() {
+        let dev: TestDevice = Default::default();
+        type Model = (Repeated<(Linear<3, 3>, ReLU), 2>, Residual<Tanh>);
+        let mut model = dev.build_module::<Model, TestDtype>();
+        let mut names = std::vec::Vec::new();
+        let mut type_names = std::vec::Vec::new();
+        model.visit_modules_mut(&mut |m| {
+            names.push(m.name().to_string());
+            type_names.push(m.type_name());
+        });
+        assert_eq!(
+            names,
+            ["0", "0.0", "0.0.0", "0.0.1", "0.1", "0.1.0", "0.1.1", "1", "1.0"]
+        );
+        assert!(type_names[2].contains("Linear"));
+        assert!(type_names[8].contains("Tanh"));
+    }
+
+    #[test]
+    fn test_visit_modules_mut_changes_modules() {
+        let dev: TestDevice = Default::default();
+        type Model = (Linear<2, 2>, Residual<(Dropout, Linear<2, 2>)>, Dropout);
+        let mut model = dev.build_module::<Model, TestDtype>();
+        model.visit_modules_mut(&mut |m| {
+            if let Some(dropout) = m.module_mut().downcast_mut::<Dropout>() {
+                dropout.p = 0.0;
+            }
+            if let Some(linear) = m
+                .module_mut()
+                .downcast_mut::<modules::Linear<2, 2, TestDtype, TestDevice>>()
+            {
+                linear.bias = dev.ones();
+            }
+        });
+        assert_eq!(model.1 .0 .0.p, 0.0);
+        assert_eq!(model.2.p, 0.0);
+        assert_eq!(model.0.bias.array(), [1.0; 2]);
+        assert_eq!(model.1 .0 .1.bias.array(), [1.0; 2]);
+    }
+
+    /// Borrows a model, so it isn't `'static` itself.
+    struct Borrowed<'a, M>(&'a mut M);
+
+    impl<'a, E: Dtype, D: Device<E>, M: TensorCollection<E, D> + 'static> TensorCollection<E, D>
+        for Borrowed<'a, M>
+    {
+        type To<E2: Dtype, D2: Device<E2>> = M::To<E2, D2>;
+
+        fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+            visitor: &mut V,
+        ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+            visitor.visit_fields(Self::module("0", |s| &*s.0, |s| &mut *s.0), |m| m)
+        }
+    }
+
+    #[test]
+    fn test_visit_modules_of_borrowed_model() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<(Linear<2, 2>, Dropout), TestDtype>();
+        let mut names = std::vec::Vec::new();
+        Borrowed(&mut model).visit_modules_mut(&mut |m| names.push(m.name().to_string()));
+        assert_eq!(names, ["0", "0.0", "0.1"]);
+    }
+}
@@ -0,0 +1,102 @@
+use num_traits::Float;
+use std::{collections::BTreeMap, string::String, vec::Vec};
+
+use super::tensor_collection::*;
+
+use crate::{shapes::*, tensor::*, tensor_ops::Device};
+
+struct Histograms {
+    bins: usize,
+    histograms: BTreeMap<String, Vec<usize>>,
+}
+
+impl<E: Dtype + Float, D: Device<E>> TensorVisitor<E, D> for Histograms {
+    type Viewer = (ViewTensorRef, ViewTensorName);
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        _: TensorOptions<S, E, D>,
+        (t, name): (&Tensor<S, E, D>, String),
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        let data: Vec<f64> = t
+            .as_vec()
+            .into_iter()
+            .filter_map(|x| x.to_f64())
+            .filter(|x| !x.is_nan())
+            .collect();
+        let min = data.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let width = (max - min) / self.bins as f64;
+
+        let mut counts = vec![0; self.bins];
+        for x in data {
+            let b = if width > 0.0 {
+                (((x - min) / width) as usize).min(self.bins - 1)
+            } else {
+                0
+            };
+            counts[b] += 1;
+        }
+        self.histograms.insert(name, counts);
+        Ok(None)
+    }
+}
+
+/// Computes a histogram of the values of each named tensor in `module`, e.g. to log
+/// the distribution of the weights during training.
+///
+/// The keys are the paths of the tensors in the model, like `"0.weight"`, and each
+/// histogram has `bins` equal width bins between the minimum and maximum value of its
+/// tensor. NaNs are not counted, and a tensor with a single value has all its values in
+/// the first bin. Uses a `BTreeMap` for no-std support.
+///
+/// **Panics** if `bins` is 0.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, nn::weight_histograms};
+/// # let dev: Cpu = Default::default();
+/// let model = dev.build_module::<(Linear<2, 5>, Linear<5, 1>), f32>();
+/// let histograms = weight_histograms(&model, 4);
+/// assert_eq!(histograms["0.weight"].iter().sum::<usize>(), 10);
+/// assert_eq!(histograms["1.bias"].len(), 4);
+/// ```
+pub fn weight_histograms<E: Dtype + Float, D: Device<E>, M: TensorCollection<E, D>>(
+    module: &M,
+    bins: usize,
+) -> BTreeMap<String, Vec<usize>> {
+    assert!(bins > 0, "expected at least one bin");
+    let mut op = Histograms {
+        bins,
+        histograms: BTreeMap::new(),
+    };
+    M::iter_tensors(&mut RecursiveWalker {
+        m: (module, String::new()),
+        f: &mut op,
+    })
+    .unwrap();
+    op.histograms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, nn::DeviceBuildExt, tests::*};
+
+    #[test]
+    fn test_weight_histograms_linear() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<Linear<2, 3>, TestDtype>();
+        model.weight = dev.tensor([[0.0, 1.0], [2.0, 3.0], [4.0, 8.0]]);
+        model.bias = dev.tensor([1.0, 1.0, 1.0]);
+
+        let histograms = weight_histograms(&model, 4);
+        assert_eq!(histograms.keys().collect::<Vec<_>>(), ["bias", "weight"]);
+        // bins of width 2 over [0, 8], with the max in the last bin
+        assert_eq!(histograms["weight"], [2, 2, 1, 1]);
+        // a constant tensor is all in the first bin
+        assert_eq!(histograms["bias"], [3, 0, 0, 0]);
+    }
+}
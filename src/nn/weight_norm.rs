@@ -0,0 +1,191 @@
+use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+use super::{linear::Linear, *};
+
+use num_traits::Float;
+use rand_distr::Uniform;
+
+pub mod builder {
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct WeightNorm<const I: usize, const O: usize>;
+}
+
+impl<const I: usize, const O: usize, E: Dtype + Float, D: Device<E>> BuildOnDevice<D, E>
+    for builder::WeightNorm<I, O>
+where
+    WeightNorm<I, O, E, D>: BuildModule<D, E>,
+{
+    type Built = WeightNorm<I, O, E, D>;
+    fn try_build_on_device(device: &D) -> Result<Self::Built, <D>::Err> {
+        Self::Built::try_build(device)
+    }
+}
+
+/// A [super::modules::Linear] whose weight is reparameterized into a direction and a magnitude:
+/// `weight[o] = g[o] * v[o] / ||v[o]||`,
+/// as introduced in [Weight Normalization](https://arxiv.org/abs/1602.07868).
+///
+/// Both [Self::v] and [Self::g] are learnable parameters, and the effective weight is reconstructed
+/// every forward, so gradients flow to both.
+///
+/// Initializes [Self::v] and [Self::bias] the same way as [super::modules::Linear], and [Self::g] to `1.0`.
+///
+/// # Generics
+/// - `I` The "input" size of vectors & matrices.
+/// - `O` The "output" size of vectors & matrices.
+///
+/// # Examples
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// type Model = WeightNorm<5, 2>;
+/// let model = dev.build_module::<Model, f32>();
+/// let _: Tensor<Rank1<2>, f32, _> = model.forward(dev.zeros::<Rank1<5>>());
+/// let _: Tensor<Rank2<10, 2>, f32, _> = model.forward(dev.zeros::<Rank2<10, 5>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightNorm<const I: usize, const O: usize, E: Dtype, D: DeviceStorage> {
+    /// Direction of each row of the weight matrix, shape (O, I)
+    pub v: Tensor<Rank2<O, I>, E, D>,
+
+    /// Magnitude of each row of the weight matrix, shape (O, )
+    pub g: Tensor<Rank1<O>, E, D>,
+
+    /// Bias vector, shape (O, )
+    pub bias: Tensor<Rank1<O>, E, D>,
+}
+
+impl<const I: usize, const O: usize, E: Dtype, D: DeviceStorage> NonMutableModule
+    for WeightNorm<I, O, E, D>
+{
+}
+
+impl<const I: usize, const O: usize, E: Dtype, D: Device<E>> WeightNorm<I, O, E, D> {
+    /// The effective weight matrix `g * v / ||v||`, shape (O, I)
+    pub fn weight(&self) -> Tensor<Rank2<O, I>, E, D> {
+        self.try_weight(NoneTape).unwrap()
+    }
+
+    fn try_weight<T: Tape<E, D>>(&self, tape: T) -> Result<Tensor<Rank2<O, I>, E, D, T>, D::Err> {
+        let v = self.v.clone().put_tape(tape);
+        let norm = v
+            .with_empty_tape()
+            .try_square()?
+            .try_sum::<Rank1<O>, _>()?
+            .try_sqrt()?;
+        let scale = self.g.retaped::<T>().try_div(norm)?;
+        v.try_mul(scale.try_broadcast()?)
+    }
+}
+
+impl<const I: usize, const O: usize, E: Dtype + Float, D: Device<E>> TensorCollection<E, D>
+    for WeightNorm<I, O, E, D>
+{
+    type To<E2: Dtype, D2: Device<E2>> = WeightNorm<I, O, E2, D2>;
+
+    fn iter_tensors<V: ModuleVisitor<Self, E, D>>(
+        visitor: &mut V,
+    ) -> Result<Option<Self::To<V::E2, V::D2>>, V::Err> {
+        visitor.visit_fields(
+            (
+                Self::tensor(
+                    "v",
+                    |s| &s.v,
+                    |s| &mut s.v,
+                    TensorOptions::reset_with(|t| {
+                        let b: E = E::ONE / E::from_usize(I).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+                Self::tensor("g", |s| &s.g, |s| &mut s.g, TensorOptions::reset_to_ones()),
+                Self::tensor(
+                    "bias",
+                    |s| &s.bias,
+                    |s| &mut s.bias,
+                    TensorOptions::reset_with(|t| {
+                        let b: E = E::ONE / E::from_usize(I).unwrap().sqrt();
+                        t.try_fill_with_distr(Uniform::new(-b, b))
+                    }),
+                ),
+            ),
+            |(v, g, bias)| WeightNorm { v, g, bias },
+        )
+    }
+}
+
+impl<const I: usize, const O: usize, S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Module<Tensor<S, E, D, T>> for WeightNorm<I, O, E, D>
+where
+    Linear<I, O, E, D>: Module<Tensor<S, E, D, T>, Error = D::Err>,
+{
+    type Output = <Linear<I, O, E, D> as Module<Tensor<S, E, D, T>>>::Output;
+    type Error = D::Err;
+
+    fn try_forward(&self, x: Tensor<S, E, D, T>) -> Result<Self::Output, D::Err> {
+        let (x, tape) = x.split_tape();
+        let (weight, tape) = self.try_weight(tape)?.split_tape();
+        let linear = Linear {
+            weight,
+            bias: self.bias.clone(),
+        };
+        linear.try_forward(x.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_weight_norm_direction_and_magnitude() {
+        let dev: TestDevice = Default::default();
+        let mut m = dev.build_module::<builder::WeightNorm<2, 2>, TestDtype>();
+        m.v = dev.tensor([[3.0, 4.0], [0.0, -2.0]]);
+        assert_close(&m.weight().array(), &[[0.6, 0.8], [0.0, -1.0]]);
+
+        m.g = dev.tensor([10.0, 0.5]);
+        assert_close(&m.weight().array(), &[[6.0, 8.0], [0.0, -0.5]]);
+
+        // scaling v doesn't change the weight
+        m.v = m.v.clone() * 7.0;
+        assert_close(&m.weight().array(), &[[6.0, 8.0], [0.0, -0.5]]);
+    }
+
+    #[test]
+    fn test_weight_norm_forward_and_grads() {
+        let dev: TestDevice = Default::default();
+        let m = dev.build_module::<builder::WeightNorm<3, 2>, TestDtype>();
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+
+        let y = m.forward(x.trace(m.alloc_grads()));
+        let linear = Linear {
+            weight: m.weight(),
+            bias: m.bias.clone(),
+        };
+        assert_close(&y.array(), &linear.forward(x.clone()).array());
+
+        let g = y.sum().backward();
+        assert_eq!(g.get(&m.bias).array(), [4.0; 2]);
+
+        // d(sum)/dg[o] = sum_b (x[b] . v[o]) / ||v[o]||, which is the output without bias
+        let w = m.weight().array();
+        let xs = x.array();
+        let g_g = g.get(&m.g).array();
+        for o in 0..2 {
+            let expected: TestDtype = xs
+                .iter()
+                .map(|xb| (0..3).map(|i| xb[i] * w[o][i]).sum::<TestDtype>())
+                .sum();
+            assert_close(&g_g[o], &expected);
+        }
+
+        // the gradient of v is orthogonal to v, since the length of v doesn't matter
+        let v = m.v.array();
+        let g_v = g.get(&m.v).array();
+        for o in 0..2 {
+            let dot: TestDtype = (0..3).map(|i| v[o][i] * g_v[o][i]).sum();
+            assert!(dot.abs() < 1e-5, "{dot}");
+        }
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
     tensor_ops::Device,
 };
 
-use super::{Optimizer, OptimizerUpdateError, UnusedTensors, WeightDecay};
+use super::{LearningRate, Optimizer, OptimizerUpdateError, UnusedTensors, WeightDecay};
 
 /// Configuration of hyperparameters for [Adam].
 ///
@@ -95,6 +95,16 @@ impl<M, E: Dtype, D: DeviceStorage> Adam<M, E, D> {
     }
 }
 
+impl<M, E: Dtype, D: DeviceStorage> LearningRate<E> for Adam<M, E, D> {
+    fn lr(&self) -> E {
+        self.cfg.lr
+    }
+
+    fn set_lr(&mut self, lr: E) {
+        self.cfg.lr = lr;
+    }
+}
+
 pub trait AdamKernel<E: Dtype>: DeviceStorage {
     fn update(
         &self,
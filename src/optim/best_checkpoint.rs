@@ -0,0 +1,136 @@
+use num_traits::Float;
+
+use super::EarlyStoppingMode;
+
+/// Keeps the model from the epoch with the best metric, like the validation loss.
+///
+/// Each epoch, [BestCheckpoint::step()] checks if the metric improved on the best one so
+/// far. If it did, the model is kept in memory and passed to a save callback, e.g. to
+/// write it to disk. At the end of training, [BestCheckpoint::restore()] replaces a model
+/// with the best one.
+///
+/// # Example Usage
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// let mut model = dev.build_module::<Linear<2, 5>, f32>();
+/// let mut best = BestCheckpoint::new(EarlyStoppingMode::Min);
+/// for val_loss in [1.0, 0.5, 0.7] {
+///     // -- snip training & validation --
+///     best.step(&model, val_loss, |_m| {
+///         // -- snip saving, e.g. with `m.save(...)` --
+///         Ok::<(), ()>(())
+///     })
+///     .unwrap();
+/// }
+/// assert_eq!(best.best_metric(), Some(0.5));
+/// assert!(best.restore(&mut model));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BestCheckpoint<M, E> {
+    /// Whether lower or higher metrics are better.
+    pub mode: EarlyStoppingMode,
+
+    best: Option<(M, E)>,
+}
+
+impl<M, E: Float> BestCheckpoint<M, E> {
+    pub fn new(mode: EarlyStoppingMode) -> Self {
+        Self { mode, best: None }
+    }
+
+    /// The best metric so far, or `None` before the first epoch.
+    pub fn best_metric(&self) -> Option<E> {
+        self.best.as_ref().map(|(_, metric)| *metric)
+    }
+
+    /// The model with the best metric so far, or `None` before the first epoch.
+    pub fn best_model(&self) -> Option<&M> {
+        self.best.as_ref().map(|(model, _)| model)
+    }
+
+    /// Returns the model with the best metric, or `None` if no epochs were recorded.
+    pub fn into_best_model(self) -> Option<M> {
+        self.best.map(|(model, _)| model)
+    }
+}
+
+impl<M: Clone, E: Float> BestCheckpoint<M, E> {
+    /// Records the `metric` of `model` for an epoch. If it improved on the best metric so
+    /// far, keeps a copy of `model`, calls `save` with it, and returns `true`.
+    /// The first metric is always an improvement, and a NaN metric never is.
+    pub fn step<Err, F: FnOnce(&M) -> Result<(), Err>>(
+        &mut self,
+        model: &M,
+        metric: E,
+        save: F,
+    ) -> Result<bool, Err> {
+        if !self
+            .mode
+            .is_improvement(metric, self.best_metric(), E::zero())
+        {
+            return Ok(false);
+        }
+        save(model)?;
+        self.best = Some((model.clone(), metric));
+        Ok(true)
+    }
+
+    /// Replaces `model` with the best model, and returns whether there was one.
+    pub fn restore(&self, model: &mut M) -> bool {
+        match self.best_model() {
+            Some(best) => {
+                *model = best.clone();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{builders::*, DeviceBuildExt},
+        tensor::*,
+        tests::*,
+    };
+
+    #[test]
+    fn test_best_checkpoint_saves_on_improvement() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<Linear<2, 2>, TestDtype>();
+        let mut best = BestCheckpoint::new(EarlyStoppingMode::Min);
+
+        let metrics = [1.0, 0.8, 0.9, 0.5, 0.5, 0.7];
+        let mut saved = std::vec::Vec::new();
+        let mut improved = std::vec::Vec::new();
+        for (epoch, &metric) in metrics.iter().enumerate() {
+            // each epoch has a different bias, so the restored model shows which epoch it was
+            model.bias = dev.tensor([epoch as TestDtype; 2]);
+            let r = best.step(&model, metric, |_| {
+                saved.push(epoch);
+                Ok::<(), ()>(())
+            });
+            improved.push(r.unwrap());
+        }
+        assert_eq!(saved, [0, 1, 3]);
+        assert_eq!(improved, [true, true, false, true, false, false]);
+        assert_eq!(best.best_metric(), Some(0.5));
+
+        assert!(best.restore(&mut model));
+        assert_eq!(model.bias.array(), [3.0; 2]);
+    }
+
+    #[test]
+    fn test_best_checkpoint_failed_save() {
+        let mut best: BestCheckpoint<i32, TestDtype> = BestCheckpoint::new(EarlyStoppingMode::Max);
+        assert_eq!(best.step(&1, 0.5, |_| Ok::<(), ()>(())), Ok(true));
+        // a failed save doesn't change the best
+        assert_eq!(best.step(&2, 0.6, |_| Err(())), Err(()));
+        assert_eq!(best.best_metric(), Some(0.5));
+        assert_eq!(best.into_best_model(), Some(1));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    nn::tensor_collection::*,
+    shapes::{Axis, Dtype, Shape},
+    tensor::{Gradients, Tensor},
+    tensor_ops::{BroadcastTo, Device, MeanTo, ReshapeTo, TrySub},
+};
+
+/// [Gradient Centralization](https://arxiv.org/abs/2004.01461) for all the parameters of `module`
+/// that have a gradient in `gradients`. Call this before [super::Optimizer::update()].
+///
+/// For each parameter with 2 or more dimensions, this subtracts the mean of each
+/// "output row" (the elements that share an index along the first dimension)
+/// from its gradient. Gradients of 1d parameters like biases are left alone.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// let model = Linear::<3, 2>::build_on_device(&dev);
+/// let x: Tensor<Rank1<3>, f32, _> = dev.sample_normal();
+/// let mut grads = model.forward(x.leaky_trace()).square().sum().backward();
+/// centralize_gradients(&mut grads, &model);
+/// let row_means = grads.get(&model.weight).mean::<Rank1<2>, _>();
+/// assert!(row_means.array().iter().all(|m| m.abs() < 1e-6));
+/// ```
+pub fn centralize_gradients<M: TensorCollection<E, D>, E: Dtype, D: Device<E>>(
+    gradients: &mut Gradients<E, D>,
+    module: &M,
+) {
+    try_centralize_gradients(gradients, module).unwrap()
+}
+
+/// See [centralize_gradients]
+pub fn try_centralize_gradients<M: TensorCollection<E, D>, E: Dtype, D: Device<E>>(
+    gradients: &mut Gradients<E, D>,
+    module: &M,
+) -> Result<(), D::Err> {
+    M::iter_tensors(&mut RecursiveWalker {
+        m: module,
+        f: &mut Centralize(gradients),
+    })?;
+    Ok(())
+}
+
+struct Centralize<'a, E: Dtype, D: Device<E>>(&'a mut Gradients<E, D>);
+
+impl<E: Dtype, D: Device<E>> TensorVisitor<E, D> for Centralize<'_, E, D> {
+    type Viewer = ViewTensorRef;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        p: &Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update
+            || S::NUM_DIMS < 2
+            || p.shape.num_elements() == 0
+            || self.0.get_ref_checked(p).is_none()
+        {
+            return Ok(None);
+        }
+        let rows = p.shape.concrete()[0];
+        let rest = p.shape.num_elements() / rows;
+        let g = self.0.get(p).try_reshape_like(&(rows, rest)).unwrap()?;
+        let mean = g
+            .clone()
+            .try_mean::<(usize,), Axis<1>>()?
+            .try_broadcast_like::<_, Axis<1>>(&(rows, rest))?;
+        let g = g.try_sub(mean)?;
+        self.0.insert(p, g.data.as_ref().clone());
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_centralize_gradients_2d() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let scale: Tensor<Rank2<2, 3>, TestDtype, _> =
+            dev.tensor([[1.0, 2.0, 3.0], [-1.0, 0.5, 4.0]]);
+        let mut grads = (w.leaky_trace() * scale).sum().backward();
+        centralize_gradients(&mut grads, &w);
+        assert_close(
+            &grads.get(&w).array(),
+            &[[-1.0, 0.0, 1.0], [-2.166_666_7, -0.666_666_7, 2.833_333_3]],
+        );
+        assert_close(&grads.get(&w).mean::<Rank1<2>, _>().array(), &[0.0; 2]);
+    }
+
+    #[test]
+    fn test_centralize_gradients_skips_1d() {
+        let dev: TestDevice = Default::default();
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.zeros();
+        let scale: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, 3.0]);
+        let mut grads = (b.leaky_trace() * scale).sum().backward();
+        centralize_gradients(&mut grads, &b);
+        assert_close(&grads.get(&b).array(), &[1.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,141 @@
+use num_traits::Float;
+
+/// Whether [EarlyStopping] and [super::BestCheckpoint] want their metric to decrease or increase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EarlyStoppingMode {
+    /// Lower is better, e.g. for a loss.
+    Min,
+    /// Higher is better, e.g. for an accuracy.
+    Max,
+}
+
+impl EarlyStoppingMode {
+    /// Whether `metric` beats `best` by more than `min_delta`. Anything but NaN
+    /// beats no `best`.
+    pub(crate) fn is_improvement<E: Float>(
+        &self,
+        metric: E,
+        best: Option<E>,
+        min_delta: E,
+    ) -> bool {
+        match (best, self) {
+            (None, _) => !metric.is_nan(),
+            (Some(best), Self::Min) => metric < best - min_delta,
+            (Some(best), Self::Max) => metric > best + min_delta,
+        }
+    }
+}
+
+/// Stops training once a monitored metric, like the validation loss,
+/// stops improving.
+///
+/// A metric only counts as an improvement if it beats the best metric so far by more
+/// than `min_delta`. [EarlyStopping::should_stop()] returns `true` once there have been
+/// `patience` epochs in a row without an improvement.
+///
+/// # Example Usage
+///
+/// ```rust
+/// # use dfdx::optim::*;
+/// let mut early_stopping = EarlyStopping::new(2, 0.0, EarlyStoppingMode::Min);
+/// let mut epochs = 0;
+/// for val_loss in [1.0, 0.5, 0.4, 0.4, 0.6, 0.3] {
+///     epochs += 1;
+///     // -- snip training & validation --
+///     if early_stopping.should_stop(val_loss) {
+///         break;
+///     }
+/// }
+/// assert_eq!(epochs, 5);
+/// assert_eq!(early_stopping.best(), Some(0.4));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EarlyStopping<E> {
+    /// The number of epochs without an improvement to wait before stopping.
+    pub patience: usize,
+
+    /// The amount the metric has to beat the best metric by to count as an improvement.
+    pub min_delta: E,
+
+    /// Whether lower or higher metrics are better.
+    pub mode: EarlyStoppingMode,
+
+    best: Option<E>,
+    num_bad_epochs: usize,
+}
+
+impl<E: Float> EarlyStopping<E> {
+    pub fn new(patience: usize, min_delta: E, mode: EarlyStoppingMode) -> Self {
+        Self {
+            patience,
+            min_delta,
+            mode,
+            best: None,
+            num_bad_epochs: 0,
+        }
+    }
+
+    /// The best metric so far, or `None` before the first epoch.
+    pub fn best(&self) -> Option<E> {
+        self.best
+    }
+
+    /// The number of epochs in a row without an improvement.
+    pub fn num_bad_epochs(&self) -> usize {
+        self.num_bad_epochs
+    }
+
+    /// Records the `metric` of an epoch, and returns whether training should stop.
+    /// The first metric is always an improvement, and a NaN metric never is.
+    pub fn should_stop(&mut self, metric: E) -> bool {
+        if self.mode.is_improvement(metric, self.best, self.min_delta) {
+            self.best = Some(metric);
+            self.num_bad_epochs = 0;
+        } else {
+            self.num_bad_epochs += 1;
+        }
+        self.num_bad_epochs >= self.patience
+    }
+
+    /// Forgets the best metric and the number of bad epochs.
+    pub fn reset(&mut self) {
+        self.best = None;
+        self.num_bad_epochs = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_early_stopping_plateau() {
+        let mut es: EarlyStopping<TestDtype> = EarlyStopping::new(3, 0.01, EarlyStoppingMode::Min);
+        // improves for 3 epochs, then plateaus with improvements smaller than `min_delta`
+        let losses = [1.0, 0.8, 0.6, 0.595, 0.6, 0.599, 0.7];
+        let stops: std::vec::Vec<bool> = losses.iter().map(|&l| es.should_stop(l)).collect();
+        assert_eq!(stops, [false, false, false, false, false, true, true]);
+        assert_eq!(es.best(), Some(0.6));
+        assert_eq!(es.num_bad_epochs(), 4);
+
+        // an improvement resets the count
+        es.reset();
+        assert!(!es.should_stop(0.5));
+        assert!(!es.should_stop(0.5));
+        assert!(!es.should_stop(0.4));
+        assert_eq!(es.num_bad_epochs(), 0);
+    }
+
+    #[test]
+    fn test_early_stopping_max() {
+        let mut es: EarlyStopping<TestDtype> = EarlyStopping::new(2, 0.0, EarlyStoppingMode::Max);
+        assert!(!es.should_stop(0.5));
+        assert!(!es.should_stop(0.7));
+        assert!(!es.should_stop(0.7));
+        assert!(!es.should_stop(0.8));
+        assert!(!es.should_stop(0.6));
+        assert!(es.should_stop(0.8));
+        assert_eq!(es.best(), Some(0.8));
+    }
+}
@@ -0,0 +1,212 @@
+use num_traits::Float;
+
+use crate::{
+    nn::tensor_collection::*,
+    shapes::{Dtype, Shape},
+    tensor::{Gradients, Tape, Tensor},
+    tensor_ops::{Device, TryMul},
+};
+
+use super::optimizer::*;
+
+/// Loss scaling for mixed precision training, like pytorch's `GradScaler`.
+///
+/// Small gradients can underflow to 0 in low precision dtypes, so the loss is multiplied by
+/// [GradScaler::scale] before backward, and the gradients are divided by it again before the
+/// optimizer uses them.
+///
+/// The scale is adjusted dynamically by [GradScaler::step()]:
+/// - If any gradient is inf or nan, the update is skipped and the scale is multiplied
+///   by [GradScaler::backoff_factor].
+/// - After [GradScaler::growth_interval] updates in a row without infs or nans, the scale
+///   is multiplied by [GradScaler::growth_factor].
+///
+/// # Example Usage
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// # type Model = Tensor<Rank0, f32, Cpu>;
+/// # let mut model: Model = dev.zeros();
+/// let mut opt: Sgd<Model, f32, Cpu> = Sgd::new(&model, Default::default());
+/// let mut scaler = GradScaler::new(1024.0);
+///
+/// let loss = (model.leaky_trace() - 1.0).square();
+/// let mut grads = scaler.scale(loss).backward();
+/// let updated = scaler.step(&mut opt, &mut model, &mut grads).unwrap();
+/// assert!(updated);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GradScaler<E> {
+    /// What the loss is multiplied by.
+    pub scale: E,
+
+    /// What [Self::scale] is multiplied by after [Self::growth_interval] steps without
+    /// infs or nans. Defaults to 2.0
+    pub growth_factor: E,
+
+    /// What [Self::scale] is multiplied by when a step has infs or nans. Defaults to 0.5
+    pub backoff_factor: E,
+
+    /// How many steps in a row without infs or nans before [Self::scale] grows.
+    /// Defaults to 2000
+    pub growth_interval: usize,
+
+    /// The number of steps in a row without infs or nans.
+    num_good_steps: usize,
+}
+
+impl<E: Dtype + Float> GradScaler<E> {
+    /// Starts out with a scale of `init_scale`, with the default growth and backoff.
+    pub fn new(init_scale: E) -> Self {
+        Self {
+            scale: init_scale,
+            growth_factor: E::from(2.0).unwrap(),
+            backoff_factor: E::from(0.5).unwrap(),
+            growth_interval: 2000,
+            num_good_steps: 0,
+        }
+    }
+
+    /// Multiplies `loss` by [Self::scale].
+    pub fn scale<S: Shape, D: Device<E>, T: Tape<E, D>>(
+        &self,
+        loss: Tensor<S, E, D, T>,
+    ) -> Tensor<S, E, D, T> {
+        self.try_scale(loss).unwrap()
+    }
+
+    /// See [GradScaler::scale]
+    pub fn try_scale<S: Shape, D: Device<E>, T: Tape<E, D>>(
+        &self,
+        loss: Tensor<S, E, D, T>,
+    ) -> Result<Tensor<S, E, D, T>, D::Err> {
+        loss.try_mul(self.scale)
+    }
+
+    /// Divides the gradients of all the parameters in `module` by [Self::scale].
+    /// Returns `false` if any of the unscaled gradients are inf or nan.
+    pub fn unscale<M: TensorCollection<E, D>, D: Device<E>>(
+        &self,
+        gradients: &mut Gradients<E, D>,
+        module: &M,
+    ) -> Result<bool, D::Err> {
+        let mut unscale = Unscale {
+            gradients,
+            inv_scale: E::one() / self.scale,
+            all_finite: true,
+        };
+        M::iter_tensors(&mut RecursiveWalker {
+            m: module,
+            f: &mut unscale,
+        })?;
+        Ok(unscale.all_finite)
+    }
+
+    /// Updates [Self::scale] after a step, where `all_finite` is whether the gradients had
+    /// no infs or nans.
+    pub fn update_scale(&mut self, all_finite: bool) {
+        if all_finite {
+            self.num_good_steps += 1;
+            if self.num_good_steps >= self.growth_interval {
+                self.scale *= self.growth_factor;
+                self.num_good_steps = 0;
+            }
+        } else {
+            self.scale *= self.backoff_factor;
+            self.num_good_steps = 0;
+        }
+    }
+
+    /// Unscales `gradients` with [GradScaler::unscale()], and then updates `module` with `opt`
+    /// if there were no infs or nans. The scale is adjusted with [GradScaler::update_scale()].
+    ///
+    /// Returns whether `module` was updated.
+    pub fn step<M: TensorCollection<E, D>, D: Device<E>, O: Optimizer<M, D, E>>(
+        &mut self,
+        opt: &mut O,
+        module: &mut M,
+        gradients: &mut Gradients<E, D>,
+    ) -> Result<bool, OptimizerUpdateError<D>> {
+        let all_finite = self
+            .unscale(gradients, module)
+            .map_err(OptimizerUpdateError::DeviceError)?;
+        if all_finite {
+            opt.update(module, gradients)?;
+        }
+        self.update_scale(all_finite);
+        Ok(all_finite)
+    }
+}
+
+/// Multiplies every gradient by `inv_scale`, and checks them for infs & nans.
+struct Unscale<'a, E: Dtype, D: Device<E>> {
+    gradients: &'a mut Gradients<E, D>,
+    inv_scale: E,
+    all_finite: bool,
+}
+
+impl<E: Dtype + Float, D: Device<E>> TensorVisitor<E, D> for Unscale<'_, E, D> {
+    type Viewer = ViewTensorRef;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        p: &Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update || self.gradients.get_ref_checked(p).is_none() {
+            return Ok(None);
+        }
+        let g = self.gradients.get(p).try_mul(self.inv_scale)?;
+        self.all_finite &= g.as_vec().iter().all(|x| x.is_finite());
+        self.gradients.insert(p, g.data.as_ref().clone());
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{optim::*, shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_grad_scaler_unscale_recovers_gradients() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.5, -1.0, 2.0]);
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1e-3, 2.0, -3.0]);
+
+        let expected = (w.leaky_trace() * x.clone()).square().sum().backward();
+
+        let scaler = GradScaler::new(256.0);
+        let loss = (w.leaky_trace() * x).square().sum();
+        let mut grads = scaler.scale(loss).backward();
+        assert_close(&grads.get(&w).array(), &(expected.get(&w) * 256.0).array());
+        assert!(scaler.unscale(&mut grads, &w).unwrap());
+        assert_close(&grads.get(&w).array(), &expected.get(&w).array());
+    }
+
+    #[test]
+    fn test_grad_scaler_skips_step_on_inf() {
+        let dev: TestDevice = Default::default();
+        let mut w: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 2.0]);
+        let mut opt = Sgd::new(&w, Default::default());
+        let mut scaler = GradScaler::new(8.0);
+
+        let inf: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, TestDtype::INFINITY]);
+        let mut grads = scaler.scale((w.leaky_trace() * inf).sum()).backward();
+        assert!(!scaler.step(&mut opt, &mut w, &mut grads).unwrap());
+        assert_eq!(w.array(), [1.0, 2.0]);
+        assert_eq!(scaler.scale, 4.0);
+
+        scaler.growth_interval = 2;
+        for _ in 0..2 {
+            let mut grads = scaler.scale(w.leaky_trace().sum()).backward();
+            assert!(scaler.step(&mut opt, &mut w, &mut grads).unwrap());
+        }
+        assert_ne!(w.array(), [1.0, 2.0]);
+        assert_eq!(scaler.scale, 8.0);
+    }
+}
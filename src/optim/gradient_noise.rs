@@ -0,0 +1,136 @@
+use num_traits::Float;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use std::vec::Vec;
+
+use crate::{
+    nn::tensor_collection::*,
+    shapes::{Dtype, Shape},
+    tensor::{Gradients, Tensor},
+    tensor_ops::{Device, TryAdd},
+};
+
+/// Annealed Gaussian gradient noise from
+/// [Adding Gradient Noise Improves Learning for Very Deep Networks](https://arxiv.org/abs/1511.06807),
+/// for all the parameters of `module` that have a gradient in `gradients`.
+/// Call this before [super::Optimizer::update()].
+///
+/// Adds noise sampled from `rng` with variance `eta / (1 + step)^gamma` to each element of the
+/// gradients. The paper uses `eta` in `{0.01, 0.3, 1.0}` and `gamma = 0.55`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # use rand::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let model = Linear::<3, 2>::build_on_device(&dev);
+/// let x: Tensor<Rank1<3>, f32, _> = dev.sample_normal();
+/// let mut grads = model.forward(x.leaky_trace()).square().sum().backward();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let step = 10;
+/// add_gradient_noise(&mut grads, &model, step, 0.3, 0.55, &mut rng);
+/// ```
+pub fn add_gradient_noise<M: TensorCollection<E, D>, E: Dtype + Float, D: Device<E>, R: Rng>(
+    gradients: &mut Gradients<E, D>,
+    module: &M,
+    step: usize,
+    eta: E,
+    gamma: E,
+    rng: &mut R,
+) {
+    try_add_gradient_noise(gradients, module, step, eta, gamma, rng).unwrap()
+}
+
+/// See [add_gradient_noise]
+pub fn try_add_gradient_noise<M: TensorCollection<E, D>, E: Dtype + Float, D: Device<E>, R: Rng>(
+    gradients: &mut Gradients<E, D>,
+    module: &M,
+    step: usize,
+    eta: E,
+    gamma: E,
+    rng: &mut R,
+) -> Result<(), D::Err> {
+    let variance = eta / (E::one() + E::from(step).unwrap()).powf(gamma);
+    M::iter_tensors(&mut RecursiveWalker {
+        m: module,
+        f: &mut GradientNoise {
+            gradients,
+            std: variance.sqrt(),
+            rng,
+        },
+    })?;
+    Ok(())
+}
+
+struct GradientNoise<'a, E: Dtype, D: Device<E>, R> {
+    gradients: &'a mut Gradients<E, D>,
+    std: E,
+    rng: &'a mut R,
+}
+
+impl<E: Dtype + Float, D: Device<E>, R: Rng> TensorVisitor<E, D> for GradientNoise<'_, E, D, R> {
+    type Viewer = ViewTensorRef;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        p: &Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update || self.gradients.get_ref_checked(p).is_none() {
+            return Ok(None);
+        }
+        let noise: Vec<E> = (0..p.shape.num_elements())
+            .map(|_| {
+                let z: f64 = self.rng.sample(StandardNormal);
+                E::from(z).unwrap() * self.std
+            })
+            .collect();
+        let noise = p.device.try_tensor_from_vec(noise, p.shape)?;
+        let g = self.gradients.get(p).try_add(noise)?;
+        self.gradients.insert(p, g.data.as_ref().clone());
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::ZeroGrads, shapes::*, tensor::*, tensor_ops::*, tests::*};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn noise_variance(step: usize) -> TestDtype {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank1<20000>, TestDtype, _> = dev.zeros();
+        let mut grads = w.alloc_grads();
+        let mut rng = StdRng::seed_from_u64(0);
+        add_gradient_noise(&mut grads, &w, step, 1.0, 0.55, &mut rng);
+        let g = grads.get(&w).as_vec();
+        let n = g.len() as TestDtype;
+        let mean = g.iter().sum::<TestDtype>() / n;
+        g.iter().map(|x| (x - mean).powi(2)).sum::<TestDtype>() / n
+    }
+
+    #[test]
+    fn test_gradient_noise_variance_anneals() {
+        let (v0, v9, v99) = (noise_variance(0), noise_variance(9), noise_variance(99));
+        // eta / (1 + step)^gamma
+        assert!((v0 - 1.0).abs() < 0.05, "{v0}");
+        assert!((v9 - 0.281_838).abs() < 0.05 * 0.281_838, "{v9}");
+        assert!((v99 - 0.079_433).abs() < 0.05 * 0.079_433, "{v99}");
+        assert!(v0 > v9 && v9 > v99);
+    }
+
+    #[test]
+    fn test_gradient_noise_adds_to_gradient() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank1<3>, TestDtype, _> = dev.zeros();
+        let scale: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, 3.0]);
+        let mut grads = (w.leaky_trace() * scale).sum().backward();
+        let mut rng = StdRng::seed_from_u64(0);
+        // a tiny eta leaves the gradient as it was
+        add_gradient_noise(&mut grads, &w, 0, 1e-16, 0.55, &mut rng);
+        assert_close(&grads.get(&w).array(), &[1.0, 2.0, 3.0]);
+    }
+}
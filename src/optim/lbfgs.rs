@@ -0,0 +1,178 @@
+use num_traits::Float;
+use std::vec::Vec;
+
+use crate::{
+    shapes::{Dtype, Rank0, Shape},
+    tensor::{OwnedTape, Tensor, Trace},
+    tensor_ops::{Backward, Device, SumTo, TryAdd, TryMul, TrySub},
+};
+
+use super::line_search::try_backtracking_line_search;
+
+/// The limited-memory BFGS quasi-Newton method, for full-batch optimization of a
+/// deterministic loss.
+///
+/// Unlike the other optimizers this doesn't implement [super::Optimizer], since every
+/// iteration evaluates the loss several times. [Lbfgs::minimize()] instead takes the
+/// parameters and a function computing the loss from them.
+///
+/// Each iteration computes a search direction from the gradient and the last `history_size`
+/// steps `s` and gradient changes `y` with the two-loop recursion, and picks the step size with
+/// [super::backtracking_line_search()].
+///
+/// # Example Usage
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// let mut opt = Lbfgs::new(10, 20, 1e-5);
+/// let x: Tensor<Rank1<2>, f32, _> = dev.tensor([3.0, -4.0]);
+/// // (x0 - 1)^2 + 2 * x1^2
+/// let target = dev.tensor([1.0, 0.0]);
+/// let scale = dev.tensor([1.0, 2.0]);
+/// let x = opt.minimize(x, |x| ((x - target.clone()).square() * scale.clone()).sum());
+/// let x = x.array();
+/// assert!((x[0] - 1.0).abs() < 1e-3 && x[1].abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lbfgs<S: Shape, E: Dtype, D: Device<E>> {
+    /// How many of the last steps to use for the search direction.
+    pub history_size: usize,
+    /// The most iterations [Lbfgs::minimize()] runs.
+    pub max_iter: usize,
+    /// [Lbfgs::minimize()] stops once the norm of the gradient is at most this.
+    pub tolerance: E,
+    /// The `(s, y, 1 / y·s)` of the last steps, oldest first.
+    #[allow(clippy::type_complexity)]
+    history: Vec<(Tensor<S, E, D>, Tensor<S, E, D>, E)>,
+}
+
+impl<S: Shape, E: Dtype + Float, D: Device<E>> Lbfgs<S, E, D> {
+    /// Creates an optimizer with an empty history.
+    pub fn new(history_size: usize, max_iter: usize, tolerance: E) -> Self {
+        Self {
+            history_size,
+            max_iter,
+            tolerance,
+            history: Vec::new(),
+        }
+    }
+
+    /// Minimizes `loss_fn` starting from `x`, and returns the parameters it ended at.
+    ///
+    /// The history of steps is cleared first, so this can be called again for a new problem.
+    pub fn minimize<F>(&mut self, x: Tensor<S, E, D>, loss_fn: F) -> Tensor<S, E, D>
+    where
+        F: FnMut(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+    {
+        self.try_minimize(x, loss_fn).unwrap()
+    }
+
+    /// See [Lbfgs::minimize()]
+    pub fn try_minimize<F>(
+        &mut self,
+        mut x: Tensor<S, E, D>,
+        mut loss_fn: F,
+    ) -> Result<Tensor<S, E, D>, D::Err>
+    where
+        F: FnMut(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+    {
+        self.history.clear();
+        let mut grad = loss_fn(x.clone().leaky_traced()).try_backward()?.get(&x);
+        for _ in 0..self.max_iter {
+            if dot(&grad, &grad)?.sqrt() <= self.tolerance {
+                break;
+            }
+            let mut direction = self.try_direction(&grad)?;
+            if dot(&grad, &direction)? >= E::zero() {
+                // the curvature estimate is bad, start over with gradient descent
+                self.history.clear();
+                direction = grad.clone().try_mul(-E::one())?;
+            }
+
+            let step = try_backtracking_line_search(
+                |x| loss_fn(x.clone().leaky_traced()).as_vec()[0],
+                &x,
+                &direction,
+                &grad,
+                E::from_f32(1e-4).unwrap(),
+                E::from_f32(0.5).unwrap(),
+            )?;
+            if step == E::zero() {
+                break;
+            }
+
+            let s = direction.try_mul(step)?;
+            let next_x = x.try_add(s.clone())?;
+            let next_grad = loss_fn(next_x.clone().leaky_traced())
+                .try_backward()?
+                .get(&next_x);
+            let y = next_grad.clone().try_sub(grad)?;
+            let ys = dot(&y, &s)?;
+            if ys > E::epsilon() {
+                if self.history.len() == self.history_size {
+                    self.history.remove(0);
+                }
+                self.history.push((s, y, E::one() / ys));
+            }
+            x = next_x;
+            grad = next_grad;
+        }
+        Ok(x)
+    }
+
+    /// The two-loop recursion, returning `-H g` with `H` the approximate inverse Hessian.
+    fn try_direction(&self, grad: &Tensor<S, E, D>) -> Result<Tensor<S, E, D>, D::Err> {
+        let mut q = grad.clone();
+        let mut alphas = Vec::with_capacity(self.history.len());
+        for (s, y, rho) in self.history.iter().rev() {
+            let alpha = *rho * dot(s, &q)?;
+            q = q.try_sub(y.clone().try_mul(alpha)?)?;
+            alphas.push(alpha);
+        }
+        // scale by the curvature of the most recent step
+        let gamma = match self.history.last() {
+            Some((s, y, _)) => dot(s, y)? / dot(y, y)?,
+            None => E::one(),
+        };
+        let mut z = q.try_mul(gamma)?;
+        for ((s, y, rho), alpha) in self.history.iter().zip(alphas.into_iter().rev()) {
+            let beta = *rho * dot(y, &z)?;
+            z = z.try_add(s.clone().try_mul(alpha - beta)?)?;
+        }
+        z.try_mul(-E::one())
+    }
+}
+
+fn dot<S: Shape, E: Dtype, D: Device<E>>(
+    a: &Tensor<S, E, D>,
+    b: &Tensor<S, E, D>,
+) -> Result<E, D::Err> {
+    Ok(a.clone()
+        .try_mul(b.clone())?
+        .try_sum::<Rank0, _>()?
+        .as_vec()[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_lbfgs_quadratic() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[3.0, 1.0], [1.0, 2.0]]);
+        let b: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, -1.0]);
+        // f(x) = x^T A x / 2 - b^T x, which is minimized at A^-1 b
+        let loss_fn = |x: Tensor<Rank1<2>, TestDtype, _, OwnedTape<_, _>>| {
+            let ax = x.with_empty_tape().matmul(a.clone());
+            let bx = (x.with_empty_tape() * b.clone()).sum::<Rank0, _>();
+            (ax * x).sum::<Rank0, _>() * 0.5 - bx
+        };
+
+        let mut opt = Lbfgs::new(5, 8, 1e-6);
+        let x = opt.minimize(dev.tensor([4.0, 3.0]), loss_fn);
+        assert_close_with_tolerance(&x.array(), &[0.6, -0.8], 1e-4);
+    }
+}
@@ -0,0 +1,108 @@
+use num_traits::Float;
+
+use crate::{
+    shapes::{Dtype, Rank0, Shape},
+    tensor::Tensor,
+    tensor_ops::{Device, SumTo, TryAdd, TryMul},
+};
+
+/// The most times [backtracking_line_search()] shrinks the step before giving up.
+const MAX_BACKTRACKS: usize = 50;
+
+/// Backtracking line search along `direction` from `x`, e.g. for a Newton or quasi-Newton
+/// step. Returns the step size to move by.
+///
+/// Starts with a step of `1`, and multiplies it by `rho` until it satisfies the Armijo
+/// condition `loss_fn(x + step * direction) <= loss_fn(x) + c * step * grad·direction`,
+/// where `grad` is the gradient of `loss_fn` at `x`. Common values are `c = 1e-4` and
+/// `rho = 0.5`. Returns `0` if no such step is found after 50 backtracking steps.
+///
+/// **Panics** if `direction` is not a descent direction, i.e. if `grad·direction >= 0`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::backtracking_line_search};
+/// # let dev: Cpu = Default::default();
+/// let loss_fn = |x: &Tensor<Rank1<2>, f32, Cpu>| x.clone().square().sum::<Rank0, _>().array();
+/// let x = dev.tensor([1.0, 2.0]);
+/// let grad = dev.tensor([2.0, 4.0]);
+/// let direction = -grad.clone();
+/// let step = backtracking_line_search(loss_fn, &x, &direction, &grad, 1e-4, 0.5);
+/// assert_eq!(step, 0.5);
+/// ```
+pub fn backtracking_line_search<S: Shape, E: Dtype + Float, D: Device<E>, F>(
+    loss_fn: F,
+    x: &Tensor<S, E, D>,
+    direction: &Tensor<S, E, D>,
+    grad: &Tensor<S, E, D>,
+    c: E,
+    rho: E,
+) -> E
+where
+    F: FnMut(&Tensor<S, E, D>) -> E,
+{
+    try_backtracking_line_search(loss_fn, x, direction, grad, c, rho).unwrap()
+}
+
+/// See [backtracking_line_search()]
+pub fn try_backtracking_line_search<S: Shape, E: Dtype + Float, D: Device<E>, F>(
+    mut loss_fn: F,
+    x: &Tensor<S, E, D>,
+    direction: &Tensor<S, E, D>,
+    grad: &Tensor<S, E, D>,
+    c: E,
+    rho: E,
+) -> Result<E, D::Err>
+where
+    F: FnMut(&Tensor<S, E, D>) -> E,
+{
+    let slope = grad
+        .clone()
+        .try_mul(direction.clone())?
+        .try_sum::<Rank0, _>()?
+        .as_vec()[0];
+    assert!(slope < E::zero(), "direction must be a descent direction");
+
+    let loss = loss_fn(x);
+    let mut step = E::one();
+    for _ in 0..MAX_BACKTRACKS {
+        let candidate = x.clone().try_add(direction.clone().try_mul(step)?)?;
+        if loss_fn(&candidate) <= loss + c * step * slope {
+            return Ok(step);
+        }
+        step *= rho;
+    }
+    Ok(E::zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_line_search_armijo_on_quadratic() {
+        let dev: TestDevice = Default::default();
+        let scale: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 10.0, 100.0]);
+        // f(x) = sum(scale * x^2) with the gradient 2 * scale * x
+        let loss_fn = |x: &Tensor<Rank1<3>, TestDtype, TestDevice>| {
+            (x.clone().square() * scale.clone())
+                .sum::<Rank0, _>()
+                .array()
+        };
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, -1.0, 0.5]);
+        let grad = x.clone() * scale.clone() * 2.0;
+        let direction = -grad.clone();
+
+        let (c, rho) = (1e-4, 0.5);
+        let step = backtracking_line_search(loss_fn, &x, &direction, &grad, c, rho);
+        assert!(step > 0.0 && step < 1.0);
+
+        let slope = (grad * direction.clone()).sum::<Rank0, _>().array();
+        let new_loss = loss_fn(&(x.clone() + direction.clone() * step));
+        assert!(new_loss < loss_fn(&x));
+        assert!(new_loss <= loss_fn(&x) + c * step * slope);
+        // the previous, larger step didn't satisfy the condition
+        let larger = step / rho;
+        assert!(loss_fn(&(x.clone() + direction * larger)) > loss_fn(&x) + c * larger * slope);
+    }
+}
@@ -0,0 +1,182 @@
+use std::marker::PhantomData;
+
+use crate::{
+    nn::tensor_collection::*,
+    shapes::{Dtype, Shape},
+    tensor::{DeviceStorage, Gradients, Tensor},
+    tensor_ops::{Device, TryAdd, TryMul, TrySub},
+};
+
+use super::optimizer::*;
+
+/// Implementation of [Lookahead Optimizer: k steps forward, 1 step back](https://arxiv.org/abs/1907.08610),
+/// which wraps a base optimizer `O`.
+///
+/// The base optimizer updates the "fast" weights of the model as usual. Every `k` updates,
+/// the "slow" weights are moved towards the fast weights with `slow += alpha * (fast - slow)`,
+/// and the fast weights are reset to the slow weights.
+///
+/// The slow weights start out as the weights of the model at the first update.
+///
+/// # Example Usage
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// # type Model = Tensor<Rank0, f32, Cpu>;
+/// # let mut model: Model = dev.zeros();
+/// let sgd: Sgd<Model, f32, Cpu> = Sgd::new(&model, Default::default());
+/// let mut opt: Lookahead<Model, _, f32, Cpu> = Lookahead::new(&model, sgd, 5, 0.5);
+/// ```
+///
+/// See module level documentation at [crate::optim] for examples of how to actually use an optimizer.
+#[derive(Debug)]
+pub struct Lookahead<M, O, E: Dtype, D: DeviceStorage> {
+    /// The base optimizer that updates the fast weights
+    pub opt: O,
+
+    /// Number of base optimizer updates between updates of the slow weights
+    pub k: usize,
+
+    /// How far to move the slow weights towards the fast weights
+    pub alpha: E,
+
+    slow: Gradients<E, D>,
+    /// Number of updates since the slow weights were last updated
+    num_fast_updates: usize,
+
+    marker: PhantomData<*const M>,
+}
+
+impl<M, O, E: Dtype, D: DeviceStorage> Lookahead<M, O, E, D> {
+    /// Wraps `opt`, updating the slow weights every `k` updates.
+    ///
+    /// **Panics** if `k` is 0.
+    pub fn new(_model: &M, opt: O, k: usize, alpha: E) -> Self {
+        assert!(k > 0, "k must be greater than 0");
+        Self {
+            opt,
+            k,
+            alpha,
+            slow: Gradients::leaky(),
+            num_fast_updates: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M, O: LearningRate<E>, E: Dtype, D: DeviceStorage> LearningRate<E> for Lookahead<M, O, E, D> {
+    /// The learning rate of the base optimizer.
+    fn lr(&self) -> E {
+        self.opt.lr()
+    }
+
+    fn set_lr(&mut self, lr: E) {
+        self.opt.set_lr(lr);
+    }
+}
+
+/// Records the initial slow weights if `sync` is false, otherwise
+/// interpolates the slow weights and resets the fast weights to them.
+struct SlowWeights<'a, E: Dtype, D: DeviceStorage> {
+    slow: &'a mut Gradients<E, D>,
+    alpha: E,
+    sync: bool,
+}
+
+impl<E: Dtype, D: Device<E>> TensorVisitor<E, D> for SlowWeights<'_, E, D> {
+    type Viewer = ViewTensorMut;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        p: &mut Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update {
+            return Ok(None);
+        }
+        if self.slow.get_ref_checked(p).is_none() {
+            self.slow.insert(p, p.data.as_ref().clone());
+        } else if self.sync {
+            let slow = self.slow.get(p);
+            let slow = p
+                .clone()
+                .try_sub(slow.clone())?
+                .try_mul(self.alpha)?
+                .try_add(slow)?;
+            self.slow.insert(p, slow.data.as_ref().clone());
+            p.data = slow.data;
+        }
+        Ok(None)
+    }
+}
+
+impl<M: TensorCollection<E, D>, O: Optimizer<M, D, E>, D: Device<E>, E: Dtype> Optimizer<M, D, E>
+    for Lookahead<M, O, E, D>
+{
+    fn update(
+        &mut self,
+        module: &mut M,
+        gradients: &Gradients<E, D>,
+    ) -> Result<(), OptimizerUpdateError<D>> {
+        let mut walk = |module: &mut M, sync| {
+            M::iter_tensors(&mut RecursiveWalker {
+                m: module,
+                f: &mut SlowWeights {
+                    slow: &mut self.slow,
+                    alpha: self.alpha,
+                    sync,
+                },
+            })
+            .map_err(OptimizerUpdateError::DeviceError)
+        };
+        if self.num_fast_updates == 0 {
+            // records the slow weights of any tensors seen for the first time
+            walk(module, false)?;
+        }
+        self.opt.update(module, gradients)?;
+        self.num_fast_updates += 1;
+        if self.num_fast_updates >= self.k {
+            walk(module, true)?;
+            self.num_fast_updates = 0;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tests::*};
+
+    /// Moves every weight up by 1 on each update
+    struct AddOne;
+
+    impl<S: Shape> Optimizer<Tensor<S, TestDtype, TestDevice>, TestDevice, TestDtype> for AddOne {
+        fn update(
+            &mut self,
+            module: &mut Tensor<S, TestDtype, TestDevice>,
+            _gradients: &Gradients<TestDtype, TestDevice>,
+        ) -> Result<(), OptimizerUpdateError<TestDevice>> {
+            module.data = (module.clone() + 1.0).data;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_lookahead_interpolates_every_k_steps() {
+        let dev: TestDevice = Default::default();
+        let mut t: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.0, 2.0]);
+        let mut opt = Lookahead::new(&t, AddOne, 2, 0.5);
+        let grads = Gradients::leaky();
+
+        // fast weights reach slow + 2 every other step, and slow moves half way there
+        for expected in [[1.0, 3.0], [1.0, 3.0], [2.0, 4.0], [2.0, 4.0]] {
+            opt.update(&mut t, &grads).unwrap();
+            assert_close(&t.array(), &expected);
+        }
+    }
+}
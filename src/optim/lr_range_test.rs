@@ -0,0 +1,147 @@
+use num_traits::Float;
+
+use crate::{
+    nn::{tensor_collection::*, ZeroGrads},
+    shapes::{Dtype, Rank0},
+    tensor::{Gradients, OwnedTape, Tensor},
+    tensor_ops::{Backward, Device},
+};
+
+use super::optimizer::*;
+
+/// The learning rate range test from
+/// [Cyclical Learning Rates for Training Neural Networks](https://arxiv.org/abs/1506.01186).
+///
+/// Trains `model` for `steps` updates, increasing the learning rate of `opt` exponentially
+/// from `min_lr` to `max_lr`, and returns the `(lr, loss)` of each step. A good learning rate
+/// is usually a bit below where the loss starts to go back up.
+///
+/// `data` is cycled through if it has fewer than `steps` batches. `loss_fn` takes the model,
+/// a batch, and the gradients to trace the batch with, and returns the loss of the batch.
+///
+/// Note that this trains `model`, so you may want to use a clone of it.
+///
+/// **Panics** if `steps` is 0 or `data` is empty.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// let mut model = dev.build_module::<Linear<2, 1>, f32>();
+/// let mut opt = Sgd::new(&model, Default::default());
+/// let x: Tensor<Rank2<4, 2>, f32, _> = dev.sample_normal();
+/// let y: Tensor<Rank2<4, 1>, f32, _> = dev.sample_normal();
+/// let history = lr_range_test(
+///     &mut model,
+///     &mut opt,
+///     [(x, y)],
+///     1e-5,
+///     1.0,
+///     10,
+///     |m, (x, y), grads| mse_loss(m.forward(x.traced(grads)), y),
+/// );
+/// assert_eq!(history.len(), 10);
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn lr_range_test<M, O, E, D, I, F>(
+    model: &mut M,
+    opt: &mut O,
+    data: I,
+    min_lr: E,
+    max_lr: E,
+    steps: usize,
+    loss_fn: F,
+) -> std::vec::Vec<(E, E)>
+where
+    M: TensorCollection<E, D>,
+    O: Optimizer<M, D, E> + LearningRate<E>,
+    E: Dtype + Float,
+    D: Device<E>,
+    I: IntoIterator,
+    I::IntoIter: Clone,
+    F: FnMut(&M, I::Item, Gradients<E, D>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+{
+    try_lr_range_test(model, opt, data, min_lr, max_lr, steps, loss_fn).unwrap()
+}
+
+/// See [lr_range_test()]
+#[allow(clippy::too_many_arguments)]
+pub fn try_lr_range_test<M, O, E, D, I, F>(
+    model: &mut M,
+    opt: &mut O,
+    data: I,
+    min_lr: E,
+    max_lr: E,
+    steps: usize,
+    mut loss_fn: F,
+) -> Result<std::vec::Vec<(E, E)>, OptimizerUpdateError<D>>
+where
+    M: TensorCollection<E, D>,
+    O: Optimizer<M, D, E> + LearningRate<E>,
+    E: Dtype + Float,
+    D: Device<E>,
+    I: IntoIterator,
+    I::IntoIter: Clone,
+    F: FnMut(&M, I::Item, Gradients<E, D>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+{
+    assert!(steps > 0, "the range test needs at least one step");
+    let mut batches = data.into_iter().cycle();
+    let mut grads = model
+        .try_alloc_grads()
+        .map_err(OptimizerUpdateError::DeviceError)?;
+    let mut history = std::vec::Vec::with_capacity(steps);
+    for i in 0..steps {
+        // lr_i = min_lr * (max_lr / min_lr)^(i / (steps - 1))
+        let pct = if steps == 1 {
+            E::zero()
+        } else {
+            E::from(i).unwrap() / E::from(steps - 1).unwrap()
+        };
+        let lr = min_lr * (max_lr / min_lr).powf(pct);
+        opt.set_lr(lr);
+
+        let batch = batches.next().expect("data must have at least one batch");
+        let loss = loss_fn(model, batch, grads);
+        let loss_value = loss.as_vec()[0];
+        grads = loss
+            .try_backward()
+            .map_err(OptimizerUpdateError::DeviceError)?;
+        opt.update(model, &grads)?;
+        model
+            .try_zero_grads(&mut grads)
+            .map_err(OptimizerUpdateError::DeviceError)?;
+        history.push((lr, loss_value));
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{losses::mse_loss, nn::builders::*, optim::Sgd, prelude::*, tests::*};
+
+    #[test]
+    fn test_lr_range_test_on_regression() {
+        let dev: TestDevice = Default::default();
+        let mut model = dev.build_module::<Linear<1, 1>, TestDtype>();
+        let mut opt = Sgd::new(&model, Default::default());
+
+        let x: Tensor<Rank2<8, 1>, TestDtype, _> = dev.sample_normal();
+        let y = x.clone() * 2.0 + 1.0;
+        let history = lr_range_test(
+            &mut model,
+            &mut opt,
+            [(x.clone(), y.clone()), (x * -1.0, y * -1.0 + 2.0)],
+            1e-4,
+            1e-1,
+            5,
+            |m, (x, y), grads| mse_loss(m.forward(x.traced(grads)), y),
+        );
+
+        assert_eq!(history.len(), 5);
+        assert!(history.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_close(&history[0].0, &1e-4);
+        assert_close(&history[4].0, &1e-1);
+        assert!(history.iter().all(|(_, loss)| loss.is_finite()));
+        assert_close(&opt.lr(), &1e-1);
+    }
+}
@@ -8,6 +8,12 @@
 //! - [Adam::new()] with [AdamConfig]
 //! - [RMSprop::new()] with [RMSpropConfig]
 //!
+//! [Lookahead] and [Sam] wrap any of these as their base optimizer.
+//!
+//! For full-batch optimization of a deterministic loss, [Lbfgs] minimizes a loss function
+//! directly, using [backtracking_line_search()] for its step sizes. [natural_gradient()]
+//! preconditions a gradient with the Fisher information, e.g. for policy gradient methods.
+//!
 //! # Updating network parameters
 //!
 //! This is done via [Optimizer::update()], where you pass in a mutable [crate::nn::Module], and
@@ -29,18 +35,50 @@
 //! opt.update(&mut model, &grads);
 //! model.zero_grads(&mut grads);
 //! ```
+//!
+//! # Learning rate schedules
+//!
+//! Schedulers like [OneCycleLR] compute a learning rate for each step, which you can assign
+//! to the `cfg` of the optimizer before calling [Optimizer::update()], or set with
+//! [LearningRate::set_lr()]. [lr_range_test()] helps pick the learning rate to use, and
+//! [EarlyStopping] ends training once a validation metric stops improving. [BestCheckpoint]
+//! keeps the model from the epoch with the best metric.
 
 mod adam;
+mod best_checkpoint;
+mod centralize;
+mod early_stopping;
+mod grad_scaler;
+mod gradient_noise;
+mod lbfgs;
+mod line_search;
+mod lookahead;
+mod lr_range_test;
+mod natural_gradient;
+mod one_cycle;
 mod optimizer;
 mod rmsprop;
+mod sam;
 mod sgd;
 
 pub use adam::{Adam, AdamConfig, AdamKernel};
+pub use best_checkpoint::BestCheckpoint;
+pub use centralize::{centralize_gradients, try_centralize_gradients};
+pub use early_stopping::{EarlyStopping, EarlyStoppingMode};
+pub use grad_scaler::GradScaler;
+pub use gradient_noise::{add_gradient_noise, try_add_gradient_noise};
+pub use lbfgs::Lbfgs;
+pub use line_search::{backtracking_line_search, try_backtracking_line_search};
+pub use lookahead::Lookahead;
+pub use lr_range_test::{lr_range_test, try_lr_range_test};
+pub use natural_gradient::{natural_gradient, try_natural_gradient};
+pub use one_cycle::OneCycleLR;
+pub use optimizer::{LearningRate, Optimizer, OptimizerUpdateError, UnusedTensors};
 pub use optimizer::{Momentum, WeightDecay};
-pub use optimizer::{Optimizer, OptimizerUpdateError, UnusedTensors};
 pub use rmsprop::{RMSprop, RMSpropConfig, RMSpropKernel};
+pub use sam::Sam;
 pub use sgd::{Sgd, SgdConfig, SgdKernel};
 
 pub mod prelude {
-    pub use super::{Optimizer, OptimizerUpdateError, UnusedTensors};
+    pub use super::{LearningRate, Optimizer, OptimizerUpdateError, UnusedTensors};
 }
@@ -0,0 +1,114 @@
+use num_traits::Float;
+
+use crate::{
+    shapes::{Dtype, Rank0, Shape},
+    tensor::{Gradients, OwnedTape, Tensor, Trace},
+    tensor_ops::{try_conjugate_gradient, try_hvp_fd, Backward, Device, HvpError, TryAdd, TryMul},
+};
+
+/// The natural gradient of `loss_fn` at `params`, as in
+/// [Trust Region Policy Optimization](https://arxiv.org/abs/1502.05477), e.g. for policy
+/// gradient methods. Returns [Gradients] with it as the gradient of `params`, to pass to
+/// [super::Optimizer::update()].
+///
+/// The natural gradient is `F^-1 g`, where `g` is the gradient of `loss_fn` and `F` is the
+/// Fisher information matrix. `F` is the Hessian of `kl_fn` at `params`, where `kl_fn`
+/// computes the KL divergence between the policy at the current `params` (held fixed) and
+/// the policy at its input. `F` is never materialized: `F v + damping * v` is approximated with
+/// finite differences by [hvp_fd()], and solved with up to 10 iterations of
+/// [conjugate_gradient()]. The result inherits the error of [hvp_fd()], so it is only accurate
+/// to 2-3 significant digits for `f32`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// let mut params: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 2.0]);
+/// let old = params.clone();
+/// let grads = natural_gradient(
+///     |p| p.square().sum(),
+///     // the KL divergence of two gaussians with means `old` and `p`, and unit variance
+///     |p| ((p - old.clone()).square() * 0.5).sum(),
+///     &params,
+///     1e-3,
+/// );
+/// let mut opt = Sgd::new(&params, Default::default());
+/// opt.update(&mut params, &grads).unwrap();
+/// ```
+pub fn natural_gradient<S: Shape, E: Dtype + Float, D: Device<E>, L, K>(
+    loss_fn: L,
+    kl_fn: K,
+    params: &Tensor<S, E, D>,
+    damping: E,
+) -> Gradients<E, D>
+where
+    L: FnMut(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+    K: FnMut(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+{
+    try_natural_gradient(loss_fn, kl_fn, params, damping).unwrap()
+}
+
+/// Fallible version of [natural_gradient()]
+pub fn try_natural_gradient<S: Shape, E: Dtype + Float, D: Device<E>, L, K>(
+    mut loss_fn: L,
+    mut kl_fn: K,
+    params: &Tensor<S, E, D>,
+    damping: E,
+) -> Result<Gradients<E, D>, HvpError<D::Err>>
+where
+    L: FnMut(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+    K: FnMut(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+{
+    let grad = loss_fn(params.clone().leaky_traced())
+        .try_backward()?
+        .get(params);
+    let iters = params.shape.num_elements().min(10);
+    let natural = try_conjugate_gradient(
+        |v| {
+            let fv = try_hvp_fd(&mut kl_fn, params.clone(), v.clone())?;
+            Ok::<_, HvpError<D::Err>>(fv.try_add(v.clone().try_mul(damping)?)?)
+        },
+        grad,
+        iters,
+        E::from_f32(1e-6).unwrap(),
+    )?;
+    let mut grads = Gradients::leaky();
+    grads.insert(params, natural.data.as_ref().clone());
+    Ok(grads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_natural_gradient_gaussian_policy() {
+        let dev: TestDevice = Default::default();
+        // a 1d gaussian policy with parameters `[mean, log_std]`
+        let params: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.5, 2.0f64.ln() as TestDtype]);
+        let pick_mean: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 0.0]);
+        let pick_log_std: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.0, 1.0]);
+
+        let (old_mean, old_std) = (0.5, 2.0);
+        // KL(N(old_mean, old_std) || N(mean, std)), whose Hessian is diag(1 / std^2, 2)
+        let kl_fn = |p: Tensor<Rank1<2>, TestDtype, _, OwnedTape<_, _>>| {
+            let mean = (p.with_empty_tape() * pick_mean.clone()).sum::<Rank0, _>();
+            let log_std = (p * pick_log_std.clone()).sum::<Rank0, _>();
+            let var = (log_std.with_empty_tape() * 2.0).exp();
+            let diff = (mean - old_mean).square() + old_std * old_std;
+            log_std - (old_std as TestDtype).ln() + diff / (var * 2.0) - 0.5
+        };
+        // a loss whose gradient is [1, 1]
+        let loss_fn = |p: Tensor<Rank1<2>, TestDtype, _, OwnedTape<_, _>>| p.sum();
+
+        let grads = natural_gradient(loss_fn, kl_fn, &params, 0.0);
+        let natural = grads.get(&params).array();
+        assert_close_with_tolerance(&natural, &[4.0, 0.5], 1e-2);
+
+        // the plain gradient points along [1, 1], but the natural gradient is scaled by the
+        // inverse fisher, taking larger steps in the mean where the policy is less sensitive
+        let cos = (natural[0] + natural[1])
+            / (2.0 * (natural[0] * natural[0] + natural[1] * natural[1])).sqrt();
+        assert!(cos < 0.9, "{cos}");
+    }
+}
@@ -0,0 +1,137 @@
+use num_traits::Float;
+
+/// The 1cycle learning rate policy from [Super-Convergence](https://arxiv.org/abs/1708.07120).
+/// Based on [pytorch's implementation](https://pytorch.org/docs/stable/generated/torch.optim.lr_scheduler.OneCycleLR.html)
+/// with cosine annealing.
+///
+/// The learning rate ramps up from `max_lr / div_factor` to `max_lr` over the first
+/// `pct_start * total_steps` steps, and then anneals down to
+/// `max_lr / (div_factor * final_div_factor)` at the last step. If `momentum` is set,
+/// it moves inversely to the learning rate, between its max and base values.
+///
+/// # Example Usage
+///
+/// [OneCycleLR::step()] returns the learning rate to use for the current step,
+/// which you can assign to the optimizer's config before each update:
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// # type Model = Tensor<Rank0, f32, Cpu>;
+/// # let mut model: Model = dev.zeros();
+/// let mut opt: Sgd<Model, f32, Cpu> = Sgd::new(&model, Default::default());
+/// let mut sched = OneCycleLR::new(1e-1, 100, 0.3);
+/// for _ in 0..100 {
+///     opt.cfg.lr = sched.step();
+///     // -- snip loss computation & update --
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OneCycleLR<E> {
+    /// The peak learning rate.
+    pub max_lr: E,
+
+    /// The number of steps in the cycle.
+    pub total_steps: usize,
+
+    /// The fraction of the cycle spent increasing the learning rate. Defaults to `0.3`.
+    pub pct_start: E,
+
+    /// The initial learning rate is `max_lr / div_factor`. Defaults to `25`.
+    pub div_factor: E,
+
+    /// The final learning rate is `max_lr / (div_factor * final_div_factor)`. Defaults to `1e4`.
+    pub final_div_factor: E,
+
+    /// Optional `(base, max)` momentum to cycle inversely to the learning rate. Defaults to `None`.
+    pub momentum: Option<(E, E)>,
+
+    step: usize,
+}
+
+impl<E: Float> OneCycleLR<E> {
+    /// Constructs with the default `div_factor` and `final_div_factor`, and without momentum.
+    pub fn new(max_lr: E, total_steps: usize, pct_start: E) -> Self {
+        Self {
+            max_lr,
+            total_steps,
+            pct_start,
+            div_factor: E::from(25.0).unwrap(),
+            final_div_factor: E::from(1e4).unwrap(),
+            momentum: None,
+            step: 0,
+        }
+    }
+
+    /// The learning rate of the current step.
+    pub fn lr(&self) -> E {
+        let initial_lr = self.max_lr / self.div_factor;
+        let min_lr = initial_lr / self.final_div_factor;
+        self.anneal((initial_lr, self.max_lr, min_lr))
+    }
+
+    /// The momentum of the current step, if `momentum` is set.
+    pub fn momentum(&self) -> Option<E> {
+        self.momentum
+            .map(|(base, max)| self.anneal((max, base, max)))
+    }
+
+    /// Returns the learning rate of the current step, and moves on to the next step.
+    /// Steps after the end of the cycle keep the final learning rate.
+    pub fn step(&mut self) -> E {
+        let lr = self.lr();
+        self.step += 1;
+        lr
+    }
+
+    /// Cosine anneals from `start` to `peak` over the first phase,
+    /// and from `peak` to `end` over the second.
+    fn anneal(&self, (start, peak, end): (E, E, E)) -> E {
+        let step = E::from(self.step.min(self.total_steps - 1)).unwrap();
+        let peak_step = self.pct_start * E::from(self.total_steps).unwrap() - E::one();
+        let end_step = E::from(self.total_steps - 1).unwrap();
+        let cos = |from: E, to: E, pct: E| {
+            let pi = E::from(core::f64::consts::PI).unwrap();
+            to + (from - to) * ((pi * pct).cos() + E::one()) / E::from(2.0).unwrap()
+        };
+        if step <= peak_step {
+            cos(start, peak, step / peak_step)
+        } else {
+            cos(peak, end, (step - peak_step) / (end_step - peak_step))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_one_cycle_lr() {
+        let mut sched: OneCycleLR<TestDtype> = OneCycleLR::new(1.0, 100, 0.3);
+        sched.momentum = Some((0.85, 0.95));
+        let mut lrs = std::vec::Vec::new();
+        let mut momentums = std::vec::Vec::new();
+        for _ in 0..100 {
+            momentums.push(sched.momentum().unwrap());
+            lrs.push(sched.step());
+        }
+        // start, peak at `pct_start * total_steps - 1`, and end
+        assert_close(&lrs[0], &0.04);
+        assert_close(&lrs[29], &1.0);
+        assert_close(&lrs[99], &4e-6);
+        assert_close(&momentums[0], &0.95);
+        assert_close(&momentums[29], &0.85);
+        assert_close(&momentums[99], &0.95);
+
+        assert!(lrs[..30].windows(2).all(|w| w[0] < w[1]));
+        assert!(lrs[29..].windows(2).all(|w| w[0] > w[1]));
+
+        // halfway through annealing is halfway between max & min
+        assert_close(&lrs[64], &((1.0 + 4e-6) / 2.0));
+
+        // past the end keeps the final learning rate
+        assert_close(&sched.step(), &4e-6);
+    }
+}
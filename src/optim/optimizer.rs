@@ -84,6 +84,16 @@ pub trait Optimizer<M, D: DeviceStorage, E: Dtype> {
     ) -> Result<(), OptimizerUpdateError<D>>;
 }
 
+/// Optimizers with a learning rate that can be changed between updates, e.g. by a
+/// learning rate schedule like [super::OneCycleLR] or by [super::lr_range_test()].
+pub trait LearningRate<E> {
+    /// The current learning rate.
+    fn lr(&self) -> E;
+
+    /// Sets the learning rate used by the next update.
+    fn set_lr(&mut self, lr: E);
+}
+
 /// Holds [UniqueId] of tensors that were missing gradients during
 /// update, and therefore are unused
 #[derive(Debug, Default)]
@@ -12,7 +12,7 @@ use crate::{
     tensor_ops::Device,
 };
 
-use super::{Optimizer, OptimizerUpdateError, UnusedTensors, WeightDecay};
+use super::{LearningRate, Optimizer, OptimizerUpdateError, UnusedTensors, WeightDecay};
 
 /// Configuration of hyperparameters for [RMSprop].
 #[derive(Debug, Clone, Copy)]
@@ -104,6 +104,16 @@ impl<M, E: Dtype, D: DeviceStorage> RMSprop<M, E, D> {
     }
 }
 
+impl<M, E: Dtype, D: DeviceStorage> LearningRate<E> for RMSprop<M, E, D> {
+    fn lr(&self) -> E {
+        self.cfg.lr
+    }
+
+    fn set_lr(&mut self, lr: E) {
+        self.cfg.lr = lr;
+    }
+}
+
 pub trait RMSpropKernel<E: Dtype>: DeviceStorage {
     fn update(
         &self,
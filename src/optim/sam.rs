@@ -0,0 +1,228 @@
+use std::marker::PhantomData;
+
+use num_traits::Float;
+
+use crate::{
+    nn::tensor_collection::*,
+    shapes::{Dtype, Shape},
+    tensor::{DeviceStorage, Gradients, Tensor},
+    tensor_ops::{Device, SumTo, TryAdd, TryMul, TrySub},
+};
+
+use super::optimizer::*;
+
+/// Implementation of [Sharpness-Aware Minimization](https://arxiv.org/abs/2010.01412),
+/// which wraps a base optimizer `O`.
+///
+/// Each update takes two forward & backward passes:
+/// 1. [Sam::first_step()] moves the weights to the (approximate) worst case point
+///    within a radius of `rho`, which is `w + rho * g / |g|` with `|g|` the norm of
+///    all the gradients together.
+/// 2. [Sam::second_step()] moves the weights back to where they were, and then
+///    updates them with the base optimizer using the gradients at the worst case point.
+///
+/// # Example Usage
+///
+/// ```rust
+/// # use dfdx::{prelude::*, optim::*};
+/// # let dev: Cpu = Default::default();
+/// # type Model = Tensor<Rank0, f32, Cpu>;
+/// # let mut model: Model = dev.zeros();
+/// let sgd: Sgd<Model, f32, Cpu> = Sgd::new(&model, Default::default());
+/// let mut opt: Sam<Model, _, f32, Cpu> = Sam::new(&model, sgd, 0.05);
+///
+/// let grads = model.leaky_trace().square().backward();
+/// opt.first_step(&mut model, &grads).unwrap();
+/// let grads = model.leaky_trace().square().backward();
+/// opt.second_step(&mut model, &grads).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Sam<M, O, E: Dtype, D: DeviceStorage> {
+    /// The base optimizer that updates the weights in [Sam::second_step()]
+    pub opt: O,
+
+    /// The radius of the neighborhood to search for the worst case point
+    pub rho: E,
+
+    /// How far each weight was moved by [Sam::first_step()]
+    perturbations: Gradients<E, D>,
+
+    marker: PhantomData<*const M>,
+}
+
+impl<M, O, E: Dtype, D: DeviceStorage> Sam<M, O, E, D> {
+    /// Wraps `opt`, searching for the worst case point within a radius of `rho`.
+    pub fn new(_model: &M, opt: O, rho: E) -> Self {
+        Self {
+            opt,
+            rho,
+            perturbations: Gradients::leaky(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M, O: LearningRate<E>, E: Dtype, D: DeviceStorage> LearningRate<E> for Sam<M, O, E, D> {
+    /// The learning rate of the base optimizer.
+    fn lr(&self) -> E {
+        self.opt.lr()
+    }
+
+    fn set_lr(&mut self, lr: E) {
+        self.opt.set_lr(lr);
+    }
+}
+
+/// Sums the squares of all the gradients.
+struct SquaredNorm<'a, E: Dtype, D: DeviceStorage> {
+    gradients: &'a Gradients<E, D>,
+    sum: E,
+}
+
+impl<E: Dtype, D: Device<E>> TensorVisitor<E, D> for SquaredNorm<'_, E, D> {
+    type Viewer = ViewTensorRef;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        p: &Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if opts.do_gradient_update && self.gradients.get_ref_checked(p).is_some() {
+            let g = self.gradients.get(p);
+            self.sum += g.try_square()?.try_sum::<(), _>()?.as_vec()[0];
+        }
+        Ok(None)
+    }
+}
+
+/// Adds `scale * gradient` to each weight if `scale` is `Some`, otherwise
+/// undoes the previous perturbation.
+struct Perturb<'a, E: Dtype, D: DeviceStorage> {
+    gradients: &'a Gradients<E, D>,
+    perturbations: &'a mut Gradients<E, D>,
+    scale: Option<E>,
+}
+
+impl<E: Dtype, D: Device<E>> TensorVisitor<E, D> for Perturb<'_, E, D> {
+    type Viewer = ViewTensorMut;
+    type Err = D::Err;
+    type E2 = E;
+    type D2 = D;
+
+    fn visit<S: Shape>(
+        &mut self,
+        opts: TensorOptions<S, E, D>,
+        p: &mut Tensor<S, E, D>,
+    ) -> Result<Option<Tensor<S, E, D>>, Self::Err> {
+        if !opts.do_gradient_update {
+            return Ok(None);
+        }
+        match self.scale {
+            Some(scale) => {
+                if self.gradients.get_ref_checked(p).is_some() {
+                    let e = self.gradients.get(p).try_mul(scale)?;
+                    p.data = p.clone().try_add(e.clone())?.data;
+                    self.perturbations.insert(p, e.data.as_ref().clone());
+                }
+            }
+            None => {
+                if self.perturbations.get_ref_checked(p).is_some() {
+                    let e = self.perturbations.get(p);
+                    p.data = p.clone().try_sub(e)?.data;
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<M: TensorCollection<E, D>, O: Optimizer<M, D, E>, E: Dtype + Float, D: Device<E>>
+    Sam<M, O, E, D>
+{
+    /// Moves `module` to the worst case point near it, using the `gradients` at
+    /// the current weights. Weights without gradients are not moved.
+    pub fn first_step(
+        &mut self,
+        module: &mut M,
+        gradients: &Gradients<E, D>,
+    ) -> Result<(), OptimizerUpdateError<D>> {
+        let mut norm = SquaredNorm {
+            gradients,
+            sum: E::zero(),
+        };
+        M::iter_tensors(&mut RecursiveWalker {
+            m: &*module,
+            f: &mut norm,
+        })
+        .map_err(OptimizerUpdateError::DeviceError)?;
+        let scale = self.rho / (norm.sum.sqrt() + E::from(1e-12).unwrap());
+
+        self.perturbations = Gradients::leaky();
+        M::iter_tensors(&mut RecursiveWalker {
+            m: module,
+            f: &mut Perturb {
+                gradients,
+                perturbations: &mut self.perturbations,
+                scale: Some(scale),
+            },
+        })
+        .map_err(OptimizerUpdateError::DeviceError)?;
+        Ok(())
+    }
+
+    /// Moves `module` back to where it was before [Sam::first_step()], and then
+    /// updates it with the base optimizer using the `gradients` at the worst case point.
+    pub fn second_step(
+        &mut self,
+        module: &mut M,
+        gradients: &Gradients<E, D>,
+    ) -> Result<(), OptimizerUpdateError<D>> {
+        M::iter_tensors(&mut RecursiveWalker {
+            m: &mut *module,
+            f: &mut Perturb {
+                gradients,
+                perturbations: &mut self.perturbations,
+                scale: None,
+            },
+        })
+        .map_err(OptimizerUpdateError::DeviceError)?;
+        self.perturbations = Gradients::leaky();
+        self.opt.update(module, gradients)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{optim::*, shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_sam_quadratic() {
+        let dev: TestDevice = Default::default();
+        let cfg = SgdConfig {
+            lr: 0.1,
+            momentum: None,
+            weight_decay: None,
+        };
+
+        let mut sgd_w: Tensor<Rank0, TestDtype, _> = dev.tensor(1.0);
+        let mut sgd = Sgd::new(&sgd_w, cfg);
+        let grads = sgd_w.leaky_trace().square().backward();
+        sgd.update(&mut sgd_w, &grads).unwrap();
+        assert_close(&sgd_w.array(), &0.8);
+
+        let mut w: Tensor<Rank0, TestDtype, _> = dev.tensor(1.0);
+        let mut sam = Sam::new(&w, Sgd::new(&w, cfg), 0.5);
+        let grads = w.leaky_trace().square().backward();
+        sam.first_step(&mut w, &grads).unwrap();
+        assert_close(&w.array(), &1.5);
+
+        // the gradient at the worst case point is steeper, so sam moves further than sgd
+        let grads = w.leaky_trace().square().backward();
+        sam.second_step(&mut w, &grads).unwrap();
+        assert_close(&w.array(), &0.7);
+    }
+}
@@ -132,6 +132,16 @@ impl<M, E: Dtype, D: DeviceStorage> Sgd<M, E, D> {
     }
 }
 
+impl<M, E: Dtype, D: DeviceStorage> LearningRate<E> for Sgd<M, E, D> {
+    fn lr(&self) -> E {
+        self.cfg.lr
+    }
+
+    fn set_lr(&mut self, lr: E) {
+        self.cfg.lr = lr;
+    }
+}
+
 pub trait SgdKernel<E: Dtype>: DeviceStorage {
     fn update(
         &self,
@@ -435,4 +445,47 @@ mod tests {
         let mut opt = Sgd::new(&t, Default::default());
         opt.update(&mut t, &Gradients::leaky()).expect_err("");
     }
+
+    #[test]
+    fn test_sgd_gradient_accumulation() {
+        use crate::{losses::mse_loss, nn::builders::*};
+
+        let dev: TestDevice = Default::default();
+        let x1 = [[1.0, -2.0, 0.5], [0.0, 1.0, 3.0]];
+        let x2 = [[-1.0, 0.5, 2.0], [2.0, 2.0, -1.0]];
+        let y1 = [[1.0, 0.0], [0.0, 1.0]];
+        let y2 = [[0.5, 0.5], [-1.0, 1.0]];
+
+        let mut full = dev.build_module::<Linear<3, 2>, TestDtype>();
+        let mut accum = full.clone();
+        let cfg = SgdConfig {
+            lr: 1e-1,
+            momentum: None,
+            weight_decay: None,
+        };
+        let mut opt_full = Sgd::new(&full, cfg);
+        let mut opt_accum = Sgd::new(&accum, cfg);
+
+        // one update using the full batch of 4
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.tensor([x1[0], x1[1], x2[0], x2[1]]);
+        let y: Tensor<Rank2<4, 2>, TestDtype, _> = dev.tensor([y1[0], y1[1], y2[0], y2[1]]);
+        let grads = full.alloc_grads();
+        let loss = mse_loss(full.forward(x.trace(grads)), y);
+        let grads = loss.backward();
+        opt_full.update(&mut full, &grads).unwrap();
+
+        // one update accumulated over 2 half batches, each scaled by 1 / 2
+        let mut grads = accum.alloc_grads();
+        for (x, y) in [(x1, y1), (x2, y2)] {
+            let x: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor(x);
+            let loss = mse_loss(accum.forward(x.trace(grads)), dev.tensor(y)) * 0.5;
+            grads = loss.backward();
+        }
+        opt_accum.update(&mut accum, &grads).unwrap();
+        accum.zero_grads(&mut grads);
+
+        assert_close(&accum.weight.array(), &full.weight.array());
+        assert_close(&accum.bias.array(), &full.bias.array());
+        assert_eq!(grads.get(&accum.weight).array(), [[0.0; 3]; 2]);
+    }
 }
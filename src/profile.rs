@@ -0,0 +1,227 @@
+//! **Requires the "profile" feature** Records how long tensor ops take to run.
+//!
+//! The profiler is off by default, and is separate for each thread. While it is on, the
+//! forward of every tensor op that runs a kernel records its wall clock duration, and so
+//! does the backward of every op when gradients are computed.
+//!
+//! Ops are named after the kernel op they run (e.g. `"Exp"` for
+//! [crate::tensor_ops::exp()]), or otherwise after the function that runs them (e.g.
+//! `"matmul"`), with the forward and backward of an op sharing a name.
+//!
+//! ```rust
+//! # use dfdx::{prelude::*, profile};
+//! # let dev: Cpu = Default::default();
+//! let x: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+//! profile::enable();
+//! let _ = x.clone().exp() + x.square();
+//! profile::disable();
+//! for entry in profile::report() {
+//!     let pass = if entry.backward { "backward" } else { "forward" };
+//!     println!("{} {pass}: {} calls in {:?}", entry.name, entry.calls, entry.total);
+//! }
+//! profile::reset();
+//! ```
+//!
+//! Note that cuda kernels run asynchronously, so on `Cuda` this only measures
+//! how long it takes to launch each op.
+
+use std::{cell::RefCell, collections::BTreeMap, time::Duration, time::Instant, vec::Vec};
+
+/// The total time spent in one kind of op.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpProfile {
+    /// The name of the op, e.g. `"Exp"` or `"matmul"`
+    pub name: &'static str,
+    /// Whether this is the time spent in the backward of the op instead of its forward
+    pub backward: bool,
+    /// The number of times the op was run
+    pub calls: usize,
+    /// The total time spent running the op
+    pub total: Duration,
+}
+
+#[derive(Default)]
+struct Profiler {
+    enabled: bool,
+    ops: BTreeMap<(&'static str, bool), (usize, Duration)>,
+}
+
+std::thread_local! {
+    static PROFILER: RefCell<Profiler> = RefCell::new(Default::default());
+}
+
+/// Turns on the profiler for the current thread.
+pub fn enable() {
+    PROFILER.with(|p| p.borrow_mut().enabled = true);
+}
+
+/// Turns off the profiler for the current thread. Keeps everything recorded so far.
+pub fn disable() {
+    PROFILER.with(|p| p.borrow_mut().enabled = false);
+}
+
+/// Clears everything recorded on the current thread.
+pub fn reset() {
+    PROFILER.with(|p| p.borrow_mut().ops.clear());
+}
+
+/// Everything recorded on the current thread, with one entry per kind of op,
+/// sorted from the most to the least total time.
+pub fn report() -> Vec<OpProfile> {
+    let mut report: Vec<OpProfile> = PROFILER.with(|p| {
+        p.borrow()
+            .ops
+            .iter()
+            .map(|(&(name, backward), &(calls, total))| OpProfile {
+                name,
+                backward,
+                calls,
+                total,
+            })
+            .collect()
+    });
+    report.sort_by_key(|e| core::cmp::Reverse(e.total));
+    report
+}
+
+/// Runs `f`, and records its duration if the profiler is on. `closure` is the type name of
+/// the closure that runs the op, which is only turned into the name of the op (see
+/// [op_name]) while the profiler is on.
+pub(crate) fn record<R>(closure: &'static str, backward: bool, f: impl FnOnce() -> R) -> R {
+    if !PROFILER.with(|p| p.borrow().enabled) {
+        return f();
+    }
+    let start = Instant::now();
+    let r = f();
+    let elapsed = start.elapsed();
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        let key = (op_name(closure), backward);
+        let (calls, total) = p.ops.entry(key).or_default();
+        *calls += 1;
+        *total += elapsed;
+    });
+    r
+}
+
+/// The name of the op run by a closure with the type name `closure`. This is the kernel op
+/// that the function defining the closure is generic over, without its `KernelOp` suffix,
+/// e.g. `"Exp"` for a closure in `try_unary_op<dfdx::tensor_ops::exp::ExpKernelOp, ...>`.
+/// Otherwise it is the name of the function without a `try_` prefix or `_op` suffix, e.g.
+/// `"sum"` for a closure in `<Tensor<...> as SumTo>::try_sum<...>`.
+fn op_name(closure: &'static str) -> &'static str {
+    let function = path_segments(closure)
+        .filter(|s| !s.starts_with("{{closure}}"))
+        .last()
+        .unwrap_or(closure);
+    let (name, generics) = match function.find('<') {
+        Some(i) => (&function[..i], &function[i + 1..]),
+        None => (function, ""),
+    };
+    let first_generic = path_segments(generics.split(',').next().unwrap())
+        .last()
+        .unwrap();
+    let first_generic = first_generic.split(['<', '>']).next().unwrap();
+    match first_generic.strip_suffix("KernelOp") {
+        Some(op) => op,
+        None => {
+            let name = name.strip_prefix("try_").unwrap_or(name);
+            name.strip_suffix("_op").unwrap_or(name)
+        }
+    }
+}
+
+/// Splits `path` on the `::`s that aren't inside of `<>`.
+fn path_segments(path: &'static str) -> impl Iterator<Item = &'static str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut segments = Vec::new();
+    let bytes = path.as_bytes();
+    for i in 0..bytes.len() {
+        match bytes[i] {
+            b'<' => depth += 1,
+            b'>' if i == 0 || bytes[i - 1] != b'-' => depth -= 1,
+            b':' if depth == 0 && i >= start && bytes.get(i + 1) == Some(&b':') => {
+                segments.push(&path[start..i]);
+                start = i + 2;
+            }
+            _ => (),
+        }
+    }
+    segments.push(&path[start..]);
+    segments.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_profile_records_each_op() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank2<4, 2>, TestDtype, _> = dev.sample_normal();
+
+        reset();
+        let _ = a.clone().exp();
+        assert!(report().is_empty(), "nothing is recorded while disabled");
+
+        enable();
+        let _ = (a.clone().exp() + a.clone().sqrt())
+            .matmul(b.clone())
+            .sum::<Rank0, _>();
+        let _ = a.clone().exp();
+        let _ = a.clone().max::<Rank1<3>, _>();
+        let _ = a.clone().slice((1.., ..));
+        let _ = a.clone().leaky_trace().matmul(b).sum().backward();
+        disable();
+
+        let report = report();
+        let mut forward: Vec<_> = report
+            .iter()
+            .filter(|e| !e.backward)
+            .map(|e| e.name)
+            .collect();
+        forward.sort();
+        assert_eq!(
+            forward,
+            ["BinaryAdd", "Exp", "Sqrt", "matmul", "max", "slice", "sum"]
+        );
+        let mut backward: Vec<_> = report
+            .iter()
+            .filter(|e| e.backward)
+            .map(|e| e.name)
+            .collect();
+        backward.sort();
+        assert_eq!(backward, ["backward", "matmul", "sum"]);
+        let exp = report.iter().find(|e| e.name == "Exp").unwrap();
+        assert_eq!(exp.calls, 2);
+        let matmul = report.iter().find(|e| e.name == "matmul").unwrap();
+        assert_eq!(matmul.calls, 2);
+        assert!(report.windows(2).all(|w| w[0].total >= w[1].total));
+
+        reset();
+        assert!(super::report().is_empty());
+    }
+
+    #[test]
+    fn test_op_name() {
+        assert_eq!(
+            op_name("dfdx::tensor_ops::utilities::ops::try_unary_op<dfdx::tensor_ops::exp::ExpKernelOp, (dfdx::shapes::Const<3>,), f32>::{{closure}}"),
+            "Exp"
+        );
+        assert_eq!(
+            op_name("dfdx::tensor_ops::utilities::ops::try_binary_op<dfdx::tensor_ops::add::ScalarAddKernelOp<f32>, f32>::{{closure}}::{{closure}}"),
+            "ScalarAdd"
+        );
+        assert_eq!(
+            op_name("<dfdx::tensor::Tensor<(dfdx::shapes::Const<3>,), f32, dfdx::tensor::Cpu> as dfdx::tensor_ops::sum_to::SumTo>::try_sum<(), dfdx::shapes::Axis<0>>::{{closure}}"),
+            "sum"
+        );
+        assert_eq!(
+            op_name("dfdx::tensor_ops::matmul::try_matmul_op<(dfdx::shapes::Const<3>,), f32>::{{closure}}"),
+            "matmul"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use super::{
+    axes::Axis,
+    broadcasts::ReduceShape,
+    shape::{Const, Dim, Shape},
+};
+
+/// Marker for shapes that can have [Axis] `Ax` collapsed to a size 1 dimension,
+/// instead of removing it like [ReduceShape]. See Self::KeptDim for the resulting type.
+pub trait KeepDimShape<Ax>: ReduceShape<Ax> {
+    type KeptDim: Shape;
+
+    /// Self with the dimension at `Ax` replaced with `Const<1>`.
+    fn keep_dim(&self) -> Self::KeptDim;
+}
+
+macro_rules! keep_dim {
+    (($($D:ident),*), $Ax:tt, ($($K:ty),*)) => {
+        impl<$($D: Dim, )*> KeepDimShape<Axis<$Ax>> for ($($D, )*) {
+            type KeptDim = ($($K, )*);
+            #[inline(always)]
+            fn keep_dim(&self) -> Self::KeptDim {
+                let mut dims = self.concrete();
+                dims[$Ax] = 1;
+                Self::KeptDim::from_concrete(&dims).unwrap()
+            }
+        }
+    };
+}
+
+type One = Const<1>;
+
+keep_dim!((A), 0, (One));
+
+keep_dim!((A, B), 0, (One, B));
+keep_dim!((A, B), 1, (A, One));
+
+keep_dim!((A, B, C), 0, (One, B, C));
+keep_dim!((A, B, C), 1, (A, One, C));
+keep_dim!((A, B, C), 2, (A, B, One));
+
+keep_dim!((A, B, C, D), 0, (One, B, C, D));
+keep_dim!((A, B, C, D), 1, (A, One, C, D));
+keep_dim!((A, B, C, D), 2, (A, B, One, D));
+keep_dim!((A, B, C, D), 3, (A, B, C, One));
+
+keep_dim!((A, B, C, D, E), 0, (One, B, C, D, E));
+keep_dim!((A, B, C, D, E), 1, (A, One, C, D, E));
+keep_dim!((A, B, C, D, E), 2, (A, B, One, D, E));
+keep_dim!((A, B, C, D, E), 3, (A, B, C, One, E));
+keep_dim!((A, B, C, D, E), 4, (A, B, C, D, One));
+
+keep_dim!((A, B, C, D, E, F), 0, (One, B, C, D, E, F));
+keep_dim!((A, B, C, D, E, F), 1, (A, One, C, D, E, F));
+keep_dim!((A, B, C, D, E, F), 2, (A, B, One, D, E, F));
+keep_dim!((A, B, C, D, E, F), 3, (A, B, C, One, E, F));
+keep_dim!((A, B, C, D, E, F), 4, (A, B, C, D, One, F));
+keep_dim!((A, B, C, D, E, F), 5, (A, B, C, D, E, One));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_dim() {
+        let s: (usize, Const<3>, usize) = (2, Const, 4);
+        assert_eq!(KeepDimShape::<Axis<1>>::keep_dim(&s), (2, Const::<1>, 4));
+        let s: (Const<2>, usize) = (Const, 5);
+        let k: (Const<2>, Const<1>) = KeepDimShape::<Axis<1>>::keep_dim(&s);
+        assert_eq!(k.concrete(), [2, 1]);
+    }
+}
@@ -11,6 +11,7 @@
 
 mod axes;
 mod broadcasts;
+mod keep_dim;
 mod permutes;
 mod realize;
 mod replace_dim;
@@ -22,6 +23,7 @@ pub(crate) use axes::Axes;
 pub(crate) use broadcasts::{
     BroadcastShapeTo, BroadcastStridesTo, ReduceShape, ReduceShapeTo, ReduceStridesTo,
 };
+pub(crate) use keep_dim::KeepDimShape;
 pub(crate) use permutes::{PermuteShapeTo, PermuteStridesTo};
 pub(crate) use realize::RealizeShapeTo;
 pub(crate) use replace_dim::{RemoveDimTo, ReplaceDimTo};
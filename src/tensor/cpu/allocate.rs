@@ -22,6 +22,11 @@ impl Cpu {
         numel: usize,
         elem: E,
     ) -> Result<Vec<E>, CpuError> {
+        if let Some(mut data) = self.pool.try_take::<E>(numel) {
+            data.fill(elem);
+            return Ok(data);
+        }
+
         #[cfg(feature = "fast-alloc")]
         {
             Ok(std::vec![elem; numel])
@@ -138,15 +143,11 @@ impl<E: Unit> SampleTensor<E> for Cpu {
         distr: D,
     ) -> Result<Tensor<S::Shape, E, Self>, Self::Err> {
         let mut tensor = self.try_zeros_like(src)?;
-        {
-            #[cfg(not(feature = "no-std"))]
-            let mut rng = self.rng.lock().unwrap();
-            #[cfg(feature = "no-std")]
-            let mut rng = self.rng.lock();
+        self.with_rng(|rng| {
             for v in Arc::get_mut(&mut tensor.data).unwrap().iter_mut() {
                 *v = rng.sample(&distr);
             }
-        }
+        });
         Ok(tensor)
     }
     fn try_fill_with_distr<D: Distribution<E>>(
@@ -154,15 +155,11 @@ impl<E: Unit> SampleTensor<E> for Cpu {
         storage: &mut Self::Vec<E>,
         distr: D,
     ) -> Result<(), Self::Err> {
-        {
-            #[cfg(not(feature = "no-std"))]
-            let mut rng = self.rng.lock().unwrap();
-            #[cfg(feature = "no-std")]
-            let mut rng = self.rng.lock();
+        self.with_rng(|rng| {
             for v in storage.iter_mut() {
                 *v = rng.sample(&distr);
             }
-        }
+        });
         Ok(())
     }
 }
@@ -1,5 +1,7 @@
 use crate::shapes::{Shape, Unit};
 use crate::tensor::{cpu::LendingIterator, storage_traits::*, Tensor};
+
+use super::TensorPool;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::{sync::Arc, vec::Vec};
 
@@ -17,12 +19,14 @@ use std::sync::Mutex;
 #[derive(Clone, Debug)]
 pub struct Cpu {
     pub(crate) rng: Arc<Mutex<StdRng>>,
+    pub(crate) pool: Arc<TensorPool>,
 }
 
 impl Default for Cpu {
     fn default() -> Self {
         Self {
             rng: Arc::new(Mutex::new(StdRng::seed_from_u64(0))),
+            pool: Default::default(),
         }
     }
 }
@@ -32,8 +36,24 @@ impl Cpu {
     pub fn seed_from_u64(seed: u64) -> Self {
         Self {
             rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
+            pool: Default::default(),
         }
     }
+
+    /// Calls `f` with the deterministic rng if it is on (see [crate::tensor::set_deterministic]),
+    /// otherwise with this device's rng.
+    pub(crate) fn with_rng<R>(&self, f: impl FnOnce(&mut StdRng) -> R) -> R {
+        #[cfg(feature = "std")]
+        let f = match crate::tensor::with_deterministic_rng(f) {
+            Ok(r) => return r,
+            Err(f) => f,
+        };
+        #[cfg(not(feature = "no-std"))]
+        let mut rng = self.rng.lock().unwrap();
+        #[cfg(feature = "no-std")]
+        let mut rng = self.rng.lock();
+        f(&mut rng)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -42,6 +62,8 @@ pub enum CpuError {
     OutOfMemory,
     /// Not enough elements were provided when creating a tensor
     WrongNumElements,
+    /// Reading or writing a file failed
+    Io,
 }
 
 impl std::fmt::Display for CpuError {
@@ -49,6 +71,7 @@ impl std::fmt::Display for CpuError {
         match self {
             Self::OutOfMemory => f.write_str("CpuError::OutOfMemory"),
             Self::WrongNumElements => f.write_str("CpuError::WrongNumElements"),
+            Self::Io => f.write_str("CpuError::Io"),
         }
     }
 }
@@ -68,14 +91,7 @@ impl DeviceStorage for Cpu {
     }
 
     fn random_u64(&self) -> u64 {
-        #[cfg(not(feature = "no-std"))]
-        {
-            self.rng.lock().unwrap().gen()
-        }
-        #[cfg(feature = "no-std")]
-        {
-            self.rng.lock().gen()
-        }
+        self.with_rng(|rng| rng.gen())
     }
 
     fn tensor_to_vec<S: Shape, E: Unit, T>(&self, tensor: &Tensor<S, E, Self, T>) -> Vec<E> {
@@ -87,6 +103,10 @@ impl DeviceStorage for Cpu {
         buf
     }
 
+    fn len<E: Unit>(storage: &Self::Vec<E>) -> Option<usize> {
+        Some(storage.len())
+    }
+
     fn try_synchronize(&self) -> Result<(), Self::Err> {
         Ok(())
     }
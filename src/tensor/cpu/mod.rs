@@ -2,8 +2,10 @@ mod allocate;
 mod device;
 mod index;
 mod iterate;
+mod pool;
 
 pub(crate) use index::index_to_i;
 pub(crate) use iterate::{LendingIterator, NdIndex};
 
 pub use device::{Cpu, CpuError};
+pub use pool::TensorPool;
@@ -0,0 +1,205 @@
+use crate::{shapes::*, tensor::Tensor};
+
+use super::Cpu;
+
+use std::{
+    any::{Any, TypeId},
+    boxed::Box,
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    vec::Vec,
+};
+
+#[cfg(feature = "no-std")]
+use spin::Mutex;
+
+#[cfg(not(feature = "no-std"))]
+use std::sync::Mutex;
+
+/// A pool of buffers that [Cpu] allocations are served from, to reduce allocator
+/// pressure in loops that repeatedly create tensors of the same sizes.
+///
+/// The pool is disabled by default. When enabled, buffers are handed back to the pool
+/// with [TensorPool::recycle()], and any allocation of the same dtype & number of elements
+/// will re-use one of them instead of allocating.
+///
+/// **Recycling is manual.** Dropping a tensor frees its buffer as usual, it does not
+/// return it to the pool. So only the buffers passed to [TensorPool::recycle()] are ever
+/// re-used, and enabling the pool without recycling anything only adds the cost of the lock.
+///
+/// Every clone of a [Cpu] shares the same pool. While the pool is disabled, allocations
+/// only check an atomic flag, and never take the pool's lock. When it is enabled, every
+/// allocation takes the lock, which can cost more than the allocator for small tensors
+/// (see `benches/tensor_pool.rs`).
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// let dev: Cpu = Default::default();
+/// dev.pool().enable();
+/// let a: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+/// let b = a.clone() + a.clone();
+/// dev.pool().recycle(b);
+/// let c = a.clone() + a.clone(); // re-uses b's buffer
+/// assert_eq!(dev.pool().num_reused(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct TensorPool {
+    enabled: AtomicBool,
+    state: Mutex<PoolState>,
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    buffers: BTreeMap<(TypeId, usize), Vec<Box<dyn Any + Send + Sync>>>,
+    num_allocated: usize,
+    num_reused: usize,
+}
+
+impl TensorPool {
+    fn state(&self) -> impl std::ops::DerefMut<Target = PoolState> + '_ {
+        #[cfg(not(feature = "no-std"))]
+        {
+            self.state.lock().unwrap()
+        }
+        #[cfg(feature = "no-std")]
+        {
+            self.state.lock()
+        }
+    }
+
+    /// Start serving allocations from the pool.
+    pub fn enable(&self) {
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop using the pool, and free all buffers it holds.
+    pub fn disable(&self) {
+        self.enabled.store(false, Ordering::Relaxed);
+        self.state().buffers.clear();
+    }
+
+    /// Whether the pool is currently enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Hands the buffer of `t` back to the pool. If the buffer is shared with
+    /// another tensor (e.g. a clone of `t`), or the pool is disabled, it is just dropped.
+    pub fn recycle<S: Shape, E: Unit, T>(&self, t: Tensor<S, E, Cpu, T>) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let Ok(buf) = Arc::try_unwrap(t.data) {
+            self.state()
+                .buffers
+                .entry((TypeId::of::<E>(), buf.len()))
+                .or_default()
+                .push(Box::new(buf));
+        }
+    }
+
+    /// The number of allocations made while the pool was enabled
+    /// that were **not** served from the pool.
+    pub fn num_allocated(&self) -> usize {
+        self.state().num_allocated
+    }
+
+    /// The number of allocations that were served from the pool.
+    pub fn num_reused(&self) -> usize {
+        self.state().num_reused
+    }
+
+    /// The number of buffers currently held by the pool.
+    pub fn num_cached(&self) -> usize {
+        self.state().buffers.values().map(|b| b.len()).sum()
+    }
+
+    /// Takes a buffer with `numel` elements out of the pool if one is available.
+    #[inline]
+    pub(crate) fn try_take<E: Unit>(&self, numel: usize) -> Option<Vec<E>> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let mut state = self.state();
+        let buf = state
+            .buffers
+            .get_mut(&(TypeId::of::<E>(), numel))
+            .and_then(|bufs| bufs.pop());
+        match buf {
+            Some(buf) => {
+                state.num_reused += 1;
+                Some(*buf.downcast::<Vec<E>>().unwrap())
+            }
+            None => {
+                state.num_allocated += 1;
+                None
+            }
+        }
+    }
+}
+
+impl Cpu {
+    /// The [TensorPool] shared by this device and all of its clones.
+    pub fn pool(&self) -> &TensorPool {
+        self.pool.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*};
+
+    #[test]
+    fn test_pool_disabled_by_default() {
+        let dev: Cpu = Default::default();
+        let a: Tensor<Rank1<5>, f32, _> = dev.zeros();
+        let b = a.clone() + a.clone();
+        dev.pool().recycle(b);
+        assert!(!dev.pool().is_enabled());
+        assert_eq!(dev.pool().num_cached(), 0);
+        assert_eq!(dev.pool().num_allocated(), 0);
+        assert_eq!(dev.pool().num_reused(), 0);
+    }
+
+    #[test]
+    fn test_pool_reuses_buffers_after_warmup() {
+        let dev: Cpu = Default::default();
+        dev.pool().enable();
+        let a: Tensor<Rank2<4, 3>, f32, _> = dev.sample_normal();
+        let b: Tensor<Rank2<3, 5>, f32, _> = dev.sample_normal();
+        let expected = (a.clone() * 2.0).matmul(b.clone()).array();
+
+        let mut allocated = std::vec::Vec::new();
+        for _ in 0..10 {
+            let c = a.clone() * 2.0;
+            let d = c.clone().matmul(b.clone());
+            assert_eq!(d.array(), expected);
+            dev.pool().recycle(c);
+            dev.pool().recycle(d);
+            allocated.push(dev.pool().num_allocated());
+        }
+        // after the first iteration, every allocation is served by the pool
+        assert!(allocated.iter().all(|&n| n == allocated[0]));
+        assert!(dev.pool().num_reused() > 0);
+    }
+
+    #[test]
+    fn test_pool_keeps_dtypes_separate() {
+        let dev: Cpu = Default::default();
+        dev.pool().enable();
+        let a: Tensor<Rank1<3>, f32, _> = dev.ones();
+        dev.pool().recycle(a);
+        let b: Tensor<Rank1<3>, f64, _> = dev.ones();
+        assert_eq!(b.array(), [1.0; 3]);
+        assert_eq!(dev.pool().num_reused(), 0);
+        let c: Tensor<Rank1<3>, f32, _> = dev.zeros();
+        assert_eq!(c.array(), [0.0; 3]);
+        assert_eq!(dev.pool().num_reused(), 1);
+
+        dev.pool().disable();
+        assert_eq!(dev.pool().num_cached(), 0);
+    }
+}
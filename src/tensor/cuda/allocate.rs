@@ -114,10 +114,8 @@ where
     ) -> Result<Tensor<S::Shape, E, Self>, Self::Err> {
         let shape = *src.shape();
         let mut buf = Vec::with_capacity(shape.num_elements());
-        {
-            let mut rng = self.cpu.rng.lock().unwrap();
-            buf.resize_with(shape.num_elements(), || rng.sample(&distr));
-        }
+        self.cpu
+            .with_rng(|rng| buf.resize_with(shape.num_elements(), || rng.sample(&distr)));
         self.tensor_from_host_buf::<S::Shape, E>(shape, buf)
     }
     fn try_fill_with_distr<D: rand_distr::Distribution<E>>(
@@ -126,10 +124,8 @@ where
         distr: D,
     ) -> Result<(), Self::Err> {
         let mut buf = Vec::with_capacity(storage.len());
-        {
-            let mut rng = self.cpu.rng.lock().unwrap();
-            buf.resize_with(storage.len(), || rng.sample(&distr));
-        }
+        self.cpu
+            .with_rng(|rng| buf.resize_with(storage.len(), || rng.sample(&distr)));
         self.dev.htod_copy_into(buf, storage)?;
         Ok(())
     }
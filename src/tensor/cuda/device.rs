@@ -136,6 +136,10 @@ impl DeviceStorage for Cuda {
         contiguous
     }
 
+    fn len<E: Unit>(storage: &Self::Vec<E>) -> Option<usize> {
+        Some(storage.len())
+    }
+
     fn try_synchronize(&self) -> Result<(), CudaError> {
         self.dev.synchronize().map_err(CudaError::from)
     }
@@ -0,0 +1,90 @@
+//! A thread-local random number generator that overrides each device's own rng,
+//! so runs are reproducible no matter how many devices are created or how they are seeded.
+
+use rand::{rngs::StdRng, SeedableRng};
+use std::cell::RefCell;
+
+std::thread_local! {
+    static RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Turns on deterministic mode for the current thread: every op that uses randomness
+/// (sampling, parameter initialization, dropout, etc.) reads from a single thread-local
+/// rng seeded with `seed`, instead of the rng of the device it runs on.
+///
+/// Calling this again re-seeds the rng, so the same sequence of ops produces the same
+/// values after every call with the same seed.
+///
+/// Reductions on [super::Cpu] always add elements in a fixed order, and threaded matmuls
+/// split the work by output element, so they're already deterministic.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor::set_deterministic};
+/// set_deterministic(0);
+/// let a: Tensor<Rank1<3>, f32, _> = Cpu::seed_from_u64(1).sample_normal();
+/// set_deterministic(0);
+/// let b: Tensor<Rank1<3>, f32, _> = Cpu::seed_from_u64(2).sample_normal();
+/// assert_eq!(a.array(), b.array());
+/// # dfdx::tensor::unset_deterministic();
+/// ```
+pub fn set_deterministic(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = Some(StdRng::seed_from_u64(seed)));
+}
+
+/// Turns off deterministic mode for the current thread, so each device uses its own rng again.
+pub fn unset_deterministic() {
+    RNG.with(|rng| *rng.borrow_mut() = None);
+}
+
+/// Whether [set_deterministic()] is on for the current thread.
+pub fn is_deterministic() -> bool {
+    RNG.with(|rng| rng.borrow().is_some())
+}
+
+/// Calls `f` with the deterministic rng if it is on, otherwise gives `f` back.
+pub(crate) fn with_deterministic_rng<R, F: FnOnce(&mut StdRng) -> R>(f: F) -> Result<R, F> {
+    RNG.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(rng) => Ok(f(rng)),
+        None => Err(f),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{optim::*, prelude::*, tests::*};
+
+    /// The gradients of one sgd step, and the weights after it
+    fn train_step(dev: &Cpu) -> std::vec::Vec<std::vec::Vec<TestDtype>> {
+        let mut model = dev.build_module::<(Linear<4, 8>, Dropout, Linear<8, 2>), TestDtype>();
+        let x: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let grads = model
+            .forward_mut(x.leaky_trace())
+            .square()
+            .mean()
+            .backward();
+        let mut opt = Sgd::new(&model, Default::default());
+        opt.update(&mut model, &grads).unwrap();
+        vec![
+            grads.get(&model.0.weight).as_vec(),
+            grads.get(&model.2.weight).as_vec(),
+            model.0.weight.as_vec(),
+        ]
+    }
+
+    #[test]
+    fn test_deterministic_training_step() {
+        set_deterministic(7);
+        assert!(is_deterministic());
+        let a = train_step(&Cpu::seed_from_u64(1));
+        set_deterministic(7);
+        let b = train_step(&Cpu::seed_from_u64(2));
+        unset_deterministic();
+        assert!(!is_deterministic());
+        assert_eq!(a, b);
+
+        let c = train_step(&Cpu::seed_from_u64(1));
+        let d = train_step(&Cpu::seed_from_u64(2));
+        assert_ne!(c, d);
+    }
+}
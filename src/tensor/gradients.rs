@@ -2,13 +2,18 @@
 #![allow(clippy::type_complexity)]
 
 use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "graphviz")]
+use std::string::String;
 use std::{boxed::Box, vec::Vec};
 
 use super::{
     storage_traits::{AllocGrad, DeviceStorage},
     unique_id, Tensor, UniqueId,
 };
-use crate::shapes::{Shape, Unit};
+use crate::{
+    shapes::{Dtype, Shape, Unit},
+    tensor_ops::{axpy::AxpyKernel, Device},
+};
 
 /// A generic container for keeping gradients of tensors keyed by the
 /// tensor's [UniqueId].
@@ -20,8 +25,9 @@ use crate::shapes::{Shape, Unit};
 /// 4. Access mutable references to arrays
 #[derive(Clone, Debug)]
 pub struct Gradients<E: Unit, D: DeviceStorage> {
-    /// Using BTreeMap for no-std support
-    gradient_by_id: BTreeMap<UniqueId, D::Vec<E>>,
+    /// Using BTreeMap for no-std support. The shape of each gradient is kept
+    /// so [Gradients::try_accumulate] can check that they match.
+    gradient_by_id: BTreeMap<UniqueId, (D::Vec<E>, GradShape)>,
     /// Using BTreeSet for no-std support
     leaf_ids: Option<BTreeSet<UniqueId>>,
 }
@@ -57,7 +63,7 @@ impl<E: Unit, D: DeviceStorage> Gradients<E, D> {
     /// Inserts a gradient for `t`
     pub(crate) fn try_alloc_for<S: Shape>(&mut self, t: &Tensor<S, E, D>) -> Result<(), D::Err> {
         if let std::collections::btree_map::Entry::Vacant(e) = self.gradient_by_id.entry(t.id) {
-            e.insert(t.try_alloc_grad()?);
+            e.insert((t.try_alloc_grad()?, GradShape::of(&t.shape)));
         }
         Ok(())
     }
@@ -70,6 +76,14 @@ impl<E: Unit, D: DeviceStorage> Gradients<E, D> {
         self.drop_non_leafs();
     }
 
+    /// Marks `id` so that its gradient is kept by [Gradients::drop_non_leafs]. Does nothing
+    /// if leaf ids aren't tracked (i.e. for [Gradients::leaky]), since then all gradients are kept.
+    pub(crate) fn retain(&mut self, id: UniqueId) {
+        if let Some(leafs) = &mut self.leaf_ids {
+            leafs.insert(id);
+        }
+    }
+
     /// Keeps all gradients marked previously by [Gradients::retain_leafs], and drops all
     /// others.
     pub fn drop_non_leafs(&mut self) {
@@ -78,26 +92,42 @@ impl<E: Unit, D: DeviceStorage> Gradients<E, D> {
         }
     }
 
+    /// The number of bytes used by all the gradients currently stored.
+    pub(crate) fn memory_footprint(&self) -> usize {
+        self.gradient_by_id
+            .values()
+            .map(|(g, shape)| {
+                D::len(g).unwrap_or_else(|| shape.num_elements()) * std::mem::size_of::<E>()
+            })
+            .sum()
+    }
+
+    /// Inserts `grad` as the gradient for `t`, replacing any existing gradient.
+    pub(crate) fn insert<S: Shape, T>(&mut self, t: &Tensor<S, E, D, T>, grad: D::Vec<E>) {
+        self.gradient_by_id
+            .insert(t.id, (grad, GradShape::of(&t.shape)));
+    }
+
     /// Returns a reference to the underlying gradient if found.
     pub(crate) fn get_ref_checked<S: Shape, T>(
         &self,
         t: &Tensor<S, E, D, T>,
     ) -> Option<&D::Vec<E>> {
-        self.gradient_by_id.get(&t.id)
+        self.gradient_by_id.get(&t.id).map(|(g, _)| g)
     }
 
     /// Returns a mutable reference to the data associated with `t`.
     ///
     /// **Panics** if data associated with `t` is not found. This indicates an unrecoverable bug.
     pub(crate) fn get_mut<S: Shape, T>(&mut self, t: &Tensor<S, E, D, T>) -> &mut D::Vec<E> {
-        self.gradient_by_id.get_mut(&t.id).unwrap()
+        &mut self.gradient_by_id.get_mut(&t.id).unwrap().0
     }
 
     /// Returns a mutable reference to the data associated with `t`.
     ///
     /// **Panics** if data associated with `t` is not found. This indicates an unrecoverable bug.
     pub(crate) fn get_ref<S: Shape, T>(&mut self, t: &Tensor<S, E, D, T>) -> &D::Vec<E> {
-        self.gradient_by_id.get(&t.id).unwrap()
+        &self.gradient_by_id.get(&t.id).unwrap().0
     }
 
     /// Clones the gradient and transforms it into a tensor.
@@ -106,7 +136,7 @@ impl<E: Unit, D: DeviceStorage> Gradients<E, D> {
     /// If no data is associated with `t` yet, this will panic due to an unwrap()
     /// on a .get() to the underlying hashmap.
     pub fn get<S: Shape, T>(&self, t: &Tensor<S, E, D, T>) -> Tensor<S, E, D> {
-        let buf = self.gradient_by_id.get(&t.id).unwrap().clone();
+        let buf = self.gradient_by_id.get(&t.id).unwrap().0.clone();
         Tensor {
             id: unique_id(),
             data: std::sync::Arc::new(buf),
@@ -178,12 +208,150 @@ impl<E: Unit, D: DeviceStorage> Gradients<E, D> {
     }
 }
 
+impl<E: Dtype, D: Device<E>> Gradients<E, D> {
+    /// Adds every gradient in `other` into `self`, e.g. to accumulate the gradients of several
+    /// micro batches before a single optimizer update. Gradients that are only in `other` are
+    /// moved into `self`.
+    ///
+    /// `self` keeps its leak setting: if it is [Gradients::leaky] it stays leaky, and otherwise
+    /// the leaf ids of `other` are added to its own. Note that if `self` is not leaky but `other`
+    /// is, [Gradients::drop_non_leafs] on the result will drop the gradients that came from
+    /// `other` for tensors that aren't leafs of `self`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let w: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, 2.0, 3.0]);
+    /// let mut grads = w.leaky_trace().sum().backward();
+    /// grads.accumulate(&dev, (w.leaky_trace() * 2.0).sum().backward());
+    /// assert_eq!(grads.get(&w).array(), [3.0; 3]);
+    /// ```
+    ///
+    /// **Panics** if [Gradients::try_accumulate] fails.
+    pub fn accumulate(&mut self, device: &D, other: Self) {
+        self.try_accumulate(device, other).unwrap()
+    }
+
+    /// Fallible version of [Gradients::accumulate]. Returns [AccumulateError::ShapeMismatch]
+    /// without changing `self` if a gradient in both has a different shape in each.
+    pub fn try_accumulate(
+        &mut self,
+        device: &D,
+        other: Self,
+    ) -> Result<(), AccumulateError<D::Err>> {
+        for (id, (_, shape)) in other.gradient_by_id.iter() {
+            match self.gradient_by_id.get(id) {
+                Some((_, dst_shape)) if dst_shape != shape => {
+                    return Err(AccumulateError::ShapeMismatch(*id));
+                }
+                _ => (),
+            }
+        }
+        if let (Some(leafs), Some(other_leafs)) = (&mut self.leaf_ids, other.leaf_ids.as_ref()) {
+            leafs.extend(other_leafs);
+        }
+        self.try_add_where(device, other, |_| true)?;
+        Ok(())
+    }
+}
+
+impl<E: Dtype, D: AxpyKernel<E>> Gradients<E, D> {
+    /// Adds the gradients in `other` whose id passes `keep` into `self`, moving the ones
+    /// `self` doesn't have yet. Unlike [Gradients::try_accumulate] this doesn't check shapes
+    /// or touch the leaf ids, so it's meant for merging the gradients of a recomputed
+    /// sub graph during backward (see [crate::nn::modules::CheckpointedRepeated]).
+    pub(crate) fn try_add_where<F: Fn(UniqueId) -> bool>(
+        &mut self,
+        device: &D,
+        other: Self,
+        keep: F,
+    ) -> Result<(), D::Err> {
+        for (id, (grad, shape)) in other.gradient_by_id {
+            if !keep(id) {
+                continue;
+            }
+            match self.gradient_by_id.get_mut(&id) {
+                Some((dst, _)) => AxpyKernel::forward(device, dst, E::ONE, &grad, E::ONE)?,
+                None => {
+                    self.gradient_by_id.insert(id, (grad, shape));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The shape of a gradient, i.e. the concrete shape of its tensor, padded with 0s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct GradShape {
+    num_dims: usize,
+    dims: [usize; 6],
+}
+
+impl GradShape {
+    fn of<S: Shape>(shape: &S) -> Self {
+        let mut dims = [0; 6];
+        dims[..S::NUM_DIMS].copy_from_slice(shape.concrete().as_ref());
+        Self {
+            num_dims: S::NUM_DIMS,
+            dims,
+        }
+    }
+
+    fn num_elements(&self) -> usize {
+        self.dims[..self.num_dims].iter().product()
+    }
+}
+
+/// An error from [Gradients::try_accumulate].
+#[derive(Debug)]
+pub enum AccumulateError<Err> {
+    /// Both [Gradients] have a gradient for the tensor with this id, but with different shapes.
+    ShapeMismatch(UniqueId),
+    /// The device failed to add the gradients.
+    Device(Err),
+}
+
+impl<Err> From<Err> for AccumulateError<Err> {
+    fn from(e: Err) -> Self {
+        Self::Device(e)
+    }
+}
+
+impl<Err: std::fmt::Display> std::fmt::Display for AccumulateError<Err> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ShapeMismatch(id) => write!(fmt, "gradients for {id:?} have different shapes"),
+            Self::Device(err) => write!(fmt, "{err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Err: std::fmt::Debug + std::fmt::Display> std::error::Error for AccumulateError<Err> {}
+
 /// Contains a [Gradients] and list of backward operations.
 pub struct OwnedTape<E: Unit, D: DeviceStorage> {
     /// A list of (Time, BackwardOp) pairs. The Time is used to ensure operations
     /// from merged tapes are executed in the correct order.
     pub(crate) operations: Vec<(UniqueId, BackwardOp<E, D, D::Err>)>,
     pub(crate) gradients: Gradients<E, D>,
+    /// Describes each operation for [OwnedTape::to_dot].
+    #[cfg(feature = "graphviz")]
+    pub(crate) graph: Vec<OpNode>,
+    /// Tensors that had gradients allocated since the last backward operation was added.
+    #[cfg(feature = "graphviz")]
+    pub(crate) pending_ids: Vec<UniqueId>,
+}
+
+/// The tensors that a single backward operation allocated gradients for.
+/// By convention the output of the operation is allocated last.
+#[cfg(feature = "graphviz")]
+#[derive(Debug)]
+pub(crate) struct OpNode {
+    time: UniqueId,
+    name: &'static str,
+    tensor_ids: Vec<UniqueId>,
 }
 
 impl<E: Unit, D: DeviceStorage> Default for OwnedTape<E, D> {
@@ -191,6 +359,10 @@ impl<E: Unit, D: DeviceStorage> Default for OwnedTape<E, D> {
         Self {
             operations: Default::default(),
             gradients: Gradients::leaky(),
+            #[cfg(feature = "graphviz")]
+            graph: Default::default(),
+            #[cfg(feature = "graphviz")]
+            pending_ids: Default::default(),
         }
     }
 }
@@ -207,6 +379,9 @@ impl<E: Unit, D: DeviceStorage> std::fmt::Debug for OwnedTape<E, D> {
 impl<E: Unit, D: DeviceStorage> OwnedTape<E, D> {
     /// Compute the [Gradients]! This just runs all the operations on a new [Gradients] struct.
     ///
+    /// The operations are run in a flat loop (last in, first out), so the depth of
+    /// the graph doesn't affect the stack depth.
+    ///
     /// Note that this method takes ownership of self, so it can't be called twice!
     pub(crate) fn execute(mut self) -> Result<Gradients<E, D>, D::Err> {
         // We must ensure that the operations are sorted in execution time order.
@@ -218,6 +393,64 @@ impl<E: Unit, D: DeviceStorage> OwnedTape<E, D> {
         }
         Ok(self.gradients)
     }
+
+    /// **Requires the "graphviz" feature** Exports the recorded operations as a
+    /// [graphviz](https://graphviz.org/) DOT graph.
+    ///
+    /// There is one node per backward operation, labeled with the function that recorded it,
+    /// and an edge from an operation to every operation that uses its output.
+    /// Each edge is labeled with the id of that tensor.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let x: Tensor<Rank1<3>, f32, _> = dev.zeros();
+    /// let (_, tape) = x.leaky_trace().relu().exp().split_tape();
+    /// let dot = tape.to_dot();
+    /// assert!(dot.starts_with("digraph"));
+    /// ```
+    #[cfg(feature = "graphviz")]
+    pub fn to_dot(&self) -> String {
+        let mut nodes: Vec<&OpNode> = self.graph.iter().collect();
+        nodes.sort_by_key(|n| n.time);
+        let mut dot = String::from("digraph {\n");
+        for (i, node) in nodes.iter().enumerate() {
+            dot.push_str(&format!("    op{i} [label=\"{}\"];\n", op_label(node.name)));
+        }
+        for (i, src) in nodes.iter().enumerate() {
+            if let Some(out_id) = src.tensor_ids.last() {
+                for (j, dst) in nodes.iter().enumerate().skip(i + 1) {
+                    let inputs = dst.tensor_ids.split_last().map_or(&[][..], |(_, ids)| ids);
+                    if inputs.contains(out_id) {
+                        dot.push_str(&format!("    op{i} -> op{j} [label=\"{out_id:?}\"];\n"));
+                    }
+                }
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+/// Turns the type name of a backward closure into a short label, e.g.
+/// `dfdx::tensor_ops::utilities::ops::try_unary_op<dfdx::tensor_ops::relu::ReLUKernelOp, ...>::{{closure}}`
+/// becomes `try_unary_op<ReLUKernelOp>`.
+#[cfg(feature = "graphviz")]
+fn op_label(type_name: &str) -> String {
+    let type_name = type_name.trim_end_matches("::{{closure}}");
+    let (path, generics) = match type_name.find('<') {
+        Some(i) => (&type_name[..i], Some(&type_name[i + 1..])),
+        None => (type_name, None),
+    };
+    let name = path.rsplit("::").next().unwrap();
+    let first_generic = generics
+        .map(|g| g.split([',', '<', '>']).next().unwrap().trim())
+        .filter(|g| g.starts_with(char::is_alphabetic))
+        .map(|g| g.rsplit("::").next().unwrap());
+    match first_generic {
+        Some(g) => format!("{name}<{g}>"),
+        None => String::from(name),
+    }
 }
 
 type BackwardOp<E, D, Err> = Box<dyn FnOnce(&mut Gradients<E, D>) -> Result<(), Err>>;
@@ -234,6 +467,10 @@ pub trait Tape<E: Unit, D: DeviceStorage>: Default + Merge<Self> + Merge<NoneTap
     where
         F: 'static + FnOnce(&mut Gradients<E, D>) -> Result<(), D::Err>;
     fn try_alloc_grad<S: Shape>(&mut self, t: &Tensor<S, E, D>) -> Result<(), D::Err>;
+    /// An estimate of the number of bytes used by the recorded operations and gradients.
+    fn memory_footprint(&self) -> usize {
+        0
+    }
 }
 
 impl<E: Unit, D: DeviceStorage> Tape<E, D> for OwnedTape<E, D> {
@@ -242,11 +479,42 @@ impl<E: Unit, D: DeviceStorage> Tape<E, D> for OwnedTape<E, D> {
     where
         F: 'static + FnOnce(&mut Gradients<E, D>) -> Result<(), D::Err>,
     {
-        self.operations.push((unique_id(), Box::new(operation)));
+        let time = unique_id();
+        #[cfg(feature = "graphviz")]
+        self.graph.push(OpNode {
+            time,
+            name: std::any::type_name::<F>(),
+            tensor_ids: std::mem::take(&mut self.pending_ids),
+        });
+        #[cfg(feature = "profile")]
+        let operation = {
+            let closure = std::any::type_name::<F>();
+            move |grads: &mut Gradients<E, D>| {
+                crate::profile::record(closure, true, || operation(grads))
+            }
+        };
+        self.operations.push((time, Box::new(operation)));
     }
     fn try_alloc_grad<S: Shape>(&mut self, t: &Tensor<S, E, D>) -> Result<(), D::Err> {
+        #[cfg(feature = "graphviz")]
+        self.pending_ids.push(t.id);
         self.gradients.try_alloc_for(t)
     }
+    fn memory_footprint(&self) -> usize {
+        // NOTE: this doesn't include the tensors captured by each operation,
+        // since those are opaque closures.
+        #[cfg(feature = "graphviz")]
+        let graph = {
+            let num_ids: usize = self.graph.iter().map(|n| n.tensor_ids.len()).sum();
+            self.graph.len() * std::mem::size_of::<OpNode>()
+                + num_ids * std::mem::size_of::<UniqueId>()
+        };
+        #[cfg(not(feature = "graphviz"))]
+        let graph = 0;
+        self.operations.len() * std::mem::size_of::<(UniqueId, BackwardOp<E, D, D::Err>)>()
+            + graph
+            + self.gradients.memory_footprint()
+    }
 }
 
 impl<E: Unit, D: DeviceStorage> Tape<E, D> for NoneTape {
@@ -291,6 +559,165 @@ impl<E: Unit, D: DeviceStorage> Merge<OwnedTape<E, D>> for OwnedTape<E, D> {
                 .extend(leafs);
         }
         self.operations.append(&mut other.operations);
+        #[cfg(feature = "graphviz")]
+        {
+            self.graph.append(&mut other.graph);
+            self.pending_ids.append(&mut other.pending_ids);
+        }
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[cfg(feature = "graphviz")]
+    #[test]
+    fn test_to_dot_chain() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let (_, tape) = x.leaky_trace().relu().exp().sum().split_tape();
+        let dot = tape.to_dot();
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.ends_with('}'));
+        assert_eq!(dot.matches("[label=").count(), 5);
+        assert_eq!(dot.matches(" -> ").count(), 2);
+        assert!(dot.contains("op0 [label=\"try_unary_op<ReLUKernelOp>\"]"));
+        assert!(dot.contains("op1 [label=\"try_unary_op<ExpKernelOp>\"]"));
+        assert!(dot.contains("op0 -> op1"));
+        assert!(dot.contains("op1 -> op2"));
+    }
+
+    #[cfg(feature = "graphviz")]
+    #[test]
+    fn test_to_dot_merged_tapes() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.sample_normal();
+        let c = a.leaky_trace().square() * b.leaky_trace().sin();
+        let (_, tape) = c.exp().split_tape();
+        let dot = tape.to_dot();
+        // square, sin, mul, exp
+        assert_eq!(
+            dot.matches("[label=\"").count() - dot.matches(" -> ").count(),
+            4
+        );
+        // square -> mul, sin -> mul, mul -> exp
+        assert_eq!(dot.matches(" -> ").count(), 3);
+    }
+
+    #[cfg(feature = "graphviz")]
+    #[test]
+    fn test_op_label() {
+        assert_eq!(
+            op_label("dfdx::tensor_ops::utilities::ops::try_unary_op<dfdx::tensor_ops::relu::ReLUKernelOp, (), f32>::{{closure}}"),
+            "try_unary_op<ReLUKernelOp>"
+        );
+        assert_eq!(
+            op_label("dfdx::tensor_ops::matmul::try_matmul_op<(dfdx::shapes::Const<3>,), f32>::{{closure}}"),
+            "try_matmul_op"
+        );
+        assert_eq!(op_label("my_crate::my_op::{{closure}}"), "my_op");
+    }
+
+    #[test]
+    fn test_accumulate_micro_batches() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let loss = |x: Tensor<Rank2<2, 3>, TestDtype, _>| {
+            (w.leaky_trace() * x).sum::<Rank1<2>, _>().mean().backward()
+        };
+        let x_arr = x.array();
+        let mut grads = loss(dev.tensor([x_arr[0], x_arr[1]]));
+        grads.accumulate(&dev, loss(dev.tensor([x_arr[2], x_arr[3]])));
+
+        // the mean over the combined batch is half the sum of the micro batch means
+        let combined = (w.leaky_trace().broadcast::<Rank3<2, 2, 3>, Axis<0>>()
+            * x.reshape::<Rank3<2, 2, 3>>())
+        .sum::<Rank2<2, 2>, _>()
+        .mean()
+        .backward();
+        assert_close(&(grads.get(&w) * 0.5).array(), &combined.get(&w).array());
+    }
+
+    #[test]
+    fn test_accumulate_different_tensors() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, 3.0]);
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([-1.0, 0.0, 1.0]);
+        let mut grads = a.leaky_trace().square().sum().backward();
+        // `a` is skipped in the second pass
+        grads.accumulate(&dev, (b.leaky_trace() * 3.0).sum().backward());
+        assert_close(&grads.get(&a).array(), &[2.0, 4.0, 6.0]);
+        assert_close(&grads.get(&b).array(), &[3.0; 3]);
+
+        let mut more = b.leaky_trace().sum().backward();
+        more.accumulate(&dev, grads);
+        assert_close(&more.get(&a).array(), &[2.0, 4.0, 6.0]);
+        assert_close(&more.get(&b).array(), &[4.0; 3]);
+    }
+
+    #[test]
+    fn test_accumulate_shape_mismatch() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.zeros();
+        let b: Tensor<Rank2<3, 2>, TestDtype, _> = dev.zeros();
+        let mut grads = a.leaky_trace().sum().backward();
+        let mut other = Gradients::leaky();
+        // same number of elements, but a different shape
+        other.insert(
+            &a.clone().reshape::<Rank2<3, 2>>(),
+            b.try_alloc_grad().unwrap(),
+        );
+        let mut other_b = Gradients::leaky();
+        other_b.insert(&b, b.try_alloc_grad().unwrap());
+        other.try_accumulate(&dev, other_b).unwrap();
+        match grads.try_accumulate(&dev, other) {
+            Err(AccumulateError::ShapeMismatch(id)) => assert_eq!(id, a.id),
+            r => panic!("{r:?}"),
+        }
+    }
+
+    #[test]
+    fn test_add_where() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, 3.0]);
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([4.0, 5.0, 6.0]);
+        let mut grads: Gradients<TestDtype, TestDevice> = Gradients::leaky();
+        grads.insert(&a, a.data.as_ref().clone());
+        let mut other = Gradients::leaky();
+        other.insert(&a, a.data.as_ref().clone());
+        other.insert(&b, b.data.as_ref().clone());
+        grads.try_add_where(&dev, other, |id| id == a.id).unwrap();
+        assert_eq!(grads.get(&a).array(), [2.0, 4.0, 6.0]);
+        assert!(grads.get_ref_checked(&b).is_none());
+    }
+
+    #[test]
+    fn test_accumulate_keeps_leak_setting() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<3>, TestDtype, _> = dev.zeros();
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.zeros();
+
+        let mut leaky = a.leaky_trace().exp().sum().backward();
+        let mut other: Gradients<TestDtype, TestDevice> = Gradients::leaky();
+        other.retain_leafs(&[b.id]);
+        leaky.accumulate(&dev, other);
+        leaky.drop_non_leafs();
+        // leaky gradients keep the gradients of intermediate tensors
+        assert!(leaky.gradient_by_id.len() > 1);
+
+        let mut grads: Gradients<TestDtype, TestDevice> = Gradients::leaky();
+        grads.retain_leafs(&[a.id]);
+        let mut other = Gradients::leaky();
+        other.retain_leafs(&[b.id]);
+        grads.accumulate(&dev, other);
+        assert_eq!(
+            grads.leaf_ids,
+            Some([a.id, b.id].into_iter().collect::<BTreeSet<_>>())
+        );
+    }
+}
@@ -127,8 +127,12 @@
 pub(crate) mod cpu;
 #[cfg(feature = "cuda")]
 pub(crate) mod cuda;
+#[cfg(feature = "std")]
+mod deterministic;
 mod gradients;
 mod masks;
+#[cfg(feature = "ndarray")]
+mod ndarray;
 #[cfg(feature = "numpy")]
 pub(crate) mod numpy;
 #[cfg(feature = "safetensors")]
@@ -140,7 +144,7 @@ mod tensor_impls;
 
 pub(crate) use storage_traits::{OneFillStorage, ZeroFillStorage};
 
-pub use cpu::{Cpu, CpuError};
+pub use cpu::{Cpu, CpuError, TensorPool};
 #[cfg(not(feature = "cuda"))]
 pub type AutoDevice = Cpu;
 
@@ -151,6 +155,11 @@ pub use cuda::{Cuda, CudaError};
 #[cfg(feature = "cuda")]
 pub type AutoDevice = Cuda;
 
+#[cfg(feature = "ndarray")]
+pub use self::ndarray::{FromNdarray, NdarrayError};
+#[cfg(feature = "numpy")]
+pub use numpy::{NpyError, NpzError};
+
 pub use storage_traits::{AsArray, CopySlice, TensorFrom, TensorFromVec};
 pub use storage_traits::{DeviceStorage, HasErr};
 pub use storage_traits::{OnesTensor, SampleTensor, TriangleTensor, ZerosTensor};
@@ -161,7 +170,12 @@ pub use tensor_impls::{Tensor0D, Tensor1D, Tensor2D, Tensor3D, Tensor4D, Tensor5
 pub(crate) use unique_id::unique_id;
 pub use unique_id::UniqueId;
 
-pub use gradients::{Gradients, Merge, NoneTape, OwnedTape, Tape};
+pub use gradients::{AccumulateError, Gradients, Merge, NoneTape, OwnedTape, Tape};
+
+#[cfg(feature = "std")]
+pub(crate) use deterministic::with_deterministic_rng;
+#[cfg(feature = "std")]
+pub use deterministic::{is_deterministic, set_deterministic, unset_deterministic};
 
 #[cfg(test)]
 mod tests {
@@ -217,6 +231,50 @@ mod tests {
         assert_eq!(t3.id, t1_id);
     }
 
+    #[test]
+    fn test_memory_footprint() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<100, 100>, f32, _> = dev.zeros();
+        assert_eq!(t.memory_footprint(), 40_000);
+
+        let t: Tensor<Rank2<100, 100>, f64, _> = dev.zeros();
+        assert_eq!(t.memory_footprint(), 80_000);
+
+        // the tape holds the gradients for the input & output, plus the operation itself
+        let x: Tensor<Rank2<100, 100>, f32, _> = dev.zeros();
+        let y = crate::tensor_ops::relu(x.leaky_trace());
+        assert!(y.memory_footprint() >= 3 * 40_000);
+        assert!(y.memory_footprint() < 3 * 40_000 + 1_000);
+    }
+
+    #[test]
+    fn test_retain_grad() {
+        use crate::{nn::ZeroGrads, tensor_ops::*};
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, -2.0, 3.0]);
+
+        let y = x.trace(x.alloc_grads()) * 2.0;
+        let y_ref = y.retaped::<NoneTape>();
+        let g = y.square().sum().backward();
+        assert!(g.get_ref_checked(&y_ref).is_none());
+
+        let y = (x.trace(x.alloc_grads()) * 2.0).retain_grad();
+        let y_ref = y.retaped::<NoneTape>();
+        let g = y.square().sum().backward();
+        assert_eq!(g.get(&y_ref).array(), [4.0, -8.0, 12.0]);
+        assert_eq!(g.get(&x).array(), [8.0, -16.0, 24.0]);
+    }
+
+    #[test]
+    fn test_grad() {
+        use crate::tensor_ops::*;
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let g = x.leaky_trace().exp().mean().backward();
+        assert_eq!(x.grad(&g).array(), g.get(&x).array());
+        assert_eq!(x.grad(&g).shape(), x.shape());
+    }
+
     #[test]
     fn test_zeros() {
         let dev: TestDevice = Default::default();
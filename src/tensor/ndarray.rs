@@ -0,0 +1,184 @@
+use ::ndarray::{ArrayD, ErrorKind, IxDyn, ShapeError};
+use std::vec::Vec;
+
+use crate::shapes::{Dtype, Shape};
+
+use super::{DeviceStorage, Tensor, TensorFromVec};
+
+/// Creates tensors from [ndarray](https://docs.rs/ndarray) arrays.
+///
+/// Requires the `"ndarray"` feature.
+pub trait FromNdarray<E: Dtype>: DeviceStorage + TensorFromVec<E> {
+    /// Moves `arr` into a tensor of shape `S`, returning a [ShapeError] if the shape of `arr`
+    /// isn't `S`.
+    ///
+    /// The data of `arr` isn't copied on [super::Cpu] if it is in standard (row major) layout.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let arr = ndarray::ArrayD::from_shape_vec(vec![2, 3], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+    ///     .unwrap();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = dev.from_ndarray(arr).unwrap();
+    /// assert_eq!(t.array(), [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_ndarray<S: Shape>(&self, arr: ArrayD<E>) -> Result<Tensor<S, E, Self>, ShapeError> {
+        self.try_from_ndarray(arr).map_err(|e| match e {
+            NdarrayError::Shape(e) => e,
+            NdarrayError::Device(e) => panic!("{e:?}"),
+        })
+    }
+
+    /// Fallible version of [FromNdarray::from_ndarray]
+    #[allow(clippy::wrong_self_convention)]
+    fn try_from_ndarray<S: Shape>(
+        &self,
+        arr: ArrayD<E>,
+    ) -> Result<Tensor<S, E, Self>, NdarrayError<Self::Err>> {
+        let shape = shape_of::<S>(arr.shape())?;
+        let len = arr.len();
+        let data: Vec<E> = if arr.is_standard_layout() {
+            let start = arr.as_ptr() as usize;
+            let data = arr.into_raw_vec();
+            // the array may be a slice of a bigger allocation, in which case only part is used
+            let offset = (start - data.as_ptr() as usize) / std::mem::size_of::<E>();
+            if offset == 0 && data.len() == len {
+                data
+            } else {
+                data[offset..offset + len].to_vec()
+            }
+        } else {
+            arr.iter().copied().collect()
+        };
+        self.try_tensor_from_vec(data, shape)
+            .map_err(NdarrayError::Device)
+    }
+}
+impl<E: Dtype, D: DeviceStorage + TensorFromVec<E>> FromNdarray<E> for D {}
+
+impl<S: Shape, E: Dtype, D: DeviceStorage, T> Tensor<S, E, D, T> {
+    /// Copies the tensor into an [ndarray](https://docs.rs/ndarray) array with the same shape.
+    ///
+    /// Requires the `"ndarray"` feature.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let arr = t.to_ndarray();
+    /// assert_eq!(arr.shape(), &[2, 3]);
+    /// assert_eq!(arr[[1, 0]], 4.0);
+    /// ```
+    pub fn to_ndarray(&self) -> ArrayD<E> {
+        let dims: Vec<usize> = self.shape.concrete().into();
+        ArrayD::from_shape_vec(IxDyn(&dims), self.as_vec()).unwrap()
+    }
+}
+
+/// The `S` with the same dimensions as `dims`.
+fn shape_of<S: Shape>(dims: &[usize]) -> Result<S, ShapeError> {
+    let incompatible = || ShapeError::from_kind(ErrorKind::IncompatibleShape);
+    if dims.len() != S::NUM_DIMS {
+        return Err(incompatible());
+    }
+    let mut concrete: S::Concrete = Default::default();
+    for (i, &d) in dims.iter().enumerate() {
+        concrete[i] = d;
+    }
+    S::from_concrete(&concrete).ok_or_else(incompatible)
+}
+
+/// An error from [FromNdarray::try_from_ndarray].
+#[derive(Debug)]
+pub enum NdarrayError<Err> {
+    /// The shape of the array doesn't match the shape of the tensor.
+    Shape(ShapeError),
+    /// The device failed to create the tensor.
+    Device(Err),
+}
+
+impl<Err> From<ShapeError> for NdarrayError<Err> {
+    fn from(e: ShapeError) -> Self {
+        Self::Shape(e)
+    }
+}
+
+impl<Err: std::fmt::Display> std::fmt::Display for NdarrayError<Err> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shape(err) => write!(fmt, "{err}"),
+            Self::Device(err) => write!(fmt, "{err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Err: std::fmt::Debug + std::fmt::Display> std::error::Error for NdarrayError<Err> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tests::*};
+    use ::ndarray::s;
+
+    #[test]
+    fn test_ndarray_3d_roundtrip() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let arr = t.to_ndarray();
+        assert_eq!(arr.shape(), &[2, 3, 4]);
+        assert_eq!(arr[[1, 2, 3]], t.array()[1][2][3]);
+        let t2: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.from_ndarray(arr).unwrap();
+        assert_eq!(t2.array(), t.array());
+    }
+
+    #[test]
+    fn test_from_ndarray_dynamic_dims() {
+        let dev: TestDevice = Default::default();
+        let arr =
+            ArrayD::from_shape_vec(vec![2, 3], (0..6).map(|x| x as TestDtype).collect()).unwrap();
+        let t: Tensor<(usize, Const<3>), TestDtype, _> = dev.from_ndarray(arr).unwrap();
+        assert_eq!(t.shape, (2, Const));
+        assert_eq!(t.as_vec(), [0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_from_ndarray_wrong_shape() {
+        let dev: TestDevice = Default::default();
+        let arr = ArrayD::<TestDtype>::zeros(vec![3, 2]);
+        let r: Result<Tensor<Rank2<2, 3>, TestDtype, _>, _> = dev.from_ndarray(arr);
+        assert_eq!(r.unwrap_err().kind(), ErrorKind::IncompatibleShape);
+        let arr = ArrayD::<TestDtype>::zeros(vec![6]);
+        let r: Result<Tensor<Rank2<2, 3>, TestDtype, _>, _> = dev.from_ndarray(arr);
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_from_ndarray_non_standard_layout() {
+        let dev: TestDevice = Default::default();
+        let arr =
+            ArrayD::from_shape_vec(vec![2, 3], (0..6).map(|x| x as TestDtype).collect()).unwrap();
+        let t: Tensor<Rank2<3, 2>, TestDtype, _> = dev.from_ndarray(arr.t().to_owned()).unwrap();
+        assert_eq!(t.array(), [[0.0, 3.0], [1.0, 4.0], [2.0, 5.0]]);
+
+        let cols = arr.clone().slice_move(s![.., 1..]).into_dyn();
+        let t: Tensor<Rank2<2, 2>, TestDtype, _> = dev.from_ndarray(cols).unwrap();
+        assert_eq!(t.array(), [[1.0, 2.0], [4.0, 5.0]]);
+
+        // standard layout, but not at the start of its allocation
+        let row = arr.slice_move(s![1.., ..]).into_dyn();
+        assert!(row.is_standard_layout());
+        let t: Tensor<Rank2<1, 3>, TestDtype, _> = dev.from_ndarray(row).unwrap();
+        assert_eq!(t.array(), [[3.0, 4.0, 5.0]]);
+    }
+
+    #[test]
+    fn test_from_ndarray_is_zero_copy_on_cpu() {
+        let dev: Cpu = Default::default();
+        let arr = ArrayD::<f32>::zeros(vec![2, 3]);
+        let ptr = arr.as_ptr();
+        let t: Tensor<Rank2<2, 3>, f32, _> = dev.from_ndarray(arr).unwrap();
+        assert_eq!(t.data.as_ptr(), ptr);
+    }
+}
@@ -27,15 +27,23 @@ impl<S: Shape, E: Dtype + NumpyDtype, D: DeviceStorage + CopySlice<E>, T> Tensor
         Ok(())
     }
 
-    /// Reads `data` from a file already in a zip archive named `filename`.
+    /// Reads `data` from a file already in a zip archive named `filename`. Also
+    /// looks for `{filename}.npy`, which is what `numpy.savez` names its files.
     pub fn read_from_npz<R: Read + Seek>(
         &mut self,
         r: &mut zip::ZipArchive<R>,
         filename: String,
     ) -> Result<(), NpzError> {
-        let mut f = r.by_name(&filename)?;
-        self.read_from(&mut f)?;
-        Ok(())
+        match r.by_name(&filename) {
+            Ok(mut f) => return Ok(self.read_from(&mut f)?),
+            Err(ZipError::FileNotFound) => (),
+            Err(e) => return Err(e.into()),
+        }
+        match r.by_name(&format!("{filename}.npy")) {
+            Ok(mut f) => Ok(self.read_from(&mut f)?),
+            Err(ZipError::FileNotFound) => Err(NpzError::MissingKey(filename)),
+            Err(e) => Err(e.into()),
+        }
     }
 
     /// Attemps to load the data from a `.npy` file at `path`
@@ -257,7 +265,7 @@ pub enum NpyError {
         found_str: String,
     },
 
-    /// Unexpected alignment for [Endian].
+    /// Unexpected alignment for the byte order.
     InvalidAlignment,
 }
 
@@ -320,6 +328,9 @@ pub enum NpzError {
 
     /// Something went wrong with loading data from a `.npy` file
     Npy(NpyError),
+
+    /// The archive does not contain a file for this key.
+    MissingKey(String),
 }
 
 impl std::fmt::Display for NpzError {
@@ -327,6 +338,7 @@ impl std::fmt::Display for NpzError {
         match self {
             NpzError::Zip(err) => write!(fmt, "{err}"),
             NpzError::Npy(err) => write!(fmt, "{err}"),
+            NpzError::MissingKey(key) => write!(fmt, "no file named {key:?} in archive"),
         }
     }
 }
@@ -336,6 +348,7 @@ impl std::error::Error for NpzError {
         match self {
             NpzError::Zip(err) => Some(err),
             NpzError::Npy(err) => Some(err),
+            NpzError::MissingKey(_) => None,
         }
     }
 }
@@ -29,6 +29,12 @@ pub trait DeviceStorage: 'static + std::fmt::Debug + Default + Clone + HasErr {
 
     fn tensor_to_vec<S: Shape, E: Unit, T>(&self, tensor: &Tensor<S, E, Self, T>) -> Vec<E>;
 
+    /// The number of elements stored in `storage`, or `None` if the device can't tell,
+    /// in which case memory footprints are estimated from the shape instead.
+    fn len<E: Unit>(_storage: &Self::Vec<E>) -> Option<usize> {
+        None
+    }
+
     /// Blocks until all work on device to complete. Useful for benchmarking.
     fn synchronize(&self) {
         self.try_synchronize().unwrap()
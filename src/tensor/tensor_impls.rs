@@ -94,7 +94,7 @@ impl<S: Shape, E: Unit, F: Unit, D: DeviceStorage> Trace<E, D> for Tensor<S, F,
     fn traced(self, gradients: Gradients<E, D>) -> Self::Traced {
         self.put_tape(OwnedTape {
             gradients,
-            operations: std::vec::Vec::new(),
+            ..Default::default()
         })
     }
 }
@@ -113,6 +113,64 @@ impl<S: Shape, E: Unit, D: DeviceStorage, T> Tensor<S, E, D, T> {
     }
 }
 
+impl<S: Shape, E: Unit, D: DeviceStorage, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// The number of bytes used by this tensor's data, plus an estimate of the
+    /// bytes used by the operations & gradients recorded on its tape.
+    ///
+    /// Note that the data may be shared with other tensors (e.g. after a clone),
+    /// in which case it is counted for each of them.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<100, 100>, f32, _> = dev.zeros();
+    /// assert_eq!(t.memory_footprint(), 40_000);
+    /// ```
+    pub fn memory_footprint(&self) -> usize {
+        let len = D::len(self.data.as_ref()).unwrap_or_else(|| self.shape.num_elements());
+        len * std::mem::size_of::<E>() + self.tape.memory_footprint()
+    }
+}
+
+impl<S: Shape, E: Unit, D: DeviceStorage, T> Tensor<S, E, D, T> {
+    /// Returns the gradient of this tensor stored in `gradients`, as a new tensor.
+    /// Shorthand for [Gradients::get()].
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let x: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, 2.0, 3.0]);
+    /// let grads = x.leaky_trace().square().sum().backward();
+    /// assert_eq!(x.grad(&grads).array(), [2.0, 4.0, 6.0]);
+    /// ```
+    ///
+    /// # Panics
+    /// If no gradient is associated with this tensor.
+    pub fn grad(&self, gradients: &Gradients<E, D>) -> Tensor<S, E, D> {
+        gradients.get(self)
+    }
+}
+
+impl<S: Shape, E: Unit, D: DeviceStorage> Tensor<S, E, D, OwnedTape<E, D>> {
+    /// Marks this tensor so that its gradient is kept in the [Gradients] returned by backward,
+    /// even though it is not a leaf tensor (e.g. the output of an intermediate operation).
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let x: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, 2.0, 3.0]);
+    /// let mut grads = x.alloc_grads();
+    /// let y = (x.trace(grads) * 2.0).retain_grad();
+    /// let y_id = y.retaped::<NoneTape>();
+    /// let grads = y.square().sum().backward();
+    /// assert_eq!(grads.get(&y_id).array(), [4.0, 8.0, 12.0]);
+    /// ```
+    pub fn retain_grad(mut self) -> Self {
+        self.tape.gradients.retain(self.id);
+        self
+    }
+}
+
 /// Put a tape of type `T` into the tensor
 pub trait PutTape<T> {
     type Output;
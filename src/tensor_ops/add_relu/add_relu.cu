@@ -0,0 +1,25 @@
+#include "binary_op_macros.cuh"
+
+struct AddReLUKernelOp {};
+
+template<typename T>
+__device__ T op_f(T x, T y) {
+    return maxg(x + y, T(0.0));
+}
+
+template<typename T>
+__device__ T op_dfdx(T x, T y) {
+    return (x + y > T(0.0)) ? 1.0 : 0.0;
+}
+
+BINARY_OP(float, add_relu_fwd_f32, add_relu_bwd_lhs_f32, add_relu_bwd_rhs_f32, AddReLUKernelOp,
+    op_f(x, y),
+    op_dfdx(x, y),
+    op_dfdx(x, y)
+)
+
+BINARY_OP(double, add_relu_fwd_f64, add_relu_bwd_lhs_f64, add_relu_bwd_rhs_f64, AddReLUKernelOp,
+    op_f(x, y),
+    op_dfdx(x, y),
+    op_dfdx(x, y)
+)
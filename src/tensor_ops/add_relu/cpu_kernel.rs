@@ -0,0 +1,24 @@
+use crate::tensor_ops::cpu_kernels::BinaryDerivative;
+
+impl<F: num_traits::Float> BinaryDerivative<F> for super::AddReLUKernelOp {
+    #[inline(always)]
+    fn f(&self, &x: &F, &y: &F) -> F {
+        (x + y).max(F::zero())
+    }
+    #[inline(always)]
+    fn dfdx(&self, &x: &F, &y: &F) -> F {
+        if x + y > F::zero() {
+            F::one()
+        } else {
+            F::zero()
+        }
+    }
+    #[inline(always)]
+    fn dfdy(&self, &x: &F, &y: &F) -> F {
+        if x + y > F::zero() {
+            F::one()
+        } else {
+            F::zero()
+        }
+    }
+}
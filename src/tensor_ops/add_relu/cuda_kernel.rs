@@ -0,0 +1,23 @@
+use super::AddReLUKernelOp as AddReLU;
+use crate::tensor_ops::cuda_kernels::cuda_binary;
+
+unsafe impl cudarc::driver::DeviceRepr for AddReLU {}
+
+const PTX: &str = include_str!(concat!(env!("OUT_DIR"), "/add_relu.ptx"));
+
+cuda_binary!(
+    AddReLU,
+    f32,
+    PTX,
+    "add_relu_fwd_f32",
+    "add_relu_bwd_lhs_f32",
+    "add_relu_bwd_rhs_f32"
+);
+cuda_binary!(
+    AddReLU,
+    f64,
+    PTX,
+    "add_relu_fwd_f64",
+    "add_relu_bwd_lhs_f64",
+    "add_relu_bwd_rhs_f64"
+);
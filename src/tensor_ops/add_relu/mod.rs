@@ -0,0 +1,100 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::{ops::try_binary_op, Device};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AddReLUKernelOp;
+
+/// Fused element wise `relu(a + b)`. This computes the same thing as `(a + b).relu()`,
+/// but in one pass with a single backward op, so no intermediate tensor is allocated.
+///
+/// Example:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a = dev.tensor([[1.0, 2.0, -3.0], [-1.0, -2.0, 3.0]]);
+/// let b = dev.tensor([[-2.0, 0.5, 1.0], [2.0, 2.0, -3.5]]);
+/// let r = a.add_relu(b);
+/// assert_eq!(r.array(), [[0.0, 2.5, 0.0], [1.0, 0.0, 0.0]]);
+/// ```
+pub fn add_relu<S: Shape, E: Dtype, D: Device<E>, LTape: Tape<E, D> + Merge<R>, R: Default>(
+    lhs: Tensor<S, E, D, LTape>,
+    rhs: Tensor<S, E, D, R>,
+) -> Tensor<S, E, D, LTape> {
+    lhs.add_relu(rhs)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, LTape: Tape<E, D>> Tensor<S, E, D, LTape> {
+    /// See [add_relu]
+    pub fn add_relu<R: Default>(self, rhs: Tensor<S, E, D, R>) -> Self
+    where
+        LTape: Merge<R>,
+    {
+        self.try_add_relu(rhs).unwrap()
+    }
+
+    /// See [add_relu]
+    pub fn try_add_relu<R: Default>(self, rhs: Tensor<S, E, D, R>) -> Result<Self, D::Err>
+    where
+        LTape: Merge<R>,
+    {
+        try_binary_op(AddReLUKernelOp, self, rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_add_relu() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<_, TestDtype, _> = dev.tensor([[-1.0, 0.0, 1.0], [3.0, 4.0, -5.0]]);
+        let b: Tensor<_, TestDtype, _> = dev.tensor([[0.5, 0.5, -2.0], [-1.0, -4.5, 6.0]]);
+
+        let fused = a.leaky_trace().add_relu(b.clone());
+        let unfused = (a.leaky_trace() + b.clone()).relu();
+        assert_eq!(fused.array(), [[0.0, 0.5, 0.0], [2.0, 0.0, 1.0]]);
+        assert_eq!(fused.array(), unfused.array());
+
+        let g1 = fused.exp().sum().backward();
+        let g2 = unfused.exp().sum().backward();
+        assert_close(&g1.get(&a).array(), &g2.get(&a).array());
+        assert_close(&g1.get(&b).array(), &g2.get(&b).array());
+    }
+
+    #[test]
+    fn test_add_relu_records_single_op() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<3, 5>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank2<3, 5>, TestDtype, _> = dev.sample_normal();
+
+        let fused = a.leaky_trace().add_relu(b.clone());
+        let unfused = (a.leaky_trace() + b.clone()).relu();
+        assert_eq!(fused.tape.operations.len(), 1);
+        assert_eq!(unfused.tape.operations.len(), 2);
+
+        // the unfused version also allocates a gradient for the intermediate `a + b`
+        let g1 = fused.sum().backward();
+        let g2 = unfused.sum().backward();
+        assert_close(&g1.get(&a).array(), &g2.get(&a).array());
+        assert_close(&g1.get(&b).array(), &g2.get(&b).array());
+    }
+
+    #[test]
+    fn test_add_relu_allocates_less_than_add_then_relu() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<8, 16>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank2<8, 16>, TestDtype, _> = dev.sample_normal();
+
+        let (g1, fused) = count_allocs(|| a.leaky_trace().add_relu(b.clone()).sum().backward());
+        let (g2, unfused) = count_allocs(|| (a.leaky_trace() + b.clone()).relu().sum().backward());
+        assert_close(&g1.get(&a).array(), &g2.get(&a).array());
+        assert!(fused < unfused, "{fused} >= {unfused}");
+    }
+}
@@ -0,0 +1,187 @@
+#![allow(clippy::type_complexity)]
+
+use crate::{
+    shapes::{Axes2, Dim, Dtype},
+    tensor::{Merge, Tape, Tensor},
+};
+
+use super::{Device, PermuteTo, TryMatMul, TryMul};
+
+use num_traits::Float;
+
+/// Scaled dot product attention scores: `q @ k^T / sqrt(K)`, where `K` is the size of the
+/// last dimension of `q` and `k`.
+///
+/// **Pytorch equivalent**: `torch.matmul(q, k.transpose(-2, -1)) / math.sqrt(q.size(-1))`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let q: Tensor<Rank2<2, 4>, f32, _> = dev.ones();
+/// let k: Tensor<Rank2<3, 4>, f32, _> = dev.ones();
+/// let r: Tensor<Rank2<2, 3>, f32, _> = attention_scores(q, k);
+/// assert_eq!(r.array(), [[2.0; 3]; 2]);
+/// ```
+pub fn attention_scores<M: Dim, N: Dim, K: Dim, E, D, T, R>(
+    q: Tensor<(M, K), E, D, T>,
+    k: Tensor<(N, K), E, D, R>,
+) -> Tensor<(M, N), E, D, T>
+where
+    E: Dtype + Float,
+    D: Device<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    q.attention_scores(k)
+}
+
+impl<M: Dim, K: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>> Tensor<(M, K), E, D, T> {
+    /// See [attention_scores]
+    pub fn attention_scores<N: Dim, R: Tape<E, D>>(
+        self,
+        k: Tensor<(N, K), E, D, R>,
+    ) -> Tensor<(M, N), E, D, T>
+    where
+        T: Merge<R>,
+    {
+        self.try_attention_scores(k).unwrap()
+    }
+
+    /// See [attention_scores]
+    pub fn try_attention_scores<N: Dim, R: Tape<E, D>>(
+        self,
+        k: Tensor<(N, K), E, D, R>,
+    ) -> Result<Tensor<(M, N), E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        let scale = E::one() / E::from_usize(self.shape.1.size()).unwrap().sqrt();
+        let k_t = k.try_permute::<_, Axes2<1, 0>>()?;
+        self.try_matmul(k_t)?.try_mul(scale)
+    }
+}
+
+/// Applies attention weights `attn` to values `v`: `attn @ v`. Each output row is the
+/// weighted combination of the rows of `v` given by the matching row of `attn`.
+///
+/// Typically `attn` is the softmax over the last axis of [attention_scores()].
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let attn: Tensor<Rank2<1, 2>, f32, _> = dev.tensor([[0.25, 0.75]]);
+/// let v: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[4.0, 0.0, 8.0], [0.0, 4.0, -4.0]]);
+/// let r: Tensor<Rank2<1, 3>, f32, _> = attention_apply(attn, v);
+/// assert_eq!(r.array(), [[1.0, 3.0, -1.0]]);
+/// ```
+pub fn attention_apply<M: Dim, N: Dim, K: Dim, E, D, T, R>(
+    attn: Tensor<(M, N), E, D, T>,
+    v: Tensor<(N, K), E, D, R>,
+) -> Tensor<(M, K), E, D, T>
+where
+    E: Dtype,
+    D: Device<E>,
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    attn.attention_apply(v)
+}
+
+impl<M: Dim, N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(M, N), E, D, T> {
+    /// See [attention_apply]
+    pub fn attention_apply<K: Dim, R: Tape<E, D>>(
+        self,
+        v: Tensor<(N, K), E, D, R>,
+    ) -> Tensor<(M, K), E, D, T>
+    where
+        T: Merge<R>,
+    {
+        self.try_attention_apply(v).unwrap()
+    }
+
+    /// See [attention_apply]
+    pub fn try_attention_apply<K: Dim, R: Tape<E, D>>(
+        self,
+        v: Tensor<(N, K), E, D, R>,
+    ) -> Result<Tensor<(M, K), E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        self.try_matmul(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_attention_scores() {
+        let dev: TestDevice = Default::default();
+        let q: Tensor<Rank2<2, 4>, TestDtype, _> =
+            dev.tensor([[1.0, 2.0, 3.0, 4.0], [-1.0, 0.0, 1.0, 0.5]]);
+        let k: Tensor<Rank2<3, 4>, TestDtype, _> = dev.tensor([
+            [0.5, 0.5, 0.5, 0.5],
+            [1.0, 0.0, -1.0, 0.0],
+            [0.0, 2.0, 0.0, -2.0],
+        ]);
+        let r = q.leaky_trace().attention_scores(k.clone());
+        assert_close(&r.array(), &[[2.5, -1.0, -2.0], [0.125, -1.0, -0.5]]);
+
+        let expected = q.clone().matmul(k.clone().permute()) / 2.0;
+        assert_close(&r.array(), &expected.array());
+
+        // d(sum(r))/dq[m][i] = sum_n k[n][i] / sqrt(4)
+        let g = r.sum().backward();
+        assert_close(&g.get(&q).array(), &[[0.75, 1.25, -0.25, -0.75]; 2]);
+    }
+
+    #[test]
+    fn test_attention_scores_grad_k() {
+        let dev: TestDevice = Default::default();
+        let q: Tensor<Rank2<2, 9>, TestDtype, _> = dev.ones();
+        let k: Tensor<Rank2<3, 9>, TestDtype, _> = dev.sample_normal();
+        let r = attention_scores(q.leaky_trace(), k.leaky_trace());
+        // d(sum(r))/dk[n][i] = sum_m q[m][i] / sqrt(9)
+        let g = r.sum().backward();
+        assert_close(&g.get(&k).array(), &[[2.0 / 3.0; 9]; 3]);
+    }
+
+    #[test]
+    fn test_attention_apply_uniform() {
+        let dev: TestDevice = Default::default();
+        let attn: Tensor<Rank2<2, 4>, TestDtype, _> = dev.ones() / 4.0;
+        let v: Tensor<Rank2<4, 3>, TestDtype, _> = dev.tensor([
+            [1.0, 2.0, 3.0],
+            [-1.0, 0.0, 1.0],
+            [4.0, 2.0, 0.0],
+            [0.0, 0.0, 4.0],
+        ]);
+        let r = attn.leaky_trace().attention_apply(v.leaky_trace());
+        assert_close(&r.array(), &[[1.0, 1.0, 2.0]; 2]);
+
+        let g = r.sum().backward();
+        // d(sum(r))/dattn[m][n] = sum_k v[n][k]
+        assert_close(&g.get(&attn).array(), &[[6.0, 0.0, 6.0, 4.0]; 2]);
+        // d(sum(r))/dv[n][k] = sum_m attn[m][n]
+        assert_close(&g.get(&v).array(), &[[0.5; 3]; 4]);
+    }
+
+    #[test]
+    fn test_attention_scores_then_apply() {
+        let dev: TestDevice = Default::default();
+        let q: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let k: Tensor<Rank2<5, 4>, TestDtype, _> = dev.sample_normal();
+        let v: Tensor<Rank2<5, 2>, TestDtype, _> = dev.sample_normal();
+        let attn = q
+            .leaky_trace()
+            .attention_scores(k.clone())
+            .softmax::<Axis<1>>();
+        let r = attn.attention_apply(v.clone());
+        let expected = (q.clone().matmul(k.permute()) / 2.0)
+            .softmax::<Axis<1>>()
+            .matmul(v);
+        assert_close(&r.array(), &expected.array());
+    }
+}
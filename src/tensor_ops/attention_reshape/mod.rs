@@ -1,5 +1,7 @@
 use crate::{shapes::*, tensor::*};
 
+use super::ops::profiled;
+
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
@@ -82,7 +84,7 @@ impl<E: Dtype, D: AttentionReshapeKernel<E>> TryAttentionReshape<E> for D {
         past_value: &Tensor<(Const<NUM_HEADS>, usize, Const<HEAD_DIM>), E, Self>,
     ) -> Result<QkvTuple<NUM_HEADS, HEAD_DIM, E, Self>, Self::Err> {
         let device = qkv.device.clone();
-        device.forward(qkv, past_key, past_value)
+        profiled(|| device.forward(qkv, past_key, past_value))
     }
 }
 
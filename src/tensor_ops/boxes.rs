@@ -0,0 +1,178 @@
+use crate::{
+    shapes::{Const, Dim, Dtype},
+    tensor::Tensor,
+};
+
+use super::Device;
+
+use num_traits::Float;
+
+/// Pairwise [intersection over union](https://en.wikipedia.org/wiki/Jaccard_index) of two
+/// sets of boxes in `(x1, y1, x2, y2)` format, where `(x1, y1)` is the top left corner
+/// of a box and `(x2, y2)` the bottom right corner.
+///
+/// `r[i][j]` is the IoU of `boxes_a[i]` and `boxes_b[j]`. Boxes that don't overlap have
+/// an IoU of 0. This is a metric, so it is not differentiable.
+///
+/// **Pytorch equivalent**: `torchvision.ops.box_iou(boxes_a, boxes_b)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank2<1, 4>, f32, _> = dev.tensor([[0.0, 0.0, 2.0, 2.0]]);
+/// let b: Tensor<Rank2<2, 4>, f32, _> = dev.tensor([[1.0, 0.0, 3.0, 2.0], [0.0, 0.0, 2.0, 2.0]]);
+/// let r = box_iou(a, b);
+/// assert_eq!(r.array(), [[1.0 / 3.0, 1.0]]);
+/// ```
+pub fn box_iou<N: Dim, M: Dim, E: Dtype + Float, D: Device<E>>(
+    boxes_a: Tensor<(N, Const<4>), E, D>,
+    boxes_b: Tensor<(M, Const<4>), E, D>,
+) -> Tensor<(N, M), E, D> {
+    boxes_a.box_iou(boxes_b)
+}
+
+impl<N: Dim, E: Dtype + Float, D: Device<E>> Tensor<(N, Const<4>), E, D> {
+    /// See [box_iou]
+    pub fn box_iou<M: Dim>(self, boxes_b: Tensor<(M, Const<4>), E, D>) -> Tensor<(N, M), E, D> {
+        self.try_box_iou(boxes_b).unwrap()
+    }
+
+    /// See [box_iou]
+    pub fn try_box_iou<M: Dim>(
+        self,
+        boxes_b: Tensor<(M, Const<4>), E, D>,
+    ) -> Result<Tensor<(N, M), E, D>, D::Err> {
+        let (a, b) = (self.as_vec(), boxes_b.as_vec());
+        let mut ious = std::vec::Vec::with_capacity(self.shape.0.size() * boxes_b.shape.0.size());
+        for box_a in a.chunks_exact(4) {
+            for box_b in b.chunks_exact(4) {
+                ious.push(iou(box_a, box_b));
+            }
+        }
+        self.device
+            .try_tensor_from_vec(ious, (self.shape.0, boxes_b.shape.0))
+    }
+}
+
+/// Non maximum suppression of `(x1, y1, x2, y2)` boxes, e.g. for removing duplicate
+/// detections of the same object. Greedily keeps the box with the highest score, and
+/// drops every remaining box whose [box_iou] with it is greater than `iou_threshold`,
+/// until no boxes are left.
+///
+/// Returns the indices of the kept boxes, sorted by decreasing score. This is not
+/// differentiable.
+///
+/// **Pytorch equivalent**: `torchvision.ops.nms(boxes, scores, iou_threshold)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let boxes: Tensor<Rank2<3, 4>, f32, _> = dev.tensor([
+///     [0.0, 0.0, 2.0, 2.0],
+///     [5.0, 5.0, 6.0, 6.0],
+///     [0.0, 0.0, 2.0, 2.1],
+/// ]);
+/// let scores = dev.tensor([0.5, 0.1, 0.9]);
+/// assert_eq!(nms(boxes, scores, 0.5), [2, 1]);
+/// ```
+pub fn nms<N: Dim, E: Dtype + Float, D: Device<E>>(
+    boxes: Tensor<(N, Const<4>), E, D>,
+    scores: Tensor<(N,), E, D>,
+    iou_threshold: E,
+) -> std::vec::Vec<usize> {
+    let boxes = boxes.as_vec();
+    let scores = scores.as_vec();
+    let mut order: std::vec::Vec<usize> = (0..scores.len()).collect();
+    // stable, so ties keep the lower index first
+    order.sort_by(|&i, &j| scores[j].partial_cmp(&scores[i]).unwrap());
+    let boxes: std::vec::Vec<&[E]> = boxes.chunks_exact(4).collect();
+
+    let mut keep = std::vec::Vec::new();
+    let mut suppressed = vec![false; scores.len()];
+    for (k, &i) in order.iter().enumerate() {
+        if suppressed[i] {
+            continue;
+        }
+        keep.push(i);
+        for &j in order[k + 1..].iter() {
+            if !suppressed[j] && iou(boxes[i], boxes[j]) > iou_threshold {
+                suppressed[j] = true;
+            }
+        }
+    }
+    keep
+}
+
+/// Area of an `(x1, y1, x2, y2)` box, which is 0 if `x2 < x1` or `y2 < y1`.
+fn area<E: Float>(b: &[E]) -> E {
+    (b[2] - b[0]).max(E::zero()) * (b[3] - b[1]).max(E::zero())
+}
+
+fn iou<E: Float>(a: &[E], b: &[E]) -> E {
+    let intersection = area(&[
+        a[0].max(b[0]),
+        a[1].max(b[1]),
+        a[2].min(b[2]),
+        a[3].min(b[3]),
+    ]);
+    let union = area(a) + area(b) - intersection;
+    if union > E::zero() {
+        intersection / union
+    } else {
+        E::zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tests::*};
+
+    #[test]
+    fn test_box_iou() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 4>, TestDtype, _> =
+            dev.tensor([[0.0, 0.0, 2.0, 2.0], [10.0, 10.0, 11.0, 12.0]]);
+        let b: Tensor<Rank2<3, 4>, TestDtype, _> = dev.tensor([
+            [1.0, 1.0, 3.0, 3.0],
+            [5.0, 5.0, 6.0, 6.0],
+            [10.0, 10.0, 11.0, 12.0],
+        ]);
+        let r = a.box_iou(b);
+        // the first boxes overlap on a 1x1 square, out of 4 + 4 - 1 = 7
+        assert_close(&r.array(), &[[1.0 / 7.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_box_iou_touching_and_empty() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 4>, TestDtype, _> =
+            dev.tensor([[0.0, 0.0, 1.0, 1.0], [2.0, 2.0, 2.0, 2.0]]);
+        let b: Tensor<Rank2<1, 4>, TestDtype, _> = dev.tensor([[1.0, 0.0, 2.0, 1.0]]);
+        assert_eq!(a.clone().box_iou(b).array(), [[0.0], [0.0]]);
+        assert_eq!(a.clone().box_iou(a).array(), [[1.0, 0.0], [0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_nms_keeps_higher_score() {
+        let dev: TestDevice = Default::default();
+        let boxes: Tensor<Rank2<2, 4>, TestDtype, _> =
+            dev.tensor([[0.0, 0.0, 10.0, 10.0], [1.0, 1.0, 10.0, 11.0]]);
+        let scores = dev.tensor([0.6, 0.8]);
+        assert_eq!(nms(boxes, scores, 0.5), [1]);
+    }
+
+    #[test]
+    fn test_nms_threshold() {
+        let dev: TestDevice = Default::default();
+        // the iou of the first two boxes is 1/3, and the third box overlaps neither
+        let boxes: Tensor<Rank2<3, 4>, TestDtype, _> = dev.tensor([
+            [0.0, 0.0, 2.0, 2.0],
+            [1.0, 0.0, 3.0, 2.0],
+            [4.0, 4.0, 5.0, 5.0],
+        ]);
+        let scores: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.3, 0.9, 0.5]);
+        assert_eq!(nms(boxes.clone(), scores.clone(), 0.5), [1, 2, 0]);
+        assert_eq!(nms(boxes, scores, 0.25), [1, 2]);
+    }
+}
@@ -0,0 +1,144 @@
+use num_traits::Float;
+use rand::Rng;
+use rand_distr::{Distribution, Standard};
+
+use crate::{
+    shapes::{Axis, Dim, Dtype, Rank0},
+    tensor::{NoneTape, Tape, Tensor, TensorFrom},
+};
+
+use super::{multinomial, Device, SelectTo, SumTo, TryMul};
+
+/// A categorical distribution over `N` classes given by unnormalized `logits`, e.g. a
+/// discrete policy in reinforcement learning.
+///
+/// [Categorical::log_prob()] and [Categorical::entropy()] take the distribution by value
+/// to keep the tape of `logits`. To use both on the same logits, build one of them from
+/// `logits.retaped()`, and the tapes are merged when the results are combined.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::Categorical};
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// # let dev: Cpu = Default::default();
+/// let logits: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, 2.0, 3.0]);
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let action = Categorical::new(logits.clone()).sample(&mut rng);
+/// assert!(action < 3);
+///
+/// let logits = logits.leaky_trace();
+/// let log_prob = Categorical::new(logits.retaped::<OwnedTape<_, _>>()).log_prob(action);
+/// let entropy = Categorical::new(logits).entropy();
+/// let loss = -log_prob - entropy * 0.01;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Categorical<N: Dim, E: Dtype, D: Device<E>, T> {
+    pub logits: Tensor<(N,), E, D, T>,
+}
+
+impl<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>> Categorical<N, E, D, T> {
+    pub fn new(logits: Tensor<(N,), E, D, T>) -> Self {
+        Self { logits }
+    }
+
+    /// Draws a class index with probability `softmax(logits)`. Not differentiable.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> usize
+    where
+        Standard: Distribution<E>,
+    {
+        self.try_sample(rng).unwrap()
+    }
+
+    /// See [Categorical::sample()]
+    pub fn try_sample<R: Rng>(&self, rng: &mut R) -> Result<usize, D::Err>
+    where
+        Standard: Distribution<E>,
+    {
+        let probs = self.logits.retaped::<NoneTape>().try_softmax::<Axis<0>>()?;
+        Ok(multinomial(&probs, 1, rng)[0])
+    }
+
+    /// The log probability of `action`, which is `log_softmax(logits)[action]`.
+    ///
+    /// **Panics** if `action` is not `< N`.
+    pub fn log_prob(self, action: usize) -> Tensor<Rank0, E, D, T> {
+        self.try_log_prob(action).unwrap()
+    }
+
+    /// See [Categorical::log_prob()]
+    pub fn try_log_prob(self, action: usize) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+        let action = self.logits.device.tensor(action);
+        self.logits.try_log_softmax::<Axis<0>>()?.try_select(action)
+    }
+
+    /// The entropy `-sum(p * log(p))` of the distribution, with `p = softmax(logits)`.
+    pub fn entropy(self) -> Tensor<Rank0, E, D, T> {
+        self.try_entropy().unwrap()
+    }
+
+    /// See [Categorical::entropy()]
+    pub fn try_entropy(self) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+        let log_probs = self.logits.try_log_softmax::<Axis<0>>()?;
+        let probs = log_probs.retaped::<T>().try_exp()?;
+        probs
+            .try_mul(log_probs)?
+            .try_sum::<Rank0, _>()?
+            .try_negate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_categorical_sample_in_range() {
+        let dev: TestDevice = Default::default();
+        let logits: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([0.0, 10.0, -100.0, 1.0]);
+        let dist = Categorical::new(logits);
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut counts = [0; 4];
+        for _ in 0..100 {
+            counts[dist.sample(&mut rng)] += 1;
+        }
+        // class 2 has a probability of about e^-110
+        assert_eq!(counts[2], 0);
+        assert!(counts[1] > 90, "{counts:?}");
+    }
+
+    #[test]
+    fn test_categorical_log_prob() {
+        let dev: TestDevice = Default::default();
+        let logits: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.5, -1.0, 2.0]);
+        let log_prob = Categorical::new(logits.leaky_trace()).log_prob(2);
+        let expected = logits.leaky_trace().log_softmax::<Axis<0>>();
+        assert_close(&log_prob.array(), &expected.array()[2]);
+
+        // d/dlogits log_softmax(logits)[a] = one_hot(a) - softmax(logits)
+        let probs = logits.clone().softmax::<Axis<0>>().array();
+        let g = log_prob.backward();
+        assert_close(
+            &g.get(&logits).array(),
+            &[-probs[0], -probs[1], 1.0 - probs[2]],
+        );
+    }
+
+    #[test]
+    fn test_categorical_entropy() {
+        let dev: TestDevice = Default::default();
+        let uniform: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([1.0; 4]);
+        let entropy = Categorical::new(uniform).entropy();
+        assert_close(&entropy.array(), &TestDtype::ln(4.0));
+
+        let logits: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.5, -1.0, 2.0]);
+        let entropy = Categorical::new(logits.leaky_trace()).entropy();
+        let p = logits.clone().softmax::<Axis<0>>().array();
+        let h = -p.iter().map(|p| p * p.ln()).sum::<TestDtype>();
+        assert_close(&entropy.array(), &h);
+
+        // dH/dlogits_i = -p_i * (log(p_i) + H)
+        let g = entropy.backward();
+        assert_close(&g.get(&logits).array(), &p.map(|p| -p * (p.ln() + h)));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{Tape, Tensor},
+};
+
+use super::{Device, TryAdd};
+
+/// The Charbonnier penalty `sqrt(t^2 + eps^2)`, a smooth version of `|t|` often used in
+/// image losses (closely related to the
+/// [Pseudo-Huber loss](https://en.wikipedia.org/wiki/Huber_loss#Pseudo-Huber_loss_function)).
+///
+/// It approaches `|t|` when `|t|` is much larger than `eps`, and unlike `|t|` has a
+/// finite gradient everywhere: `t / sqrt(t^2 + eps^2)`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<3>, f32, _> = dev.tensor([-4.0, 0.0, 3.0]);
+/// let r = charbonnier(t, 3.0);
+/// assert_eq!(r.array(), [5.0, 3.0, 4.2426405]);
+/// ```
+pub fn charbonnier<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    eps: E,
+) -> Tensor<S, E, D, T> {
+    t.charbonnier(eps)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [charbonnier]
+    pub fn charbonnier(self, eps: E) -> Self {
+        self.try_charbonnier(eps).unwrap()
+    }
+
+    /// See [charbonnier]
+    pub fn try_charbonnier(self, eps: E) -> Result<Self, D::Err> {
+        self.try_square()?.try_add(eps * eps)?.try_sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_charbonnier_approximates_abs() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([-100.0, -10.0, 10.0, 100.0]);
+        let r = t.leaky_trace().charbonnier(1e-2);
+        assert_close_with_tolerance(&r.array(), &[100.0, 10.0, 10.0, 100.0], 1e-4);
+
+        let g = r.mean().backward();
+        assert_close_with_tolerance(&g.get(&t).array(), &[-0.25, -0.25, 0.25, 0.25], 1e-4);
+    }
+
+    #[test]
+    fn test_charbonnier_smooth_near_zero() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([-0.1, 0.0, 0.1]);
+        let r = t.leaky_trace().charbonnier(0.1);
+        let sqrt_2 = TestDtype::sqrt(2.0);
+        assert_close(&r.array(), &[0.1 * sqrt_2, 0.1, 0.1 * sqrt_2]);
+
+        // the gradient goes smoothly through 0 instead of jumping from -1 to 1
+        let g = r.sum().backward();
+        assert_close(&g.get(&t).array(), &[-1.0 / sqrt_2, 0.0, 1.0 / sqrt_2]);
+    }
+}
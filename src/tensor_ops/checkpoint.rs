@@ -0,0 +1,30 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{unique_id, Gradients, OwnedTape, PutTape, SplitTape, Tensor},
+};
+
+use super::Device;
+
+/// The backward of an activation checkpoint: re-runs `f` on `x` with a tape, seeds it with
+/// the gradient of `y` (the output of the original, untaped run) and adds the resulting
+/// gradients of `x` and any parameters used by `f` into `grads`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn try_recompute_backward<S: Shape, S2: Shape, E: Dtype, D: Device<E>, F>(
+    grads: &mut Gradients<E, D>,
+    x: Tensor<S, E, D>,
+    y: &Tensor<S2, E, D>,
+    f: F,
+) -> Result<(), D::Err>
+where
+    F: FnOnce(
+        Tensor<S, E, D, OwnedTape<E, D>>,
+    ) -> Result<Tensor<S2, E, D, OwnedTape<E, D>>, D::Err>,
+{
+    let device = x.device.clone();
+    // every tensor created by the recomputation has an id after this one,
+    // so anything before it is an input to `f` (i.e. `x` or a parameter).
+    let recompute_start = unique_id();
+    let (y2, mut sub_tape) = f(x.put_tape(OwnedTape::default()))?.split_tape();
+    sub_tape.gradients.insert(&y2, grads.get_ref(y).clone());
+    grads.try_add_where(&device, sub_tape.execute()?, |id| id < recompute_start)
+}
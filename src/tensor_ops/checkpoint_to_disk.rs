@@ -0,0 +1,275 @@
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::PathBuf,
+    sync::atomic::AtomicUsize,
+    vec::Vec,
+};
+
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{cpu::CpuError, OwnedTape, PutTape, SplitTape, Tape, Tensor, UniqueId},
+};
+
+use super::{checkpoint::try_recompute_backward, Device};
+
+impl<S: Shape, E: Dtype, D: Device<E>> Tensor<S, E, D, OwnedTape<E, D>>
+where
+    D::Err: From<CpuError>,
+{
+    /// A disk-backed activation checkpoint of `f`: the input to `f` can be offloaded to
+    /// disk until backward.
+    ///
+    /// `f` runs without keeping any of its intermediate values, and is re-run with
+    /// a tape during backward to compute gradients. The only thing kept for backward is
+    /// the input to `f`, and if it takes at least `spill_bytes` bytes, it is written to
+    /// a temporary file instead of being kept in memory. The file is read back (and
+    /// removed) during backward.
+    ///
+    /// This is not a tape mode that spills every intermediate. Only the input is ever
+    /// written to disk. The intermediate values of `f` aren't spilled, they are recomputed,
+    /// and everything recorded on the tape outside of `f` stays in memory as usual. To
+    /// spill several intermediates of a model, checkpoint each of its blocks, so that the
+    /// output of each block is spilled as the input of the next one.
+    ///
+    /// Since `f` is re-run, it must be deterministic (e.g. no dropout). Parameters
+    /// captured by `f` get gradients if they are retaped inside of it. Note that the input
+    /// is only freed from memory if nothing else holds on to it, and that inputs that
+    /// aren't contiguous (e.g. broadcasted) are always kept in memory.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let w: Tensor<Rank2<3, 3>, f32, _> = dev.sample_normal();
+    /// let x: Tensor<Rank2<2, 3>, f32, _> = dev.sample_normal();
+    /// let w2 = w.clone();
+    /// let y = x
+    ///     .leaky_trace()
+    ///     .checkpoint_to_disk(move |x| x.matmul(w2.retaped::<OwnedTape<_, _>>()).relu(), 0);
+    /// let grads = y.sum().backward();
+    /// assert_eq!(grads.get(&w).shape(), &(Const::<3>, Const::<3>));
+    /// ```
+    pub fn checkpoint_to_disk<S2: Shape, F>(
+        self,
+        f: F,
+        spill_bytes: usize,
+    ) -> Tensor<S2, E, D, OwnedTape<E, D>>
+    where
+        F: 'static + Fn(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<S2, E, D, OwnedTape<E, D>>,
+    {
+        self.try_checkpoint_to_disk(f, spill_bytes).unwrap()
+    }
+
+    /// See [Tensor::checkpoint_to_disk]
+    #[allow(clippy::type_complexity)]
+    pub fn try_checkpoint_to_disk<S2: Shape, F>(
+        self,
+        f: F,
+        spill_bytes: usize,
+    ) -> Result<Tensor<S2, E, D, OwnedTape<E, D>>, D::Err>
+    where
+        F: 'static + Fn(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<S2, E, D, OwnedTape<E, D>>,
+    {
+        let (x, mut tape) = self.split_tape();
+        let (y, _) = f(x.clone().put_tape(Default::default())).split_tape();
+        let phantom_y = y.clone();
+        tape.try_alloc_grad(&x)?;
+        tape.try_alloc_grad(&y)?;
+
+        let contiguous = x.strides == x.shape.strides();
+        let saved =
+            if contiguous && x.shape.num_elements() * std::mem::size_of::<E>() >= spill_bytes {
+                Saved::Spilled {
+                    file: SpillFile::try_write(&x.as_vec())?,
+                    id: x.id,
+                    shape: x.shape,
+                    device: x.device.clone(),
+                }
+            } else {
+                Saved::InMemory(x)
+            };
+
+        tape.add_backward_op(move |grads| {
+            let x = match &saved {
+                Saved::InMemory(x) => x.clone(),
+                Saved::Spilled {
+                    file,
+                    id,
+                    shape,
+                    device,
+                } => {
+                    let mut x = device.try_tensor_from_vec(file.try_read()?, *shape)?;
+                    x.id = *id;
+                    x
+                }
+            };
+            try_recompute_backward(grads, x, &phantom_y, |x| Ok(f(x)))
+        });
+        Ok(y.put_tape(tape))
+    }
+}
+
+/// The input to a checkpoint, as it is kept until backward.
+enum Saved<S: Shape, E: Dtype, D: Device<E>> {
+    InMemory(Tensor<S, E, D>),
+    /// The data is in the file, and the rest is what's needed to rebuild the tensor.
+    Spilled {
+        file: SpillFile,
+        id: UniqueId,
+        shape: S,
+        device: D,
+    },
+}
+
+/// A temporary file holding the raw bytes of a tensor's data. Removed when dropped.
+struct SpillFile {
+    path: PathBuf,
+    len: usize,
+}
+
+impl SpillFile {
+    fn try_write<E: Dtype>(data: &[E]) -> Result<Self, CpuError> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let n = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("dfdx-spill-{}-{n}.bin", std::process::id()));
+        // Safety: `E` is a plain numeric type, so any of its values can be viewed as bytes.
+        let bytes = unsafe {
+            std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+        };
+        let file = Self {
+            path,
+            len: data.len(),
+        };
+        File::create(&file.path)
+            .and_then(|mut f| f.write_all(bytes))
+            .map_err(|_| CpuError::Io)?;
+        Ok(file)
+    }
+
+    fn try_read<E: Dtype>(&self) -> Result<Vec<E>, CpuError> {
+        let mut data = vec![E::default(); self.len];
+        // Safety: the file was written from a `[E]` of the same length, so every
+        // byte pattern read back is a valid `E`.
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(
+                data.as_mut_ptr() as *mut u8,
+                std::mem::size_of_val(data.as_slice()),
+            )
+        };
+        File::open(&self.path)
+            .and_then(|mut f| f.read_exact(bytes))
+            .map_err(|_| CpuError::Io)?;
+        Ok(data)
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+    use std::sync::{Arc, Mutex};
+
+    type Block =
+        fn(
+            Tensor<Rank2<8, 16>, TestDtype, TestDevice, OwnedTape<TestDtype, TestDevice>>,
+            &Tensor<Rank2<16, 16>, TestDtype, TestDevice>,
+            &Tensor<Rank2<16, 16>, TestDtype, TestDevice>,
+        )
+            -> Tensor<Rank2<8, 16>, TestDtype, TestDevice, OwnedTape<TestDtype, TestDevice>>;
+
+    const BLOCK: Block = |x, w1, w2| {
+        let h = x.matmul(w1.retaped::<OwnedTape<_, _>>()).tanh();
+        h.matmul(w2.retaped::<OwnedTape<_, _>>()).sigmoid().square()
+    };
+
+    #[test]
+    fn test_checkpoint_to_disk_matches_in_memory() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<8, 16>, TestDtype, _> = dev.sample_normal();
+        let w1: Tensor<Rank2<16, 16>, TestDtype, _> = dev.sample_normal();
+        let w2: Tensor<Rank2<16, 16>, TestDtype, _> = dev.sample_normal();
+
+        let y = BLOCK(x.leaky_trace().exp(), &w1, &w2);
+        let y_array = y.array();
+        let expected = y.mean().backward();
+
+        for spill_bytes in [0, usize::MAX] {
+            let (a, b) = (w1.clone(), w2.clone());
+            let h = x.leaky_trace().exp();
+            let h_data = h.data.clone();
+            let y2 = h.checkpoint_to_disk(move |h| BLOCK(h, &a, &b), spill_bytes);
+            // when spilled, the checkpoint doesn't hold on to the input's data
+            let expected_refs = if spill_bytes == 0 { 2 } else { 3 };
+            assert_eq!(Arc::strong_count(&h_data), expected_refs);
+            drop(h_data);
+
+            assert_close(&y2.array(), &y_array);
+            let grads = y2.mean().backward();
+            assert_close(&grads.get(&x).array(), &expected.get(&x).array());
+            assert_close(&grads.get(&w1).array(), &expected.get(&w1).array());
+            assert_close(&grads.get(&w2).array(), &expected.get(&w2).array());
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_to_disk_round_trips_intermediate() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<8, 16>, TestDtype, _> = dev.sample_normal();
+        let w1: Tensor<Rank2<16, 16>, TestDtype, _> = dev.sample_normal();
+        let w2: Tensor<Rank2<16, 16>, TestDtype, _> = dev.sample_normal();
+
+        // `h` is an intermediate of the graph, which is spilled and read back in backward
+        let h = x.leaky_trace().exp();
+        let h_vec = h.as_vec();
+        let seen = Arc::new(Mutex::new(std::vec::Vec::new()));
+        let (a, b, s) = (w1.clone(), w2.clone(), seen.clone());
+        let y = h.checkpoint_to_disk(
+            move |h| {
+                s.lock().unwrap().push((h.id, h.as_vec()));
+                BLOCK(h, &a, &b)
+            },
+            0,
+        );
+        let _ = y.mean().backward();
+
+        // once in forward, and once with the data read back from disk in backward
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0], seen[1]);
+        assert_eq!(seen[1].1, h_vec);
+    }
+
+    #[test]
+    fn test_checkpoint_to_disk_broadcasted_input() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<16>, TestDtype, _> = dev.sample_normal();
+        let h: Tensor<Rank2<8, 16>, _, _, _> = x.leaky_trace().broadcast();
+        let expected = h.square().sin().mean().backward();
+
+        // broadcasted inputs aren't spilled, but still get the right gradients
+        let h: Tensor<Rank2<8, 16>, _, _, _> = x.leaky_trace().broadcast();
+        let grads = h
+            .checkpoint_to_disk(|h| h.square().sin(), 0)
+            .mean()
+            .backward();
+        assert_close(&grads.get(&x).array(), &expected.get(&x).array());
+    }
+
+    #[test]
+    fn test_spill_file_roundtrip() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<3, 5>, TestDtype, _> = dev.sample_normal();
+        let file = SpillFile::try_write(&t.as_vec()).unwrap();
+        let path = file.path.clone();
+        assert!(path.exists());
+        assert_eq!(file.try_read::<TestDtype>().unwrap(), t.as_vec());
+        drop(file);
+        assert!(!path.exists());
+    }
+}
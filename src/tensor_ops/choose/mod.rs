@@ -8,6 +8,8 @@ use crate::{
     tensor::{DeviceStorage, HasErr, Merge, PutTape, SplitTape, Tape, Tensor},
 };
 
+use super::ops::profiled;
+
 pub trait ChooseKernel<E: Dtype>: DeviceStorage {
     fn forward<S: Shape>(
         &self,
@@ -72,7 +74,7 @@ impl<
         let (lhs, tape) = lhs.split_tape();
         let (rhs, rhs_tape) = rhs.split_tape();
 
-        let out = lhs.device.forward(&self, &lhs, &rhs)?;
+        let out = profiled(|| lhs.device.forward(&self, &lhs, &rhs))?;
         let phantom_out = out.clone();
 
         let mut tape = tape.merge(rhs_tape);
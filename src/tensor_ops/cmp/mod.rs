@@ -3,6 +3,8 @@ use crate::{
     tensor::{DeviceStorage, NoneTape, Tape, Tensor},
 };
 
+use super::ops::profiled;
+
 mod cpu_kernels;
 #[cfg(feature = "cuda")]
 mod cuda_kernels;
@@ -20,7 +22,7 @@ fn try_cmp_op<Op, S: Shape, E: Unit, D: CmpKernel<Op, E>, T: Tape<E, D>>(
     rhs: &Tensor<S, E, D, T>,
 ) -> Result<Tensor<S, bool, D, NoneTape>, D::Err> {
     assert_eq!(lhs.shape(), rhs.shape());
-    lhs.device.forward(lhs, rhs)
+    profiled(|| lhs.device.forward(lhs, rhs))
 }
 
 pub trait ScalarCmpKernel<Op, E: Unit>: DeviceStorage {
@@ -35,7 +37,7 @@ fn try_scalar_cmp_op<Op, S: Shape, E: Unit, D: ScalarCmpKernel<Op, E>, T: Tape<E
     tensor: &Tensor<S, E, D, T>,
     scalar: E,
 ) -> Result<Tensor<S, bool, D, NoneTape>, D::Err> {
-    tensor.device.forward(tensor, scalar)
+    profiled(|| tensor.device.forward(tensor, scalar))
 }
 
 pub enum EqKernelOp {}
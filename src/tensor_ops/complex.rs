@@ -0,0 +1,155 @@
+use num_traits::Float;
+
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{Merge, NoneTape, Tape, Tensor},
+};
+
+use super::{Device, TryAdd, TryMul, TrySub};
+
+/// A tensor of complex numbers, stored as its real part `re` and imaginary part `im`, e.g.
+/// the output of [super::rfft()].
+///
+/// The methods take `self` by value to keep the tapes of both parts, like
+/// [super::Normal].
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::ComplexTensor};
+/// # let dev: Cpu = Default::default();
+/// // (1 + 2i) * (3 - i) = 5 + 5i
+/// let a = ComplexTensor::new(dev.tensor([1.0f32]), dev.tensor([2.0]));
+/// let b = ComplexTensor::new(dev.tensor([3.0]), dev.tensor([-1.0]));
+/// let c = a * b;
+/// assert_eq!(c.re.array(), [5.0]);
+/// assert_eq!(c.im.array(), [5.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComplexTensor<S: Shape, E: Dtype, D: Device<E>, T = NoneTape> {
+    pub re: Tensor<S, E, D, T>,
+    pub im: Tensor<S, E, D, T>,
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> ComplexTensor<S, E, D, T> {
+    pub fn new(re: Tensor<S, E, D, T>, im: Tensor<S, E, D, T>) -> Self {
+        Self { re, im }
+    }
+
+    /// Element wise `(a + bi) + (c + di) = (a + c) + (b + d)i`, which is also available
+    /// as `lhs + rhs`.
+    pub fn try_add<R: Tape<E, D>>(self, rhs: ComplexTensor<S, E, D, R>) -> Result<Self, D::Err>
+    where
+        T: Merge<R>,
+    {
+        Ok(Self {
+            re: self.re.try_add(rhs.re)?,
+            im: self.im.try_add(rhs.im)?,
+        })
+    }
+
+    /// Element wise `(a + bi) * (c + di) = (ac - bd) + (ad + bc)i`, which is also
+    /// available as `lhs * rhs`.
+    pub fn try_mul<R: Tape<E, D>>(self, rhs: ComplexTensor<S, E, D, R>) -> Result<Self, D::Err>
+    where
+        T: Merge<R> + Merge<T>,
+    {
+        // each part is used twice, so the second uses are retaped copies
+        let bd = self.im.retaped::<T>().try_mul(rhs.im.retaped::<R>())?;
+        let bc = self.im.try_mul(rhs.re.retaped::<R>())?;
+        let ad = self.re.retaped::<T>().try_mul(rhs.im)?;
+        let ac = self.re.try_mul(rhs.re)?;
+        Ok(Self {
+            re: ac.try_sub(bd)?,
+            im: ad.try_add(bc)?,
+        })
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D> + Merge<R>, R: Tape<E, D>>
+    std::ops::Add<ComplexTensor<S, E, D, R>> for ComplexTensor<S, E, D, T>
+{
+    type Output = Self;
+    /// See [ComplexTensor::try_add()]
+    fn add(self, rhs: ComplexTensor<S, E, D, R>) -> Self {
+        self.try_add(rhs).unwrap()
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T, R: Tape<E, D>> std::ops::Mul<ComplexTensor<S, E, D, R>>
+    for ComplexTensor<S, E, D, T>
+where
+    T: Tape<E, D> + Merge<R> + Merge<T>,
+{
+    type Output = Self;
+    /// See [ComplexTensor::try_mul()]
+    fn mul(self, rhs: ComplexTensor<S, E, D, R>) -> Self {
+        self.try_mul(rhs).unwrap()
+    }
+}
+
+impl<S: Shape, E: Dtype + Float, D: Device<E>, T: Tape<E, D> + Merge<T>> ComplexTensor<S, E, D, T> {
+    /// Element wise `|a + bi| = sqrt(a^2 + b^2)`. The gradient is `NaN` where it's 0.
+    pub fn magnitude(self) -> Tensor<S, E, D, T> {
+        self.try_magnitude().unwrap()
+    }
+
+    /// See [ComplexTensor::magnitude()]
+    pub fn try_magnitude(self) -> Result<Tensor<S, E, D, T>, D::Err> {
+        self.re
+            .try_square()?
+            .try_add(self.im.try_square()?)?
+            .try_sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_complex_mul() {
+        let dev: TestDevice = Default::default();
+        let (a, b, c, d) = (
+            [1.0, -2.0, 0.5],
+            [3.0, 1.0, 0.0],
+            [2.0, 4.0, -1.0],
+            [-1.0, 0.5, 2.0],
+        );
+        let x = ComplexTensor::new(dev.tensor(a), dev.tensor(b));
+        let y = ComplexTensor::new(dev.tensor(c), dev.tensor(d));
+        let z = x + y.clone();
+        assert_close(&z.re.array(), &[3.0, 2.0, -0.5]);
+        assert_close(&z.im.array(), &[2.0, 1.5, 2.0]);
+
+        let x = ComplexTensor::new(dev.tensor(a).leaky_trace(), dev.tensor(b).leaky_trace());
+        let z = x * y;
+        let mut re: [TestDtype; 3] = [0.0; 3];
+        let mut im: [TestDtype; 3] = [0.0; 3];
+        for i in 0..3 {
+            re[i] = a[i] * c[i] - b[i] * d[i];
+            im[i] = a[i] * d[i] + b[i] * c[i];
+        }
+        assert_close(&z.re.array(), &re);
+        assert_close(&z.im.array(), &im);
+
+        let mut mag = re;
+        for i in 0..3 {
+            mag[i] = (re[i] * re[i] + im[i] * im[i]).sqrt();
+        }
+        assert_close(&z.magnitude().array(), &mag);
+    }
+
+    #[test]
+    fn test_complex_mul_grads() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, -2.0]);
+        let b: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([3.0, 0.5]);
+        let x = ComplexTensor::new(a.leaky_trace(), b.leaky_trace());
+        let y = ComplexTensor::new(dev.tensor([2.0, 4.0]), dev.tensor([-1.0, 1.0]));
+        let z = x * y;
+        // d/da (re + im) = c + d, d/db (re + im) = c - d
+        let g = (z.re + z.im).sum::<Rank0, _>().backward();
+        assert_close(&g.get(&a).array(), &[1.0, 5.0]);
+        assert_close(&g.get(&b).array(), &[3.0, 3.0]);
+    }
+}
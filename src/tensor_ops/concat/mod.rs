@@ -1,5 +1,7 @@
 use crate::{shapes::*, tensor::*};
 
+use super::ops::profiled;
+
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
@@ -61,7 +63,7 @@ where
         let (rhs, b_tape) = rhs.split_tape();
         let mut tape = a_tape.merge(b_tape);
         let device = lhs.device.clone();
-        let out = device.forward(&lhs, &rhs)?;
+        let out = profiled(|| device.forward(&lhs, &rhs))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&lhs)?;
         tape.try_alloc_grad(&rhs)?;
@@ -0,0 +1,104 @@
+use num_traits::Float;
+
+use crate::{
+    shapes::{Dtype, Rank0, Shape},
+    tensor::Tensor,
+};
+
+use super::{Device, SumTo, TryAdd, TryMul, TrySub};
+
+/// Solves `A x = b` for a symmetric positive definite `A` with the conjugate gradient method,
+/// where `A` is only given as the matrix-vector product `matvec(v) = A v`, e.g. from [super::hvp_fd()].
+///
+/// Starts from `x = 0`, and runs at most `iters` iterations, stopping early once the norm of the
+/// residual `b - A x` is at most `tol` times the norm of `b`. In exact arithmetic this converges
+/// in at most as many iterations as `b` has elements.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::conjugate_gradient};
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[2.0, 1.0], [1.0, 3.0]]);
+/// let b: Tensor<Rank1<2>, f32, _> = dev.tensor([3.0, 5.0]);
+/// let x = conjugate_gradient(|v| v.clone().matmul(a.clone()), b, 10, 1e-6);
+/// let x = x.array();
+/// assert!((x[0] - 0.8).abs() < 1e-5 && (x[1] - 1.4).abs() < 1e-5);
+/// ```
+pub fn conjugate_gradient<S: Shape, E: Dtype + Float, D: Device<E>, F>(
+    mut matvec: F,
+    b: Tensor<S, E, D>,
+    iters: usize,
+    tol: E,
+) -> Tensor<S, E, D>
+where
+    F: FnMut(&Tensor<S, E, D>) -> Tensor<S, E, D>,
+{
+    try_conjugate_gradient(|v| Ok::<_, D::Err>(matvec(v)), b, iters, tol).unwrap()
+}
+
+/// See [conjugate_gradient()]. Here `matvec` can fail too, with any error that a device
+/// error converts into.
+pub fn try_conjugate_gradient<S: Shape, E: Dtype + Float, D: Device<E>, F, Err>(
+    mut matvec: F,
+    b: Tensor<S, E, D>,
+    iters: usize,
+    tol: E,
+) -> Result<Tensor<S, E, D>, Err>
+where
+    F: FnMut(&Tensor<S, E, D>) -> Result<Tensor<S, E, D>, Err>,
+    Err: From<D::Err>,
+{
+    let dot = |a: &Tensor<S, E, D>, b: &Tensor<S, E, D>| -> Result<E, D::Err> {
+        Ok(a.clone()
+            .try_mul(b.clone())?
+            .try_sum::<Rank0, _>()?
+            .as_vec()[0])
+    };
+
+    let mut x = b.device.try_zeros_like(&b.shape)?;
+    let mut r = b.clone();
+    let mut p = b.clone();
+    let mut r_norm_sq = dot(&r, &r)?;
+    let threshold = tol * tol * r_norm_sq;
+    for _ in 0..iters {
+        if r_norm_sq <= threshold {
+            break;
+        }
+        let ap = matvec(&p)?;
+        let alpha = r_norm_sq / dot(&p, &ap)?;
+        x = x.try_add(p.clone().try_mul(alpha)?)?;
+        r = r.try_sub(ap.try_mul(alpha)?)?;
+        let next_norm_sq = dot(&r, &r)?;
+        p = r.clone().try_add(p.try_mul(next_norm_sq / r_norm_sq)?)?;
+        r_norm_sq = next_norm_sq;
+    }
+    Ok(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_conjugate_gradient_spd_system() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<3, 3>, TestDtype, _> =
+            dev.tensor([[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]]);
+        // A @ [1, -2, 3]
+        let b: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([2.0, -2.0, 4.0]);
+
+        let mut num_matvecs = 0;
+        let x = conjugate_gradient(
+            |v| {
+                num_matvecs += 1;
+                v.clone().matmul(a.clone())
+            },
+            b,
+            100,
+            1e-6,
+        );
+        assert_close_with_tolerance(&x.array(), &[1.0, -2.0, 3.0], 1e-4);
+        // converges in about as many iterations as there are unknowns
+        assert!(num_matvecs <= 5, "{num_matvecs}");
+    }
+}
@@ -8,6 +8,8 @@ use crate::{
     tensor::{DeviceStorage, HasErr, PutTape, SplitTape, Tape, Tensor, ZerosTensor},
 };
 
+use super::ops::profiled;
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub(super) struct Conv2DOp {
@@ -161,7 +163,7 @@ impl<
         let mut out = lhs
             .device
             .alloc((Const, h.convolve_dim(), w.convolve_dim()))?;
-        lhs.device.forward(op, &lhs, &rhs, &mut out)?;
+        profiled(|| lhs.device.forward(op, &lhs, &rhs, &mut out))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&lhs)?;
         tape.try_alloc_grad(&rhs)?;
@@ -205,7 +207,7 @@ impl<
             .device
             .alloc((batch, Const, h.convolve_dim(), w.convolve_dim()))?;
         let mut tape = ltape.merge(rtape);
-        lhs.device.forward(op, &lhs, &rhs, &mut out)?;
+        profiled(|| lhs.device.forward(op, &lhs, &rhs, &mut out))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&lhs)?;
         tape.try_alloc_grad(&rhs)?;
@@ -8,6 +8,8 @@ use crate::{
     tensor::{DeviceStorage, HasErr, PutTape, SplitTape, Tape, Tensor, ZerosTensor},
 };
 
+use super::ops::profiled;
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub(super) struct ConvTrans2DOp {
@@ -160,7 +162,7 @@ impl<
         let mut out = lhs
             .device
             .try_zeros_like(&(Const, h.convolve_dim(), w.convolve_dim()))?;
-        lhs.device.forward(op, &lhs, &rhs, &mut out)?;
+        profiled(|| lhs.device.forward(op, &lhs, &rhs, &mut out))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&lhs)?;
         tape.try_alloc_grad(&rhs)?;
@@ -205,7 +207,7 @@ impl<
             lhs.device
                 .try_zeros_like(&(batch, Const, h.convolve_dim(), w.convolve_dim()))?;
         let mut tape = ltape.merge(rtape);
-        lhs.device.forward(op, &lhs, &rhs, &mut out)?;
+        profiled(|| lhs.device.forward(op, &lhs, &rhs, &mut out))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&lhs)?;
         tape.try_alloc_grad(&rhs)?;
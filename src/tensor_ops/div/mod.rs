@@ -3,7 +3,7 @@ mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
-use super::{ops::*, Device};
+use super::{ops::*, Device, TryMul};
 use crate::{shapes::*, tensor::*};
 
 #[repr(C)]
@@ -78,6 +78,33 @@ where
     }
 }
 
+/// Reverse scalar division; `s / t`. The gradient is `-s / t^2`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([0.5, 1.0, 4.0]);
+/// let r = t.rdiv_scalar(2.0);
+/// assert_eq!(r.array(), [4.0, 2.0, 0.5]);
+/// ```
+pub fn rdiv_scalar<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    s: E,
+) -> Tensor<S, E, D, T> {
+    t.rdiv_scalar(s)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [rdiv_scalar]
+    pub fn rdiv_scalar(self, s: E) -> Self {
+        self.try_rdiv_scalar(s).unwrap()
+    }
+    /// See [rdiv_scalar]
+    pub fn try_rdiv_scalar(self, s: E) -> Result<Self, D::Err> {
+        self.try_powi(-1)?.try_mul(s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tensor::*;
@@ -173,4 +200,14 @@ mod tests {
         let g = r.exp().sum().backward();
         assert_close(&g.get(&x).array(), &[[0.8243606; 2]; 3]);
     }
+
+    #[test]
+    fn test_rdiv_scalar() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<_, TestDtype, _> = dev.tensor([0.5, 1.0, 2.0]);
+        let r = x.leaky_trace().rdiv_scalar(3.0);
+        assert_close(&r.array(), &[6.0, 3.0, 1.5]);
+        let g = r.sum().backward();
+        assert_close(&g.get(&x).array(), &[-12.0, -3.0, -0.75]);
+    }
 }
@@ -8,6 +8,8 @@ use crate::{
     tensor::{DeviceStorage, PutTape, SplitTape, Tape, Tensor},
 };
 
+use super::ops::profiled;
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct DropoutKernelOp<F> {
@@ -66,7 +68,7 @@ impl<S: Shape, E: Dtype, D: DropoutKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T>
         let seed = self.device.random_u64();
         let op = DropoutKernelOp { seed, prob };
         let (inp, mut tape) = self.split_tape();
-        let out = inp.device.forward(op, &inp)?;
+        let out = profiled(|| inp.device.forward(op, &inp))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&inp)?;
         tape.try_alloc_grad(&out)?;
@@ -0,0 +1,71 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+use num_traits::{Float, FloatConst};
+
+/// Below this the taylor series of erf is used, and above it the continued fraction of erfc.
+const SERIES_CUTOFF: f64 = 1.5;
+
+/// Taylor series `2 / sqrt(pi) * sum((-1)^n * x^(2n+1) / (n! * (2n+1)))`.
+fn erf_series(x: f64) -> f64 {
+    let x2 = x * x;
+    let mut term = x;
+    let mut sum = x;
+    for n in 1..100 {
+        term *= -x2 / n as f64;
+        let c = term / (2 * n + 1) as f64;
+        sum += c;
+        if c.abs() <= f64::EPSILON * sum.abs() {
+            break;
+        }
+    }
+    sum * core::f64::consts::FRAC_2_SQRT_PI
+}
+
+/// Continued fraction `exp(-x^2) / sqrt(pi) / (x + (1/2) / (x + 1 / (x + (3/2) / (x + ...))))`,
+/// which converges for `x > 0`.
+fn erfc_continued_fraction(x: f64) -> f64 {
+    let mut t = x;
+    for k in (1..=100).rev() {
+        t = x + (k as f64 / 2.0) / t;
+    }
+    (-x * x).exp() * core::f64::consts::FRAC_2_SQRT_PI / (2.0 * t)
+}
+
+pub(super) fn erf(x: f64) -> f64 {
+    if x.abs() < SERIES_CUTOFF {
+        erf_series(x)
+    } else {
+        (1.0 - erfc_continued_fraction(x.abs())).copysign(x)
+    }
+}
+
+pub(super) fn erfc(x: f64) -> f64 {
+    if x.abs() < SERIES_CUTOFF {
+        1.0 - erf_series(x)
+    } else if x > 0.0 {
+        erfc_continued_fraction(x)
+    } else {
+        2.0 - erfc_continued_fraction(-x)
+    }
+}
+
+impl<F: Float + FloatConst> UnaryDerivative<F> for super::ErfKernelOp {
+    #[inline(always)]
+    fn f(&self, x: &F) -> F {
+        F::from(erf(x.to_f64().unwrap())).unwrap()
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        F::FRAC_2_SQRT_PI() * (-x * x).exp()
+    }
+}
+
+impl<F: Float + FloatConst> UnaryDerivative<F> for super::ErfcKernelOp {
+    #[inline(always)]
+    fn f(&self, x: &F) -> F {
+        F::from(erfc(x.to_f64().unwrap())).unwrap()
+    }
+    #[inline(always)]
+    fn df(&self, &x: &F) -> F {
+        -F::FRAC_2_SQRT_PI() * (-x * x).exp()
+    }
+}
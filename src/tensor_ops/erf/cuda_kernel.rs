@@ -0,0 +1,35 @@
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for super::ErfKernelOp {}
+unsafe impl cudarc::driver::DeviceRepr for super::ErfcKernelOp {}
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/erf.ptx"));
+
+cuda_unary!(
+    super::ErfKernelOp,
+    f32,
+    PTX_SRC,
+    "erf_fwd_f32",
+    "erf_bwd_f32"
+);
+cuda_unary!(
+    super::ErfKernelOp,
+    f64,
+    PTX_SRC,
+    "erf_fwd_f64",
+    "erf_bwd_f64"
+);
+cuda_unary!(
+    super::ErfcKernelOp,
+    f32,
+    PTX_SRC,
+    "erfc_fwd_f32",
+    "erfc_bwd_f32"
+);
+cuda_unary!(
+    super::ErfcKernelOp,
+    f64,
+    PTX_SRC,
+    "erfc_fwd_f64",
+    "erfc_bwd_f64"
+);
@@ -0,0 +1,22 @@
+#include "unary_op_macros.cuh"
+#define _USE_MATH_DEFINES
+#include <math.h>
+
+struct ErfKernelOp {};
+struct ErfcKernelOp {};
+
+UNARY_OP(float, erf_fwd_f32, erf_bwd_f32, ErfKernelOp,
+        erff(x),
+        M_2_SQRTPI * expf(-x * x))
+
+UNARY_OP(double, erf_fwd_f64, erf_bwd_f64, ErfKernelOp,
+        erf(x),
+        M_2_SQRTPI * exp(-x * x))
+
+UNARY_OP(float, erfc_fwd_f32, erfc_bwd_f32, ErfcKernelOp,
+        erfcf(x),
+        -M_2_SQRTPI * expf(-x * x))
+
+UNARY_OP(double, erfc_fwd_f64, erfc_bwd_f64, ErfcKernelOp,
+        erfc(x),
+        -M_2_SQRTPI * exp(-x * x))
@@ -0,0 +1,123 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ErfKernelOp;
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ErfcKernelOp;
+
+/// [Error function](https://en.wikipedia.org/wiki/Error_function). `2 / sqrt(pi) * integral(exp(-s^2), 0, t)`
+///
+/// It's derivative is `2 / sqrt(pi) * exp(-t^2)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.erf();
+/// ```
+pub fn erf<S: Shape, E: Dtype, D: UnaryKernel<ErfKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.erf()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<ErfKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [erf]
+    pub fn erf(self) -> Self {
+        self.try_erf().unwrap()
+    }
+    /// See [erf]
+    pub fn try_erf(self) -> Result<Self, D::Err> {
+        try_unary_op(ErfKernelOp, self)
+    }
+}
+
+/// Complementary error function. `1 - erf(t)`, but without the loss of precision
+/// for large `t`.
+///
+/// It's derivative is `-2 / sqrt(pi) * exp(-t^2)`.
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.erfc();
+/// ```
+pub fn erfc<S: Shape, E: Dtype, D: UnaryKernel<ErfcKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.erfc()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<ErfcKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [erfc]
+    pub fn erfc(self) -> Self {
+        self.try_erfc().unwrap()
+    }
+    /// See [erfc]
+    pub fn try_erfc(self) -> Result<Self, D::Err> {
+        try_unary_op(ErfcKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    const XS: [f64; 5] = [-2.0, -0.5, 0.0, 0.3, 1.5];
+
+    fn finite_differences(f: impl Fn(f64) -> f64) -> [TestDtype; 5] {
+        let eps = 1e-5;
+        XS.map(|x| ((f(x + eps) - f(x - eps)) / (2.0 * eps)) as TestDtype)
+    }
+
+    #[test]
+    fn test_erf() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<_, TestDtype, _> = dev.tensor(XS.map(|x| x as TestDtype));
+        let r = x.leaky_trace().erf();
+        assert_close(
+            &r.array(),
+            &[-0.9953223, -0.5204999, 0.0, 0.32862675, 0.96610515],
+        );
+        let g = r.sum().backward();
+        assert_close(&g.get(&x).array(), &finite_differences(libm::erf));
+    }
+
+    #[test]
+    fn test_erfc() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<_, TestDtype, _> = dev.tensor(XS.map(|x| x as TestDtype));
+        let r = x.leaky_trace().erfc();
+        assert_close(
+            &r.array(),
+            &[1.9953223, 1.5205, 1.0, 0.67137325, 0.033894854],
+        );
+        let g = r.sum().backward();
+        assert_close(&g.get(&x).array(), &finite_differences(libm::erfc));
+    }
+
+    #[test]
+    fn test_cpu_erf_matches_libm() {
+        for i in -6000..=6000 {
+            let x = i as f64 / 1000.0;
+            let (erf, erfc) = (super::cpu_kernel::erf(x), super::cpu_kernel::erfc(x));
+            assert!((erf - libm::erf(x)).abs() <= 1e-13, "erf({x}) = {erf}");
+            assert!(
+                (erfc - libm::erfc(x)).abs() <= 1e-13 * libm::erfc(x),
+                "erfc({x}) = {erfc}"
+            );
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for super::Expm1KernelOp {}
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/expm1.ptx"));
+
+cuda_unary!(
+    super::Expm1KernelOp,
+    f32,
+    PTX_SRC,
+    "expm1_fwd_f32",
+    "expm1_bwd_f32"
+);
+cuda_unary!(
+    super::Expm1KernelOp,
+    f64,
+    PTX_SRC,
+    "expm1_fwd_f64",
+    "expm1_bwd_f64"
+);
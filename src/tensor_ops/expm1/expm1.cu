@@ -0,0 +1,11 @@
+#include "unary_op_macros.cuh"
+
+struct Expm1KernelOp {};
+
+UNARY_OP(float, expm1_fwd_f32, expm1_bwd_f32, Expm1KernelOp,
+        expm1f(x),
+        expf(x))
+
+UNARY_OP(double, expm1_fwd_f64, expm1_bwd_f64, Expm1KernelOp,
+        expm1(x),
+        exp(x))
@@ -0,0 +1,75 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Expm1KernelOp;
+
+/// `exp(t) - 1`, computed without the loss of precision that `t.exp() - 1` has
+/// for values of `t` close to 0.
+///
+/// It's derivative is `exp(t)`.
+///
+/// **Pytorch equivalent**: `torch.expm1(t)`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1e-7, 1.0]);
+/// let r = t.expm1();
+/// ```
+pub fn expm1<S: Shape, E: Dtype, D: UnaryKernel<Expm1KernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.expm1()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<Expm1KernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [expm1]
+    pub fn expm1(self) -> Self {
+        self.try_expm1().unwrap()
+    }
+    /// See [expm1]
+    pub fn try_expm1(self) -> Result<Self, D::Err> {
+        try_unary_op(Expm1KernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_expm1() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<_, TestDtype, _> = dev.tensor([-1.0, 0.0, 1.0]);
+        let r = x.leaky_trace().expm1();
+        assert_close(
+            &r.array(),
+            &[TestDtype::exp(-1.0) - 1.0, 0.0, TestDtype::exp(1.0) - 1.0],
+        );
+        let g = r.sum().backward();
+        assert_close(
+            &g.get(&x).array(),
+            &[TestDtype::exp(-1.0), 1.0, TestDtype::exp(1.0)],
+        );
+    }
+
+    #[test]
+    fn test_expm1_small_values() {
+        let dev: TestDevice = Default::default();
+        let small: TestDtype = 1e-10;
+        // exp(x) - 1 = x + x^2 / 2 + ...
+        let expected = small + small * small / 2.0;
+        let stable = dev.tensor([small]).expm1().array()[0];
+        let naive = (dev.tensor([small]).exp() - 1.0).array()[0];
+        assert!((stable - expected).abs() < (naive - expected).abs());
+        assert!((stable - expected).abs() / expected < 1e-6);
+    }
+}
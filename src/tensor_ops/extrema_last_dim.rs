@@ -0,0 +1,135 @@
+use crate::{
+    shapes::{Dtype, ReduceShape, Shape},
+    tensor::{Tape, Tensor},
+};
+
+use super::{Device, MaxTo, MinTo};
+
+/// Takes the max of the last dimension. Same as [MaxTo::max] with `S::LastAxis`.
+///
+/// Ties are handled like [MaxTo::max]: every element equal to the maximum of its row
+/// gets the full gradient of that row.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[3.0, 3.0, 1.0], [-1.0, 2.0, 0.0]]);
+/// let r = max_last_dim(t.leaky_trace());
+/// assert_eq!(r.array(), [3.0, 2.0]);
+/// let g = r.sum().backward();
+/// assert_eq!(g.get(&t).array(), [[1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]);
+/// ```
+pub fn max_last_dim<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D, T> {
+    t.max_last_dim()
+}
+
+/// Takes the min of the last dimension. Same as [MinTo::min] with `S::LastAxis`.
+///
+/// Like [max_last_dim], every element equal to the minimum of its row gets the full
+/// gradient of that row.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[3.0, 1.0, 1.0], [-1.0, 2.0, 0.0]]);
+/// let r = min_last_dim(t.leaky_trace());
+/// assert_eq!(r.array(), [1.0, -1.0]);
+/// let g = r.sum().backward();
+/// assert_eq!(g.get(&t).array(), [[0.0, 1.0, 1.0], [1.0, 0.0, 0.0]]);
+/// ```
+pub fn min_last_dim<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D, T> {
+    t.min_last_dim()
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [max_last_dim]
+    pub fn max_last_dim(self) -> Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D, T> {
+        self.try_max_last_dim().unwrap()
+    }
+
+    /// See [max_last_dim]
+    #[allow(clippy::type_complexity)]
+    pub fn try_max_last_dim(
+        self,
+    ) -> Result<Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D, T>, D::Err> {
+        self.try_max::<_, S::LastAxis>()
+    }
+
+    /// See [min_last_dim]
+    pub fn min_last_dim(self) -> Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D, T> {
+        self.try_min_last_dim().unwrap()
+    }
+
+    /// See [min_last_dim]
+    #[allow(clippy::type_complexity)]
+    pub fn try_min_last_dim(
+        self,
+    ) -> Result<Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D, T>, D::Err> {
+        self.try_min::<_, S::LastAxis>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_max_last_dim_ties_all_get_gradient() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor([[3.0, 3.0, 1.0], [0.5, -1.0, 2.0]]);
+        let r = t.leaky_trace().max_last_dim();
+        assert_eq!(r.array(), [3.0, 2.0]);
+        let g = r.exp().mean().backward();
+        let (a, b) = (3.0f64.exp() / 2.0, 2.0f64.exp() / 2.0);
+        assert_close(
+            &g.get(&t).array(),
+            &[
+                [a as TestDtype, a as TestDtype, 0.0],
+                [0.0, 0.0, b as TestDtype],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_min_last_dim_ties_all_get_gradient() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor([[3.0, 1.0, 1.0], [0.5, -1.0, 2.0]]);
+        let r = t.leaky_trace().min_last_dim();
+        assert_eq!(r.array(), [1.0, -1.0]);
+        let g = r.exp().mean().backward();
+        let (a, b) = (1.0f64.exp() / 2.0, (-1.0f64).exp() / 2.0);
+        assert_close(
+            &g.get(&t).array(),
+            &[
+                [0.0, a as TestDtype, a as TestDtype],
+                [0.0, b as TestDtype, 0.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn test_max_last_dim_3d_is_per_row() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 2, 3>, TestDtype, _> = dev.tensor([
+            [[1.0, 5.0, 2.0], [-3.0, -2.0, -4.0]],
+            [[0.0, 0.5, 0.0], [7.0, 1.0, 9.0]],
+        ]);
+        let r = max_last_dim(t.leaky_trace());
+        assert_eq!(r.array(), [[5.0, -2.0], [0.5, 9.0]]);
+        let g = r.sum().backward();
+        assert_eq!(
+            g.get(&t).array(),
+            [
+                [[0.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+                [[0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+            ]
+        );
+        let r = min_last_dim(t);
+        assert_eq!(r.array(), [[1.0, -4.0], [0.0, 1.0]]);
+    }
+}
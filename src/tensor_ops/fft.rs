@@ -0,0 +1,181 @@
+use num_traits::Float;
+use std::vec::Vec;
+
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Merge, Tape, Tensor},
+};
+
+use super::{ComplexTensor, Device, TryAdd, TryMatMul};
+
+/// `(rows, cols)` matrices of `scale(row) * cos(theta)` and `scale(row) * sin(theta)` with
+/// `theta = 2 * pi * row * col / len`, in row major order.
+fn dft_matrices<E: Dtype + Float>(
+    rows: usize,
+    cols: usize,
+    len: usize,
+    scale: impl Fn(usize) -> f64,
+) -> (Vec<E>, Vec<E>) {
+    let mut cos = Vec::with_capacity(rows * cols);
+    let mut sin = Vec::with_capacity(rows * cols);
+    for r in 0..rows {
+        for c in 0..cols {
+            let theta = 2.0 * core::f64::consts::PI * ((r * c) % len) as f64 / len as f64;
+            cos.push(E::from(scale(r) * theta.cos()).unwrap());
+            sin.push(E::from(scale(r) * theta.sin()).unwrap());
+        }
+    }
+    (cos, sin)
+}
+
+/// The discrete Fourier transform of the real signal `t`, at its `N / 2 + 1` non-negative
+/// frequencies. The rest are their complex conjugates.
+///
+/// This is computed with a matrix multiplication by the DFT matrix, which takes `O(N^2)`,
+/// and the gradient is the conjugate transpose of that. The real part keeps the tape of `t`
+/// and the imaginary part gets an empty tape, which is merged back once the two are combined.
+///
+/// **Pytorch equivalent**: `torch.fft.rfft(t)`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::rfft};
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<4>, f32, _> = dev.tensor([1.0, 0.0, -1.0, 0.0]);
+/// let freqs = rfft(t);
+/// assert_eq!(freqs.re.shape(), &(3,));
+/// assert!((freqs.re.as_vec()[1] - 2.0).abs() < 1e-6);
+/// assert!(freqs.im.as_vec().iter().all(|x| x.abs() < 1e-6));
+/// ```
+pub fn rfft<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(N,), E, D, T>,
+) -> ComplexTensor<(usize,), E, D, T> {
+    try_rfft(t).unwrap()
+}
+
+/// See [rfft()]
+pub fn try_rfft<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(N,), E, D, T>,
+) -> Result<ComplexTensor<(usize,), E, D, T>, D::Err> {
+    let n = t.shape.0;
+    let num_freqs = n.size() / 2 + 1;
+    // `(N, M)` matrices, with `X[k] = sum_n x[n] * (cos - i * sin)`
+    let (cos, sin) = dft_matrices::<E>(n.size(), num_freqs, n.size(), |_| 1.0);
+    let neg_sin = sin.into_iter().map(|s| -s).collect();
+    let cos = t.device.try_tensor_from_vec(cos, (n, num_freqs))?;
+    let neg_sin = t.device.try_tensor_from_vec(neg_sin, (n, num_freqs))?;
+    let im = t.retaped::<T>().try_matmul(neg_sin)?;
+    let re = t.try_matmul(cos)?;
+    Ok(ComplexTensor::new(re, im))
+}
+
+/// The inverse of [rfft()], which recovers the real signal of length `n` from its `n / 2 + 1`
+/// non-negative frequencies.
+///
+/// Like [rfft()], this is a matrix multiplication that takes `O(n^2)`. The imaginary parts of
+/// the first frequency, and of the last if `n` is even, are ignored.
+///
+/// **Panics** if `freqs` doesn't have `n / 2 + 1` elements.
+///
+/// **Pytorch equivalent**: `torch.fft.irfft(freqs, n)`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::{irfft, rfft}};
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<5>, f32, _> = dev.tensor([1.0, 2.0, 3.0, 4.0, 5.0]);
+/// let r = irfft(rfft(t.clone()), 5);
+/// assert!(r.as_vec().iter().zip(t.array()).all(|(a, b)| (a - b).abs() < 1e-5));
+/// ```
+pub fn irfft<M: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D> + Merge<T>>(
+    freqs: ComplexTensor<(M,), E, D, T>,
+    n: usize,
+) -> Tensor<(usize,), E, D, T> {
+    try_irfft(freqs, n).unwrap()
+}
+
+/// See [irfft()]
+pub fn try_irfft<M: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D> + Merge<T>>(
+    freqs: ComplexTensor<(M,), E, D, T>,
+    n: usize,
+) -> Result<Tensor<(usize,), E, D, T>, D::Err> {
+    let ComplexTensor { re, im } = freqs;
+    let num_freqs = re.shape.0;
+    assert_eq!(
+        num_freqs.size(),
+        n / 2 + 1,
+        "expected n / 2 + 1 frequencies"
+    );
+    assert_eq!(im.shape.0.size(), num_freqs.size());
+    // every frequency but the first & the nyquist stands in for its conjugate too
+    let weight = |k: usize| {
+        let mirrored = k > 0 && 2 * k != n;
+        (if mirrored { 2.0 } else { 1.0 }) / n as f64
+    };
+    // `(M, n)` matrices, `x[n] = sum_k w_k * (re[k] * cos - im[k] * sin)`
+    let (cos, sin) = dft_matrices::<E>(num_freqs.size(), n, n, weight);
+    let neg_sin = sin.into_iter().map(|s| -s).collect();
+    let cos = re.device.try_tensor_from_vec(cos, (num_freqs, n))?;
+    let neg_sin = re.device.try_tensor_from_vec(neg_sin, (num_freqs, n))?;
+    let from_im = im.try_matmul(neg_sin)?;
+    re.try_matmul(cos)?.try_add(from_im)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_rfft_irfft_round_trip() {
+        let dev: TestDevice = Default::default();
+        let even: Tensor<Rank1<8>, TestDtype, _> = dev.sample_normal();
+        let freqs = rfft(even.clone());
+        assert_eq!(freqs.re.shape(), &(5,));
+        let r: [TestDtype; 8] = irfft(freqs, 8).as_vec().try_into().unwrap();
+        assert_close(&r, &even.array());
+
+        let odd: Tensor<Rank1<7>, TestDtype, _> = dev.sample_normal();
+        let freqs = rfft(odd.clone());
+        assert_eq!(freqs.im.shape(), &(4,));
+        let r: [TestDtype; 7] = irfft(freqs, 7).as_vec().try_into().unwrap();
+        assert_close(&r, &odd.array());
+    }
+
+    #[test]
+    fn test_rfft_matches_dft() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([1.0, 2.0, 0.0, -1.0]);
+        let ComplexTensor { re, im } = rfft(x);
+        let re: [TestDtype; 3] = re.as_vec().try_into().unwrap();
+        let im: [TestDtype; 3] = im.as_vec().try_into().unwrap();
+        // X[k] = sum_n x[n] * e^(-2 pi i k n / 4)
+        assert_close(&re, &[2.0, 1.0, 0.0]);
+        assert_close(&im, &[0.0, -3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_rfft_grad_finite_differences() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<6>, TestDtype, _> = dev.sample_normal();
+        let w_re: Tensor<(usize,), TestDtype, _> = dev.sample_normal_like(&(4,));
+        let w_im: Tensor<(usize,), TestDtype, _> = dev.sample_normal_like(&(4,));
+        // a loss that depends on both the real & imaginary parts, non linearly
+        let loss = |x: Tensor<Rank1<6>, TestDtype, _, OwnedTape<_, _>>| {
+            let ComplexTensor { re, im } = rfft(x);
+            let re = (re * w_re.clone()).square().sum::<Rank0, _>();
+            re + (im * w_im.clone()).sum::<Rank0, _>()
+        };
+        let g = loss(x.leaky_trace()).backward().get(&x).array();
+
+        let h = 1e-2;
+        let x_arr = x.array();
+        for i in 0..6 {
+            let (mut plus, mut minus) = (x_arr, x_arr);
+            plus[i] += h;
+            minus[i] -= h;
+            let fd = (loss(dev.tensor(plus).leaky_trace()).array()
+                - loss(dev.tensor(minus).leaky_trace()).array())
+                / (2.0 * h);
+            assert!((fd - g[i]).abs() < 1e-2, "{i}: {fd} vs {}", g[i]);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use num_traits::Float;
+use std::vec::Vec;
+
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Merge, Tape, Tensor},
+};
+
+use super::{ops::try_host_binary_op, Device};
+
+/// In place radix 2 fast Fourier transform of `buf`, whose length must be a power of 2.
+/// The inverse isn't scaled by `1 / len`.
+fn fft(buf: &mut [(f64, f64)], inverse: bool) {
+    let n = buf.len();
+    // bit reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            buf.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let theta = sign * 2.0 * core::f64::consts::PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (s, c) = (theta * k as f64).sin_cos();
+                let (a, b) = (buf[start + k], buf[start + k + len / 2]);
+                let b = (b.0 * c - b.1 * s, b.0 * s + b.1 * c);
+                buf[start + k] = (a.0 + b.0, a.1 + b.1);
+                buf[start + k + len / 2] = (a.0 - b.0, a.1 - b.1);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// The full linear convolution of `a` and `b`, of length `a.len() + b.len() - 1`.
+fn convolve_full(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let out_len = a.len() + b.len() - 1;
+    let n = out_len.next_power_of_two();
+    let mut fa: Vec<(f64, f64)> = a.iter().map(|&x| (x, 0.0)).collect();
+    let mut fb: Vec<(f64, f64)> = b.iter().map(|&x| (x, 0.0)).collect();
+    fa.resize(n, (0.0, 0.0));
+    fb.resize(n, (0.0, 0.0));
+    fft(&mut fa, false);
+    fft(&mut fb, false);
+    for (a, b) in fa.iter_mut().zip(fb.iter()) {
+        *a = (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0);
+    }
+    fft(&mut fa, true);
+    fa[..out_len].iter().map(|x| x.0 / n as f64).collect()
+}
+
+/// The valid 1d convolution of `signal` with `kernel`, computed in the frequency domain,
+/// which takes `O(L log L)` instead of the `O(L * K)` of a direct convolution. So it's
+/// faster for large kernels.
+///
+/// Like [super::TryConv2D], this is a cross correlation, i.e. the kernel isn't flipped,
+/// so `out[i] = sum_k signal[i + k] * kernel[k]` for `i` in `0..L - K + 1`. The gradients
+/// of both the signal and the kernel are computed with FFTs too.
+///
+/// **Panics** if the kernel is empty or longer than the signal.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::fft_conv1d};
+/// # let dev: Cpu = Default::default();
+/// let signal: Tensor<Rank1<5>, f32, _> = dev.tensor([1.0, 2.0, 3.0, 4.0, 5.0]);
+/// let kernel: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, -1.0]);
+/// let r = fft_conv1d(signal, kernel);
+/// assert!(r.as_vec().iter().all(|x| (x + 1.0).abs() < 1e-5));
+/// ```
+pub fn fft_conv1d<L: Dim, K: Dim, E: Dtype + Float, D: Device<E>, T, R>(
+    signal: Tensor<(L,), E, D, T>,
+    kernel: Tensor<(K,), E, D, R>,
+) -> Tensor<(usize,), E, D, T>
+where
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    try_fft_conv1d(signal, kernel).unwrap()
+}
+
+/// See [fft_conv1d()]
+pub fn try_fft_conv1d<L: Dim, K: Dim, E: Dtype + Float, D: Device<E>, T, R>(
+    signal: Tensor<(L,), E, D, T>,
+    kernel: Tensor<(K,), E, D, R>,
+) -> Result<Tensor<(usize,), E, D, T>, D::Err>
+where
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    let (len, k) = (signal.shape.0.size(), kernel.shape.0.size());
+    assert!(k > 0, "the kernel can't be empty");
+    assert!(k <= len, "the kernel can't be longer than the signal");
+    let out_len = len - k + 1;
+    let to_f64 = |x: &[E]| x.iter().map(|x| x.to_f64().unwrap()).collect::<Vec<f64>>();
+    let from_f64 = |x: &[f64]| x.iter().map(|&x| E::from_f64(x).unwrap()).collect();
+    try_host_binary_op(
+        signal,
+        kernel,
+        (out_len,),
+        |signal, kernel| {
+            let mut flipped = to_f64(kernel);
+            flipped.reverse();
+            from_f64(&convolve_full(&to_f64(signal), &flipped)[k - 1..len])
+        },
+        move |signal, kernel, _, grad_out| {
+            let grad_out = to_f64(grad_out);
+            // the signal's gradient is the full convolution of the output's with the kernel
+            let grad_signal = convolve_full(&grad_out, &to_f64(kernel));
+            // and the kernel's is the cross correlation of the signal with the output's
+            let mut flipped = grad_out;
+            flipped.reverse();
+            let grad_kernel = convolve_full(&to_f64(signal), &flipped);
+            (
+                from_f64(&grad_signal),
+                from_f64(&grad_kernel[out_len - 1..len]),
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    /// `out[i] = sum_k signal[i + k] * kernel[k]`
+    fn direct_conv1d(signal: &[TestDtype], kernel: &[TestDtype]) -> Vec<TestDtype> {
+        (0..signal.len() - kernel.len() + 1)
+            .map(|i| {
+                kernel
+                    .iter()
+                    .enumerate()
+                    .map(|(k, w)| signal[i + k] * w)
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fft_conv1d_matches_direct() {
+        let dev: TestDevice = Default::default();
+        let signal: Tensor<Rank1<300>, TestDtype, _> = dev.sample_normal();
+        let kernel: Tensor<Rank1<61>, TestDtype, _> = dev.sample_normal();
+        let r = fft_conv1d(signal.clone(), kernel.clone());
+        assert_eq!(r.shape(), &(240,));
+        let expected = direct_conv1d(&signal.as_vec(), &kernel.as_vec());
+        for (a, b) in r.as_vec().iter().zip(expected) {
+            assert!((a - b).abs() < 1e-3, "{a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn test_fft_conv1d_grads() {
+        let dev: TestDevice = Default::default();
+        let signal: Tensor<Rank1<10>, TestDtype, _> = dev.sample_normal();
+        let kernel: Tensor<Rank1<4>, TestDtype, _> = dev.sample_normal();
+        let weights: Tensor<(usize,), TestDtype, _> = dev.sample_normal_like(&(7,));
+        let r = fft_conv1d(signal.leaky_trace(), kernel.clone());
+        let g = (r * weights.clone()).sum::<Rank0, _>().backward();
+
+        // with a weighted sum, each gradient sums the weights of the outputs it's in
+        let (s, w, o) = (signal.array(), kernel.array(), weights.as_vec());
+        let mut grad_signal = [0.0; 10];
+        let mut grad_kernel = [0.0; 4];
+        for i in 0..7 {
+            for k in 0..4 {
+                grad_signal[i + k] += o[i] * w[k];
+                grad_kernel[k] += o[i] * s[i + k];
+            }
+        }
+        assert_close(&g.get(&signal).array(), &grad_signal);
+        assert_close(&g.get(&kernel).array(), &grad_kernel);
+    }
+}
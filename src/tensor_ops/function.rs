@@ -0,0 +1,143 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{unique_id, PutTape, SplitTape, Tape, Tensor},
+};
+
+use super::{axpy::AxpyKernel, Device, ReshapeTo};
+
+/// A custom operation with a hand-written backward pass. [Function::apply()] runs
+/// [Function::forward()] and records [Function::backward()] on the tape of the input.
+///
+/// Both are given tensors without a tape, so they can use any of the tensor operations.
+/// The gradient returned by `backward` is added to the gradient of the input.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::Function};
+/// # let dev: Cpu = Default::default();
+/// struct Cube;
+/// impl<S: Shape, D: Device<f32>> Function<S, f32, D> for Cube {
+///     type Output = S;
+///     type Saved = Tensor<S, f32, D>;
+///     fn forward(&self, x: Tensor<S, f32, D>) -> Result<(Tensor<S, f32, D>, Self::Saved), D::Err> {
+///         Ok((x.clone().try_powi(3)?, x))
+///     }
+///     fn backward(
+///         &self,
+///         x: Self::Saved,
+///         grad_out: Tensor<S, f32, D>,
+///     ) -> Result<Tensor<S, f32, D>, D::Err> {
+///         x.try_square()?.try_mul(3.0)?.try_mul(grad_out)
+///     }
+/// }
+/// let x: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 2.0]);
+/// let y = Cube.apply(x.leaky_trace());
+/// assert_eq!(y.array(), [1.0, 8.0]);
+/// let g = y.sum().backward();
+/// assert_eq!(g.get(&x).array(), [3.0, 12.0]);
+/// ```
+pub trait Function<S: Shape, E: Dtype, D: Device<E>>: 'static + Sized {
+    /// The shape of the output.
+    type Output: Shape;
+    /// What [Function::forward()] saves for [Function::backward()].
+    type Saved: 'static;
+
+    /// Computes the output, and anything the backward pass needs.
+    #[allow(clippy::type_complexity)]
+    fn forward(
+        &self,
+        input: Tensor<S, E, D>,
+    ) -> Result<(Tensor<Self::Output, E, D>, Self::Saved), D::Err>;
+
+    /// Computes the gradient of the input from the gradient of the output.
+    fn backward(
+        &self,
+        saved: Self::Saved,
+        grad_output: Tensor<Self::Output, E, D>,
+    ) -> Result<Tensor<S, E, D>, D::Err>;
+
+    /// Runs [Function::forward()], and records [Function::backward()] on the tape.
+    fn apply<T: Tape<E, D>>(self, input: Tensor<S, E, D, T>) -> Tensor<Self::Output, E, D, T> {
+        self.try_apply(input).unwrap()
+    }
+
+    /// See [Function::apply()]
+    fn try_apply<T: Tape<E, D>>(
+        self,
+        input: Tensor<S, E, D, T>,
+    ) -> Result<Tensor<Self::Output, E, D, T>, D::Err> {
+        // a contiguous input has a gradient with the same layout as `backward`'s result
+        let shape = input.shape;
+        let (inp, mut tape) = input.try_reshape_like(&shape).unwrap()?.split_tape();
+        let (mut out, saved) = self.forward(inp.clone())?;
+        // a new id, in case `forward` returned its input
+        out.id = unique_id();
+        let phantom_out = out.clone();
+        tape.try_alloc_grad(&inp)?;
+        tape.try_alloc_grad(&out)?;
+        tape.add_backward_op(move |grads| {
+            let grad_out = grads.get(&phantom_out);
+            let grad_inp = self.backward(saved, grad_out)?;
+            let grad_inp = grad_inp.try_reshape_like(&shape).unwrap()?;
+            AxpyKernel::forward(
+                &inp.device,
+                grads.get_mut(&inp),
+                E::ONE,
+                grad_inp.data.as_ref(),
+                E::ONE,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    struct Square;
+
+    impl<S: Shape, E: Dtype, D: Device<E>> Function<S, E, D> for Square {
+        type Output = S;
+        type Saved = Tensor<S, E, D>;
+
+        fn forward(&self, x: Tensor<S, E, D>) -> Result<(Tensor<S, E, D>, Self::Saved), D::Err> {
+            Ok((x.clone().try_mul(x.clone())?, x))
+        }
+
+        fn backward(
+            &self,
+            x: Self::Saved,
+            grad_out: Tensor<S, E, D>,
+        ) -> Result<Tensor<S, E, D>, D::Err> {
+            x.try_mul(E::from_f32(2.0).unwrap())?.try_mul(grad_out)
+        }
+    }
+
+    #[test]
+    fn test_custom_square_matches_builtin() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+
+        let y = Square.apply(x.leaky_trace());
+        let expected = x.leaky_trace().square();
+        assert_close(&y.array(), &expected.array());
+
+        let g = (y * w.clone()).sum().backward();
+        let g_expected = (expected * w).sum().backward();
+        assert_close(&g.get(&x).array(), &g_expected.get(&x).array());
+    }
+
+    #[test]
+    fn test_custom_function_broadcasted_input() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, 3.0]);
+        let y = Square.apply(x.leaky_trace().broadcast::<Rank2<2, 3>, _>());
+        assert_close(&y.array(), &[[1.0, 4.0, 9.0]; 2]);
+
+        // the gradient is summed over the broadcasted axis
+        let g = y.sum().backward();
+        assert_close(&g.get(&x).array(), &[4.0, 8.0, 12.0]);
+    }
+}
@@ -3,7 +3,10 @@ mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
-use super::ops::{try_unary_op, UnaryKernel};
+use super::{
+    ops::{try_unary_op, UnaryKernel},
+    Device, TryAdd, TryMul,
+};
 use crate::{shapes::*, tensor::*};
 
 #[repr(C)]
@@ -36,6 +39,42 @@ impl<S: Shape, E: Dtype, D: UnaryKernel<GeLUKernelOp, E>, T: Tape<E, D>> Tensor<
     }
 }
 
+/// Exact [Gaussian Linear Unit (GeLU)](https://paperswithcode.com/method/gelu) using
+/// the Gaussian CDF instead of the tanh approximation used by [gelu()]. `0.5 * x * (1 + erf(x / sqrt(2)))`
+///
+/// **Pytorch equivalent**: `torch.nn.functional.gelu(t, approximate="none")`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 1.0, 2.0]);
+/// let r = t.exact_gelu();
+/// ```
+pub fn exact_gelu<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.exact_gelu()
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [exact_gelu]
+    pub fn exact_gelu(self) -> Self {
+        self.try_exact_gelu().unwrap()
+    }
+    /// See [exact_gelu]
+    pub fn try_exact_gelu(self) -> Result<Self, D::Err> {
+        let half = E::from_f64(0.5).unwrap();
+        let cdf = self
+            .retaped::<T>()
+            .try_mul(E::from_f64(core::f64::consts::FRAC_1_SQRT_2).unwrap())?
+            .try_erf()?
+            .try_add(E::ONE)?
+            .try_mul(half)?;
+        cdf.try_mul(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{tensor::*, tensor_ops::*, tests::*};
@@ -57,4 +96,25 @@ mod tests {
             &[-0.016455507, -0.014156329, 0.1, 0.5023068, 1.5338063],
         );
     }
+
+    #[test]
+    fn test_exact_gelu() {
+        let dev: TestDevice = Default::default();
+        let xs = [-2.0, -1.0, 0.0, 1.0, 2.0];
+        let x: Tensor<_, TestDtype, _> = dev.tensor(xs);
+        let r = x.leaky_trace().exact_gelu();
+        assert_close(
+            &r.array(),
+            &[-0.04550026, -0.15865525, 0.0, 0.8413447, 1.9544997],
+        );
+
+        let f = |x: f64| 0.5 * x * (1.0 + libm::erf(x / std::f64::consts::SQRT_2));
+        let eps = 1e-5;
+        let expected = xs.map(|x: TestDtype| {
+            let x = x as f64;
+            ((f(x + eps) - f(x - eps)) / (2.0 * eps)) as TestDtype
+        });
+        let g = r.sum().backward();
+        assert_close(&g.get(&x).array(), &expected);
+    }
 }
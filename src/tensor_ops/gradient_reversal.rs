@@ -0,0 +1,80 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{unique_id, NoneTape, PutTape, SplitTape, Tape, Tensor},
+};
+
+use super::{axpy::AxpyKernel, Device};
+
+/// Passes `t` through unchanged, but multiplies its gradient by `-lambda`, as in
+/// [Unsupervised Domain Adaptation by Backpropagation](https://arxiv.org/abs/1409.7495).
+///
+/// Everything before this is trained to make the loss after it worse, e.g. so that a
+/// feature extractor learns features that a domain classifier can't tell apart.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::gradient_reversal};
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, 2.0, 3.0]);
+/// let r = gradient_reversal(t.leaky_trace(), 0.5);
+/// assert_eq!(r.array(), [1.0, 2.0, 3.0]);
+/// let g = r.square().sum().backward();
+/// assert_eq!(g.get(&t).array(), [-1.0, -2.0, -3.0]);
+/// ```
+pub fn gradient_reversal<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    lambda: E,
+) -> Tensor<S, E, D, T> {
+    t.gradient_reversal(lambda)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [gradient_reversal]
+    pub fn gradient_reversal(self, lambda: E) -> Self {
+        self.try_gradient_reversal(lambda).unwrap()
+    }
+
+    /// See [gradient_reversal]
+    pub fn try_gradient_reversal(self, lambda: E) -> Result<Self, D::Err> {
+        let (inp, mut tape) = self.split_tape();
+        // same values as `inp`, but a different id so it gets its own gradient
+        let out = Tensor {
+            id: unique_id(),
+            data: inp.data.clone(),
+            shape: inp.shape,
+            strides: inp.strides,
+            device: inp.device.clone(),
+            tape: NoneTape,
+        };
+        let phantom_out = out.clone();
+        tape.try_alloc_grad(&inp)?;
+        tape.try_alloc_grad(&out)?;
+        tape.add_backward_op(move |grads| {
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp, &phantom_out);
+            AxpyKernel::forward(
+                &inp.device,
+                grad_inp,
+                E::ONE,
+                grad_out,
+                E::default() - lambda,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_gradient_reversal() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.sample_normal();
+        let r = t.leaky_trace().gradient_reversal(0.3);
+        assert_eq!(r.array(), t.array());
+
+        // the upstream gradient of `r.square().sum()` is `2 * t`
+        let g = r.square().sum().backward();
+        assert_close(&g.get(&t).array(), &(t.clone() * -0.6).array());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Tape, Tensor},
+};
+
+use super::{Device, PermuteTo, ReshapeTo, TryDiv, TryMatMul};
+
+/// The gram matrix of a `(C, H, W)` feature map, as used by style losses. Flattens
+/// the spatial dimensions into `F` with shape `(C, H * W)`, and computes `F @ F^T / (H * W)`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank3<2, 1, 2>, f32, _> = dev.tensor([[[1.0, 2.0]], [[3.0, -1.0]]]);
+/// let r = gram_matrix(t);
+/// assert_eq!(r.array(), [[2.5, 0.5], [0.5, 5.0]]);
+/// ```
+pub fn gram_matrix<C: Dim, H: Dim, W: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(C, H, W), E, D, T>,
+) -> Tensor<(C, C), E, D, T> {
+    t.gram_matrix()
+}
+
+impl<C: Dim, H: Dim, W: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(C, H, W), E, D, T> {
+    /// See [gram_matrix]
+    pub fn gram_matrix(self) -> Tensor<(C, C), E, D, T> {
+        self.try_gram_matrix().unwrap()
+    }
+
+    /// See [gram_matrix]
+    #[allow(clippy::type_complexity)]
+    pub fn try_gram_matrix(self) -> Result<Tensor<(C, C), E, D, T>, D::Err> {
+        let (c, h, w) = self.shape;
+        let hw = h.size() * w.size();
+        let f = self.try_reshape_like(&(c, hw)).unwrap()?;
+        // scaling before the matmul also gives the rhs its own id
+        let f_t = f
+            .retaped::<T>()
+            .try_div(E::from_usize(hw).unwrap())?
+            .try_permute()?;
+        f.try_matmul(f_t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_gram_matrix_2x2x2() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 2, 2>, TestDtype, _> =
+            dev.tensor([[[1.0, 2.0], [0.0, -1.0]], [[3.0, 0.5], [2.0, 1.0]]]);
+        let r = t.leaky_trace().gram_matrix();
+        assert_close(&r.array(), &[[1.5, 0.75], [0.75, 3.5625]]);
+
+        // d/dF[c][k] of sum(F @ F^T) / 4 is 2 * sum_i F[i][k] / 4
+        let g = r.sum().backward();
+        assert_close(
+            &g.get(&t).array(),
+            &[[[2.0, 1.25], [1.0, 0.0]], [[2.0, 1.25], [1.0, 0.0]]],
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use num_traits::Float;
+
+use crate::{
+    shapes::{Dtype, Rank0, Shape},
+    tensor::{OwnedTape, Tensor, Trace},
+};
+
+use super::{Backward, Device, TryAdd, TryDiv, TryMul, TrySub};
+
+/// Approximates the Hessian-vector product `H·v` of a scalar function `f` at `x` with
+/// finite differences, without materializing the Hessian `H`.
+///
+/// This is **not** double backward: gradients can't be differentiated again, since a backward
+/// pass isn't recorded on a tape. Instead this takes the central difference of two gradients
+/// along `v`: `(∇f(x + hv) - ∇f(x - hv)) / 2h`, which costs two forward and backward passes.
+///
+/// The step is `h = cbrt(E::epsilon()) * (1 + |x|) / |v|` (`|.|` is the l2 norm), which
+/// balances the two sources of error:
+/// - truncation error of `O(h^2)`, scaled by the third derivative of `∇f` along `v`. This is 0
+///   for functions whose gradient is at most quadratic, like `x^3`.
+/// - rounding error of `O(epsilon / h)`, relative to the size of the gradients.
+///
+/// For `f32` that is a relative error of roughly `1e-3` to `1e-2`, so don't expect more than
+/// 2-3 significant digits.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::hvp_fd};
+/// # let dev: Cpu = Default::default();
+/// let x: Tensor<Rank1<1>, f32, _> = dev.tensor([2.0]);
+/// let v: Tensor<Rank1<1>, f32, _> = dev.tensor([1.0]);
+/// let hv = hvp_fd(|x| x.powi(3).sum(), x, v);
+/// assert!((hv.array()[0] - 12.0).abs() < 1e-2);
+/// ```
+pub fn hvp_fd<S: Shape, E: Dtype + Float, D: Device<E>, F>(
+    f: F,
+    x: Tensor<S, E, D>,
+    v: Tensor<S, E, D>,
+) -> Tensor<S, E, D>
+where
+    F: FnMut(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+{
+    try_hvp_fd(f, x, v).unwrap()
+}
+
+/// Fallible version of [hvp_fd()]. Returns [HvpError::ShapeMismatch] if `x` and `v` have
+/// different shapes.
+pub fn try_hvp_fd<S: Shape, E: Dtype + Float, D: Device<E>, F>(
+    mut f: F,
+    x: Tensor<S, E, D>,
+    v: Tensor<S, E, D>,
+) -> Result<Tensor<S, E, D>, HvpError<D::Err>>
+where
+    F: FnMut(Tensor<S, E, D, OwnedTape<E, D>>) -> Tensor<Rank0, E, D, OwnedTape<E, D>>,
+{
+    if x.shape != v.shape {
+        return Err(HvpError::ShapeMismatch);
+    }
+    let norm = |t: &Tensor<S, E, D>| {
+        t.as_vec()
+            .into_iter()
+            .fold(E::zero(), |acc, a| acc + a * a)
+            .sqrt()
+    };
+    let v_norm = norm(&v);
+    if v_norm == E::zero() {
+        return Ok(v.try_mul(E::zero())?);
+    }
+    let h = E::epsilon().cbrt() * (E::one() + norm(&x)) / v_norm;
+
+    let step = v.try_mul(h)?;
+    let x_plus = x.clone().try_add(step.clone())?;
+    let x_minus = x.try_sub(step)?;
+    let g_plus = f(x_plus.clone().leaky_traced())
+        .try_backward()?
+        .get(&x_plus);
+    let g_minus = f(x_minus.clone().leaky_traced())
+        .try_backward()?
+        .get(&x_minus);
+    Ok(g_plus.try_sub(g_minus)?.try_div(h + h)?)
+}
+
+/// An error from [try_hvp_fd()].
+#[derive(Debug)]
+pub enum HvpError<Err> {
+    /// `x` and `v` have different shapes.
+    ShapeMismatch,
+    /// The device failed to compute the gradients.
+    Device(Err),
+}
+
+impl<Err> From<Err> for HvpError<Err> {
+    fn from(e: Err) -> Self {
+        Self::Device(e)
+    }
+}
+
+impl<Err: std::fmt::Display> std::fmt::Display for HvpError<Err> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ShapeMismatch => write!(fmt, "x and v have different shapes"),
+            Self::Device(err) => write!(fmt, "{err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Err: std::fmt::Debug + std::fmt::Display> std::error::Error for HvpError<Err> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_hvp_fd_cube() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank0, TestDtype, _> = dev.tensor(2.0);
+        let hv = hvp_fd(|x| x.powi(3), x, dev.tensor(1.0));
+        // the second derivative of x^3 is 6x
+        assert_close_with_tolerance(&hv.array(), &12.0, 1e-2);
+    }
+
+    #[test]
+    fn test_hvp_fd_quadratic_form() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<3, 3>, TestDtype, _> =
+            dev.tensor([[2.0, 1.0, 0.0], [0.0, 3.0, -1.0], [1.0, 0.0, 1.0]]);
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.5, -1.0, 2.0]);
+        let v: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, -1.0]);
+
+        // f(x) = x^T A x has the Hessian A + A^T
+        let hv = hvp_fd(
+            |x| (x.with_empty_tape().matmul(a.clone()) * x).sum(),
+            x,
+            v.clone(),
+        );
+        let expected = v.matmul(a.clone() + a.permute());
+        assert_close_with_tolerance(&hv.array(), &expected.array(), 1e-2);
+    }
+
+    #[test]
+    fn test_try_hvp_fd_shape_mismatch() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<(usize,), TestDtype, _> = dev.zeros_like(&(3,));
+        let v: Tensor<(usize,), TestDtype, _> = dev.ones_like(&(2,));
+        let r = try_hvp_fd(|x| x.square().sum(), x, v);
+        assert!(matches!(r, Err(HvpError::ShapeMismatch)));
+    }
+}
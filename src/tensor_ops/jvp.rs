@@ -0,0 +1,281 @@
+use crate::{
+    shapes::{Axes, Dtype, ReduceShapeTo, Shape},
+    tensor::Tensor,
+};
+
+use super::{ChooseFrom, Device, SumTo, TryMatMul};
+
+/// Forward-mode automatic differentiation: computes `f(x)` and the directional derivative
+/// `J·v` of `f` at `x`, in a single forward pass without a tape or a backward pass.
+///
+/// `f` is given `x` as a [Dual], a pair of "primal" and "tangent" tensors, and computes
+/// with [Dual]'s operations, which carry the tangent along with the value. `v` must have
+/// the same shape as `x`. Returns `(f(x), J·v)`.
+///
+/// This is cheaper than reverse mode when `f` has fewer inputs than outputs, e.g. for
+/// sensitivity analysis along a single direction.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::jvp};
+/// # let dev: Cpu = Default::default();
+/// let x: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 2.0]);
+/// let v: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 0.0]);
+/// let (y, jv) = jvp(|x| (x.clone() * x).sum::<Rank0, _>(), x, v);
+/// assert_eq!(y.array(), 5.0);
+/// assert_eq!(jv.array(), 2.0);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn jvp<S: Shape, S2: Shape, E: Dtype, D: Device<E>, F>(
+    f: F,
+    x: Tensor<S, E, D>,
+    v: Tensor<S, E, D>,
+) -> (Tensor<S2, E, D>, Tensor<S2, E, D>)
+where
+    F: FnOnce(Dual<S, E, D>) -> Dual<S2, E, D>,
+{
+    assert_eq!(x.shape, v.shape);
+    let y = f(Dual {
+        primal: x,
+        tangent: v,
+    });
+    (y.primal, y.tangent)
+}
+
+/// A tensor and its derivative along some direction, for forward-mode differentiation
+/// with [jvp()].
+///
+/// Tensor operations that a [Dual] doesn't have can be added by computing the `primal`
+/// and `tangent` of the result directly.
+#[derive(Debug, Clone)]
+pub struct Dual<S: Shape, E: Dtype, D: Device<E>> {
+    /// The value, `f(x)`.
+    pub primal: Tensor<S, E, D>,
+    /// The directional derivative of the value, `J·v`.
+    pub tangent: Tensor<S, E, D>,
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>> Dual<S, E, D> {
+    /// A [Dual] whose tangent is zero, e.g. for a value that doesn't depend on the input.
+    pub fn constant(primal: Tensor<S, E, D>) -> Self {
+        let tangent = primal.device.zeros_like(&primal.shape);
+        Self { primal, tangent }
+    }
+
+    /// `x^2`, with tangent `2x·v`.
+    pub fn square(self) -> Self {
+        let tangent = self.tangent * self.primal.clone() * E::from_f32(2.0).unwrap();
+        Self {
+            primal: self.primal.square(),
+            tangent,
+        }
+    }
+
+    /// `exp(x)`, with tangent `exp(x)·v`.
+    pub fn exp(self) -> Self {
+        let primal = self.primal.exp();
+        let tangent = self.tangent * primal.clone();
+        Self { primal, tangent }
+    }
+
+    /// `ln(x)`, with tangent `v / x`.
+    pub fn ln(self) -> Self {
+        let tangent = self.tangent / self.primal.clone();
+        Self {
+            primal: self.primal.ln(),
+            tangent,
+        }
+    }
+
+    /// `sin(x)`, with tangent `cos(x)·v`.
+    pub fn sin(self) -> Self {
+        let tangent = self.tangent * self.primal.clone().cos();
+        Self {
+            primal: self.primal.sin(),
+            tangent,
+        }
+    }
+
+    /// `cos(x)`, with tangent `-sin(x)·v`.
+    pub fn cos(self) -> Self {
+        let tangent = -(self.tangent * self.primal.clone().sin());
+        Self {
+            primal: self.primal.cos(),
+            tangent,
+        }
+    }
+
+    /// `tanh(x)`, with tangent `(1 - tanh(x)^2)·v`.
+    pub fn tanh(self) -> Self {
+        let primal = self.primal.tanh();
+        let tangent = self.tangent * (-primal.clone().square() + E::ONE);
+        Self { primal, tangent }
+    }
+
+    /// `sigmoid(x)`, with tangent `sigmoid(x)·(1 - sigmoid(x))·v`.
+    pub fn sigmoid(self) -> Self {
+        let primal = self.primal.sigmoid();
+        let tangent = self.tangent * primal.clone() * (-primal.clone() + E::ONE);
+        Self { primal, tangent }
+    }
+
+    /// `relu(x)`, whose tangent is zeroed wherever `x <= 0`.
+    pub fn relu(self) -> Self {
+        let zeros = self.tangent.device.zeros_like(&self.tangent.shape);
+        let tangent = self
+            .primal
+            .clone()
+            .scalar_gt(E::default())
+            .choose(self.tangent, zeros);
+        Self {
+            primal: self.primal.relu(),
+            tangent,
+        }
+    }
+
+    /// Sums along `Ax`; the tangent is summed the same way.
+    pub fn sum<Dst: Shape, Ax: Axes>(self) -> Dual<Dst, E, D>
+    where
+        S: ReduceShapeTo<Dst, Ax>,
+    {
+        Dual {
+            primal: self.primal.sum(),
+            tangent: self.tangent.sum(),
+        }
+    }
+
+    /// Multiplies by a constant `rhs`, e.g. the weight of a layer.
+    pub fn matmul<R: Clone, S2: Shape>(self, rhs: R) -> Dual<S2, E, D>
+    where
+        Tensor<S, E, D>: TryMatMul<R, Output = Tensor<S2, E, D>>,
+    {
+        Dual {
+            primal: self.primal.matmul(rhs.clone()),
+            tangent: self.tangent.matmul(rhs),
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>> std::ops::Add for Dual<S, E, D> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            primal: self.primal + rhs.primal,
+            tangent: self.tangent + rhs.tangent,
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>> std::ops::Sub for Dual<S, E, D> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            primal: self.primal - rhs.primal,
+            tangent: self.tangent - rhs.tangent,
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>> std::ops::Mul for Dual<S, E, D> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let tangent = self.tangent * rhs.primal.clone() + self.primal.clone() * rhs.tangent;
+        Self {
+            primal: self.primal * rhs.primal,
+            tangent,
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>> std::ops::Div for Dual<S, E, D> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let numerator = self.tangent * rhs.primal.clone() - self.primal.clone() * rhs.tangent;
+        let tangent = numerator / rhs.primal.clone().square();
+        Self {
+            primal: self.primal / rhs.primal,
+            tangent,
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>> std::ops::Mul<E> for Dual<S, E, D> {
+    type Output = Self;
+    fn mul(self, rhs: E) -> Self {
+        Self {
+            primal: self.primal * rhs,
+            tangent: self.tangent * rhs,
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>> std::ops::Add<E> for Dual<S, E, D> {
+    type Output = Self;
+    fn add(self, rhs: E) -> Self {
+        Self {
+            primal: self.primal + rhs,
+            tangent: self.tangent,
+        }
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>> std::ops::Neg for Dual<S, E, D> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            primal: -self.primal,
+            tangent: -self.tangent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_jvp_square() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank0, TestDtype, _> = dev.tensor(3.0);
+        let (y, jv) = jvp(|x| x.square(), x.clone(), dev.tensor(1.0));
+        assert_close(&y.array(), &9.0);
+        assert_close(&jv.array(), &6.0);
+
+        let g = x.leaky_trace().square().backward();
+        assert_close(&jv.array(), &g.get(&x).array());
+    }
+
+    #[test]
+    fn test_jvp_relu() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([-1.0, 0.0, 0.5, 2.0]);
+        let v: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([1.0, 2.0, 3.0, 4.0]);
+        let (y, jv) = jvp(|x| x.relu(), x, v);
+        assert_close(&y.array(), &[0.0, 0.0, 0.5, 2.0]);
+        assert_close(&jv.array(), &[0.0, 0.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_jvp_matches_reverse_mode() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<4>, TestDtype, _> = dev.sample_normal();
+        let v: Tensor<Rank1<4>, TestDtype, _> = dev.sample_normal();
+        let w: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+
+        let (y, jv) = jvp(
+            |x| {
+                let h = (x.clone().sin() * x.clone().exp() + x.tanh()).matmul(w.clone());
+                (h.clone().sigmoid() / (h.relu() + 1.0)).sum::<Rank0, _>()
+            },
+            x.clone(),
+            v.clone(),
+        );
+
+        let h = (x.leaky_trace().sin() * x.leaky_trace().exp() + x.leaky_trace().tanh())
+            .matmul(w.clone());
+        let expected = (h.with_empty_tape().sigmoid() / (h.relu() + 1.0)).sum::<Rank0, _>();
+        assert_close(&y.array(), &expected.array());
+        let g = expected.backward().get(&x);
+        assert_close(&jv.array(), &(g * v).sum::<Rank0, _>().array());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    shapes::{Axes2, Dtype, Rank2, Rank4},
+    tensor::{Merge, Tape, Tensor},
+};
+
+use super::{BroadcastTo, Device, ReshapeTo, TryMul};
+
+/// **Requires Nightly** Kronecker product of two matrices:
+/// `out[i * P + p][j * Q + q] = a[i][j] * b[p][q]`.
+///
+/// **Pytorch equivalent**: `torch.kron(a, b)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+/// let b: Tensor<Rank2<1, 2>, f32, _> = dev.tensor([[1.0, -1.0]]);
+/// let r: Tensor<Rank2<2, 4>, f32, _> = kron(a, b);
+/// assert_eq!(r.array(), [[1.0, -1.0, 2.0, -2.0], [3.0, -3.0, 4.0, -4.0]]);
+/// ```
+pub fn kron<
+    const M: usize,
+    const N: usize,
+    const P: usize,
+    const Q: usize,
+    E: Dtype,
+    D: Device<E>,
+    T,
+    R,
+>(
+    a: Tensor<Rank2<M, N>, E, D, T>,
+    b: Tensor<Rank2<P, Q>, E, D, R>,
+) -> Tensor<Rank2<{ M * P }, { N * Q }>, E, D, T>
+where
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    a.kron(b)
+}
+
+impl<const M: usize, const N: usize, E: Dtype, D: Device<E>, T: Tape<E, D>>
+    Tensor<Rank2<M, N>, E, D, T>
+{
+    /// See [kron]
+    pub fn kron<const P: usize, const Q: usize, R: Tape<E, D>>(
+        self,
+        b: Tensor<Rank2<P, Q>, E, D, R>,
+    ) -> Tensor<Rank2<{ M * P }, { N * Q }>, E, D, T>
+    where
+        T: Merge<R>,
+    {
+        self.try_kron(b).unwrap()
+    }
+
+    /// See [kron]
+    pub fn try_kron<const P: usize, const Q: usize, R: Tape<E, D>>(
+        self,
+        b: Tensor<Rank2<P, Q>, E, D, R>,
+    ) -> Result<Tensor<Rank2<{ M * P }, { N * Q }>, E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        let a = self.try_broadcast::<Rank4<M, P, N, Q>, Axes2<1, 3>>()?;
+        let b = b.try_broadcast::<Rank4<M, P, N, Q>, Axes2<0, 2>>()?;
+        a.try_mul(b)?.try_reshape()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_kron_2x2() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+        let b: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[0.0, 5.0], [6.0, 7.0]]);
+        let r = a.leaky_trace().kron(b.leaky_trace());
+        assert_close(
+            &r.array(),
+            &[
+                [0.0, 5.0, 0.0, 10.0],
+                [6.0, 7.0, 12.0, 14.0],
+                [0.0, 15.0, 0.0, 20.0],
+                [18.0, 21.0, 24.0, 28.0],
+            ],
+        );
+
+        // each element of a multiplies the whole of b, and vice versa
+        let g = r.sum().backward();
+        assert_close(&g.get(&a).array(), &[[18.0; 2]; 2]);
+        assert_close(&g.get(&b).array(), &[[10.0; 2]; 2]);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Tape, Tensor},
+};
+
+use super::super::{ops::try_host_unary_op, Device};
+
+use num_traits::Float;
+
+/// Lower triangular `L` such that `L @ L^T = t`, for a symmetric positive definite `t`.
+///
+/// Only the lower triangle of `t` is read, so the gradient of the upper triangle is
+/// always zero. The result is filled with `NaN` if `t` is not positive definite.
+///
+/// **Pytorch equivalent**: `torch.linalg.cholesky(t)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[4.0, 2.0], [2.0, 10.0]]);
+/// let r = cholesky(t);
+/// assert_eq!(r.array(), [[2.0, 0.0], [1.0, 3.0]]);
+/// ```
+pub fn cholesky<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(N, N), E, D, T>,
+) -> Tensor<(N, N), E, D, T> {
+    t.cholesky()
+}
+
+impl<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>> Tensor<(N, N), E, D, T> {
+    /// See [cholesky]
+    pub fn cholesky(self) -> Self {
+        self.try_cholesky().unwrap()
+    }
+
+    /// See [cholesky]
+    pub fn try_cholesky(self) -> Result<Self, D::Err> {
+        let shape = self.shape;
+        let n = shape.0.size();
+        try_host_unary_op(
+            self,
+            shape,
+            |a| cholesky_fwd(n, a),
+            move |_, l, grad_l| cholesky_bwd(n, l, grad_l),
+        )
+    }
+}
+
+fn cholesky_fwd<E: Float>(n: usize, a: &[E]) -> std::vec::Vec<E> {
+    let mut l = vec![E::zero(); n * n];
+    for j in 0..n {
+        for i in j..n {
+            let mut s = a[i * n + j];
+            for k in 0..j {
+                s = s - l[i * n + k] * l[j * n + k];
+            }
+            l[i * n + j] = if i == j { s.sqrt() } else { s / l[j * n + j] };
+        }
+    }
+    l
+}
+
+/// Runs the loops of [cholesky_fwd] in reverse, accumulating into the gradient of
+/// `l` as each of its elements is used.
+fn cholesky_bwd<E: Float>(n: usize, l: &[E], grad_l: &[E]) -> std::vec::Vec<E> {
+    let mut grad_l = grad_l.to_vec();
+    let mut grad_a = vec![E::zero(); n * n];
+    let two = E::one() + E::one();
+    for j in (0..n).rev() {
+        let l_jj = l[j * n + j];
+        for i in (j..n).rev() {
+            let grad_s = if i == j {
+                grad_l[j * n + j] / (two * l_jj)
+            } else {
+                let g = grad_l[i * n + j];
+                grad_l[j * n + j] = grad_l[j * n + j] - g * l[i * n + j] / l_jj;
+                g / l_jj
+            };
+            grad_a[i * n + j] = grad_s;
+            for k in 0..j {
+                grad_l[i * n + k] = grad_l[i * n + k] - grad_s * l[j * n + k];
+                grad_l[j * n + k] = grad_l[j * n + k] - grad_s * l[i * n + k];
+            }
+        }
+    }
+    grad_a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_cholesky_reconstructs_input() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[4.0, 2.0], [2.0, 3.0]]);
+        let l = a.clone().cholesky();
+        assert_close(&l.array(), &[[2.0, 0.0], [1.0, TestDtype::sqrt(2.0)]]);
+        let r = l.clone().matmul(l.permute());
+        assert_close(&r.array(), &a.array());
+    }
+
+    #[test]
+    fn test_cholesky_grad_finite_differences() {
+        let dev: TestDevice = Default::default();
+        let a = [[4.0, 2.0, 0.4], [2.0, 3.0, -0.5], [0.4, -0.5, 2.0]];
+        let w = [[1.0, 0.0, 0.0], [-2.0, 0.5, 0.0], [3.0, 1.0, -1.5]];
+        let f = |a: &[f64]| -> f64 {
+            cholesky_fwd(3, a)
+                .iter()
+                .zip(w.iter().flatten())
+                .map(|(l, w)| l * w)
+                .sum()
+        };
+
+        let x: Tensor<Rank2<3, 3>, TestDtype, _> = dev.tensor(a.map(|r| r.map(|v| v as TestDtype)));
+        let w_t: Tensor<Rank2<3, 3>, TestDtype, _> =
+            dev.tensor(w.map(|r| r.map(|v| v as TestDtype)));
+        let g = (x.leaky_trace().cholesky() * w_t).sum().backward();
+
+        let eps = 1e-6;
+        let mut expected = [[0.0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                let mut hi = a;
+                hi[i][j] += eps;
+                let mut lo = a;
+                lo[i][j] -= eps;
+                let df = f(hi.as_flattened()) - f(lo.as_flattened());
+                expected[i][j] = (df / (2.0 * eps)) as TestDtype;
+            }
+        }
+        assert_close(&g.get(&x).array(), &expected);
+        assert_eq!(expected[0][1], 0.0);
+    }
+}
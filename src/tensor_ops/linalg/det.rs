@@ -0,0 +1,190 @@
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Tape, Tensor},
+};
+
+use super::{super::ops::try_host_unary_op, super::Device, Lu};
+
+use num_traits::Float;
+
+/// The determinant of a square matrix, computed with an LU decomposition.
+///
+/// The gradient is `det(t) * t^-T`, which is not defined when `t` is singular.
+///
+/// **Pytorch equivalent**: `torch.linalg.det(t)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[2.0, 1.0], [1.0, 3.0]]);
+/// let r = det(t);
+/// assert_eq!(r.array(), 5.0);
+/// ```
+pub fn det<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(N, N), E, D, T>,
+) -> Tensor<(), E, D, T> {
+    t.det()
+}
+
+/// The sign and the log of the absolute value of the determinant of a square matrix,
+/// such that `det(t) = sign * exp(logabsdet)`.
+///
+/// This is more stable than taking the log of [det], since the determinant of a large
+/// matrix easily overflows. The sign is `0` (and `logabsdet` is `-inf`) when `t` is singular.
+///
+/// The gradient of `logabsdet` is `t^-T`.
+///
+/// **Pytorch equivalent**: `torch.linalg.slogdet(t)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[1.0, 2.0], [0.0, -3.0]]);
+/// let (sign, logabsdet) = slogdet(t);
+/// assert_eq!(sign, -1.0);
+/// assert_eq!(logabsdet.array(), 3.0f32.ln());
+/// ```
+pub fn slogdet<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(N, N), E, D, T>,
+) -> (E, Tensor<(), E, D, T>) {
+    t.slogdet()
+}
+
+impl<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>> Tensor<(N, N), E, D, T> {
+    /// See [det]
+    pub fn det(self) -> Tensor<(), E, D, T> {
+        self.try_det().unwrap()
+    }
+
+    /// See [det]
+    pub fn try_det(self) -> Result<Tensor<(), E, D, T>, D::Err> {
+        let n = self.shape.0.size();
+        try_host_unary_op(
+            self,
+            (),
+            |a| vec![Lu::new(n, a).det()],
+            move |a, det, grad_det| {
+                let inv = Lu::new(n, a).inverse();
+                let scale = det[0] * grad_det[0];
+                let mut grad_a = vec![E::zero(); n * n];
+                for i in 0..n {
+                    for j in 0..n {
+                        grad_a[i * n + j] = scale * inv[j * n + i];
+                    }
+                }
+                grad_a
+            },
+        )
+    }
+
+    /// See [slogdet]
+    pub fn slogdet(self) -> (E, Tensor<(), E, D, T>) {
+        self.try_slogdet().unwrap()
+    }
+
+    /// See [slogdet]
+    #[allow(clippy::type_complexity)]
+    pub fn try_slogdet(self) -> Result<(E, Tensor<(), E, D, T>), D::Err> {
+        let n = self.shape.0.size();
+        let mut sign = E::one();
+        let logabsdet = try_host_unary_op(
+            self,
+            (),
+            |a| {
+                let lu = Lu::new(n, a);
+                let mut logabsdet = E::zero();
+                sign = lu.sign;
+                for i in 0..n {
+                    let u_ii = lu.lu[i * n + i];
+                    logabsdet += u_ii.abs().ln();
+                    sign *= u_ii.signum();
+                }
+                if logabsdet == E::neg_infinity() {
+                    sign = E::zero();
+                }
+                vec![logabsdet]
+            },
+            move |a, _, grad_logabsdet| {
+                let inv = Lu::new(n, a).inverse();
+                let mut grad_a = vec![E::zero(); n * n];
+                for i in 0..n {
+                    for j in 0..n {
+                        grad_a[i * n + j] = grad_logabsdet[0] * inv[j * n + i];
+                    }
+                }
+                grad_a
+            },
+        )?;
+        Ok((sign, logabsdet))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_det_2x2() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+        let r = a.leaky_trace().det();
+        assert_close(&r.array(), &-2.0);
+
+        // the gradient of `ad - bc` is `[[d, -c], [-b, a]]`
+        let g = r.backward();
+        assert_close(&g.get(&a).array(), &[[4.0, -3.0], [-2.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_det_3x3() {
+        let dev: TestDevice = Default::default();
+        let a = [[2.0, -1.0, 0.5], [1.0, 3.0, 2.0], [0.0, 1.0, 4.0]];
+        let x = dev.tensor(a).to_dtype::<TestDtype>();
+        let r = x.leaky_trace().det();
+        assert_close(&r.array(), &24.5);
+
+        let eps = 1e-6;
+        let mut expected = [[0.0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                let (mut hi, mut lo) = (a, a);
+                hi[i][j] += eps;
+                lo[i][j] -= eps;
+                let df = Lu::new(3, hi.as_flattened()).det() - Lu::new(3, lo.as_flattened()).det();
+                expected[i][j] = (df / (2.0 * eps)) as TestDtype;
+            }
+        }
+        let g = r.backward();
+        assert_close_with_tolerance(&g.get(&x).array(), &expected, 1e-4);
+    }
+
+    #[test]
+    fn test_slogdet() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+        let (sign, r) = a.leaky_trace().slogdet();
+        assert_eq!(sign, -1.0);
+        assert_close(&r.array(), &TestDtype::ln(2.0));
+
+        // the gradient of `ln|ad - bc|` is `[[d, -c], [-b, a]] / (ad - bc)`
+        let g = r.backward();
+        assert_close(&g.get(&a).array(), &[[-2.0, 1.5], [1.0, -0.5]]);
+    }
+
+    #[test]
+    fn test_slogdet_matches_det() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<3, 3>, TestDtype, _> =
+            dev.tensor([[0.0, -1.0, 0.5], [1.0, 3.0, 2.0], [2.0, 1.0, 4.0]]);
+        let d = a.clone().det().array();
+        let (sign, r) = a.slogdet();
+        assert_close(&(sign * r.array().exp()), &d);
+        assert_eq!(sign, d.signum());
+
+        let z: Tensor<Rank2<2, 2>, TestDtype, _> = dev.zeros();
+        let (sign, r) = z.slogdet();
+        assert_eq!(sign, 0.0);
+        assert_eq!(r.array(), TestDtype::NEG_INFINITY);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Tape, Tensor},
+};
+
+use super::{super::ops::try_host_unary_op, super::Device, Lu};
+
+use num_traits::Float;
+
+/// The inverse of a square matrix, computed with an LU decomposition.
+///
+/// The gradient is `-t^-T @ grad_out @ t^-T`. The result is filled with `inf` or `NaN`
+/// if `t` is singular.
+///
+/// **Pytorch equivalent**: `torch.linalg.inv(t)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[2.0, 0.0], [0.0, 4.0]]);
+/// let r = inverse(t);
+/// assert_eq!(r.array(), [[0.5, 0.0], [0.0, 0.25]]);
+/// ```
+pub fn inverse<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(N, N), E, D, T>,
+) -> Tensor<(N, N), E, D, T> {
+    t.inverse()
+}
+
+impl<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>> Tensor<(N, N), E, D, T> {
+    /// See [inverse]
+    pub fn inverse(self) -> Self {
+        self.try_inverse().unwrap()
+    }
+
+    /// See [inverse]
+    pub fn try_inverse(self) -> Result<Self, D::Err> {
+        let shape = self.shape;
+        let n = shape.0.size();
+        try_host_unary_op(
+            self,
+            shape,
+            |a| Lu::new(n, a).inverse(),
+            move |_, inv, grad_inv| {
+                // tmp = grad_out @ inv^T
+                let mut tmp = vec![E::zero(); n * n];
+                for i in 0..n {
+                    for j in 0..n {
+                        for k in 0..n {
+                            tmp[i * n + j] += grad_inv[i * n + k] * inv[j * n + k];
+                        }
+                    }
+                }
+                // grad_a = -inv^T @ tmp
+                let mut grad_a = vec![E::zero(); n * n];
+                for i in 0..n {
+                    for j in 0..n {
+                        for k in 0..n {
+                            grad_a[i * n + j] -= inv[k * n + i] * tmp[k * n + j];
+                        }
+                    }
+                }
+                grad_a
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_inverse_2x2() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[4.0, 7.0], [2.0, 6.0]]);
+        let r = a.clone().inverse();
+        assert_close(&r.array(), &[[0.6, -0.7], [-0.2, 0.4]]);
+        assert_close(&a.matmul(r).array(), &[[1.0, 0.0], [0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_inverse_grad_finite_differences() {
+        let dev: TestDevice = Default::default();
+        let a = [[4.0, 7.0], [2.0, 6.0]];
+        let w = [[1.0, -2.0], [0.5, 3.0]];
+        let f = |a: &[f64]| -> f64 {
+            let inv = Lu::new(2, a).inverse();
+            inv.iter().zip(w.iter().flatten()).map(|(x, w)| x * w).sum()
+        };
+
+        let x = dev.tensor(a).to_dtype::<TestDtype>();
+        let w_t = dev.tensor(w).to_dtype::<TestDtype>();
+        let g = (x.leaky_trace().inverse() * w_t).sum().backward();
+
+        let eps = 1e-6;
+        let mut expected = [[0.0; 2]; 2];
+        for i in 0..2 {
+            for j in 0..2 {
+                let (mut hi, mut lo) = (a, a);
+                hi[i][j] += eps;
+                lo[i][j] -= eps;
+                let df = f(hi.as_flattened()) - f(lo.as_flattened());
+                expected[i][j] = (df / (2.0 * eps)) as TestDtype;
+            }
+        }
+        assert_close_with_tolerance(&g.get(&x).array(), &expected, 1e-4);
+    }
+}
@@ -0,0 +1,154 @@
+//! Differentiable linear algebra for small matrices.
+//!
+//! These ops copy their inputs to the host and run simple `O(N^3)` algorithms there,
+//! so they are meant for matrices with at most a few dozen rows, like covariances
+//! or the jacobians of normalizing flows.
+
+mod cholesky;
+mod det;
+mod inverse;
+mod solve;
+
+pub use cholesky::cholesky;
+pub use det::{det, slogdet};
+pub use inverse::inverse;
+pub use solve::{solve, solve_triangular};
+
+use num_traits::Float;
+
+/// LU decomposition with partial pivoting of a row major `(n, n)` matrix, such that
+/// row `i` of `L @ U` is row `perm[i]` of `a`.
+///
+/// `L` (with an implicit unit diagonal) and `U` are packed into the same matrix.
+pub(crate) struct Lu<E> {
+    pub(crate) n: usize,
+    pub(crate) lu: std::vec::Vec<E>,
+    pub(crate) perm: std::vec::Vec<usize>,
+    /// `1` or `-1` depending on the parity of `perm`.
+    pub(crate) sign: E,
+}
+
+impl<E: Float> Lu<E> {
+    pub(crate) fn new(n: usize, a: &[E]) -> Self {
+        let mut lu = a.to_vec();
+        let mut perm: std::vec::Vec<usize> = (0..n).collect();
+        let mut sign = E::one();
+        for k in 0..n {
+            let mut pivot = k;
+            for i in k + 1..n {
+                if lu[i * n + k].abs() > lu[pivot * n + k].abs() {
+                    pivot = i;
+                }
+            }
+            if pivot != k {
+                for j in 0..n {
+                    lu.swap(k * n + j, pivot * n + j);
+                }
+                perm.swap(k, pivot);
+                sign = -sign;
+            }
+            if lu[k * n + k] == E::zero() {
+                // the rest of the column is already zero, and `a` is singular
+                continue;
+            }
+            for i in k + 1..n {
+                let f = lu[i * n + k] / lu[k * n + k];
+                lu[i * n + k] = f;
+                for j in k + 1..n {
+                    lu[i * n + j] = lu[i * n + j] - f * lu[k * n + j];
+                }
+            }
+        }
+        Self { n, lu, perm, sign }
+    }
+
+    /// The determinant of `a`.
+    pub(crate) fn det(&self) -> E {
+        let n = self.n;
+        (0..n).fold(self.sign, |d, i| d * self.lu[i * n + i])
+    }
+
+    /// Solves `a @ x = b`.
+    pub(crate) fn solve(&self, b: &[E]) -> std::vec::Vec<E> {
+        let n = self.n;
+        let mut x: std::vec::Vec<E> = self.perm.iter().map(|&p| b[p]).collect();
+        solve_lower(n, &self.lu, &mut x, true);
+        solve_upper(n, &self.lu, &mut x, false);
+        x
+    }
+
+    /// Solves `a^T @ x = b`.
+    pub(crate) fn solve_transposed(&self, b: &[E]) -> std::vec::Vec<E> {
+        let n = self.n;
+        let mut y = b.to_vec();
+        solve_upper_transposed(n, &self.lu, &mut y);
+        solve_lower_transposed(n, &self.lu, &mut y);
+        let mut x = vec![E::zero(); n];
+        for (i, &p) in self.perm.iter().enumerate() {
+            x[p] = y[i];
+        }
+        x
+    }
+
+    /// The inverse of `a`, in row major order.
+    pub(crate) fn inverse(&self) -> std::vec::Vec<E> {
+        let n = self.n;
+        let mut inv = vec![E::zero(); n * n];
+        let mut e = vec![E::zero(); n];
+        for j in 0..n {
+            e[j] = E::one();
+            for (i, v) in self.solve(&e).into_iter().enumerate() {
+                inv[i * n + j] = v;
+            }
+            e[j] = E::zero();
+        }
+        inv
+    }
+}
+
+/// Forward substitution of `l @ x = b` in place, where `b` is passed in as `x`.
+/// Only the lower triangle of `l` is read.
+pub(crate) fn solve_lower<E: Float>(n: usize, l: &[E], x: &mut [E], unit_diagonal: bool) {
+    for i in 0..n {
+        let mut s = x[i];
+        for j in 0..i {
+            s = s - l[i * n + j] * x[j];
+        }
+        x[i] = if unit_diagonal { s } else { s / l[i * n + i] };
+    }
+}
+
+/// Back substitution of `u @ x = b` in place, where `b` is passed in as `x`.
+/// Only the upper triangle of `u` is read.
+pub(crate) fn solve_upper<E: Float>(n: usize, u: &[E], x: &mut [E], unit_diagonal: bool) {
+    for i in (0..n).rev() {
+        let mut s = x[i];
+        for j in i + 1..n {
+            s = s - u[i * n + j] * x[j];
+        }
+        x[i] = if unit_diagonal { s } else { s / u[i * n + i] };
+    }
+}
+
+/// Solves `u^T @ x = b` in place, reading only the upper triangle of `u`.
+fn solve_upper_transposed<E: Float>(n: usize, u: &[E], x: &mut [E]) {
+    for i in 0..n {
+        let mut s = x[i];
+        for j in 0..i {
+            s = s - u[j * n + i] * x[j];
+        }
+        x[i] = s / u[i * n + i];
+    }
+}
+
+/// Solves `l^T @ x = b` in place, reading only the strictly lower triangle of `l`
+/// and assuming a unit diagonal.
+fn solve_lower_transposed<E: Float>(n: usize, l: &[E], x: &mut [E]) {
+    for i in (0..n).rev() {
+        let mut s = x[i];
+        for j in i + 1..n {
+            s = s - l[j * n + i] * x[j];
+        }
+        x[i] = s;
+    }
+}
@@ -0,0 +1,249 @@
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Merge, Tape, Tensor},
+};
+
+use super::{super::ops::try_host_binary_op, super::Device, solve_lower, solve_upper, Lu};
+
+use num_traits::Float;
+
+/// Solves the linear system `a @ x = b` for `x`, using an LU decomposition of `a`
+/// with partial pivoting.
+///
+/// The gradients are `grad_b = a^-T @ grad_x` and `grad_a = -outer(grad_b, x)`.
+///
+/// **Pytorch equivalent**: `torch.linalg.solve(a, b)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[3.0, 1.0], [1.0, 2.0]]);
+/// let b: Tensor<Rank1<2>, f32, _> = dev.tensor([9.0, 8.0]);
+/// let x = solve(a, b);
+/// assert_eq!(x.array(), [2.0, 3.0]);
+/// ```
+pub fn solve<N: Dim, E: Dtype + Float, D: Device<E>, T, R>(
+    a: Tensor<(N, N), E, D, T>,
+    b: Tensor<(N,), E, D, R>,
+) -> Tensor<(N,), E, D, T>
+where
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    a.solve(b)
+}
+
+/// Solves `a @ x = b` for `x` by substitution, where `a` is lower triangular, or upper
+/// triangular if `upper` is true. Only that triangle of `a` is read, so the gradient of
+/// the other triangle is zero.
+///
+/// **Pytorch equivalent**: `torch.linalg.solve_triangular(a, b.unsqueeze(1), upper=upper)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[2.0, 0.0], [1.0, 4.0]]);
+/// let b: Tensor<Rank1<2>, f32, _> = dev.tensor([2.0, 9.0]);
+/// let x = solve_triangular(a, b, false);
+/// assert_eq!(x.array(), [1.0, 2.0]);
+/// ```
+pub fn solve_triangular<N: Dim, E: Dtype + Float, D: Device<E>, T, R>(
+    a: Tensor<(N, N), E, D, T>,
+    b: Tensor<(N,), E, D, R>,
+    upper: bool,
+) -> Tensor<(N,), E, D, T>
+where
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    a.solve_triangular(b, upper)
+}
+
+impl<N: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>> Tensor<(N, N), E, D, T> {
+    /// See [solve]
+    pub fn solve<R: Tape<E, D>>(self, b: Tensor<(N,), E, D, R>) -> Tensor<(N,), E, D, T>
+    where
+        T: Merge<R>,
+    {
+        self.try_solve(b).unwrap()
+    }
+
+    /// See [solve]
+    pub fn try_solve<R: Tape<E, D>>(
+        self,
+        b: Tensor<(N,), E, D, R>,
+    ) -> Result<Tensor<(N,), E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        let n = self.shape.0.size();
+        let dst = b.shape;
+        try_host_binary_op(
+            self,
+            b,
+            dst,
+            |a, b| Lu::new(n, a).solve(b),
+            move |a, _, x, grad_x| {
+                let grad_b = Lu::new(n, a).solve_transposed(grad_x);
+                let mut grad_a = vec![E::zero(); n * n];
+                for i in 0..n {
+                    for j in 0..n {
+                        grad_a[i * n + j] = -grad_b[i] * x[j];
+                    }
+                }
+                (grad_a, grad_b)
+            },
+        )
+    }
+
+    /// See [solve_triangular]
+    pub fn solve_triangular<R: Tape<E, D>>(
+        self,
+        b: Tensor<(N,), E, D, R>,
+        upper: bool,
+    ) -> Tensor<(N,), E, D, T>
+    where
+        T: Merge<R>,
+    {
+        self.try_solve_triangular(b, upper).unwrap()
+    }
+
+    /// See [solve_triangular]
+    pub fn try_solve_triangular<R: Tape<E, D>>(
+        self,
+        b: Tensor<(N,), E, D, R>,
+        upper: bool,
+    ) -> Result<Tensor<(N,), E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        let n = self.shape.0.size();
+        let dst = b.shape;
+        let substitute = move |a: &[E], x: &mut [E]| match upper {
+            true => solve_upper(n, a, x, false),
+            false => solve_lower(n, a, x, false),
+        };
+        try_host_binary_op(
+            self,
+            b,
+            dst,
+            |a, b| {
+                let mut x = b.to_vec();
+                substitute(a, &mut x);
+                x
+            },
+            move |a, _, x, grad_x| {
+                // the transpose of a lower triangular matrix is upper triangular
+                let mut a_t = vec![E::zero(); n * n];
+                for i in 0..n {
+                    for j in 0..n {
+                        a_t[j * n + i] = a[i * n + j];
+                    }
+                }
+                let mut grad_b = grad_x.to_vec();
+                match upper {
+                    true => solve_lower(n, &a_t, &mut grad_b, false),
+                    false => solve_upper(n, &a_t, &mut grad_b, false),
+                }
+                let mut grad_a = vec![E::zero(); n * n];
+                for i in 0..n {
+                    for j in 0..n {
+                        if (upper && j >= i) || (!upper && j <= i) {
+                            grad_a[i * n + j] = -grad_b[i] * x[j];
+                        }
+                    }
+                }
+                (grad_a, grad_b)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    const A: [[f64; 3]; 3] = [[2.0, 1.0, -1.0], [-3.0, -1.0, 2.0], [-2.0, 1.0, 2.0]];
+    const B: [f64; 3] = [8.0, -11.0, -3.0];
+    const W: [f64; 3] = [1.0, -2.0, 0.5];
+
+    type Traced<S> = Tensor<S, TestDtype, TestDevice, OwnedTape<TestDtype, TestDevice>>;
+
+    /// Checks the gradients of `sum(w * x(a, b))` against finite differences
+    fn check_grads(
+        dev: &TestDevice,
+        op: impl Fn(Traced<Rank2<3, 3>>, Traced<Rank1<3>>) -> Traced<Rank1<3>>,
+        x: impl Fn(&[f64], &[f64]) -> std::vec::Vec<f64>,
+    ) {
+        let a = dev.tensor(A).to_dtype::<TestDtype>();
+        let b = dev.tensor(B).to_dtype::<TestDtype>();
+        let w = dev.tensor(W).to_dtype::<TestDtype>();
+        let g = (op(a.leaky_trace(), b.leaky_trace()) * w).sum().backward();
+
+        let eps = 1e-6;
+        let f = |a: &[f64], b: &[f64]| -> f64 { x(a, b).iter().zip(W).map(|(x, w)| x * w).sum() };
+        let mut grad_a = [[0.0; 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                let (mut hi, mut lo) = (A, A);
+                hi[i][j] += eps;
+                lo[i][j] -= eps;
+                let df = f(hi.as_flattened(), &B) - f(lo.as_flattened(), &B);
+                grad_a[i][j] = (df / (2.0 * eps)) as TestDtype;
+            }
+        }
+        let mut grad_b = [0.0; 3];
+        for i in 0..3 {
+            let (mut hi, mut lo) = (B, B);
+            hi[i] += eps;
+            lo[i] -= eps;
+            let df = f(A.as_flattened(), &hi) - f(A.as_flattened(), &lo);
+            grad_b[i] = (df / (2.0 * eps)) as TestDtype;
+        }
+        assert_close_with_tolerance(&g.get(&a).array(), &grad_a, 1e-4);
+        assert_close_with_tolerance(&g.get(&b).array(), &grad_b, 1e-4);
+    }
+
+    #[test]
+    fn test_solve_2x2() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+        let b: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([5.0, 6.0]);
+        let x = a.leaky_trace().solve(b.leaky_trace());
+        assert_close(&x.array(), &[-4.0, 4.5]);
+
+        // grad_b = a^-T @ 1, grad_a = -outer(grad_b, x)
+        let g = x.sum().backward();
+        assert_close(&g.get(&b).array(), &[-0.5, 0.5]);
+        assert_close(&g.get(&a).array(), &[[-2.0, 2.25], [2.0, -2.25]]);
+    }
+
+    #[test]
+    fn test_solve_3x3() {
+        let dev: TestDevice = Default::default();
+        let a = dev.tensor(A).to_dtype::<TestDtype>();
+        let b = dev.tensor(B).to_dtype::<TestDtype>();
+        assert_close(&a.solve(b).array(), &[2.0, 3.0, -1.0]);
+        check_grads(&dev, |a, b| a.solve(b), |a, b| Lu::new(3, a).solve(b));
+    }
+
+    #[test]
+    fn test_solve_triangular() {
+        let dev: TestDevice = Default::default();
+        for upper in [false, true] {
+            check_grads(
+                &dev,
+                |a, b| a.solve_triangular(b, upper),
+                |a, b| {
+                    let mut x = b.to_vec();
+                    match upper {
+                        true => solve_upper(3, a, &mut x, false),
+                        false => solve_lower(3, a, &mut x, false),
+                    }
+                    x
+                },
+            );
+        }
+    }
+}
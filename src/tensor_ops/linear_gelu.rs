@@ -0,0 +1,129 @@
+use crate::{
+    shapes::{Axes2, Dim, Dtype},
+    tensor::{PutTape, SplitTape, Tape, Tensor},
+};
+
+use super::{
+    add::BinaryAddKernelOp,
+    gelu::GeLUKernelOp,
+    matmul::MatMatKernel,
+    ops::{profiled, BinaryKernel, UnaryKernel},
+    BroadcastTo, Device, PermuteTo,
+};
+
+/// Computes `gelu(x * w^T + b)`, the first half of a transformer MLP block, as a single op.
+/// `w` and `b` are the weight and bias of a [crate::nn::modules::Linear], and get gradients
+/// just like the linear would.
+///
+/// Unlike `linear.forward(x).gelu()`, this only keeps the pre activation output around for
+/// the backward, instead of the results & gradients of the matmul, broadcast, add and gelu.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::linear_gelu};
+/// # let dev: Cpu = Default::default();
+/// let linear = dev.build_module::<Linear<3, 5>, f32>();
+/// let x: Tensor<Rank2<4, 3>, f32, _> = dev.sample_normal();
+/// let y = linear_gelu(x.leaky_trace(), &linear.weight, &linear.bias);
+/// assert_eq!(y.shape(), &(Const::<4>, Const::<5>));
+/// ```
+pub fn linear_gelu<M: Dim, I: Dim, O: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    x: Tensor<(M, I), E, D, T>,
+    w: &Tensor<(O, I), E, D>,
+    b: &Tensor<(O,), E, D>,
+) -> Tensor<(M, O), E, D, T> {
+    x.fused_linear_gelu(w, b)
+}
+
+impl<M: Dim, I: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(M, I), E, D, T> {
+    /// See [linear_gelu]
+    pub fn fused_linear_gelu<O: Dim>(
+        self,
+        w: &Tensor<(O, I), E, D>,
+        b: &Tensor<(O,), E, D>,
+    ) -> Tensor<(M, O), E, D, T> {
+        self.try_fused_linear_gelu(w, b).unwrap()
+    }
+
+    /// See [linear_gelu]
+    #[allow(clippy::type_complexity)]
+    pub fn try_fused_linear_gelu<O: Dim>(
+        self,
+        w: &Tensor<(O, I), E, D>,
+        b: &Tensor<(O,), E, D>,
+    ) -> Result<Tensor<(M, O), E, D, T>, D::Err> {
+        let (x, mut tape) = self.split_tape();
+        let wt = w.clone().try_permute::<_, Axes2<1, 0>>()?;
+        let b = b.clone();
+
+        // the pre activation output is the only intermediate the backward needs
+        let (z, bb, out) = profiled(|| {
+            let xw = MatMatKernel::forward(&x.device, &x, &wt)?;
+            let bb = b.clone().try_broadcast_like(&xw.shape)?;
+            let z = BinaryKernel::forward(&x.device, BinaryAddKernelOp, &xw, &bb)?;
+            drop(xw);
+            let out = UnaryKernel::forward(&x.device, GeLUKernelOp, &z)?;
+            Ok::<_, D::Err>((z, bb, out))
+        })?;
+
+        let phantom_out = out.clone();
+        tape.try_alloc_grad(&x)?;
+        tape.try_alloc_grad(&wt)?;
+        tape.try_alloc_grad(&b)?;
+        tape.try_alloc_grad(&out)?;
+        tape.add_backward_op(move |grads| {
+            let mut grad_z = x.device.try_alloc_grad(z.data.as_ref())?;
+            UnaryKernel::backward(
+                &x.device,
+                GeLUKernelOp,
+                &z,
+                &mut grad_z,
+                grads.get_ref(&phantom_out),
+            )?;
+            // the add's backward only looks at shapes, so `z` stands in for `x * w^T`
+            let mut grad_xw = x.device.try_alloc_grad(z.data.as_ref())?;
+            let grad_b = grads.get_mut(&b);
+            BinaryKernel::backward(
+                &x.device,
+                BinaryAddKernelOp,
+                &z,
+                &mut grad_xw,
+                &bb,
+                grad_b,
+                &grad_z,
+            )?;
+            let (grad_x, grad_wt, _) = grads.muts_and_ref(&x, &wt, &phantom_out);
+            MatMatKernel::backward(&x.device, &x, grad_x, &wt, grad_wt, &grad_xw)
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nn::builders::*, shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_linear_gelu_matches_unfused() {
+        let dev: TestDevice = Default::default();
+        let linear = dev.build_module::<Linear<3, 5>, TestDtype>();
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+
+        let y = linear_gelu(x.leaky_trace(), &linear.weight, &linear.bias);
+        let expected = linear.forward(x.leaky_trace()).gelu();
+        assert_close(&y.array(), &expected.array());
+
+        let w: Tensor<Rank2<4, 5>, TestDtype, _> = dev.sample_normal();
+        let g = (y * w.clone()).sum().backward();
+        let expected_g = (expected * w).sum().backward();
+        assert_close(&g.get(&x).array(), &expected_g.get(&x).array());
+        assert_close(
+            &g.get(&linear.weight).array(),
+            &expected_g.get(&linear.weight).array(),
+        );
+        assert_close(
+            &g.get(&linear.bias).array(),
+            &expected_g.get(&linear.bias).array(),
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+use num_traits::Float;
+
+impl<F: Float> UnaryDerivative<F> for super::Log1pKernelOp {
+    #[inline(always)]
+    fn f(&self, x: &F) -> F {
+        x.ln_1p()
+    }
+    #[inline(always)]
+    fn df(&self, x: &F) -> F {
+        (F::one() + *x).recip()
+    }
+}
@@ -0,0 +1,69 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Log1pKernelOp;
+
+/// `ln(1 + t)`, computed without the loss of precision that `(t + 1).ln()` has
+/// for values of `t` close to 0.
+///
+/// It's derivative is `1 / (1 + t)`.
+///
+/// **Pytorch equivalent**: `torch.log1p(t)`
+///
+/// Examples:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-0.5, 0.0, 1e-7, 1.0]);
+/// let r = t.log1p();
+/// ```
+pub fn log1p<S: Shape, E: Dtype, D: UnaryKernel<Log1pKernelOp, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.log1p()
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<Log1pKernelOp, E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [log1p]
+    pub fn log1p(self) -> Self {
+        self.try_log1p().unwrap()
+    }
+    /// See [log1p]
+    pub fn try_log1p(self) -> Result<Self, D::Err> {
+        try_unary_op(Log1pKernelOp, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_log1p() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<_, TestDtype, _> = dev.tensor([-0.5, 0.0, 1.0]);
+        let r = x.leaky_trace().log1p();
+        assert_close(&r.array(), &[TestDtype::ln(0.5), 0.0, TestDtype::ln(2.0)]);
+        let g = r.sum().backward();
+        assert_close(&g.get(&x).array(), &[2.0, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_log1p_small_values() {
+        let dev: TestDevice = Default::default();
+        let small: TestDtype = 1e-10;
+        // ln(1 + x) = x - x^2 / 2 + ...
+        let expected = small - small * small / 2.0;
+        let stable = dev.tensor([small]).log1p().array()[0];
+        let naive = (dev.tensor([small]) + 1.0).ln().array()[0];
+        assert!((stable - expected).abs() < (naive - expected).abs());
+        assert!((stable - expected).abs() / expected < 1e-6);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    shapes::{Dtype, ReduceShape, ReduceStridesTo, Shape},
+    tensor::{
+        cpu::{Cpu, CpuError, LendingIterator, NdIndex},
+        PutTape, SplitTape, Tape, Tensor, ZerosTensor,
+    },
+};
+
+use super::ops::profiled;
+
+/// How [map_reduce_last_dim] combines the mapped elements of the last dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduction {
+    Sum,
+    Mean,
+}
+
+/// Applies `map_fn` to every element and then reduces the last dimension with `reduce`,
+/// as a single op. `map_df` is the derivative of `map_fn`, and is used in the backward.
+///
+/// Unlike e.g. `t.square().sum()`, this doesn't create the mapped tensor or its gradient.
+///
+/// **Only available on [Cpu]**, since arbitrary closures can't run in cuda kernels.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[1.0, 2.0], [-3.0, 4.0]]);
+/// let r = map_reduce_last_dim(t, |x| x * x, |x| 2.0 * x, Reduction::Sum);
+/// assert_eq!(r.array(), [5.0, 25.0]);
+/// ```
+pub fn map_reduce_last_dim<S: Shape, E: Dtype, T: Tape<E, Cpu>>(
+    t: Tensor<S, E, Cpu, T>,
+    map_fn: impl FnMut(E) -> E,
+    map_df: impl 'static + FnMut(E) -> E,
+    reduce: Reduction,
+) -> Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, Cpu, T> {
+    t.map_reduce_last_dim(map_fn, map_df, reduce)
+}
+
+impl<S: Shape, E: Dtype, T: Tape<E, Cpu>> Tensor<S, E, Cpu, T> {
+    /// See [map_reduce_last_dim]
+    pub fn map_reduce_last_dim(
+        self,
+        map_fn: impl FnMut(E) -> E,
+        map_df: impl 'static + FnMut(E) -> E,
+        reduce: Reduction,
+    ) -> Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, Cpu, T> {
+        self.try_map_reduce_last_dim(map_fn, map_df, reduce)
+            .unwrap()
+    }
+
+    /// See [map_reduce_last_dim]
+    #[allow(clippy::type_complexity)]
+    pub fn try_map_reduce_last_dim(
+        self,
+        mut map_fn: impl FnMut(E) -> E,
+        mut map_df: impl 'static + FnMut(E) -> E,
+        reduce: Reduction,
+    ) -> Result<Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, Cpu, T>, CpuError> {
+        let dst: <S as ReduceShape<S::LastAxis>>::Reduced =
+            <S as ReduceStridesTo<_, S::LastAxis>>::reduced(&self.shape);
+        let last = match S::NUM_DIMS {
+            0 => 1,
+            n => self.shape.concrete()[n - 1],
+        };
+        let scale = match reduce {
+            Reduction::Sum => E::ONE,
+            Reduction::Mean if last > 0 => E::ONE / E::from_usize(last).unwrap(),
+            Reduction::Mean => E::default(),
+        };
+
+        let (inp, mut tape) = self.split_tape();
+        let mut out = inp.device.try_zeros_like(&dst)?;
+        profiled(|| {
+            // `out` is contiguous, so its buffer is in the same order as `inp.iter()`
+            let buf = std::sync::Arc::make_mut(&mut out.data);
+            let mut iter = inp.iter();
+            let mut i = 0;
+            while let Some(x) = iter.next() {
+                buf[i / last] += map_fn(*x);
+                i += 1;
+            }
+            for o in buf.iter_mut() {
+                *o *= scale;
+            }
+        });
+
+        let phantom_out = out.clone();
+        tape.try_alloc_grad(&inp)?;
+        tape.try_alloc_grad(&out)?;
+        tape.add_backward_op(move |grads| {
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp, &phantom_out);
+            let mut idx = NdIndex::new(inp.shape, inp.strides);
+            let mut i = 0;
+            while let Some(j) = idx.next() {
+                grad_inp[j] += map_df(inp.data[j]) * grad_out[i / last] * scale;
+                i += 1;
+            }
+            Ok(())
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_map_reduce_sum_of_squares() {
+        let dev: Cpu = Default::default();
+        let t: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+
+        let r = t
+            .leaky_trace()
+            .map_reduce_last_dim(|x| x * x, |x| 2.0 * x, Reduction::Sum);
+        let expected = t.leaky_trace().square().sum::<Rank1<3>, _>();
+        assert_close(&r.array(), &expected.array());
+
+        let w: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, -2.0, 0.5]);
+        let g = (r * w.clone()).sum().backward();
+        let expected_g = (expected * w).sum().backward();
+        assert_close(&g.get(&t).array(), &expected_g.get(&t).array());
+    }
+
+    #[test]
+    fn test_map_reduce_mean_broadcasted() {
+        let dev: Cpu = Default::default();
+        let t: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 3.0]);
+        let r = t
+            .leaky_trace()
+            .broadcast::<Rank2<3, 2>, _>()
+            .map_reduce_last_dim(|x| x.abs(), |x| x.signum(), Reduction::Mean);
+        assert_close(&r.array(), &[2.0; 3]);
+
+        // each element of `t` is used by all 3 rows
+        let g = r.sum().backward();
+        assert_close(&g.get(&t).array(), &[1.5; 2]);
+    }
+}
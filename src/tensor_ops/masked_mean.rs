@@ -0,0 +1,84 @@
+use crate::{
+    shapes::{Axis, Dim, Dtype},
+    tensor::{Tape, Tensor},
+};
+
+use super::{Device, SumTo, TryMul};
+
+/// Averages each row `b` of `t` over its first `lengths[b]` elements, ignoring the
+/// rest. Useful for pooling over padded sequences.
+///
+/// Padding elements get a gradient of 0, and valid elements get `1 / lengths[b]`.
+///
+/// **Panics** if `lengths` doesn't have one entry per row, or if any length is 0
+/// or more than the length of a row.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 3.0, 100.0], [1.0, 2.0, 3.0]]);
+/// let r = masked_mean_last_dim(t, &[2, 3]);
+/// assert_eq!(r.array(), [2.0, 2.0]);
+/// ```
+pub fn masked_mean_last_dim<B: Dim, L: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(B, L), E, D, T>,
+    lengths: &[usize],
+) -> Tensor<(B,), E, D, T> {
+    t.masked_mean_last_dim(lengths)
+}
+
+impl<B: Dim, L: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(B, L), E, D, T> {
+    /// See [masked_mean_last_dim]
+    pub fn masked_mean_last_dim(self, lengths: &[usize]) -> Tensor<(B,), E, D, T> {
+        self.try_masked_mean_last_dim(lengths).unwrap()
+    }
+
+    /// See [masked_mean_last_dim]
+    pub fn try_masked_mean_last_dim(
+        self,
+        lengths: &[usize],
+    ) -> Result<Tensor<(B,), E, D, T>, D::Err> {
+        let (b, l) = self.shape;
+        assert_eq!(lengths.len(), b.size(), "expected one length per row");
+        let mut weights = vec![E::default(); b.size() * l.size()];
+        for (row, &len) in weights.chunks_exact_mut(l.size()).zip(lengths) {
+            assert!(
+                0 < len && len <= l.size(),
+                "length {len} is not in 1..={}",
+                l.size()
+            );
+            let w = E::ONE / E::from_usize(len).unwrap();
+            row[..len].fill(w);
+        }
+        let weights = self.device.try_tensor_from_vec(weights, self.shape)?;
+        self.try_mul(weights)?.try_sum::<_, Axis<1>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_masked_mean_last_dim() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 4>, TestDtype, _> =
+            dev.tensor([[1.0, 2.0, -9.0, -9.0], [3.0, 6.0, 0.0, 9.0]]);
+        let r = t.leaky_trace().masked_mean_last_dim(&[2, 3]);
+        assert_close(&r.array(), &[1.5, 3.0]);
+
+        let g = r.sum().backward();
+        assert_close(
+            &g.get(&t).array(),
+            &[[0.5, 0.5, 0.0, 0.0], [1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0, 0.0]],
+        );
+    }
+
+    #[test]
+    #[should_panic = "length 0 is not in 1..=4"]
+    fn test_masked_mean_zero_length() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 4>, TestDtype, _> = dev.zeros();
+        t.masked_mean_last_dim(&[0, 3]);
+    }
+}
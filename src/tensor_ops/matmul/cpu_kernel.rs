@@ -388,6 +388,67 @@ where
     }
 }
 
+impl<E: Dtype> super::MatBrMatKernel<E> for Cpu
+where
+    Self: MatMulImpl<E>,
+{
+    fn forward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(M, K), E, Self>,
+        rhs: &Tensor<(B, K, N), E, Self>,
+    ) -> Result<Tensor<(B, M, N), E, Self>, Self::Err> {
+        let (m, k) = lhs.shape;
+        let (batch, _, n) = rhs.shape;
+        let mut out = self.try_zeros_like(&(batch, m, n))?;
+        let cp = Arc::get_mut(&mut out.data).unwrap();
+        for i in 0..batch.size() {
+            Self::matmul(
+                (m, k, n),
+                lhs.data.as_ptr(),
+                lhs.strides,
+                rhs.data[i * rhs.strides[0]..].as_ptr(),
+                [rhs.strides[1], rhs.strides[2]],
+                cp[i * out.strides[0]..].as_mut_ptr(),
+                [out.strides[1], out.strides[2]],
+            );
+        }
+        Ok(out)
+    }
+    fn backward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(M, K), E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<(B, K, N), E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let (m, k) = lhs.shape;
+        let (batch, _, n) = rhs.shape;
+        let strides = (batch, m, n).strides();
+        for i in 0..batch.size() {
+            Self::matmul(
+                (m, n, k),
+                grad_out[i * strides[0]..].as_ptr(),
+                [strides[1], strides[2]],
+                rhs.data[i * rhs.strides[0]..].as_ptr(),
+                [rhs.strides[2], rhs.strides[1]],
+                grad_lhs.as_mut_ptr(),
+                lhs.strides,
+            );
+            Self::matmul(
+                (k, m, n),
+                lhs.data.as_ptr(),
+                [lhs.strides[1], lhs.strides[0]],
+                grad_out[i * strides[0]..].as_ptr(),
+                [strides[1], strides[2]],
+                grad_rhs[i * rhs.strides[0]..].as_mut_ptr(),
+                [rhs.strides[1], rhs.strides[2]],
+            );
+        }
+        Ok(())
+    }
+}
+
 impl<E: Dtype> super::MatMatBatch3Kernel<E> for Cpu
 where
     Self: MatMulImpl<E>,
@@ -463,6 +463,83 @@ where
     }
 }
 
+impl<E: Dtype> super::MatBrMatKernel<E> for Cuda
+where
+    CudaBlas: Gemm<E>,
+{
+    fn forward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(M, K), E, Self>,
+        rhs: &Tensor<(B, K, N), E, Self>,
+    ) -> Result<Tensor<(B, M, N), E, Self>, Self::Err> {
+        assert_ne!(rhs.strides[0], 0);
+        let (m, _) = lhs.shape;
+        let (batch, k, n) = rhs.shape;
+        let shape = (batch, m, n);
+        let strides = shape.strides();
+        let mut storage = unsafe { self.dev.alloc::<E>(shape.num_elements()) }?;
+        unsafe {
+            self.gemm_batch(
+                (batch, m, k, n),
+                lhs.data.as_ref(),
+                [0, lhs.strides[0], lhs.strides[1]],
+                rhs.data.as_ref(),
+                rhs.strides,
+                Default::default(),
+                &mut storage,
+                strides,
+            )?;
+        }
+        Ok(self.build_tensor(shape, strides, storage))
+    }
+    fn backward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(M, K), E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<(B, K, N), E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let (m, _) = lhs.shape;
+        let (batch, k, n) = rhs.shape;
+        let strides = (batch, m, n).strides();
+        self.par_stream.wait_for_default()?;
+        unsafe {
+            self.blas.set_stream(Some(self.par_stream.as_ref()))?;
+            // grad_lhs += grad_out * rhs^T
+            for i in 0..batch.size() {
+                // NOTE: these have to be sequential since grad_lhs is broadcasted and cublas doesn't support
+                // 0 stride
+                self.gemm(
+                    (m, n, k),
+                    &grad_out.slice(i * strides[0]..),
+                    [strides[1], strides[2]],
+                    &rhs.data.slice(i * rhs.strides[0]..),
+                    [rhs.strides[2], rhs.strides[1]],
+                    E::ONE,
+                    grad_lhs,
+                    lhs.strides,
+                )?;
+            }
+            self.blas.set_stream(None)?;
+
+            // grad_rhs += lhs^T * grad_out
+            self.gemm_batch(
+                (batch, k, m, n),
+                lhs.data.as_ref(),
+                [0, lhs.strides[1], lhs.strides[0]],
+                grad_out,
+                strides,
+                E::ONE,
+                grad_rhs,
+                rhs.strides,
+            )?;
+        }
+        self.dev.wait_for(self.par_stream.as_ref())?;
+        Ok(())
+    }
+}
+
 impl<E: Dtype> super::MatMatBatch3Kernel<E> for Cuda
 where
     CudaBlas: Gemm<E>,
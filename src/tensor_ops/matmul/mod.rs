@@ -5,6 +5,7 @@ pub(super) mod cpu_kernel;
 #[cfg(feature = "cuda")]
 pub(super) mod cuda_kernel;
 
+use super::ops::profiled;
 use crate::{
     shapes::{Dim, Dtype, Shape},
     tensor::{DeviceStorage, HasErr, Merge, PutTape, SplitTape, Tape, Tensor},
@@ -58,6 +59,15 @@ use crate::{
 /// let _: Tensor<Rank3<10, 3, 4>, f32, _> = x.matmul(y);
 /// ```
 ///
+/// 6. Broadcasted matmul with a shared lhs matrix
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let x: Tensor<Rank2<3, 2>, f32, _> = dev.zeros();
+/// let y: Tensor<Rank3<10, 2, 4>, f32, _> = dev.zeros();
+/// let _: Tensor<Rank3<10, 3, 4>, f32, _> = x.matmul(y);
+/// ```
+///
 pub fn matmul<Lhs, Rhs>(lhs: Lhs, rhs: Rhs) -> Lhs::Output
 where
     Lhs: TryMatMul<Rhs>,
@@ -75,7 +85,7 @@ pub trait TryMatMul<Rhs>: HasErr {
 }
 
 #[rustfmt::skip]
-fn try_binary_op<
+fn try_matmul_op<
     Lhs: Shape,
     Rhs: Shape,
     Out: Shape,
@@ -94,7 +104,7 @@ fn try_binary_op<
     let (lhs, ltape) = lhs.split_tape();
     let (rhs, rtape) = rhs.split_tape();
     let mut tape = ltape.merge(rtape);
-    let out = fwd(&lhs.device, &lhs, &rhs)?;
+    let out = profiled(|| fwd(&lhs.device, &lhs, &rhs))?;
     let phantom_out = out.clone();
     tape.try_alloc_grad(&lhs)?;
     tape.try_alloc_grad(&rhs)?;
@@ -128,7 +138,7 @@ impl<M: Dim, N: Dim, E: Dtype, D: VecVecKernel<E>, T: Tape<E, D> + Merge<R>, R:
 {
     type Output = Tensor<(M, N), E, D, T>;
     fn try_matmul(self, rhs: Tensor<(N,), E, D, R>) -> Result<Self::Output, Self::Err> {
-        try_binary_op(self, rhs, D::forward, D::backward)
+        try_matmul_op(self, rhs, D::forward, D::backward)
     }
 }
 
@@ -155,7 +165,7 @@ impl<K: Dim, N: Dim, E: Dtype, D: VecMatKernel<E>, T: Tape<E, D> + Merge<R>, R:
     type Output = Tensor<(N,), E, D, T>;
     fn try_matmul(self, rhs: Tensor<(K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
         assert_eq!(self.shape.0, rhs.shape.0);
-        try_binary_op(self, rhs, D::forward, D::backward)
+        try_matmul_op(self, rhs, D::forward, D::backward)
     }
 }
 
@@ -192,7 +202,7 @@ where
     /// ```
     fn try_matmul(self, rhs: Tensor<(K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
         assert_eq!(self.shape.1, rhs.shape.0);
-        try_binary_op(self, rhs, D::forward, D::backward)
+        try_matmul_op(self, rhs, D::forward, D::backward)
     }
 }
 
@@ -229,7 +239,44 @@ where
     /// ```
     fn try_matmul(self, rhs: Tensor<(K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
         assert_eq!(self.shape.2, rhs.shape.0);
-        try_binary_op(self, rhs, D::forward, D::backward)
+        try_matmul_op(self, rhs, D::forward, D::backward)
+    }
+}
+
+pub trait MatBrMatKernel<E: Dtype>: DeviceStorage {
+    fn forward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(M, K), E, Self>,
+        rhs: &Tensor<(B, K, N), E, Self>,
+    ) -> Result<Tensor<(B, M, N), E, Self>, Self::Err>;
+
+    fn backward<B: Dim, M: Dim, K: Dim, N: Dim>(
+        &self,
+        lhs: &Tensor<(M, K), E, Self>,
+        grad_lhs: &mut Self::Vec<E>,
+        rhs: &Tensor<(B, K, N), E, Self>,
+        grad_rhs: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
+
+impl<B: Dim, M: Dim, K: Dim, N: Dim, E: Dtype, D: MatBrMatKernel<E>, T, R>
+    TryMatMul<Tensor<(B, K, N), E, D, R>> for Tensor<(M, K), E, D, T>
+where
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    type Output = Tensor<(B, M, N), E, D, T>;
+    /// ```compile_fail
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let x: Tensor<Rank2<3, 2>, f32, _> = dev.zeros();
+    /// let y: Tensor<Rank3<1, 3, 4>, f32, _> = dev.zeros();
+    /// let _: Tensor<Rank3<1, 3, 4>, f32, _> = x.try_matmul(y);
+    /// ```
+    fn try_matmul(self, rhs: Tensor<(B, K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
+        assert_eq!(self.shape.1, rhs.shape.1);
+        try_matmul_op(self, rhs, D::forward, D::backward)
     }
 }
 
@@ -268,7 +315,7 @@ where
     fn try_matmul(self, rhs: Tensor<(B, K, N), E, D, R>) -> Result<Self::Output, Self::Err> {
         assert_eq!(self.shape.0, rhs.shape.0);
         assert_eq!(self.shape.2, rhs.shape.1);
-        try_binary_op(self, rhs, D::forward, D::backward)
+        try_matmul_op(self, rhs, D::forward, D::backward)
     }
 }
 
@@ -308,7 +355,7 @@ where
         assert_eq!(self.shape.0, rhs.shape.0);
         assert_eq!(self.shape.1, rhs.shape.1);
         assert_eq!(self.shape.3, rhs.shape.2);
-        try_binary_op(self, rhs, D::forward, D::backward)
+        try_matmul_op(self, rhs, D::forward, D::backward)
     }
 }
 
@@ -476,6 +523,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_matmul_broadcast_lhs() {
+        const N: usize = 5;
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank3<N, 3, 2>, TestDtype, _> = dev.sample_normal();
+        let b_array = b.array();
+        let r = a.leaky_trace().matmul(b.clone());
+        let r_array = r.array();
+        for i in 0..N {
+            let sub_b = dev.tensor(b_array[i]);
+            let sub_c = a.clone().matmul(sub_b);
+            assert_close(&r_array[i], &sub_c.array());
+        }
+        let gs = r.sum().backward();
+        let b_grad = gs.get(&b).array();
+        let mut sub_as_summed = [[0.0; 3]; 4];
+        let mut sub_bs = [[[0.0; 2]; 3]; N];
+        for i in 0..N {
+            let sub_b = dev.tensor(b_array[i]);
+            let sub_gs = a.leaky_trace().matmul(sub_b.clone()).sum().backward();
+            let sub_a_grad = sub_gs.get(&a).array();
+            let sub_b_grad = sub_gs.get(&sub_b).array();
+            for x in 0..4 {
+                for y in 0..3 {
+                    sub_as_summed[x][y] += sub_a_grad[x][y];
+                }
+            }
+            for x in 0..3 {
+                for y in 0..2 {
+                    sub_bs[i][x][y] = sub_b_grad[x][y];
+                }
+            }
+        }
+        assert_close(&b_grad, &sub_bs);
+        assert_close(&gs.get(&a).array(), &sub_as_summed);
+    }
+
+    #[test]
+    fn test_matmul_broadcast_lhs_actual() {
+        const N: usize = 5;
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+        let b: Tensor<Rank3<N, 3, 2>, TestDtype, _> = dev.sample_normal();
+        let a_up = dev.tensor([a.array(); N]);
+        let r1 = a_up.leaky_trace().matmul(b.clone());
+        let r2 = a.leaky_trace().matmul(b.clone());
+        assert_close(&r1.array(), &r2.array());
+        let g1 = r1.exp().mean().backward();
+        let g2 = r2.exp().mean().backward();
+        assert_close(&g1.get(&b).array(), &g2.get(&b).array());
+        assert_close(
+            &dev.tensor(g1.get(&a_up).array())
+                .sum::<_, Axis<0>>()
+                .array(),
+            &g2.get(&a).array(),
+        );
+    }
+
     #[test]
     fn test_matmul_batched_3d() {
         let dev: TestDevice = Default::default();
@@ -729,6 +835,15 @@ mod tests {
         let _: Tensor<(Const<1>, Const<3>, Const<4>), f32, _> = x.matmul(y);
     }
 
+    #[test]
+    #[should_panic]
+    fn test_dynamic_matmul_matbrmat_fail() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<(Const<3>, usize), f32, _> = dev.zeros_like(&(Const, 3));
+        let y: Tensor<(Const<1>, usize, Const<4>), f32, _> = dev.zeros_like(&(Const, 4, Const));
+        let _: Tensor<(Const<1>, Const<3>, Const<4>), f32, _> = x.matmul(y);
+    }
+
     #[test]
     #[should_panic = "left: `3`,\n right: `4`"]
     fn test_dynamic_matmul_matmat_batch_fail() {
@@ -1,7 +1,9 @@
 use crate::{
     shapes::{Axes, Dtype, HasAxes, ReduceShapeTo, Shape},
     tensor::{Cpu, Tensor, ZerosTensor},
-    tensor_ops::utilities::reduction_utils::index_for_reductions,
+    tensor_ops::utilities::reduction_utils::{
+        contiguous_last_dim, for_each_row, index_for_reductions, reduce_row,
+    },
 };
 
 use num_traits::Float;
@@ -23,6 +25,13 @@ impl<E: Dtype + Float> super::MaxReduceKernel<E> for Cpu {
                 tmp = i.max(tmp);
             }
             std::sync::Arc::get_mut(&mut out.data).unwrap()[0] = tmp;
+        } else if let Some(n) = contiguous_last_dim::<Src, Ax>(inp) {
+            let out_buf = std::sync::Arc::get_mut(&mut out.data).unwrap();
+            if n == 0 {
+                out_buf.fill(E::neg_infinity());
+            } else {
+                for_each_row(inp.data.as_slice(), out_buf, (n, 1), max_rows);
+            }
         } else {
             let num_elems_reduced = <Src as HasAxes<Ax>>::size(&inp.shape);
             let inp_buf = inp.data.as_ref();
@@ -48,6 +57,18 @@ impl<E: Dtype + Float> super::MaxReduceKernel<E> for Cpu {
     where
         Src: ReduceShapeTo<Dst, Ax>,
     {
+        if let Some(n) = contiguous_last_dim::<Src, Ax>(inp) {
+            if n > 0 {
+                let rows = inp.data.chunks_exact(n).zip(grad_inp.chunks_exact_mut(n));
+                for ((row, grad_row), (&o, &go)) in rows.zip(out.data.iter().zip(grad_out.iter())) {
+                    for (&x, g) in row.iter().zip(grad_row.iter_mut()) {
+                        *g += go * if x == o { E::one() } else { E::zero() };
+                    }
+                }
+            }
+            return Ok(());
+        }
+
         let num_elems_reduced = <Src as HasAxes<Ax>>::size(&inp.shape);
 
         let inp_buf = inp.data.as_ref();
@@ -67,3 +88,10 @@ impl<E: Dtype + Float> super::MaxReduceKernel<E> for Cpu {
         Ok(())
     }
 }
+
+/// Takes the max of each row of `n` elements of `inp` into the matching element of `out`.
+fn max_rows<E: Dtype + Float>(inp: &[E], out: &mut [E], n: usize) {
+    for (o, row) in out.iter_mut().zip(inp.chunks_exact(n)) {
+        *o = reduce_row(row, E::neg_infinity(), |a, b| a.max(b));
+    }
+}
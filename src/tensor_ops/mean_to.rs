@@ -44,6 +44,38 @@ impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> MeanTo for Tensor<S, E, D,
     }
 }
 
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Mean over the leading (batch) axis only, keeping the rest of the shape.
+    /// Shorthand for `self.mean::<_, Axis<0>>()`.
+    ///
+    /// **Pytorch equivalent**: `t.mean(0)`
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [3.0, 4.0, 5.0]]);
+    /// let r: Tensor<Rank1<3>, f32, _> = t.batch_mean();
+    /// assert_eq!(r.array(), [2.0, 3.0, 4.0]);
+    /// ```
+    pub fn batch_mean(self) -> Tensor<<S as ReduceShape<Axis<0>>>::Reduced, E, D, T>
+    where
+        S: ReduceShape<Axis<0>>,
+    {
+        self.try_batch_mean().unwrap()
+    }
+
+    /// Fallible version of [Tensor::batch_mean]
+    #[allow(clippy::type_complexity)]
+    pub fn try_batch_mean(
+        self,
+    ) -> Result<Tensor<<S as ReduceShape<Axis<0>>>::Reduced, E, D, T>, D::Err>
+    where
+        S: ReduceShape<Axis<0>>,
+    {
+        self.try_mean::<<S as ReduceShape<Axis<0>>>::Reduced, Axis<0>>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,4 +175,15 @@ mod tests {
         let r2 = t.sum::<_, Axis<0>>().sum::<_, Axis<0>>() / 6.0;
         assert_close(&r.array(), &r2.array());
     }
+
+    #[test]
+    fn test_batch_mean() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 2, 2>, TestDtype, _> =
+            dev.tensor([[[1.0, 2.0], [3.0, 4.0]], [[3.0, 6.0], [5.0, 0.0]]]);
+        let r = t.leaky_trace().batch_mean();
+        assert_eq!(r.array(), [[2.0, 4.0], [4.0, 2.0]]);
+        let g = r.sum().backward();
+        assert_eq!(g.get(&t).array(), [[[0.5; 2]; 2]; 2]);
+    }
 }
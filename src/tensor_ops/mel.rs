@@ -0,0 +1,142 @@
+use num_traits::Float;
+use std::vec::Vec;
+
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Tape, Tensor},
+};
+
+use super::{Device, TryMatMul};
+
+fn hz_to_mel(hz: f64) -> f64 {
+    2595.0 * (1.0 + hz / 700.0).log10()
+}
+
+fn mel_to_hz(mel: f64) -> f64 {
+    700.0 * (10.0f64.powf(mel / 2595.0) - 1.0)
+}
+
+/// Maps each frequency bin of a spectrogram, e.g. from [super::stft()], to `n_mels` bands
+/// on the [mel scale](https://en.wikipedia.org/wiki/Mel_scale).
+///
+/// The `Bins` frequency bins are assumed to be spaced evenly from 0 to `sample_rate / 2`,
+/// as [super::stft()] outputs. Band `m` is a triangular filter that peaks with a weight of
+/// 1 at the `m + 1`th of `n_mels + 2` points spaced evenly in mels between 0 and
+/// `sample_rate / 2`, and reaches 0 at the points on either side. So between the first and
+/// last peak, the weights of each bin sum to 1. The mel scale is `2595 * log10(1 + hz / 700)`.
+///
+/// The output is the matrix product of `spectrogram` with the `(Bins, n_mels)` weights, so
+/// it's differentiable.
+///
+/// **Panics** if there are less than 2 bins, or `n_mels` is 0.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::{mel_filterbank, stft, Window}};
+/// # let dev: Cpu = Default::default();
+/// let signal: Tensor<Rank1<4000>, f32, _> = dev.sample_normal();
+/// let spec = stft(signal, 400, 160, Window::Hann);
+/// let mel = mel_filterbank(spec, 40, 16000.0);
+/// assert_eq!(mel.shape(), &(23, 40));
+/// ```
+pub fn mel_filterbank<F: Dim, B: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    spectrogram: Tensor<(F, B), E, D, T>,
+    n_mels: usize,
+    sample_rate: f32,
+) -> Tensor<(F, usize), E, D, T> {
+    try_mel_filterbank(spectrogram, n_mels, sample_rate).unwrap()
+}
+
+/// See [mel_filterbank()]
+#[allow(clippy::type_complexity)]
+pub fn try_mel_filterbank<F: Dim, B: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    spectrogram: Tensor<(F, B), E, D, T>,
+    n_mels: usize,
+    sample_rate: f32,
+) -> Result<Tensor<(F, usize), E, D, T>, D::Err> {
+    let bins = spectrogram.shape.1;
+    let num_bins = bins.size();
+    assert!(num_bins >= 2, "expected at least 2 frequency bins");
+    assert!(n_mels > 0, "n_mels must be positive");
+
+    let nyquist = sample_rate as f64 / 2.0;
+    let max_mel = hz_to_mel(nyquist);
+    let points: Vec<f64> = (0..n_mels + 2)
+        .map(|i| mel_to_hz(max_mel * i as f64 / (n_mels + 1) as f64))
+        .collect();
+
+    let mut weights = Vec::with_capacity(num_bins * n_mels);
+    for k in 0..num_bins {
+        let hz = nyquist * k as f64 / (num_bins - 1) as f64;
+        for m in 0..n_mels {
+            let (lo, peak, hi) = (points[m], points[m + 1], points[m + 2]);
+            let rising = (hz - lo) / (peak - lo);
+            let falling = (hi - hz) / (hi - peak);
+            weights.push(E::from(rising.min(falling).max(0.0)).unwrap());
+        }
+    }
+    let weights = spectrogram
+        .device
+        .try_tensor_from_vec(weights, (bins, n_mels))?;
+    spectrogram.try_matmul(weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_mel_filterbank_weights() {
+        let dev: TestDevice = Default::default();
+        // an identity spectrogram gives the weights of each bin
+        let mut eye = std::vec![0.0; 65 * 65];
+        for k in 0..65 {
+            eye[k * 65 + k] = 1.0;
+        }
+        let eye: Tensor<(usize, Const<65>), TestDtype, _> = dev.tensor_from_vec(eye, (65, Const));
+        let weights = mel_filterbank(eye, 10, 8000.0);
+        assert_eq!(weights.shape(), &(65, 10));
+
+        let weights = weights.as_vec();
+        let (first, last) = (
+            mel_to_hz(hz_to_mel(4000.0) / 11.0),
+            mel_to_hz(hz_to_mel(4000.0) * 10.0 / 11.0),
+        );
+        for k in 0..65 {
+            let row = &weights[k * 10..(k + 1) * 10];
+            let sum: TestDtype = row.iter().sum();
+            let hz = 4000.0 * k as f64 / 64.0;
+            if (first..=last).contains(&hz) {
+                assert!((sum - 1.0).abs() < 1e-5, "bin {k} sums to {sum}");
+            } else {
+                assert!(sum < 1.0);
+            }
+            // each bin is in at most 2 overlapping filters
+            assert!(row.iter().filter(|&&w| w > 0.0).count() <= 2);
+        }
+        // and the dc & nyquist bins are outside every filter
+        assert_eq!(weights[..10], [0.0; 10]);
+        assert_eq!(weights[64 * 10..], [0.0; 10]);
+    }
+
+    #[test]
+    fn test_mel_filterbank_grad() {
+        let dev: TestDevice = Default::default();
+        let spec: Tensor<Rank2<3, 9>, TestDtype, _> = dev.sample_uniform();
+        let mel = mel_filterbank(spec.leaky_trace(), 4, 1000.0);
+        assert_eq!(mel.shape(), &(Const::<3>, 4));
+
+        // the gradient of a bin is the sum of its weights in every filter
+        let mut eye = [[0.0; 9]; 9];
+        for (k, row) in eye.iter_mut().enumerate() {
+            row[k] = 1.0;
+        }
+        let weights = mel_filterbank(dev.tensor(eye), 4, 1000.0).as_vec();
+        let mut bin_sums = [0.0; 9];
+        for (k, sum) in bin_sums.iter_mut().enumerate() {
+            *sum = weights[k * 4..(k + 1) * 4].iter().sum();
+        }
+        let g = mel.sum::<Rank0, _>().backward();
+        assert_close(&g.get(&spec).array(), &[bin_sums; 3]);
+    }
+}
@@ -5,6 +5,8 @@ mod cuda_kernel;
 
 use crate::{shapes::*, tensor::*};
 
+use super::ops::profiled;
+
 pub trait MinReduceKernel<E: Dtype>: DeviceStorage {
     fn forward<Src: Shape, Dst: Shape, Ax: Axes>(
         &self,
@@ -28,8 +30,8 @@ pub trait MinReduceKernel<E: Dtype>: DeviceStorage {
 pub trait MinTo: HasErr + HasShape {
     /// Min reduction. **Pytorch equivalent**: `t.amin(Ax)`
     ///
-    /// **NOTE** This evenly distributes gradients between all equal maximum values, instead
-    /// of only exactly 1 value.
+    /// **NOTE** Ties are not broken: every value equal to the minimum gets the full
+    /// gradient, instead of only exactly 1 value (or an even split between them).
     ///
     /// Example reducing a single axis:
     /// ```rust
@@ -67,7 +69,7 @@ impl<S: Shape, E: Dtype, D: MinReduceKernel<E>, T: Tape<E, D>> MinTo for Tensor<
     {
         let dst: Dst = self.shape().reduced();
         let (inp, mut tape) = self.split_tape();
-        let out = inp.device.forward(dst, &inp)?;
+        let out = profiled(|| inp.device.forward(dst, &inp))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&inp)?;
         tape.try_alloc_grad(&out)?;
@@ -133,4 +135,63 @@ mod tests {
             [[1.0, 1.0], [1.0, 1.0], [1.0, 0.0], [1.0, 0.0]]
         );
     }
+
+    #[test]
+    fn test_min_last_axis_ties() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 2, 3>, TestDtype, _> = dev.tensor([
+            [[3.0, 1.0, 1.0], [2.0, 2.0, 2.0]],
+            [[1.0, 0.0, 1.0], [-5.0, -4.0, -5.0]],
+        ]);
+        let r = t.leaky_trace().min::<_, Axis<2>>();
+        assert_eq!(r.array(), [[1.0, 2.0], [0.0, -5.0]]);
+        // every tied element gets the whole gradient of its output
+        let g = r.mean().backward();
+        assert_eq!(
+            g.get(&t).array(),
+            [
+                [[0.0, 0.25, 0.25], [0.25, 0.25, 0.25]],
+                [[0.0, 0.25, 0.0], [0.25, 0.0, 0.25]]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_min_last_dim_matches_strided() {
+        let dev: TestDevice = Default::default();
+        // 37 is not a multiple of the number of lanes, and reducing `Axes2<2, 3>` over the
+        // size 1 axis 2 goes through the strided kernel on the same elements.
+        // the values repeat, so every row has ties
+        let data = (0..555).map(|i| ((i * 7) % 11) as TestDtype).collect();
+        let t: Tensor<Rank4<3, 5, 1, 37>, TestDtype, _> =
+            dev.tensor_from_vec(data, Default::default());
+        let r1 = t.leaky_trace().min::<Rank3<3, 5, 1>, Axis<3>>();
+        let r2 = t.leaky_trace().min::<Rank2<3, 5>, Axes2<2, 3>>();
+        assert_eq!(r1.as_vec(), r2.as_vec());
+        let g1 = r1.exp().sum().backward();
+        let g2 = r2.exp().sum().backward();
+        assert_eq!(g1.get(&t).as_vec(), g2.get(&t).as_vec());
+    }
+
+    #[test]
+    fn test_min_last_dim_degenerate_dims() {
+        let dev: TestDevice = Default::default();
+
+        let t: Tensor<Rank3<0, 2, 3>, TestDtype, _> = dev.sample_normal();
+        let r = t.leaky_trace().min::<Rank2<0, 2>, _>();
+        let r = r.sum::<Rank1<2>, _>();
+        assert_eq!(r.sum().backward().get(&t).shape(), t.shape());
+
+        let t: Tensor<Rank3<1, 1, 3>, TestDtype, _> = dev.tensor([[[1.0, 2.0, 3.0]]]);
+        let r = t.leaky_trace().min::<Rank2<1, 1>, _>();
+        assert_eq!(
+            r.array(),
+            [[t.array()[0][0].into_iter().reduce(TestDtype::min).unwrap()]]
+        );
+
+        let t: Tensor<Rank2<2, 0>, TestDtype, _> = dev.zeros();
+        let r = t.leaky_trace().min::<Rank1<2>, _>();
+        assert_eq!(r.array(), [TestDtype::INFINITY; 2]);
+        assert_eq!(r.sum().backward().get(&t).shape(), t.shape());
+    }
 }
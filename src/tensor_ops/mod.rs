@@ -151,108 +151,195 @@ pub use utilities::*;
 
 mod abs;
 mod add;
+mod add_relu;
+mod attention;
 mod attention_reshape;
 pub(crate) mod axpy;
 mod bce;
 mod boolean;
+mod boxes;
 mod broadcast_to;
+mod categorical;
+mod charbonnier;
+pub(crate) mod checkpoint;
+#[cfg(feature = "std")]
+mod checkpoint_to_disk;
 mod choose;
 mod clamp;
 mod cmp;
+mod complex;
 mod concat;
+mod conjugate_gradient;
 mod cos;
 mod div;
 mod dropout;
+mod erf;
 mod exp;
+mod expm1;
+mod extrema_last_dim;
+mod fft;
+mod fft_conv;
+mod function;
 mod gelu;
+mod gradient_reversal;
+mod gram_matrix;
 mod huber_error;
+mod hvp;
+mod jvp;
+mod linalg;
+mod linear_gelu;
 mod ln;
+mod log1p;
 mod log_softmax;
 mod logsumexp_to;
+mod map_reduce;
+mod masked_mean;
 mod matmul;
 mod max_to;
 mod maximum;
 mod mean_to;
+mod mel;
 mod min_to;
 mod minimum;
 mod mul;
 mod nans_to;
 mod negate;
+mod normal;
 mod normalize;
+mod norms;
+mod pad;
+mod pairwise_distance;
 mod permute_to;
 mod pow;
+mod power_iteration;
 mod realize_to;
+mod rearrange;
+mod reduce_keepdim;
+mod reduce_with;
 mod relu;
 mod reshape_to;
+mod rmsnorm;
 mod roll;
+mod rotary;
+mod sample;
 mod select_and_gather;
 mod sigmoid;
 mod sin;
 mod slice;
 mod softmax;
+mod softmax_cross_entropy;
+mod sparse;
 mod sqrt;
 mod square;
 mod stack;
 mod stddev_to;
+mod stft;
+mod stop_grad;
+mod straight_through;
 mod sub;
 mod sum_to;
 mod tanh;
+mod threshold;
 mod to_dtype;
 mod tri;
 mod var_to;
+mod vmap;
 
 pub use abs::abs;
 pub use add::{add, TryAdd};
+pub use add_relu::add_relu;
+pub use attention::{attention_apply, attention_scores};
 pub use attention_reshape::TryAttentionReshape;
 pub use axpy::axpy;
 pub use bce::bce_with_logits;
 pub use boolean::{bool_and, bool_not, bool_or, bool_xor};
+pub use boxes::{box_iou, nms};
 pub use broadcast_to::BroadcastTo;
+pub use categorical::Categorical;
+pub use charbonnier::charbonnier;
 pub use choose::ChooseFrom;
 pub use clamp::clamp;
 pub use cmp::{eq, ge, gt, le, lt, ne};
+pub use complex::ComplexTensor;
 pub use concat::TryConcat;
+pub use conjugate_gradient::{conjugate_gradient, try_conjugate_gradient};
 pub use cos::cos;
-pub use div::{div, TryDiv};
+pub use div::{div, rdiv_scalar, TryDiv};
 pub use dropout::dropout;
+pub use erf::{erf, erfc};
 pub use exp::exp;
-pub use gelu::gelu;
+pub use expm1::expm1;
+pub use extrema_last_dim::{max_last_dim, min_last_dim};
+pub use fft::{irfft, rfft, try_irfft, try_rfft};
+pub use fft_conv::{fft_conv1d, try_fft_conv1d};
+pub use function::Function;
+pub use gelu::{exact_gelu, gelu};
+pub use gradient_reversal::gradient_reversal;
+pub use gram_matrix::gram_matrix;
 pub use huber_error::huber_error;
+pub use hvp::{hvp_fd, try_hvp_fd, HvpError};
+pub use jvp::{jvp, Dual};
+pub use linalg::{cholesky, det, inverse, slogdet, solve, solve_triangular};
+pub use linear_gelu::linear_gelu;
 pub use ln::ln;
+pub use log1p::log1p;
 pub use log_softmax::log_softmax;
 pub use logsumexp_to::LogSumExpTo;
+pub use map_reduce::{map_reduce_last_dim, Reduction};
+pub use masked_mean::masked_mean_last_dim;
 pub use matmul::{matmul, TryMatMul};
 pub use max_to::MaxTo;
 pub use maximum::maximum;
 pub use mean_to::MeanTo;
+pub use mel::{mel_filterbank, try_mel_filterbank};
 pub use min_to::MinTo;
 pub use minimum::minimum;
 pub use mul::{mul, TryMul};
 pub use nans_to::nans_to;
 pub use negate::negate;
+pub use normal::{reparameterize, try_reparameterize, Normal};
 pub use normalize::normalize;
+pub use norms::{frobenius_norm, p_norm};
+pub use pad::{Pad1DShape, Pad2DShape, PadMode};
+pub use pairwise_distance::pairwise_distance;
 pub use permute_to::PermuteTo;
 pub use pow::{powf, powi};
+pub use power_iteration::power_iteration_sigma;
 pub use realize_to::RealizeTo;
+pub use reduce_keepdim::reduce_axis_keepdim;
 pub use relu::relu;
 pub use reshape_to::ReshapeTo;
+pub use rmsnorm::rmsnorm;
 pub use roll::Roll;
+pub use rotary::apply_rotary;
+pub use sample::{bernoulli, multinomial};
 pub use select_and_gather::{GatherTo, SelectTo};
 pub use sigmoid::sigmoid;
 pub use sin::sin;
 pub use slice::slice;
 pub use softmax::softmax;
+pub use sparse::{sparse_matmul, try_sparse_matmul, SparseTensor2D};
 pub use sqrt::sqrt;
 pub use square::square;
-pub use stack::TryStack;
+pub use stack::{vstack, AddDim, TryStack};
 pub use stddev_to::StddevTo;
-pub use sub::{sub, TrySub};
+pub use stft::{griffin_lim, stft, try_stft, Window};
+pub use stop_grad::{detach, stop_grad_where};
+pub use straight_through::{ste_round, ste_sign};
+pub use sub::{rsub_scalar, sub, TrySub};
 pub use sum_to::SumTo;
 pub use tanh::tanh;
+pub use threshold::threshold;
 pub use to_dtype::to_dtype;
 pub use tri::{lower_tri, upper_tri};
 pub use var_to::VarTo;
+pub use vmap::vmap;
 
+pub(crate) use gelu::GeLUKernelOp;
+pub(crate) use relu::ReLUKernelOp;
+pub(crate) use sigmoid::SigmoidKernelOp;
+pub(crate) use softmax_cross_entropy::SoftmaxCrossEntropyKernel;
+pub(crate) use tanh::TanhKernelOp;
 pub(crate) use to_dtype::ToDtypeKernel;
 
 #[cfg(feature = "nightly")]
@@ -265,10 +352,17 @@ mod convtrans2d;
 #[cfg(feature = "nightly")]
 pub use convtrans2d::{ConvTransAlgebra, TryConvTrans2D, TryConvTrans2DTo};
 
-mod upscale2d;
 #[cfg(feature = "nightly")]
+mod kron;
+#[cfg(feature = "nightly")]
+pub use kron::kron;
+
+mod upscale2d;
 pub(crate) use upscale2d::Upscale2DKernel;
-pub use upscale2d::{Bilinear, ConstUpscale2D, NearestNeighbor, TryUpscale2D, UpscaleMethod};
+pub use upscale2d::{
+    resize_bilinear, Bicubic, Bilinear, BilinearHalfPixel, ConstUpscale2D, NearestNeighbor,
+    TryUpscale2D, UpscaleMethod,
+};
 
 #[cfg(feature = "nightly")]
 mod pool2d;
@@ -0,0 +1,236 @@
+use num_traits::Float;
+use rand::Rng;
+use rand_distr::{Distribution, StandardNormal};
+
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{Merge, NoneTape, Tape, Tensor},
+};
+
+use super::{Device, TryAdd, TryDiv, TryMul, TrySub};
+
+/// A normal distribution with a `mean` and standard deviation `std` per element, e.g. a
+/// continuous policy in reinforcement learning, or the latent posterior of a VAE.
+///
+/// Like [super::Categorical], the methods take the distribution by value to keep the tapes
+/// of `mean` and `std`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::Normal};
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// # let dev: Cpu = Default::default();
+/// let mean: Tensor<Rank1<2>, f32, _> = dev.tensor([0.0, 1.0]);
+/// let std: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 0.5]);
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let z = Normal::new(mean.leaky_trace(), std.leaky_trace()).rsample(&mut rng);
+/// let prior = Normal::new(dev.zeros(), dev.ones());
+/// let kl = Normal::new(mean, std).kl_divergence(&prior);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Normal<S: Shape, E: Dtype, D: Device<E>, T> {
+    pub mean: Tensor<S, E, D, T>,
+    /// Must be positive.
+    pub std: Tensor<S, E, D, T>,
+}
+
+impl<S: Shape, E: Dtype + Float, D: Device<E>, T: Tape<E, D> + Merge<T>> Normal<S, E, D, T> {
+    pub fn new(mean: Tensor<S, E, D, T>, std: Tensor<S, E, D, T>) -> Self {
+        Self { mean, std }
+    }
+
+    /// A reparameterized sample `mean + std * eps` with `eps ~ N(0, 1)` drawn from `rng`,
+    /// so gradients flow back to both `mean` and `std`.
+    pub fn rsample<R: Rng>(self, rng: &mut R) -> Tensor<S, E, D, T>
+    where
+        StandardNormal: Distribution<E>,
+    {
+        self.try_rsample(rng).unwrap()
+    }
+
+    /// See [Normal::rsample()]
+    pub fn try_rsample<R: Rng>(self, rng: &mut R) -> Result<Tensor<S, E, D, T>, D::Err>
+    where
+        StandardNormal: Distribution<E>,
+    {
+        let shape = self.mean.shape;
+        let eps = (0..shape.num_elements())
+            .map(|_| rng.sample(StandardNormal))
+            .collect();
+        let eps = self.mean.device.try_tensor_from_vec(eps, shape)?;
+        self.mean.try_add(self.std.try_mul(eps)?)
+    }
+
+    /// The log density of `x` for each element,
+    /// `-(x - mean)^2 / (2 * std^2) - ln(std) - ln(2 * pi) / 2`.
+    pub fn log_prob(self, x: Tensor<S, E, D>) -> Tensor<S, E, D, T> {
+        self.try_log_prob(x).unwrap()
+    }
+
+    /// See [Normal::log_prob()]
+    pub fn try_log_prob(self, x: Tensor<S, E, D>) -> Result<Tensor<S, E, D, T>, D::Err> {
+        let half_ln_2pi = E::from_f64(0.5 * (2.0 * core::f64::consts::PI).ln()).unwrap();
+        let z = self.mean.try_sub(x)?.try_div(self.std.retaped::<T>())?;
+        let half_z_sq = z.try_square()?.try_mul(E::from_f32(0.5).unwrap())?;
+        half_z_sq
+            .try_add(self.std.try_ln()?)?
+            .try_add(half_ln_2pi)?
+            .try_negate()
+    }
+
+    /// The KL divergence `KL(self || other)` for each element,
+    /// `ln(other.std / std) + (std^2 + (mean - other.mean)^2) / (2 * other.std^2) - 1 / 2`.
+    ///
+    /// `other` is held fixed, so only `self` gets gradients.
+    pub fn kl_divergence<R>(self, other: &Normal<S, E, D, R>) -> Tensor<S, E, D, T> {
+        self.try_kl_divergence(other).unwrap()
+    }
+
+    /// See [Normal::kl_divergence()]
+    pub fn try_kl_divergence<R>(
+        self,
+        other: &Normal<S, E, D, R>,
+    ) -> Result<Tensor<S, E, D, T>, D::Err> {
+        let other_mean = other.mean.retaped::<NoneTape>();
+        let other_std = other.std.retaped::<NoneTape>();
+        let two_other_var = other_std
+            .clone()
+            .try_square()?
+            .try_mul(E::from_f32(2.0).unwrap())?;
+        let ln_ratio = self.std.retaped::<T>().try_ln()?.try_negate()?;
+        let ln_ratio = ln_ratio.try_add(other_std.try_ln()?)?;
+        let mean_diff_sq = self.mean.try_sub(other_mean)?.try_square()?;
+        let numerator = self.std.try_square()?.try_add(mean_diff_sq)?;
+        ln_ratio
+            .try_add(numerator.try_div(two_other_var)?)?
+            .try_sub(E::from_f32(0.5).unwrap())
+    }
+}
+
+/// The reparameterization trick of a VAE, which samples `mean + exp(0.5 * logvar) * eps`
+/// with `eps ~ N(0, 1)` drawn from `rng`. Gradients flow back to `mean` and `logvar`, but
+/// not `eps`.
+///
+/// See [Normal::rsample()].
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::reparameterize};
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// # let dev: Cpu = Default::default();
+/// let mean: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+/// let logvar: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let z = reparameterize(mean.leaky_trace(), logvar.leaky_trace(), &mut rng);
+/// ```
+pub fn reparameterize<S: Shape, E: Dtype + Float, D: Device<E>, T, R: Rng>(
+    mean: Tensor<S, E, D, T>,
+    logvar: Tensor<S, E, D, T>,
+    rng: &mut R,
+) -> Tensor<S, E, D, T>
+where
+    T: Tape<E, D> + Merge<T>,
+    StandardNormal: Distribution<E>,
+{
+    try_reparameterize(mean, logvar, rng).unwrap()
+}
+
+/// See [reparameterize()]
+pub fn try_reparameterize<S: Shape, E: Dtype + Float, D: Device<E>, T, R: Rng>(
+    mean: Tensor<S, E, D, T>,
+    logvar: Tensor<S, E, D, T>,
+    rng: &mut R,
+) -> Result<Tensor<S, E, D, T>, D::Err>
+where
+    T: Tape<E, D> + Merge<T>,
+    StandardNormal: Distribution<E>,
+{
+    let std = logvar.try_mul(E::from_f32(0.5).unwrap())?.try_exp()?;
+    Normal::new(mean, std).try_rsample(rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_normal_rsample_grads() {
+        let dev: TestDevice = Default::default();
+        let mean: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.0, 1.0, -2.0]);
+        let std: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 0.5, 2.0]);
+        let mut rng = StdRng::seed_from_u64(0);
+        let z = Normal::new(mean.leaky_trace(), std.leaky_trace()).rsample(&mut rng);
+        let eps = ((z.retaped::<NoneTape>() - mean.clone()) / std.clone()).array();
+
+        let g = z.sum().backward();
+        assert_eq!(g.get(&mean).array(), [1.0; 3]);
+        assert_close(&g.get(&std).array(), &eps);
+        assert!(eps.iter().all(|e| *e != 0.0));
+    }
+
+    #[test]
+    fn test_reparameterize_seeded() {
+        let dev: TestDevice = Default::default();
+        let mean: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.0, 1.0, -2.0]);
+        let logvar: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.0, -1.0, 2.0]);
+        let z = reparameterize(
+            mean.leaky_trace(),
+            logvar.leaky_trace(),
+            &mut StdRng::seed_from_u64(0),
+        );
+        let z2 = reparameterize(mean.clone(), logvar.clone(), &mut StdRng::seed_from_u64(0));
+        assert_eq!(z.array(), z2.array());
+
+        let (m, lv, z_arr) = (mean.array(), logvar.array(), z.array());
+        let std = lv.map(|lv| (0.5 * lv).exp());
+        let eps = [0, 1, 2].map(|i| (z_arr[i] - m[i]) / std[i]);
+        let g = z.sum().backward();
+        assert_eq!(g.get(&mean).array(), [1.0; 3]);
+        assert_close(
+            &g.get(&logvar).array(),
+            &[0, 1, 2].map(|i| 0.5 * std[i] * eps[i]),
+        );
+    }
+
+    #[test]
+    fn test_normal_log_prob() {
+        let dev: TestDevice = Default::default();
+        let mean: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.0, 1.0]);
+        let std: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 0.5]);
+        let x: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.5, 2.0]);
+        let log_prob = Normal::new(mean.leaky_trace(), std.leaky_trace()).log_prob(x);
+
+        let density = |x: TestDtype, m: TestDtype, s: TestDtype| {
+            let z = (x - m) / s;
+            (-0.5 * z * z).exp() / (s * (2.0 * core::f64::consts::PI as TestDtype).sqrt())
+        };
+        assert_close(
+            &log_prob.array(),
+            &[density(0.5, 0.0, 1.0).ln(), density(2.0, 1.0, 0.5).ln()],
+        );
+
+        // d/dmean = (x - mean) / std^2, d/dstd = (x - mean)^2 / std^3 - 1 / std
+        let g = log_prob.sum().backward();
+        assert_close(&g.get(&mean).array(), &[0.5, 4.0]);
+        assert_close(&g.get(&std).array(), &[0.25 - 1.0, 8.0 - 2.0]);
+    }
+
+    #[test]
+    fn test_normal_kl_against_standard_normal() {
+        let dev: TestDevice = Default::default();
+        let mean: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([0.0, 1.0, -0.5]);
+        let std: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 0.5, 2.0]);
+        let prior = Normal::new(dev.zeros(), dev.ones());
+        let kl = Normal::new(mean.leaky_trace(), std.leaky_trace()).kl_divergence(&prior);
+
+        // 0.5 * (std^2 + mean^2 - 1) - ln(std)
+        let (m, s) = (mean.array(), std.array());
+        let expected = [0, 1, 2].map(|i| 0.5 * (s[i] * s[i] + m[i] * m[i] - 1.0) - s[i].ln());
+        assert_close(&kl.array(), &expected);
+        assert_eq!(kl.array()[0], 0.0);
+
+        let g = kl.sum().backward();
+        assert_close(&g.get(&mean).array(), &m);
+        assert_close(&g.get(&std).array(), &s.map(|s| s - 1.0 / s));
+    }
+}
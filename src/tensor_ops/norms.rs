@@ -0,0 +1,129 @@
+use crate::{
+    shapes::{Dtype, Rank0, Shape},
+    tensor::{Tape, Tensor},
+};
+
+use super::{Device, SumTo};
+
+/// The frobenius norm of `t`, i.e. the square root of the sum of all elements squared.
+/// For vectors this is the euclidean (L2) norm.
+///
+/// **Pytorch equivalent**: `torch.linalg.norm(t)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[1.0, -2.0], [2.0, 4.0]]);
+/// assert_eq!(t.frobenius_norm().array(), 5.0);
+/// ```
+pub fn frobenius_norm<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<Rank0, E, D, T> {
+    t.frobenius_norm()
+}
+
+/// The entrywise `p`-norm of `t`: `sum(|t|^p)^(1/p)`. `p=2.0` is the same as [frobenius_norm()].
+///
+/// **Pytorch equivalent**: `torch.linalg.vector_norm(t, ord=p)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, -2.0, 3.0]);
+/// assert_eq!(t.p_norm(1.0).array(), 6.0);
+/// ```
+pub fn p_norm<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    p: E,
+) -> Tensor<Rank0, E, D, T> {
+    t.p_norm(p)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [frobenius_norm]
+    pub fn frobenius_norm(self) -> Tensor<Rank0, E, D, T> {
+        self.try_frobenius_norm().unwrap()
+    }
+
+    /// See [frobenius_norm]
+    pub fn try_frobenius_norm(self) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+        self.try_square()?
+            .try_sum::<Rank0, S::AllAxes>()?
+            .try_sqrt()
+    }
+
+    /// See [p_norm]
+    pub fn p_norm(self, p: E) -> Tensor<Rank0, E, D, T> {
+        self.try_p_norm(p).unwrap()
+    }
+
+    /// See [p_norm]
+    pub fn try_p_norm(self, p: E) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+        self.try_abs()?
+            .try_powf(p)?
+            .try_sum::<Rank0, S::AllAxes>()?
+            .try_powf(E::ONE / p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_frobenius_norm_grad() {
+        let dev: TestDevice = Default::default();
+        let x = [[1.0, -2.0, 0.5], [3.0, 0.25, -1.0]];
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor(x);
+        let r = t.leaky_trace().frobenius_norm();
+        let norm = r.array();
+        assert_close(&norm, &(15.3125 as TestDtype).sqrt());
+        let g = r.backward();
+        assert_close(&g.get(&t).array(), &x.map(|row| row.map(|v| v / norm)));
+
+        // finite differences
+        let eps = 1e-2;
+        let mut fd = [[0.0; 3]; 2];
+        for i in 0..2 {
+            for j in 0..3 {
+                let mut hi = x;
+                hi[i][j] += eps;
+                let mut lo = x;
+                lo[i][j] -= eps;
+                let hi: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor(hi);
+                let lo: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor(lo);
+                fd[i][j] =
+                    (hi.frobenius_norm().array() - lo.frobenius_norm().array()) / (2.0 * eps);
+            }
+        }
+        assert_close_with_tolerance(&g.get(&t).array(), &fd, 1e-3);
+    }
+
+    #[test]
+    fn test_p_norm() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, -2.0, 3.0]);
+        assert_close(
+            &t.clone().p_norm(2.0).array(),
+            &t.clone().frobenius_norm().array(),
+        );
+        assert_close(
+            &t.clone().p_norm(3.0).array(),
+            &(36.0 as TestDtype).powf(1.0 / 3.0),
+        );
+
+        let r = t.leaky_trace().p_norm(1.0);
+        assert_eq!(r.array(), 6.0);
+        let g = r.backward();
+        assert_eq!(g.get(&t).array(), [1.0, -1.0, 1.0]);
+
+        // d/dx_i (sum |x|^3)^(1/3) = sign(x_i) * x_i^2 / norm^2
+        let r = t.leaky_trace().p_norm(3.0);
+        let norm2 = r.array().powi(2);
+        let g = r.backward();
+        assert_close(
+            &g.get(&t).array(),
+            &[1.0 / norm2, -4.0 / norm2, 9.0 / norm2],
+        );
+    }
+}
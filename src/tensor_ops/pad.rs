@@ -0,0 +1,293 @@
+use crate::{
+    shapes::{Dim, Dtype, Shape},
+    tensor::{Tape, Tensor},
+};
+
+use super::{ops::try_host_unary_op, Device};
+
+use std::vec::Vec;
+
+/// How [Tensor::pad1d()] and [Tensor::pad2d()] fill the padding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadMode {
+    /// Pads with zeros.
+    Zeros,
+    /// Wraps around, so the padding before the start is copied from the end and the padding
+    /// after the end is copied from the start, as for periodic boundary conditions.
+    ///
+    /// **Pytorch equivalent**: `F.pad(t, pad, mode="circular")`
+    Circular,
+}
+
+impl PadMode {
+    /// The index of the input that output `o` is copied from, if any.
+    fn source(&self, o: usize, before: usize, len: usize) -> Option<usize> {
+        let i = o as isize - before as isize;
+        match self {
+            PadMode::Zeros => (0..len as isize).contains(&i).then_some(i as usize),
+            PadMode::Circular => Some(i.rem_euclid(len as isize) as usize),
+        }
+    }
+}
+
+/// A shape whose last dimension can be padded by [Tensor::pad1d()].
+pub trait Pad1DShape: Shape {
+    type Padded: Shape;
+    /// The size of the last dimension.
+    fn last(&self) -> usize;
+    fn padded(&self, last: usize) -> Self::Padded;
+}
+
+impl<L: Dim> Pad1DShape for (L,) {
+    type Padded = (usize,);
+    fn last(&self) -> usize {
+        self.0.size()
+    }
+    fn padded(&self, last: usize) -> Self::Padded {
+        (last,)
+    }
+}
+
+impl<C: Dim, L: Dim> Pad1DShape for (C, L) {
+    type Padded = (C, usize);
+    fn last(&self) -> usize {
+        self.1.size()
+    }
+    fn padded(&self, last: usize) -> Self::Padded {
+        (self.0, last)
+    }
+}
+
+impl<B: Dim, C: Dim, L: Dim> Pad1DShape for (B, C, L) {
+    type Padded = (B, C, usize);
+    fn last(&self) -> usize {
+        self.2.size()
+    }
+    fn padded(&self, last: usize) -> Self::Padded {
+        (self.0, self.1, last)
+    }
+}
+
+/// A shape whose last two dimensions can be padded by [Tensor::pad2d()].
+pub trait Pad2DShape: Shape {
+    type Padded: Shape;
+    /// The sizes of the last two dimensions.
+    fn last_two(&self) -> (usize, usize);
+    fn padded(&self, h: usize, w: usize) -> Self::Padded;
+}
+
+impl<H: Dim, W: Dim> Pad2DShape for (H, W) {
+    type Padded = (usize, usize);
+    fn last_two(&self) -> (usize, usize) {
+        (self.0.size(), self.1.size())
+    }
+    fn padded(&self, h: usize, w: usize) -> Self::Padded {
+        (h, w)
+    }
+}
+
+impl<C: Dim, H: Dim, W: Dim> Pad2DShape for (C, H, W) {
+    type Padded = (C, usize, usize);
+    fn last_two(&self) -> (usize, usize) {
+        (self.1.size(), self.2.size())
+    }
+    fn padded(&self, h: usize, w: usize) -> Self::Padded {
+        (self.0, h, w)
+    }
+}
+
+impl<B: Dim, C: Dim, H: Dim, W: Dim> Pad2DShape for (B, C, H, W) {
+    type Padded = (B, C, usize, usize);
+    fn last_two(&self) -> (usize, usize) {
+        (self.2.size(), self.3.size())
+    }
+    fn padded(&self, h: usize, w: usize) -> Self::Padded {
+        (self.0, self.1, h, w)
+    }
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Pads the last dimension with `left` elements before it and `right` after it, filled
+    /// according to `mode`, e.g. before a convolution.
+    ///
+    /// ```rust
+    /// # use dfdx::{prelude::*, tensor_ops::PadMode};
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, 2.0, 3.0]);
+    /// let r = t.clone().pad1d(1, 1, PadMode::Circular);
+    /// assert_eq!(r.as_vec(), [3.0, 1.0, 2.0, 3.0, 1.0]);
+    /// let r = t.pad1d(2, 0, PadMode::Zeros);
+    /// assert_eq!(r.as_vec(), [0.0, 0.0, 1.0, 2.0, 3.0]);
+    /// ```
+    pub fn pad1d(self, left: usize, right: usize, mode: PadMode) -> Tensor<S::Padded, E, D, T>
+    where
+        S: Pad1DShape,
+    {
+        self.try_pad1d(left, right, mode).unwrap()
+    }
+
+    /// See [Tensor::pad1d()]
+    pub fn try_pad1d(
+        self,
+        left: usize,
+        right: usize,
+        mode: PadMode,
+    ) -> Result<Tensor<S::Padded, E, D, T>, D::Err>
+    where
+        S: Pad1DShape,
+    {
+        let len = self.shape.last();
+        let dst = self.shape.padded(left + len + right);
+        try_pad(self, dst, (1, len), (0, 0), (left, right), mode)
+    }
+
+    /// Pads the last two dimensions with `(before, after)` elements for each of `pad_h` and
+    /// `pad_w`, filled according to `mode`. With [PadMode::Circular] the corners wrap around
+    /// both dimensions.
+    ///
+    /// ```rust
+    /// # use dfdx::{prelude::*, tensor_ops::PadMode};
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank3<1, 2, 2>, f32, _> = dev.tensor([[[1.0, 2.0], [3.0, 4.0]]]);
+    /// let r = t.pad2d((1, 0), (0, 1), PadMode::Circular);
+    /// assert_eq!(r.as_vec(), [3.0, 4.0, 3.0, 1.0, 2.0, 1.0, 3.0, 4.0, 3.0]);
+    /// ```
+    pub fn pad2d(
+        self,
+        pad_h: (usize, usize),
+        pad_w: (usize, usize),
+        mode: PadMode,
+    ) -> Tensor<S::Padded, E, D, T>
+    where
+        S: Pad2DShape,
+    {
+        self.try_pad2d(pad_h, pad_w, mode).unwrap()
+    }
+
+    /// See [Tensor::pad2d()]
+    pub fn try_pad2d(
+        self,
+        pad_h: (usize, usize),
+        pad_w: (usize, usize),
+        mode: PadMode,
+    ) -> Result<Tensor<S::Padded, E, D, T>, D::Err>
+    where
+        S: Pad2DShape,
+    {
+        let (h, w) = self.shape.last_two();
+        let dst = self
+            .shape
+            .padded(pad_h.0 + h + pad_h.1, pad_w.0 + w + pad_w.1);
+        try_pad(self, dst, (h, w), pad_h, pad_w, mode)
+    }
+}
+
+/// Pads the last two dimensions `(h, w)` of `t`, where 1d padding has `h = 1`.
+fn try_pad<S: Shape, Dst: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    dst: Dst,
+    (h, w): (usize, usize),
+    pad_h: (usize, usize),
+    pad_w: (usize, usize),
+    mode: PadMode,
+) -> Result<Tensor<Dst, E, D, T>, D::Err> {
+    assert!(
+        mode != PadMode::Circular || (h > 0 && w > 0),
+        "can't circularly pad an empty dimension"
+    );
+    let (h_out, w_out) = (pad_h.0 + h + pad_h.1, pad_w.0 + w + pad_w.1);
+    let outer = t.shape.num_elements() / (h * w).max(1);
+    // the input index each output element is copied from, if any
+    let mut sources: Vec<Option<usize>> = Vec::with_capacity(outer * h_out * w_out);
+    for o in 0..outer {
+        for i in 0..h_out {
+            for j in 0..w_out {
+                let src = mode.source(i, pad_h.0, h).zip(mode.source(j, pad_w.0, w));
+                sources.push(src.map(|(i, j)| (o * h + i) * w + j));
+            }
+        }
+    }
+    let fwd_sources = sources.clone();
+    try_host_unary_op(
+        t,
+        dst,
+        |inp| {
+            fwd_sources
+                .iter()
+                .map(|src| src.map_or(E::default(), |i| inp[i]))
+                .collect()
+        },
+        move |inp, _, grad_out| {
+            let mut grad = vec![E::default(); inp.len()];
+            for (src, &g) in sources.iter().zip(grad_out) {
+                if let Some(i) = src {
+                    grad[*i] += g;
+                }
+            }
+            grad
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_circular_pad1d() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, 3.0]);
+        let r = t.leaky_trace().pad1d(1, 1, PadMode::Circular);
+        assert_eq!(r.shape(), &(5,));
+        assert_eq!(r.as_vec(), [3.0, 1.0, 2.0, 3.0, 1.0]);
+
+        // the gradients of the wrapped copies go back to their sources
+        let weights = dev.tensor_from_vec(std::vec![1.0, 2.0, 3.0, 4.0, 5.0], (5,));
+        let g = (r * weights).sum::<Rank0, _>().backward();
+        assert_eq!(g.get(&t).array(), [2.0 + 5.0, 3.0, 4.0 + 1.0]);
+    }
+
+    #[test]
+    fn test_pad1d_batched_and_zeros() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0]]);
+        let r = t.clone().pad1d(3, 0, PadMode::Circular);
+        assert_eq!(r.shape(), &(Const::<2>, 5));
+        assert_eq!(
+            r.as_vec(),
+            [2.0, 1.0, 2.0, 1.0, 2.0, 4.0, 3.0, 4.0, 3.0, 4.0]
+        );
+
+        let r = t.leaky_trace().pad1d(1, 2, PadMode::Zeros);
+        assert_eq!(
+            r.as_vec(),
+            [0.0, 1.0, 2.0, 0.0, 0.0, 0.0, 3.0, 4.0, 0.0, 0.0]
+        );
+        let g = r.exp().sum::<Rank0, _>().backward();
+        assert_close(&g.get(&t).array(), &t.array().map(|r| r.map(|x| x.exp())));
+    }
+
+    #[test]
+    fn test_circular_pad2d() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<1, 2, 3>, TestDtype, _> =
+            dev.tensor([[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]]);
+        let r = t.leaky_trace().pad2d((1, 1), (1, 1), PadMode::Circular);
+        assert_eq!(r.shape(), &(Const::<1>, 4, 5));
+        #[rustfmt::skip]
+        assert_eq!(
+            r.as_vec(),
+            [
+                6.0, 4.0, 5.0, 6.0, 4.0,
+                3.0, 1.0, 2.0, 3.0, 1.0,
+                6.0, 4.0, 5.0, 6.0, 4.0,
+                3.0, 1.0, 2.0, 3.0, 1.0,
+            ]
+        );
+
+        // each element is copied once per time it appears
+        let g = r.sum::<Rank0, _>().backward();
+        assert_eq!(g.get(&t).array(), [[[4.0, 2.0, 4.0], [4.0, 2.0, 4.0]]]);
+    }
+}
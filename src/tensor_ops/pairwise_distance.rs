@@ -0,0 +1,116 @@
+#![allow(clippy::type_complexity)]
+
+use crate::{
+    shapes::{Axis, Dim, Dtype},
+    tensor::{Merge, Tape, Tensor},
+};
+
+use super::{BroadcastTo, Device, SumTo, TrySub};
+
+/// Euclidean distances between every row of `a` and every row of `b`:
+/// `out[i][j] = ||a[i] - b[j]||`.
+///
+/// The gradient is undefined where two rows are equal, since the derivative
+/// of `sqrt` is infinite at 0.
+///
+/// **Pytorch equivalent**: `torch.cdist(a, b)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[0.0, 0.0], [1.0, 1.0]]);
+/// let b: Tensor<Rank2<1, 2>, f32, _> = dev.tensor([[3.0, 4.0]]);
+/// let r: Tensor<Rank2<2, 1>, f32, _> = pairwise_distance(a, b);
+/// assert_eq!(r.array(), [[5.0], [13.0f32.sqrt()]]);
+/// ```
+pub fn pairwise_distance<M: Dim, N: Dim, K: Dim, E: Dtype, D: Device<E>, T, R>(
+    a: Tensor<(M, K), E, D, T>,
+    b: Tensor<(N, K), E, D, R>,
+) -> Tensor<(M, N), E, D, T>
+where
+    T: Tape<E, D> + Merge<R>,
+    R: Tape<E, D>,
+{
+    a.pairwise_distance(b)
+}
+
+impl<M: Dim, K: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(M, K), E, D, T> {
+    /// See [pairwise_distance]
+    pub fn pairwise_distance<N: Dim, R: Tape<E, D>>(
+        self,
+        b: Tensor<(N, K), E, D, R>,
+    ) -> Tensor<(M, N), E, D, T>
+    where
+        T: Merge<R>,
+    {
+        self.try_pairwise_distance(b).unwrap()
+    }
+
+    /// See [pairwise_distance]
+    pub fn try_pairwise_distance<N: Dim, R: Tape<E, D>>(
+        self,
+        b: Tensor<(N, K), E, D, R>,
+    ) -> Result<Tensor<(M, N), E, D, T>, D::Err>
+    where
+        T: Merge<R>,
+    {
+        let (m, k) = self.shape;
+        let n = b.shape.0;
+        let dst = (m, n, k);
+        let a = self.try_broadcast_like::<_, Axis<1>>(&dst)?;
+        let b = b.try_broadcast_like::<_, Axis<0>>(&dst)?;
+        a.try_sub(b)?
+            .try_square()?
+            .try_sum::<_, Axis<2>>()?
+            .try_sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_pairwise_distance() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor([[0.0, 0.0, 0.0], [1.0, 2.0, 2.0]]);
+        let b: Tensor<Rank2<3, 3>, TestDtype, _> =
+            dev.tensor([[3.0, 0.0, 4.0], [1.0, 0.0, 0.0], [1.0, 2.0, 3.0]]);
+        let r = a.leaky_trace().pairwise_distance(b.clone());
+        assert_close(
+            &r.array(),
+            &[
+                [5.0, 1.0, TestDtype::sqrt(14.0)],
+                [TestDtype::sqrt(12.0), TestDtype::sqrt(8.0), 1.0],
+            ],
+        );
+
+        // d||a_i - b_j|| / da_i = (a_i - b_j) / ||a_i - b_j||
+        let g = r.sum().backward();
+        let a_arr = a.array();
+        let b_arr = b.array();
+        let mut expected = [[0.0; 3]; 2];
+        for i in 0..2 {
+            for b_j in b_arr.iter() {
+                let diff: [TestDtype; 3] = std::array::from_fn(|k| a_arr[i][k] - b_j[k]);
+                let dist = diff.iter().map(|d| d * d).sum::<TestDtype>().sqrt();
+                for k in 0..3 {
+                    expected[i][k] += diff[k] / dist;
+                }
+            }
+        }
+        assert_close(&g.get(&a).array(), &expected);
+    }
+
+    #[test]
+    fn test_pairwise_distance_grad_both() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<1, 2>, TestDtype, _> = dev.tensor([[0.0, 0.0]]);
+        let b: Tensor<Rank2<1, 2>, TestDtype, _> = dev.tensor([[3.0, 4.0]]);
+        let r = a.leaky_trace().pairwise_distance(b.leaky_trace());
+        assert_close(&r.array(), &[[5.0]]);
+        let g = r.sum().backward();
+        assert_close(&g.get(&a).array(), &[[-0.6, -0.8]]);
+        assert_close(&g.get(&b).array(), &[[0.6, 0.8]]);
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     tensor::{DeviceStorage, HasErr, PutTape, SplitTape, Tape, Tensor, ZerosTensor},
 };
 
-use super::conv2d::ConvAlgebra;
+use super::{conv2d::ConvAlgebra, ops::profiled};
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -107,7 +107,7 @@ macro_rules! pool2d {
                 let mut out =
                     inp.device
                         .try_zeros_like(&(chan, h.convolve_dim(), w.convolve_dim()))?;
-                inp.device.forward(op, &inp, &mut out)?;
+                profiled(|| inp.device.forward(op, &inp, &mut out))?;
                 let phantom_out = out.clone();
                 tape.try_alloc_grad(&inp)?;
                 tape.try_alloc_grad(&out)?;
@@ -148,7 +148,7 @@ macro_rules! pool2d {
                     h.convolve_dim(),
                     w.convolve_dim(),
                 ))?;
-                inp.device.forward(op, &inp, &mut out)?;
+                profiled(|| inp.device.forward(op, &inp, &mut out))?;
                 let phantom_out = out.clone();
                 tape.try_alloc_grad(&inp)?;
                 tape.try_alloc_grad(&out)?;
@@ -0,0 +1,88 @@
+use crate::{
+    shapes::{Dim, Dtype, Rank0},
+    tensor::{PutTape, SplitTape, Tape, Tensor},
+};
+
+use super::{BroadcastTo, Device, PermuteTo, TryAdd, TryDiv, TryMatMul};
+
+/// Estimates the largest singular value of `w` with `iters` steps of power iteration,
+/// starting from a vector of ones.
+///
+/// Unlike [crate::nn::modules::SpectralNorm], which keeps its singular vectors around
+/// between calls, the singular vectors are recomputed from scratch every call and
+/// the gradient flows through every step of the iteration.
+///
+/// Converges faster the larger the gap between the two largest singular values.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let w: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[0.5, 0.0], [0.0, -2.0]]);
+/// let sigma = power_iteration_sigma(w, 10);
+/// assert!((sigma.array() - 2.0).abs() < 1e-6);
+/// ```
+pub fn power_iteration_sigma<M: Dim, N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    w: Tensor<(M, N), E, D, T>,
+    iters: usize,
+) -> Tensor<Rank0, E, D, T> {
+    w.power_iteration_sigma(iters)
+}
+
+impl<M: Dim, N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(M, N), E, D, T> {
+    /// See [power_iteration_sigma]
+    pub fn power_iteration_sigma(self, iters: usize) -> Tensor<Rank0, E, D, T> {
+        self.try_power_iteration_sigma(iters).unwrap()
+    }
+
+    /// See [power_iteration_sigma]
+    pub fn try_power_iteration_sigma(self, iters: usize) -> Result<Tensor<Rank0, E, D, T>, D::Err> {
+        let (w, tape) = self.split_tape();
+        let u = w.device.try_ones_like(&(w.shape.0,))?;
+        let mut u = try_l2_normalize(u.put_tape(tape))?;
+        for _ in 0..iters {
+            let v = try_l2_normalize(u.try_matmul(w.retaped::<T>())?)?;
+            u = try_l2_normalize(v.try_matmul(w.retaped::<T>().try_permute()?)?)?;
+        }
+        u.try_matmul(w.retaped::<T>())?.try_frobenius_norm()
+    }
+}
+
+fn try_l2_normalize<N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<(N,), E, D, T>,
+) -> Result<Tensor<(N,), E, D, T>, D::Err> {
+    let norm = t
+        .retaped::<T>()
+        .try_frobenius_norm()?
+        .try_add(E::from_f32(1e-12).unwrap())?;
+    let shape = t.shape;
+    t.try_div(norm.try_broadcast_like(&shape)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_power_iteration_sigma_diagonal() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank2<3, 3>, TestDtype, _> =
+            dev.tensor([[1.0, 0.0, 0.0], [0.0, -4.0, 0.0], [0.0, 0.0, 2.0]]);
+        let sigma = w.leaky_trace().power_iteration_sigma(20);
+        assert_close(&sigma.array(), &4.0);
+
+        // d sigma / dw = u v^T for the top singular vectors u & v
+        let g = sigma.backward();
+        assert_close_with_tolerance(
+            &g.get(&w).array(),
+            &[[0.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 0.0]],
+            1e-4,
+        );
+    }
+
+    #[test]
+    fn test_power_iteration_sigma_rectangular() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor([[3.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_close(&w.power_iteration_sigma(20).array(), &3.0);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{shapes::*, tensor::*};
+
+use super::{reshape_to::ReshapeKernel, ReshapeTo};
+
+use std::vec::Vec;
+
+/// einops style rearranging of a tensor's axes. Shorthand for [Tensor::einops_rearrange()],
+/// where the sizes of any split axes are passed as `name = size`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, rearrange};
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<6, 4>, f32, _> = dev.zeros();
+/// let r: Tensor<Rank3<2, 3, 4>, f32, _> = rearrange!(t, "(b h) w -> b h w", h = 3);
+/// let r: Tensor<Rank2<4, 6>, f32, _> = rearrange!(r, "b h w -> w (b h)");
+/// ```
+#[macro_export]
+macro_rules! rearrange {
+    ($t:expr, $pattern:expr $(, $name:ident = $size:expr)* $(,)?) => {
+        $t.einops_rearrange($pattern, &[$((stringify!($name), $size)),*])
+    };
+}
+
+/// The axes of one side of a pattern, where each group is a dimension of the tensor.
+type Groups<'a> = Vec<Vec<&'a str>>;
+
+fn parse_side(side: &str) -> Groups<'_> {
+    let mut groups = Vec::new();
+    let mut group: Option<Vec<&str>> = None;
+    let mut chars = side.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match (c, group.as_mut()) {
+            (c, _) if c.is_whitespace() => {}
+            ('(', None) => group = Some(Vec::new()),
+            (')', Some(_)) => groups.push(group.take().unwrap()),
+            ('(', Some(_)) | (')', None) => panic!("Unbalanced parentheses in `{side}`"),
+            (c, g) => {
+                assert!(
+                    c.is_alphanumeric() || c == '_',
+                    "Unexpected `{c}` in `{side}`"
+                );
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let name = &side[start..end];
+                match g {
+                    Some(g) => g.push(name),
+                    None => groups.push(vec![name]),
+                }
+            }
+        }
+    }
+    assert!(group.is_none(), "Unbalanced parentheses in `{side}`");
+    groups
+}
+
+/// Works out the size of each axis in `lhs`, using `sizes` for the axes of split dimensions.
+fn axis_sizes<'a>(
+    lhs: &Groups<'a>,
+    dims: &[usize],
+    sizes: &[(&str, usize)],
+) -> Vec<(&'a str, usize)> {
+    assert_eq!(
+        lhs.len(),
+        dims.len(),
+        "Pattern has {} dimensions, but the tensor has {}",
+        lhs.len(),
+        dims.len()
+    );
+    let mut known = Vec::new();
+    for (group, &dim) in lhs.iter().zip(dims.iter()) {
+        let lookup = |name: &str| sizes.iter().find(|(n, _)| *n == name).map(|&(_, s)| s);
+        let unknown: Vec<_> = group.iter().filter(|n| lookup(n).is_none()).collect();
+        let product: usize = group.iter().filter_map(|n| lookup(n)).product();
+        let inferred = match unknown.len() {
+            0 => None,
+            1 if product > 0 && dim % product == 0 => Some(dim / product),
+            1 => panic!("Can't split dimension of size {dim} into {group:?}"),
+            _ => panic!("Sizes of {unknown:?} are ambiguous, pass all but one of them"),
+        };
+        for &name in group.iter() {
+            let size = lookup(name).or(inferred).unwrap();
+            known.push((name, size));
+        }
+        let total: usize = known[known.len() - group.len()..]
+            .iter()
+            .map(|s| s.1)
+            .product();
+        assert_eq!(
+            total, dim,
+            "{group:?} doesn't have the size of its dimension {dim}"
+        );
+    }
+    for (name, _) in sizes.iter() {
+        assert!(
+            known.iter().any(|(n, _)| n == name),
+            "Size was given for `{name}`, which isn't in the pattern"
+        );
+    }
+    known
+}
+
+impl<S: Shape, E: Dtype, D: ReshapeKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Rearranges the axes of this tensor with an einops style `pattern`, like
+    /// `"(b h) w -> b h w"`. Dimensions can be split and merged by putting their axes in
+    /// parentheses, and axes can be reordered. This is usually called with [rearrange!].
+    ///
+    /// `sizes` are the sizes of axes in split dimensions, where one axis of each split
+    /// dimension can be left out and is inferred. At most 6 axes are supported.
+    ///
+    /// **Panics** if the pattern is invalid, or doesn't match the tensor or `Dst`.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// let r: Tensor<Rank1<6>, f32, _> = t.einops_rearrange("a b -> (b a)", &[]);
+    /// assert_eq!(r.array(), [1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    /// ```
+    pub fn einops_rearrange<Dst: Shape>(
+        self,
+        pattern: &str,
+        sizes: &[(&str, usize)],
+    ) -> Tensor<Dst, E, D, T> {
+        self.try_einops_rearrange(pattern, sizes).unwrap()
+    }
+
+    /// See [Tensor::einops_rearrange]
+    pub fn try_einops_rearrange<Dst: Shape>(
+        self,
+        pattern: &str,
+        sizes: &[(&str, usize)],
+    ) -> Result<Tensor<Dst, E, D, T>, D::Err> {
+        let (lhs, rhs) = pattern
+            .split_once("->")
+            .unwrap_or_else(|| panic!("Pattern `{pattern}` is missing `->`"));
+        let (lhs, rhs) = (parse_side(lhs), parse_side(rhs));
+
+        let axes = axis_sizes(&lhs, self.shape.concrete().as_ref(), sizes);
+        let mut out_axes: Vec<&str> = rhs.iter().flatten().copied().collect();
+        assert!(axes.len() <= 6, "At most 6 axes are supported");
+        assert_eq!(
+            axes.len(),
+            out_axes.len(),
+            "Both sides of `{pattern}` must have the same axes"
+        );
+        out_axes.sort_unstable();
+        out_axes.dedup();
+        assert_eq!(axes.len(), out_axes.len(), "Axes repeated in `{pattern}`");
+
+        // strides of every axis, as if they were dimensions of the contiguous input
+        let mut strides = vec![0; axes.len()];
+        let mut stride = 1;
+        for i in (0..axes.len()).rev() {
+            strides[i] = stride;
+            stride *= axes[i].1;
+        }
+
+        // view the axes in output order as a 6d tensor, padded with 1s at the front.
+        // like with permute, this only changes how the data is looked at.
+        let mut view_dims = [1; 6];
+        let mut view_strides = [1; 6];
+        let pad = 6 - axes.len();
+        for (i, name) in rhs.iter().flatten().enumerate() {
+            let j = axes
+                .iter()
+                .position(|(n, _)| n == name)
+                .unwrap_or_else(|| panic!("`{name}` is missing from the left of `{pattern}`"));
+            view_dims[pad + i] = axes[j].1;
+            view_strides[pad + i] = strides[j];
+        }
+
+        let mut dst: Dst::Concrete = Default::default();
+        assert_eq!(
+            Dst::NUM_DIMS,
+            rhs.len(),
+            "Pattern has {} output dimensions, but Dst has {}",
+            rhs.len(),
+            Dst::NUM_DIMS
+        );
+        for (i, group) in rhs.iter().enumerate() {
+            dst[i] = group
+                .iter()
+                .map(|name| axes.iter().find(|(n, _)| n == name).unwrap().1)
+                .product();
+        }
+        let dst = Dst::from_concrete(&dst)
+            .unwrap_or_else(|| panic!("Output sizes {dst:?} don't match the Dst shape"));
+
+        let shape = self.shape;
+        let t = self.try_reshape_like(&shape).unwrap()?;
+        let view: Tensor<[usize; 6], E, D, T> = Tensor {
+            id: t.id,
+            data: t.data,
+            shape: view_dims,
+            strides: view_strides,
+            device: t.device,
+            tape: t.tape,
+        };
+        view.try_reshape_like(&dst).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_rearrange_split() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<6, 4>, TestDtype, _> = dev.sample_normal();
+        let r: Tensor<Rank3<2, 3, 4>, TestDtype, _> =
+            rearrange!(t.clone(), "(b h) w -> b h w", h = 3);
+        assert_eq!(r.array(), t.reshape::<Rank3<2, 3, 4>>().array());
+    }
+
+    #[test]
+    fn test_rearrange_permute_and_merge() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let r: Tensor<Rank2<4, 6>, TestDtype, _, _> =
+            rearrange!(t.leaky_trace(), "b h w -> w (b h)");
+        let expected = t
+            .clone()
+            .permute::<Rank3<4, 2, 3>, _>()
+            .reshape::<Rank2<4, 6>>();
+        assert_eq!(r.array(), expected.array());
+
+        let w: Tensor<Rank2<4, 6>, TestDtype, _> = dev.sample_normal();
+        let g = (r * w.clone()).sum().backward();
+        let expected = w.reshape::<Rank3<4, 2, 3>>().permute::<Rank3<2, 3, 4>, _>();
+        assert_eq!(g.get(&t).array(), expected.array());
+    }
+
+    #[test]
+    fn test_rearrange_runtime_dims() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<(usize, Const<4>), TestDtype, _> = dev.zeros_like(&(6, Const));
+        let r: Tensor<(usize, usize, Const<4>), TestDtype, _> =
+            rearrange!(t, "(b h) w -> h b w", b = 2);
+        assert_eq!(r.shape(), &(3, 2, Const::<4>));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rearrange_wrong_dst() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<6, 4>, TestDtype, _> = dev.zeros();
+        let _: Tensor<Rank3<3, 2, 4>, TestDtype, _> = rearrange!(t, "(b h) w -> b h w", h = 3);
+    }
+}
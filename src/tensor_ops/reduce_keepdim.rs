@@ -0,0 +1,173 @@
+use crate::{
+    shapes::{Axis, Dtype, HasShape, KeepDimShape, ReduceShape, Shape},
+    tensor::{Tape, Tensor},
+};
+
+use super::{Device, MeanTo, ReshapeTo, VarTo};
+
+/// Reduces axis `AXIS` of `t` with `fold`, but keeps it around as a size 1 dimension
+/// instead of removing it. The result can be broadcast back to the shape of `t`.
+/// **Pytorch equivalent**: `t.sum(AXIS, keepdim=True)`, `t.mean(AXIS, keepdim=True)`, etc.
+///
+/// `fold` can be any reduction that removes `AXIS`, which lets this work with
+/// sum/mean/max/min/var/etc.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::reduce_axis_keepdim};
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [-4.0, 5.0, -6.0]]);
+/// let r: Tensor<Rank2<1, 3>, f32, _> = reduce_axis_keepdim::<0, _, _, _, _, _>(t, |t| t.max());
+/// assert_eq!(r.array(), [[1.0, 5.0, 3.0]]);
+/// ```
+pub fn reduce_axis_keepdim<
+    const AXIS: isize,
+    S: Shape + KeepDimShape<Axis<AXIS>>,
+    E: Dtype,
+    D: Device<E>,
+    T: Tape<E, D>,
+    F: FnOnce(Tensor<S, E, D, T>) -> Tensor<<S as ReduceShape<Axis<AXIS>>>::Reduced, E, D, T>,
+>(
+    t: Tensor<S, E, D, T>,
+    fold: F,
+) -> Tensor<S::KeptDim, E, D, T> {
+    t.reduce_axis_keepdim::<AXIS, _>(fold)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [reduce_axis_keepdim]
+    pub fn reduce_axis_keepdim<const AXIS: isize, F>(
+        self,
+        fold: F,
+    ) -> Tensor<<S as KeepDimShape<Axis<AXIS>>>::KeptDim, E, D, T>
+    where
+        S: KeepDimShape<Axis<AXIS>>,
+        F: FnOnce(Self) -> Tensor<<S as ReduceShape<Axis<AXIS>>>::Reduced, E, D, T>,
+    {
+        self.try_reduce_axis_keepdim::<AXIS, _>(|t| Ok(fold(t)))
+            .unwrap()
+    }
+
+    /// See [reduce_axis_keepdim]
+    #[allow(clippy::type_complexity)]
+    pub fn try_reduce_axis_keepdim<const AXIS: isize, F>(
+        self,
+        fold: F,
+    ) -> Result<Tensor<<S as KeepDimShape<Axis<AXIS>>>::KeptDim, E, D, T>, D::Err>
+    where
+        S: KeepDimShape<Axis<AXIS>>,
+        F: FnOnce(Self) -> Result<Tensor<<S as ReduceShape<Axis<AXIS>>>::Reduced, E, D, T>, D::Err>,
+    {
+        let kept = self.shape().keep_dim();
+        fold(self)?.try_reshape_like(&kept).unwrap()
+    }
+
+    /// Takes the mean of axis `AXIS`, keeping it as a size 1 dimension.
+    /// **Pytorch equivalent**: `t.mean(AXIS, keepdim=True)`
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [-1.0, -2.0, -3.0]]);
+    /// let r: Tensor<Rank2<2, 1>, f32, _> = t.mean_axis_keepdim::<1>();
+    /// assert_eq!(r.array(), [[2.0], [-2.0]]);
+    /// ```
+    pub fn mean_axis_keepdim<const AXIS: isize>(
+        self,
+    ) -> Tensor<<S as KeepDimShape<Axis<AXIS>>>::KeptDim, E, D, T>
+    where
+        S: KeepDimShape<Axis<AXIS>>,
+    {
+        self.try_mean_axis_keepdim::<AXIS>().unwrap()
+    }
+
+    /// See [Tensor::mean_axis_keepdim]
+    #[allow(clippy::type_complexity)]
+    pub fn try_mean_axis_keepdim<const AXIS: isize>(
+        self,
+    ) -> Result<Tensor<<S as KeepDimShape<Axis<AXIS>>>::KeptDim, E, D, T>, D::Err>
+    where
+        S: KeepDimShape<Axis<AXIS>>,
+    {
+        self.try_reduce_axis_keepdim::<AXIS, _>(|t| t.try_mean())
+    }
+
+    /// Takes the (biased) variance of axis `AXIS`, keeping it as a size 1 dimension.
+    /// **Pytorch equivalent**: `t.var(AXIS, unbiased=False, keepdim=True)`
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[1.0, 3.0], [0.0, 0.0]]);
+    /// let r: Tensor<Rank2<2, 1>, f32, _> = t.var_axis_keepdim::<1>();
+    /// assert_eq!(r.array(), [[1.0], [0.0]]);
+    /// ```
+    pub fn var_axis_keepdim<const AXIS: isize>(
+        self,
+    ) -> Tensor<<S as KeepDimShape<Axis<AXIS>>>::KeptDim, E, D, T>
+    where
+        S: KeepDimShape<Axis<AXIS>>,
+    {
+        self.try_var_axis_keepdim::<AXIS>().unwrap()
+    }
+
+    /// See [Tensor::var_axis_keepdim]
+    #[allow(clippy::type_complexity)]
+    pub fn try_var_axis_keepdim<const AXIS: isize>(
+        self,
+    ) -> Result<Tensor<<S as KeepDimShape<Axis<AXIS>>>::KeptDim, E, D, T>, D::Err>
+    where
+        S: KeepDimShape<Axis<AXIS>>,
+    {
+        self.try_reduce_axis_keepdim::<AXIS, _>(|t| t.try_var())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_reduce_axis_keepdim_broadcast_sub() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let r: Tensor<Rank3<2, 1, 4>, TestDtype, _, _> =
+            t.leaky_trace().reduce_axis_keepdim::<1, _>(|t| t.sum());
+        assert_eq!(r.shape(), &(Const::<2>, Const::<1>, Const::<4>));
+
+        let expected = t.clone().sum::<Rank2<2, 4>, _>().array();
+        let r_arr = r.array();
+        for i in 0..2 {
+            for k in 0..4 {
+                assert_eq!(r_arr[i][0][k], expected[i][k]);
+            }
+        }
+
+        // the kept dim broadcasts back to the input's shape
+        let centered = t.leaky_trace() - r.reshape::<Rank2<2, 4>>().broadcast::<_, Axis<1>>();
+        let t_arr = t.array();
+        let c_arr = centered.array();
+        for i in 0..2 {
+            for j in 0..3 {
+                for k in 0..4 {
+                    assert_eq!(c_arr[i][j][k], t_arr[i][j][k] - expected[i][k]);
+                }
+            }
+        }
+        // every element is subtracted once directly, and 3 times through the sum
+        let g = centered.sum().backward();
+        assert_close(&g.get(&t).array(), &[[[-2.0; 4]; 3]; 2]);
+    }
+
+    #[test]
+    fn test_mean_var_axis_keepdim() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 3, 4>, TestDtype, _> = dev.sample_normal();
+        let m: Tensor<Rank3<2, 1, 4>, TestDtype, _> = t.clone().mean_axis_keepdim::<1>();
+        let m2: Tensor<Rank2<2, 4>, TestDtype, _> = t.clone().mean();
+        assert_eq!(m.reshape::<Rank2<2, 4>>().array(), m2.array());
+
+        let v: Tensor<Rank3<1, 3, 4>, TestDtype, _> = t.clone().var_axis_keepdim::<0>();
+        let v2: Tensor<Rank2<3, 4>, TestDtype, _> = t.var();
+        assert_eq!(v.reshape::<Rank2<3, 4>>().array(), v2.array());
+    }
+}
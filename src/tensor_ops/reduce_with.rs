@@ -0,0 +1,83 @@
+use crate::{
+    shapes::{Dtype, ReduceShape, ReduceStridesTo, Shape},
+    tensor::Tensor,
+};
+
+use super::Device;
+
+impl<S: Shape, E: Dtype, D: Device<E>> Tensor<S, E, D> {
+    /// Reduces the last dimension with a custom reduction. Each output element starts
+    /// out as `init`, then `fold(acc, x)` is applied with every element `x` along the last
+    /// dimension, and the result is `finalize(acc)`.
+    ///
+    /// This runs on the host, and does not record a gradient since `fold` and `finalize`
+    /// are arbitrary closures. Prefer the builtin reductions if they can express what you need.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[3.0, 4.0], [-5.0, 12.0]]);
+    /// let l2 = t.reduce_last_dim_with(0.0, |acc, x| acc + x * x, f32::sqrt);
+    /// assert_eq!(l2.array(), [5.0, 13.0]);
+    /// ```
+    pub fn reduce_last_dim_with(
+        &self,
+        init: E,
+        fold: impl FnMut(E, E) -> E,
+        finalize: impl FnMut(E) -> E,
+    ) -> Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D> {
+        self.try_reduce_last_dim_with(init, fold, finalize).unwrap()
+    }
+
+    /// See [Tensor::reduce_last_dim_with]
+    #[allow(clippy::type_complexity)]
+    pub fn try_reduce_last_dim_with(
+        &self,
+        init: E,
+        mut fold: impl FnMut(E, E) -> E,
+        mut finalize: impl FnMut(E) -> E,
+    ) -> Result<Tensor<<S as ReduceShape<S::LastAxis>>::Reduced, E, D>, D::Err> {
+        let dst: <S as ReduceShape<S::LastAxis>>::Reduced =
+            <S as ReduceStridesTo<_, S::LastAxis>>::reduced(&self.shape);
+        let last = match S::NUM_DIMS {
+            0 => 1,
+            n => self.shape.concrete()[n - 1],
+        };
+        let out = if last == 0 {
+            vec![finalize(init); dst.num_elements()]
+        } else {
+            self.as_vec()
+                .chunks_exact(last)
+                .map(|row| finalize(row.iter().fold(init, |acc, &x| fold(acc, x))))
+                .collect()
+        };
+        self.device.try_tensor_from_vec(out, dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_reduce_last_dim_with_l2_norm() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank3<2, 2, 3>, TestDtype, _> = dev.sample_normal();
+        let r = t.reduce_last_dim_with(0.0, |acc, x| acc + x * x, |acc| acc.sqrt());
+        let expected = t.square().sum::<Rank2<2, 2>, _>().sqrt();
+        assert_close(&r.array(), &expected.array());
+    }
+
+    #[test]
+    fn test_reduce_last_dim_with_broadcasted() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, -2.0]);
+        let t: Tensor<Rank2<3, 2>, TestDtype, _> = t.broadcast();
+        let r = t.reduce_last_dim_with(TestDtype::NEG_INFINITY, TestDtype::max, |m| m * 2.0);
+        assert_eq!(r.array(), [2.0; 3]);
+
+        let empty: Tensor<(Const<2>, usize), TestDtype, _> = dev.zeros_like(&(Const, 0));
+        let r = empty.reduce_last_dim_with(1.0, |acc, x| acc * x, |acc| acc + 1.0);
+        assert_eq!(r.array(), [2.0; 2]);
+    }
+}
@@ -5,6 +5,8 @@ mod cuda_kernel;
 
 use crate::{shapes::*, tensor::*};
 
+use super::ops::profiled;
+
 pub trait ReshapeKernel<E: Dtype>: DeviceStorage {
     fn forward<Src: Shape, Dst: Shape>(
         &self,
@@ -62,6 +64,64 @@ pub trait ReshapeTo: HasErr + HasShape {
         <Self::Shape as AssertSameNumel<Dst>>::assert_same_numel();
         self.try_reshape_like::<Dst>(&Default::default()).unwrap()
     }
+    /// Views `self` as a 1d tensor. Shorthand for `self.reshape::<Rank1<M>>()`, so
+    /// no data is copied if `self` is contiguous.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = dev.zeros();
+    /// let t: Tensor<Rank1<6>, f32, _> = t.as_1d();
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn as_1d<const M: usize>(self) -> Self::WithShape<Rank1<M>>
+    where
+        Self::Shape: ConstShape,
+    {
+        self.reshape()
+    }
+    /// Views `self` as a 2d tensor. Shorthand for `self.reshape::<Rank2<M, N>>()`, so
+    /// no data is copied if `self` is contiguous.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank1<6>, f32, _> = dev.zeros();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = t.as_2d();
+    /// ```
+    ///
+    /// The number of elements must match:
+    /// ```compile_fail
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank1<6>, f32, _> = dev.zeros();
+    /// let t: Tensor<Rank2<2, 4>, f32, _> = t.as_2d();
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn as_2d<const M: usize, const N: usize>(self) -> Self::WithShape<Rank2<M, N>>
+    where
+        Self::Shape: ConstShape,
+    {
+        self.reshape()
+    }
+    /// Views `self` as a 3d tensor. Shorthand for `self.reshape::<Rank3<M, N, O>>()`, so
+    /// no data is copied if `self` is contiguous.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<6, 4>, f32, _> = dev.zeros();
+    /// let t: Tensor<Rank3<2, 3, 4>, f32, _> = t.as_3d();
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn as_3d<const M: usize, const N: usize, const O: usize>(
+        self,
+    ) -> Self::WithShape<Rank3<M, N, O>>
+    where
+        Self::Shape: ConstShape,
+    {
+        self.reshape()
+    }
     /// Reshapes a tensor to a different runtime shape.
     fn reshape_like<Dst: Shape>(self, dst: &Dst) -> Option<Self::WithShape<Dst>> {
         self.try_reshape_like(dst).map(Result::unwrap)
@@ -90,7 +150,7 @@ impl<S: Shape, E: Dtype, D: ReshapeKernel<E>, T: Tape<E, D>> ReshapeTo for Tenso
                 })
             } else {
                 let (inp, mut tape) = self.split_tape();
-                let out = inp.device.forward(dst, &inp)?;
+                let out = profiled(|| inp.device.forward(dst, &inp))?;
                 let phantom_out = out.clone();
                 tape.try_alloc_grad(&inp)?;
                 tape.try_alloc_grad(&out)?;
@@ -176,6 +236,32 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_as_2d_as_1d_roundtrip() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<_, TestDtype, _> = dev.tensor([0.1, 0.2, 0.3, 0.4, 0.5, 0.6]);
+        let b: Tensor<Rank2<2, 3>, _, _, _> = a.leaky_trace().as_2d();
+        assert!(std::sync::Arc::ptr_eq(&a.data, &b.data));
+        assert_eq!(b.array(), [[0.1, 0.2, 0.3], [0.4, 0.5, 0.6]]);
+        let c: Tensor<Rank1<6>, _, _, _> = b.as_1d();
+        assert!(std::sync::Arc::ptr_eq(&a.data, &c.data));
+        assert_eq!(c.array(), a.array());
+        let g = c.exp().mean().backward();
+        assert_close(
+            &g.get(&a).array(),
+            &[
+                0.18419516, 0.20356713, 0.22497648, 0.24863747, 0.2747869, 0.3036865,
+            ],
+        );
+
+        let d: Tensor<Rank3<1, 2, 3>, _, _, _> = a.leaky_trace().as_3d();
+        assert_eq!(d.array(), [[[0.1, 0.2, 0.3], [0.4, 0.5, 0.6]]]);
+        let g = (d.as_2d::<3, 2>() * dev.tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]))
+            .sum()
+            .backward();
+        assert_eq!(g.get(&a).array(), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+
     #[test]
     fn test_1d_reshape_non_contiguous() {
         let dev: TestDevice = Default::default();
@@ -0,0 +1,74 @@
+use crate::{
+    shapes::{Axes, Dtype, ReduceShape, Shape},
+    tensor::{HasErr, Tape, Tensor},
+};
+
+use super::{BroadcastTo, Device, MeanTo, TryAdd, TryDiv};
+
+/// Divides `t` by its root mean square along `Ax`, without subtracting the mean like
+/// [super::normalize()] does. `epsilon` is added to the mean square.
+/// Computes `t / sqrt(mean(t^2, Ax) + epsilon)`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<2>, f32, _> = dev.tensor([3.0, -4.0]);
+/// let r = t.rmsnorm::<Axis<0>>(0.0);
+/// assert_eq!(r.array(), [3.0 / 12.5f32.sqrt(), -4.0 / 12.5f32.sqrt()]);
+/// ```
+pub fn rmsnorm<Ax: Axes, S: Shape + ReduceShape<Ax>, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    epsilon: E,
+) -> Tensor<S, E, D, T> {
+    t.rmsnorm::<Ax>(epsilon)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [rmsnorm]
+    pub fn rmsnorm<Ax: Axes>(self, epsilon: E) -> Self
+    where
+        S: ReduceShape<Ax>,
+    {
+        self.try_rmsnorm::<Ax>(epsilon).unwrap()
+    }
+
+    /// See [rmsnorm]
+    pub fn try_rmsnorm<Ax: Axes>(self, epsilon: E) -> Result<Self, <Self as HasErr>::Err>
+    where
+        S: ReduceShape<Ax>,
+    {
+        let shape = self.shape;
+        let rms = self
+            .retaped::<T>()
+            .try_square()?
+            .try_mean::<_, Ax>()?
+            .try_add(epsilon)?
+            .try_sqrt()?;
+        self.try_div(rms.try_broadcast_like(&shape)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_rmsnorm_last_axis() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[1.0, -1.0], [3.0, 4.0]]);
+        let r = t.leaky_trace().rmsnorm::<Axis<1>>(0.0);
+        let s = 12.5f64.sqrt() as TestDtype;
+        assert_close(&r.array(), &[[1.0, -1.0], [3.0 / s, 4.0 / s]]);
+
+        // d/dx_i sum_j x_j / rms = (1 - x_i * mean(x) / rms^2) / rms
+        let g = r.sum().backward();
+        let (m, ms) = (3.5, 12.5);
+        assert_close(
+            &g.get(&t).array(),
+            &[
+                [1.0, 1.0],
+                [(1.0 - 3.0 * m / ms) / s, (1.0 - 4.0 * m / ms) / s],
+            ],
+        );
+    }
+}
@@ -3,6 +3,8 @@ use crate::{
     tensor::*,
 };
 
+use super::ops::profiled;
+
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
@@ -72,7 +74,7 @@ impl<S: Shape, E: Dtype, D: RollKernel<E>, T: Tape<E, D>> Roll for Tensor<S, E,
             amount,
         };
         let (t, mut tape) = self.split_tape();
-        let out = t.device.forward(op, &t)?;
+        let out = profiled(|| t.device.forward(op, &t))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&t)?;
         tape.try_alloc_grad(&out)?;
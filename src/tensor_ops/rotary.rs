@@ -0,0 +1,103 @@
+use num_traits::Float;
+
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Tape, Tensor},
+};
+
+use super::{Device, GatherTo, TryAdd, TryMul};
+
+/// Rotary positional embeddings (RoPE) from
+/// [RoFormer: Enhanced Transformer with Rotary Position Embedding](https://arxiv.org/abs/2104.09864).
+///
+/// Each pair of features `(2i, 2i + 1)` of row `s` of `q` is rotated by the angle
+/// `positions[s] * 10000^(-2i / H)`.
+///
+/// **Panics** if `H` is odd, or `positions` doesn't have one entry per row.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::apply_rotary};
+/// # let dev: Cpu = Default::default();
+/// let q: Tensor<Rank2<2, 2>, f32, _> = dev.tensor([[1.0, 0.0], [1.0, 0.0]]);
+/// let r = apply_rotary(q, &[0, 1]);
+/// assert_eq!(r.array(), [[1.0, 0.0], [1.0f32.cos(), 1.0f32.sin()]]);
+/// ```
+pub fn apply_rotary<S: Dim, H: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    q: Tensor<(S, H), E, D, T>,
+    positions: &[usize],
+) -> Tensor<(S, H), E, D, T> {
+    q.rotary_embedding(positions)
+}
+
+impl<S: Dim, H: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>> Tensor<(S, H), E, D, T> {
+    /// See [apply_rotary]
+    pub fn rotary_embedding(self, positions: &[usize]) -> Self {
+        self.try_rotary_embedding(positions).unwrap()
+    }
+
+    /// See [apply_rotary]
+    pub fn try_rotary_embedding(self, positions: &[usize]) -> Result<Self, D::Err> {
+        let (s, h) = self.shape;
+        assert_eq!(h.size() % 2, 0, "RoPE needs an even number of features");
+        assert_eq!(positions.len(), s.size(), "expected one position per row");
+
+        let base = E::from(10000.0).unwrap();
+        let num = s.size() * h.size();
+        let mut cos = vec![E::default(); num];
+        let mut sin = vec![E::default(); num];
+        let mut swap = vec![0; num];
+        for (row, &pos) in positions.iter().enumerate() {
+            for i in 0..h.size() / 2 {
+                let freq =
+                    base.powf(-E::from_usize(2 * i).unwrap() / E::from_usize(h.size()).unwrap());
+                let angle = E::from_usize(pos).unwrap() * freq;
+                let j = row * h.size() + 2 * i;
+                cos[j] = angle.cos();
+                cos[j + 1] = angle.cos();
+                sin[j] = -angle.sin();
+                sin[j + 1] = angle.sin();
+                swap[j] = 2 * i + 1;
+                swap[j + 1] = 2 * i;
+            }
+        }
+        let cos = self.device.try_tensor_from_vec(cos, self.shape)?;
+        let sin = self.device.try_tensor_from_vec(sin, self.shape)?;
+        let swap = self.device.try_tensor_from_vec(swap, self.shape)?;
+
+        // (x0, x1) -> (x0 * cos - x1 * sin, x1 * cos + x0 * sin)
+        let swapped = self.retaped::<T>().try_gather(swap)?;
+        self.try_mul(cos)?.try_add(swapped.try_mul(sin)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_rotary_position_0_is_identity() {
+        let dev: TestDevice = Default::default();
+        let q: Tensor<Rank2<3, 4>, TestDtype, _> = dev.sample_normal();
+        let r = q.clone().rotary_embedding(&[0, 0, 0]);
+        assert_close(&r.array(), &q.array());
+    }
+
+    #[test]
+    fn test_rotary_preserves_norms() {
+        let dev: TestDevice = Default::default();
+        let q: Tensor<Rank2<3, 6>, TestDtype, _> = dev.sample_normal();
+        let r = q.leaky_trace().rotary_embedding(&[1, 5, 17]);
+        assert_close_with_tolerance(
+            &r.retaped::<NoneTape>()
+                .square()
+                .sum::<Rank1<3>, _>()
+                .array(),
+            &q.clone().square().sum::<Rank1<3>, _>().array(),
+            1e-5,
+        );
+
+        // the rotation is orthogonal, so d/dq |Rq|^2 = 2 R^T R q = 2q
+        let g = r.square().sum().backward();
+        assert_close_with_tolerance(&g.get(&q).array(), &(q.clone() * 2.0).array(), 1e-5);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    shapes::{Dim, Dtype, Shape},
+    tensor::{Tensor, TensorFromVec},
+};
+
+use num_traits::Float;
+use rand::Rng;
+use rand_distr::{Distribution, Standard};
+
+/// Samples `1` with probability `probs[i]` and `0` otherwise, independently for each element.
+/// The result is not differentiable, so any tape on `probs` is ignored.
+///
+/// **Pytorch equivalent**: `torch.bernoulli(probs)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// # let dev: Cpu = Default::default();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let probs = dev.tensor([0.0f32, 1.0, 0.0, 1.0]);
+/// let r = bernoulli(&probs, &mut rng);
+/// assert_eq!(r.array(), [0.0, 1.0, 0.0, 1.0]);
+/// ```
+pub fn bernoulli<S: Shape, E: Dtype + Float, D: TensorFromVec<E>, T, R: Rng>(
+    probs: &Tensor<S, E, D, T>,
+    rng: &mut R,
+) -> Tensor<S, E, D>
+where
+    Standard: Distribution<E>,
+{
+    let samples = probs
+        .as_vec()
+        .into_iter()
+        .map(|p| {
+            let val: E = rng.sample(Standard);
+            if val < p {
+                E::one()
+            } else {
+                E::zero()
+            }
+        })
+        .collect();
+    probs.device.tensor_from_vec(samples, probs.shape)
+}
+
+/// Draws `num_samples` indices (with replacement) from the categorical distribution
+/// given by `probs`. `probs` does not need to sum to 1, but must be non-negative,
+/// and indices with a probability of zero are never returned.
+///
+/// **Pytorch equivalent**: `torch.multinomial(probs, num_samples, replacement=True)`
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// # let dev: Cpu = Default::default();
+/// let mut rng = StdRng::seed_from_u64(0);
+/// let probs = dev.tensor([0.0f32, 0.0, 1.0]);
+/// assert_eq!(multinomial(&probs, 3, &mut rng), [2, 2, 2]);
+/// ```
+pub fn multinomial<N: Dim, E: Dtype + Float, D: TensorFromVec<E>, T, R: Rng>(
+    probs: &Tensor<(N,), E, D, T>,
+    num_samples: usize,
+    rng: &mut R,
+) -> std::vec::Vec<usize>
+where
+    Standard: Distribution<E>,
+{
+    let mut cumulative = probs.as_vec();
+    let mut total = E::zero();
+    for p in cumulative.iter_mut() {
+        assert!(
+            *p >= E::zero(),
+            "multinomial probabilities must be non-negative"
+        );
+        total += *p;
+        *p = total;
+    }
+    assert!(total > E::zero(), "multinomial probabilities sum to 0");
+    // rounding can put `val * total` on the very end of the range, so fall back
+    // to the last index that has any probability mass.
+    let last = cumulative.iter().position(|&c| c == total).unwrap();
+
+    (0..num_samples)
+        .map(|_| {
+            let val: E = rng.sample(Standard);
+            let val = val * total;
+            cumulative.iter().position(|&c| val < c).unwrap_or(last)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tests::*};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_bernoulli_all_ones() {
+        let dev: TestDevice = Default::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        let probs: Tensor<Rank2<4, 8>, TestDtype, _> = dev.ones();
+        let r = bernoulli(&probs, &mut rng);
+        assert_eq!(r.array(), [[1.0; 8]; 4]);
+        let probs: Tensor<Rank2<4, 8>, TestDtype, _> = dev.zeros();
+        let r = bernoulli(&probs, &mut rng);
+        assert_eq!(r.array(), [[0.0; 8]; 4]);
+    }
+
+    #[test]
+    fn test_bernoulli_mean() {
+        let dev: TestDevice = Default::default();
+        let mut rng = StdRng::seed_from_u64(1);
+        let probs: Tensor<Rank1<1000>, TestDtype, _> = dev.ones() * 0.25;
+        let r = bernoulli(&probs, &mut rng);
+        let mean = r.as_vec().into_iter().sum::<TestDtype>() / 1000.0;
+        assert!((mean - 0.25).abs() < 0.05, "{mean}");
+    }
+
+    #[test]
+    fn test_multinomial_nonzero_only() {
+        let dev: TestDevice = Default::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        let probs: Tensor<Rank1<5>, TestDtype, _> = dev.tensor([0.0, 0.3, 0.0, 0.7, 0.0]);
+        let samples = multinomial(&probs, 1000, &mut rng);
+        assert_eq!(samples.len(), 1000);
+        assert!(samples.iter().all(|&i| i == 1 || i == 3));
+        let num_3 = samples.iter().filter(|&&i| i == 3).count();
+        assert!((600..800).contains(&num_3), "{num_3}");
+    }
+}
@@ -7,6 +7,8 @@ mod cuda_kernel;
 
 use crate::{shapes::*, tensor::*};
 
+use super::ops::profiled;
+
 pub trait ReplaceDimKernel<E: Dtype>: DeviceStorage {
     fn forward<Src: Shape, Dst: Shape, Idx: Shape>(
         &self,
@@ -103,7 +105,7 @@ impl<Src: Shape, E: Dtype, D: RemoveDimKernel<E>, T: Tape<E, D>> SelectTo<D>
     {
         self.shape().check(idx.shape());
         let (inp, mut tape) = self.split_tape();
-        let out = inp.device.forward(&inp, &idx)?;
+        let out = profiled(|| inp.device.forward(&inp, &idx))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&inp)?;
         tape.try_alloc_grad(&out)?;
@@ -173,7 +175,7 @@ impl<Src: Shape, E: Dtype, D: ReplaceDimKernel<E>, T: Tape<E, D>> GatherTo<D>
     {
         self.shape().check(idx.shape());
         let (inp, mut tape) = self.split_tape();
-        let out = inp.device.forward(&inp, &idx)?;
+        let out = profiled(|| inp.device.forward(&inp, &idx))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&inp)?;
         tape.try_alloc_grad(&out)?;
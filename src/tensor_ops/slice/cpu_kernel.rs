@@ -2,7 +2,29 @@ use crate::prelude::cpu::{LendingIterator, NdIndex};
 
 use super::*;
 
-impl<E: Unit> SliceKernel<E> for Cpu {
+mod sealed {
+    use crate::shapes::Dtype;
+
+    /// `+=` for gradients of any [crate::shapes::Unit]. `bool` has no meaningful
+    /// gradients, so accumulating them is a logical or.
+    pub trait AccumulateGrad: Copy {
+        fn accumulate(&mut self, other: Self);
+    }
+
+    impl<E: Dtype> AccumulateGrad for E {
+        fn accumulate(&mut self, other: Self) {
+            *self += other;
+        }
+    }
+
+    impl AccumulateGrad for bool {
+        fn accumulate(&mut self, other: Self) {
+            *self |= other;
+        }
+    }
+}
+
+impl<E: Unit + sealed::AccumulateGrad> SliceKernel<E> for Cpu {
     fn forward<Src: Shape + SliceShape<Slice>, Slice>(
         &self,
         inp: &Tensor<Src, E, Self>,
@@ -42,7 +64,7 @@ impl<E: Unit> SliceKernel<E> for Cpu {
         let view = &mut grad_inp[start_idx..];
 
         while let Some((inp_i, o)) = inp_idx.next().zip(out_iter.next()) {
-            view[inp_i] = *o;
+            view[inp_i].accumulate(*o);
         }
 
         Ok(())
@@ -1,5 +1,7 @@
 use crate::{shapes::*, tensor::*};
 
+use super::ops::profiled;
+
 mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
@@ -59,7 +61,7 @@ impl<S: Shape, E: Unit, D: SliceKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
         Slice: 'static,
     {
         let (inp, mut tape) = self.split_tape();
-        let out = inp.device.forward(&inp, &slice)?;
+        let out = profiled(|| inp.device.forward(&inp, &slice))?;
         let phantom_out = out.clone();
 
         tape.try_alloc_grad(&inp)?;
@@ -85,7 +87,7 @@ impl<S: Shape, E: Unit, D: SliceKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
 mod tests {
     use super::*;
     use crate::tensor_ops::*;
-    use crate::tests::TestDevice;
+    use crate::tests::{TestDevice, TestDtype};
 
     #[test]
     fn test_slice() {
@@ -176,4 +178,31 @@ mod tests {
             [[0.; 4], [0.; 4], [0., 0., 22., 24.], [0., 0., 30., 32.]]
         );
     }
+
+    #[test]
+    fn test_overlapping_slices_backward() {
+        let dev = TestDevice::default();
+        let a: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([1., 2., 3., 4.]);
+        let l = a.leaky_trace().slice((..3,));
+        let r = a.leaky_trace().slice((1..,));
+        let g = (r - l).sum().backward();
+        assert_eq!(g.get(&a).array(), [-1., 0., 0., 1.]);
+    }
+
+    #[test]
+    fn test_slice_broadcast_backward() {
+        let dev = TestDevice::default();
+        let a: Tensor<Rank1<4>, TestDtype, _> = dev.tensor([1., 2., 3., 4.]);
+        let b: Tensor<Rank2<3, 4>, _, _, _> = a.leaky_trace().broadcast();
+        let g = b.slice((1.., 1..3)).sum().backward();
+        assert_eq!(g.get(&a).array(), [0., 2., 2., 0.]);
+    }
+
+    #[test]
+    fn test_slice_bool() {
+        let dev: crate::tensor::Cpu = Default::default();
+        let a = dev.tensor([[true, false], [false, true]]);
+        let b = a.slice((1.., ..));
+        assert_eq!(b.as_vec(), [false, true]);
+    }
 }
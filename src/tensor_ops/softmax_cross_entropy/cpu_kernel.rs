@@ -0,0 +1,61 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{unique_id, Cpu, Tensor},
+};
+use num_traits::Float;
+
+use std::sync::Arc;
+
+impl<E: Dtype + Float> super::SoftmaxCrossEntropyKernel<E> for Cpu {
+    fn forward<S: Shape>(
+        &self,
+        num_rows: usize,
+        logits: &Tensor<S, E, Self>,
+        target_probs: &Tensor<S, E, Self>,
+    ) -> Result<(Tensor<(usize,), E, Self>, Tensor<(usize,), E, Self>), Self::Err> {
+        let num_classes = logits.data.len() / num_rows;
+        let mut losses = self.try_alloc_zeros::<E>(num_rows)?;
+        let mut logsumexp = self.try_alloc_zeros::<E>(num_rows)?;
+        let rows = logits.data.chunks_exact(num_classes);
+        let targets = target_probs.data.chunks_exact(num_classes);
+        for (i, (x, t)) in rows.zip(targets).enumerate() {
+            let max = x.iter().fold(E::neg_infinity(), |m, &v| m.max(v));
+            let lse = max + x.iter().fold(E::zero(), |s, &v| s + (v - max).exp()).ln();
+            losses[i] = x.iter().zip(t).fold(E::zero(), |l, (&v, &p)| l + p * (lse - v));
+            logsumexp[i] = lse;
+        }
+        let vector = |data| Tensor {
+            id: unique_id(),
+            data: Arc::new(data),
+            shape: (num_rows,),
+            strides: [1],
+            device: self.clone(),
+            tape: Default::default(),
+        };
+        Ok((vector(losses), vector(logsumexp)))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        num_rows: usize,
+        logits: &Tensor<S, E, Self>,
+        target_probs: &Tensor<S, E, Self>,
+        logsumexp: &Tensor<(usize,), E, Self>,
+        scale: E,
+        grad_logits: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let num_classes = logits.data.len() / num_rows;
+        let scale = grad_out[0] * scale;
+        let rows = logits.data.chunks_exact(num_classes);
+        let targets = target_probs.data.chunks_exact(num_classes);
+        let grads = grad_logits.chunks_exact_mut(num_classes);
+        for (((x, t), g), &lse) in rows.zip(targets).zip(grads).zip(logsumexp.data.iter()) {
+            let t_sum = t.iter().fold(E::zero(), |s, &p| s + p);
+            for ((g, &v), &p) in g.iter_mut().zip(x).zip(t) {
+                *g += scale * ((v - lse).exp() * t_sum - p);
+            }
+        }
+        Ok(())
+    }
+}
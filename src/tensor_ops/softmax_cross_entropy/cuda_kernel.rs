@@ -0,0 +1,85 @@
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{launch_cfg, Cuda, Tensor},
+};
+
+use cudarc::driver::LaunchAsync;
+
+const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/softmax_cross_entropy.ptx"));
+
+trait HasCudaKernel<E> {
+    const FNS: &'static [&'static str];
+}
+impl HasCudaKernel<f32> for Cuda {
+    const FNS: &'static [&'static str] = &[
+        "softmax_cross_entropy_fwd_f32",
+        "softmax_cross_entropy_bwd_f32",
+    ];
+}
+impl HasCudaKernel<f64> for Cuda {
+    const FNS: &'static [&'static str] = &[
+        "softmax_cross_entropy_fwd_f64",
+        "softmax_cross_entropy_bwd_f64",
+    ];
+}
+
+impl<E: Dtype> super::SoftmaxCrossEntropyKernel<E> for Cuda
+where
+    Self: HasCudaKernel<E>,
+{
+    fn forward<S: Shape>(
+        &self,
+        num_rows: usize,
+        logits: &Tensor<S, E, Self>,
+        target_probs: &Tensor<S, E, Self>,
+    ) -> Result<(Tensor<(usize,), E, Self>, Tensor<(usize,), E, Self>), Self::Err> {
+        if !self.dev.has_func(Self::FNS[0], Self::FNS[0]) {
+            self.dev.load_ptx(PTX_SRC.into(), Self::FNS[0], Self::FNS)?;
+        }
+        let num_classes = logits.data.len() / num_rows;
+        let mut losses = unsafe { self.dev.alloc::<E>(num_rows) }?;
+        let mut logsumexp = unsafe { self.dev.alloc::<E>(num_rows) }?;
+        let fwd = self.dev.get_func(Self::FNS[0], Self::FNS[0]).unwrap();
+        let cfg = launch_cfg(num_rows as u32);
+        let params = (
+            num_rows,
+            num_classes,
+            logits.data.as_ref(),
+            target_probs.data.as_ref(),
+            &mut losses,
+            &mut logsumexp,
+        );
+        unsafe { fwd.launch(cfg, params) }?;
+        Ok((
+            self.build_tensor((num_rows,), [1], losses),
+            self.build_tensor((num_rows,), [1], logsumexp),
+        ))
+    }
+
+    fn backward<S: Shape>(
+        &self,
+        num_rows: usize,
+        logits: &Tensor<S, E, Self>,
+        target_probs: &Tensor<S, E, Self>,
+        logsumexp: &Tensor<(usize,), E, Self>,
+        scale: E,
+        grad_logits: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let num_classes = logits.data.len() / num_rows;
+        let bwd = self.dev.get_func(Self::FNS[0], Self::FNS[1]).unwrap();
+        let cfg = launch_cfg(num_rows as u32);
+        let params = (
+            num_rows,
+            num_classes,
+            logits.data.as_ref(),
+            target_probs.data.as_ref(),
+            logsumexp.data.as_ref(),
+            scale,
+            grad_logits,
+            grad_out,
+        );
+        unsafe { bwd.launch(cfg, params) }?;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{DeviceStorage, Tensor},
+};
+
+/// Row wise `-sum(target_probs * log_softmax(logits))` over the last dimension, used by
+/// [crate::losses::softmax_cross_entropy].
+///
+/// Both `logits` and `target_probs` must be contiguous, and are split into `num_rows`
+/// rows of `num_classes` elements.
+pub trait SoftmaxCrossEntropyKernel<E: Dtype>: DeviceStorage {
+    /// Returns the loss of each row, and the `logsumexp` of each row, which is all
+    /// backward needs besides the inputs.
+    #[allow(clippy::type_complexity)]
+    fn forward<S: Shape>(
+        &self,
+        num_rows: usize,
+        logits: &Tensor<S, E, Self>,
+        target_probs: &Tensor<S, E, Self>,
+    ) -> Result<(Tensor<(usize,), E, Self>, Tensor<(usize,), E, Self>), Self::Err>;
+
+    /// Adds `grad_out[0] * scale * (softmax(logits) * sum(target_probs) - target_probs)`
+    /// to `grad_logits`, row by row.
+    #[allow(clippy::too_many_arguments)]
+    fn backward<S: Shape>(
+        &self,
+        num_rows: usize,
+        logits: &Tensor<S, E, Self>,
+        target_probs: &Tensor<S, E, Self>,
+        logsumexp: &Tensor<(usize,), E, Self>,
+        scale: E,
+        grad_logits: &mut Self::Vec<E>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err>;
+}
@@ -0,0 +1,93 @@
+#include "cuda_utils.cuh"
+
+// One thread per row of `num_classes` elements.
+template<typename T>
+__device__ void softmax_cross_entropy_fwd(
+    const size_t num_rows,
+    const size_t num_classes,
+    const T *logits,
+    const T *target_probs,
+    T *losses,
+    T *logsumexp
+) {
+    unsigned int row = blockIdx.x * blockDim.x + threadIdx.x;
+    if (row >= num_rows) {
+        return;
+    }
+    const T *x = logits + row * num_classes;
+    const T *t = target_probs + row * num_classes;
+
+    T max = -INFINITY;
+    for (size_t i = 0; i < num_classes; i++) {
+        max = maxg(max, x[i]);
+    }
+    T sum = 0.0;
+    for (size_t i = 0; i < num_classes; i++) {
+        sum += expg(x[i] - max);
+    }
+    T lse = max + logg(sum);
+
+    T loss = 0.0;
+    for (size_t i = 0; i < num_classes; i++) {
+        loss += t[i] * (lse - x[i]);
+    }
+    losses[row] = loss;
+    logsumexp[row] = lse;
+}
+
+template<typename T>
+__device__ void softmax_cross_entropy_bwd(
+    const size_t num_rows,
+    const size_t num_classes,
+    const T *logits,
+    const T *target_probs,
+    const T *logsumexp,
+    const T scale,
+    T *grad_logits,
+    const T *grad_out
+) {
+    unsigned int row = blockIdx.x * blockDim.x + threadIdx.x;
+    if (row >= num_rows) {
+        return;
+    }
+    const T *x = logits + row * num_classes;
+    const T *t = target_probs + row * num_classes;
+    T *g = grad_logits + row * num_classes;
+    const T lse = logsumexp[row];
+    const T s = grad_out[0] * scale;
+
+    T t_sum = 0.0;
+    for (size_t i = 0; i < num_classes; i++) {
+        t_sum += t[i];
+    }
+    for (size_t i = 0; i < num_classes; i++) {
+        g[i] += s * (expg(x[i] - lse) * t_sum - t[i]);
+    }
+}
+
+#define SOFTMAX_CROSS_ENTROPY(TYPENAME, FWD, BWD) \
+extern "C" __global__ void FWD( \
+    const size_t num_rows, \
+    const size_t num_classes, \
+    const TYPENAME *logits, \
+    const TYPENAME *target_probs, \
+    TYPENAME *losses, \
+    TYPENAME *logsumexp \
+) { \
+    softmax_cross_entropy_fwd(num_rows, num_classes, logits, target_probs, losses, logsumexp); \
+} \
+extern "C" __global__ void BWD( \
+    const size_t num_rows, \
+    const size_t num_classes, \
+    const TYPENAME *logits, \
+    const TYPENAME *target_probs, \
+    const TYPENAME *logsumexp, \
+    const TYPENAME scale, \
+    TYPENAME *grad_logits, \
+    const TYPENAME *grad_out \
+) { \
+    softmax_cross_entropy_bwd(num_rows, num_classes, logits, target_probs, logsumexp, scale, grad_logits, grad_out); \
+}
+
+SOFTMAX_CROSS_ENTROPY(float, softmax_cross_entropy_fwd_f32, softmax_cross_entropy_bwd_f32);
+SOFTMAX_CROSS_ENTROPY(double, softmax_cross_entropy_fwd_f64, softmax_cross_entropy_bwd_f64);
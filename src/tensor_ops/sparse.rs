@@ -0,0 +1,201 @@
+use std::vec::Vec;
+
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Tape, Tensor},
+};
+
+use super::{ops::try_host_unary_op, Device};
+
+/// A sparse `(M, K)` matrix in [compressed sparse row](https://en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_row_(CSR,_CRS_or_Yale_format))
+/// format, for [sparse_matmul()]. It lives on the host, and its values are constants.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::SparseTensor2D};
+/// // [[1, 0, 0],
+/// //  [0, 0, 2]]
+/// let s = SparseTensor2D::new((Const::<2>, Const::<3>), vec![0, 1, 2], vec![0, 2], vec![1.0f32, 2.0]);
+/// assert_eq!(s.nnz(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseTensor2D<M: Dim, K: Dim, E> {
+    shape: (M, K),
+    row_ptr: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<E>,
+}
+
+impl<M: Dim, K: Dim, E: Dtype> SparseTensor2D<M, K, E> {
+    /// The values of row `i` are `values[row_ptr[i]..row_ptr[i + 1]]`, in the columns
+    /// `col_indices[row_ptr[i]..row_ptr[i + 1]]`.
+    ///
+    /// **Panics** if `row_ptr` doesn't have `M + 1` non decreasing offsets from 0 to the number
+    /// of values, or a column index is out of bounds.
+    pub fn new(
+        shape: (M, K),
+        row_ptr: Vec<usize>,
+        col_indices: Vec<usize>,
+        values: Vec<E>,
+    ) -> Self {
+        assert_eq!(
+            row_ptr.len(),
+            shape.0.size() + 1,
+            "expected M + 1 row offsets"
+        );
+        assert_eq!(row_ptr[0], 0, "row offsets must start at 0");
+        assert!(
+            row_ptr.windows(2).all(|w| w[0] <= w[1]),
+            "row offsets must be non decreasing"
+        );
+        assert_eq!(col_indices.len(), values.len());
+        assert_eq!(row_ptr[shape.0.size()], values.len());
+        assert!(
+            col_indices.iter().all(|&c| c < shape.1.size()),
+            "column index out of bounds"
+        );
+        Self {
+            shape,
+            row_ptr,
+            col_indices,
+            values,
+        }
+    }
+
+    /// The non zero elements of `dense`.
+    pub fn from_dense<D: Device<E>, T>(dense: &Tensor<(M, K), E, D, T>) -> Self {
+        let (m, k) = dense.shape;
+        let data = dense.as_vec();
+        let mut row_ptr = Vec::with_capacity(m.size() + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+        row_ptr.push(0);
+        for row in data.chunks(k.size().max(1)).take(m.size()) {
+            for (c, &v) in row.iter().enumerate() {
+                if v != E::default() {
+                    col_indices.push(c);
+                    values.push(v);
+                }
+            }
+            row_ptr.push(values.len());
+        }
+        Self::new(dense.shape, row_ptr, col_indices, values)
+    }
+
+    pub fn shape(&self) -> &(M, K) {
+        &self.shape
+    }
+
+    /// The number of stored elements.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// `(row, col, value)` of each stored element.
+    fn entries(&self) -> impl Iterator<Item = (usize, usize, E)> + '_ {
+        (0..self.shape.0.size()).flat_map(move |r| {
+            (self.row_ptr[r]..self.row_ptr[r + 1])
+                .map(move |i| (r, self.col_indices[i], self.values[i]))
+        })
+    }
+}
+
+/// Multiplies the sparse `(M, K)` matrix `sparse` with the `(K, N)` matrix `dense`, in
+/// `O(nnz * N)`.
+///
+/// Only `dense` gets a gradient, which is `sparse^T * grad_out`.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::{sparse_matmul, SparseTensor2D}};
+/// # let dev: Cpu = Default::default();
+/// let a: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 0.0, 0.0], [0.0, 0.0, 2.0]]);
+/// let b: Tensor<Rank2<3, 2>, f32, _> = dev.tensor([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+/// let r = sparse_matmul(&SparseTensor2D::from_dense(&a), b);
+/// assert_eq!(r.array(), [[1.0, 2.0], [10.0, 12.0]]);
+/// ```
+pub fn sparse_matmul<M: Dim, K: Dim, N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    sparse: &SparseTensor2D<M, K, E>,
+    dense: Tensor<(K, N), E, D, T>,
+) -> Tensor<(M, N), E, D, T> {
+    try_sparse_matmul(sparse, dense).unwrap()
+}
+
+/// See [sparse_matmul()]
+#[allow(clippy::type_complexity)]
+pub fn try_sparse_matmul<M: Dim, K: Dim, N: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    sparse: &SparseTensor2D<M, K, E>,
+    dense: Tensor<(K, N), E, D, T>,
+) -> Result<Tensor<(M, N), E, D, T>, D::Err> {
+    let (k, n) = dense.shape;
+    assert_eq!(
+        sparse.shape.1.size(),
+        k.size(),
+        "inner dimensions must match"
+    );
+    let m = sparse.shape.0;
+    let n_size = n.size();
+    let entries: Vec<(usize, usize, E)> = sparse.entries().collect();
+    let fwd_entries = entries.clone();
+    try_host_unary_op(
+        dense,
+        (m, n),
+        |dense| {
+            let mut out = vec![E::default(); m.size() * n_size];
+            for (r, c, v) in fwd_entries {
+                for j in 0..n_size {
+                    out[r * n_size + j] += v * dense[c * n_size + j];
+                }
+            }
+            out
+        },
+        move |dense, _, grad_out| {
+            let mut grad = vec![E::default(); dense.len()];
+            for (r, c, v) in entries {
+                for j in 0..n_size {
+                    grad[c * n_size + j] += v * grad_out[r * n_size + j];
+                }
+            }
+            grad
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_sparse_matmul_matches_dense() {
+        let dev: TestDevice = Default::default();
+        let a: Tensor<Rank2<3, 4>, TestDtype, _> = dev.tensor([
+            [0.0, 2.0, 0.0, -1.0],
+            [0.0, 0.0, 0.0, 0.0],
+            [3.0, 0.0, 0.5, 0.0],
+        ]);
+        let sparse = SparseTensor2D::from_dense(&a);
+        assert_eq!(sparse.nnz(), 4);
+        assert_eq!(sparse.row_ptr, [0, 2, 2, 4]);
+        assert_eq!(sparse.col_indices, [1, 3, 0, 2]);
+
+        let b: Tensor<Rank2<4, 2>, TestDtype, _> = dev.sample_normal();
+        let r = sparse_matmul(&sparse, b.leaky_trace());
+        assert_close(&r.array(), &a.clone().matmul(b.clone()).array());
+
+        let w: Tensor<Rank2<3, 2>, TestDtype, _> = dev.sample_normal();
+        let g = (r * w.clone()).sum::<Rank0, _>().backward();
+        // the gradient of a dense matmul is a^T * grad_out
+        let expected = a.permute::<Rank2<4, 3>, _>().matmul(w);
+        assert_close(&g.get(&b).array(), &expected.array());
+    }
+
+    #[test]
+    #[should_panic = "column index out of bounds"]
+    fn test_sparse_out_of_bounds() {
+        SparseTensor2D::<_, _, TestDtype>::new(
+            (Const::<1>, Const::<2>),
+            std::vec![0, 1],
+            std::vec![2],
+            std::vec![1.0],
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{shapes::*, tensor::*};
 
+use super::ops::profiled;
+
 use std::vec::Vec;
 
 mod cpu_kernel;
@@ -154,7 +156,7 @@ where
     }
 
     // we map to storage refs so kernels don't have to know about tensors
-    let out = device.forward(new_dim, &tensors)?;
+    let out = profiled(|| device.forward(new_dim, &tensors))?;
 
     let phantom_out = out.clone();
     tape.try_alloc_grad(&out)?;
@@ -166,6 +168,27 @@ where
     Ok(out.put_tape(tape))
 }
 
+/// Stacks rows into a 2d tensor with a runtime number of rows. Intended for building
+/// datasets, so no tape is involved and the rows are borrowed instead of consumed.
+///
+/// Each row is copied into the output as a single contiguous block.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let rows: Vec<Tensor<Rank1<3>, f32, _>> = vec![dev.zeros(), dev.ones()];
+/// let t: Tensor<(usize, Const<3>), f32, _> = vstack(&rows);
+/// assert_eq!(t.as_vec(), [0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+/// ```
+///
+/// Panics if `rows` is empty.
+pub fn vstack<const N: usize, E: Dtype, D: StackKernel<E>>(
+    rows: &[Tensor<Rank1<N>, E, D>],
+) -> Tensor<(usize, Const<N>), E, D> {
+    assert!(!rows.is_empty());
+    profiled(|| rows[0].device.forward(rows.len(), rows).unwrap())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,4 +276,25 @@ mod tests {
         assert_eq!(r_grad[1], g.get(&y).array());
         assert_eq!(r_grad[2], g.get(&z).array());
     }
+
+    #[test]
+    fn test_vstack_rows() {
+        let dev: TestDevice = Default::default();
+        let rows: Vec<Tensor<Rank1<3>, TestDtype, _>> = (0..5)
+            .map(|i| {
+                let i = i as TestDtype;
+                dev.tensor([3.0 * i, 3.0 * i + 1.0, 3.0 * i + 2.0])
+            })
+            .collect();
+        let t = vstack(&rows);
+        assert_eq!(t.shape(), &(5, Const::<3>));
+        assert_eq!(t.strides, [3, 1]);
+        let v = t.as_vec();
+        for (i, x) in v.iter().enumerate() {
+            assert_eq!(*x, i as TestDtype);
+        }
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(t.clone().select(dev.tensor(i)).array(), row.array());
+        }
+    }
 }
@@ -0,0 +1,334 @@
+use num_traits::Float;
+use std::vec::Vec;
+
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{Tape, Tensor, TensorFromVec},
+};
+
+use super::{ops::try_host_unary_op, Device};
+
+/// The window each frame of [stft()] is multiplied by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    /// No windowing.
+    Rectangular,
+    /// The periodic Hann window `0.5 - 0.5 * cos(2 * pi * n / n_fft)`.
+    Hann,
+    /// The periodic Hamming window `0.54 - 0.46 * cos(2 * pi * n / n_fft)`.
+    Hamming,
+}
+
+impl Window {
+    fn weights<E: Float>(&self, n_fft: usize) -> Vec<E> {
+        let (a, b) = match self {
+            Window::Rectangular => (1.0, 0.0),
+            Window::Hann => (0.5, 0.5),
+            Window::Hamming => (0.54, 0.46),
+        };
+        (0..n_fft)
+            .map(|n| {
+                let phase = 2.0 * core::f64::consts::PI * n as f64 / n_fft as f64;
+                E::from(a - b * phase.cos()).unwrap()
+            })
+            .collect()
+    }
+}
+
+/// The `(cos, sin)` of `2 * pi * i / n_fft` for `i` in `0..n_fft`. The twiddle factor of bin
+/// `k` and sample `n` is at `k * n % n_fft`.
+fn twiddles(n_fft: usize) -> Vec<(f64, f64)> {
+    (0..n_fft)
+        .map(|i| {
+            let theta = 2.0 * core::f64::consts::PI * i as f64 / n_fft as f64;
+            (theta.cos(), theta.sin())
+        })
+        .collect()
+}
+
+/// The magnitude spectrogram of `signal`, from a short-time Fourier transform.
+///
+/// `signal` is split into frames of `n_fft` samples, each starting `hop` samples after the
+/// last, without any padding. Each frame is multiplied by `window`, and the output has the
+/// magnitude `|DFT(frame)[k]|` of frequency bins `k = 0..=n_fft / 2`, so it has a shape of
+/// `(1 + (L - n_fft) / hop, n_fft / 2 + 1)`.
+///
+/// The magnitude is differentiable, and bins with a magnitude of 0 have a gradient of 0.
+///
+/// **Panics** if `n_fft` or `hop` are 0, or `n_fft` is longer than `signal`.
+///
+/// **Pytorch equivalent**: `torch.stft(signal, n_fft, hop, window=window, center=False, return_complex=True).abs().T`
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::{stft, Window}};
+/// # let dev: Cpu = Default::default();
+/// let signal: Tensor<Rank1<1024>, f32, _> = dev.sample_normal();
+/// let spec = stft(signal, 256, 128, Window::Hann);
+/// assert_eq!(spec.shape(), &(7, 129));
+/// ```
+pub fn stft<L: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    signal: Tensor<(L,), E, D, T>,
+    n_fft: usize,
+    hop: usize,
+    window: Window,
+) -> Tensor<(usize, usize), E, D, T> {
+    try_stft(signal, n_fft, hop, window).unwrap()
+}
+
+/// See [stft()]
+#[allow(clippy::type_complexity)]
+pub fn try_stft<L: Dim, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    signal: Tensor<(L,), E, D, T>,
+    n_fft: usize,
+    hop: usize,
+    window: Window,
+) -> Result<Tensor<(usize, usize), E, D, T>, D::Err> {
+    let len = signal.shape.0.size();
+    assert!(n_fft > 0 && hop > 0, "n_fft and hop must be positive");
+    assert!(
+        n_fft <= len,
+        "n_fft ({n_fft}) is longer than the signal ({len})"
+    );
+    let num_frames = 1 + (len - n_fft) / hop;
+    let num_bins = n_fft / 2 + 1;
+
+    let window = window.weights::<E>(n_fft);
+    let twiddles: Vec<(E, E)> = twiddles(n_fft)
+        .into_iter()
+        .map(|(cos, sin)| (E::from(cos).unwrap(), E::from(sin).unwrap()))
+        .collect();
+    let (bwd_window, bwd_twiddles) = (window.clone(), twiddles.clone());
+    // the real & imaginary parts of each bin of each frame
+    let dft = move |x: &[E]| {
+        let mut out = Vec::with_capacity(num_frames * num_bins);
+        for f in 0..num_frames {
+            let frame = &x[f * hop..f * hop + n_fft];
+            for k in 0..num_bins {
+                let (mut re, mut im) = (E::zero(), E::zero());
+                for (n, (&x, &w)) in frame.iter().zip(window.iter()).enumerate() {
+                    let (cos, sin) = twiddles[k * n % n_fft];
+                    re += w * x * cos;
+                    im -= w * x * sin;
+                }
+                out.push((re, im));
+            }
+        }
+        out
+    };
+    let forward_dft = dft.clone();
+    try_host_unary_op(
+        signal,
+        (num_frames, num_bins),
+        |x| {
+            forward_dft(x)
+                .into_iter()
+                .map(|(re, im)| (re * re + im * im).sqrt())
+                .collect()
+        },
+        move |x, _, grad_out| {
+            let mut grad = vec![E::zero(); x.len()];
+            // d|X|/dx = (re * w * cos - im * w * sin) / |X|
+            for (i, (re, im)) in dft(x).into_iter().enumerate() {
+                let mag = (re * re + im * im).sqrt();
+                if mag == E::zero() {
+                    continue;
+                }
+                let (f, k) = (i / num_bins, i % num_bins);
+                let scale = grad_out[i] / mag;
+                for n in 0..n_fft {
+                    let (cos, sin) = bwd_twiddles[k * n % n_fft];
+                    grad[f * hop + n] += scale * bwd_window[n] * (re * cos - im * sin);
+                }
+            }
+            grad
+        },
+    )
+}
+
+/// Reconstructs a signal whose [stft()] has the given `magnitude`, with the
+/// [Griffin-Lim algorithm](https://doi.org/10.1109/TASSP.1984.1164317). This is not
+/// differentiable, so any tape on `magnitude` is ignored.
+///
+/// `n_fft`, `hop` and `window` are the arguments [stft()] was called with. Starting from a
+/// phase of 0, each of the `iters` iterations computes the signal with the current phase by
+/// a least squares inverse STFT, and replaces the phase with that of the signal's STFT.
+///
+/// The signal has `(Frames - 1) * hop + n_fft` samples. Samples that every window is 0 at,
+/// like the first with [Window::Hann], are 0.
+///
+/// **Panics** if `Bins` isn't `n_fft / 2 + 1`, or `hop` is 0.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::{griffin_lim, stft, Window}};
+/// # let dev: Cpu = Default::default();
+/// let signal: Tensor<Rank1<1024>, f32, _> = dev.sample_normal();
+/// let magnitude = stft(signal, 256, 64, Window::Hann);
+/// let reconstructed = griffin_lim(&magnitude, 16, 256, 64, Window::Hann);
+/// assert_eq!(reconstructed.shape(), &(1024,));
+/// ```
+pub fn griffin_lim<F: Dim, B: Dim, E: Dtype + Float, D: TensorFromVec<E>, T>(
+    magnitude: &Tensor<(F, B), E, D, T>,
+    iters: usize,
+    n_fft: usize,
+    hop: usize,
+    window: Window,
+) -> Tensor<(usize,), E, D> {
+    let (frames, bins) = magnitude.shape;
+    let (num_frames, num_bins) = (frames.size(), bins.size());
+    assert_eq!(num_bins, n_fft / 2 + 1, "expected n_fft / 2 + 1 bins");
+    assert!(hop > 0, "hop must be positive");
+    let len = (num_frames.max(1) - 1) * hop + n_fft;
+    let mag: Vec<f64> = magnitude
+        .as_vec()
+        .into_iter()
+        .map(|m| m.to_f64().unwrap())
+        .collect();
+    let window = window.weights::<f64>(n_fft);
+    let twiddles = twiddles(n_fft);
+
+    // the least squares signal for the spectrum `(re, im)` of each frame
+    let istft = |spectrum: &[(f64, f64)]| {
+        let mut signal = vec![0.0; len];
+        let mut norm = vec![0.0; len];
+        for f in 0..num_frames {
+            let frame = &spectrum[f * num_bins..(f + 1) * num_bins];
+            for n in 0..n_fft {
+                // the inverse dft of the real frame, from its half spectrum
+                let mut x = 0.0;
+                for (k, &(re, im)) in frame.iter().enumerate() {
+                    let (cos, sin) = twiddles[k * n % n_fft];
+                    let mirrored = k > 0 && 2 * k != n_fft;
+                    x += (re * cos - im * sin) * if mirrored { 2.0 } else { 1.0 };
+                }
+                signal[f * hop + n] += window[n] * x / n_fft as f64;
+                norm[f * hop + n] += window[n] * window[n];
+            }
+        }
+        for (x, norm) in signal.iter_mut().zip(norm) {
+            if norm > 1e-10 {
+                *x /= norm;
+            }
+        }
+        signal
+    };
+    let stft = |signal: &[f64]| {
+        let mut spectrum = Vec::with_capacity(num_frames * num_bins);
+        for f in 0..num_frames {
+            let frame = &signal[f * hop..f * hop + n_fft];
+            for k in 0..num_bins {
+                let (mut re, mut im) = (0.0, 0.0);
+                for (n, (&x, &w)) in frame.iter().zip(window.iter()).enumerate() {
+                    let (cos, sin) = twiddles[k * n % n_fft];
+                    re += w * x * cos;
+                    im -= w * x * sin;
+                }
+                spectrum.push((re, im));
+            }
+        }
+        spectrum
+    };
+
+    let mut spectrum: Vec<(f64, f64)> = mag.iter().map(|&m| (m, 0.0)).collect();
+    for _ in 0..iters {
+        let estimate = stft(&istft(&spectrum));
+        for ((s, &m), (re, im)) in spectrum.iter_mut().zip(mag.iter()).zip(estimate) {
+            let norm = (re * re + im * im).sqrt();
+            *s = if norm > 1e-10 {
+                (m * re / norm, m * im / norm)
+            } else {
+                (m, 0.0)
+            };
+        }
+    }
+    let signal = istft(&spectrum)
+        .into_iter()
+        .map(|x| E::from(x).unwrap())
+        .collect();
+    magnitude.device.tensor_from_vec(signal, (len,))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_stft_sine_peaks_at_its_bin() {
+        let dev: TestDevice = Default::default();
+        // 8 periods per 64 samples, i.e. exactly bin 8
+        let signal: std::vec::Vec<TestDtype> = (0..256)
+            .map(|t| (2.0 * core::f64::consts::PI * 8.0 * t as f64 / 64.0).sin() as TestDtype)
+            .collect();
+        let signal = dev.tensor_from_vec(signal, (Const::<256>,));
+
+        let spec = stft(signal.clone(), 64, 32, Window::Rectangular);
+        assert_eq!(spec.shape(), &(7, 33));
+        let spec = spec.as_vec();
+        for f in 0..7 {
+            let frame = &spec[f * 33..(f + 1) * 33];
+            for (k, &mag) in frame.iter().enumerate() {
+                if k == 8 {
+                    assert!((mag - 32.0).abs() < 1e-3, "{mag}");
+                } else {
+                    assert!(mag < 1e-3, "bin {k} has {mag}");
+                }
+            }
+        }
+
+        // a hann window leaks half of the peak into each neighboring bin
+        let spec = stft(signal, 64, 32, Window::Hann).as_vec();
+        let frame = &spec[..33];
+        assert!((frame[8] - 16.0).abs() < 1e-3);
+        assert!((frame[7] - 8.0).abs() < 1e-3 && (frame[9] - 8.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_stft_grad_finite_differences() {
+        let dev: TestDevice = Default::default();
+        let signal: Tensor<Rank1<11>, TestDtype, _> = dev.sample_normal();
+        let weights: Tensor<(usize, usize), TestDtype, _> = dev.sample_uniform_like(&(3, 3));
+        let loss = |s: Tensor<Rank1<11>, TestDtype, _>| {
+            (stft(s, 4, 3, Window::Hann) * weights.clone())
+                .sum::<Rank0, _>()
+                .array()
+        };
+        let g = (stft(signal.leaky_trace(), 4, 3, Window::Hann) * weights.clone())
+            .sum::<Rank0, _>()
+            .backward();
+        let g = g.get(&signal).array();
+
+        let x = signal.array();
+        let h = 1e-2;
+        for i in 0..11 {
+            let (mut plus, mut minus) = (x, x);
+            plus[i] += h;
+            minus[i] -= h;
+            let fd = (loss(dev.tensor(plus)) - loss(dev.tensor(minus))) / (2.0 * h);
+            assert!((fd - g[i]).abs() < 1e-2, "{i}: {fd} vs {}", g[i]);
+        }
+        // the last sample isn't in any frame
+        assert_eq!(g[10], 0.0);
+    }
+
+    #[test]
+    fn test_griffin_lim_sine() {
+        let dev: TestDevice = Default::default();
+        let signal: std::vec::Vec<TestDtype> = (0..256)
+            .map(|t| (2.0 * core::f64::consts::PI * 8.0 * t as f64 / 64.0 + 1.0).sin() as TestDtype)
+            .collect();
+        let signal = dev.tensor_from_vec(signal, (Const::<256>,));
+        let magnitude = stft(signal, 64, 16, Window::Hann);
+
+        let reconstructed = griffin_lim(&magnitude, 32, 64, 16, Window::Hann);
+        assert_eq!(reconstructed.shape(), &(256,));
+        let rebuilt = stft(reconstructed, 64, 16, Window::Hann);
+        assert_eq!(rebuilt.shape(), magnitude.shape());
+
+        // the spectral convergence, i.e. the relative error of the magnitudes
+        let (a, b) = (magnitude.as_vec(), rebuilt.as_vec());
+        let err: TestDtype = a.iter().zip(b.iter()).map(|(a, b)| (a - b) * (a - b)).sum();
+        let norm: TestDtype = a.iter().map(|a| a * a).sum();
+        let convergence = (err / norm).sqrt();
+        assert!(convergence < 1e-2, "{convergence}");
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    shapes::{Dtype, Shape, Unit},
+    tensor::{unique_id, DeviceStorage, NoneTape, PutTape, SplitTape, Tape, Tensor, TensorFromVec},
+};
+
+use super::{choose::ChooseKernel, ChooseFrom};
+
+/// Passes `t` through unchanged, but stops the gradient from flowing back to any position where
+/// `mask` is `true`. `mask` is in the same row major order as [Tensor::as_vec()].
+///
+/// This is useful for ignoring padding positions while keeping the rest differentiable.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<3>, f32, _> = dev.tensor([1.0, 2.0, 3.0]);
+/// let r = t.leaky_trace().stop_grad_where(&[false, true, false]);
+/// assert_eq!(r.array(), [1.0, 2.0, 3.0]);
+/// let g = r.sum().backward();
+/// assert_eq!(g.get(&t).array(), [1.0, 0.0, 1.0]);
+/// ```
+pub fn stop_grad_where<S: Shape, E: Dtype, D, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    mask: &[bool],
+) -> Tensor<S, E, D, T>
+where
+    D: ChooseKernel<E> + TensorFromVec<bool>,
+{
+    t.stop_grad_where(mask)
+}
+
+impl<S: Shape, E: Dtype, D: ChooseKernel<E> + TensorFromVec<bool>, T: Tape<E, D>>
+    Tensor<S, E, D, T>
+{
+    /// See [stop_grad_where]
+    pub fn stop_grad_where(self, mask: &[bool]) -> Self {
+        self.try_stop_grad_where(mask).unwrap()
+    }
+
+    /// See [stop_grad_where]
+    pub fn try_stop_grad_where(self, mask: &[bool]) -> Result<Self, D::Err> {
+        assert_eq!(mask.len(), self.shape.num_elements());
+        let keep = mask.iter().map(|m| !m).collect();
+        let keep = self.device.try_tensor_from_vec(keep, self.shape)?;
+        let (t, tape) = self.split_tape();
+        // same values as `t`, but not on the tape, so no gradient flows through it
+        let stopped = Tensor {
+            id: unique_id(),
+            data: t.data.clone(),
+            shape: t.shape,
+            strides: t.strides,
+            device: t.device.clone(),
+            tape: NoneTape,
+        };
+        keep.try_choose(t.put_tape(tape), stopped)
+    }
+}
+
+/// Stops all gradient flow through `t`, by dropping its tape. The result has the same
+/// values as `t` (sharing its data), so it can be used as a constant, e.g. for the
+/// targets computed by a target network.
+///
+/// None of the operations recorded before `detach` get a gradient from uses of the
+/// result. The result also gets a new id, so that its gradients can't be mixed up
+/// with the gradients of `t` on another branch of the graph.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<2>, f32, _> = dev.tensor([1.0, 2.0]);
+/// let r = t.leaky_trace().square().detach();
+/// assert_eq!(r.array(), [1.0, 4.0]);
+/// ```
+pub fn detach<S: Shape, E: Unit, D: DeviceStorage, T>(t: Tensor<S, E, D, T>) -> Tensor<S, E, D> {
+    t.detach()
+}
+
+impl<S: Shape, E: Unit, D: DeviceStorage, T> Tensor<S, E, D, T> {
+    /// See [detach]
+    pub fn detach(self) -> Tensor<S, E, D> {
+        Tensor {
+            id: unique_id(),
+            data: self.data,
+            shape: self.shape,
+            strides: self.strides,
+            device: self.device,
+            tape: NoneTape,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_stop_grad_where_padding() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor([[1.0, 2.0, 0.0], [3.0, 0.0, 0.0]]);
+        let padding = [false, false, true, false, true, true];
+        let r = t.leaky_trace().stop_grad_where(&padding);
+        assert_eq!(r.array(), t.array());
+        let g = r.square().sum().backward();
+        assert_eq!(g.get(&t).array(), [[2.0, 4.0, 0.0], [6.0, 0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_stop_grad_where_broadcasted() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, 2.0, 3.0]);
+        let r = t
+            .leaky_trace()
+            .broadcast::<Rank2<2, 3>, _>()
+            .stop_grad_where(&[true, false, false, true, true, false]);
+        assert_eq!(r.array(), [[1.0, 2.0, 3.0]; 2]);
+        let g = r.sum().backward();
+        assert_eq!(g.get(&t).array(), [0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_stop_grad_where_wrong_len() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<3>, TestDtype, _> = dev.zeros();
+        let _ = t.stop_grad_where(&[true, false]);
+    }
+
+    #[test]
+    fn test_detach_branch_gets_no_gradient() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<3>, TestDtype, _> = dev.tensor([1.0, -2.0, 3.0]);
+        let a = x.leaky_trace();
+        let detached = a.retaped::<OwnedTape<_, _>>().square().detach();
+        let r = (a.exp() + detached).sum();
+        assert_close(
+            &r.array(),
+            &[1.0, -2.0, 3.0]
+                .map(|v: TestDtype| v.exp() + v * v)
+                .iter()
+                .sum(),
+        );
+
+        // only the exp branch contributes to the gradient
+        let g = r.backward();
+        assert_close(&g.get(&x).array(), &x.array().map(TestDtype::exp));
+    }
+
+    #[test]
+    fn test_detach_whole_graph() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([1.0, 2.0]);
+        let y = x.leaky_trace().square().detach();
+        let w: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([3.0, 4.0]);
+        let g = (w.leaky_trace() * y).sum().backward();
+        // the tape of `x` was dropped, so it never even gets a gradient allocated
+        assert!(g.get_ref_checked(&x).is_none());
+        assert_eq!(g.get(&w).array(), [1.0, 4.0]);
+    }
+}
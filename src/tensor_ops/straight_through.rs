@@ -0,0 +1,104 @@
+use num_traits::Float;
+
+use crate::{
+    shapes::{Dtype, Shape},
+    tensor::{NoneTape, Tape, Tensor},
+};
+
+use super::{Device, TryAdd, TryMul, TrySub};
+
+/// Rounds `t` to the nearest integer, with a straight-through gradient: the gradient
+/// is passed through unchanged, as if this were the identity.
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::ste_round};
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<3>, f32, _> = dev.tensor([0.3, 0.8, -1.6]);
+/// let r = ste_round(t.leaky_trace());
+/// assert_eq!(r.array(), [0.0, 1.0, -2.0]);
+/// let g = r.sum().backward();
+/// assert_eq!(g.get(&t).array(), [1.0; 3]);
+/// ```
+pub fn ste_round<S: Shape, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.straight_through_estimator(Float::round, None)
+}
+
+/// Binarizes `t` to `-1` or `1` with [Float::signum()], with a straight-through gradient
+/// that is clipped to zero where `|t| > 1`, as in
+/// [Binarized Neural Networks](https://arxiv.org/abs/1602.02830).
+///
+/// ```rust
+/// # use dfdx::{prelude::*, tensor_ops::ste_sign};
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank1<3>, f32, _> = dev.tensor([0.3, -0.8, 1.5]);
+/// let r = ste_sign(t.leaky_trace());
+/// assert_eq!(r.array(), [1.0, -1.0, 1.0]);
+/// let g = r.sum().backward();
+/// assert_eq!(g.get(&t).array(), [1.0, 1.0, 0.0]);
+/// ```
+pub fn ste_sign<S: Shape, E: Dtype + Float, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+) -> Tensor<S, E, D, T> {
+    t.straight_through_estimator(Float::signum, Some(E::ONE))
+}
+
+impl<S: Shape, E: Dtype + Float, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Applies `f` to each element in the forward pass, but passes the gradient straight
+    /// through as if `f` were the identity. If `clip` is `Some(c)`, the gradient is zero
+    /// wherever `|t| > c`. See [ste_round()] and [ste_sign()].
+    pub fn straight_through_estimator<F: FnMut(E) -> E>(self, f: F, clip: Option<E>) -> Self {
+        self.try_straight_through_estimator(f, clip).unwrap()
+    }
+
+    /// See [Tensor::straight_through_estimator()]
+    pub fn try_straight_through_estimator<F: FnMut(E) -> E>(
+        self,
+        f: F,
+        clip: Option<E>,
+    ) -> Result<Self, D::Err> {
+        let values = self.as_vec();
+        let out = values.iter().copied().map(f).collect();
+        let out = self.device.try_tensor_from_vec(out, self.shape)?;
+        let pass = match clip {
+            Some(c) => {
+                let mask = values
+                    .iter()
+                    .map(|v| if v.abs() <= c { E::ONE } else { E::zero() })
+                    .collect();
+                let mask = self.device.try_tensor_from_vec(mask, self.shape)?;
+                self.try_mul(mask)?
+            }
+            None => self,
+        };
+        // `pass - pass` is exactly zero, but only the first one is on the tape
+        let stopped = pass.retaped::<NoneTape>().detach();
+        pass.try_sub(stopped)?.try_add(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_ste_round() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank1<2>, TestDtype, _> = dev.tensor([0.3, 0.8]);
+        let r = ste_round(t.leaky_trace());
+        assert_eq!(r.array(), [0.0, 1.0]);
+        let g = (r * dev.tensor([2.0, -3.0])).sum().backward();
+        assert_eq!(g.get(&t).array(), [2.0, -3.0]);
+    }
+
+    #[test]
+    fn test_ste_sign_clips_gradient() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 2>, TestDtype, _> = dev.tensor([[0.5, -1.0], [-2.0, 3.0]]);
+        let r = ste_sign(t.leaky_trace());
+        assert_eq!(r.array(), [[1.0, -1.0], [-1.0, 1.0]]);
+        let g = r.sum().backward();
+        assert_eq!(g.get(&t).array(), [[1.0, 1.0], [0.0, 0.0]]);
+    }
+}
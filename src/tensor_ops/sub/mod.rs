@@ -3,7 +3,7 @@ mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
-use super::{ops::*, Device};
+use super::{ops::*, Device, TryAdd};
 use crate::{shapes::*, tensor::*};
 
 #[repr(C)]
@@ -75,6 +75,33 @@ where
     }
 }
 
+/// Reverse scalar subtraction; `s - t`. The gradient is `-1`.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([0.25, 0.5, 1.0]);
+/// let r = t.rsub_scalar(1.0);
+/// assert_eq!(r.array(), [0.75, 0.5, 0.0]);
+/// ```
+pub fn rsub_scalar<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    s: E,
+) -> Tensor<S, E, D, T> {
+    t.rsub_scalar(s)
+}
+
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// See [rsub_scalar]
+    pub fn rsub_scalar(self, s: E) -> Self {
+        self.try_rsub_scalar(s).unwrap()
+    }
+    /// See [rsub_scalar]
+    pub fn try_rsub_scalar(self, s: E) -> Result<Self, D::Err> {
+        self.try_negate()?.try_add(s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tensor::*;
@@ -158,4 +185,14 @@ mod tests {
         let g = r.exp().sum().backward();
         assert_close(&g.get(&x).array(), &[[0.36787945; 2]; 3]);
     }
+
+    #[test]
+    fn test_rsub_scalar() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<_, TestDtype, _> = dev.tensor([0.2, 0.5]);
+        let r = x.leaky_trace().rsub_scalar(1.0);
+        assert_close(&r.array(), &[0.8, 0.5]);
+        let g = r.sum().backward();
+        assert_eq!(g.get(&x).array(), [-1.0, -1.0]);
+    }
 }
@@ -1,7 +1,11 @@
+#[cfg(feature = "cpu-par-reduce")]
+use crate::tensor_ops::utilities::reduction_utils::reduce_row;
 use crate::{
     shapes::{Axes, Dtype, HasAxes, ReduceShapeTo, Shape},
     tensor::{Cpu, Tensor, ZerosTensor},
-    tensor_ops::utilities::reduction_utils::index_for_reductions,
+    tensor_ops::utilities::reduction_utils::{
+        contiguous_last_dim, for_each_row, index_for_reductions,
+    },
 };
 
 impl<E: Dtype> super::SumKernel<E> for Cpu {
@@ -22,6 +26,11 @@ impl<E: Dtype> super::SumKernel<E> for Cpu {
                 tmp += *v;
             }
             std::sync::Arc::get_mut(&mut out.data).unwrap()[0] = tmp * scale;
+        } else if let Some(n) = contiguous_last_dim::<Src, Ax>(inp) {
+            let out_buf = std::sync::Arc::get_mut(&mut out.data).unwrap();
+            if n > 0 {
+                for_each_row(inp.data.as_slice(), out_buf, (n, 1), sum_rows);
+            }
         } else {
             let num_elems_reduced = <Src as HasAxes<Ax>>::size(&inp.shape);
             let inp_buf = inp.data.as_ref();
@@ -53,6 +62,15 @@ impl<E: Dtype> super::SumKernel<E> for Cpu {
             for i in grad_inp.iter_mut() {
                 *i += v * scale;
             }
+        } else if let Some(n) = contiguous_last_dim::<Src, Ax>(inp) {
+            if n > 0 {
+                for_each_row(
+                    grad_out.as_slice(),
+                    grad_inp.as_mut_slice(),
+                    (1, n),
+                    add_rows,
+                );
+            }
         } else {
             let num_elems_reduced = <Src as HasAxes<Ax>>::size(&inp.shape);
             let mut idx = index_for_reductions::<Src, Ax>(inp.shape, inp.strides);
@@ -65,3 +83,31 @@ impl<E: Dtype> super::SumKernel<E> for Cpu {
         Ok(())
     }
 }
+
+/// Sums each row of `n` elements of `inp` into the matching element of `out`.
+///
+/// The elements of a row are added in order, like the strided path, so both round the
+/// same way. With the `cpu-par-reduce` feature rows are summed with [reduce_row] instead,
+/// which is faster but adds in a different order.
+fn sum_rows<E: Dtype>(inp: &[E], out: &mut [E], n: usize) {
+    for (o, row) in out.iter_mut().zip(inp.chunks_exact(n)) {
+        #[cfg(feature = "cpu-par-reduce")]
+        {
+            *o = reduce_row(row, Default::default(), |a, b| a + b);
+        }
+        #[cfg(not(feature = "cpu-par-reduce"))]
+        {
+            *o = row.iter().fold(E::default(), |a, &b| a + b);
+        }
+    }
+}
+
+/// Adds each element of `grad_out` to every element of the matching row of `n`
+/// elements in `grad_inp`.
+fn add_rows<E: Dtype>(grad_out: &[E], grad_inp: &mut [E], n: usize) {
+    for (row, &o) in grad_inp.chunks_exact_mut(n).zip(grad_out.iter()) {
+        for g in row.iter_mut() {
+            *g += o;
+        }
+    }
+}
@@ -3,7 +3,9 @@ mod cpu_kernel;
 #[cfg(feature = "cuda")]
 mod cuda_kernel;
 
-use crate::{shapes::*, tensor::*};
+use crate::{shapes::*, tensor::*, tensor_ops::ReshapeTo};
+
+use super::{ops::profiled, reshape_to::ReshapeKernel};
 
 pub trait SumKernel<E: Dtype>: DeviceStorage {
     fn forward<Src: Shape, Dst: Shape, Ax: Axes>(
@@ -64,7 +66,7 @@ impl<S: Shape, E: Dtype, D: SumKernel<E>, T: Tape<E, D>> SumTo for Tensor<S, E,
     {
         let dst: Dst = self.shape().reduced();
         let (inp, mut tape) = self.split_tape();
-        let out = inp.device.forward(dst, &inp)?;
+        let out = profiled(|| inp.device.forward(dst, &inp))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&inp)?;
         tape.try_alloc_grad(&out)?;
@@ -76,6 +78,36 @@ impl<S: Shape, E: Dtype, D: SumKernel<E>, T: Tape<E, D>> SumTo for Tensor<S, E,
     }
 }
 
+impl<S: Shape, E: Dtype, D: SumKernel<E> + ReshapeKernel<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Sums the last dimension, but keeps it around as a size 1 dimension instead
+    /// of removing it. **Pytorch equivalent**: `t.sum(-1, keepdim=True)`
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [-1.0, -2.0, -3.0]]);
+    /// let r: Tensor<Rank2<2, 1>, f32, _> = t.sum_last_dim_keepdim();
+    /// assert_eq!(r.array(), [[6.0], [-6.0]]);
+    /// ```
+    pub fn sum_last_dim_keepdim(self) -> Tensor<S::KeptDim, E, D, T>
+    where
+        S: KeepDimShape<S::LastAxis>,
+    {
+        self.try_sum_last_dim_keepdim().unwrap()
+    }
+
+    /// Fallible version of [Tensor::sum_last_dim_keepdim]
+    pub fn try_sum_last_dim_keepdim(self) -> Result<Tensor<S::KeptDim, E, D, T>, D::Err>
+    where
+        S: KeepDimShape<S::LastAxis>,
+    {
+        let kept = self.shape().keep_dim();
+        let summed = self.try_sum::<<S as ReduceShape<S::LastAxis>>::Reduced, S::LastAxis>()?;
+        // the output of sum is contiguous, so this does not copy any data
+        summed.try_reshape_like(&kept).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,4 +204,90 @@ mod tests {
         let g = c.backward();
         assert_eq!(g.get(&a).array(), [8.0; 3]);
     }
+
+    #[test]
+    fn test_sum_last_dim_keepdim() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<2, 3>, TestDtype, _> = dev.tensor([[1.0, 2.0, 3.0], [-2.0, 4.0, -6.0]]);
+        let r: Tensor<Rank2<2, 1>, TestDtype, _, _> = t.leaky_trace().sum_last_dim_keepdim();
+        assert_eq!(r.array(), [[6.0], [-4.0]]);
+
+        let r: Tensor<Rank1<2>, TestDtype, _, _> = r.reshape();
+        let centered = t.leaky_trace() - r.broadcast();
+        assert_eq!(centered.array(), [[-5.0, -4.0, -3.0], [2.0, 8.0, -2.0]]);
+        let g = centered.square().sum().backward();
+        // d/dt_ij sum_k (t_ik - s_i)^2 = 2 * (c_ij - sum_k c_ik)
+        assert_close(
+            &g.get(&t).array(),
+            &[[14.0, 16.0, 18.0], [-12.0, 0.0, -20.0]],
+        );
+    }
+
+    #[test]
+    fn test_sum_last_dim_matches_strided() {
+        let dev: TestDevice = Default::default();
+        // 37 is not a multiple of any simd width, and reducing `Axes2<2, 3>` over the
+        // size 1 axis 2 goes through the strided kernel on the same elements.
+        let t: Tensor<Rank4<3, 5, 1, 37>, TestDtype, _> = dev.sample_normal();
+        // with `cpu-par-reduce` (or on Cuda) the elements of a row may be added in a
+        // different order, so the results are only close to the strided ones.
+        let r1 = t.leaky_trace().sum::<Rank3<3, 5, 1>, Axis<3>>();
+        let r2 = t.leaky_trace().sum::<Rank2<3, 5>, Axes2<2, 3>>();
+        let r2_array = r2.array().map(|r| r.map(|x| [x]));
+        assert_close_with_tolerance(&r1.array(), &r2_array, 1e-5);
+        let g1 = r1.square().sum().backward();
+        let g2 = r2.square().sum().backward();
+        assert_close_with_tolerance(&g1.get(&t).array(), &g2.get(&t).array(), 1e-5);
+    }
+
+    #[cfg(not(feature = "cpu-par-reduce"))]
+    #[test]
+    fn test_cpu_sum_last_dim_is_bit_identical_to_strided() {
+        let dev: crate::tensor::Cpu = Default::default();
+        let t: Tensor<Rank3<4, 1, 1000>, TestDtype, _> = dev.sample_normal();
+        let r1 = t.leaky_trace().sum::<Rank2<4, 1>, Axis<2>>();
+        let r2 = t.leaky_trace().sum::<Rank1<4>, Axes2<1, 2>>();
+        assert_eq!(r1.array().map(|[x]| x), r2.array());
+        let g1 = r1.square().sum().backward();
+        let g2 = r2.square().sum().backward();
+        assert_eq!(g1.get(&t).array(), g2.get(&t).array());
+    }
+
+    #[test]
+    fn test_sum_last_dim_degenerate_dims() {
+        let dev: TestDevice = Default::default();
+
+        let t: Tensor<Rank3<0, 2, 3>, TestDtype, _> = dev.sample_normal();
+        let r = t.leaky_trace().sum::<Rank2<0, 2>, _>();
+        let r = r.sum::<Rank1<2>, _>();
+        assert_eq!(r.array(), [0.0; 2]);
+        assert_eq!(r.sum().backward().get(&t).shape(), t.shape());
+
+        let t: Tensor<Rank3<1, 1, 3>, TestDtype, _> = dev.tensor([[[1.0, 2.0, 3.0]]]);
+        let r = t.leaky_trace().sum::<Rank2<1, 1>, _>();
+        assert_eq!(r.array(), [[6.0]]);
+        let g = (r * 2.0).sum().backward();
+        assert_eq!(g.get(&t).array(), [[[2.0; 3]]]);
+
+        let t: Tensor<Rank2<2, 0>, TestDtype, _> = dev.zeros();
+        let r = t.leaky_trace().sum::<Rank1<2>, _>();
+        assert_eq!(r.array(), [0.0; 2]);
+        assert_eq!(r.sum().backward().get(&t).shape(), t.shape());
+    }
+
+    #[test]
+    fn test_sum_last_dim_large() {
+        let dev: TestDevice = Default::default();
+        // big enough to be split across threads with `cpu-par-reduce`
+        let t: Tensor<Rank3<4, 129, 131>, TestDtype, _> = dev.sample_normal();
+        let r1 = t.leaky_trace().sum::<Rank2<4, 129>, Axis<2>>();
+        let r2 = t
+            .leaky_trace()
+            .reshape::<Rank4<4, 129, 1, 131>>()
+            .sum::<Rank2<4, 129>, Axes2<2, 3>>();
+        assert_close_with_tolerance(&r1.array(), &r2.array(), 1e-4);
+        let g1 = r1.sum().backward();
+        let g2 = r2.sum().backward();
+        assert_eq!(g1.get(&t).as_vec(), g2.get(&t).as_vec());
+    }
 }
@@ -0,0 +1,21 @@
+use crate::tensor_ops::cpu_kernels::UnaryDerivative;
+use num_traits::Float;
+
+impl<F: Float + PartialOrd> UnaryDerivative<F> for super::ThresholdKernelOp<F> {
+    #[inline(always)]
+    fn f(&self, &x: &F) -> F {
+        if x > self.threshold {
+            x
+        } else {
+            self.value
+        }
+    }
+    #[inline(always)]
+    fn df(&self, x: &F) -> F {
+        if x > &self.threshold {
+            F::one()
+        } else {
+            F::zero()
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use super::ThresholdKernelOp;
+use crate::tensor_ops::cuda_kernels::cuda_unary;
+
+unsafe impl cudarc::driver::DeviceRepr for ThresholdKernelOp<f32> {}
+unsafe impl cudarc::driver::DeviceRepr for ThresholdKernelOp<f64> {}
+
+const P: &str = include_str!(concat!(env!("OUT_DIR"), "/threshold.ptx"));
+
+cuda_unary!(
+    ThresholdKernelOp<f32>,
+    f32,
+    P,
+    "threshold_fwd_f32",
+    "threshold_bwd_f32"
+);
+cuda_unary!(
+    ThresholdKernelOp<f64>,
+    f64,
+    P,
+    "threshold_fwd_f64",
+    "threshold_bwd_f64"
+);
@@ -0,0 +1,64 @@
+mod cpu_kernel;
+
+#[cfg(feature = "cuda")]
+mod cuda_kernel;
+
+use super::ops::{try_unary_op, UnaryKernel};
+use crate::{shapes::*, tensor::*};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ThresholdKernelOp<E> {
+    pub threshold: E,
+    pub value: E,
+}
+
+/// Keeps elements greater than `threshold`, and replaces all others with `value`.
+///
+/// The gradient is 1 above `threshold` and 0 everywhere else.
+///
+/// **Pytorch equivalent**: `torch.nn.functional.threshold(t, threshold, value)`
+///
+/// Example:
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t = dev.tensor([-1.0, 0.0, 0.5, 2.0]);
+/// let r = t.threshold(0.0, -5.0);
+/// assert_eq!(r.array(), [-5.0, -5.0, 0.5, 2.0]);
+/// ```
+pub fn threshold<S: Shape, E: Dtype, D: UnaryKernel<ThresholdKernelOp<E>, E>, T: Tape<E, D>>(
+    t: Tensor<S, E, D, T>,
+    threshold: E,
+    value: E,
+) -> Tensor<S, E, D, T> {
+    t.threshold(threshold, value)
+}
+
+impl<S: Shape, E: Dtype, D: UnaryKernel<ThresholdKernelOp<E>, E>, T: Tape<E, D>>
+    Tensor<S, E, D, T>
+{
+    /// See [threshold]
+    pub fn threshold(self, threshold: E, value: E) -> Self {
+        self.try_threshold(threshold, value).unwrap()
+    }
+    /// See [threshold]
+    pub fn try_threshold(self, threshold: E, value: E) -> Result<Self, D::Err> {
+        try_unary_op(ThresholdKernelOp { threshold, value }, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_threshold() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<_, TestDtype, _> = dev.tensor([-1.0, 0.0, 2.0]);
+        let r = t.leaky_trace().threshold(0.0, -5.0);
+        assert_eq!(r.array(), [-5.0, -5.0, 2.0]);
+        let g = r.sum().backward();
+        assert_eq!(g.get(&t).array(), [0.0, 0.0, 1.0]);
+    }
+}
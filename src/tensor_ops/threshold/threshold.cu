@@ -0,0 +1,15 @@
+#include "unary_op_macros.cuh"
+
+template<typename F>
+struct ThresholdKernelOp {
+    F threshold;
+    F value;
+};
+
+UNARY_OP(float, threshold_fwd_f32, threshold_bwd_f32, ThresholdKernelOp<float>,
+        x > op.threshold ? x : op.value,
+        x > op.threshold ? 1.0 : 0.0)
+
+UNARY_OP(double, threshold_fwd_f64, threshold_bwd_f64, ThresholdKernelOp<double>,
+    x > op.threshold ? x : op.value,
+    x > op.threshold ? 1.0 : 0.0)
@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 use num_traits::Float;
 
-use super::{Bilinear, NearestNeighbor};
+use super::{Bicubic, Bilinear, BilinearHalfPixel, NearestNeighbor};
 
 fn make_4d<S: Shape>(strides: S::Concrete) -> [usize; 4] {
     match S::NUM_DIMS {
@@ -195,3 +195,173 @@ impl<E: Float + Unit + std::ops::AddAssign + std::ops::DivAssign>
         Ok(())
     }
 }
+
+/// The two source pixels `(i0, i1)` of output pixel `o`, and the weight of `i1`, using
+/// the mapping `src = (o + 0.5) * inp / out - 0.5`. Sources past the edges are clamped
+/// to the edge pixels.
+fn half_pixel_source(o: usize, inp: usize, out: usize) -> (usize, usize, f32) {
+    let src = ((o as f32 + 0.5) * (inp as f32 / out as f32) - 0.5).max(0.0);
+    let i0 = (src as usize).min(inp - 1);
+    let i1 = (i0 + 1).min(inp - 1);
+    (i0, i1, src - i0 as f32)
+}
+
+impl<E: Float + Unit + std::ops::AddAssign + std::ops::DivAssign>
+    super::Upscale2DKernel<E, BilinearHalfPixel> for Cpu
+{
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Upscale2DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_4d::<I>(inp.strides);
+        let ostr = make_4d::<O>(out.strides);
+
+        let buf = inp.data.as_ref();
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                let inp_at = |ih: usize, iw: usize| {
+                    buf[b * istr[0] + c * istr[1] + ih * istr[2] + iw * istr[3]]
+                };
+                for oh in 0..op.h_out {
+                    let (h0, h1, hs) = half_pixel_source(oh, op.h_in, op.h_out);
+                    let hs = E::from(hs).unwrap();
+                    for ow in 0..op.w_out {
+                        let (w0, w1, ws) = half_pixel_source(ow, op.w_in, op.w_out);
+                        let ws = E::from(ws).unwrap();
+                        out_buf[b * ostr[0] + c * ostr[1] + oh * ostr[2] + ow * ostr[3]] =
+                            inp_at(h0, w0) * (E::one() - hs) * (E::one() - ws)
+                                + inp_at(h0, w1) * (E::one() - hs) * ws
+                                + inp_at(h1, w0) * hs * (E::one() - ws)
+                                + inp_at(h1, w1) * hs * ws;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::Upscale2DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_4d::<I>(inp.strides);
+        let ostr = make_4d::<O>(out.strides);
+
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                let inp_i =
+                    |ih: usize, iw: usize| b * istr[0] + c * istr[1] + ih * istr[2] + iw * istr[3];
+                for oh in 0..op.h_out {
+                    let (h0, h1, hs) = half_pixel_source(oh, op.h_in, op.h_out);
+                    let hs = E::from(hs).unwrap();
+                    for ow in 0..op.w_out {
+                        let (w0, w1, ws) = half_pixel_source(ow, op.w_in, op.w_out);
+                        let ws = E::from(ws).unwrap();
+                        let g = grad_out[b * ostr[0] + c * ostr[1] + oh * ostr[2] + ow * ostr[3]];
+                        grad_inp[inp_i(h0, w0)] += g * (E::one() - hs) * (E::one() - ws);
+                        grad_inp[inp_i(h0, w1)] += g * (E::one() - hs) * ws;
+                        grad_inp[inp_i(h1, w0)] += g * hs * (E::one() - ws);
+                        grad_inp[inp_i(h1, w1)] += g * hs * ws;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The four source pixels of output pixel `o` and their weights, using the same mapping as
+/// [half_pixel_source] without clamping the source, and the cubic convolution kernel with
+/// `a = -0.75`. Source pixels past the edges are clamped to the edge pixels.
+fn bicubic_source(o: usize, inp: usize, out: usize) -> ([usize; 4], [f32; 4]) {
+    const A: f32 = -0.75;
+    // for |x| <= 1, and for 1 < |x| < 2
+    let near = |x: f32| ((A + 2.0) * x - (A + 3.0)) * x * x + 1.0;
+    let far = |x: f32| ((A * x - 5.0 * A) * x + 8.0 * A) * x - 4.0 * A;
+
+    let src = (o as f32 + 0.5) * (inp as f32 / out as f32) - 0.5;
+    let floor = src.floor();
+    let t = src - floor;
+    let idx = |k: isize| (floor as isize + k).clamp(0, inp as isize - 1) as usize;
+    (
+        [idx(-1), idx(0), idx(1), idx(2)],
+        [far(t + 1.0), near(t), near(1.0 - t), far(2.0 - t)],
+    )
+}
+
+impl<E: Float + Unit + std::ops::AddAssign + std::ops::DivAssign> super::Upscale2DKernel<E, Bicubic>
+    for Cpu
+{
+    fn forward<I: Shape, O: Shape>(
+        &self,
+        op: super::Upscale2DOp,
+        inp: &Tensor<I, E, Self>,
+        out: &mut Tensor<O, E, Self>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_4d::<I>(inp.strides);
+        let ostr = make_4d::<O>(out.strides);
+
+        let buf = inp.data.as_ref();
+        let out_buf = Arc::make_mut(&mut out.data);
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                let inp_at = |ih: usize, iw: usize| {
+                    buf[b * istr[0] + c * istr[1] + ih * istr[2] + iw * istr[3]]
+                };
+                for oh in 0..op.h_out {
+                    let (hs, h_weights) = bicubic_source(oh, op.h_in, op.h_out);
+                    for ow in 0..op.w_out {
+                        let (ws, w_weights) = bicubic_source(ow, op.w_in, op.w_out);
+                        let mut v = E::zero();
+                        for (&ih, &hw) in hs.iter().zip(h_weights.iter()) {
+                            for (&iw, &ww) in ws.iter().zip(w_weights.iter()) {
+                                v += inp_at(ih, iw) * E::from(hw * ww).unwrap();
+                            }
+                        }
+                        out_buf[b * ostr[0] + c * ostr[1] + oh * ostr[2] + ow * ostr[3]] = v;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn backward<I: Shape, O: Shape>(
+        &self,
+        op: super::Upscale2DOp,
+        inp: &Tensor<I, E, Self>,
+        grad_inp: &mut Self::Vec<E>,
+        out: &Tensor<O, E, Self>,
+        grad_out: &Self::Vec<E>,
+    ) -> Result<(), Self::Err> {
+        let istr = make_4d::<I>(inp.strides);
+        let ostr = make_4d::<O>(out.strides);
+
+        for b in 0..op.batch {
+            for c in 0..op.chan {
+                let inp_i =
+                    |ih: usize, iw: usize| b * istr[0] + c * istr[1] + ih * istr[2] + iw * istr[3];
+                for oh in 0..op.h_out {
+                    let (hs, h_weights) = bicubic_source(oh, op.h_in, op.h_out);
+                    for ow in 0..op.w_out {
+                        let (ws, w_weights) = bicubic_source(ow, op.w_in, op.w_out);
+                        let g = grad_out[b * ostr[0] + c * ostr[1] + oh * ostr[2] + ow * ostr[3]];
+                        for (&ih, &hw) in hs.iter().zip(h_weights.iter()) {
+                            for (&iw, &ww) in ws.iter().zip(w_weights.iter()) {
+                                grad_inp[inp_i(ih, iw)] += g * E::from(hw * ww).unwrap();
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use std::sync::Arc;
 
 use cudarc::driver::{DeviceRepr, LaunchAsync, LaunchConfig};
 
-use super::{Bilinear, NearestNeighbor};
+use super::{Bicubic, Bilinear, BilinearHalfPixel, NearestNeighbor};
 
 const PTX_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/upscale2d.ptx"));
 
@@ -111,3 +111,15 @@ pool_impl!(
     "bilinear_upscale2d_fwd_f32",
     "bilinear_upscale2d_bwd_f32"
 );
+
+pool_impl!(
+    Upscale2DKernel<f32, BilinearHalfPixel>,
+    "bilinear_half_pixel_upscale2d_fwd_f32",
+    "bilinear_half_pixel_upscale2d_bwd_f32"
+);
+
+pool_impl!(
+    Upscale2DKernel<f32, Bicubic>,
+    "bicubic_upscale2d_fwd_f32",
+    "bicubic_upscale2d_bwd_f32"
+);
@@ -8,6 +8,8 @@ use crate::{
     tensor::{DeviceStorage, HasErr, PutTape, SplitTape, Tape, Tensor, ZerosTensor},
 };
 
+use super::ops::profiled;
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct Upscale2DOp {
@@ -29,6 +31,11 @@ impl Upscale2DOp {
             w_out >= w_in,
             "Output width must be larger than input width"
         );
+        Self::new_resize([b, c, h_in, w_in], [h_out, w_out])
+    }
+
+    /// Like [Upscale2DOp::new], but also allows the output to be smaller than the input.
+    fn new_resize([b, c, h_in, w_in]: [usize; 4], [h_out, w_out]: [usize; 2]) -> Self {
         Self {
             batch: b,
             chan: c,
@@ -47,11 +54,35 @@ pub struct NearestNeighbor;
 
 impl UpscaleMethod for NearestNeighbor {}
 
+/// Bilinear interpolation that aligns the corner pixels of the input and the output,
+/// mapping output pixel `o` to `o * (in - 1) / (out - 1)` in the input.
+///
+/// **Pytorch equivalent**: `F.interpolate(t, mode="bilinear", align_corners=True)`
 #[derive(Clone, Copy, Default)]
 pub struct Bilinear;
 
 impl UpscaleMethod for Bilinear {}
 
+/// Bilinear interpolation that treats pixels as squares and aligns the centers of
+/// input and output pixels, mapping output pixel `o` to `(o + 0.5) * in / out - 0.5`
+/// in the input (clamped to the edge pixels).
+///
+/// **Pytorch equivalent**: `F.interpolate(t, mode="bilinear", align_corners=False)`
+#[derive(Clone, Copy, Default)]
+pub struct BilinearHalfPixel;
+
+impl UpscaleMethod for BilinearHalfPixel {}
+
+/// Bicubic interpolation with the half pixel mapping of [BilinearHalfPixel], using the 4x4
+/// nearest input pixels with the cubic convolution kernel of `a = -0.75`. Source pixels past
+/// the edges are clamped to the edge pixels.
+///
+/// **Pytorch equivalent**: `F.interpolate(t, mode="bicubic", align_corners=False)`
+#[derive(Clone, Copy, Default)]
+pub struct Bicubic;
+
+impl UpscaleMethod for Bicubic {}
+
 pub trait Upscale2DKernel<E: Unit, M: UpscaleMethod>: DeviceStorage {
     fn forward<I: Shape, O: Shape>(
         &self,
@@ -118,7 +149,7 @@ impl<
         let mut out = inp
             .device
             .try_zeros_like(&(chan, Default::default(), Default::default()))?;
-        inp.device.forward(op, &inp, &mut out)?;
+        profiled(|| inp.device.forward(op, &inp, &mut out))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&inp)?;
         tape.try_alloc_grad(&out)?;
@@ -153,7 +184,7 @@ impl<
         let mut out =
             inp.device
                 .try_zeros_like(&(batch, chan, Default::default(), Default::default()))?;
-        inp.device.forward(op, &inp, &mut out)?;
+        profiled(|| inp.device.forward(op, &inp, &mut out))?;
         let phantom_out = out.clone();
         tape.try_alloc_grad(&inp)?;
         tape.try_alloc_grad(&out)?;
@@ -166,11 +197,141 @@ impl<
     }
 }
 
+/// Resizes a `(C, H, W)` image to `(C, OH, OW)` with bilinear interpolation. Unlike
+/// [TryUpscale2D::upscale_2d], the output may be smaller than the input.
+///
+/// Corners of the input are aligned with corners of the output, and the gradient of each
+/// output pixel is distributed to its four source pixels by their interpolation weights.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let t: Tensor<Rank3<1, 4, 4>, f32, _> = dev.zeros();
+/// let r: Tensor<Rank3<1, 3, 3>, f32, _> = t.resize_bilinear::<3, 3>();
+/// ```
+pub fn resize_bilinear<
+    const OH: usize,
+    const OW: usize,
+    C: Dim,
+    const H: usize,
+    const W: usize,
+    E: Dtype,
+    D: Upscale2DKernel<E, Bilinear> + ZerosTensor<E>,
+    T: 'static + Tape<E, D>,
+>(
+    t: Tensor<(C, Const<H>, Const<W>), E, D, T>,
+) -> Tensor<(C, Const<OH>, Const<OW>), E, D, T> {
+    t.resize_bilinear::<OH, OW>()
+}
+
+impl<
+        C: Dim,
+        const H: usize,
+        const W: usize,
+        E: Dtype,
+        D: Upscale2DKernel<E, Bilinear> + ZerosTensor<E>,
+        T: 'static + Tape<E, D>,
+    > Tensor<(C, Const<H>, Const<W>), E, D, T>
+{
+    /// See [resize_bilinear]
+    pub fn resize_bilinear<const OH: usize, const OW: usize>(
+        self,
+    ) -> Tensor<(C, Const<OH>, Const<OW>), E, D, T> {
+        self.try_resize_bilinear().unwrap()
+    }
+
+    /// See [resize_bilinear]
+    #[allow(clippy::type_complexity)]
+    pub fn try_resize_bilinear<const OH: usize, const OW: usize>(
+        self,
+    ) -> Result<Tensor<(C, Const<OH>, Const<OW>), E, D, T>, D::Err> {
+        let &(chan, _, _) = self.shape();
+        let op = Upscale2DOp::new_resize([1, chan.size(), H, W], [OH, OW]);
+        let (inp, mut tape) = self.split_tape();
+        let mut out = inp
+            .device
+            .try_zeros_like(&(chan, Default::default(), Default::default()))?;
+        profiled(|| Upscale2DKernel::<E, Bilinear>::forward(&inp.device, op, &inp, &mut out))?;
+        let phantom_out = out.clone();
+        tape.try_alloc_grad(&inp)?;
+        tape.try_alloc_grad(&out)?;
+        tape.add_backward_op(move |grads| {
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp, &phantom_out);
+            Upscale2DKernel::<E, Bilinear>::backward(
+                &inp.device,
+                op,
+                &inp,
+                grad_inp,
+                &phantom_out,
+                grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
+impl<
+        B: Dim,
+        C: Dim,
+        H: Dim,
+        W: Dim,
+        E: Dtype,
+        D: Upscale2DKernel<E, Bilinear> + ZerosTensor<E>,
+        T: 'static + Tape<E, D>,
+    > Tensor<(B, C, H, W), E, D, T>
+{
+    /// Resizes a batch of `(C, H, W)` images to the runtime size `(h, w)`, like
+    /// [resize_bilinear], e.g. for sizes that change during training.
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank4<2, 1, 4, 4>, f32, _> = dev.zeros();
+    /// let r = t.resize_bilinear_to(3, 2);
+    /// assert_eq!(r.shape(), &(Const::<2>, Const::<1>, 3, 2));
+    /// ```
+    pub fn resize_bilinear_to(self, h: usize, w: usize) -> Tensor<(B, C, usize, usize), E, D, T> {
+        self.try_resize_bilinear_to(h, w).unwrap()
+    }
+
+    /// See [Tensor::resize_bilinear_to]
+    #[allow(clippy::type_complexity)]
+    pub fn try_resize_bilinear_to(
+        self,
+        h: usize,
+        w: usize,
+    ) -> Result<Tensor<(B, C, usize, usize), E, D, T>, D::Err> {
+        let &(batch, chan, h_in, w_in) = self.shape();
+        let op = Upscale2DOp::new_resize(
+            [batch.size(), chan.size(), h_in.size(), w_in.size()],
+            [h, w],
+        );
+        let (inp, mut tape) = self.split_tape();
+        let mut out = inp.device.try_zeros_like(&(batch, chan, h, w))?;
+        profiled(|| Upscale2DKernel::<E, Bilinear>::forward(&inp.device, op, &inp, &mut out))?;
+        let phantom_out = out.clone();
+        tape.try_alloc_grad(&inp)?;
+        tape.try_alloc_grad(&out)?;
+        tape.add_backward_op(move |grads| {
+            let (grad_inp, grad_out) = grads.mut_and_ref(&inp, &phantom_out);
+            Upscale2DKernel::<E, Bilinear>::backward(
+                &inp.device,
+                op,
+                &inp,
+                grad_inp,
+                &phantom_out,
+                grad_out,
+            )
+        });
+        Ok(out.put_tape(tape))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{prelude::*, tests::*};
 
-    use super::{Bilinear, NearestNeighbor, TryUpscale2D};
+    use super::{Bicubic, Bilinear, BilinearHalfPixel, NearestNeighbor, TryUpscale2D};
 
     #[test]
     fn nearest_upscale2d_even() {
@@ -270,4 +431,175 @@ mod tests {
             ]],
         );
     }
+
+    #[test]
+    fn bilinear_conventions_2x2_to_4x4() {
+        let dev = TestDevice::default();
+
+        let x: Tensor<Rank3<1, 2, 2>, TestDtype, _> = dev.tensor([[[1.0, 0.0], [2.0, 3.0]]]);
+
+        // corners of the output are exactly the corners of the input
+        let y = x.clone().upscale_2d::<4, 4, Bilinear>();
+        assert_close(
+            &y.array(),
+            &[[
+                [1.0, 2.0 / 3.0, 1.0 / 3.0, 0.0],
+                [4.0 / 3.0, 11.0 / 9.0, 10.0 / 9.0, 1.0],
+                [5.0 / 3.0, 16.0 / 9.0, 17.0 / 9.0, 2.0],
+                [2.0, 7.0 / 3.0, 8.0 / 3.0, 3.0],
+            ]],
+        );
+
+        // output pixels map to 0.0, 0.25, 0.75 and 1.25 (clamped to 1.0) in the input,
+        // so the edges repeat the input's edges instead of interpolating up to them
+        let y = x.leaky_trace().upscale_2d::<4, 4, BilinearHalfPixel>();
+        assert_close(
+            &y.array(),
+            &[[
+                [1.0, 0.75, 0.25, 0.0],
+                [1.25, 1.125, 0.875, 0.75],
+                [1.75, 1.875, 2.125, 2.25],
+                [2.0, 2.25, 2.75, 3.0],
+            ]],
+        );
+
+        // every input pixel has a total weight of 4
+        let g = y.sum().backward();
+        assert_close(&g.get(&x).array(), &[[[4.0, 4.0], [4.0, 4.0]]]);
+
+        // y[1][2] is 0.25 of the way between rows, and 0.75 of the way between columns
+        let weights = dev.tensor([[
+            [0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0],
+        ]]);
+        let y = x.leaky_trace().upscale_2d::<4, 4, BilinearHalfPixel>();
+        let g = (y * weights).sum().backward();
+        assert_close(&g.get(&x).array(), &[[[0.1875, 0.5625], [0.0625, 0.1875]]]);
+    }
+
+    #[test]
+    fn test_resize_bilinear_down() {
+        let dev = TestDevice::default();
+
+        let x: Tensor<Rank3<1, 4, 4>, TestDtype, _> = dev.tensor([[
+            [0.0, 1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0, 7.0],
+            [8.0, 9.0, 10.0, 11.0],
+            [12.0, 13.0, 14.0, 15.0],
+        ]]);
+        let y = x.leaky_trace().resize_bilinear::<3, 3>();
+        let y_arr = y.array();
+        assert_close(&y_arr[0][1][1], &7.5);
+        assert_close(&y_arr[0][2][1], &13.5);
+        assert_close(
+            &y_arr,
+            &[[[0.0, 1.5, 3.0], [6.0, 7.5, 9.0], [12.0, 13.5, 15.0]]],
+        );
+
+        let g = y.sum().backward();
+        let g_x = g.get(&x).array();
+        let total: TestDtype = g_x[0].iter().flatten().sum();
+        assert_close(&total, &9.0);
+        assert_close(
+            &g_x,
+            &[[
+                [1.0, 0.5, 0.5, 1.0],
+                [0.5, 0.25, 0.25, 0.5],
+                [0.5, 0.25, 0.25, 0.5],
+                [1.0, 0.5, 0.5, 1.0],
+            ]],
+        );
+    }
+
+    #[test]
+    fn test_bicubic_upscale2d_matches_reference() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank4<1, 1, 4, 4>, TestDtype, _> = dev.tensor([[[
+            [0.0, 1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0, 7.0],
+            [8.0, 9.0, 10.0, 11.0],
+            [12.0, 13.0, 14.0, 15.0],
+        ]]]);
+        // the expected values come from a plain python port of pytorch's cpu kernel for
+        // `F.interpolate(x, size=(8, 8), mode="bicubic", align_corners=False)`
+        // (`upsample_bicubic2d` in aten/src/ATen/native), not from pytorch itself
+        let y = x.leaky_trace().upscale_2d::<8, 8, Bicubic>();
+        let y_arr = y.array();
+        assert_close(
+            &y_arr[0][0][0],
+            &[
+                -0.527344, -0.230469, 0.246094, 0.875, 1.28125, 1.910156, 2.386719, 2.683594,
+            ],
+        );
+        assert_close(
+            &y_arr[0][0][3],
+            &[
+                5.082031, 5.378906, 5.855469, 6.484375, 6.890625, 7.519531, 7.996094, 8.292969,
+            ],
+        );
+        assert_close(
+            &y_arr[0][0][7],
+            &[
+                12.316406, 12.613281, 13.089844, 13.71875, 14.125, 14.753906, 15.230469, 15.527344,
+            ],
+        );
+
+        // the edge pixels are clamped, so they get the weights of the pixels past the edges
+        let g = y.sum().backward();
+        let (a, b) = (3.860611, 3.998764);
+        let c = 4.141861;
+        assert_close(
+            &g.get(&x).array(),
+            &[[[[a, b, b, a], [b, c, c, b], [b, c, c, b], [a, b, b, a]]]],
+        );
+    }
+
+    #[test]
+    fn test_bicubic_upscale2d_non_integer_scale() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank3<2, 4, 4>, TestDtype, _> = dev.sample_normal();
+        let y = x.clone().upscale_2d::<9, 9, Bicubic>();
+        let batched = x
+            .clone()
+            .broadcast::<Rank4<3, 2, 4, 4>, _>()
+            .upscale_2d::<9, 9, Bicubic>();
+        for b in batched.array() {
+            assert_close(&b, &y.array());
+        }
+
+        // the weights of each output pixel sum to 1
+        let ones: Tensor<Rank3<2, 4, 4>, TestDtype, _> = dev.ones();
+        let y = ones.leaky_trace().upscale_2d::<9, 9, Bicubic>();
+        assert_close(&y.array(), &[[[1.0; 9]; 9]; 2]);
+        let g = y.sum().backward();
+        let total: TestDtype = g.get(&ones).array().iter().flatten().flatten().sum();
+        assert_close_with_tolerance(&total, &(2.0 * 81.0), 1e-4);
+
+        // from the same python port as test_bicubic_upscale2d_matches_reference
+        let x: Tensor<Rank3<1, 4, 4>, TestDtype, _> = dev.tensor([[
+            [0.5, -1.0, 2.0, 0.0],
+            [1.5, 0.25, -0.5, 3.0],
+            [-2.0, 1.0, 0.75, -1.25],
+            [0.0, 2.5, -1.5, 1.0],
+        ]]);
+        let y = x.upscale_2d::<9, 9, Bicubic>().array();
+        assert_close_with_tolerance(
+            &y[0][0],
+            &[
+                0.557286, 0.112998, -0.933262, -1.037339, 0.668277, 2.2153, 1.666922, 0.105969,
+                -0.608286,
+            ],
+            1e-5,
+        );
+        assert_close_with_tolerance(
+            &y[0][4],
+            &[
+                -0.446475, -0.1994, 0.300884, 0.612903, 0.361084, 0.094219, 0.364085, 0.815538,
+                1.037001,
+            ],
+            1e-5,
+        );
+    }
 }
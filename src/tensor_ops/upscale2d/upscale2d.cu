@@ -207,6 +207,234 @@ __device__ void bilinear_upscale2d_bwd(
     }
 }
 
+// The two source pixels of output pixel `o` and the weight of `i1`, using
+// `src = (o + 0.5) * in / out - 0.5` clamped to the edge pixels.
+__device__ void half_pixel_source(size_t o, size_t in, size_t out, size_t *i0, size_t *i1, float *w1) {
+    float src = ((float)o + 0.5) * ((float)in / out) - 0.5;
+    src = src > 0 ? src : 0;
+    *i0 = (size_t)src < in - 1 ? (size_t)src : in - 1;
+    *i1 = *i0 + 1 < in - 1 ? *i0 + 1 : in - 1;
+    *w1 = src - *i0;
+}
+
+template<typename T>
+__device__ void bilinear_half_pixel_upscale2d_fwd(
+    const Upscale2dOp op,
+    const size_t *inp_strides,
+    const size_t *inp_sizes,
+    const size_t *out_strides,
+    const size_t *out_sizes,
+    const T *inp, // 4d (Batch, Channels, Height, Width)
+    T *out // 4d (Batch, Channels, HeightOut, WidthOut)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    const size_t numel = op.batch * op.chan * op.h_out * op.w_out;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t ow = idx % op.w_out;
+    idx /= op.w_out;
+    const size_t oh = idx % op.h_out;
+    idx /= op.h_out;
+    const size_t c = idx % op.chan;
+    idx /= op.chan;
+    const size_t b = idx % op.batch;
+    idx /= op.batch;
+
+    size_t h0, h1, w0, w1;
+    float hs_f, ws_f;
+    half_pixel_source(oh, op.h_in, op.h_out, &h0, &h1, &hs_f);
+    half_pixel_source(ow, op.w_in, op.w_out, &w0, &w1, &ws_f);
+    T hs = hs_f;
+    T ws = ws_f;
+
+    const T *img = inp + b * inp_strides[0] + c * inp_strides[1];
+    T ll = img[h0 * inp_strides[2] + w0 * inp_strides[3]] * (1-hs) * (1-ws);
+    T lh = img[h0 * inp_strides[2] + w1 * inp_strides[3]] * (1-hs) * ws;
+    T hl = img[h1 * inp_strides[2] + w0 * inp_strides[3]] * hs * (1-ws);
+    T hh = img[h1 * inp_strides[2] + w1 * inp_strides[3]] * hs * ws;
+
+    out[i] = ll + lh + hl + hh;
+}
+
+template<typename T>
+__device__ void bilinear_half_pixel_upscale2d_bwd(
+    const Upscale2dOp op,
+    const size_t *inp_strides,
+    const size_t *inp_sizes,
+    const size_t *out_strides,
+    const size_t *out_sizes,
+    const T *inp, // 4d (Batch, Channels, Height, Width)
+    T *grad_inp,
+    const T *out, // 4d (Batch, Channels, HeightOut, WidthOut)
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    const size_t numel = op.batch * op.chan * op.h_in * op.w_in;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t x = idx % op.w_in;
+    idx /= op.w_in;
+    const size_t y = idx % op.h_in;
+    idx /= op.h_in;
+    const size_t c = idx % op.chan;
+    idx /= op.chan;
+    const size_t b = idx % op.batch;
+    idx /= op.batch;
+
+    // Probably isn't efficient, but it works
+    for (size_t oh = 0; oh < op.h_out; oh++) {
+        size_t h0, h1;
+        float hs;
+        half_pixel_source(oh, op.h_in, op.h_out, &h0, &h1, &hs);
+        T wh = (h0 == y ? 1 - hs : 0) + (h1 == y ? hs : 0);
+        if (wh == 0) {
+            continue;
+        }
+        for (size_t ow = 0; ow < op.w_out; ow++) {
+            size_t w0, w1;
+            float ws;
+            half_pixel_source(ow, op.w_in, op.w_out, &w0, &w1, &ws);
+            T ww = (w0 == x ? 1 - ws : 0) + (w1 == x ? ws : 0);
+            if (ww == 0) {
+                continue;
+            }
+            size_t out_i = b * out_strides[0] + c * out_strides[1] + oh * out_strides[2] + ow * out_strides[3];
+            grad_inp[i] += grad_out[out_i] * wh * ww;
+        }
+    }
+}
+
+// The four source pixels of output pixel `o` and their weights, using
+// `src = (o + 0.5) * in / out - 0.5` without clamping `src`, and the cubic
+// convolution kernel with `a = -0.75`. Source pixels past the edges are clamped
+// to the edge pixels.
+__device__ void bicubic_source(size_t o, size_t in, size_t out, size_t *idx, float *w) {
+    const float A = -0.75;
+    float src = ((float)o + 0.5) * ((float)in / out) - 0.5;
+    float fl = floorf(src);
+    float t = src - fl;
+    for (int k = 0; k < 4; k++) {
+        long j = (long)fl + k - 1;
+        j = j > 0 ? j : 0;
+        idx[k] = j < (long)in - 1 ? j : in - 1;
+    }
+    // for |x| <= 1, and for 1 < |x| < 2
+    float x0 = t + 1;
+    float x1 = t;
+    float x2 = 1 - t;
+    float x3 = 2 - t;
+    w[0] = ((A * x0 - 5 * A) * x0 + 8 * A) * x0 - 4 * A;
+    w[1] = ((A + 2) * x1 - (A + 3)) * x1 * x1 + 1;
+    w[2] = ((A + 2) * x2 - (A + 3)) * x2 * x2 + 1;
+    w[3] = ((A * x3 - 5 * A) * x3 + 8 * A) * x3 - 4 * A;
+}
+
+template<typename T>
+__device__ void bicubic_upscale2d_fwd(
+    const Upscale2dOp op,
+    const size_t *inp_strides,
+    const size_t *inp_sizes,
+    const size_t *out_strides,
+    const size_t *out_sizes,
+    const T *inp, // 4d (Batch, Channels, Height, Width)
+    T *out // 4d (Batch, Channels, HeightOut, WidthOut)
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    const size_t numel = op.batch * op.chan * op.h_out * op.w_out;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t ow = idx % op.w_out;
+    idx /= op.w_out;
+    const size_t oh = idx % op.h_out;
+    idx /= op.h_out;
+    const size_t c = idx % op.chan;
+    idx /= op.chan;
+    const size_t b = idx % op.batch;
+    idx /= op.batch;
+
+    size_t hs[4], ws[4];
+    float h_weights[4], w_weights[4];
+    bicubic_source(oh, op.h_in, op.h_out, hs, h_weights);
+    bicubic_source(ow, op.w_in, op.w_out, ws, w_weights);
+
+    const T *img = inp + b * inp_strides[0] + c * inp_strides[1];
+    T v = 0;
+    for (int y = 0; y < 4; y++) {
+        for (int x = 0; x < 4; x++) {
+            T w = h_weights[y] * w_weights[x];
+            v += img[hs[y] * inp_strides[2] + ws[x] * inp_strides[3]] * w;
+        }
+    }
+    out[i] = v;
+}
+
+template<typename T>
+__device__ void bicubic_upscale2d_bwd(
+    const Upscale2dOp op,
+    const size_t *inp_strides,
+    const size_t *inp_sizes,
+    const size_t *out_strides,
+    const size_t *out_sizes,
+    const T *inp, // 4d (Batch, Channels, Height, Width)
+    T *grad_inp,
+    const T *out, // 4d (Batch, Channels, HeightOut, WidthOut)
+    const T *grad_out
+) {
+    unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;
+    const size_t numel = op.batch * op.chan * op.h_in * op.w_in;
+    if (i >= numel) {
+        return;
+    }
+
+    unsigned int idx = i;
+    const size_t x = idx % op.w_in;
+    idx /= op.w_in;
+    const size_t y = idx % op.h_in;
+    idx /= op.h_in;
+    const size_t c = idx % op.chan;
+    idx /= op.chan;
+    const size_t b = idx % op.batch;
+    idx /= op.batch;
+
+    // Probably isn't efficient, but it works. Because of the clamping at the edges,
+    // an input pixel can be more than one of the four source pixels of an output pixel.
+    for (size_t oh = 0; oh < op.h_out; oh++) {
+        size_t hs[4];
+        float h_weights[4];
+        bicubic_source(oh, op.h_in, op.h_out, hs, h_weights);
+        T wh = 0;
+        for (int k = 0; k < 4; k++) {
+            wh += hs[k] == y ? h_weights[k] : 0;
+        }
+        if (wh == 0) {
+            continue;
+        }
+        for (size_t ow = 0; ow < op.w_out; ow++) {
+            size_t ws[4];
+            float w_weights[4];
+            bicubic_source(ow, op.w_in, op.w_out, ws, w_weights);
+            T ww = 0;
+            for (int k = 0; k < 4; k++) {
+                ww += ws[k] == x ? w_weights[k] : 0;
+            }
+            if (ww == 0) {
+                continue;
+            }
+            size_t out_i = b * out_strides[0] + c * out_strides[1] + oh * out_strides[2] + ow * out_strides[3];
+            grad_inp[i] += grad_out[out_i] * wh * ww;
+        }
+    }
+}
+
 #define UPSCALE_OP(TYPENAME, fwd, bwd, fwd_FN, bwd_FN) \
 extern "C" __global__ void fwd( \
     const Upscale2dOp op, \
@@ -252,4 +480,24 @@ UPSCALE_OP(
     double,
     bilinear_upscale2d_fwd_f64, bilinear_upscale2d_bwd_f64,
     bilinear_upscale2d_fwd, bilinear_upscale2d_bwd
-);
\ No newline at end of file
+);
+UPSCALE_OP(
+    float,
+    bilinear_half_pixel_upscale2d_fwd_f32, bilinear_half_pixel_upscale2d_bwd_f32,
+    bilinear_half_pixel_upscale2d_fwd, bilinear_half_pixel_upscale2d_bwd
+);
+UPSCALE_OP(
+    double,
+    bilinear_half_pixel_upscale2d_fwd_f64, bilinear_half_pixel_upscale2d_bwd_f64,
+    bilinear_half_pixel_upscale2d_fwd, bilinear_half_pixel_upscale2d_bwd
+);
+UPSCALE_OP(
+    float,
+    bicubic_upscale2d_fwd_f32, bicubic_upscale2d_bwd_f32,
+    bicubic_upscale2d_fwd, bicubic_upscale2d_bwd
+);
+UPSCALE_OP(
+    double,
+    bicubic_upscale2d_fwd_f64, bicubic_upscale2d_bwd_f64,
+    bicubic_upscale2d_fwd, bicubic_upscale2d_bwd
+);
@@ -22,3 +22,24 @@ impl<E: Dtype, D: OneFillStorage<E>> Backward<E, D> for Tensor<Rank0, E, D, Owne
         Ok(grads)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_backward_very_deep_graph() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<Rank0, TestDtype, _> = dev.tensor(1.0);
+        let mut y = x.leaky_trace();
+        let mut expected = 1.0;
+        for _ in 0..10_000 {
+            y = y * 1.0001;
+            expected *= 1.0001;
+        }
+        assert_close(&y.array(), &expected);
+        let g = y.backward();
+        assert_close(&g.get(&x).array(), &expected);
+    }
+}
@@ -1,4 +1,4 @@
-use super::ops::{BinaryKernel, UnaryKernel};
+use super::ops::{BinaryKernel, UnaryInPlaceKernel, UnaryKernel};
 use crate::{
     shapes::{Dtype, Shape},
     tensor::{
@@ -56,6 +56,19 @@ impl<E: Dtype, Op: UnaryDerivative<E>> UnaryKernel<Op, E> for Cpu {
     }
 }
 
+impl<E: Dtype, Op: UnaryDerivative<E>> UnaryInPlaceKernel<Op, E> for Cpu {
+    fn forward_inplace<S: Shape>(
+        &self,
+        op: Op,
+        t: &mut Tensor<S, E, Self>,
+    ) -> Result<(), Self::Err> {
+        for x in t.buf_iter_mut() {
+            *x = op.f(x);
+        }
+        Ok(())
+    }
+}
+
 impl<E: Dtype, Op: BinaryDerivative<E>> BinaryKernel<Op, E> for Cpu {
     fn forward<S: Shape>(
         &self,
@@ -1,9 +1,9 @@
 use crate::{
     shapes::{Dtype, Shape},
     tensor::{launch_cfg, unique_id, Cuda, Tensor},
-    tensor_ops::ops::{BinaryKernel, UnaryKernel},
+    tensor_ops::ops::{BinaryKernel, UnaryInPlaceKernel, UnaryKernel},
 };
-use cudarc::driver::{DeviceRepr, DeviceSlice, LaunchAsync};
+use cudarc::driver::{DevicePtr, DeviceRepr, DeviceSlice, LaunchAsync};
 use std::{sync::Arc, vec::Vec};
 
 pub trait UnaryOpCudaKernel<E> {
@@ -80,6 +80,31 @@ impl<E: Dtype, K: UnaryOpCudaKernel<E> + DeviceRepr> UnaryKernel<K, E> for Cuda
     }
 }
 
+impl<E: Dtype, K: UnaryOpCudaKernel<E> + DeviceRepr> UnaryInPlaceKernel<K, E> for Cuda {
+    fn forward_inplace<S: Shape>(
+        &self,
+        op: K,
+        t: &mut Tensor<S, E, Self>,
+    ) -> Result<(), Self::Err> {
+        if !self.dev.has_func(K::MODULE_NAME, K::FWD_FN_NAME) {
+            self.dev
+                .load_ptx(K::PTX_SRC.into(), K::MODULE_NAME, &K::ALL_FN_NAMES)?;
+        }
+
+        let storage = Arc::make_mut(&mut t.data);
+        let numel = storage.len();
+        // the forward kernels read each element before writing it, so `inp` and
+        // `out` can be the same buffer. cudarc can't borrow it as both though, so
+        // it's passed by its raw pointer.
+        let ptr = *storage.device_ptr();
+        let fwd_fn = self.dev.get_func(K::MODULE_NAME, K::FWD_FN_NAME).unwrap();
+        let cfg = launch_cfg(numel as u32);
+        let params = (op, numel, ptr, ptr);
+        unsafe { fwd_fn.launch(cfg, params) }?;
+        Ok(())
+    }
+}
+
 pub trait BinaryOpCudaKernel<E> {
     /// Compiled by build.rs
     const PTX_SRC: &'static str;
@@ -45,6 +45,7 @@ pub trait Device<E: Dtype>:
     + super::super::matmul::MatMatKernel<E>
     + super::super::matmul::VecVecKernel<E>
     + super::super::matmul::MatMatBrKernel<E>
+    + super::super::matmul::MatBrMatKernel<E>
     + super::super::matmul::MatMatBatch3Kernel<E>
     + super::super::matmul::MatMatBatch4Kernel<E>
 
@@ -62,14 +63,19 @@ pub trait Device<E: Dtype>:
 
     // boolean operations
     + super::super::boolean::BooleanKernel
+    + super::super::cmp::ScalarCmpKernel<super::super::cmp::GtKernelOp, E>
 
     // unary
     + UnaryKernel<super::super::abs::AbsKernelOp, E>
     + UnaryKernel<super::super::clamp::ClampKernelOp<E>, E>
     + UnaryKernel<super::super::cos::CosKernelOp, E>
     + super::super::dropout::DropoutKernel<E>
+    + UnaryKernel<super::super::erf::ErfKernelOp, E>
+    + UnaryKernel<super::super::erf::ErfcKernelOp, E>
     + UnaryKernel<super::super::exp::ExpKernelOp, E>
+    + UnaryKernel<super::super::expm1::Expm1KernelOp, E>
     + UnaryKernel<super::super::ln::LnKernelOp, E>
+    + UnaryKernel<super::super::log1p::Log1pKernelOp, E>
     + UnaryKernel<super::super::nans_to::NansToKernelOp<E>, E>
     + UnaryKernel<super::super::negate::NegateKernelOp, E>
     + UnaryKernel<super::super::relu::ReLUKernelOp, E>
@@ -79,6 +85,7 @@ pub trait Device<E: Dtype>:
     + UnaryKernel<super::super::sqrt::SqrtKernelOp, E>
     + UnaryKernel<super::super::square::SquareKernelOp, E>
     + UnaryKernel<super::super::tanh::TanhKernelOp, E>
+    + UnaryKernel<super::super::threshold::ThresholdKernelOp<E>, E>
     + UnaryKernel<super::super::pow::PowfKernelOp<E>, E>
     + UnaryKernel<super::super::pow::PowiKernelOp, E>
 
@@ -93,7 +100,11 @@ pub trait Device<E: Dtype>:
     + BinaryKernel<super::super::huber_error::HuberErrorKernelOp<E>, E>
     + BinaryKernel<super::super::maximum::MaximumKernelOp, E>
     + BinaryKernel<super::super::minimum::MinimumKernelOp, E>
+    + BinaryKernel<super::super::add_relu::AddReLUKernelOp, E>
     + crate::tensor_ops::axpy::AxpyKernel<E>
+
+    // losses
+    + super::super::softmax_cross_entropy::SoftmaxCrossEntropyKernel<E>
 {
 }
 
@@ -1,3 +1,4 @@
+use super::super::{axpy::AxpyKernel, Device, ReshapeTo};
 use crate::{
     shapes::{Dtype, HasShape, Shape},
     tensor::{DeviceStorage, Merge, PutTape, SplitTape, Tape, Tensor},
@@ -18,6 +19,16 @@ pub trait UnaryKernel<Op, E: Dtype>: DeviceStorage {
     ) -> Result<(), Self::Err>;
 }
 
+/// Applies the forward of a unary op directly to the buffer of `t`. Only copies the
+/// buffer if it is shared with other tensors.
+pub trait UnaryInPlaceKernel<Op, E: Dtype>: DeviceStorage {
+    fn forward_inplace<S: Shape>(
+        &self,
+        op: Op,
+        t: &mut Tensor<S, E, Self>,
+    ) -> Result<(), Self::Err>;
+}
+
 pub trait BinaryKernel<Op, E: Dtype>: DeviceStorage {
     fn forward<S: Shape>(
         &self,
@@ -37,6 +48,19 @@ pub trait BinaryKernel<Op, E: Dtype>: DeviceStorage {
     ) -> Result<(), Self::Err>;
 }
 
+/// Runs the forward of an op, recording its duration if the "profile" feature is enabled.
+/// See [crate::profile].
+#[cfg(feature = "profile")]
+pub(crate) fn profiled<R, F: FnOnce() -> R>(f: F) -> R {
+    crate::profile::record(core::any::type_name::<F>(), false, f)
+}
+
+#[cfg(not(feature = "profile"))]
+#[inline(always)]
+pub(crate) fn profiled<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
 pub(crate) fn try_unary_op<
     Op: 'static + Clone,
     S: Shape,
@@ -48,7 +72,7 @@ pub(crate) fn try_unary_op<
     inp: Tensor<S, E, D, T>,
 ) -> Result<Tensor<S, E, D, T>, D::Err> {
     let (inp, mut tape) = inp.split_tape();
-    let out = inp.device.forward(op.clone(), &inp)?;
+    let out = profiled(|| inp.device.forward(op.clone(), &inp))?;
     let phantom_out = out.clone();
     tape.try_alloc_grad(&inp)?;
     tape.try_alloc_grad(&out)?;
@@ -76,7 +100,7 @@ pub(crate) fn try_binary_op<
     let (lhs, ltape) = lhs.split_tape();
     let (rhs, rtape) = rhs.split_tape();
     let mut tape = ltape.merge(rtape);
-    let out = lhs.device.forward(op, &lhs, &rhs)?;
+    let out = profiled(|| lhs.device.forward(op, &lhs, &rhs))?;
     let phantom_out = out.clone();
     tape.try_alloc_grad(&lhs)?;
     tape.try_alloc_grad(&rhs)?;
@@ -89,3 +113,91 @@ pub(crate) fn try_binary_op<
     });
     Ok(out.put_tape(tape))
 }
+
+/// Runs `f` on a host copy of `inp` to create a tensor of shape `dst`, and records
+/// a backward op that calls `df(inp, out, grad_out)` to get the gradient of `inp`.
+///
+/// All slices are in row major order.
+pub(crate) fn try_host_unary_op<S: Shape, Dst: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>>(
+    inp: Tensor<S, E, D, T>,
+    dst: Dst,
+    f: impl FnOnce(&[E]) -> std::vec::Vec<E>,
+    df: impl 'static + FnOnce(&[E], &[E], &[E]) -> std::vec::Vec<E>,
+) -> Result<Tensor<Dst, E, D, T>, D::Err> {
+    // gradients are copied back in row major order, so `inp` must be contiguous
+    let shape = inp.shape;
+    let inp = inp.try_reshape_like(&shape).unwrap()?;
+    let (inp, mut tape) = inp.split_tape();
+    let inp_vec = inp.as_vec();
+    let out = inp
+        .device
+        .try_tensor_from_vec(profiled(|| f(&inp_vec)), dst)?;
+    let phantom_out = out.clone();
+    tape.try_alloc_grad(&inp)?;
+    tape.try_alloc_grad(&out)?;
+    tape.add_backward_op(move |grads| {
+        let grad_out = grads.get(&phantom_out).as_vec();
+        let grad_inp = df(&inp_vec, &phantom_out.as_vec(), &grad_out);
+        let grad_inp = inp.device.try_tensor_from_vec(grad_inp, inp.shape)?;
+        AxpyKernel::forward(
+            &inp.device,
+            grads.get_mut(&inp),
+            E::ONE,
+            grad_inp.data.as_ref(),
+            E::ONE,
+        )
+    });
+    Ok(out.put_tape(tape))
+}
+
+/// Same as [try_host_unary_op], but with two inputs. `df(lhs, rhs, out, grad_out)`
+/// returns the gradients of `lhs` and `rhs`.
+pub(crate) fn try_host_binary_op<
+    L: Shape,
+    R: Shape,
+    Dst: Shape,
+    E: Dtype,
+    D: Device<E>,
+    LhsTape: Tape<E, D> + Merge<RhsTape>,
+    RhsTape: Tape<E, D>,
+>(
+    lhs: Tensor<L, E, D, LhsTape>,
+    rhs: Tensor<R, E, D, RhsTape>,
+    dst: Dst,
+    f: impl FnOnce(&[E], &[E]) -> std::vec::Vec<E>,
+    df: impl 'static + FnOnce(&[E], &[E], &[E], &[E]) -> (std::vec::Vec<E>, std::vec::Vec<E>),
+) -> Result<Tensor<Dst, E, D, LhsTape>, D::Err> {
+    let (l_shape, r_shape) = (lhs.shape, rhs.shape);
+    let (lhs, ltape) = lhs.try_reshape_like(&l_shape).unwrap()?.split_tape();
+    let (rhs, rtape) = rhs.try_reshape_like(&r_shape).unwrap()?.split_tape();
+    let mut tape = ltape.merge(rtape);
+    let (lhs_vec, rhs_vec) = (lhs.as_vec(), rhs.as_vec());
+    let out = lhs
+        .device
+        .try_tensor_from_vec(profiled(|| f(&lhs_vec, &rhs_vec)), dst)?;
+    let phantom_out = out.clone();
+    tape.try_alloc_grad(&lhs)?;
+    tape.try_alloc_grad(&rhs)?;
+    tape.try_alloc_grad(&out)?;
+    tape.add_backward_op(move |grads| {
+        let grad_out = grads.get(&phantom_out).as_vec();
+        let (grad_lhs, grad_rhs) = df(&lhs_vec, &rhs_vec, &phantom_out.as_vec(), &grad_out);
+        let grad_lhs = lhs.device.try_tensor_from_vec(grad_lhs, lhs.shape)?;
+        let grad_rhs = rhs.device.try_tensor_from_vec(grad_rhs, rhs.shape)?;
+        AxpyKernel::forward(
+            &lhs.device,
+            grads.get_mut(&lhs),
+            E::ONE,
+            grad_lhs.data.as_ref(),
+            E::ONE,
+        )?;
+        AxpyKernel::forward(
+            &rhs.device,
+            grads.get_mut(&rhs),
+            E::ONE,
+            grad_rhs.data.as_ref(),
+            E::ONE,
+        )
+    });
+    Ok(out.put_tape(tape))
+}
@@ -1,5 +1,5 @@
-use crate::shapes::{Axes, Shape};
-use crate::tensor::cpu::NdIndex;
+use crate::shapes::{Axes, Dtype, Shape};
+use crate::tensor::{cpu::NdIndex, Cpu, Tensor};
 #[cfg(feature = "cuda")]
 use std::vec::Vec;
 
@@ -119,6 +119,82 @@ pub(crate) fn reduction_elems_per_thread<Ax: IntoIterator<Item = isize>, S: Shap
         .product()
 }
 
+/// Returns the size of the last dimension if `Ax` is just the last axis of `inp`, and
+/// `inp` is laid out contiguously. In that case every reduced row is a contiguous
+/// slice of the buffer, so the Cpu kernels can skip [index_for_reductions].
+pub(crate) fn contiguous_last_dim<S: Shape, Ax: Axes>(
+    inp: &Tensor<S, impl Dtype, Cpu>,
+) -> Option<usize> {
+    let last_axis = S::NUM_DIMS.checked_sub(1)? as isize;
+    let only_last = Ax::as_array().into_iter().eq([last_axis]);
+    (only_last && inp.strides == inp.shape.strides() && inp.data.len() == inp.shape.num_elements())
+        .then(|| inp.shape.concrete()[S::NUM_DIMS - 1])
+}
+
+/// The number of independent accumulators used by [reduce_row].
+const LANES: usize = 8;
+
+/// Reduces `row` with `f`, starting from `init`.
+///
+/// Elements are folded into [LANES] separate accumulators that are only combined at the
+/// end, so the inner loop has no dependency between iterations and can be vectorized.
+/// `f` must be associative and commutative (up to rounding), with `init` as its identity.
+#[inline(always)]
+pub(crate) fn reduce_row<E: Dtype, F: Fn(E, E) -> E>(row: &[E], init: E, f: F) -> E {
+    let mut acc = [init; LANES];
+    let mut chunks = row.chunks_exact(LANES);
+    for chunk in &mut chunks {
+        for i in 0..LANES {
+            acc[i] = f(acc[i], chunk[i]);
+        }
+    }
+    let mut tmp = init;
+    for a in acc {
+        tmp = f(tmp, a);
+    }
+    for &x in chunks.remainder() {
+        tmp = f(tmp, x);
+    }
+    tmp
+}
+
+/// Minimum number of elements before rows are split across threads.
+#[cfg(feature = "cpu-par-reduce")]
+const PAR_THRESHOLD: usize = 1 << 16;
+
+/// Runs `f(src, dst, n)` over all rows, where a row is `per_row.0` elements of `src`
+/// and `per_row.1` elements of `dst`.
+///
+/// With the `cpu-par-reduce` feature, large inputs are split into blocks of whole
+/// rows that run on separate threads. Each row is still handled by a single call, so
+/// the results are identical to the sequential version.
+pub(crate) fn for_each_row<E: Dtype, F: Fn(&[E], &mut [E], usize) + Sync>(
+    src: &[E],
+    dst: &mut [E],
+    per_row: (usize, usize),
+    f: F,
+) {
+    let n = per_row.0.max(per_row.1);
+    #[cfg(feature = "cpu-par-reduce")]
+    {
+        let num_rows = src.len() / per_row.0;
+        let num_threads = std::thread::available_parallelism().map_or(1, |t| t.get());
+        if num_rows * n >= PAR_THRESHOLD && num_threads > 1 && num_rows > 1 {
+            let rows_per_block = num_rows.div_ceil(num_threads);
+            let f = &f;
+            std::thread::scope(|s| {
+                let src_blocks = src.chunks(rows_per_block * per_row.0);
+                let dst_blocks = dst.chunks_mut(rows_per_block * per_row.1);
+                for (src, dst) in src_blocks.zip(dst_blocks) {
+                    s.spawn(move || f(src, dst, n));
+                }
+            });
+            return;
+        }
+    }
+    f(src, dst, n)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,6 +40,38 @@ impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> VarTo for Tensor<S, E, D,
     }
 }
 
+impl<S: Shape, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<S, E, D, T> {
+    /// Biased variance over the leading (batch) axis only, keeping the rest of the shape.
+    /// Shorthand for `self.var::<_, Axis<0>>()`.
+    ///
+    /// **Pytorch equivalent**: `t.var(0, unbiased=False)`
+    ///
+    /// ```rust
+    /// # use dfdx::prelude::*;
+    /// # let dev: Cpu = Default::default();
+    /// let t: Tensor<Rank2<2, 3>, f32, _> = dev.tensor([[1.0, 2.0, 3.0], [3.0, 2.0, 7.0]]);
+    /// let r: Tensor<Rank1<3>, f32, _> = t.batch_var();
+    /// assert_eq!(r.array(), [1.0, 0.0, 4.0]);
+    /// ```
+    pub fn batch_var(self) -> Tensor<<S as ReduceShape<Axis<0>>>::Reduced, E, D, T>
+    where
+        S: ReduceShape<Axis<0>>,
+    {
+        self.try_batch_var().unwrap()
+    }
+
+    /// Fallible version of [Tensor::batch_var]
+    #[allow(clippy::type_complexity)]
+    pub fn try_batch_var(
+        self,
+    ) -> Result<Tensor<<S as ReduceShape<Axis<0>>>::Reduced, E, D, T>, D::Err>
+    where
+        S: ReduceShape<Axis<0>>,
+    {
+        self.try_var::<<S as ReduceShape<Axis<0>>>::Reduced, Axis<0>>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,4 +105,31 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_batch_mean_var_4x3() {
+        let dev: TestDevice = Default::default();
+        let t: Tensor<Rank2<4, 3>, TestDtype, _> = dev.tensor([
+            [1.0, 0.0, -2.0],
+            [2.0, 0.0, 2.0],
+            [3.0, 4.0, -2.0],
+            [4.0, 4.0, 2.0],
+        ]);
+        assert_eq!(t.clone().batch_mean().array(), [2.5, 2.0, 0.0]);
+
+        let r = t.leaky_trace().batch_var();
+        assert_eq!(r.array(), [1.25, 4.0, 4.0]);
+
+        // d var / dx_i = 2 * (x_i - mean) / B, separately for every column
+        let g = r.sum().backward();
+        assert_eq!(
+            g.get(&t).array(),
+            [
+                [-0.75, -1.0, -1.0],
+                [-0.25, -1.0, 1.0],
+                [0.25, 1.0, -1.0],
+                [0.75, 1.0, 1.0],
+            ]
+        );
+    }
 }
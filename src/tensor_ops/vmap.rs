@@ -0,0 +1,117 @@
+#![allow(clippy::type_complexity)]
+
+use crate::{
+    shapes::{Dim, Dtype},
+    tensor::{PutTape, SplitTape, Tape, Tensor, TensorFrom},
+};
+
+use super::{Device, ReshapeTo, SelectTo, TryStack};
+
+/// Applies `f` to each row of `batch`, and stacks the results into a new batch.
+/// The tape is threaded through every call to `f`, so gradients flow back to `batch`
+/// (and to anything `f` captures) as if `f` had been written to work on the whole batch.
+///
+/// If the batch is empty, `f` is called once on a row of zeros to find the size of
+/// the output rows, and its result is discarded.
+///
+/// ```rust
+/// # use dfdx::prelude::*;
+/// # let dev: Cpu = Default::default();
+/// let batch: Tensor<Rank2<4, 3>, f32, _> = dev.ones();
+/// let r: Tensor<Rank2<4, 2>, f32, _> = vmap(|x: Tensor<Rank1<3>, f32, _>| x.sum::<Rank0, _>().broadcast(), batch);
+/// assert_eq!(r.array(), [[3.0; 2]; 4]);
+/// ```
+pub fn vmap<B: Dim, I: Dim, O: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>, F>(
+    f: F,
+    batch: Tensor<(B, I), E, D, T>,
+) -> Tensor<(B, O), E, D, T>
+where
+    F: FnMut(Tensor<(I,), E, D, T>) -> Tensor<(O,), E, D, T>,
+{
+    batch.vmap(f)
+}
+
+impl<B: Dim, I: Dim, E: Dtype, D: Device<E>, T: Tape<E, D>> Tensor<(B, I), E, D, T> {
+    /// See [vmap]
+    pub fn vmap<O: Dim, F>(self, f: F) -> Tensor<(B, O), E, D, T>
+    where
+        F: FnMut(Tensor<(I,), E, D, T>) -> Tensor<(O,), E, D, T>,
+    {
+        self.try_vmap(f).unwrap()
+    }
+
+    /// See [vmap]
+    pub fn try_vmap<O: Dim, F>(self, mut f: F) -> Result<Tensor<(B, O), E, D, T>, D::Err>
+    where
+        F: FnMut(Tensor<(I,), E, D, T>) -> Tensor<(O,), E, D, T>,
+    {
+        let batch_size = self.shape.0;
+        let (batch, mut tape) = self.split_tape();
+        let mut rows = std::vec::Vec::with_capacity(batch_size.size());
+        for i in 0..batch_size.size() {
+            let idx = batch.device.try_tensor(i)?;
+            let row = batch.clone().put_tape(tape).try_select(idx)?;
+            let (row, row_tape) = f(row).split_tape();
+            tape = row_tape;
+            rows.push(row.put_tape(T::default()));
+        }
+        if rows.is_empty() {
+            // without any rows, `f` has to be run to find the size of its output
+            let row = batch.device.try_zeros_like(&(batch.shape.1,))?;
+            let out_dim = f(row.put_tape(T::default())).shape.0;
+            let out = batch.device.try_zeros_like(&(batch_size, out_dim))?;
+            return Ok(out.put_tape(tape));
+        }
+        let out_dim = rows[0].shape.0;
+        let (out, stack_tape) = rows.try_stack()?.split_tape();
+        let out = out.put_tape(tape.merge(stack_tape));
+        out.try_reshape_like(&(batch_size, out_dim)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::*, tensor::*, tensor_ops::*, tests::*};
+
+    #[test]
+    fn test_vmap_matches_batched() {
+        let dev: TestDevice = Default::default();
+        let w: Tensor<Rank2<3, 2>, TestDtype, _> = dev.sample_normal();
+        let x: Tensor<Rank2<4, 3>, TestDtype, _> = dev.sample_normal();
+
+        let y1 = x.leaky_trace().vmap(|row: Tensor<Rank1<3>, _, _, _>| {
+            row.matmul(w.retaped::<OwnedTape<_, _>>()).tanh()
+        });
+
+        let y2 = x.leaky_trace().matmul(w.leaky_trace()).tanh();
+        assert_close(&y1.array(), &y2.array());
+        let g1 = y1.exp().mean().backward();
+        let g2 = y2.exp().mean().backward();
+
+        assert_close(&g1.get(&x).array(), &g2.get(&x).array());
+        assert_close(&g1.get(&w).array(), &g2.get(&w).array());
+    }
+
+    #[test]
+    fn test_vmap_dynamic_batch() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<(usize, Const<2>), TestDtype, _> =
+            dev.tensor_from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], (3, Const));
+        let y = vmap(|r| r.square() * 2.0, x.leaky_trace());
+        assert_eq!(y.shape().0, 3);
+        assert_eq!(y.as_vec(), [2.0, 8.0, 18.0, 32.0, 50.0, 72.0]);
+        let g = y.sum().backward();
+        assert_eq!(g.get(&x).as_vec(), [4.0, 8.0, 12.0, 16.0, 20.0, 24.0]);
+    }
+
+    #[test]
+    fn test_vmap_empty_batch() {
+        let dev: TestDevice = Default::default();
+        let x: Tensor<(usize, Const<2>), TestDtype, _> = dev.zeros_like(&(0, Const));
+        let y = x
+            .leaky_trace()
+            .vmap(|r| r.broadcast::<Rank2<2, 3>, _>().reshape::<Rank1<6>>());
+        assert_eq!(y.shape(), &(0, Const::<6>));
+    }
+}